@@ -0,0 +1,189 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+
+//! Measures what rules cost on top of parsing the module and the analyses
+//! shared by all of them, run with `cargo bench`. Fails if a rule costs
+//! about as much as parsing or analyzing the module again.
+
+use deno_lint::linter::{Context, LinterBuilder};
+use deno_lint::rules::{get_recommended_rules, LintRule};
+use deno_lint::scopes::{analyze, ScopeManager};
+use deno_lint::swc_ecmascript::ast::Module;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 10;
+const RULE_COUNT: usize = 20;
+
+/// Generates a module of about 5000 lines with a mix of imports, classes,
+/// functions, loops and conditionals.
+fn synthetic_module() -> String {
+  let mut source = String::new();
+  for i in 0..20 {
+    source.push_str(&format!(
+      "import {{ a{i}, b{i} }} from \"./mod{i}.ts\";\n",
+      i = i
+    ));
+  }
+  for i in 0..200 {
+    source.push_str(&format!(
+      r#"
+export class Service{i} {{
+  private cache = new Map<string, number>();
+
+  constructor(private readonly name: string) {{}}
+
+  get size(): number {{
+    return this.cache.size;
+  }}
+
+  lookup(key: string): number | undefined {{
+    if (this.cache.has(key)) {{
+      return this.cache.get(key);
+    }}
+    const value = key.length * {i};
+    this.cache.set(key, value);
+    return value;
+  }}
+}}
+
+export function process{i}(items: string[]): number {{
+  let total = 0;
+  for (const item of items) {{
+    switch (item) {{
+      case "a":
+        total += a{j};
+        break;
+      default:
+        total += item.length;
+    }}
+  }}
+  return total > 0 ? total : b{j};
+}}
+"#,
+      i = i,
+      j = i % 20
+    ));
+  }
+  source
+}
+
+/// Returns the fastest of `RUNS` lints of `source` with the rules returned
+/// by `rules`, which excludes the noise of the slower runs.
+fn time_lint(
+  source: &str,
+  rules: impl Fn() -> Vec<Box<dyn LintRule>>,
+) -> Duration {
+  (0..RUNS)
+    .map(|_| {
+      let mut linter = LinterBuilder::default().rules(rules()).build();
+      let start = Instant::now();
+      linter
+        .lint("bench.ts".to_string(), source.to_string())
+        .expect("Failed to lint");
+      start.elapsed()
+    })
+    .min()
+    .unwrap()
+}
+
+fn main() {
+  let source = synthetic_module();
+  assert!(source.lines().count() >= 5000);
+  rules_share_the_parsed_module(&source);
+  rules_share_the_scope_analysis(&source);
+}
+
+fn rules_share_the_parsed_module(source: &str) {
+  // Parsing and the analyses shared by all rules
+  let base = time_lint(source, Vec::new);
+  let with_rules = time_lint(source, || {
+    get_recommended_rules()
+      .into_iter()
+      .take(RULE_COUNT)
+      .collect()
+  });
+  let per_rule = with_rules.saturating_sub(base) / RULE_COUNT as u32;
+  println!(
+    "parse and shared analyses: {:?}, with {} recommended rules: {:?}, per rule: {:?}",
+    base, RULE_COUNT, with_rules, per_rule
+  );
+
+  // A rule parsing the module again would cost at least as much as the
+  // base, while walking the shared AST costs a fraction of it.
+  assert!(
+    per_rule < base / 2,
+    "a rule costs {:?}, the shared parse {:?}",
+    per_rule,
+    base
+  );
+}
+
+/// Resolves every reference of the module, like rules tracking variables,
+/// either with the scope analysis shared by all rules or with its own.
+struct ResolveReferences {
+  code: &'static str,
+  own_analysis: bool,
+}
+
+impl ResolveReferences {
+  fn rules(own_analysis: bool) -> Vec<Box<dyn LintRule>> {
+    (0..RULE_COUNT)
+      .map(|i| -> Box<dyn LintRule> {
+        Box::new(ResolveReferences {
+          code: Box::leak(format!("bench-resolve-{}", i).into_boxed_str()),
+          own_analysis,
+        })
+      })
+      .collect()
+  }
+}
+
+impl LintRule for ResolveReferences {
+  fn new() -> Box<Self> {
+    Box::new(ResolveReferences {
+      code: "bench-resolve",
+      own_analysis: false,
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    self.code
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let own;
+    let scope_manager: &ScopeManager = if self.own_analysis {
+      own = analyze(module);
+      &own
+    } else {
+      &context.scope_manager
+    };
+    let resolved = scope_manager
+      .references()
+      .iter()
+      .filter(|reference| scope_manager.lookup(reference.span()).is_some())
+      .count();
+    assert!(resolved > 0);
+  }
+}
+
+fn rules_share_the_scope_analysis(source: &str) {
+  let base = time_lint(source, Vec::new);
+  let shared = time_lint(source, || ResolveReferences::rules(false));
+  let own = time_lint(source, || ResolveReferences::rules(true));
+  let per_rule_shared = shared.saturating_sub(base) / RULE_COUNT as u32;
+  let per_rule_own = own.saturating_sub(base) / RULE_COUNT as u32;
+  println!(
+    "resolving references per rule with the shared analysis: {:?}, with its own: {:?}",
+    per_rule_shared, per_rule_own
+  );
+
+  // The linter analyzed the module once before running the rules, which
+  // only look up the result.
+  assert!(
+    per_rule_shared < per_rule_own / 2,
+    "a rule using the shared analysis costs {:?}, analyzing on its own {:?}",
+    per_rule_shared,
+    per_rule_own
+  );
+}
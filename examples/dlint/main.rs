@@ -3,7 +3,7 @@ use clap::App;
 use clap::Arg;
 use deno_lint::diagnostic::LintDiagnostic;
 use deno_lint::linter::LinterBuilder;
-use deno_lint::rules::get_recommended_rules;
+use deno_lint::rules::{get_recommended_rules, get_recommended_rules_for_file};
 use rayon::prelude::*;
 use std::fmt;
 use std::io::Write;
@@ -80,6 +80,12 @@ pub fn format_diagnostic(diagnostic: &LintDiagnostic, source: &str) -> String {
     format!("./{}", file_name)
   };
 
+  // Diagnostics about the whole file have no source to show.
+  if diagnostic.is_file_level() {
+    let pretty_location = cyan(format!("--> {}", location)).to_string();
+    return vec![pretty_error, pretty_location].join("\n");
+  }
+
   let line_str_len = diagnostic.range.end.line.to_string().len();
   let pretty_location = cyan(format!(
     "{}--> {}:{}:{}",
@@ -94,8 +100,7 @@ pub fn format_diagnostic(diagnostic: &LintDiagnostic, source: &str) -> String {
 
   if diagnostic.range.start.line == diagnostic.range.end.line {
     let snippet_length = diagnostic.range.end.col - diagnostic.range.start.col;
-    let source_lines: Vec<&str> = source.split('\n').collect();
-    let line = source_lines[diagnostic.range.start.line - 1];
+    let line = &diagnostic.line_text;
     let pretty_line_src = format!("{} | {}", diagnostic.range.start.line, line);
     let red_glyphs = format!(
       "{} | {}{}",
@@ -206,12 +211,15 @@ fn main() {
       std::fs::read_to_string(&file_path).expect("Failed to read file");
 
     let mut linter = LinterBuilder::default()
-      .rules(get_recommended_rules())
+      .rules(get_recommended_rules_for_file(file_path))
       .build();
 
-    let file_diagnostics = linter
-      .lint(file_path.to_string(), source_code.clone())
-      .expect("Failed to lint");
+    // Syntax errors are shown like lint diagnostics
+    let file_diagnostics =
+      match linter.lint(file_path.to_string(), source_code.clone()) {
+        Ok(diagnostics) => diagnostics,
+        Err(err) => err.diagnostics,
+      };
 
     error_counts.fetch_add(file_diagnostics.len(), Ordering::Relaxed);
     let _g = output_lock.lock().unwrap();
@@ -226,3 +234,48 @@ fn main() {
     std::process::exit(1);
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use deno_lint::diagnostic::{ByteRange, Position, Range, Severity};
+
+  fn diagnostic(start: (usize, usize), end: (usize, usize)) -> LintDiagnostic {
+    LintDiagnostic {
+      range: Range {
+        start: Position {
+          line: start.0,
+          col: start.1,
+        },
+        end: Position {
+          line: end.0,
+          col: end.1,
+        },
+      },
+      byte_range: ByteRange { start: 0, end: 0 },
+      line_text: String::new(),
+      filename: "copyFile.ts".to_string(),
+      message: "Filename `copyFile` is not in snake_case".to_string(),
+      code: "consistent-filename-casing".to_string(),
+      severity: Severity::Error,
+      hint: None,
+      fix: None,
+      related: vec![],
+    }
+  }
+
+  #[test]
+  fn format_file_level_diagnostic() {
+    let formatted = format_diagnostic(&diagnostic((1, 0), (1, 0)), "");
+    assert_eq!(formatted.lines().count(), 2);
+    assert!(formatted.contains("./copyFile.ts"));
+    assert!(!formatted.contains("copyFile.ts:1:0"));
+  }
+
+  #[test]
+  fn format_source_diagnostic() {
+    let formatted = format_diagnostic(&diagnostic((1, 4), (1, 7)), "let foo;");
+    assert!(formatted.contains("./copyFile.ts:1:4"));
+    assert!(formatted.contains("1 | let foo;"));
+  }
+}
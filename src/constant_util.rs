@@ -0,0 +1,239 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use swc_ecmascript::ast::{AssignOp, BinaryOp, Expr, Lit, UnaryOp};
+
+fn check_short_circuit(expr: &Expr, operator: BinaryOp) -> bool {
+  match expr {
+    Expr::Lit(lit) => match lit {
+      Lit::Bool(boolean) => {
+        (operator == BinaryOp::LogicalOr && boolean.value)
+          || (operator == BinaryOp::LogicalAnd && !boolean.value)
+      }
+      _ => false,
+    },
+    Expr::Unary(unary) => {
+      operator == BinaryOp::LogicalAnd && unary.op == UnaryOp::Void
+    }
+    Expr::Bin(bin)
+      if bin.op == BinaryOp::LogicalAnd || bin.op == BinaryOp::LogicalOr =>
+    {
+      check_short_circuit(&bin.left, bin.op)
+        || check_short_circuit(&bin.right, bin.op)
+    }
+    _ => false,
+  }
+}
+
+/// Tells whether `node` always evaluates to the same value, or, when it's
+/// `in_boolean_position`, to the same truthiness.
+pub(crate) fn is_constant_expr(
+  node: &Expr,
+  parent_node: Option<&Expr>,
+  in_boolean_position: bool,
+) -> bool {
+  match node {
+    Expr::Lit(_) | Expr::Arrow(_) | Expr::Fn(_) | Expr::Object(_) => true,
+    Expr::Tpl(tpl) => {
+      (in_boolean_position
+        && tpl.quasis.iter().any(|quasi| match &quasi.cooked {
+          Some(str) => !str.is_empty(),
+          None => false,
+        }))
+        || tpl
+          .exprs
+          .iter()
+          .all(|expr| is_constant_expr(expr, parent_node, in_boolean_position))
+    }
+    // TODO(humancalico) confirm in_boolean_position here
+    Expr::Paren(paren) => is_constant_expr(&paren.expr, Some(node), false),
+    Expr::Array(arr) => match parent_node {
+      Some(Expr::Bin(bin)) => {
+        if bin.op == BinaryOp::Add {
+          arr.elems.iter().all(|element| {
+            is_constant_expr(
+              &element.as_ref().unwrap().expr,
+              parent_node,
+              false,
+            )
+          })
+        } else {
+          true
+        }
+      }
+      _ => true,
+    },
+    Expr::Unary(unary) => {
+      if unary.op == UnaryOp::Void {
+        true
+      } else {
+        (unary.op == UnaryOp::TypeOf && in_boolean_position)
+          || is_constant_expr(&unary.arg, Some(node), true)
+      }
+    }
+    Expr::Bin(bin) => {
+      // This is for LogicalExpression
+      if bin.op == BinaryOp::LogicalOr || bin.op == BinaryOp::LogicalAnd {
+        let is_left_constant =
+          is_constant_expr(&bin.left, Some(node), in_boolean_position);
+        let is_right_constant =
+          is_constant_expr(&bin.right, Some(node), in_boolean_position);
+        let is_left_short_circuit =
+          is_left_constant && check_short_circuit(&bin.left, bin.op);
+        let is_right_short_circuit =
+          is_right_constant && check_short_circuit(&bin.right, bin.op);
+        (is_left_constant && is_right_constant)
+        // TODO(humancalico) add more condiitons here from https://github.com/eslint/eslint/blob/f4d7b9e1a599346b2f21ff9de003b311b51411e6/lib/rules/no-constant-condition.js#L135-L146
+          || is_left_short_circuit
+          || is_right_short_circuit
+      }
+      // These are fo regular BinaryExpression
+      else if bin.op != BinaryOp::In {
+        is_constant_expr(&bin.left, Some(node), false)
+          && is_constant_expr(&bin.right, Some(node), false)
+      } else {
+        false
+      }
+    }
+    Expr::Assign(assign) => {
+      assign.op == AssignOp::Assign
+        && is_constant_expr(&assign.right, Some(node), in_boolean_position)
+    }
+    Expr::Seq(seq) => is_constant_expr(
+      &seq.exprs[seq.exprs.len() - 1],
+      Some(node),
+      in_boolean_position,
+    ),
+    _ => false,
+  }
+}
+
+/// Returns the truthiness of `expr` if it's the same every time it's
+/// evaluated, eg. `Some(true)` for `{}` or `typeof x` and `Some(false)` for
+/// `void x` or `""`.
+pub(crate) fn constant_truthiness(expr: &Expr) -> Option<bool> {
+  match expr {
+    Expr::Lit(lit) => match lit {
+      Lit::Bool(boolean) => Some(boolean.value),
+      Lit::Num(num) => Some(num.value != 0.0 && !num.value.is_nan()),
+      Lit::Str(str) => Some(!str.value.is_empty()),
+      Lit::BigInt(bigint) => Some(bigint.value != 0.into()),
+      Lit::Null(_) => Some(false),
+      Lit::Regex(_) => Some(true),
+      Lit::JSXText(_) => None,
+    },
+    Expr::Tpl(tpl) => {
+      let has_text = tpl.quasis.iter().any(|quasi| match &quasi.cooked {
+        Some(str) => !str.value.is_empty(),
+        None => false,
+      });
+      if has_text {
+        Some(true)
+      } else if tpl.exprs.is_empty() {
+        Some(false)
+      } else {
+        None
+      }
+    }
+    Expr::Ident(ident) if ident.sym == *"undefined" => Some(false),
+    Expr::Unary(unary) => match unary.op {
+      UnaryOp::Void => Some(false),
+      UnaryOp::TypeOf => Some(true),
+      UnaryOp::Bang => constant_truthiness(&unary.arg).map(|value| !value),
+      _ => None,
+    },
+    Expr::Bin(bin) => match bin.op {
+      BinaryOp::LogicalOr => match constant_truthiness(&bin.left) {
+        Some(true) => Some(true),
+        Some(false) => constant_truthiness(&bin.right),
+        None => constant_truthiness(&bin.right).filter(|value| *value),
+      },
+      BinaryOp::LogicalAnd => match constant_truthiness(&bin.left) {
+        Some(false) => Some(false),
+        Some(true) => constant_truthiness(&bin.right),
+        None => constant_truthiness(&bin.right).filter(|value| !*value),
+      },
+      BinaryOp::NullishCoalescing => match constant_nullishness(&bin.left) {
+        Some(true) => constant_truthiness(&bin.right),
+        Some(false) => constant_truthiness(&bin.left),
+        None => None,
+      },
+      _ => None,
+    },
+    Expr::Assign(assign) if assign.op == AssignOp::Assign => {
+      constant_truthiness(&assign.right)
+    }
+    Expr::Paren(paren) => constant_truthiness(&paren.expr),
+    Expr::Seq(seq) => constant_truthiness(seq.exprs.last()?),
+    _ if is_always_new(expr) => Some(true),
+    _ => None,
+  }
+}
+
+/// Returns whether `expr` is always nullish (`Some(true)`, eg. `null` or
+/// `void x`) or never nullish (`Some(false)`, eg. a literal, an arithmetic
+/// or comparison expression, or a newly-created object).
+pub(crate) fn constant_nullishness(expr: &Expr) -> Option<bool> {
+  match expr {
+    Expr::Lit(Lit::Null(_)) => Some(true),
+    Expr::Lit(_) | Expr::Tpl(_) | Expr::Update(_) => Some(false),
+    Expr::Ident(ident) if ident.sym == *"undefined" => Some(true),
+    Expr::Unary(unary) => Some(unary.op == UnaryOp::Void),
+    Expr::Bin(bin) => match bin.op {
+      BinaryOp::LogicalOr | BinaryOp::LogicalAnd => None,
+      BinaryOp::NullishCoalescing => match constant_nullishness(&bin.left) {
+        Some(true) => constant_nullishness(&bin.right),
+        Some(false) => Some(false),
+        None => constant_nullishness(&bin.right).filter(|value| !*value),
+      },
+      _ => Some(false),
+    },
+    Expr::Assign(assign) if assign.op == AssignOp::Assign => {
+      constant_nullishness(&assign.right)
+    }
+    Expr::Paren(paren) => constant_nullishness(&paren.expr),
+    Expr::Seq(seq) => constant_nullishness(seq.exprs.last()?),
+    _ if is_always_new(expr) => Some(false),
+    _ => None,
+  }
+}
+
+/// Tells whether `expr` creates a new object each time it's evaluated, so
+/// it's never strictly equal to anything else.
+pub(crate) fn is_always_new(expr: &Expr) -> bool {
+  match expr {
+    Expr::Object(_)
+    | Expr::Array(_)
+    | Expr::Fn(_)
+    | Expr::Arrow(_)
+    | Expr::Class(_)
+    | Expr::New(_)
+    | Expr::Lit(Lit::Regex(_)) => true,
+    Expr::Paren(paren) => is_always_new(&paren.expr),
+    Expr::Seq(seq) => {
+      matches!(seq.exprs.last(), Some(last) if is_always_new(last))
+    }
+    _ => false,
+  }
+}
+
+/// Tells whether `expr` always evaluates to a boolean, eg. `!x` or `a < b`.
+pub(crate) fn is_boolean_producing(expr: &Expr) -> bool {
+  match expr {
+    Expr::Lit(Lit::Bool(_)) => true,
+    Expr::Unary(unary) => unary.op == UnaryOp::Bang,
+    Expr::Bin(bin) => matches!(
+      bin.op,
+      BinaryOp::EqEq
+        | BinaryOp::NotEq
+        | BinaryOp::EqEqEq
+        | BinaryOp::NotEqEq
+        | BinaryOp::Lt
+        | BinaryOp::LtEq
+        | BinaryOp::Gt
+        | BinaryOp::GtEq
+        | BinaryOp::In
+        | BinaryOp::InstanceOf
+    ),
+    Expr::Paren(paren) => is_boolean_producing(&paren.expr),
+    _ => false,
+  }
+}
@@ -1,3 +1,5 @@
+pub(crate) mod terminator;
+
 use std::{
   collections::{HashMap, HashSet},
   mem::take,
@@ -0,0 +1,248 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+//! A syntactic check of whether every path through a list of statements
+//! ends with `return` or `throw`. Unlike `ControlFlow`, it looks at a single
+//! body on demand, and is conservative: when a path may complete normally,
+//! eg. through a loop which runs zero times or a `break`, the statements
+//! don't terminate.
+
+use swc_ecmascript::ast::*;
+use swc_ecmascript::utils::{ExprExt, Value};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+/// Checks if every path through `stmts` ends with a `return` or `throw`,
+/// whether or not the `return` has a value.
+pub(crate) fn always_terminates(stmts: &[Stmt]) -> bool {
+  for stmt in stmts {
+    match stmt {
+      // The statements after them are left before terminating
+      Stmt::Break(_) | Stmt::Continue(_) => return false,
+      _ if terminates(stmt) => return true,
+      _ => {}
+    }
+  }
+  false
+}
+
+fn terminates(stmt: &Stmt) -> bool {
+  match stmt {
+    Stmt::Return(_) | Stmt::Throw(_) => true,
+    Stmt::Block(block_stmt) => always_terminates(&block_stmt.stmts),
+    Stmt::If(if_stmt) => match &if_stmt.alt {
+      Some(alt) => terminates(&if_stmt.cons) && terminates(alt),
+      None => false,
+    },
+    Stmt::Switch(switch_stmt) => {
+      let has_default =
+        switch_stmt.cases.iter().any(|case| case.test.is_none());
+      // Without `break`s every case falls through to the last one
+      has_default
+        && !switch_stmt.cases.iter().any(|case| escapes(&case.cons))
+        && matches!(
+          switch_stmt.cases.last(),
+          Some(case) if always_terminates(&case.cons)
+        )
+    }
+    Stmt::Try(try_stmt) => {
+      let finalizer_terminates = match &try_stmt.finalizer {
+        Some(finalizer) => always_terminates(&finalizer.stmts),
+        None => false,
+      };
+      let handler_terminates = match &try_stmt.handler {
+        Some(handler) => always_terminates(&handler.body.stmts),
+        None => true,
+      };
+      finalizer_terminates
+        || (always_terminates(&try_stmt.block.stmts) && handler_terminates)
+    }
+    Stmt::Labeled(labeled_stmt) => {
+      terminates(&labeled_stmt.body)
+        && !escapes(std::slice::from_ref(&*labeled_stmt.body))
+    }
+    // A loop only terminates if it can't be left other than by returning
+    // or throwing, ie. it is infinite or its body always terminates.
+    Stmt::While(while_stmt) => {
+      is_always_true(&while_stmt.test)
+        && !escapes(std::slice::from_ref(&*while_stmt.body))
+    }
+    Stmt::For(for_stmt) => {
+      let is_infinite = match &for_stmt.test {
+        Some(test) => is_always_true(test),
+        None => true,
+      };
+      is_infinite && !escapes(std::slice::from_ref(&*for_stmt.body))
+    }
+    Stmt::DoWhile(do_while_stmt) => {
+      let body = std::slice::from_ref(&*do_while_stmt.body);
+      (always_terminates(body) || is_always_true(&do_while_stmt.test))
+        && !escapes(body)
+    }
+    _ => false,
+  }
+}
+
+fn is_always_true(test: &Expr) -> bool {
+  matches!(test.as_bool(), (_, Value::Known(true)))
+}
+
+/// Checks if `stmts` contain a `break` or `continue` which may leave them,
+/// ie. one which is labeled, or not inside a nested loop or switch.
+fn escapes(stmts: &[Stmt]) -> bool {
+  let mut finder = EscapeFinder {
+    depth: 0,
+    found: false,
+  };
+  for stmt in stmts {
+    stmt.visit_with(
+      &Invalid {
+        span: swc_common::DUMMY_SP,
+      },
+      &mut finder,
+    );
+  }
+  finder.found
+}
+
+struct EscapeFinder {
+  /// Number of loops and switches the visited statement is nested in.
+  depth: usize,
+  found: bool,
+}
+
+impl EscapeFinder {
+  fn nested<N: VisitWith<Self>>(&mut self, node: &N) {
+    self.depth += 1;
+    node.visit_children_with(self);
+    self.depth -= 1;
+  }
+}
+
+impl Visit for EscapeFinder {
+  noop_visit_type!();
+
+  fn visit_break_stmt(&mut self, break_stmt: &BreakStmt, _parent: &dyn Node) {
+    if break_stmt.label.is_some() || self.depth == 0 {
+      self.found = true;
+    }
+  }
+
+  fn visit_continue_stmt(
+    &mut self,
+    continue_stmt: &ContinueStmt,
+    _parent: &dyn Node,
+  ) {
+    if continue_stmt.label.is_some() || self.depth == 0 {
+      self.found = true;
+    }
+  }
+
+  fn visit_switch_stmt(
+    &mut self,
+    switch_stmt: &SwitchStmt,
+    _parent: &dyn Node,
+  ) {
+    self.nested(switch_stmt);
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, _parent: &dyn Node) {
+    self.nested(while_stmt);
+  }
+
+  fn visit_do_while_stmt(
+    &mut self,
+    do_while_stmt: &DoWhileStmt,
+    _parent: &dyn Node,
+  ) {
+    self.nested(do_while_stmt);
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _parent: &dyn Node) {
+    self.nested(for_stmt);
+  }
+
+  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, _parent: &dyn Node) {
+    self.nested(for_in_stmt);
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, _parent: &dyn Node) {
+    self.nested(for_of_stmt);
+  }
+
+  // `break` and `continue` can't cross function boundaries
+  fn visit_function(&mut self, _function: &Function, _parent: &dyn Node) {}
+
+  fn visit_arrow_expr(&mut self, _arrow_expr: &ArrowExpr, _parent: &dyn Node) {}
+
+  fn visit_class(&mut self, _class: &Class, _parent: &dyn Node) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use super::always_terminates;
+  use crate::swc_util::{self, AstParser};
+  use swc_ecmascript::ast::{Decl, ModuleItem, Stmt};
+
+  /// Checks whether the body of the function declared by `source_code`
+  /// always terminates.
+  fn terminates(source_code: &str) -> bool {
+    let ast_parser = AstParser::new();
+    let syntax = swc_util::get_default_ts_config();
+    let (parse_result, _comments) =
+      ast_parser.parse_module("file_name.ts", syntax, source_code);
+    let module = parse_result.unwrap();
+    match &module.body[0] {
+      ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => {
+        always_terminates(&fn_decl.function.body.as_ref().unwrap().stmts)
+      }
+      _ => panic!("expected a function declaration"),
+    }
+  }
+
+  #[test]
+  fn terminating_bodies() {
+    for source_code in &[
+      "function f() { return; }",
+      "function f() { foo(); throw new Error(); }",
+      "function f() { if (a) { return 1; } else { throw a; } }",
+      "function f() { if (a) return 1; else if (b) return 2; else return 3; }",
+      "function f() { { return 1; } }",
+      "function f() { switch (a) { case 1: return 1; default: return 2; } }",
+      "function f() { switch (a) { case 1: case 2: foo(); default: return 2; } }",
+      "function f() { switch (a) { default: for (;;) { break; } return 1; } }",
+      "function f() { try { return foo(); } catch (e) { return 1; } }",
+      "function f() { try { return foo(); } finally { cleanup(); } }",
+      "function f() { try { foo(); } finally { return 1; } }",
+      "function f() { while (true) { foo(); } }",
+      "function f() { for (;;) { if (a) { return 1; } } }",
+      "function f() { for (;;) { for (const b of c) { break; } } }",
+      "function f() { do { return 1; } while (a); }",
+      "function f() { a: { return 1; } }",
+      "function f() { while (true) { const g = () => { for (;;) {} }; } }",
+    ] {
+      assert!(terminates(source_code), "{}", source_code);
+    }
+  }
+
+  #[test]
+  fn non_terminating_bodies() {
+    for source_code in &[
+      "function f() {}",
+      "function f() { foo(); }",
+      "function f() { if (a) { return 1; } }",
+      "function f() { if (a) { return 1; } else { foo(); } }",
+      "function f() { switch (a) { case 1: return 1; } }",
+      "function f() { switch (a) { case 1: return 1; default: foo(); } }",
+      "function f() { switch (a) { case 1: break; default: return 1; } }",
+      "function f() { try { return foo(); } catch (e) { log(e); } }",
+      "function f() { try { foo(); } catch (e) { return 1; } }",
+      "function f() { for (const b of c) { return b; } }",
+      "function f() { for (let i = 0; i < 1; i++) { return i; } }",
+      "function f() { while (a) { return 1; } }",
+      "function f() { while (true) { if (a) { break; } } }",
+      "function f() { a: for (;;) { for (;;) { break a; } } }",
+      "function f() { do { if (a) { break; } return 1; } while (b); }",
+      "function f() { const g = () => { return 1; }; }",
+    ] {
+      assert!(!terminates(source_code), "{}", source_code);
+    }
+  }
+}
@@ -0,0 +1,120 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+
+/// The runtime permissions of Deno, granted with `--allow-<name>`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Permission {
+  Read,
+  Write,
+  Net,
+  Env,
+  Run,
+  Ffi,
+}
+
+impl Permission {
+  pub fn name(self) -> &'static str {
+    match self {
+      Permission::Read => "read",
+      Permission::Write => "write",
+      Permission::Net => "net",
+      Permission::Env => "env",
+      Permission::Run => "run",
+      Permission::Ffi => "ffi",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "read" => Some(Permission::Read),
+      "write" => Some(Permission::Write),
+      "net" => Some(Permission::Net),
+      "env" => Some(Permission::Env),
+      "run" => Some(Permission::Run),
+      "ffi" => Some(Permission::Ffi),
+      _ => None,
+    }
+  }
+}
+
+use Permission::*;
+
+/// The permission-gated APIs of the `Deno` namespace, by their member path,
+/// and the permissions they need.
+static PERMISSION_GATED_APIS: &[(&str, &[Permission])] = &[
+  ("chdir", &[Read]),
+  ("chmod", &[Write]),
+  ("chmodSync", &[Write]),
+  ("chown", &[Write]),
+  ("chownSync", &[Write]),
+  ("Command", &[Run]),
+  ("connect", &[Net]),
+  ("connectTls", &[Net]),
+  ("copyFile", &[Read, Write]),
+  ("copyFileSync", &[Read, Write]),
+  ("create", &[Read, Write]),
+  ("createSync", &[Read, Write]),
+  ("cwd", &[Read]),
+  ("dlopen", &[Ffi]),
+  ("env.delete", &[Env]),
+  ("env.get", &[Env]),
+  ("env.has", &[Env]),
+  ("env.set", &[Env]),
+  ("env.toObject", &[Env]),
+  ("kill", &[Run]),
+  ("link", &[Read, Write]),
+  ("linkSync", &[Read, Write]),
+  ("listen", &[Net]),
+  ("listenDatagram", &[Net]),
+  ("listenTls", &[Net]),
+  ("lstat", &[Read]),
+  ("lstatSync", &[Read]),
+  ("makeTempDir", &[Write]),
+  ("makeTempDirSync", &[Write]),
+  ("makeTempFile", &[Write]),
+  ("makeTempFileSync", &[Write]),
+  ("mkdir", &[Write]),
+  ("mkdirSync", &[Write]),
+  ("open", &[Read]),
+  ("openSync", &[Read]),
+  ("readDir", &[Read]),
+  ("readDirSync", &[Read]),
+  ("readFile", &[Read]),
+  ("readFileSync", &[Read]),
+  ("readLink", &[Read]),
+  ("readLinkSync", &[Read]),
+  ("readTextFile", &[Read]),
+  ("readTextFileSync", &[Read]),
+  ("realPath", &[Read]),
+  ("realPathSync", &[Read]),
+  ("remove", &[Write]),
+  ("removeSync", &[Write]),
+  ("rename", &[Read, Write]),
+  ("renameSync", &[Read, Write]),
+  ("resolveDns", &[Net]),
+  ("run", &[Run]),
+  ("serve", &[Net]),
+  ("startTls", &[Net]),
+  ("stat", &[Read]),
+  ("statSync", &[Read]),
+  ("symlink", &[Write]),
+  ("symlinkSync", &[Write]),
+  ("truncate", &[Write]),
+  ("truncateSync", &[Write]),
+  ("utime", &[Write]),
+  ("utimeSync", &[Write]),
+  ("watchFs", &[Read]),
+  ("writeFile", &[Write]),
+  ("writeFileSync", &[Write]),
+  ("writeTextFile", &[Write]),
+  ("writeTextFileSync", &[Write]),
+];
+
+/// Returns the permissions needed to call the member of the `Deno`
+/// namespace at `path`, eg. `["env", "get"]`, if it's permission-gated.
+pub fn required_permissions(path: &[String]) -> Option<&'static [Permission]> {
+  let path = path.join(".");
+  PERMISSION_GATED_APIS
+    .iter()
+    .find(|(api, _)| *api == path)
+    .map(|(_, permissions)| *permissions)
+}
@@ -0,0 +1,105 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use crate::swc_util::member_path;
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSpread, ExprOrSuper, ObjectLit, Prop, PropName,
+  PropOrSpread,
+};
+
+/// Recognizes the `Deno.test` signatures (also `Deno.test.only` and
+/// `Deno.test.ignore`) and returns the test function passed to the call:
+///
+/// - `Deno.test(fn)`
+/// - `Deno.test(name, fn)`
+/// - `Deno.test(options, fn)` and `Deno.test(name, options, fn)`
+/// - `Deno.test({ name, fn })`
+pub fn test_callback(call: &CallExpr) -> Option<&Expr> {
+  let callee = match &call.callee {
+    ExprOrSuper::Expr(callee) => callee,
+    ExprOrSuper::Super(_) => return None,
+  };
+  let (obj, path) = member_path(callee);
+  match obj {
+    Expr::Ident(ident) if ident.sym == *"Deno" => {}
+    _ => return None,
+  }
+  match path
+    .iter()
+    .map(String::as_str)
+    .collect::<Vec<_>>()
+    .as_slice()
+  {
+    ["test"] | ["test", "only"] | ["test", "ignore"] => {}
+    _ => return None,
+  }
+
+  definition_callback(&call.args)
+}
+
+/// Recognizes the signatures of `t.step`, where `t` is a test context, and
+/// returns the step function passed to the call:
+///
+/// - `t.step(fn)`
+/// - `t.step(name, fn)`
+/// - `t.step({ name, fn })`
+///
+/// The receiver of the call is not checked.
+pub fn step_callback(call: &CallExpr) -> Option<&Expr> {
+  let callee = match &call.callee {
+    ExprOrSuper::Expr(callee) => callee,
+    ExprOrSuper::Super(_) => return None,
+  };
+  match member_path(callee) {
+    (Expr::Ident(_), path) if path.len() == 1 && path[0] == "step" => {}
+    _ => return None,
+  }
+  definition_callback(&call.args)
+}
+
+/// Finds the function of the arguments of a test or step definition, which
+/// is either the last argument or the `fn` property of a sole object.
+fn definition_callback(args: &[ExprOrSpread]) -> Option<&Expr> {
+  match args {
+    [ExprOrSpread { spread: None, expr }] => {
+      if let Expr::Object(object) = unwrap_paren(expr) {
+        fn_property(object)
+      } else {
+        as_function(expr)
+      }
+    }
+    [.., ExprOrSpread { spread: None, expr }] => as_function(expr),
+    _ => None,
+  }
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn as_function(expr: &Expr) -> Option<&Expr> {
+  match unwrap_paren(expr) {
+    expr @ Expr::Fn(_) | expr @ Expr::Arrow(_) => Some(expr),
+    _ => None,
+  }
+}
+
+/// Finds the function of a test definition object, `{ name, fn: ... }`.
+fn fn_property(object: &ObjectLit) -> Option<&Expr> {
+  object.props.iter().find_map(|prop| match prop {
+    PropOrSpread::Prop(prop) => match &**prop {
+      Prop::KeyValue(kv) if is_fn_key(&kv.key) => as_function(&kv.value),
+      _ => None,
+    },
+    PropOrSpread::Spread(_) => None,
+  })
+}
+
+fn is_fn_key(key: &PropName) -> bool {
+  match key {
+    PropName::Ident(ident) => ident.sym == *"fn",
+    PropName::Str(s) => s.value == *"fn",
+    _ => false,
+  }
+}
@@ -1,11 +1,16 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 #[cfg(feature = "json")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize))]
 pub struct Position {
+  /// 1-based line, the number editors show for it. Protocols counting lines
+  /// from 0, like LSP, subtract 1; the CLI output, the positions of ignore
+  /// directives and existing consumers of the JSON output expect 1-based
+  /// lines, so they stay that way.
   pub line: usize,
+  /// 0-based column, in chars.
   pub col: usize,
 }
 
@@ -29,12 +34,200 @@ pub struct Range {
   pub end: Position,
 }
 
+/// Byte offsets into the linted source, `end` being exclusive.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct ByteRange {
+  pub start: usize,
+  pub end: usize,
+}
+
+/// How serious a diagnostic is, so callers can decide which ones fail the
+/// build.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase"))]
+pub enum Severity {
+  Error,
+  Warning,
+  Hint,
+}
+
+/// A problem found by a rule, or a syntax error.
+///
+/// With the `json` feature it serializes to an object with the fields
+/// below, eg. `{"range": {"start": {"line": 1, "col": 0}, "end": ...},
+/// "byteRange": ..., "code": "no-var", "message": ..., "hint": ...}`, which
+/// are kept stable. Like the rest of the JSON output, `LintReport` and
+/// `FileDiagnostics` included, field names are camelCase.
+/// Lines are 1-based and columns 0-based, as in editors, see `Position`.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase"))]
 pub struct LintDiagnostic {
+  /// The reported source text, the end being exclusive.
   pub range: Range,
+  pub byte_range: ByteRange,
+  /// The source line the diagnostic starts on, without the line break.
+  /// Empty for diagnostics about the whole file.
+  pub line_text: String,
   pub filename: String,
   pub message: String,
+  /// Code of the rule which reported the diagnostic, or `syntax-error`.
   pub code: String,
+  /// The severity of the rule, unless overridden for the linter.
+  pub severity: Severity,
+  /// How to resolve the problem, `null` in JSON if the rule has no hint.
   pub hint: Option<String>,
+  /// Edits resolving the diagnostic without changing what the code does,
+  /// ordered by position and not overlapping each other. `None` if it has
+  /// to be fixed by hand.
+  #[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
+  pub fix: Option<Vec<LintEdit>>,
+  /// Other parts of the source involved in the problem, eg. where a value
+  /// is read. Left out of the JSON when empty.
+  #[cfg_attr(feature = "json", serde(skip_serializing_if = "Vec::is_empty"))]
+  pub related: Vec<RelatedLocation>,
+}
+
+/// A part of the source a diagnostic refers to besides its own range, with
+/// a message saying what happens there.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase"))]
+pub struct RelatedLocation {
+  pub range: Range,
+  pub byte_range: ByteRange,
+  pub message: String,
+}
+
+impl LintDiagnostic {
+  /// Checks if the diagnostic is about the whole file, in which case it
+  /// has an empty range at the start of the file and no source to show.
+  pub fn is_file_level(&self) -> bool {
+    self.range.start == self.range.end
+      && self.range.start == Position { line: 1, col: 0 }
+  }
+
+  /// Checks if the fixes of both diagnostics edit the same text, in which
+  /// case only one of them can be applied before linting again.
+  pub fn fix_overlaps(&self, other: &LintDiagnostic) -> bool {
+    match (&self.fix, &other.fix) {
+      (Some(edits), Some(other_edits)) => edits.iter().any(|edit| {
+        other_edits
+          .iter()
+          .any(|other_edit| edit.overlaps(other_edit))
+      }),
+      _ => false,
+    }
+  }
+}
+
+/// Diagnostics collected while linting a file, in the order the rules
+/// reported them.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+  diagnostics: Vec<LintDiagnostic>,
+}
+
+impl Diagnostics {
+  pub fn push(&mut self, diagnostic: LintDiagnostic) {
+    self.diagnostics.push(diagnostic);
+  }
+
+  pub fn len(&self) -> usize {
+    self.diagnostics.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.diagnostics.is_empty()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &LintDiagnostic> {
+    self.diagnostics.iter()
+  }
+
+  /// Keeps only the diagnostics for which `keep` returns `true`.
+  pub fn retain(&mut self, keep: impl FnMut(&LintDiagnostic) -> bool) {
+    self.diagnostics.retain(keep);
+  }
+
+  /// Sorts the diagnostics by start position, end position and rule code,
+  /// and removes exact duplicates, eg. when two passes of a rule report the
+  /// same span. Positions are compared by line and column rather than by
+  /// offset in the source map, so the order doesn't depend on which other
+  /// files were parsed before, nor on the order the rules were registered.
+  pub fn finish(mut self) -> Vec<LintDiagnostic> {
+    self.diagnostics.sort_by(|a, b| {
+      let key = |diagnostic: &LintDiagnostic| {
+        (
+          diagnostic.range.start.line,
+          diagnostic.range.start.col,
+          diagnostic.range.end.line,
+          diagnostic.range.end.col,
+        )
+      };
+      key(a)
+        .cmp(&key(b))
+        .then_with(|| a.code.cmp(&b.code))
+        .then_with(|| a.message.cmp(&b.message))
+    });
+    self.diagnostics.dedup_by(|a, b| {
+      a.range == b.range && a.code == b.code && a.message == b.message
+    });
+    self.diagnostics
+  }
+}
+
+impl Extend<LintDiagnostic> for Diagnostics {
+  fn extend<T: IntoIterator<Item = LintDiagnostic>>(&mut self, iter: T) {
+    self.diagnostics.extend(iter);
+  }
+}
+
+/// Replaces the text at `range` with `replacement`, which is empty when
+/// the text is removed.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase"))]
+pub struct LintEdit {
+  pub range: Range,
+  pub byte_range: ByteRange,
+  pub replacement: String,
+}
+
+impl LintEdit {
+  /// Checks if both edits replace some of the same text, or insert at the
+  /// same position.
+  pub fn overlaps(&self, other: &LintEdit) -> bool {
+    (self.byte_range.start < other.byte_range.end
+      && other.byte_range.start < self.byte_range.end)
+      || self.byte_range.start == other.byte_range.start
+  }
+}
+
+/// Applies `edits`, which have to be ordered by position and not overlap,
+/// to `source`.
+pub fn apply_edits(source: &str, edits: &[LintEdit]) -> String {
+  let mut fixed = String::with_capacity(source.len());
+  let mut last_end = 0;
+  for edit in edits {
+    fixed.push_str(&source[last_end..edit.byte_range.start]);
+    fixed.push_str(&edit.replacement);
+    last_end = edit.byte_range.end;
+  }
+  fixed.push_str(&source[last_end..]);
+  fixed
+}
+
+/// Result of linting a single file, which also lists the rules that were
+/// run so that "no findings" can be told apart from "rule not enabled".
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase"))]
+pub struct LintReport {
+  /// Version of deno_lint that produced the report.
+  pub version: &'static str,
+  pub checks_run: Vec<String>,
+  pub diagnostics: Vec<LintDiagnostic>,
 }
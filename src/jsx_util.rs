@@ -0,0 +1,130 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  Expr, JSXAttr, JSXAttrName, JSXAttrOrSpread, JSXAttrValue, JSXElementName,
+  JSXExpr, JSXOpeningElement, Lit, UnaryExpr, UnaryOp,
+};
+
+/// Tags that are focusable and operable with the keyboard without any
+/// additional attributes.
+static INTERACTIVE_ELEMENTS: &[&str] = &[
+  "a", "area", "button", "input", "option", "select", "summary", "textarea",
+];
+
+/// Roles which correspond to one of the natively interactive elements.
+static INTERACTIVE_ROLES: &[&str] = &[
+  "button",
+  "checkbox",
+  "combobox",
+  "link",
+  "listbox",
+  "option",
+  "radio",
+  "searchbox",
+  "slider",
+  "spinbutton",
+  "textbox",
+];
+
+/// Returns the name of a JSX attribute as written in the source,
+/// namespaced names are joined with a colon, eg. `xlink:href`.
+pub fn attr_name(name: &JSXAttrName) -> String {
+  match name {
+    JSXAttrName::Ident(ident) => ident.sym.to_string(),
+    JSXAttrName::JSXNamespacedName(namespaced) => {
+      format!("{}:{}", namespaced.ns.sym, namespaced.name.sym)
+    }
+  }
+}
+
+pub fn attr_name_span(name: &JSXAttrName) -> Span {
+  match name {
+    JSXAttrName::Ident(ident) => ident.span,
+    JSXAttrName::JSXNamespacedName(namespaced) => {
+      namespaced.ns.span.to(namespaced.name.span)
+    }
+  }
+}
+
+/// Returns the tag name of a DOM element like `div`, `None` for components
+/// (`Foo`, `foo.Bar`) and namespaced names.
+pub fn dom_element_name(name: &JSXElementName) -> Option<&str> {
+  match name {
+    JSXElementName::Ident(ident)
+      if ident.sym.starts_with(|c: char| c.is_ascii_lowercase()) =>
+    {
+      Some(&ident.sym)
+    }
+    _ => None,
+  }
+}
+
+pub fn is_interactive_element(tag: &str) -> bool {
+  INTERACTIVE_ELEMENTS.contains(&tag)
+}
+
+pub fn is_interactive_role(role: &str) -> bool {
+  INTERACTIVE_ROLES.contains(&role)
+}
+
+/// Finds an attribute by name, like the DOM ignoring the case.
+pub fn find_attr<'a>(
+  opening: &'a JSXOpeningElement,
+  name: &str,
+) -> Option<&'a JSXAttr> {
+  opening.attrs.iter().find_map(|attr| match attr {
+    JSXAttrOrSpread::JSXAttr(attr)
+      if attr_name(&attr.name).eq_ignore_ascii_case(name) =>
+    {
+      Some(attr)
+    }
+    _ => None,
+  })
+}
+
+pub fn has_spread(opening: &JSXOpeningElement) -> bool {
+  opening
+    .attrs
+    .iter()
+    .any(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(_)))
+}
+
+/// Returns the value of `name="value"` and `name={"value"}`.
+pub fn attr_str_value(attr: &JSXAttr) -> Option<String> {
+  match &attr.value {
+    Some(JSXAttrValue::Lit(Lit::Str(s))) => Some(s.value.to_string()),
+    Some(JSXAttrValue::JSXExprContainer(container)) => match &container.expr {
+      JSXExpr::Expr(expr) => match &**expr {
+        Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+        _ => None,
+      },
+      JSXExpr::JSXEmptyExpr(_) => None,
+    },
+    _ => None,
+  }
+}
+
+/// Returns the numeric value of `name={1}`, `name={-1}` and `name="1"`.
+pub fn attr_number_value(attr: &JSXAttr) -> Option<f64> {
+  fn number(expr: &Expr) -> Option<f64> {
+    match expr {
+      Expr::Lit(Lit::Num(num)) => Some(num.value),
+      Expr::Unary(UnaryExpr {
+        op: UnaryOp::Minus,
+        arg,
+        ..
+      }) => number(arg).map(|value| -value),
+      Expr::Paren(paren) => number(&paren.expr),
+      _ => None,
+    }
+  }
+
+  if let Some(JSXAttrValue::JSXExprContainer(container)) = &attr.value {
+    if let JSXExpr::Expr(expr) = &container.expr {
+      if let Some(value) = number(expr) {
+        return Some(value);
+      }
+    }
+  }
+  attr_str_value(attr)?.trim().parse().ok()
+}
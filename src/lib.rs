@@ -8,23 +8,38 @@ extern crate lazy_static;
 #[macro_use]
 extern crate log;
 
+mod constant_util;
 mod control_flow;
+mod deno_api_util;
+mod deno_test_util;
 pub mod diagnostic;
 mod globals;
 mod js_regex;
+mod jsx_util;
 pub mod linter;
+pub mod media_type;
 pub mod rules;
-mod scopes;
+pub mod scopes;
+pub mod session;
+mod sort_util;
 pub mod swc_util;
 
+// Rules defined outside of this crate work with these versions of swc
+pub use swc_common;
+pub use swc_ecmascript;
+
 #[cfg(test)]
 mod test_util;
 
 #[cfg(test)]
 mod lint_tests {
-  use crate::diagnostic::LintDiagnostic;
+  use crate::diagnostic::{LintDiagnostic, Position};
   use crate::linter::*;
   use crate::rules::get_recommended_rules;
+  use crate::rules::no_debugger::NoDebugger;
+  use crate::rules::no_var::NoVar;
+  use crate::rules::prefer_const::PreferConst;
+  use crate::rules::LintRule;
   use crate::test_util::assert_diagnostic;
 
   fn lint(
@@ -159,4 +174,232 @@ mod lint_tests {
     assert_eq!(diagnostics.len(), 1);
     assert_diagnostic(&diagnostics[0], "ban-unused-ignore", 4, 1, src);
   }
+
+  fn lint_with_rules(
+    rules: Vec<Box<dyn LintRule>>,
+    source: &str,
+  ) -> Vec<LintDiagnostic> {
+    let mut linter = LinterBuilder::default().rules(rules).build();
+    linter
+      .lint("lint_test.ts".to_string(), source.to_string())
+      .expect("Failed to lint")
+  }
+
+  #[test]
+  fn directive_ignores_next_line_only() {
+    let src = r#"
+// deno-lint-ignore no-debugger
+debugger;
+debugger;
+"#;
+    let diagnostics = lint_with_rules(vec![NoDebugger::new()], src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 4, 0, src);
+  }
+
+  #[test]
+  fn directive_without_codes() {
+    let src = r#"
+// deno-lint-ignore
+var a = 1; debugger;
+var b = 1;
+"#;
+    let diagnostics =
+      lint_with_rules(vec![NoDebugger::new(), NoVar::new()], src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-var", 4, 0, src);
+  }
+
+  #[test]
+  fn directive_with_codes_ignores_only_those() {
+    let src = r#"
+// deno-lint-ignore no-var
+var a = 1; debugger;
+"#;
+    let diagnostics =
+      lint_with_rules(vec![NoDebugger::new(), NoVar::new()], src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 3, 11, src);
+  }
+
+  #[test]
+  fn directive_with_prefixed_code() {
+    let src = r#"
+// deno-lint-ignore @typescript-eslint/no-var
+var a = 1;
+// deno-lint-ignore acme-no-var
+var b = 1;
+"#;
+    let diagnostics = lint_with_rules(vec![NoVar::new()], src)
+      .into_iter()
+      .filter(|d| d.code == "no-var")
+      .collect::<Vec<_>>();
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-var", 5, 0, src);
+  }
+
+  #[test]
+  fn directive_without_codes_unused() {
+    let src = r#"
+// deno-lint-ignore
+const a = 1;
+"#;
+    let diagnostics = lint_with_rules(vec![NoDebugger::new()], src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "ban-unused-ignore", 2, 0, src);
+    assert_eq!(diagnostics[0].message, "Ignore directive has no effect.");
+  }
+
+  #[test]
+  fn file_directive_without_codes() {
+    let src = r#"
+// deno-lint-ignore-file
+var a = 1; debugger;
+"#;
+    let diagnostics =
+      lint_with_rules(vec![NoDebugger::new(), NoVar::new()], src);
+    assert!(diagnostics.is_empty());
+  }
+
+  #[test]
+  fn syntax_error_positions() {
+    let src = "interface Foo {\n  bar: string;\n}\nfunction foo(a: Foo {\n  return a;\n}\n";
+    let mut linter = LinterBuilder::default()
+      .rules(vec![NoDebugger::new()])
+      .build();
+    let err = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .unwrap_err();
+    assert_eq!(err.diagnostics.len(), 1);
+    let diagnostic = &err.diagnostics[0];
+    assert_eq!(diagnostic.code, "syntax-error");
+    assert_eq!(diagnostic.message, "Expected ,, got {");
+    assert_eq!(diagnostic.filename, "lint_test.ts");
+    assert_eq!(diagnostic.range.start, Position { line: 4, col: 20 });
+    assert_eq!(diagnostic.line_text, "function foo(a: Foo {");
+    assert_eq!(err.to_string(), "Expected ,, got { at lint_test.ts:4:20");
+  }
+
+  #[test]
+  fn recovered_syntax_errors_are_reported_with_lint_diagnostics() {
+    let src = "const a = b ?? c || d;\ndebugger;\n";
+    let mut linter = LinterBuilder::default()
+      .rules(vec![NoDebugger::new()])
+      .build();
+    let diagnostics = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    assert_eq!(diagnostics.len(), 2);
+    assert_diagnostic(&diagnostics[0], "syntax-error", 1, 10, src);
+    assert_eq!(
+      diagnostics[0].message,
+      "Nullish coalescing operator(??) requires parens when mixing with logical operators"
+    );
+    assert_diagnostic(&diagnostics[1], "no-debugger", 2, 0, src);
+  }
+
+  #[test]
+  fn syntax_by_media_type() {
+    use crate::media_type::MediaType;
+
+    let src = "const a = <div>{b}</div>;\n";
+    let lint_jsx = |file_name: &str, media_type: Option<MediaType>| {
+      let mut builder = LinterBuilder::default().rules(vec![NoVar::new()]);
+      if let Some(media_type) = media_type {
+        builder = builder.media_type(media_type);
+      }
+      builder
+        .build()
+        .lint(file_name.to_string(), src.to_string())
+        .is_ok()
+    };
+    assert!(lint_jsx("component.tsx", None));
+    assert!(lint_jsx("component.jsx", None));
+    assert!(!lint_jsx("component.ts", None));
+    assert!(lint_jsx("component.ts", Some(MediaType::TSX)));
+    assert!(!lint_jsx("component.tsx", Some(MediaType::TypeScript)));
+
+    let src = "export declare function f(a: string): void;\nexport interface A {\n  b?: number;\n}\n";
+    let diagnostics = lint_with_rules(get_recommended_rules(), src);
+    assert!(diagnostics.is_empty());
+    let diagnostics = LinterBuilder::default()
+      .rules(get_recommended_rules())
+      .build()
+      .lint("lib.d.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    assert!(diagnostics.is_empty());
+  }
+
+  #[test]
+  fn severity_override() {
+    use crate::diagnostic::Severity;
+    use crate::rules::sort_imports::SortImports;
+    use std::collections::HashMap;
+
+    let src = "import b from 'b.js';\nimport a from 'a.js';\ndebugger;\n";
+    let lint_with_severities = |severities: HashMap<String, Severity>| {
+      let mut linter = LinterBuilder::default()
+        .severities(severities)
+        .rules(vec![NoDebugger::new(), SortImports::new()])
+        .build();
+      linter
+        .lint("lint_test.ts".to_string(), src.to_string())
+        .expect("Failed to lint")
+        .iter()
+        .map(|d| (d.code.clone(), d.severity))
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      lint_with_severities(HashMap::new()),
+      vec![
+        ("sort-imports".to_string(), Severity::Error),
+        ("no-debugger".to_string(), Severity::Error),
+      ]
+    );
+    let mut severities = HashMap::new();
+    severities.insert("sort-imports".to_string(), Severity::Warning);
+    assert_eq!(
+      lint_with_severities(severities),
+      vec![
+        ("sort-imports".to_string(), Severity::Warning),
+        ("no-debugger".to_string(), Severity::Error),
+      ]
+    );
+  }
+
+  #[test]
+  fn diagnostics_have_positions_and_line_text() {
+    let src = "let a = 1;\r\nconst é = 1; var b = (c) => { debugger; };\n";
+    let mut linter = LinterBuilder::default()
+      .rules(vec![NoDebugger::new(), NoVar::new(), PreferConst::new()])
+      .build();
+    let diagnostics = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    let positions = diagnostics
+      .iter()
+      .map(|d| {
+        (
+          d.code.as_str(),
+          (d.range.start.line, d.range.start.col),
+          (d.range.end.line, d.range.end.col),
+          (d.byte_range.start, d.byte_range.end),
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      positions,
+      vec![
+        ("prefer-const", (1, 4), (1, 5), (4, 5)),
+        ("no-var", (2, 13), (2, 16), (26, 29)),
+        ("no-debugger", (2, 30), (2, 38), (43, 51)),
+      ]
+    );
+    assert_eq!(diagnostics[0].line_text, "let a = 1;");
+    assert_eq!(
+      diagnostics[2].line_text,
+      "const é = 1; var b = (c) => { debugger; };"
+    );
+  }
 }
@@ -1,22 +1,36 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
-use crate::diagnostic::{LintDiagnostic, Position, Range};
+use crate::diagnostic::{
+  ByteRange, Diagnostics, LintDiagnostic, LintEdit, LintReport, Position,
+  Range, RelatedLocation, Severity,
+};
+use crate::media_type::MediaType;
 use crate::rules::LintRule;
-use crate::scopes::{analyze, Scope};
-use crate::swc_util::get_default_ts_config;
-use crate::swc_util::AstParser;
+use crate::scopes::{analyze, ScopeManager};
+use crate::session::LintSession;
+use crate::swc_util::{count_nodes, AstParser};
 use crate::{control_flow::ControlFlow, swc_util::SwcDiagnosticBuffer};
-use std::collections::HashMap;
+use rayon::prelude::*;
+#[cfg(feature = "json")]
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::Instant;
 use swc_common::comments::CommentKind;
 use swc_common::comments::SingleThreadedComments;
+use swc_common::sync::Lrc;
 use swc_common::BytePos;
+use swc_common::FileName;
+use swc_common::SourceFile;
 use swc_common::SourceMap;
 use swc_common::Span;
+use swc_common::DUMMY_SP;
 use swc_common::{comments::Comment, SyntaxContext};
+use swc_ecmascript::ast::Ident;
+use swc_ecmascript::parser::JscTarget;
 use swc_ecmascript::parser::Syntax;
+use swc_ecmascript::utils::ident::IdentLike;
 
 lazy_static! {
   static ref IGNORE_COMMENT_CODE_RE: regex::Regex =
@@ -26,40 +40,217 @@ lazy_static! {
 #[derive(Clone)]
 pub struct Context {
   pub file_name: String,
-  pub diagnostics: Arc<Mutex<Vec<LintDiagnostic>>>,
+  pub diagnostics: Arc<Mutex<Diagnostics>>,
   pub source_map: Arc<SourceMap>,
+  /// The linted file, whose text rules scanning the raw source read.
+  pub(crate) source_file: Lrc<SourceFile>,
   pub(crate) leading_comments: HashMap<BytePos, Vec<Comment>>,
   pub(crate) trailing_comments: HashMap<BytePos, Vec<Comment>>,
   pub ignore_directives: Vec<IgnoreDirective>,
-  /// Arc as it's not modified
-  pub(crate) scope: Arc<Scope>,
+  /// Bindings and references of the module, analyzed once for all rules.
+  pub scope_manager: Arc<ScopeManager>,
+  /// Number of statements, expressions, patterns and other nodes of the
+  /// module, counted once for all rules.
+  pub node_count: usize,
   pub(crate) control_flow: Arc<ControlFlow>,
   pub(crate) top_level_ctxt: SyntaxContext,
+  /// ECMAScript version the linted code has to run on, `None` if the
+  /// latest features are available.
+  pub(crate) target: Option<JscTarget>,
+  /// Media type of the linted file, which rules for TypeScript-only syntax
+  /// check to skip JavaScript files.
+  pub(crate) media_type: MediaType,
+  /// Summaries of the other modules of the project, `None` if the file is
+  /// linted on its own.
+  pub(crate) session: Option<Arc<LintSession>>,
+  /// Severity of the diagnostics of each rule code.
+  pub(crate) severities: HashMap<String, Severity>,
 }
 
 impl Context {
-  pub(crate) fn add_diagnostic(&self, span: Span, code: &str, message: &str) {
-    let diagnostic = self.create_diagnostic(span, code, message);
+  pub fn add_diagnostic(&self, span: Span, code: &str, message: &str) {
+    let diagnostic = self.create_diagnostic(span, code, message, None, None);
     let mut diags = self.diagnostics.lock().unwrap();
     diags.push(diagnostic);
   }
 
+  pub fn add_diagnostic_with_hint(
+    &self,
+    span: Span,
+    code: &str,
+    message: &str,
+    hint: &str,
+  ) {
+    let diagnostic =
+      self.create_diagnostic(span, code, message, Some(hint.to_string()), None);
+    let mut diags = self.diagnostics.lock().unwrap();
+    diags.push(diagnostic);
+  }
+
+  /// Adds a diagnostic which also points at other parts of the source,
+  /// given as spans with a message saying what happens there.
+  pub fn add_diagnostic_with_related(
+    &self,
+    span: Span,
+    code: &str,
+    message: &str,
+    hint: &str,
+    related: Vec<(Span, String)>,
+  ) {
+    let mut diagnostic =
+      self.create_diagnostic(span, code, message, Some(hint.to_string()), None);
+    diagnostic.related = related
+      .into_iter()
+      .map(|(related_span, message)| RelatedLocation {
+        range: span_to_range(&self.source_map, related_span),
+        byte_range: span_to_byte_range(&self.source_map, related_span),
+        message,
+      })
+      .collect();
+    let mut diags = self.diagnostics.lock().unwrap();
+    diags.push(diagnostic);
+  }
+
+  /// Adds a diagnostic which is fixed by replacing the text at each span
+  /// of `edits` with its replacement. The edits mustn't overlap.
+  pub fn add_diagnostic_with_fix(
+    &self,
+    span: Span,
+    code: &str,
+    message: &str,
+    hint: &str,
+    mut edits: Vec<(Span, String)>,
+  ) {
+    edits.sort_by_key(|(edit_span, _)| edit_span.lo());
+    debug_assert!(
+      edits
+        .windows(2)
+        .all(|pair| pair[0].0.hi() <= pair[1].0.lo()),
+      "overlapping edits in a fix of {}",
+      code
+    );
+    let fix = edits
+      .into_iter()
+      .map(|(edit_span, replacement)| LintEdit {
+        range: span_to_range(&self.source_map, edit_span),
+        byte_range: span_to_byte_range(&self.source_map, edit_span),
+        replacement,
+      })
+      .collect();
+    let diagnostic = self.create_diagnostic(
+      span,
+      code,
+      message,
+      Some(hint.to_string()),
+      Some(fix),
+    );
+    let mut diags = self.diagnostics.lock().unwrap();
+    diags.push(diagnostic);
+  }
+
+  /// Returns the source text covered by `span`, which keeps the form
+  /// literals were written in, eg. escape sequences and number prefixes.
+  pub fn span_text(&self, span: Span) -> String {
+    self
+      .source_map
+      .span_to_snippet(span)
+      .expect("error in loading snippet")
+  }
+
+  /// Returns the source text of the whole file, for rules looking at
+  /// characters which are not part of the AST, like whitespace.
+  pub fn source(&self) -> &str {
+    &self.source_file.src
+  }
+
+  /// Converts a range of byte offsets into `source()`, the end being
+  /// exclusive, to a span, eg. to report a match found in the source text.
+  pub fn byte_range_to_span(
+    &self,
+    range: std::ops::Range<usize>,
+  ) -> Span {
+    let start_pos = self.source_file.start_pos;
+    Span::new(
+      start_pos + BytePos(range.start as u32),
+      start_pos + BytePos(range.end as u32),
+      SyntaxContext::empty(),
+    )
+  }
+
+  /// Returns the line and column range of `span`.
+  pub fn span_to_range(&self, span: Span) -> Range {
+    span_to_range(&self.source_map, span)
+  }
+
+  /// Returns the byte offsets of `span` into the source.
+  pub fn span_to_byte_range(&self, span: Span) -> ByteRange {
+    span_to_byte_range(&self.source_map, span)
+  }
+
+  /// Returns all comments of the file, whether they are attached to the
+  /// start or the end of a node, in no particular order.
+  pub(crate) fn all_comments(&self) -> impl Iterator<Item = &Comment> {
+    self
+      .leading_comments
+      .values()
+      .chain(self.trailing_comments.values())
+      .flatten()
+  }
+
+  /// Returns the comments inside `span`, eg. the ones explaining why a
+  /// block is empty.
+  pub(crate) fn comments_in(
+    &self,
+    span: Span,
+  ) -> impl Iterator<Item = &Comment> {
+    self
+      .all_comments()
+      .filter(move |comment| span.contains(comment.span))
+  }
+
+  /// Checks if `ident` refers to the global `name`, ie. it isn't shadowed
+  /// by any binding in the module.
+  pub(crate) fn is_global(&self, ident: &Ident, name: &str) -> bool {
+    ident.sym == *name
+      && ident.span.ctxt == self.top_level_ctxt
+      && self.scope_manager.binding(&ident.to_id()).is_none()
+  }
+
+  /// Adds a diagnostic about the whole file, like its name, which doesn't
+  /// point at any part of the source.
+  pub(crate) fn add_file_diagnostic_with_hint(
+    &self,
+    code: &str,
+    message: &str,
+    hint: &str,
+  ) {
+    self.add_diagnostic_with_hint(DUMMY_SP, code, message, hint);
+  }
+
   fn create_diagnostic(
     &self,
     span: Span,
     code: &str,
     message: &str,
+    hint: Option<String>,
+    fix: Option<Vec<LintEdit>>,
   ) -> LintDiagnostic {
     let time_start = Instant::now();
-    let start: Position = self.source_map.lookup_char_pos(span.lo()).into();
-    let end: Position = self.source_map.lookup_char_pos(span.hi()).into();
-
     let diagnostic = LintDiagnostic {
-      range: Range { start, end },
+      range: span_to_range(&self.source_map, span),
+      byte_range: span_to_byte_range(&self.source_map, span),
+      line_text: span_line_text(&self.source_map, span),
       filename: self.file_name.clone(),
       message: message.to_string(),
       code: code.to_string(),
-      hint: None,
+      severity: self
+        .severities
+        .get(code)
+        .copied()
+        .unwrap_or(Severity::Error),
+      hint,
+      fix,
+      related: vec![],
     };
 
     let time_end = Instant::now();
@@ -71,6 +262,64 @@ impl Context {
   }
 }
 
+fn span_to_range(source_map: &SourceMap, span: Span) -> Range {
+  // File level diagnostics are reported at the start of the file.
+  if span.is_dummy() {
+    let start = Position { line: 1, col: 0 };
+    return Range {
+      end: start.clone(),
+      start,
+    };
+  }
+  let start: Position = source_map.lookup_char_pos(span.lo()).into();
+  let end: Position = source_map.lookup_char_pos(span.hi()).into();
+  Range { start, end }
+}
+
+fn span_to_byte_range(source_map: &SourceMap, span: Span) -> ByteRange {
+  if span.is_dummy() {
+    return ByteRange { start: 0, end: 0 };
+  }
+  ByteRange {
+    start: source_map.lookup_byte_offset(span.lo()).pos.0 as usize,
+    end: source_map.lookup_byte_offset(span.hi()).pos.0 as usize,
+  }
+}
+
+fn span_line_text(source_map: &SourceMap, span: Span) -> String {
+  if span.is_dummy() {
+    return String::new();
+  }
+  let loc = source_map.lookup_char_pos(span.lo());
+  loc
+    .file
+    .get_line(loc.line - 1)
+    .map(|line| line.trim_end_matches('\r').to_string())
+    .unwrap_or_default()
+}
+
+/// Creates the diagnostic for a syntax error, which is reported by the
+/// parser rather than a rule and can't be ignored.
+pub(crate) fn syntax_error_diagnostic(
+  source_map: &SourceMap,
+  file_name: &str,
+  span: Span,
+  message: &str,
+) -> LintDiagnostic {
+  LintDiagnostic {
+    range: span_to_range(source_map, span),
+    byte_range: span_to_byte_range(source_map, span),
+    line_text: span_line_text(source_map, span),
+    filename: file_name.to_string(),
+    message: message.to_string(),
+    code: "syntax-error".to_string(),
+    severity: Severity::Error,
+    hint: None,
+    fix: None,
+    related: vec![],
+  }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct IgnoreDirective {
   pub position: Position,
@@ -78,6 +327,8 @@ pub struct IgnoreDirective {
   pub codes: Vec<String>,
   pub used_codes: HashMap<String, bool>,
   pub is_global: bool,
+  /// Whether a directive without codes suppressed any diagnostic.
+  pub used: bool,
 }
 
 impl IgnoreDirective {
@@ -94,11 +345,18 @@ impl IgnoreDirective {
       return false;
     }
 
+    // A directive without codes ignores every diagnostic on the next line
+    if self.codes.is_empty() {
+      self.used = true;
+      return true;
+    }
+
     let mut should_ignore = false;
     for code in self.codes.iter() {
-      // `ends_with` allows to skip `@typescript-eslint` prefix - not ideal
-      // but works for now
-      if code.ends_with(&diagnostic.code) {
+      // Allows to skip prefixes like `@typescript-eslint/`, but not to
+      // match other codes ending the same, like `acme-no-debugger`
+      let unprefixed = code.rsplit('/').next().unwrap();
+      if unprefixed == diagnostic.code {
         should_ignore = true;
         *self.used_codes.get_mut(code).unwrap() = true;
       }
@@ -108,12 +366,37 @@ impl IgnoreDirective {
   }
 }
 
+/// Error returned by `LinterBuilder::try_build` for a rule code used by more
+/// than one rule.
+#[derive(Debug, PartialEq)]
+pub struct DuplicateRuleCode {
+  pub code: String,
+}
+
+impl std::fmt::Display for DuplicateRuleCode {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "Rule code \"{}\" is used by more than one rule",
+      self.code
+    )
+  }
+}
+
+impl std::error::Error for DuplicateRuleCode {}
+
 pub struct LinterBuilder {
   ignore_file_directives: Vec<String>,
   ignore_diagnostic_directives: Vec<String>,
   lint_unused_ignore_directives: bool,
   lint_unknown_rules: bool,
-  syntax: swc_ecmascript::parser::Syntax,
+  syntax: Option<Syntax>,
+  media_type: Option<MediaType>,
+  target: Option<JscTarget>,
+  session: Option<Arc<LintSession>>,
+  severities: HashMap<String, Severity>,
+  node_budget: Option<usize>,
+  expensive_rules: Vec<String>,
   rules: Vec<Box<dyn LintRule>>,
 }
 
@@ -124,20 +407,54 @@ impl LinterBuilder {
       ignore_diagnostic_directives: vec!["deno-lint-ignore".to_string()],
       lint_unused_ignore_directives: true,
       lint_unknown_rules: true,
-      syntax: get_default_ts_config(),
+      syntax: None,
+      media_type: None,
+      target: None,
+      session: None,
+      severities: HashMap::new(),
+      node_budget: None,
+      expensive_rules: vec![],
       rules: vec![],
     }
   }
 
+  /// Builds the linter.
+  ///
+  /// # Panics
+  ///
+  /// If two rules have the same code, see `try_build`.
   pub fn build(self) -> Linter {
-    Linter::new(
+    match self.try_build() {
+      Ok(linter) => linter,
+      Err(err) => panic!("{}", err),
+    }
+  }
+
+  /// Builds the linter, unless two rules have the same code, in which case
+  /// their diagnostics and ignore directives couldn't be told apart.
+  pub fn try_build(self) -> Result<Linter, DuplicateRuleCode> {
+    let mut codes = HashSet::new();
+    for rule in &self.rules {
+      if !codes.insert(rule.code()) {
+        return Err(DuplicateRuleCode {
+          code: rule.code().to_string(),
+        });
+      }
+    }
+    Ok(Linter::new(
       self.ignore_file_directives,
       self.ignore_diagnostic_directives,
       self.lint_unused_ignore_directives,
       self.lint_unknown_rules,
       self.syntax,
+      self.media_type,
+      self.target,
+      self.session,
+      self.severities,
+      self.node_budget,
+      self.expensive_rules,
       self.rules,
-    )
+    ))
   }
 
   pub fn ignore_file_directives(mut self, directives: Vec<&str>) -> Self {
@@ -165,8 +482,53 @@ impl LinterBuilder {
     self
   }
 
+  /// Sets the parser syntax, which is otherwise chosen by the media type.
   pub fn syntax(mut self, syntax: Syntax) -> Self {
-    self.syntax = syntax;
+    self.syntax = Some(syntax);
+    self
+  }
+
+  /// Sets the media type of the linted file, which is otherwise derived
+  /// from its name.
+  pub fn media_type(mut self, media_type: MediaType) -> Self {
+    self.media_type = Some(media_type);
+    self
+  }
+
+  /// Sets the ECMAScript version the linted code has to run on, rules
+  /// suggesting newer APIs are disabled for older targets.
+  pub fn target(mut self, target: JscTarget) -> Self {
+    self.target = Some(target);
+    self
+  }
+
+  /// Sets the summaries of the project the linted file belongs to, which
+  /// rules checking consistency across files rely on.
+  pub fn session(mut self, session: Arc<LintSession>) -> Self {
+    self.session = Some(session);
+    self
+  }
+
+  /// Overrides the severity of the diagnostics of the given rule codes,
+  /// eg. to report a rule as a warning only.
+  pub fn severities(mut self, severities: HashMap<String, Severity>) -> Self {
+    self.severities = severities;
+    self
+  }
+
+  /// Skips the expensive rules on files with more than `max_nodes` nodes,
+  /// reporting a `rule-skipped` hint for each of them instead. Rules are
+  /// expensive if `LintRule::is_expensive` says so or they're listed in
+  /// `expensive_rules`. All rules run by default.
+  pub fn node_budget(mut self, max_nodes: usize) -> Self {
+    self.node_budget = Some(max_nodes);
+    self
+  }
+
+  /// Codes of rules which `node_budget` applies to, besides the ones whose
+  /// `LintRule::is_expensive` is true.
+  pub fn expensive_rules(mut self, codes: Vec<&str>) -> Self {
+    self.expensive_rules = codes.iter().map(|s| s.to_string()).collect();
     self
   }
 
@@ -174,36 +536,95 @@ impl LinterBuilder {
     self.rules = rules;
     self
   }
+
+  /// Adds a rule to the ones set by `rules`, eg. a custom rule defined
+  /// outside of this crate.
+  pub fn add_rule(mut self, rule: Box<dyn LintRule>) -> Self {
+    self.rules.push(rule);
+    self
+  }
+}
+
+/// Diagnostics of a file linted by `Linter::lint_files`. With the `json`
+/// feature, the results of all files serialize to JSON in one call, eg.
+/// `serde_json::to_string(&linter.lint_files(inputs))`.
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase"))]
+pub struct FileDiagnostics {
+  pub filename: String,
+  /// Diagnostics of the rules, and the syntax errors the parser recovered
+  /// from. Empty if the file couldn't be parsed.
+  pub diagnostics: Vec<LintDiagnostic>,
+  /// The syntax errors which prevented linting the file.
+  pub parse_errors: Vec<LintDiagnostic>,
+}
+
+impl FileDiagnostics {
+  pub fn new(
+    filename: String,
+    result: Result<Vec<LintDiagnostic>, SwcDiagnosticBuffer>,
+  ) -> Self {
+    match result {
+      Ok(diagnostics) => FileDiagnostics {
+        filename,
+        diagnostics,
+        parse_errors: vec![],
+      },
+      Err(err) => FileDiagnostics {
+        filename,
+        diagnostics: vec![],
+        parse_errors: err.diagnostics,
+      },
+    }
+  }
 }
 
 pub struct Linter {
   has_linted: bool,
-  ast_parser: AstParser,
   ignore_file_directives: Vec<String>,
   ignore_diagnostic_directives: Vec<String>,
   lint_unused_ignore_directives: bool,
   lint_unknown_rules: bool,
-  syntax: Syntax,
+  syntax: Option<Syntax>,
+  media_type: Option<MediaType>,
+  target: Option<JscTarget>,
+  session: Option<Arc<LintSession>>,
+  severities: HashMap<String, Severity>,
+  node_budget: Option<usize>,
+  expensive_rules: Vec<String>,
   rules: Vec<Box<dyn LintRule>>,
 }
 
 impl Linter {
+  #[allow(clippy::too_many_arguments)]
   fn new(
     ignore_file_directives: Vec<String>,
     ignore_diagnostic_directives: Vec<String>,
     lint_unused_ignore_directives: bool,
     lint_unknown_rules: bool,
-    syntax: Syntax,
+    syntax: Option<Syntax>,
+    media_type: Option<MediaType>,
+    target: Option<JscTarget>,
+    session: Option<Arc<LintSession>>,
+    severities: HashMap<String, Severity>,
+    node_budget: Option<usize>,
+    expensive_rules: Vec<String>,
     rules: Vec<Box<dyn LintRule>>,
   ) -> Self {
     Linter {
       has_linted: false,
-      ast_parser: AstParser::new(),
       ignore_file_directives,
       ignore_diagnostic_directives,
       lint_unused_ignore_directives,
       lint_unknown_rules,
       syntax,
+      media_type,
+      target,
+      session,
+      severities,
+      node_budget,
+      expensive_rules,
       rules,
     }
   }
@@ -218,55 +639,123 @@ impl Linter {
       "Linter can be used only on a single module."
     );
     self.has_linted = true;
+    self.lint_source(file_name, source_code)
+  }
+
+  /// Lints the files, given as pairs of file name and source code, in
+  /// parallel. The results are in the order of `inputs`, and the same as
+  /// linting each file on its own.
+  pub fn lint_files(
+    &self,
+    inputs: Vec<(String, String)>,
+  ) -> Vec<FileDiagnostics> {
+    inputs
+      .into_par_iter()
+      .map(|(file_name, source_code)| {
+        FileDiagnostics::new(
+          file_name.clone(),
+          self.lint_source(file_name, source_code),
+        )
+      })
+      .collect()
+  }
+
+  fn lint_source(
+    &self,
+    file_name: String,
+    source_code: String,
+  ) -> Result<Vec<LintDiagnostic>, SwcDiagnosticBuffer> {
     let start = Instant::now();
+    // Each file gets its own parser, so files can be linted in parallel
+    let ast_parser = AstParser::new();
     let diagnostics = if source_code.is_empty() {
       vec![]
     } else {
-      let (parse_result, comments) =
+      let syntax = self.syntax.unwrap_or_else(|| {
         self
-          .ast_parser
-          .parse_module(&file_name, self.syntax, &source_code);
+          .media_type
+          .unwrap_or_else(|| MediaType::from_file_name(&file_name))
+          .syntax()
+      });
+      let (parse_result, comments, syntax_errors) =
+        ast_parser.parse_module_recovering(&file_name, syntax, &source_code);
       let end_parse_module = Instant::now();
       debug!(
         "ast_parser.parse_module took {:#?}",
         end_parse_module - start
       );
       let module = parse_result?;
-      self.lint_module(file_name, module, comments)
+      let mut diagnostics =
+        self.lint_module(&ast_parser, file_name, module, comments);
+      // Syntax errors the parser recovered from are reported alongside the
+      // diagnostics of the rules, which ran on the recovered module
+      diagnostics.extend(syntax_errors);
+      diagnostics.finish()
     };
 
     let end = Instant::now();
-    debug!("Linter::lint took {:#?}", end - start);
+    debug!("Linter::lint_source took {:#?}", end - start);
     Ok(diagnostics)
   }
 
+  /// Like `lint`, but also reports the version and which rules were run.
+  pub fn lint_with_report(
+    &mut self,
+    file_name: String,
+    source_code: String,
+  ) -> Result<LintReport, SwcDiagnosticBuffer> {
+    let diagnostics = self.lint(file_name, source_code)?;
+    let mut checks_run = self
+      .rules
+      .iter()
+      .map(|rule| rule.code().to_string())
+      .collect::<Vec<_>>();
+    checks_run.sort();
+    checks_run.dedup();
+
+    Ok(LintReport {
+      version: env!("CARGO_PKG_VERSION"),
+      checks_run,
+      diagnostics,
+    })
+  }
+
   fn filter_diagnostics(
     &self,
     context: Arc<Context>,
     rules: &[Box<dyn LintRule>],
-  ) -> Vec<LintDiagnostic> {
+  ) -> Diagnostics {
     let start = Instant::now();
     let mut ignore_directives = context.ignore_directives.clone();
-    let diagnostics = context.diagnostics.lock().unwrap();
+    let mut filtered_diagnostics =
+      std::mem::take(&mut *context.diagnostics.lock().unwrap());
 
     let rule_codes = rules
       .iter()
       .map(|r| r.code().to_string())
       .collect::<Vec<String>>();
 
-    let mut filtered_diagnostics: Vec<LintDiagnostic> = diagnostics
-      .as_slice()
-      .iter()
-      .cloned()
-      .filter(|diagnostic| {
-        !ignore_directives.iter_mut().any(|ignore_directive| {
-          ignore_directive.maybe_ignore_diagnostic(&diagnostic)
-        })
+    filtered_diagnostics.retain(|diagnostic| {
+      !ignore_directives.iter_mut().any(|ignore_directive| {
+        ignore_directive.maybe_ignore_diagnostic(&diagnostic)
       })
-      .collect();
+    });
 
     if self.lint_unused_ignore_directives || self.lint_unknown_rules {
       for ignore_directive in ignore_directives {
+        if self.lint_unused_ignore_directives
+          && ignore_directive.codes.is_empty()
+          && !ignore_directive.used
+        {
+          filtered_diagnostics.push(context.create_diagnostic(
+            ignore_directive.span,
+            "ban-unused-ignore",
+            "Ignore directive has no effect.",
+            None,
+            None,
+          ));
+        }
+
         for (code, used) in ignore_directive.used_codes.iter() {
           if self.lint_unused_ignore_directives
             && !used
@@ -276,6 +765,8 @@ impl Linter {
               ignore_directive.span,
               "ban-unused-ignore",
               &format!("Ignore for code \"{}\" was not used.", code),
+              None,
+              None,
             );
             filtered_diagnostics.push(diagnostic);
           }
@@ -285,27 +776,38 @@ impl Linter {
               ignore_directive.span,
               "ban-unknown-rule-code",
               &format!("Unknown rule for code \"{}\"", code),
+              None,
+              None,
             ))
           }
         }
       }
     }
 
-    filtered_diagnostics
-      .sort_by(|a, b| a.range.start.line.cmp(&b.range.start.line));
-
     let end = Instant::now();
     debug!("Linter::filter_diagnostics took {:#?}", end - start);
 
     filtered_diagnostics
   }
 
+  fn exceeds_budget(&self, rule: &dyn LintRule, node_count: usize) -> bool {
+    match self.node_budget {
+      Some(max_nodes) => {
+        node_count > max_nodes
+          && (rule.is_expensive()
+            || self.expensive_rules.iter().any(|code| code == rule.code()))
+      }
+      None => false,
+    }
+  }
+
   fn lint_module(
     &self,
+    ast_parser: &AstParser,
     file_name: String,
     module: swc_ecmascript::ast::Module,
     comments: SingleThreadedComments,
-  ) -> Vec<LintDiagnostic> {
+  ) -> Diagnostics {
     let start = Instant::now();
     let file_ignore_directive = comments.with_leading(module.span.lo(), |c| {
       let directives = c
@@ -313,7 +815,7 @@ impl Linter {
         .filter_map(|comment| {
           parse_ignore_comment(
             &self.ignore_file_directives,
-            &*self.ast_parser.source_map,
+            &*ast_parser.source_map,
             comment,
             true,
           )
@@ -331,7 +833,7 @@ impl Linter {
     // whole file and skip linting it.
     if let Some(ignore_directive) = &file_ignore_directive {
       if ignore_directive.codes.is_empty() {
-        return vec![];
+        return Diagnostics::default();
       }
     }
 
@@ -347,7 +849,7 @@ impl Linter {
 
     let mut ignore_directives = parse_ignore_directives(
       &self.ignore_diagnostic_directives,
-      &self.ast_parser.source_map,
+      &ast_parser.source_map,
       &leading,
       &trailing,
     );
@@ -356,24 +858,61 @@ impl Linter {
       ignore_directives.insert(0, ignore_directive);
     }
 
-    let scope = Arc::new(analyze(&module));
+    let scope_manager = Arc::new(analyze(&module));
     let control_flow = Arc::new(ControlFlow::analyze(&module));
 
+    let mut severities = self
+      .rules
+      .iter()
+      .map(|rule| (rule.code().to_string(), rule.severity()))
+      .collect::<HashMap<_, _>>();
+    severities.insert("rule-skipped".to_string(), Severity::Hint);
+    severities.extend(self.severities.clone());
+
+    let media_type = self
+      .media_type
+      .unwrap_or_else(|| MediaType::from_file_name(&file_name));
+    let source_file = ast_parser
+      .source_map
+      .get_source_file(&FileName::Custom(file_name.clone()))
+      .expect("the linted file is in the source map");
     let context = Arc::new(Context {
       file_name,
-      diagnostics: Arc::new(Mutex::new(vec![])),
-      source_map: self.ast_parser.source_map.clone(),
+      diagnostics: Arc::new(Mutex::new(Diagnostics::default())),
+      source_file,
+      source_map: ast_parser.source_map.clone(),
       leading_comments: leading,
       trailing_comments: trailing,
       ignore_directives,
-      scope,
+      scope_manager,
+      node_count: count_nodes(&module),
       control_flow,
-      top_level_ctxt: swc_common::GLOBALS.set(&self.ast_parser.globals, || {
-        SyntaxContext::empty().apply_mark(self.ast_parser.top_level_mark)
+      top_level_ctxt: swc_common::GLOBALS.set(&ast_parser.globals, || {
+        SyntaxContext::empty().apply_mark(ast_parser.top_level_mark)
       }),
+      target: self.target,
+      media_type,
+      session: self.session.clone(),
+      severities,
     });
 
+    // The module is parsed and analyzed once, every rule walks the same AST
     for rule in &self.rules {
+      if self.exceeds_budget(rule.as_ref(), context.node_count) {
+        context.add_file_diagnostic_with_hint(
+          "rule-skipped",
+          &format!(
+            "Rule {} skipped on this file: exceeds size budget",
+            rule.code()
+          ),
+          &format!(
+            "The file has {} nodes, more than the {} allowed for expensive rules",
+            context.node_count,
+            self.node_budget.unwrap_or_default()
+          ),
+        );
+        continue;
+      }
       rule.lint_module(context.clone(), &module);
     }
 
@@ -461,6 +1000,7 @@ fn parse_ignore_comment(
           codes,
           used_codes,
           is_global,
+          used: false,
         });
       }
     }
@@ -473,6 +1013,7 @@ fn parse_ignore_comment(
 mod tests {
   use super::*;
   use crate::swc_util;
+  use swc_common::Spanned;
 
   #[test]
   fn test_parse_ignore_comments() {
@@ -532,4 +1073,237 @@ object | undefined {}
     assert_eq!(d.position, Position { line: 17, col: 3 });
     assert_eq!(d.codes, vec!["ban-types"]);
   }
+
+  #[test]
+  fn diagnostic_lines_are_one_based() {
+    use crate::rules::no_debugger::NoDebugger;
+
+    let diagnostics = LinterBuilder::default()
+      .rules(vec![NoDebugger::new()])
+      .build()
+      .lint("lint_test.ts".to_string(), "debugger;".to_string())
+      .expect("Failed to lint");
+    assert_eq!(diagnostics.len(), 1);
+    let range = &diagnostics[0].range;
+    assert_eq!(range.start, Position { line: 1, col: 0 });
+    assert_eq!(range.end, Position { line: 1, col: 8 });
+    assert_eq!(diagnostics[0].byte_range, ByteRange { start: 0, end: 8 });
+  }
+
+  #[test]
+  fn node_budget_skips_expensive_rules() {
+    use crate::rules::ban_commented_out_code::BanCommentedOutCode;
+    use crate::rules::no_debugger::NoDebugger;
+
+    let source = "debugger;\n/* const b = compute(a);\n   log(b); */\n";
+    let lint = |builder: LinterBuilder| {
+      builder
+        .rules(vec![BanCommentedOutCode::new(), NoDebugger::new()])
+        .build()
+        .lint("lint_test.ts".to_string(), source.to_string())
+        .expect("Failed to lint")
+        .into_iter()
+        .map(|d| (d.code, d.severity, d.message))
+        .collect::<Vec<_>>()
+    };
+    let skipped = |code: &str| {
+      (
+        "rule-skipped".to_string(),
+        Severity::Hint,
+        format!("Rule {} skipped on this file: exceeds size budget", code),
+      )
+    };
+    let debugger = (
+      "no-debugger".to_string(),
+      Severity::Error,
+      "`debugger` statement is not allowed".to_string(),
+    );
+
+    // Off by default, and files within the budget are linted by every rule
+    let all = lint(LinterBuilder::default());
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].0, "no-debugger");
+    assert_eq!(all[1].0, "ban-commented-out-code");
+    assert_eq!(lint(LinterBuilder::default().node_budget(100)), all);
+
+    assert_eq!(
+      lint(LinterBuilder::default().node_budget(0)),
+      vec![skipped("ban-commented-out-code"), debugger]
+    );
+    assert_eq!(
+      lint(
+        LinterBuilder::default()
+          .node_budget(0)
+          .expensive_rules(vec!["no-debugger"])
+      ),
+      vec![skipped("ban-commented-out-code"), skipped("no-debugger"),]
+    );
+  }
+
+  fn lint_report(rules: Vec<Box<dyn LintRule>>, source: &str) -> LintReport {
+    let mut linter = LinterBuilder::default().rules(rules).build();
+    linter
+      .lint_with_report("lint_test.ts".to_string(), source.to_string())
+      .expect("Failed to lint")
+  }
+
+  #[test]
+  fn lint_report_checks_run() {
+    use crate::rules::{get_filtered_rules, get_recommended_rules};
+    use crate::rules::{no_debugger::NoDebugger, no_var::NoVar};
+
+    let report = lint_report(vec![NoVar::new(), NoDebugger::new()], "var a;");
+    assert_eq!(report.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(report.checks_run, vec!["no-debugger", "no-var"]);
+    assert_eq!(report.diagnostics.len(), 1);
+
+    // A rule that isn't enabled is not listed, even without findings.
+    let report = lint_report(vec![NoDebugger::new()], "var a;");
+    assert_eq!(report.checks_run, vec!["no-debugger"]);
+    assert!(report.diagnostics.is_empty());
+
+    // Rules filtered by include and exclude lists
+    let rules =
+      get_filtered_rules(&["no-debugger", "no-var", "eqeqeq"], &["no-var"])
+        .unwrap();
+    let report = lint_report(rules, "var a;\ndebugger;");
+    assert_eq!(report.checks_run, vec!["eqeqeq", "no-debugger"]);
+    assert_eq!(report.diagnostics.len(), 1);
+    assert_eq!(report.diagnostics[0].code, "no-debugger");
+
+    let rules = get_filtered_rules(&[], &["no-debugger"]).unwrap();
+    let report = lint_report(rules, "debugger;");
+    assert!(!report.checks_run.contains(&"no-debugger".to_string()));
+    assert_eq!(report.checks_run.len(), get_recommended_rules().len() - 1);
+    assert!(report.diagnostics.is_empty());
+  }
+
+  #[test]
+  fn lint_files_matches_sequential_lint() {
+    use crate::rules::get_recommended_rules;
+
+    let inputs = (0..100)
+      .map(|i| {
+        let source = match i % 4 {
+          0 => format!("var a{} = 1;\ndebugger;\n", i),
+          1 => format!("function f{}() {{\n  if (x == -0) {{}}\n}}\n", i),
+          2 => format!("const a{} = (;\n", i),
+          _ => format!("export const a{} = {};\n", i, i),
+        };
+        (format!("file{}.ts", i), source)
+      })
+      .collect::<Vec<_>>();
+
+    let linter = LinterBuilder::default()
+      .rules(get_recommended_rules())
+      .build();
+    let parallel = linter.lint_files(inputs.clone());
+
+    let sequential = inputs
+      .into_iter()
+      .map(|(file_name, source_code)| {
+        let mut linter = LinterBuilder::default()
+          .rules(get_recommended_rules())
+          .build();
+        FileDiagnostics::new(
+          file_name.clone(),
+          linter.lint(file_name, source_code),
+        )
+      })
+      .collect::<Vec<_>>();
+
+    assert_eq!(parallel.len(), 100);
+    assert!(!parallel[0].diagnostics.is_empty());
+    assert!(parallel[2].diagnostics.is_empty());
+    assert!(!parallel[2].parse_errors.is_empty());
+    assert_eq!(format!("{:?}", parallel), format!("{:?}", sequential));
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn lint_report_json() {
+    use crate::rules::no_var::NoVar;
+
+    let report = lint_report(vec![NoVar::new()], "var a;");
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(json["checksRun"], serde_json::json!(["no-var"]));
+    assert_eq!(json["diagnostics"][0]["code"], "no-var");
+    assert_eq!(json["diagnostics"][0]["severity"], "error");
+    // Field names are camelCase throughout
+    assert_eq!(
+      json["diagnostics"][0]["byteRange"],
+      serde_json::json!({"start": 0, "end": 3})
+    );
+    assert_eq!(json["diagnostics"][0]["lineText"], "var a;");
+  }
+
+  /// Reports the span of every statement, once per pass.
+  struct ReportStatements {
+    code: &'static str,
+    passes: usize,
+  }
+
+  impl LintRule for ReportStatements {
+    fn new() -> Box<Self> {
+      Box::new(ReportStatements {
+        code: "report-statements",
+        passes: 1,
+      })
+    }
+
+    fn code(&self) -> &'static str {
+      self.code
+    }
+
+    fn lint_module(
+      &self,
+      context: Arc<Context>,
+      module: &swc_ecmascript::ast::Module,
+    ) {
+      for _ in 0..self.passes {
+        for item in module.body.iter().rev() {
+          context.add_diagnostic(item.span(), self.code, "statement");
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn diagnostics_are_sorted_and_deduplicated() {
+    let lint = |rules: Vec<Box<dyn LintRule>>| {
+      LinterBuilder::default()
+        .rules(rules)
+        .build()
+        .lint(
+          "lint_test.ts".to_string(),
+          "a;
+b;
+"
+          .to_string(),
+        )
+        .expect("Failed to lint")
+        .into_iter()
+        .map(|diagnostic| {
+          (
+            diagnostic.range.start.line,
+            diagnostic.range.start.col,
+            diagnostic.code,
+          )
+        })
+        .collect::<Vec<_>>()
+    };
+    let rule = |code, passes| -> Box<dyn LintRule> {
+      Box::new(ReportStatements { code, passes })
+    };
+    let expected = vec![
+      (1, 0, "a-rule".to_string()),
+      (1, 0, "b-rule".to_string()),
+      (2, 0, "a-rule".to_string()),
+      (2, 0, "b-rule".to_string()),
+    ];
+
+    assert_eq!(lint(vec![rule("b-rule", 2), rule("a-rule", 1)]), expected);
+    assert_eq!(lint(vec![rule("a-rule", 1), rule("b-rule", 2)]), expected);
+  }
 }
@@ -0,0 +1,167 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use crate::swc_util::{get_default_es_config, get_default_ts_config};
+use swc_ecmascript::parser::{EsConfig, Syntax, TsConfig};
+
+/// The kind of source a file contains, which decides how it's parsed.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MediaType {
+  JavaScript,
+  JSX,
+  TypeScript,
+  TSX,
+  /// TypeScript declaration file, `.d.ts`.
+  Dts,
+  /// CommonJS module, `.cjs`.
+  Cjs,
+  /// TypeScript compiled to a CommonJS module, `.cts`.
+  Cts,
+}
+
+impl MediaType {
+  /// Derives the media type from the extension of `file_name`. Files with
+  /// an unknown extension are treated as TypeScript.
+  pub fn from_file_name(file_name: &str) -> Self {
+    let name = file_name.rsplit(&['/', '\\'][..]).next().unwrap();
+    let lowercase = name.to_lowercase();
+    if lowercase.ends_with(".d.ts")
+      || lowercase.ends_with(".d.mts")
+      || lowercase.ends_with(".d.cts")
+    {
+      return MediaType::Dts;
+    }
+    let extension = lowercase.rfind('.').map(|index| &lowercase[index + 1..]);
+    match extension {
+      Some("js") | Some("mjs") => MediaType::JavaScript,
+      Some("cjs") => MediaType::Cjs,
+      Some("cts") => MediaType::Cts,
+      Some("jsx") => MediaType::JSX,
+      Some("tsx") => MediaType::TSX,
+      _ => MediaType::TypeScript,
+    }
+  }
+
+  /// Whether files of this media type may contain TypeScript syntax, like
+  /// types or namespaces.
+  pub fn is_typescript(self) -> bool {
+    !matches!(
+      self,
+      MediaType::JavaScript | MediaType::JSX | MediaType::Cjs
+    )
+  }
+
+  /// Whether files of this media type are CommonJS modules, which don't
+  /// support ES module features like top-level `await`.
+  pub fn is_commonjs(self) -> bool {
+    matches!(self, MediaType::Cjs | MediaType::Cts)
+  }
+
+  /// Returns the parser syntax for files of this media type.
+  pub fn syntax(self) -> Syntax {
+    match self {
+      MediaType::JavaScript | MediaType::JSX | MediaType::Cjs => {
+        match get_default_es_config() {
+          Syntax::Es(es_config) => Syntax::Es(EsConfig {
+            jsx: self == MediaType::JSX,
+            ..es_config
+          }),
+          syntax => syntax,
+        }
+      }
+      _ => match get_default_ts_config() {
+        Syntax::Typescript(ts_config) => Syntax::Typescript(TsConfig {
+          tsx: self == MediaType::TSX,
+          dts: self == MediaType::Dts,
+          ..ts_config
+        }),
+        syntax => syntax,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn media_type_from_file_name() {
+    let cases = [
+      ("mod.js", MediaType::JavaScript),
+      ("mod.mjs", MediaType::JavaScript),
+      ("/project/mod.cjs", MediaType::Cjs),
+      ("mod.cts", MediaType::Cts),
+      ("component.jsx", MediaType::JSX),
+      ("mod.ts", MediaType::TypeScript),
+      ("C:\\project\\mod.ts", MediaType::TypeScript),
+      ("component.tsx", MediaType::TSX),
+      ("Component.TSX", MediaType::TSX),
+      ("lib.d.ts", MediaType::Dts),
+      ("/project/types.d.mts", MediaType::Dts),
+      ("d.ts", MediaType::TypeScript),
+      ("README", MediaType::TypeScript),
+      ("mod.json", MediaType::TypeScript),
+    ];
+    for (file_name, media_type) in cases.iter() {
+      assert_eq!(
+        MediaType::from_file_name(file_name),
+        *media_type,
+        "{}",
+        file_name
+      );
+    }
+  }
+
+  #[test]
+  fn media_type_is_typescript() {
+    assert!(!MediaType::JavaScript.is_typescript());
+    assert!(!MediaType::JSX.is_typescript());
+    assert!(MediaType::TypeScript.is_typescript());
+    assert!(MediaType::TSX.is_typescript());
+    assert!(MediaType::Dts.is_typescript());
+    assert!(!MediaType::Cjs.is_typescript());
+    assert!(MediaType::Cts.is_typescript());
+  }
+
+  #[test]
+  fn media_type_is_commonjs() {
+    assert!(MediaType::Cjs.is_commonjs());
+    assert!(MediaType::Cts.is_commonjs());
+    assert!(!MediaType::JavaScript.is_commonjs());
+    assert!(!MediaType::TypeScript.is_commonjs());
+  }
+
+  #[test]
+  fn media_type_syntax() {
+    assert!(matches!(
+      MediaType::JavaScript.syntax(),
+      Syntax::Es(EsConfig { jsx: false, .. })
+    ));
+    assert!(matches!(
+      MediaType::JSX.syntax(),
+      Syntax::Es(EsConfig { jsx: true, .. })
+    ));
+    assert!(matches!(
+      MediaType::TypeScript.syntax(),
+      Syntax::Typescript(TsConfig {
+        tsx: false,
+        dts: false,
+        decorators: true,
+        dynamic_import: true,
+        ..
+      })
+    ));
+    assert!(matches!(
+      MediaType::TSX.syntax(),
+      Syntax::Typescript(TsConfig { tsx: true, .. })
+    ));
+    assert!(matches!(
+      MediaType::Dts.syntax(),
+      Syntax::Typescript(TsConfig {
+        tsx: false,
+        dts: true,
+        ..
+      })
+    ));
+  }
+}
@@ -1,15 +1,13 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use crate::swc_util::Key;
+use crate::swc_util::ExtractMethod;
 use std::collections::HashSet;
 use std::sync::Arc;
 use swc_common::Span;
 use swc_common::Spanned;
 use swc_ecmascript::ast::{
-  Class, ClassMember, ClassMethod, Decl, ExportDecl, Expr, FnDecl, Ident, Lit,
-  Module, ModuleDecl, ModuleItem, Stmt, Str, TsInterfaceBody,
-  TsMethodSignature, TsModuleBlock, TsTypeElement, TsTypeLit,
+  Class, Module, TsInterfaceBody, TsModuleBlock, TsTypeLit,
 };
 use swc_ecmascript::visit::{Node, Visit};
 
@@ -24,6 +22,48 @@ impl LintRule for AdjacentOverloadSignatures {
     "adjacent-overload-signatures"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires overload signatures of the same function, method or interface member to be placed next to each other.
+
+Overloads scattered across a module or class are easy to miss when reading or changing one of them.
+
+### Valid:
+```typescript
+function error(a: string);
+function error(b: number);
+function error(ab: string | number) {}
+export { error };
+```
+```typescript
+class Foo {
+  bar(s: string): void;
+  bar(n: number): void;
+  bar(sn: string | number): void {}
+  baz(): void {}
+}
+```
+
+### Invalid:
+```typescript
+export function foo(s: string);
+export function foo(n: number);
+export function bar(): void {}
+export function foo(sn: string | number) {}
+```
+```typescript
+class Foo {
+  bar(s: string): void;
+  baz(): void {}
+  bar(n: number): void;
+  bar(sn: string | number): void {}
+}
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = AdjacentOverloadSignaturesVisitor::new(context);
     visitor.visit_module(module, module);
@@ -71,76 +111,6 @@ impl AdjacentOverloadSignaturesVisitor {
   }
 }
 
-trait ExtractMethod {
-  fn get_method(&self) -> Option<Method>;
-}
-
-impl ExtractMethod for ModuleItem {
-  fn get_method(&self) -> Option<Method> {
-    let extract_ident = |decl: &Decl| match decl {
-      Decl::Fn(FnDecl { ref ident, .. }) => Some(ident.sym.to_string()),
-      _ => None,
-    };
-
-    let method_name = match self {
-      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
-        ref decl,
-        ..
-      })) => extract_ident(decl),
-      ModuleItem::Stmt(ref stmt) => match stmt {
-        Stmt::Decl(ref decl) => extract_ident(decl),
-        _ => None,
-      },
-      _ => None,
-    };
-
-    method_name.map(Method::Method)
-  }
-}
-
-impl ExtractMethod for ClassMember {
-  fn get_method(&self) -> Option<Method> {
-    match self {
-      ClassMember::Method(ClassMethod {
-        ref key, is_static, ..
-      }) => key.get_key().map(|k| {
-        if *is_static {
-          Method::Static(k)
-        } else {
-          Method::Method(k)
-        }
-      }),
-      ClassMember::Constructor(_) => {
-        Some(Method::Method("constructor".to_string()))
-      }
-      _ => None,
-    }
-  }
-}
-
-impl ExtractMethod for TsTypeElement {
-  fn get_method(&self) -> Option<Method> {
-    match self {
-      TsTypeElement::TsMethodSignature(TsMethodSignature {
-        ref key, ..
-      }) => match &**key {
-        Expr::Ident(Ident { ref sym, .. }) => {
-          Some(Method::Method(sym.to_string()))
-        }
-        Expr::Lit(Lit::Str(Str { ref value, .. })) => {
-          Some(Method::Method(value.to_string()))
-        }
-        _ => None,
-      },
-      TsTypeElement::TsCallSignatureDecl(_) => Some(Method::CallSignature),
-      TsTypeElement::TsConstructSignatureDecl(_) => {
-        Some(Method::ConstructSignature)
-      }
-      _ => None,
-    }
-  }
-}
-
 impl Visit for AdjacentOverloadSignaturesVisitor {
   fn visit_module(&mut self, module: &Module, parent: &dyn Node) {
     self.check(&module.body);
@@ -180,24 +150,6 @@ impl Visit for AdjacentOverloadSignaturesVisitor {
   }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone)]
-enum Method {
-  Method(String),
-  Static(String),
-  CallSignature,
-  ConstructSignature,
-}
-
-impl Method {
-  fn get_name(&self) -> &str {
-    match self {
-      Method::Method(ref s) | Method::Static(ref s) => s,
-      Method::CallSignature => "call",
-      Method::ConstructSignature => "new",
-    }
-  }
-}
-
 #[cfg(test)]
 mod tests {
   use super::*;
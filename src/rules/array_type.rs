@@ -0,0 +1,395 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  TsEntityName, TsType, TsTypeOperator, TsTypeOperatorOp, TsTypeRef,
+};
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArrayTypeMode {
+  /// `T[]` and `readonly T[]` everywhere.
+  Array,
+  /// `Array<T>` and `ReadonlyArray<T>` everywhere.
+  Generic,
+  /// `T[]` when `T` is a simple type, `Array<T>` otherwise.
+  ArraySimple,
+}
+
+pub struct ArrayTypeOptions {
+  pub mode: ArrayTypeMode,
+}
+
+impl Default for ArrayTypeOptions {
+  fn default() -> Self {
+    Self {
+      mode: ArrayTypeMode::Array,
+    }
+  }
+}
+
+pub struct ArrayType {
+  options: ArrayTypeOptions,
+}
+
+impl ArrayType {
+  pub fn with_options(options: ArrayTypeOptions) -> Box<Self> {
+    Box::new(ArrayType { options })
+  }
+}
+
+impl LintRule for ArrayType {
+  fn new() -> Box<Self> {
+    Self::with_options(ArrayTypeOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "array-type"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires a consistent style for array types, either `T[]` or `Array<T>`.
+
+### Options
+
+`ArrayTypeOptions::mode` is one of:
+- `ArrayTypeMode::Array` (default): always `T[]` and `readonly T[]`.
+- `ArrayTypeMode::Generic`: always `Array<T>` and `ReadonlyArray<T>`.
+- `ArrayTypeMode::ArraySimple`: `T[]` for simple types like `string` or `Foo`, and `Array<T>` for everything else.
+
+### Valid:
+```typescript
+let a: string[] = [];
+```
+```typescript
+function foo(a: readonly number[][]): void {}
+```
+
+### Invalid:
+```typescript
+let a: Array<string> = [];
+```
+```typescript
+let a: ReadonlyArray<string> = [];
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = ArrayTypeVisitor::new(context, self.options.mode);
+    visitor.visit_module(module, module);
+  }
+}
+
+/// An array type in either notation.
+struct ArrayLike<'a> {
+  span: Span,
+  elem: &'a TsType,
+  readonly: bool,
+  generic: bool,
+}
+
+fn array_like(ty: &TsType) -> Option<ArrayLike<'_>> {
+  match ty {
+    TsType::TsArrayType(array) => Some(ArrayLike {
+      span: array.span,
+      elem: &array.elem_type,
+      readonly: false,
+      generic: false,
+    }),
+    TsType::TsTypeOperator(TsTypeOperator {
+      span,
+      op: TsTypeOperatorOp::ReadOnly,
+      type_ann,
+    }) => match &**type_ann {
+      TsType::TsArrayType(array) => Some(ArrayLike {
+        span: *span,
+        elem: &array.elem_type,
+        readonly: true,
+        generic: false,
+      }),
+      _ => None,
+    },
+    TsType::TsTypeRef(TsTypeRef {
+      span,
+      type_name: TsEntityName::Ident(ident),
+      type_params: Some(type_params),
+    }) if type_params.params.len() == 1 => {
+      let readonly = match ident.sym.as_ref() {
+        "Array" => false,
+        "ReadonlyArray" => true,
+        _ => return None,
+      };
+      Some(ArrayLike {
+        span: *span,
+        elem: &type_params.params[0],
+        readonly,
+        generic: true,
+      })
+    }
+    _ => None,
+  }
+}
+
+/// Keywords, type references without type arguments (including qualified
+/// names) and arrays of those are simple.
+fn is_simple(ty: &TsType) -> bool {
+  match ty {
+    TsType::TsKeywordType(_) | TsType::TsThisType(_) => true,
+    TsType::TsTypeRef(TsTypeRef {
+      type_params: None, ..
+    }) => true,
+    _ => match array_like(ty) {
+      Some(array) => !array.readonly && is_simple(array.elem),
+      None => false,
+    },
+  }
+}
+
+/// Whether the type has to be parenthesized to be used as `T[]`.
+fn needs_parens(ty: &TsType) -> bool {
+  matches!(
+    ty,
+    TsType::TsUnionOrIntersectionType(_)
+      | TsType::TsFnOrConstructorType(_)
+      | TsType::TsConditionalType(_)
+      | TsType::TsTypeOperator(_)
+      | TsType::TsTypeQuery(_)
+      | TsType::TsInferType(_)
+  )
+}
+
+fn unwrap_paren(ty: &TsType) -> &TsType {
+  match ty {
+    TsType::TsParenthesizedType(paren) => unwrap_paren(&paren.type_ann),
+    _ => ty,
+  }
+}
+
+struct ArrayTypeVisitor {
+  context: Arc<Context>,
+  mode: ArrayTypeMode,
+}
+
+impl ArrayTypeVisitor {
+  fn new(context: Arc<Context>, mode: ArrayTypeMode) -> Self {
+    Self { context, mode }
+  }
+
+  fn prefers_generic(&self, elem: &TsType) -> bool {
+    match self.mode {
+      ArrayTypeMode::Array => false,
+      ArrayTypeMode::Generic => true,
+      ArrayTypeMode::ArraySimple => !is_simple(unwrap_paren(elem)),
+    }
+  }
+
+  /// Renders the type in the preferred notation, nested arrays included.
+  fn render(&self, ty: &TsType) -> String {
+    let array = match array_like(ty) {
+      Some(array) => array,
+      None => return self.context.span_text(ty.span()),
+    };
+    let elem = unwrap_paren(array.elem);
+    let elem_text = self.render(elem);
+    match (self.prefers_generic(elem), array.readonly) {
+      (true, false) => format!("Array<{}>", elem_text),
+      (true, true) => format!("ReadonlyArray<{}>", elem_text),
+      (false, readonly) => {
+        let prefix = if readonly { "readonly " } else { "" };
+        // `readonly` binds looser than `[]` too, which also covers
+        // `ReadonlyArray<T>` rendered as `readonly T[]`.
+        if needs_parens(elem) || elem_text.starts_with("readonly ") {
+          format!("{}({})[]", prefix, elem_text)
+        } else {
+          format!("{}{}[]", prefix, elem_text)
+        }
+      }
+    }
+  }
+
+  fn check(&self, ty: &TsType, array: &ArrayLike) {
+    let prefers_generic = self.prefers_generic(array.elem);
+    if prefers_generic == array.generic {
+      return;
+    }
+
+    let (generic, array_notation) = if array.readonly {
+      ("ReadonlyArray<T>", "readonly T[]")
+    } else {
+      ("Array<T>", "T[]")
+    };
+    let message = match (self.mode, prefers_generic) {
+      (ArrayTypeMode::ArraySimple, true) => format!(
+        "Array type using `{}` is forbidden for non-simple types. Use `{}` instead",
+        array_notation, generic
+      ),
+      (ArrayTypeMode::ArraySimple, false) => format!(
+        "Array type using `{}` is forbidden for simple types. Use `{}` instead",
+        generic, array_notation
+      ),
+      (_, true) => format!(
+        "Array type using `{}` is forbidden. Use `{}` instead",
+        array_notation, generic
+      ),
+      (_, false) => format!(
+        "Array type using `{}` is forbidden. Use `{}` instead",
+        generic, array_notation
+      ),
+    };
+    self.context.add_diagnostic_with_hint(
+      array.span,
+      "array-type",
+      &message,
+      &format!("Rewrite as `{}`", self.render(ty)),
+    );
+  }
+}
+
+impl Visit for ArrayTypeVisitor {
+  fn visit_ts_type(&mut self, ty: &TsType, parent: &dyn Node) {
+    if let Some(array) = array_like(ty) {
+      self.check(ty, &array);
+      // The array inside of `readonly T[]` is part of the same type.
+      if array.readonly && !array.generic {
+        self.visit_ts_type(array.elem, ty);
+        return;
+      }
+    }
+    swc_ecmascript::visit::visit_ts_type(self, ty, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn rule(mode: ArrayTypeMode) -> Box<ArrayType> {
+    ArrayType::with_options(ArrayTypeOptions { mode })
+  }
+
+  fn hints(mode: ArrayTypeMode, source: &str) -> Vec<String> {
+    lint(rule(mode), source)
+      .into_iter()
+      .filter_map(|diagnostic| diagnostic.hint)
+      .collect()
+  }
+
+  #[test]
+  fn array_type_valid() {
+    assert_lint_ok_n::<ArrayType>(vec![
+      "let a: string[] = [];",
+      "let a: (string | number)[] = [];",
+      "let a: readonly string[] = [];",
+      "let a: string[][] = [];",
+      "let a: Array = [];",
+      "let a: Set<string> = new Set();",
+      "let a: keyof string[];",
+    ]);
+  }
+
+  #[test]
+  fn array_type_invalid() {
+    assert_lint_err::<ArrayType>("let a: Array<string> = [];", 7);
+    assert_lint_err::<ArrayType>("let a: ReadonlyArray<string> = [];", 7);
+    assert_lint_err_n::<ArrayType>(
+      "function foo(a: Array<Array<number>>): void {}",
+      vec![16, 22],
+    );
+    assert_eq!(
+      hints(ArrayTypeMode::Array, "let a: Array<string | number>;"),
+      vec!["Rewrite as `(string | number)[]`"]
+    );
+    assert_eq!(
+      hints(ArrayTypeMode::Array, "let a: ReadonlyArray<Foo>;"),
+      vec!["Rewrite as `readonly Foo[]`"]
+    );
+    assert_eq!(
+      hints(ArrayTypeMode::Array, "let a: Array<ReadonlyArray<Foo>>;"),
+      vec![
+        "Rewrite as `(readonly Foo[])[]`",
+        "Rewrite as `readonly Foo[]`"
+      ]
+    );
+  }
+
+  #[test]
+  fn array_type_generic() {
+    let valid = vec![
+      "let a: Array<string> = [];",
+      "let a: ReadonlyArray<string> = [];",
+      "let a: Array<Array<string>> = [];",
+    ];
+    for src in valid {
+      assert_lint_ok_with(rule(ArrayTypeMode::Generic), src);
+    }
+
+    assert_lint_err_on_line_n_with(
+      rule(ArrayTypeMode::Generic),
+      "let a: readonly string[] = [];",
+      vec![(1, 7)],
+    );
+    assert_eq!(
+      hints(ArrayTypeMode::Generic, "let a: (string | number)[];"),
+      vec!["Rewrite as `Array<string | number>`"]
+    );
+    assert_eq!(
+      hints(ArrayTypeMode::Generic, "let a: readonly string[][];"),
+      vec![
+        "Rewrite as `ReadonlyArray<Array<string>>`",
+        "Rewrite as `Array<string>`",
+      ]
+    );
+  }
+
+  #[test]
+  fn array_type_array_simple() {
+    let valid = vec![
+      "let a: string[] = [];",
+      "let a: Foo.Bar[] = [];",
+      "let a: readonly number[] = [];",
+      "let a: string[][] = [];",
+      "let a: Array<string | number> = [];",
+      "let a: ReadonlyArray<() => void> = [];",
+      "let a: Array<Set<string>> = [];",
+      "let a: Array<Array<string | number>> = [];",
+    ];
+    for src in valid {
+      assert_lint_ok_with(rule(ArrayTypeMode::ArraySimple), src);
+    }
+
+    assert_lint_err_on_line_n_with(
+      rule(ArrayTypeMode::ArraySimple),
+      "let a: (string | number)[] = []; let b: Array<string> = [];",
+      vec![(1, 7), (1, 40)],
+    );
+    assert_lint_err_on_line_n_with(
+      rule(ArrayTypeMode::ArraySimple),
+      "let a: readonly (() => void)[] = [];",
+      vec![(1, 7)],
+    );
+    assert_eq!(
+      hints(ArrayTypeMode::ArraySimple, "let a: Array<Array<string>>;"),
+      vec!["Rewrite as `string[][]`", "Rewrite as `string[]`"]
+    );
+    assert_eq!(
+      hints(ArrayTypeMode::ArraySimple, "let a: Set<string>[][];"),
+      vec![
+        "Rewrite as `Array<Set<string>>`",
+        "Rewrite as `Array<Array<Set<string>>>`"
+      ]
+    );
+  }
+}
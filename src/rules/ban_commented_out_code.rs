@@ -0,0 +1,449 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::get_default_ts_config;
+use crate::swc_util::AstParser;
+use std::sync::Arc;
+use swc_common::comments::Comment;
+use swc_common::comments::CommentKind;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  AssignExpr, BlockStmt, CallExpr, Decl, Module, ModuleDecl, ModuleItem,
+  ObjectLit, Stmt,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+/// Prefixes of comments which configure tools rather than describe code.
+static DIRECTIVE_PREFIXES: &[&str] = &[
+  "deno-lint-ignore",
+  "deno-fmt-ignore",
+  "eslint",
+  "prettier-ignore",
+  "@ts-",
+  "@jsx",
+  "/ <reference",
+];
+
+declare_rule! {
+  BanCommentedOutCode, "ban-commented-out-code", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows comments which contain code that has been commented out.
+
+Dead code in comments goes stale quickly and clutters the source; version control keeps the history of removed code anyway. Comments that merely mention code in prose, like license headers, are allowed.
+
+### Valid:
+```typescript
+// Copyright 2020 the authors.
+const a = 1;
+```
+```typescript
+// Computes the double of `x`.
+function double(x) {
+  return x * 2;
+}
+```
+
+### Invalid:
+```typescript
+const a = 1;
+// function old(x) {
+//   return x * 2;
+// }
+a();
+```
+```typescript
+const a = 1;
+/* const b = compute(a);
+   log(b); */
+```"#
+  }
+
+  // Every comment which could be code is parsed as a module
+  fn is_expensive(&self) -> bool {
+    true
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut comments: Vec<&Comment> = context.all_comments().collect();
+    comments.sort_by_key(|comment| comment.span.lo());
+    comments.dedup_by_key(|comment| comment.span.lo());
+
+    // The comment block a file starts with is usually a license header.
+    let header_end = match module.body.first() {
+      Some(item) => item.span().lo(),
+      None => module.span.hi(),
+    };
+    let header_start = comments.first().map(|comment| comment.span.lo());
+    for run in comment_runs(&context, &comments) {
+      if Some(run[0].span.lo()) == header_start
+        && run[0].span.lo() < header_end
+      {
+        continue;
+      }
+      let span = run[0].span.to(run[run.len() - 1].span);
+      let text = run
+        .iter()
+        .map(|comment| strip_markers(comment))
+        .collect::<Vec<_>>()
+        .join("\n");
+      if looks_like_code(&text) {
+        context.add_diagnostic_with_hint(
+          span,
+          "ban-commented-out-code",
+          "Commented-out code should be removed",
+          "Remove the code, version control keeps its history",
+        );
+      }
+    }
+  }
+}
+
+fn is_exempt(comment: &Comment) -> bool {
+  let text = comment.text.trim_start();
+  // JSDoc, `/** ... */`
+  (comment.kind == CommentKind::Block && comment.text.starts_with('*'))
+    || comment.text.contains("://")
+    || DIRECTIVE_PREFIXES
+      .iter()
+      .any(|prefix| text.starts_with(prefix))
+}
+
+/// Groups consecutive line comments, each on its own line, into runs of two
+/// or more. Block comments form a run on their own.
+fn comment_runs<'a>(
+  context: &Context,
+  comments: &[&'a Comment],
+) -> Vec<Vec<&'a Comment>> {
+  fn finish<'a>(
+    runs: &mut Vec<Vec<&'a Comment>>,
+    current: &mut Vec<&'a Comment>,
+  ) {
+    if current.len() >= 2 {
+      runs.push(std::mem::take(current));
+    }
+    current.clear();
+  }
+
+  let mut runs: Vec<Vec<&Comment>> = vec![];
+  let mut current: Vec<&Comment> = vec![];
+  for &comment in comments {
+    if is_exempt(comment) {
+      finish(&mut runs, &mut current);
+      continue;
+    }
+    if comment.kind == CommentKind::Block {
+      finish(&mut runs, &mut current);
+      runs.push(vec![comment]);
+      continue;
+    }
+
+    if let Some(last) = current.last() {
+      let between = context.span_text(Span::new(
+        last.span.hi(),
+        comment.span.lo(),
+        Default::default(),
+      ));
+      let adjacent =
+        between.trim().is_empty() && between.matches('\n').count() == 1;
+      if !adjacent {
+        finish(&mut runs, &mut current);
+      }
+    }
+    // A comment after code on the same line doesn't start a run.
+    if current.is_empty() && !starts_line(context, comment) {
+      continue;
+    }
+    current.push(comment);
+  }
+  finish(&mut runs, &mut current);
+  runs.sort_by_key(|run| run[0].span.lo());
+  runs
+}
+
+fn starts_line(context: &Context, comment: &Comment) -> bool {
+  let loc = context.source_map.lookup_char_pos(comment.span.lo());
+  let line_start = loc.file.lines[loc.line - 1];
+  context
+    .span_text(Span::new(line_start, comment.span.lo(), Default::default()))
+    .trim()
+    .is_empty()
+}
+
+fn strip_markers(comment: &Comment) -> String {
+  match comment.kind {
+    CommentKind::Line => comment.text.to_string(),
+    // `/* ... */` comments spanning multiple lines often prefix each line
+    // with `*`.
+    CommentKind::Block => comment
+      .text
+      .lines()
+      .map(|line| {
+        let trimmed = line.trim_start();
+        trimmed.strip_prefix('*').unwrap_or(trimmed)
+      })
+      .collect::<Vec<_>>()
+      .join("\n"),
+  }
+}
+
+/// Parses the text as a module. Text the parser only recovers from, like
+/// legacy octal literals or `with` statements, isn't taken as code, as prose
+/// is more likely to parse that way by accident.
+fn parse(source: &str) -> Option<Module> {
+  let ast_parser = AstParser::new();
+  let (module, _, recovered) = ast_parser.parse_module_recovering(
+    "comment.ts",
+    get_default_ts_config(),
+    source,
+  );
+  if !recovered.is_empty() {
+    return None;
+  }
+  module.ok()
+}
+
+/// Returns true if the text parses as code and contains something prose
+/// is unlikely to: an assignment, a call with arguments, a declaration or
+/// braces.
+fn looks_like_code(text: &str) -> bool {
+  if text.trim().is_empty() {
+    return false;
+  }
+
+  let mut finder = CodeFinder { found: false };
+  if let Some(module) = parse(text) {
+    module.visit_with(&module, &mut finder);
+    return finder.found;
+  }
+
+  // Code commented out of a function body can contain `return`, which is
+  // only valid inside of a function.
+  let wrapped = format!("async function __commented__() {{\n{}\n}}", text);
+  let module = match parse(&wrapped) {
+    Some(module) => module,
+    None => return false,
+  };
+  if let Some(ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl)))) =
+    module.body.first()
+  {
+    if let Some(body) = &fn_decl.function.body {
+      for stmt in &body.stmts {
+        stmt.visit_with(body, &mut finder);
+      }
+    }
+  }
+  finder.found
+}
+
+struct CodeFinder {
+  found: bool,
+}
+
+impl Visit for CodeFinder {
+  noop_visit_type!();
+
+  fn visit_assign_expr(
+    &mut self,
+    _assign_expr: &AssignExpr,
+    _parent: &dyn Node,
+  ) {
+    self.found = true;
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if !call_expr.args.is_empty() {
+      self.found = true;
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+
+  fn visit_decl(&mut self, _decl: &Decl, _parent: &dyn Node) {
+    self.found = true;
+  }
+
+  fn visit_module_decl(
+    &mut self,
+    _module_decl: &ModuleDecl,
+    _parent: &dyn Node,
+  ) {
+    self.found = true;
+  }
+
+  fn visit_block_stmt(&mut self, _block: &BlockStmt, _parent: &dyn Node) {
+    self.found = true;
+  }
+
+  fn visit_object_lit(&mut self, _object_lit: &ObjectLit, _parent: &dyn Node) {
+    self.found = true;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn ban_commented_out_code_valid() {
+    assert_lint_ok_n::<BanCommentedOutCode>(vec![
+      r#"
+// Copyright 2020 the authors.
+// const license = require("license");
+const a = 1;
+      "#,
+      r#"
+const a = 1;
+// Check the value (if any) before calling it,
+// otherwise the callback (see below) throws.
+a();
+      "#,
+      r#"
+const a = 1;
+// Note: this is slow.
+// TODO: make it faster.
+a();
+      "#,
+      r#"
+const a = 1;
+// single line foo(a, b);
+a();
+      "#,
+      r#"
+const a = 1;
+/**
+ * @example
+ * const b = foo(a);
+ */
+export function foo(x) {}
+      "#,
+      r#"
+const a = 1;
+// See https://deno.land/
+// for fetch(url) examples.
+a();
+      "#,
+      r#"
+const a = 1;
+// deno-lint-ignore no-explicit-any
+// @ts-ignore
+const b: any = a;
+      "#,
+      r#"
+const a = 1;
+foo(a); // first(a, b)
+bar(a); // second(a, b)
+      "#,
+      r#"
+const a = 1;
+// foo
+// bar
+a();
+      "#,
+      // Only parses as labeled assignments with legacy octal literals
+      r#"
+const a = 1;
+// Defaults: mode = 0644,
+// umask = 022
+a();
+      "#,
+    ]);
+  }
+
+  #[test]
+  fn ban_commented_out_code_invalid() {
+    assert_lint_err_on_line::<BanCommentedOutCode>(
+      r#"
+const a = 1;
+// function old(x) {
+//   return x * 2;
+// }
+a();
+      "#,
+      3,
+      0,
+    );
+    assert_lint_err_on_line::<BanCommentedOutCode>(
+      r#"
+const a = 1;
+/* const b = compute(a);
+   log(b); */
+      "#,
+      3,
+      0,
+    );
+    assert_lint_err_on_line::<BanCommentedOutCode>(
+      r#"
+function f(a) {
+  // if (!a) {
+  //   return null;
+  // }
+  return a;
+}
+      "#,
+      3,
+      2,
+    );
+    assert_lint_err_on_line::<BanCommentedOutCode>(
+      r#"
+import { a } from "./a.ts";
+// import { b } from "./b.ts";
+// console.log(a, b);
+      "#,
+      3,
+      0,
+    );
+  }
+
+  #[test]
+  fn ban_commented_out_code_before_first_statement() {
+    assert_lint_err_on_line::<BanCommentedOutCode>(
+      r#"
+// Copyright 2020 the authors.
+// MIT license.
+
+// import { old } from "./old.ts";
+// old();
+const a = 1;
+      "#,
+      5,
+      0,
+    );
+    assert_lint_err_on_line::<BanCommentedOutCode>(
+      r#"
+// Copyright 2020 the authors.
+
+/* const b = compute(a);
+   log(b); */
+const a = 1;
+      "#,
+      4,
+      0,
+    );
+    assert_lint_err_on_line::<BanCommentedOutCode>(
+      r#"
+// Copyright 2020 the authors.
+/* const b = compute(a);
+   log(b); */
+const a = 1;
+      "#,
+      3,
+      0,
+    );
+  }
+
+  #[test]
+  fn ban_commented_out_code_run_span() {
+    let diagnostics = lint(
+      BanCommentedOutCode::new(),
+      "const a = 1;\n// let b = a;\n// b++;\n// foo(b);\na();",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(diagnostics[0].range.end.line, 4);
+  }
+}
@@ -0,0 +1,422 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::{member_path, unwrap_paren, MUTATING_METHODS};
+use std::collections::HashMap;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  AssignExpr, CallExpr, Expr, ExprOrSuper, ImportSpecifier, Module, ModuleDecl,
+  ModuleItem, Pat, PatOrExpr, UnaryExpr, UnaryOp, UpdateExpr, VarDecl,
+  VarDeclKind,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Default)]
+pub struct BanReassigningImportedNamespaceMembersOptions {
+  /// Import sources whose exports are meant to be mutable, eg.
+  /// `"./state.ts"`. They're compared with the specifier as written.
+  pub allow: Vec<String>,
+}
+
+/// Reports mutations of the objects exported by other modules, made
+/// through a namespace or named import.
+pub struct BanReassigningImportedNamespaceMembers {
+  options: BanReassigningImportedNamespaceMembersOptions,
+}
+
+impl BanReassigningImportedNamespaceMembers {
+  pub fn with_options(
+    options: BanReassigningImportedNamespaceMembersOptions,
+  ) -> Box<Self> {
+    Box::new(BanReassigningImportedNamespaceMembers { options })
+  }
+}
+
+const CODE: &str = "ban-reassigning-imported-namespace-members";
+
+impl LintRule for BanReassigningImportedNamespaceMembers {
+  fn new() -> Box<Self> {
+    Self::with_options(BanReassigningImportedNamespaceMembersOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows mutating the objects exported by other modules.
+
+Imports look read-only, but `ns.config.debug = true` changes the exported object for every module importing it. Assignments, compound assignments, updates and `delete` of a property, `Object.assign()` and mutating methods like `push()` are reported on namespace and named imports, and on `const` bindings initialized to one of them.
+
+### Options
+
+- `BanReassigningImportedNamespaceMembersOptions::allow`: import sources whose exports are meant to be mutable, eg. `["./state.ts"]`, empty by default.
+
+### Valid:
+```typescript
+import * as config from "./config.ts";
+
+const options = { ...config.defaults, debug: true };
+```
+
+### Invalid:
+```typescript
+import * as config from "./config.ts";
+
+config.defaults.debug = true;
+```
+```typescript
+import { handlers } from "./handlers.ts";
+
+handlers.push(onLoad);
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let imports = collect_imports(module, &self.options);
+    if imports.is_empty() {
+      return;
+    }
+    let mut visitor = BanReassigningImportedNamespaceMembersVisitor {
+      context,
+      imports,
+      aliases: HashMap::new(),
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct ImportedBinding {
+  /// Local name of the binding.
+  name: String,
+  source: String,
+  /// Span of the local name in the import declaration.
+  span: Span,
+  namespace: bool,
+}
+
+fn collect_imports(
+  module: &Module,
+  options: &BanReassigningImportedNamespaceMembersOptions,
+) -> HashMap<Id, ImportedBinding> {
+  let mut imports = HashMap::new();
+  for item in &module.body {
+    let import_decl = match item {
+      ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl))
+        if !import_decl.type_only =>
+      {
+        import_decl
+      }
+      _ => continue,
+    };
+    let source = import_decl.src.value.to_string();
+    if options.allow.contains(&source) {
+      continue;
+    }
+    for specifier in &import_decl.specifiers {
+      let (local, namespace) = match specifier {
+        ImportSpecifier::Named(named) => (&named.local, false),
+        ImportSpecifier::Namespace(namespace) => (&namespace.local, true),
+        ImportSpecifier::Default(_) => continue,
+      };
+      imports.insert(
+        local.to_id(),
+        ImportedBinding {
+          name: local.sym.to_string(),
+          source: source.clone(),
+          span: local.span,
+          namespace,
+        },
+      );
+    }
+  }
+  imports
+}
+
+struct BanReassigningImportedNamespaceMembersVisitor {
+  context: Arc<Context>,
+  imports: HashMap<Id, ImportedBinding>,
+  /// `const` bindings initialized to an import or one of its members, like
+  /// `const c = ns.config`, with the import and the number of properties
+  /// accessed on it.
+  aliases: HashMap<Id, (Id, usize)>,
+}
+
+impl BanReassigningImportedNamespaceMembersVisitor {
+  /// Resolves the innermost object of `expr` to an import, directly or
+  /// through an alias. Returns the import and the number of properties
+  /// accessed on it.
+  fn resolve(&self, expr: &Expr) -> Option<(&ImportedBinding, usize)> {
+    let (root, path) = member_path(expr);
+    let ident = match root {
+      Expr::Ident(ident) => ident,
+      _ => return None,
+    };
+    // Identifiers are resolved, so a local binding shadowing an import
+    // has another id
+    let id = ident.to_id();
+    if let Some(import) = self.imports.get(&id) {
+      return Some((import, path.len()));
+    }
+    let (import_id, depth) = self.aliases.get(&id)?;
+    Some((self.imports.get(import_id)?, depth + path.len()))
+  }
+
+  /// Checks a property which is written to, like the left side of an
+  /// assignment.
+  fn check_property_write(&self, span: Span, target: &Expr) {
+    if !matches!(unwrap_paren(target), Expr::Member(_)) {
+      return;
+    }
+    if let Some((import, _)) = self.resolve(target) {
+      self.report(span, import);
+    }
+  }
+
+  /// Checks an object which is mutated as a whole, like the receiver of
+  /// `push()`. A namespace object itself only has the exports as
+  /// properties, so `ns.push()` calls an exported function.
+  fn check_object_mutation(&self, span: Span, object: &Expr) {
+    match self.resolve(object) {
+      Some((import, depth)) if depth > 0 || !import.namespace => {
+        self.report(span, import);
+      }
+      _ => {}
+    }
+  }
+
+  fn report(&self, span: Span, import: &ImportedBinding) {
+    let import_location =
+      self.context.source_map.lookup_char_pos(import.span.lo());
+    self.context.add_diagnostic_with_hint(
+      span,
+      CODE,
+      &format!(
+        "Mutating `{}` changes state exported by '{}'",
+        import.name, import.source
+      ),
+      &format!(
+        "`{}` is imported at {}:{}; copy the object before changing it, or allow '{}' if its exports are meant to be mutable",
+        import.name,
+        import_location.line,
+        import_location.col.0,
+        import.source
+      ),
+    );
+  }
+}
+
+impl Visit for BanReassigningImportedNamespaceMembersVisitor {
+  noop_visit_type!();
+
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, parent: &dyn Node) {
+    if var_decl.kind == VarDeclKind::Const {
+      for decl in &var_decl.decls {
+        if let (Pat::Ident(ident), Some(init)) = (&decl.name, &decl.init) {
+          // Only one level of aliasing is followed.
+          let (root, path) = member_path(init);
+          if let Expr::Ident(root) = root {
+            let root_id = root.to_id();
+            if self.imports.contains_key(&root_id) {
+              self.aliases.insert(ident.to_id(), (root_id, path.len()));
+            }
+          }
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_var_decl(self, var_decl, parent);
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, parent: &dyn Node) {
+    let left = match &assign_expr.left {
+      PatOrExpr::Expr(expr) => Some(&**expr),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => Some(&**expr),
+        _ => None,
+      },
+    };
+    if let Some(left) = left {
+      self.check_property_write(assign_expr.span, left);
+    }
+    swc_ecmascript::visit::visit_assign_expr(self, assign_expr, parent);
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, parent: &dyn Node) {
+    self.check_property_write(update_expr.span, &update_expr.arg);
+    swc_ecmascript::visit::visit_update_expr(self, update_expr, parent);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, parent: &dyn Node) {
+    if unary_expr.op == UnaryOp::Delete {
+      self.check_property_write(unary_expr.span, &unary_expr.arg);
+    }
+    swc_ecmascript::visit::visit_unary_expr(self, unary_expr, parent);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      let (object, path) = member_path(callee);
+      if let [.., method] = path.as_slice() {
+        if path.len() == 1
+          && method == "assign"
+          && matches!(object, Expr::Ident(ident) if ident.sym == *"Object")
+        {
+          if let Some(target) = call_expr.args.first() {
+            self.check_object_mutation(call_expr.span, &target.expr);
+          }
+        } else if MUTATING_METHODS.contains(&method.as_str()) {
+          if let Expr::Member(member) = unwrap_paren(callee) {
+            if let ExprOrSuper::Expr(receiver) = &member.obj {
+              self.check_object_mutation(call_expr.span, receiver);
+            }
+          }
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn ban_reassigning_imported_namespace_members_valid() {
+    assert_lint_ok_n::<BanReassigningImportedNamespaceMembers>(vec![
+      r#"
+import * as ns from "./mod.ts";
+import { config } from "./config.ts";
+console.log(ns.config.debug, config.a.b);
+const copy = { ...config, debug: true };
+copy.debug = false;
+ns.set("key", 1);
+ns.helpers.format(config.value);
+      "#,
+      r#"
+import { config } from "./config.ts";
+function f(config) {
+  config.debug = true;
+}
+      "#,
+      r#"
+import { config } from "./config.ts";
+let current = config;
+current.debug = true;
+      "#,
+      r#"
+import type { Config } from "./config.ts";
+import defaults from "./defaults.ts";
+defaults.debug = true;
+      "#,
+    ]);
+
+    assert_lint_ok_with(
+      BanReassigningImportedNamespaceMembers::with_options(
+        BanReassigningImportedNamespaceMembersOptions {
+          allow: vec!["./state.ts".to_string()],
+        },
+      ),
+      r#"
+import * as state from "./state.ts";
+state.store.count++;
+      "#,
+    );
+  }
+
+  #[test]
+  fn ban_reassigning_imported_namespace_members_invalid() {
+    assert_lint_err_on_line_n::<BanReassigningImportedNamespaceMembers>(
+      r#"
+import * as ns from "./mod.ts";
+import { cfg, list as items } from "./config.ts";
+cfg.a.b = 1;
+ns.config.debug = true;
+ns.counter.count += 1;
+ns.counter.count++;
+--cfg.count;
+delete ns.config.debug;
+Object.assign(cfg, { debug: true });
+Object.assign(ns.config, { debug: true });
+items.push(1);
+ns.config.list.splice(0, 1);
+cfg["a"].b = 2;
+      "#,
+      vec![
+        (4, 0),
+        (5, 0),
+        (6, 0),
+        (7, 0),
+        (8, 0),
+        (9, 0),
+        (10, 0),
+        (11, 0),
+        (12, 0),
+        (13, 0),
+        (14, 0),
+      ],
+    );
+  }
+
+  #[test]
+  fn ban_reassigning_imported_namespace_members_aliases() {
+    assert_lint_err_on_line_n::<BanReassigningImportedNamespaceMembers>(
+      r#"
+import * as ns from "./mod.ts";
+const config = ns.config;
+const alias = ns;
+config.debug = true;
+alias.config.debug = true;
+config.list.push(1);
+      "#,
+      vec![(5, 0), (6, 0), (7, 0)],
+    );
+
+    // Only one level of aliasing is followed
+    assert_lint_ok::<BanReassigningImportedNamespaceMembers>(
+      r#"
+import * as ns from "./mod.ts";
+const config = ns.config;
+const nested = config.nested;
+nested.debug = true;
+      "#,
+    );
+  }
+
+  #[test]
+  fn ban_reassigning_imported_namespace_members_reexported() {
+    let diagnostics = lint(
+      BanReassigningImportedNamespaceMembers::new(),
+      r#"
+import { config as settings } from "./config.ts";
+export { settings };
+settings.debug = true;
+      "#,
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 4);
+    assert_eq!(
+      diagnostics[0].message,
+      "Mutating `settings` changes state exported by './config.ts'"
+    );
+
+    let diagnostics = lint(
+      BanReassigningImportedNamespaceMembers::new(),
+      "import * as ns from './mod.ts';\nns.a.b = 1;",
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("`ns` is imported at 1:12; copy the object before changing it, or allow './mod.ts' if its exports are meant to be mutable")
+    );
+  }
+}
@@ -5,54 +5,150 @@ use std::sync::Arc;
 use swc_common::comments::Comment;
 use swc_common::comments::CommentKind;
 
-pub struct BanTsComment;
+/// Whether a directive is allowed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DirectiveOption {
+  /// The directive is reported even with a description.
+  Ban,
+  /// The directive is allowed when followed by a description of why it is
+  /// needed, eg. `// @ts-expect-error: the types of foo are wrong`.
+  AllowWithDescription,
+  /// The directive is never reported.
+  Allow,
+}
+
+#[derive(Clone, Copy)]
+pub struct BanTsCommentOptions {
+  pub ts_expect_error: DirectiveOption,
+  pub ts_ignore: DirectiveOption,
+  pub ts_nocheck: DirectiveOption,
+}
+
+impl Default for BanTsCommentOptions {
+  fn default() -> Self {
+    Self {
+      ts_expect_error: DirectiveOption::AllowWithDescription,
+      ts_ignore: DirectiveOption::AllowWithDescription,
+      ts_nocheck: DirectiveOption::AllowWithDescription,
+    }
+  }
+}
+
+/// Reports the directives silencing the TypeScript compiler in line
+/// comments.
+pub struct BanTsComment {
+  options: BanTsCommentOptions,
+}
 
 impl BanTsComment {
+  pub fn with_options(options: BanTsCommentOptions) -> Box<Self> {
+    Box::new(BanTsComment { options })
+  }
+
   fn lint_comment(&self, context: &Context, comment: &Comment) {
     if comment.kind != CommentKind::Line {
       return;
     }
 
     lazy_static! {
-      static ref BTC_REGEX: regex::Regex =
-        regex::Regex::new(r#"^/*\s*@ts-(expect-error|ignore|nocheck)$"#)
-          .unwrap();
+      static ref BTC_REGEX: regex::Regex = regex::Regex::new(
+        r#"^/*\s*@ts-(expect-error|ignore|nocheck)(?:$|[\s:](.*))"#
+      )
+      .unwrap();
     }
 
-    if BTC_REGEX.is_match(&comment.text) {
-      context.add_diagnostic(
-        comment.span,
-        "ban-ts-comment",
-        "ts directives are not allowed",
-      );
-    }
+    let captures = match BTC_REGEX.captures(&comment.text) {
+      Some(captures) => captures,
+      None => return,
+    };
+    let directive = &captures[1];
+    let option = match directive {
+      "expect-error" => self.options.ts_expect_error,
+      "ignore" => self.options.ts_ignore,
+      _ => self.options.ts_nocheck,
+    };
+    let has_description = match captures.get(2) {
+      Some(description) => !description
+        .as_str()
+        .trim_start_matches(':')
+        .trim()
+        .is_empty(),
+      None => false,
+    };
+    let hint = match option {
+      DirectiveOption::Allow => return,
+      DirectiveOption::AllowWithDescription if has_description => return,
+      DirectiveOption::AllowWithDescription => {
+        "Fix the type error, or add a description of why the directive is needed, eg. `// @ts-expect-error: the types of foo are wrong`"
+      }
+      DirectiveOption::Ban => "Fix the type error instead",
+    };
+    context.add_diagnostic_with_hint(
+      comment.span,
+      "ban-ts-comment",
+      &format!("`@ts-{}` directive is not allowed", directive),
+      hint,
+    );
   }
 }
 
 impl LintRule for BanTsComment {
   fn new() -> Box<Self> {
-    Box::new(BanTsComment)
+    Self::with_options(BanTsCommentOptions::default())
   }
 
   fn code(&self) -> &'static str {
     "ban-ts-comment"
   }
 
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows the `@ts-expect-error`, `@ts-ignore` and `@ts-nocheck` directives in line comments.
+
+These directives silence the type checker, hiding errors instead of fixing them. By default a directive is allowed when it is followed by a description of why it is needed.
+
+### Options
+
+- `BanTsCommentOptions::ts_expect_error`: whether `@ts-expect-error` is allowed, `DirectiveOption::Ban`, `DirectiveOption::AllowWithDescription` (default) or `DirectiveOption::Allow`.
+- `BanTsCommentOptions::ts_ignore`: the same for `@ts-ignore`.
+- `BanTsCommentOptions::ts_nocheck`: the same for `@ts-nocheck`.
+
+### Valid:
+```typescript
+// just a comment containing @ts-ignore somewhere
+function foo() {}
+```
+```typescript
+// @ts-expect-error: the declared type of `a` is deliberately wrong
+let a: number = "a";
+```
+
+### Invalid:
+```typescript
+// @ts-expect-error
+let a: number = "a";
+```
+```typescript
+// @ts-nocheck
+let a: number = "a";
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     _module: &swc_ecmascript::ast::Module,
   ) {
-    context.leading_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
-    context.trailing_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
+    for comment in context.all_comments() {
+      self.lint_comment(&context, comment);
+    }
   }
 }
 
@@ -135,4 +231,64 @@ console.log('hello');
     assert_lint_err::<BanTsComment>(r#"// @ts-ignore"#, 0);
     assert_lint_err::<BanTsComment>(r#"// @ts-nocheck"#, 0);
   }
+
+  #[test]
+  fn ban_ts_comment_trailing_comments() {
+    assert_lint_err::<BanTsComment>("let a: number = 'a'; // @ts-ignore", 21);
+    assert_lint_err_on_line::<BanTsComment>(
+      "function foo() {\n  return bar; // @ts-expect-error\n}",
+      2,
+      14,
+    );
+    assert_lint_ok::<BanTsComment>(
+      "let a: number = 'a'; // @ts-ignore: deliberately wrong",
+    );
+
+    let diagnostics = lint(BanTsComment::new(), "foo(); // @ts-nocheck ");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "`@ts-nocheck` directive is not allowed"
+    );
+    assert_eq!(diagnostics[0].range.start.col, 7);
+    assert_eq!(diagnostics[0].range.end.col, 22);
+  }
+
+  #[test]
+  fn ban_ts_comment_options() {
+    let rule = |ts_expect_error: DirectiveOption| {
+      BanTsComment::with_options(BanTsCommentOptions {
+        ts_expect_error,
+        ..Default::default()
+      })
+    };
+    let with_description =
+      "// @ts-expect-error: the types of foo are wrong\nfoo(1);";
+    let without_description = "// @ts-expect-error\nfoo(1);";
+
+    assert_lint_ok_with(
+      rule(DirectiveOption::AllowWithDescription),
+      with_description,
+    );
+    assert_lint_err_on_line_n_with(
+      rule(DirectiveOption::AllowWithDescription),
+      without_description,
+      vec![(1, 0)],
+    );
+    assert_lint_ok_with(rule(DirectiveOption::Allow), without_description);
+    assert_lint_err_on_line_n_with(
+      rule(DirectiveOption::Ban),
+      with_description,
+      vec![(1, 0)],
+    );
+    assert_lint_err_on_line_n_with(
+      rule(DirectiveOption::Allow),
+      "// @ts-expect-error\n// @ts-ignore\nfoo(1);",
+      vec![(2, 0)],
+    );
+    assert_lint_ok_with(
+      rule(DirectiveOption::Ban),
+      "/* @ts-expect-error: block comments are not directives */",
+    );
+  }
 }
@@ -34,21 +34,41 @@ impl LintRule for BanTsIgnore {
     "ban-ts-ignore"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Disallows `@ts-ignore` comments.
+
+`@ts-ignore` silences every type error on the next line, hiding errors instead of fixing them.
+
+### Valid:
+```typescript
+function foo(): number {
+  return 1;
+}
+```
+
+### Invalid:
+```typescript
+// @ts-ignore
+function foo() {
+  // pass
+}
+```
+```typescript
+function bar() {
+  // @ts-ignore
+  const a = "bar";
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     _module: &swc_ecmascript::ast::Module,
   ) {
-    context.leading_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
-    context.trailing_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
+    for comment in context.all_comments() {
+      self.lint_comment(&context, comment);
+    }
   }
 }
 
@@ -17,6 +17,35 @@ impl LintRule for BanTypes {
     "ban-types"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows the use of some built-in types which are easy to misuse.
+
+The wrapper types `String`, `Boolean`, `Number` and `Symbol` should be written in lower case, `Function` accepts any function and `Object` or `{}` accept almost any value.
+
+### Valid:
+```typescript
+let a: string;
+let b: { x: number; y: number } = { x: 1, y: 1 };
+let c: () => void;
+let d: Record<string, unknown>;
+```
+
+### Invalid:
+```typescript
+let a: String;
+```
+```typescript
+let a: Function;
+```
+```typescript
+let a: Object;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -15,6 +15,32 @@ impl LintRule for BanUntaggedIgnore {
     "ban-untagged-ignore"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires `deno-lint-ignore` directives to name the rules they ignore.
+
+A directive without rule codes ignores every rule, including ones which may catch real problems in the future.
+
+### Valid:
+```typescript
+// deno-lint-ignore no-explicit-any
+function bar(a: any) {
+  // pass
+}
+```
+
+### Invalid:
+```typescript
+// deno-lint-ignore
+function foo(a: any) {
+  // pass
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -11,26 +11,30 @@ pub struct BanUntaggedTodo;
 
 impl BanUntaggedTodo {
   fn lint_comment(&self, context: &Context, comment: &Comment) {
-    if comment.kind != CommentKind::Line {
-      return;
+    lazy_static! {
+      static ref TODO_RE: Regex = Regex::new(r#"(?i)^todo\b"#).unwrap();
+      static ref TAGGED_TODO_RE: Regex =
+        Regex::new(r#"(?i)^todo\s*\((#|@)\S+\)"#).unwrap();
     }
 
-    let comment_text = comment.text.to_lowercase().trim_start().to_string();
-
-    if !comment_text.starts_with("todo") {
-      return;
-    }
-
-    let re = Regex::new(r#"todo\((#|@)\S+\)"#).unwrap();
-    if re.is_match(&comment_text) {
-      return;
+    // Each line of a block comment may start with a `*`, as in JSDoc.
+    let is_untagged = |line: &str| {
+      let line = match comment.kind {
+        CommentKind::Line => line.trim_start(),
+        CommentKind::Block => {
+          line.trim_start().trim_start_matches('*').trim_start()
+        }
+      };
+      TODO_RE.is_match(line) && !TAGGED_TODO_RE.is_match(line)
+    };
+    if comment.text.lines().any(is_untagged) {
+      context.add_diagnostic_with_hint(
+        comment.span,
+        "ban-untagged-todo",
+        "TODO should be tagged with (@username) or (#issue)",
+        "Add a user name or an issue to the TODO, eg. `TODO(@username)` or `TODO(#1234)`",
+      );
     }
-
-    context.add_diagnostic(
-      comment.span,
-      "ban-untagged-todo",
-      "TODO should be tagged with (@username) or (#issue)",
-    );
   }
 }
 
@@ -43,21 +47,48 @@ impl LintRule for BanUntaggedTodo {
     "ban-untagged-todo"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Requires TODO comments to be tagged with a user name, `(@username)`, or an issue, `(#issue)`.
+
+Untagged TODOs tend to be forgotten because nobody is responsible for them. Both line comments and the lines of block comments are checked.
+
+### Valid:
+```typescript
+// TODO(#1234)
+const b = "b";
+```
+```typescript
+// TODO(@username)
+const c = "c";
+```
+
+### Invalid:
+```typescript
+// TODO
+function foo() {
+  // pass
+}
+```
+```typescript
+// TODO(username)
+const a = "a";
+```
+```typescript
+/**
+ * TODO: handle errors
+ */
+function bar() {}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     _module: &swc_ecmascript::ast::Module,
   ) {
-    context.leading_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
-    context.trailing_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
+    for comment in context.all_comments() {
+      self.lint_comment(&context, comment);
+    }
   }
 }
 
@@ -97,4 +128,36 @@ const a = "a";
       0,
     );
   }
+
+  #[test]
+  fn ban_untagged_todo_block_comments() {
+    assert_lint_ok_n::<BanUntaggedTodo>(vec![
+      "/* TODO(@someusername) */",
+      "/**\n * Does foo.\n *\n * TODO(#1234): support bar\n */\nfunction foo() {}",
+      "/* Lists the todos of a user */",
+      "// todos are listed elsewhere",
+    ]);
+    assert_lint_err::<BanUntaggedTodo>("/* TODO: support bar */", 0);
+    assert_lint_err_on_line::<BanUntaggedTodo>(
+      "/**\n * Does foo.\n *\n * TODO: support bar\n */\nfunction foo() {}",
+      1,
+      0,
+    );
+  }
+
+  #[test]
+  fn ban_untagged_todo_trailing_comments() {
+    assert_lint_ok::<BanUntaggedTodo>("foo(); // TODO(@someusername)");
+    assert_lint_err::<BanUntaggedTodo>("foo(); // TODO", 7);
+    assert_lint_err_on_line::<BanUntaggedTodo>(
+      "function foo() {\n  return 1; // todo: compute it\n}",
+      2,
+      12,
+    );
+
+    let diagnostics = lint(BanUntaggedTodo::new(), "foo(); // TODO fix");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 7);
+    assert_eq!(diagnostics[0].range.end.col, 18);
+  }
 }
@@ -0,0 +1,479 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use regex::Regex;
+use std::sync::Arc;
+use swc_ecmascript::ast::{
+  ArrowExpr, CatchClause, ClassDecl, ClassExpr, ClassMethod, ClassProp, Expr,
+  FnDecl, FnExpr, Ident, ImportDefaultSpecifier, ImportNamedSpecifier,
+  ImportStarAsSpecifier, Module, ObjectPatProp, Param, Pat, PrivateMethod,
+  PrivateProp, Prop, PropName, SetterProp, TsParamProp, TsParamPropParam,
+  VarDeclarator,
+};
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Default)]
+pub struct CamelcaseOptions {
+  /// Doesn't report the local names of imports, which often come from
+  /// modules following another convention.
+  pub ignore_imports: bool,
+  /// Doesn't report bindings destructured under the name of their
+  /// property, like `const { user_id } = row;`.
+  pub ignore_destructuring: bool,
+  /// Names matching any of the patterns are not reported.
+  pub allow: Vec<Regex>,
+}
+
+/// Reports snake_case names where they are introduced: declarations,
+/// parameters, imports, class members and object literal keys. Names used
+/// as they are defined elsewhere, like `response.user_name`, are never
+/// reported.
+pub struct Camelcase {
+  options: CamelcaseOptions,
+}
+
+impl Camelcase {
+  pub fn with_options(options: CamelcaseOptions) -> Box<Self> {
+    Box::new(Camelcase { options })
+  }
+}
+
+impl LintRule for Camelcase {
+  fn new() -> Box<Self> {
+    Self::with_options(CamelcaseOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "camelcase"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires names introduced in the code to be in camelCase.
+
+Variables, functions, parameters, classes, class members, imports and object literal keys are checked. Leading and trailing underscores are ignored, and so are ALL_CAPS constants like `MAX_SIZE`. Properties of other objects, like `response.user_name`, are never reported since their name isn't chosen by the code using them.
+
+### Options
+
+- `CamelcaseOptions::ignore_imports`: doesn't report the local names of imports, `false` by default.
+- `CamelcaseOptions::ignore_destructuring`: doesn't report bindings destructured under the name of their property, like `const { user_id } = row;`, `false` by default.
+- `CamelcaseOptions::allow`: names matching any of the patterns are not reported, empty by default.
+
+### Valid:
+```typescript
+const userName = response.user_name;
+const MAX_SIZE = 10;
+const { user_id: userId } = row;
+function _internal(__proto__: unknown) {}
+```
+
+### Invalid:
+```typescript
+const user_name = "deno";
+```
+```typescript
+function get_user(userId: string) {}
+```
+```typescript
+class User {
+  #first_name = "";
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = CamelcaseVisitor {
+      context,
+      options: &self.options,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+/// Checks if `name` contains an underscore between other characters,
+/// unless it's in ALL_CAPS.
+fn is_underscored(name: &str) -> bool {
+  let name = name.trim_matches('_');
+  name.contains('_') && name != name.to_uppercase()
+}
+
+/// Returns `name` in camelCase, keeping its leading and trailing
+/// underscores.
+fn to_camel_case(name: &str) -> String {
+  let trimmed = name.trim_matches('_');
+  let leading = &name[..name.len() - name.trim_start_matches('_').len()];
+  let trailing = &name[name.trim_end_matches('_').len()..];
+  let mut camel = String::from(leading);
+  for (i, word) in trimmed.split('_').filter(|w| !w.is_empty()).enumerate() {
+    let mut chars = word.chars();
+    if i > 0 {
+      camel.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+    }
+    camel.extend(chars);
+  }
+  camel.push_str(trailing);
+  camel
+}
+
+struct CamelcaseVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a CamelcaseOptions,
+}
+
+impl<'a> CamelcaseVisitor<'a> {
+  fn check_ident(&self, ident: &Ident) {
+    let name = ident.sym.as_ref();
+    if !is_underscored(name)
+      || self.options.allow.iter().any(|re| re.is_match(name))
+    {
+      return;
+    }
+    self.context.add_diagnostic_with_hint(
+      ident.span,
+      "camelcase",
+      &format!("Identifier '{}' is not in camel case", name),
+      &format!("Consider renaming it to `{}`", to_camel_case(name)),
+    );
+  }
+
+  fn check_prop_name(&self, prop_name: &PropName) {
+    if let PropName::Ident(ident) = prop_name {
+      self.check_ident(ident);
+    }
+  }
+
+  /// Checks the bindings introduced by `pat`.
+  fn check_pat(&self, pat: &Pat) {
+    match pat {
+      Pat::Ident(ident) => self.check_ident(ident),
+      Pat::Array(array_pat) => {
+        for elem in array_pat.elems.iter().flatten() {
+          self.check_pat(elem);
+        }
+      }
+      Pat::Object(object_pat) => {
+        for prop in &object_pat.props {
+          match prop {
+            ObjectPatProp::KeyValue(key_value) => {
+              let same_name = match (&key_value.key, &*key_value.value) {
+                (PropName::Ident(key), Pat::Ident(value)) => {
+                  key.sym == value.sym
+                }
+                _ => false,
+              };
+              if !(same_name && self.options.ignore_destructuring) {
+                self.check_pat(&key_value.value);
+              }
+            }
+            ObjectPatProp::Assign(assign) => {
+              if !self.options.ignore_destructuring {
+                self.check_ident(&assign.key);
+              }
+            }
+            ObjectPatProp::Rest(rest) => self.check_pat(&rest.arg),
+          }
+        }
+      }
+      Pat::Rest(rest_pat) => self.check_pat(&rest_pat.arg),
+      Pat::Assign(assign_pat) => self.check_pat(&assign_pat.left),
+      Pat::Invalid(_) | Pat::Expr(_) => {}
+    }
+  }
+}
+
+impl<'a> Visit for CamelcaseVisitor<'a> {
+  fn visit_var_declarator(
+    &mut self,
+    var_declarator: &VarDeclarator,
+    parent: &dyn Node,
+  ) {
+    self.check_pat(&var_declarator.name);
+    swc_ecmascript::visit::visit_var_declarator(self, var_declarator, parent);
+  }
+
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, parent: &dyn Node) {
+    self.check_ident(&fn_decl.ident);
+    swc_ecmascript::visit::visit_fn_decl(self, fn_decl, parent);
+  }
+
+  fn visit_fn_expr(&mut self, fn_expr: &FnExpr, parent: &dyn Node) {
+    if let Some(ident) = &fn_expr.ident {
+      self.check_ident(ident);
+    }
+    swc_ecmascript::visit::visit_fn_expr(self, fn_expr, parent);
+  }
+
+  fn visit_class_decl(&mut self, class_decl: &ClassDecl, parent: &dyn Node) {
+    self.check_ident(&class_decl.ident);
+    swc_ecmascript::visit::visit_class_decl(self, class_decl, parent);
+  }
+
+  fn visit_class_expr(&mut self, class_expr: &ClassExpr, parent: &dyn Node) {
+    if let Some(ident) = &class_expr.ident {
+      self.check_ident(ident);
+    }
+    swc_ecmascript::visit::visit_class_expr(self, class_expr, parent);
+  }
+
+  fn visit_param(&mut self, param: &Param, parent: &dyn Node) {
+    self.check_pat(&param.pat);
+    swc_ecmascript::visit::visit_param(self, param, parent);
+  }
+
+  fn visit_ts_param_prop(
+    &mut self,
+    param_prop: &TsParamProp,
+    parent: &dyn Node,
+  ) {
+    match &param_prop.param {
+      TsParamPropParam::Ident(ident) => self.check_ident(ident),
+      TsParamPropParam::Assign(assign_pat) => self.check_pat(&assign_pat.left),
+    }
+    swc_ecmascript::visit::visit_ts_param_prop(self, param_prop, parent);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+    for param in &arrow_expr.params {
+      self.check_pat(param);
+    }
+    swc_ecmascript::visit::visit_arrow_expr(self, arrow_expr, parent);
+  }
+
+  fn visit_setter_prop(&mut self, setter_prop: &SetterProp, parent: &dyn Node) {
+    self.check_pat(&setter_prop.param);
+    swc_ecmascript::visit::visit_setter_prop(self, setter_prop, parent);
+  }
+
+  fn visit_catch_clause(
+    &mut self,
+    catch_clause: &CatchClause,
+    parent: &dyn Node,
+  ) {
+    if let Some(param) = &catch_clause.param {
+      self.check_pat(param);
+    }
+    swc_ecmascript::visit::visit_catch_clause(self, catch_clause, parent);
+  }
+
+  fn visit_import_named_specifier(
+    &mut self,
+    specifier: &ImportNamedSpecifier,
+    _parent: &dyn Node,
+  ) {
+    if !self.options.ignore_imports {
+      self.check_ident(&specifier.local);
+    }
+  }
+
+  fn visit_import_default_specifier(
+    &mut self,
+    specifier: &ImportDefaultSpecifier,
+    _parent: &dyn Node,
+  ) {
+    if !self.options.ignore_imports {
+      self.check_ident(&specifier.local);
+    }
+  }
+
+  fn visit_import_star_as_specifier(
+    &mut self,
+    specifier: &ImportStarAsSpecifier,
+    _parent: &dyn Node,
+  ) {
+    if !self.options.ignore_imports {
+      self.check_ident(&specifier.local);
+    }
+  }
+
+  fn visit_class_method(
+    &mut self,
+    class_method: &ClassMethod,
+    parent: &dyn Node,
+  ) {
+    self.check_prop_name(&class_method.key);
+    swc_ecmascript::visit::visit_class_method(self, class_method, parent);
+  }
+
+  fn visit_private_method(
+    &mut self,
+    private_method: &PrivateMethod,
+    parent: &dyn Node,
+  ) {
+    self.check_ident(&private_method.key.id);
+    swc_ecmascript::visit::visit_private_method(self, private_method, parent);
+  }
+
+  fn visit_class_prop(&mut self, class_prop: &ClassProp, parent: &dyn Node) {
+    if let (Expr::Ident(ident), false) = (&*class_prop.key, class_prop.computed)
+    {
+      self.check_ident(ident);
+    }
+    swc_ecmascript::visit::visit_class_prop(self, class_prop, parent);
+  }
+
+  fn visit_private_prop(
+    &mut self,
+    private_prop: &PrivateProp,
+    parent: &dyn Node,
+  ) {
+    self.check_ident(&private_prop.key.id);
+    swc_ecmascript::visit::visit_private_prop(self, private_prop, parent);
+  }
+
+  fn visit_prop(&mut self, prop: &Prop, parent: &dyn Node) {
+    // Shorthand properties use a name introduced elsewhere
+    match prop {
+      Prop::KeyValue(key_value) => self.check_prop_name(&key_value.key),
+      Prop::Method(method) => self.check_prop_name(&method.key),
+      Prop::Getter(getter) => self.check_prop_name(&getter.key),
+      Prop::Setter(setter) => self.check_prop_name(&setter.key),
+      Prop::Shorthand(_) | Prop::Assign(_) => {}
+    }
+    swc_ecmascript::visit::visit_prop(self, prop, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn camelcase_valid() {
+    assert_lint_ok_n::<Camelcase>(vec![
+      "const userName = 'deno';",
+      "const MAX_SIZE = 10;",
+      "const _private = 1, trailing_ = 2, __proto__ = 3;",
+      "const a = response.user_name;",
+      "response.user_name = 'deno';",
+      "foo_bar();",
+      "const { user_name: userName } = response;",
+      "const [first, second] = pairs;",
+      "function getUser(userId, ...restArgs) {}",
+      "const fn = (userId) => userId;",
+      "class UserStore { userName = ''; #lastName = ''; getUser() {} }",
+      "import { fooBar } from './mod.ts';",
+      "import { foo_bar as fooBar } from './mod.ts';",
+      "const a = { 'user_name': 1, [user_name]: 2 };",
+      "const a = { user_name };",
+      "try {} catch (fooError) {}",
+      "export { foo_bar } from './mod.ts';",
+      "type user_name = string;",
+    ]);
+  }
+
+  #[test]
+  fn camelcase_invalid() {
+    assert_lint_err::<Camelcase>("const user_name = 'deno';", 6);
+    assert_lint_err::<Camelcase>("let first_name, lastName;", 4);
+    assert_lint_err::<Camelcase>("function get_user() {}", 9);
+    assert_lint_err::<Camelcase>("const a = function get_user() {};", 19);
+    assert_lint_err::<Camelcase>("function getUser(user_id) {}", 17);
+    assert_lint_err::<Camelcase>("function getUser(user_id = 1) {}", 17);
+    assert_lint_err::<Camelcase>("function getUser(...rest_args) {}", 20);
+    assert_lint_err::<Camelcase>("const fn = (user_id) => user_id;", 12);
+    assert_lint_err::<Camelcase>("class user_store {}", 6);
+    assert_lint_err::<Camelcase>("try {} catch (foo_error) {}", 14);
+    assert_lint_err::<Camelcase>("import { foo_bar } from './mod.ts';", 9);
+    assert_lint_err::<Camelcase>(
+      "import { fooBar as foo_bar } from './mod.ts';",
+      19,
+    );
+    assert_lint_err::<Camelcase>("import foo_bar from './mod.ts';", 7);
+    assert_lint_err::<Camelcase>("import * as foo_bar from './mod.ts';", 12);
+    assert_lint_err::<Camelcase>("const a = { user_name: 1 };", 12);
+    assert_lint_err::<Camelcase>("const a = { get_user() {} };", 12);
+  }
+
+  #[test]
+  fn camelcase_class_members() {
+    assert_lint_err_on_line_n::<Camelcase>(
+      r#"
+class User {
+  first_name = "";
+  #last_name = "";
+  get_name() {}
+  #format_name() {}
+  constructor(private user_id: string) {}
+}"#,
+      vec![(3, 2), (4, 3), (5, 2), (6, 3), (7, 22)],
+    );
+  }
+
+  #[test]
+  fn camelcase_destructuring() {
+    assert_lint_err::<Camelcase>("const { user_name } = response;", 8);
+    assert_lint_err::<Camelcase>("const { user_name = '' } = response;", 8);
+    assert_lint_err::<Camelcase>(
+      "const { userName: user_name } = response;",
+      18,
+    );
+    assert_lint_err::<Camelcase>("const [first_item] = items;", 7);
+    assert_lint_err::<Camelcase>("const { a: { b_c } } = d;", 13);
+    assert_lint_err::<Camelcase>("const { ...rest_props } = props;", 11);
+    assert_lint_err::<Camelcase>("function f({ user_id }) {}", 13);
+
+    let ignore_destructuring = || {
+      Camelcase::with_options(CamelcaseOptions {
+        ignore_destructuring: true,
+        ..Default::default()
+      })
+    };
+    assert_lint_ok_with(
+      ignore_destructuring(),
+      "const { user_name, user_id = 1 } = response;",
+    );
+    assert_lint_ok_with(
+      ignore_destructuring(),
+      "const { user_name: user_name } = response;",
+    );
+    assert_lint_err_on_line_n_with(
+      ignore_destructuring(),
+      "const { userName: user_name } = response;",
+      vec![(1, 18)],
+    );
+  }
+
+  #[test]
+  fn camelcase_options() {
+    let ignore_imports = Camelcase::with_options(CamelcaseOptions {
+      ignore_imports: true,
+      ..Default::default()
+    });
+    assert_lint_ok_with(
+      ignore_imports,
+      "import foo_bar, { baz_qux } from './mod.ts';",
+    );
+
+    let allow = || {
+      Camelcase::with_options(CamelcaseOptions {
+        allow: vec![Regex::new("^UNSAFE_").unwrap()],
+        ..Default::default()
+      })
+    };
+    assert_lint_ok_with(allow(), "function UNSAFE_render() {}");
+    assert_lint_err_on_line_n_with(
+      allow(),
+      "function render_UNSAFE() {}",
+      vec![(1, 9)],
+    );
+  }
+
+  #[test]
+  fn camelcase_message() {
+    let diagnostics = lint(Camelcase::new(), "const __my_var_name_ = 1;");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Identifier '__my_var_name_' is not in camel case"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Consider renaming it to `__myVarName_`")
+    );
+  }
+}
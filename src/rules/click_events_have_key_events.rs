@@ -0,0 +1,217 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::jsx_util::{
+  attr_str_value, dom_element_name, find_attr, has_spread,
+  is_interactive_element, is_interactive_role,
+};
+use std::sync::Arc;
+use swc_ecmascript::ast::JSXOpeningElement;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+static KEYBOARD_HANDLERS: &[&str] = &["onKeyDown", "onKeyUp", "onKeyPress"];
+
+pub struct ClickEventsHaveKeyEventsOptions {
+  /// Mouse handlers that require a keyboard handler next to them.
+  pub handlers: Vec<String>,
+}
+
+impl Default for ClickEventsHaveKeyEventsOptions {
+  fn default() -> Self {
+    Self {
+      handlers: vec!["onClick".to_string()],
+    }
+  }
+}
+
+pub struct ClickEventsHaveKeyEvents {
+  options: ClickEventsHaveKeyEventsOptions,
+}
+
+impl ClickEventsHaveKeyEvents {
+  pub fn with_options(options: ClickEventsHaveKeyEventsOptions) -> Box<Self> {
+    Box::new(ClickEventsHaveKeyEvents { options })
+  }
+}
+
+impl LintRule for ClickEventsHaveKeyEvents {
+  fn new() -> Box<Self> {
+    Self::with_options(ClickEventsHaveKeyEventsOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "click-events-have-key-events"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires JSX elements with an `onClick` handler to also handle keyboard events with `onKeyDown`, `onKeyUp` or `onKeyPress`.
+
+Users who can't use a mouse need a keyboard equivalent of click handlers. Interactive elements like `<button>`, `<a>` and `<input>`, components, elements with spread props and elements with `role="presentation"` or `role="button"` are not checked.
+
+### Options
+
+`ClickEventsHaveKeyEventsOptions::handlers`: the mouse handlers which require a keyboard handler, `["onClick"]` by default.
+
+### Valid:
+```tsx
+<div onClick={a} onKeyDown={b} />
+```
+```tsx
+<button onClick={a} />
+```
+
+### Invalid:
+```tsx
+<div onClick={a} />
+```
+```tsx
+<li onClick={a} />
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor =
+      ClickEventsHaveKeyEventsVisitor::new(context, &self.options.handlers);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct ClickEventsHaveKeyEventsVisitor<'a> {
+  context: Arc<Context>,
+  handlers: &'a [String],
+}
+
+impl<'a> ClickEventsHaveKeyEventsVisitor<'a> {
+  fn new(context: Arc<Context>, handlers: &'a [String]) -> Self {
+    Self { context, handlers }
+  }
+
+  fn check(&self, opening: &JSXOpeningElement) {
+    // Components can forward the handler to anything.
+    let tag = match dom_element_name(&opening.name) {
+      Some(tag) => tag,
+      None => return,
+    };
+    // A spread might provide the keyboard handler.
+    if is_interactive_element(tag) || has_spread(opening) {
+      return;
+    }
+
+    let handler = match self
+      .handlers
+      .iter()
+      .find(|handler| find_attr(opening, handler).is_some())
+    {
+      Some(handler) => handler,
+      None => return,
+    };
+    if KEYBOARD_HANDLERS
+      .iter()
+      .any(|handler| find_attr(opening, handler).is_some())
+    {
+      return;
+    }
+    let role = find_attr(opening, "role").and_then(attr_str_value);
+    if let Some(role) = role {
+      if is_interactive_role(role.trim()) {
+        return;
+      }
+    }
+
+    self.context.add_diagnostic_with_hint(
+      opening.span,
+      "click-events-have-key-events",
+      &format!(
+        "`<{}>` with `{}` must also handle keyboard events",
+        tag, handler
+      ),
+      "Add an `onKeyDown`, `onKeyUp` or `onKeyPress` handler, or use a `<button>` instead",
+    );
+  }
+}
+
+impl<'a> Visit for ClickEventsHaveKeyEventsVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_jsx_opening_element(
+    &mut self,
+    opening: &JSXOpeningElement,
+    parent: &dyn Node,
+  ) {
+    self.check(opening);
+    swc_ecmascript::visit::visit_jsx_opening_element(self, opening, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn click_events_have_key_events_valid() {
+    for source in &[
+      "<div onClick={a} onKeyDown={b} />",
+      "<div onClick={a} onKeyUp={b} />",
+      "<div onClick={a} onKeyPress={b} />",
+      "<div role='button' onClick={a} onKeyDown={b} />",
+      "<div role='button' onClick={a} />",
+      "<div onClick={a} {...props} />",
+      "<button onClick={a} />",
+      "<a href='#' onClick={a} />",
+      "<input onClick={a} />",
+      "<Button onClick={a} />",
+      "<ui.Card onClick={a} />",
+      "<div onMouseDown={a} />",
+      "<div />",
+    ] {
+      assert_lint_ok_tsx(ClickEventsHaveKeyEvents::new(), source);
+    }
+  }
+
+  #[test]
+  fn click_events_have_key_events_invalid() {
+    let rule = ClickEventsHaveKeyEvents::new;
+    assert_lint_err_on_line_n_tsx(rule(), "<div onClick={a} />", vec![(1, 0)]);
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<span role='presentation' onClick={a}>x</span>",
+      vec![(1, 0)],
+    );
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<section onClick={a}><div onclick={b} /></section>",
+      vec![(1, 0), (1, 21)],
+    );
+    let diagnostics = lint_tsx(rule(), "<li onClick={a} />");
+    assert_eq!(
+      diagnostics[0].message,
+      "`<li>` with `onClick` must also handle keyboard events"
+    );
+  }
+
+  #[test]
+  fn click_events_have_key_events_handlers() {
+    let rule = || {
+      ClickEventsHaveKeyEvents::with_options(ClickEventsHaveKeyEventsOptions {
+        handlers: vec!["onClick".to_string(), "onMouseDown".to_string()],
+      })
+    };
+    assert_lint_ok_tsx(rule(), "<div onMouseDown={a} onKeyDown={b} />");
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<div onMouseDown={a} />",
+      vec![(1, 0)],
+    );
+  }
+}
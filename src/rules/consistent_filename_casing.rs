@@ -0,0 +1,443 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::sync::Arc;
+use swc_ecmascript::ast::{
+  DefaultDecl, ExportSpecifier, Expr, Module, ModuleDecl, ModuleItem,
+};
+
+/// Files which are named after their directory, so their name doesn't have
+/// to follow the convention or match their default export.
+static INDEX_FILES: &[&str] = &["mod", "index"];
+
+static TEST_SUFFIXES: &[&str] = &["_test", "-test", "_bench", "-bench"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilenameCase {
+  /// `copy_file.ts`, the style of the Deno standard library.
+  SnakeCase,
+  /// `copy-file.ts`
+  KebabCase,
+  /// `copyFile.ts`
+  CamelCase,
+}
+
+impl FilenameCase {
+  fn describe(self) -> &'static str {
+    match self {
+      FilenameCase::SnakeCase => "snake_case",
+      FilenameCase::KebabCase => "kebab-case",
+      FilenameCase::CamelCase => "camelCase",
+    }
+  }
+
+  fn is_valid(self, name: &str) -> bool {
+    let is_lower_word = |word: &str| {
+      !word.is_empty()
+        && word
+          .chars()
+          .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    };
+    match self {
+      FilenameCase::SnakeCase => name.split('_').all(is_lower_word),
+      FilenameCase::KebabCase => name.split('-').all(is_lower_word),
+      FilenameCase::CamelCase => {
+        name.starts_with(|c: char| c.is_ascii_lowercase())
+          && name.chars().all(|c| c.is_ascii_alphanumeric())
+      }
+    }
+  }
+
+  fn join(self, words: &[String]) -> String {
+    match self {
+      FilenameCase::SnakeCase => words.join("_"),
+      FilenameCase::KebabCase => words.join("-"),
+      FilenameCase::CamelCase => words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+          let mut chars = word.chars();
+          match chars.next() {
+            Some(first) if i > 0 => {
+              first.to_ascii_uppercase().to_string() + chars.as_str()
+            }
+            _ => word.to_string(),
+          }
+        })
+        .collect(),
+    }
+  }
+}
+
+pub struct ConsistentFilenameCasingOptions {
+  pub case: FilenameCase,
+  /// Requires the name of the default export, if the file has one, to
+  /// consist of the same words as the filename.
+  pub match_default_export: bool,
+}
+
+impl Default for ConsistentFilenameCasingOptions {
+  fn default() -> Self {
+    Self {
+      case: FilenameCase::SnakeCase,
+      match_default_export: true,
+    }
+  }
+}
+
+/// Reports filenames which don't follow the configured case, and files
+/// whose default export is named differently than the file, eg.
+/// `export default class FileServer` in `server.ts`.
+///
+/// Extensions, including `.d.ts` and `.test.ts`, and test suffixes like
+/// `_test` are ignored, as are leading underscores of private modules.
+/// `mod.ts` and `index.ts` are always allowed.
+pub struct ConsistentFilenameCasing {
+  options: ConsistentFilenameCasingOptions,
+}
+
+impl ConsistentFilenameCasing {
+  pub fn with_options(options: ConsistentFilenameCasingOptions) -> Box<Self> {
+    Box::new(ConsistentFilenameCasing { options })
+  }
+}
+
+impl LintRule for ConsistentFilenameCasing {
+  fn new() -> Box<Self> {
+    Self::with_options(ConsistentFilenameCasingOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "consistent-filename-casing"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires filenames to follow one case style, and to match the name of the module's default export.
+
+Extensions, including `.d.ts` and `.test.ts`, test suffixes like `_test` and leading underscores of private modules are ignored. `mod.ts` and `index.ts` are always allowed.
+
+### Options
+
+- `ConsistentFilenameCasingOptions::case`: `FilenameCase::SnakeCase` (default), `FilenameCase::KebabCase` or `FilenameCase::CamelCase`.
+- `ConsistentFilenameCasingOptions::match_default_export`: requires the default export to be named with the same words as the file, `true` by default.
+
+### Valid file names:
+```text
+copy_file.ts
+_util.ts
+file_server_test.ts
+```
+
+### Invalid file names:
+```text
+copyFile.ts
+copy-file.ts
+```
+
+A file `server.ts` with `export default class FileServer {}` is also reported."#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let (prefix, name, extension) = match file_stem(&context.file_name) {
+      Some(stem) => stem,
+      None => return,
+    };
+    if INDEX_FILES.contains(&name) {
+      return;
+    }
+
+    let case = self.options.case;
+    if !case.is_valid(name) {
+      context.add_file_diagnostic_with_hint(
+        "consistent-filename-casing",
+        &format!("Filename `{}` is not in {}", name, case.describe()),
+        &format!(
+          "Rename the file to `{}{}{}`",
+          prefix,
+          case.join(&words(name)),
+          extension
+        ),
+      );
+      return;
+    }
+
+    if !self.options.match_default_export {
+      return;
+    }
+    if let Some(export) = default_export_name(module) {
+      let export_words = words(&export);
+      if export_words != words(name) {
+        context.add_file_diagnostic_with_hint(
+          "consistent-filename-casing",
+          &format!(
+            "Filename `{}` doesn't match the default export `{}`",
+            name, export
+          ),
+          &format!(
+            "Rename the file to `{}{}{}`, or the default export to match the filename",
+            prefix,
+            case.join(&export_words),
+            extension
+          ),
+        );
+      }
+    }
+  }
+}
+
+/// Splits the basename of a path or URL into the leading underscores of
+/// private modules, the name to check and the rest, which starts with the
+/// extension.
+fn file_stem(file_name: &str) -> Option<(&str, &str, &str)> {
+  let path = file_name.split(&['?', '#'][..]).next()?;
+  let basename = path.rsplit(&['/', '\\'][..]).next()?;
+  let unprefixed = basename.trim_start_matches('_');
+  let prefix = &basename[..basename.len() - unprefixed.len()];
+  let mut name = match unprefixed.find('.') {
+    Some(dot) => &unprefixed[..dot],
+    None => unprefixed,
+  };
+  for suffix in TEST_SUFFIXES {
+    if name.len() > suffix.len() && name.ends_with(suffix) {
+      name = &name[..name.len() - suffix.len()];
+    }
+  }
+  if name.is_empty() {
+    return None;
+  }
+  Some((prefix, name, &unprefixed[name.len()..]))
+}
+
+/// Splits a name in any case into lowercase words: `copyFile`,
+/// `copy_file`, `CopyFile` and `copy-file` all result in `["copy", "file"]`.
+fn words(name: &str) -> Vec<String> {
+  let mut words = vec![];
+  let mut current = String::new();
+  let chars: Vec<char> = name.chars().collect();
+  for (i, &c) in chars.iter().enumerate() {
+    if !c.is_alphanumeric() {
+      if !current.is_empty() {
+        words.push(std::mem::take(&mut current));
+      }
+      continue;
+    }
+    let starts_word = c.is_uppercase()
+      && i > 0
+      && (chars[i - 1].is_lowercase()
+        || chars[i - 1].is_ascii_digit()
+        || (chars[i - 1].is_uppercase()
+          && matches!(chars.get(i + 1), Some(next) if next.is_lowercase())));
+    if starts_word && !current.is_empty() {
+      words.push(std::mem::take(&mut current));
+    }
+    current.extend(c.to_lowercase());
+  }
+  if !current.is_empty() {
+    words.push(current);
+  }
+  words
+}
+
+/// Returns the local name of the default export, if it has one.
+fn default_export_name(module: &Module) -> Option<String> {
+  module.body.iter().find_map(|item| {
+    let module_decl = match item {
+      ModuleItem::ModuleDecl(module_decl) => module_decl,
+      ModuleItem::Stmt(_) => return None,
+    };
+    match module_decl {
+      ModuleDecl::ExportDefaultDecl(default_decl) => match &default_decl.decl {
+        DefaultDecl::Class(class_expr) => class_expr.ident.as_ref(),
+        DefaultDecl::Fn(fn_expr) => fn_expr.ident.as_ref(),
+        DefaultDecl::TsInterfaceDecl(interface) => Some(&interface.id),
+      }
+      .map(|ident| ident.sym.to_string()),
+      ModuleDecl::ExportDefaultExpr(default_expr) => {
+        match &*default_expr.expr {
+          Expr::Ident(ident) => Some(ident.sym.to_string()),
+          _ => None,
+        }
+      }
+      ModuleDecl::ExportNamed(named_export) if named_export.src.is_none() => {
+        named_export
+          .specifiers
+          .iter()
+          .find_map(|specifier| match specifier {
+            ExportSpecifier::Named(named) => match &named.exported {
+              Some(exported) if exported.sym == *"default" => {
+                Some(named.orig.sym.to_string())
+              }
+              _ => None,
+            },
+            _ => None,
+          })
+      }
+      _ => None,
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn with_case(case: FilenameCase) -> Box<ConsistentFilenameCasing> {
+    ConsistentFilenameCasing::with_options(ConsistentFilenameCasingOptions {
+      case,
+      ..Default::default()
+    })
+  }
+
+  #[test]
+  fn consistent_filename_casing_valid() {
+    let files = vec![
+      "copy_file.ts",
+      "/project/fs/copy_file.ts",
+      "C:\\project\\fs\\copy_file.ts",
+      "https://deno.land/std/fs/copy_file.ts",
+      "https://deno.land/std/fs/copy_file.ts?v=1#top",
+      "file:///project/http/file_server.d.ts",
+      "base64.ts",
+      "copy_file_test.ts",
+      "copy_file.test.ts",
+      "_util.ts",
+      "mod.ts",
+      "/project/Components/index.tsx",
+    ];
+    for file_name in files {
+      let diagnostics =
+        lint_as(ConsistentFilenameCasing::new(), file_name, "const a = 1;");
+      assert!(diagnostics.is_empty(), "{}", file_name);
+    }
+
+    let defaults = vec![
+      ("file_server.ts", "export default class FileServer {}"),
+      ("file_server.ts", "export default function fileServer() {}"),
+      (
+        "file_server.ts",
+        "const fileServer = 1;\nexport default fileServer;",
+      ),
+      (
+        "file_server.ts",
+        "const a = 1;\nexport { a as fileServer };",
+      ),
+      ("file_server.ts", "export default function () {}"),
+      ("file_server.ts", "export default 1;"),
+      ("http_server.ts", "export default class HTTPServer {}"),
+      ("mod.ts", "export default class Server {}"),
+    ];
+    for (file_name, source) in defaults {
+      let diagnostics =
+        lint_as(ConsistentFilenameCasing::new(), file_name, source);
+      assert!(diagnostics.is_empty(), "{}", source);
+    }
+
+    assert!(lint_as(
+      with_case(FilenameCase::KebabCase),
+      "/project/copy-file.ts",
+      "const a = 1;"
+    )
+    .is_empty());
+    assert!(lint_as(
+      with_case(FilenameCase::CamelCase),
+      "/project/copyFile.ts",
+      "export default function copyFile() {}"
+    )
+    .is_empty());
+  }
+
+  #[test]
+  fn consistent_filename_casing_invalid() {
+    let cases = vec![
+      (
+        "/project/fs/copyFile.ts",
+        "Filename `copyFile` is not in snake_case",
+        "Rename the file to `copy_file.ts`",
+      ),
+      (
+        "https://deno.land/x/mod/Copy-File.d.ts",
+        "Filename `Copy-File` is not in snake_case",
+        "Rename the file to `copy_file.d.ts`",
+      ),
+      (
+        "/project/fs/copyFile_test.ts",
+        "Filename `copyFile` is not in snake_case",
+        "Rename the file to `copy_file_test.ts`",
+      ),
+      (
+        "/project/fs/_copyFile.ts",
+        "Filename `copyFile` is not in snake_case",
+        "Rename the file to `_copy_file.ts`",
+      ),
+    ];
+    for (file_name, message, hint) in cases {
+      let diagnostics =
+        lint_as(ConsistentFilenameCasing::new(), file_name, "const a = 1;");
+      assert_eq!(diagnostics.len(), 1, "{}", file_name);
+      assert_eq!(diagnostics[0].message, message);
+      assert_eq!(diagnostics[0].hint.as_deref(), Some(hint));
+    }
+
+    let diagnostics = lint_as(
+      with_case(FilenameCase::KebabCase),
+      "/project/copy_file.ts",
+      "const a = 1;",
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Rename the file to `copy-file.ts`")
+    );
+
+    let diagnostics = lint_as(
+      ConsistentFilenameCasing::new(),
+      "/project/server.ts",
+      "export default class FileServer {}",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Filename `server` doesn't match the default export `FileServer`"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Rename the file to `file_server.ts`, or the default export to match the filename")
+    );
+
+    let rule =
+      ConsistentFilenameCasing::with_options(ConsistentFilenameCasingOptions {
+        match_default_export: false,
+        ..Default::default()
+      });
+    assert!(lint_as(
+      rule,
+      "/project/server.ts",
+      "export default class FileServer {}"
+    )
+    .is_empty());
+  }
+
+  #[test]
+  fn consistent_filename_casing_file_level() {
+    // Empty files aren't linted.
+    assert!(
+      lint_as(ConsistentFilenameCasing::new(), "copyFile.ts", "").is_empty()
+    );
+
+    // Reported once for the whole file, even if it has a lot of code.
+    let diagnostics = lint_as(
+      ConsistentFilenameCasing::new(),
+      "/project/copyFile.ts",
+      "\n\nexport function copyFile() {}\nexport default copyFile;\n",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].is_file_level());
+    assert_eq!(diagnostics[0].range.start.line, 1);
+    assert_eq!(diagnostics[0].range.start.col, 0);
+    assert_eq!(diagnostics[0].range.start, diagnostics[0].range.end);
+  }
+}
@@ -0,0 +1,338 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::DropSpan;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ClassProp, Expr, NewExpr, Pat, TsEntityName, TsType, TsTypeAnn,
+  TsTypeParamInstantiation, TsTypeRef, VarDeclarator,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConsistentGenericConstructorsMode {
+  /// Type arguments go on the constructor: `const m = new Map<K, V>();`
+  Constructor,
+  /// Type arguments go on the type annotation:
+  /// `const m: Map<K, V> = new Map();`
+  TypeAnnotation,
+}
+
+pub struct ConsistentGenericConstructorsOptions {
+  pub mode: ConsistentGenericConstructorsMode,
+}
+
+impl Default for ConsistentGenericConstructorsOptions {
+  fn default() -> Self {
+    Self {
+      mode: ConsistentGenericConstructorsMode::Constructor,
+    }
+  }
+}
+
+pub struct ConsistentGenericConstructors {
+  options: ConsistentGenericConstructorsOptions,
+}
+
+impl ConsistentGenericConstructors {
+  pub fn with_options(
+    options: ConsistentGenericConstructorsOptions,
+  ) -> Box<Self> {
+    Box::new(ConsistentGenericConstructors { options })
+  }
+}
+
+impl LintRule for ConsistentGenericConstructors {
+  fn new() -> Box<Self> {
+    Self::with_options(ConsistentGenericConstructorsOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "consistent-generic-constructors"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires the type arguments of a generic constructor to be specified consistently, either on the constructor call or on the type annotation, but not on both.
+
+### Options
+
+`ConsistentGenericConstructorsOptions::mode` is one of:
+- `ConsistentGenericConstructorsMode::Constructor` (default): `const m = new Map<string, number>();`
+- `ConsistentGenericConstructorsMode::TypeAnnotation`: `const m: Map<string, number> = new Map();`
+
+### Valid:
+```typescript
+const m = new Map<string, number>();
+```
+```typescript
+const foo: Foo = new Foo();
+```
+
+### Invalid:
+```typescript
+const m: Map<string, number> = new Map();
+```
+```typescript
+class A {
+  m: Map<string, number> = new Map();
+}
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor =
+      ConsistentGenericConstructorsVisitor::new(context, self.options.mode);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct ConsistentGenericConstructorsVisitor {
+  context: Arc<Context>,
+  mode: ConsistentGenericConstructorsMode,
+}
+
+fn type_args_equal(
+  a: &TsTypeParamInstantiation,
+  b: &TsTypeParamInstantiation,
+) -> bool {
+  a.clone().drop_span() == b.clone().drop_span()
+}
+
+impl ConsistentGenericConstructorsVisitor {
+  fn new(
+    context: Arc<Context>,
+    mode: ConsistentGenericConstructorsMode,
+  ) -> Self {
+    Self { context, mode }
+  }
+
+  fn args_text(&self, new_expr: &NewExpr) -> String {
+    new_expr
+      .args
+      .iter()
+      .flatten()
+      .map(|arg| {
+        let text = self.context.span_text(arg.expr.span());
+        if arg.spread.is_some() {
+          format!("...{}", text)
+        } else {
+          text
+        }
+      })
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+
+  /// `name` is the source text of the annotated binding, used in hints.
+  fn check(&self, name: &str, type_ann: Option<&TsTypeAnn>, init: &Expr) {
+    let new_expr = match init {
+      Expr::New(new_expr) => new_expr,
+      _ => return,
+    };
+    let callee = match &*new_expr.callee {
+      Expr::Ident(ident) => ident,
+      _ => return,
+    };
+
+    // `const m: ReadonlyMap<K, V> = new Map()` annotates a different type,
+    // which is the only way to write it.
+    let annotation_args = match type_ann.map(|ann| &*ann.type_ann) {
+      Some(TsType::TsTypeRef(TsTypeRef {
+        type_name: TsEntityName::Ident(type_name),
+        type_params,
+        ..
+      }))
+        if type_name.sym == callee.sym =>
+      {
+        Some(type_params.as_ref())
+      }
+      Some(_) => return,
+      None => None,
+    };
+    let constructor_args = new_expr.type_args.as_ref();
+
+    match self.mode {
+      ConsistentGenericConstructorsMode::Constructor => {
+        let (ann, lhs) = match (type_ann, annotation_args) {
+          (Some(ann), Some(Some(lhs))) => (ann, lhs),
+          _ => return,
+        };
+        if let Some(rhs) = constructor_args {
+          if !type_args_equal(lhs, rhs) {
+            return;
+          }
+        }
+        self.context.add_diagnostic_with_hint(
+          ann.span,
+          "consistent-generic-constructors",
+          "The type arguments should be specified on the constructor call",
+          &format!(
+            "Remove the type annotation and use `new {}{}({})`",
+            callee.sym,
+            self.context.span_text(lhs.span),
+            self.args_text(new_expr)
+          ),
+        );
+      }
+      ConsistentGenericConstructorsMode::TypeAnnotation => {
+        let rhs = match constructor_args {
+          Some(rhs) => rhs,
+          None => return,
+        };
+        match annotation_args {
+          None => {}
+          Some(Some(lhs)) if type_args_equal(lhs, rhs) => {}
+          Some(_) => return,
+        }
+        self.context.add_diagnostic_with_hint(
+          rhs.span,
+          "consistent-generic-constructors",
+          "The type arguments should be specified on the type annotation",
+          &format!(
+            "Rewrite as `{}: {}{} = new {}({})`",
+            name,
+            callee.sym,
+            self.context.span_text(rhs.span),
+            callee.sym,
+            self.args_text(new_expr)
+          ),
+        );
+      }
+    }
+  }
+}
+
+impl Visit for ConsistentGenericConstructorsVisitor {
+  noop_visit_type!();
+
+  fn visit_var_declarator(
+    &mut self,
+    var_declarator: &VarDeclarator,
+    parent: &dyn Node,
+  ) {
+    if let (Pat::Ident(ident), Some(init)) =
+      (&var_declarator.name, &var_declarator.init)
+    {
+      self.check(&ident.sym, ident.type_ann.as_ref(), init);
+    }
+    swc_ecmascript::visit::visit_var_declarator(self, var_declarator, parent);
+  }
+
+  fn visit_class_prop(&mut self, class_prop: &ClassProp, parent: &dyn Node) {
+    if let Some(value) = &class_prop.value {
+      let name = self.context.span_text(class_prop.key.span());
+      self.check(&name, class_prop.type_ann.as_ref(), value);
+    }
+    swc_ecmascript::visit::visit_class_prop(self, class_prop, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn type_annotation() -> Box<ConsistentGenericConstructors> {
+    ConsistentGenericConstructors::with_options(
+      ConsistentGenericConstructorsOptions {
+        mode: ConsistentGenericConstructorsMode::TypeAnnotation,
+      },
+    )
+  }
+
+  #[test]
+  fn consistent_generic_constructors_valid() {
+    assert_lint_ok_n::<ConsistentGenericConstructors>(vec![
+      "const m = new Map<string, number>();",
+      "const m: Map<string, number> = new Map<string, boolean>();",
+      "const m: ReadonlyMap<string, number> = new Map<string, number>();",
+      "const m: Map = new Map();",
+      "const s = new Set();",
+      "const s: Set<string> = foo();",
+      "class A { m = new Map<string, number>(); }",
+    ]);
+  }
+
+  #[test]
+  fn consistent_generic_constructors_invalid() {
+    assert_lint_err::<ConsistentGenericConstructors>(
+      "const m: Map<string, number> = new Map<string, number>();",
+      7,
+    );
+    assert_lint_err::<ConsistentGenericConstructors>(
+      "const m: Map<string, number> = new Map();",
+      7,
+    );
+    assert_lint_err::<ConsistentGenericConstructors>(
+      "const s: Set<Array<string>> = new Set([a, b]);",
+      7,
+    );
+    assert_lint_err::<ConsistentGenericConstructors>(
+      "class A { m: Map<string, number> = new Map(); }",
+      11,
+    );
+  }
+
+  #[test]
+  fn consistent_generic_constructors_type_annotation() {
+    let valid = vec![
+      "const m: Map<string, number> = new Map();",
+      "const m = new Map();",
+      "const m: ReadonlyMap<string, number> = new Map<string, number>();",
+      "class A { m: Map<string, number> = new Map(); }",
+    ];
+    for src in valid {
+      assert_lint_ok_with(type_annotation(), src);
+    }
+
+    assert_lint_err_on_line_n_with(
+      type_annotation(),
+      "const m = new Map<string, number>();",
+      vec![(1, 17)],
+    );
+    assert_lint_err_on_line_n_with(
+      type_annotation(),
+      "const m: Map<string, number> = new Map<string, number>();",
+      vec![(1, 38)],
+    );
+    assert_lint_err_on_line_n_with(
+      type_annotation(),
+      "class A { m = new Map<string, number>(); }",
+      vec![(1, 21)],
+    );
+  }
+
+  #[test]
+  fn consistent_generic_constructors_hint() {
+    let diagnostics = lint(
+      ConsistentGenericConstructors::new(),
+      "const m: Map<string, number> = new Map(entries);",
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some(
+        "Remove the type annotation and use `new Map<string, number>(entries)`"
+      )
+    );
+
+    let diagnostics = lint(
+      type_annotation(),
+      "const m = new Map<string, number>(entries);",
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Rewrite as `m: Map<string, number> = new Map(entries)`")
+    );
+  }
+}
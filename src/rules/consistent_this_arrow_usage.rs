@@ -0,0 +1,496 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use regex::Regex;
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  CallExpr, Class, ClassMember, Expr, ExprOrSuper, Function, Lit, MemberExpr,
+  MethodKind, PropName,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConsistentThisArrowUsageMode {
+  /// Class members are written as methods, arrow function fields are
+  /// reported.
+  Method,
+  /// Class members are written as arrow function fields, methods are
+  /// reported.
+  Field,
+}
+
+pub struct ConsistentThisArrowUsageOptions {
+  pub mode: ConsistentThisArrowUsageMode,
+  /// Arrow function fields whose name matches are allowed in `Method` mode,
+  /// they're usually event handlers which need a bound `this`.
+  pub handler_pattern: Regex,
+  /// Whether arrow function fields which the class itself passes around
+  /// without calling them, like `addEventListener("click", this.foo)` or
+  /// `onClick={this.foo}`, are allowed in `Method` mode.
+  pub allow_referenced_fields: bool,
+}
+
+impl Default for ConsistentThisArrowUsageOptions {
+  fn default() -> Self {
+    Self {
+      mode: ConsistentThisArrowUsageMode::Method,
+      handler_pattern: Regex::new(r"^(on|handle)[A-Z]").unwrap(),
+      allow_referenced_fields: false,
+    }
+  }
+}
+
+pub struct ConsistentThisArrowUsage {
+  options: ConsistentThisArrowUsageOptions,
+}
+
+impl ConsistentThisArrowUsage {
+  pub fn with_options(options: ConsistentThisArrowUsageOptions) -> Box<Self> {
+    Box::new(ConsistentThisArrowUsage { options })
+  }
+}
+
+const CODE: &str = "consistent-this-arrow-usage";
+
+impl LintRule for ConsistentThisArrowUsage {
+  fn new() -> Box<Self> {
+    Self::with_options(ConsistentThisArrowUsageOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Enforces a consistent style for functions defined on classes, either methods or arrow function fields.
+
+Arrow function fields keep `this` bound when they're passed around, but they're created again for every instance and aren't on the prototype. Mixing both styles makes it hard to tell which members are safe to pass by reference.
+
+Getters, setters, constructors and members without a body are not checked.
+
+### Options
+
+- `ConsistentThisArrowUsageOptions::mode`:
+  - `ConsistentThisArrowUsageMode::Method` (default): arrow function fields are reported.
+  - `ConsistentThisArrowUsageMode::Field`: methods are reported.
+- `ConsistentThisArrowUsageOptions::handler_pattern`: arrow function fields whose name matches are allowed in `Method` mode, defaults to `^(on|handle)[A-Z]`.
+- `ConsistentThisArrowUsageOptions::allow_referenced_fields`: allows arrow function fields in `Method` mode when the class passes them around without calling them, like `addEventListener("click", this.foo)`, defaults to `false`.
+
+### Valid:
+```typescript
+class Counter {
+  count = 0;
+  increment() {
+    this.count++;
+  }
+  handleClick = () => {
+    this.increment();
+  };
+}
+```
+
+### Invalid:
+```typescript
+class Counter {
+  count = 0;
+  increment = () => {
+    this.count++;
+  };
+}
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor =
+      ConsistentThisArrowUsageVisitor::new(context, &self.options);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct ConsistentThisArrowUsageVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a ConsistentThisArrowUsageOptions,
+}
+
+impl<'a> ConsistentThisArrowUsageVisitor<'a> {
+  fn new(
+    context: Arc<Context>,
+    options: &'a ConsistentThisArrowUsageOptions,
+  ) -> Self {
+    Self { context, options }
+  }
+
+  fn check_method_mode(&self, class: &Class) {
+    let referenced = if self.options.allow_referenced_fields {
+      referenced_members(class)
+    } else {
+      HashSet::new()
+    };
+    for member in &class.body {
+      let (name, span) = match member {
+        ClassMember::ClassProp(prop) if !prop.computed => {
+          if !matches!(prop.value.as_deref(), Some(value) if is_arrow(value)) {
+            continue;
+          }
+          match key_name(&prop.key) {
+            Some(name) => (name, prop.key.span()),
+            None => continue,
+          }
+        }
+        ClassMember::PrivateProp(prop) => {
+          if !matches!(prop.value.as_deref(), Some(value) if is_arrow(value)) {
+            continue;
+          }
+          (format!("#{}", prop.key.id.sym), prop.key.span)
+        }
+        _ => continue,
+      };
+      let bare_name = name.trim_start_matches('#');
+      if self.options.handler_pattern.is_match(bare_name)
+        || referenced.contains(&name)
+      {
+        continue;
+      }
+      self.context.add_diagnostic_with_hint(
+        span,
+        CODE,
+        &format!("Arrow function field `{}` should be a method", name),
+        &format!(
+          "Rewrite it as `{}() {{ ... }}`, or name it like an event handler if it needs a bound `this`",
+          name
+        ),
+      );
+    }
+  }
+
+  fn check_field_mode(&self, class: &Class) {
+    for member in &class.body {
+      let (name, span) = match member {
+        ClassMember::Method(method)
+          if method.kind == MethodKind::Method
+            && method.function.body.is_some() =>
+        {
+          match prop_name(&method.key) {
+            Some(name) => (name, method.key.span()),
+            None => continue,
+          }
+        }
+        ClassMember::PrivateMethod(method)
+          if method.kind == MethodKind::Method
+            && method.function.body.is_some() =>
+        {
+          (format!("#{}", method.key.id.sym), method.key.span)
+        }
+        _ => continue,
+      };
+      self.context.add_diagnostic_with_hint(
+        span,
+        CODE,
+        &format!("Method `{}` should be an arrow function field", name),
+        &format!("Rewrite it as `{} = () => {{ ... }}`", name),
+      );
+    }
+  }
+}
+
+impl<'a> Visit for ConsistentThisArrowUsageVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_class(&mut self, class: &Class, parent: &dyn Node) {
+    match self.options.mode {
+      ConsistentThisArrowUsageMode::Method => self.check_method_mode(class),
+      ConsistentThisArrowUsageMode::Field => self.check_field_mode(class),
+    }
+    swc_ecmascript::visit::visit_class(self, class, parent);
+  }
+}
+
+fn is_arrow(expr: &Expr) -> bool {
+  match expr {
+    Expr::Arrow(_) => true,
+    Expr::Paren(paren) => is_arrow(&paren.expr),
+    _ => false,
+  }
+}
+
+fn key_name(key: &Expr) -> Option<String> {
+  match key {
+    Expr::Ident(ident) => Some(ident.sym.to_string()),
+    Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+    _ => None,
+  }
+}
+
+fn prop_name(key: &PropName) -> Option<String> {
+  match key {
+    PropName::Ident(ident) => Some(ident.sym.to_string()),
+    PropName::Str(s) => Some(s.value.to_string()),
+    _ => None,
+  }
+}
+
+/// Names of the members which the class reads through `this` without
+/// calling them, private names keep their `#`.
+fn referenced_members(class: &Class) -> HashSet<String> {
+  let mut collector = ReferenceCollector {
+    names: HashSet::new(),
+  };
+  for member in &class.body {
+    match member {
+      ClassMember::Constructor(constructor) => {
+        collector.visit_opt_block_stmt(
+          constructor.body.as_ref(),
+          constructor as &dyn Node,
+        );
+      }
+      ClassMember::Method(method) => {
+        collector.visit_opt_block_stmt(
+          method.function.body.as_ref(),
+          method as &dyn Node,
+        );
+      }
+      ClassMember::PrivateMethod(method) => {
+        collector.visit_opt_block_stmt(
+          method.function.body.as_ref(),
+          method as &dyn Node,
+        );
+      }
+      ClassMember::ClassProp(prop) => {
+        collector.visit_opt_expr(prop.value.as_ref(), prop as &dyn Node);
+      }
+      ClassMember::PrivateProp(prop) => {
+        collector.visit_opt_expr(prop.value.as_ref(), prop as &dyn Node);
+      }
+      ClassMember::TsIndexSignature(_) | ClassMember::Empty(_) => {}
+    }
+  }
+  collector.names
+}
+
+struct ReferenceCollector {
+  names: HashSet<String>,
+}
+
+impl ReferenceCollector {
+  fn this_member_name(member: &MemberExpr) -> Option<String> {
+    if !matches!(&member.obj, ExprOrSuper::Expr(obj) if matches!(&**obj, Expr::This(_)))
+    {
+      return None;
+    }
+    match &*member.prop {
+      Expr::Ident(ident) if !member.computed => Some(ident.sym.to_string()),
+      Expr::PrivateName(name) => Some(format!("#{}", name.id.sym)),
+      Expr::Lit(Lit::Str(s)) if member.computed => Some(s.value.to_string()),
+      _ => None,
+    }
+  }
+}
+
+impl Visit for ReferenceCollector {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call: &CallExpr, parent: &dyn Node) {
+    // `this.foo()` is a call, not a reference to `foo`.
+    if let ExprOrSuper::Expr(callee) = &call.callee {
+      if let Expr::Member(member) = &**callee {
+        if Self::this_member_name(member).is_some() {
+          for arg in &call.args {
+            self.visit_expr_or_spread(arg, call as &dyn Node);
+          }
+          return;
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call, parent);
+  }
+
+  fn visit_member_expr(&mut self, member: &MemberExpr, parent: &dyn Node) {
+    if let Some(name) = Self::this_member_name(member) {
+      self.names.insert(name);
+    }
+    swc_ecmascript::visit::visit_member_expr(self, member, parent);
+  }
+
+  // Functions and classes nested in a member have their own `this`.
+  fn visit_function(&mut self, _function: &Function, _parent: &dyn Node) {}
+
+  fn visit_class(&mut self, _class: &Class, _parent: &dyn Node) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn field_mode() -> Box<ConsistentThisArrowUsage> {
+    ConsistentThisArrowUsage::with_options(ConsistentThisArrowUsageOptions {
+      mode: ConsistentThisArrowUsageMode::Field,
+      ..Default::default()
+    })
+  }
+
+  fn allow_referenced() -> Box<ConsistentThisArrowUsage> {
+    ConsistentThisArrowUsage::with_options(ConsistentThisArrowUsageOptions {
+      allow_referenced_fields: true,
+      ..Default::default()
+    })
+  }
+
+  #[test]
+  fn consistent_this_arrow_usage_valid() {
+    assert_lint_ok_n::<ConsistentThisArrowUsage>(vec![
+      r#"
+class A {
+  count = 0;
+  callback = foo;
+  get value() { return this.count; }
+  increment() { this.count++; }
+  handleClick = () => this.increment();
+  onChange = () => {};
+  #onSubmit = () => {};
+  static handleEvent = () => {};
+}
+      "#,
+      r#"
+class A {
+  [name] = () => {};
+}
+      "#,
+    ]);
+
+    assert_lint_ok_with(
+      field_mode(),
+      r#"
+abstract class A {
+  constructor() {}
+  increment = () => {};
+  #reset = () => {};
+  static create = () => new B();
+  get value() { return 1; }
+  set value(v) {}
+  abstract run(): void;
+  overloaded(): void;
+}
+      "#,
+    );
+
+    assert_lint_ok_with(
+      allow_referenced(),
+      r#"
+class A {
+  constructor(el) {
+    el.addEventListener("click", this.toggle);
+  }
+  toggle = () => {};
+  #cleanup = () => {};
+  dispose() {
+    queue(this.#cleanup);
+  }
+}
+      "#,
+    );
+  }
+
+  #[test]
+  fn consistent_this_arrow_usage_invalid() {
+    assert_lint_err_on_line_n::<ConsistentThisArrowUsage>(
+      r#"
+class A {
+  increment = () => {};
+  static create = () => new A();
+  #reset = (() => {});
+  "quoted" = () => {};
+  handler = () => {};
+}
+      "#,
+      vec![(3, 2), (4, 9), (5, 2), (6, 2), (7, 2)],
+    );
+
+    assert_lint_err_on_line_n_with(
+      field_mode(),
+      r#"
+class A {
+  increment() {}
+  static create() {}
+  #reset() {}
+  handleClick() {}
+}
+      "#,
+      vec![(3, 2), (4, 9), (5, 2), (6, 2)],
+    );
+
+    let diagnostics = lint(
+      ConsistentThisArrowUsage::new(),
+      "class A { #reset = () => {}; }",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Arrow function field `#reset` should be a method"
+    );
+  }
+
+  #[test]
+  fn consistent_this_arrow_usage_referenced_fields() {
+    // Calling a field, or referencing it from a nested function with its
+    // own `this`, doesn't exempt it.
+    assert_lint_err_on_line_n_with(
+      allow_referenced(),
+      r#"
+class A {
+  toggle = () => {};
+  reset = () => {};
+  run() {
+    this.toggle();
+    function inner() {
+      return this.reset;
+    }
+  }
+}
+      "#,
+      vec![(3, 2), (4, 2)],
+    );
+    assert_lint_err_on_line_n_with(
+      ConsistentThisArrowUsage::new(),
+      r#"
+class A {
+  toggle = () => {};
+  constructor(el) {
+    el.addEventListener("click", this.toggle);
+  }
+}
+      "#,
+      vec![(3, 2)],
+    );
+  }
+
+  #[test]
+  fn consistent_this_arrow_usage_jsx() {
+    let source = r#"
+class Button extends Component {
+  toggle = () => {};
+  render() {
+    return <button onClick={this.toggle} />;
+  }
+}
+    "#;
+    assert_lint_ok_tsx(allow_referenced(), source);
+    assert_lint_err_on_line_n_tsx(
+      ConsistentThisArrowUsage::new(),
+      source,
+      vec![(3, 2)],
+    );
+  }
+}
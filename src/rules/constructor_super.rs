@@ -22,6 +22,53 @@ impl LintRule for ConstructorSuper {
     "constructor-super"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Verifies the correct usage of constructors and calls to `super()`.
+
+Constructors of derived classes must call `super()` exactly once, and constructors of non-derived classes, or of classes extending a non-constructor like `null`, must not call it.
+
+### Valid:
+```typescript
+class A {}
+class B extends A {
+  constructor() {
+    super();
+  }
+}
+```
+```typescript
+class A {
+  constructor() {}
+}
+```
+
+### Invalid:
+```typescript
+class A {}
+class B extends A {
+  constructor() {}
+}
+```
+```typescript
+class A {
+  constructor() {
+    super();
+  }
+}
+```
+```typescript
+class A extends null {
+  constructor() {
+    super();
+  }
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -0,0 +1,269 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::sync::Arc;
+use swc_common::BytePos;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  DoWhileStmt, ForInStmt, ForOfStmt, ForStmt, IfStmt, Stmt, WhileStmt,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CurlyMode {
+  /// Every body of `if`, `else`, `for`, `while` and `do` must be a block.
+  All,
+  /// Only bodies that start on a different line than the statement they
+  /// belong to must be wrapped in braces.
+  MultiLine,
+}
+
+pub struct CurlyOptions {
+  pub mode: CurlyMode,
+}
+
+impl Default for CurlyOptions {
+  fn default() -> Self {
+    Self {
+      mode: CurlyMode::All,
+    }
+  }
+}
+
+pub struct Curly {
+  options: CurlyOptions,
+}
+
+impl Curly {
+  pub fn with_options(options: CurlyOptions) -> Box<Self> {
+    Box::new(Curly { options })
+  }
+}
+
+impl LintRule for Curly {
+  fn new() -> Box<Self> {
+    Self::with_options(CurlyOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "curly"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires the bodies of `if`, `else`, `for`, `while` and `do` statements to be wrapped in braces.
+
+Omitting braces makes it easy to add a statement which looks like it belongs to the body but doesn't.
+
+### Options
+
+`CurlyOptions::mode` is one of:
+- `CurlyMode::All` (default): every body must be a block.
+- `CurlyMode::MultiLine`: only bodies which start on a different line than their statement must be a block.
+
+### Valid:
+```typescript
+if (a) {
+  b();
+} else {
+  c();
+}
+```
+```typescript
+while (a) {
+  b();
+}
+```
+
+### Invalid:
+```typescript
+if (a) b();
+```
+```typescript
+for (;;)
+  a();
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = CurlyVisitor::new(context, self.options.mode);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct CurlyVisitor {
+  context: Arc<Context>,
+  mode: CurlyMode,
+}
+
+impl CurlyVisitor {
+  fn new(context: Arc<Context>, mode: CurlyMode) -> Self {
+    Self { context, mode }
+  }
+
+  fn line_of(&self, span: Span) -> usize {
+    self.context.source_map.lookup_char_pos(span.lo()).line
+  }
+
+  /// There's no span for the `else` keyword in the AST, so it's looked up
+  /// in the source text between the consequent and the alternate.
+  fn else_keyword_span(&self, if_stmt: &IfStmt, alt: &Stmt) -> Span {
+    let between = Span::new(
+      if_stmt.cons.span().hi(),
+      alt.span().lo(),
+      Default::default(),
+    );
+    let offset = self
+      .context
+      .source_map
+      .span_to_snippet(between)
+      .ok()
+      .and_then(|text| text.rfind("else"))
+      .unwrap_or(0);
+    let lo = between.lo() + BytePos(offset as u32);
+    between.with_lo(lo).with_hi(lo)
+  }
+
+  /// Checks a statement body; `keyword_span` is the span whose start is
+  /// on the same line as the keyword owning the body.
+  fn check_body(&self, keyword_span: Span, body: &Stmt, keyword: &str) {
+    if let Stmt::Block(_) = body {
+      return;
+    }
+
+    if self.mode == CurlyMode::MultiLine
+      && self.line_of(keyword_span) == self.line_of(body.span())
+    {
+      return;
+    }
+
+    self.context.add_diagnostic(
+      body.span(),
+      "curly",
+      &format!("Expected {{ after '{}'", keyword),
+    );
+  }
+}
+
+impl Visit for CurlyVisitor {
+  noop_visit_type!();
+
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, parent: &dyn Node) {
+    self.check_body(if_stmt.span, &if_stmt.cons, "if");
+
+    if let Some(alt) = &if_stmt.alt {
+      // `else if` chains are not bodies on their own.
+      if !matches!(&**alt, Stmt::If(_)) {
+        let else_span = self.else_keyword_span(if_stmt, alt);
+        self.check_body(else_span, alt, "else");
+      }
+    }
+
+    swc_ecmascript::visit::visit_if_stmt(self, if_stmt, parent);
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
+    self.check_body(for_stmt.span, &for_stmt.body, "for");
+    swc_ecmascript::visit::visit_for_stmt(self, for_stmt, parent);
+  }
+
+  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, parent: &dyn Node) {
+    self.check_body(for_in_stmt.span, &for_in_stmt.body, "for-in");
+    swc_ecmascript::visit::visit_for_in_stmt(self, for_in_stmt, parent);
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, parent: &dyn Node) {
+    self.check_body(for_of_stmt.span, &for_of_stmt.body, "for-of");
+    swc_ecmascript::visit::visit_for_of_stmt(self, for_of_stmt, parent);
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, parent: &dyn Node) {
+    self.check_body(while_stmt.span, &while_stmt.body, "while");
+    swc_ecmascript::visit::visit_while_stmt(self, while_stmt, parent);
+  }
+
+  fn visit_do_while_stmt(
+    &mut self,
+    do_while_stmt: &DoWhileStmt,
+    parent: &dyn Node,
+  ) {
+    self.check_body(do_while_stmt.span, &do_while_stmt.body, "do");
+    swc_ecmascript::visit::visit_do_while_stmt(self, do_while_stmt, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn multi_line() -> Box<Curly> {
+    Curly::with_options(CurlyOptions {
+      mode: CurlyMode::MultiLine,
+    })
+  }
+
+  #[test]
+  fn curly_valid() {
+    assert_lint_ok_n::<Curly>(vec![
+      "if (a) { b(); }",
+      "if (a) { b(); } else { c(); }",
+      "if (a) { b(); } else if (c) { d(); }",
+      "for (;;) { a(); }",
+      "for (const a in b) { c(); }",
+      "for (const a of b) { c(); }",
+      "while (a) { b(); }",
+      "do { a(); } while (b);",
+    ]);
+  }
+
+  #[test]
+  fn curly_invalid() {
+    assert_lint_err_n::<Curly>("if (a) b(); else c();", vec![7, 17]);
+    assert_lint_err::<Curly>("if (a) { b(); } else c();", 21);
+    assert_lint_err::<Curly>("for (;;) a();", 9);
+    assert_lint_err::<Curly>("for (const a in b) c();", 19);
+    assert_lint_err::<Curly>("for (const a of b) c();", 19);
+    assert_lint_err::<Curly>("while (a) b();", 10);
+    assert_lint_err::<Curly>("do a(); while (b);", 3);
+    assert_lint_err_on_line::<Curly>("if (a)\n  b();", 2, 2);
+  }
+
+  #[test]
+  fn curly_multi_line_valid() {
+    assert_lint_ok_with(multi_line(), "if (a) b(); else c();");
+    assert_lint_ok_with(multi_line(), "if (a) {\n  b();\n} else c();");
+    assert_lint_ok_with(multi_line(), "while (a) b();");
+    assert_lint_ok_with(multi_line(), "if (a) {\n  b();\n}");
+    assert_lint_ok_with(multi_line(), "if (a) b();\nelse c();");
+  }
+
+  #[test]
+  fn curly_multi_line_invalid() {
+    assert_lint_err_on_line_n_with(
+      multi_line(),
+      "if (a)\n  b();\nelse\n  c();",
+      vec![(2, 2), (4, 2)],
+    );
+    assert_lint_err_on_line_n_with(
+      multi_line(),
+      "for (;;)\n  a();",
+      vec![(2, 2)],
+    );
+    assert_lint_err_on_line_n_with(
+      multi_line(),
+      "if (a) b(); else\n  c();",
+      vec![(2, 2)],
+    );
+  }
+}
@@ -2,7 +2,9 @@
 use super::Context;
 use super::LintRule;
 use swc_common::Span;
-use swc_ecmascript::ast::{ArrowExpr, Function, Pat};
+use swc_ecmascript::ast::{
+  ArrowExpr, Constructor, Function, ParamOrTsParamProp, Pat, TsParamPropParam,
+};
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 use swc_ecmascript::visit::{self, noop_visit_type};
@@ -20,6 +22,40 @@ impl LintRule for DefaultParamLast {
     "default-param-last"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires parameters with default values to come after the parameters without.
+
+A default value before a required parameter can't be used without passing `undefined` explicitly. The same goes for optional parameters in TypeScript, eg. `a?: string`. Rest parameters come last anyway.
+
+### Valid:
+```typescript
+function f(a, b = 2) {}
+```
+```typescript
+function f(a, b = 2, ...c) {}
+```
+```typescript
+function f(a: number, b?: number) {}
+```
+
+### Invalid:
+```typescript
+function f(a = 2, b) {}
+```
+```typescript
+function f(a = 5, b, c = 6, d) {}
+```
+```typescript
+class Foo {
+  constructor(private a = 1, b: number) {}
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -40,27 +76,29 @@ impl DefaultParamLastVisitor {
   }
 
   fn report(&self, span: Span) {
-    self.context.add_diagnostic(
+    self.context.add_diagnostic_with_hint(
       span,
       "default-param-last",
       "default parameters should be at last",
+      "Move the parameter after the required ones, or make it required",
     );
   }
 
-  fn check_params<'a, 'b, I>(&'a self, params: I)
+  /// Checks the kinds of the parameters, from the last to the first.
+  fn check_params<I>(&self, params: I)
   where
-    I: Iterator<Item = &'b Pat>,
+    I: Iterator<Item = ParamKind>,
   {
     let mut has_seen_normal_param = false;
     for param in params {
       match param {
-        Pat::Assign(pat) => {
+        ParamKind::Default(span) | ParamKind::Optional(span) => {
           if has_seen_normal_param {
-            self.report(pat.span);
+            self.report(span);
           }
         }
-        Pat::Rest(_) => {}
-        _ => {
+        ParamKind::Rest => {}
+        ParamKind::Required => {
           has_seen_normal_param = true;
         }
       }
@@ -68,18 +106,74 @@ impl DefaultParamLastVisitor {
   }
 }
 
+enum ParamKind {
+  /// A parameter with a default value, eg. `a = 1`.
+  Default(Span),
+  /// An optional parameter in TypeScript, eg. `a?: number`.
+  Optional(Span),
+  Rest,
+  Required,
+}
+
+impl ParamKind {
+  fn of_pat(pat: &Pat) -> Self {
+    match pat {
+      Pat::Assign(assign_pat) => ParamKind::Default(assign_pat.span),
+      Pat::Ident(ident) if ident.optional => ParamKind::Optional(ident.span),
+      Pat::Rest(_) => ParamKind::Rest,
+      _ => ParamKind::Required,
+    }
+  }
+
+  fn of_constructor_param(param: &ParamOrTsParamProp) -> Self {
+    match param {
+      ParamOrTsParamProp::Param(param) => ParamKind::of_pat(&param.pat),
+      ParamOrTsParamProp::TsParamProp(param_prop) => match &param_prop.param {
+        TsParamPropParam::Assign(assign_pat) => {
+          ParamKind::Default(assign_pat.span)
+        }
+        TsParamPropParam::Ident(ident) if ident.optional => {
+          ParamKind::Optional(ident.span)
+        }
+        TsParamPropParam::Ident(_) => ParamKind::Required,
+      },
+    }
+  }
+}
+
 impl Visit for DefaultParamLastVisitor {
   noop_visit_type!();
 
   fn visit_function(&mut self, function: &Function, parent: &dyn Node) {
-    self.check_params(function.params.iter().rev().map(|p| &p.pat));
+    self.check_params(
+      function
+        .params
+        .iter()
+        .rev()
+        .map(|p| ParamKind::of_pat(&p.pat)),
+    );
     visit::visit_function(self, function, parent);
   }
 
   fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
-    self.check_params(arrow_expr.params.iter().rev());
+    self.check_params(arrow_expr.params.iter().rev().map(ParamKind::of_pat));
     visit::visit_arrow_expr(self, arrow_expr, parent);
   }
+
+  fn visit_constructor(
+    &mut self,
+    constructor: &Constructor,
+    parent: &dyn Node,
+  ) {
+    self.check_params(
+      constructor
+        .params
+        .iter()
+        .rev()
+        .map(ParamKind::of_constructor_param),
+    );
+    visit::visit_constructor(self, constructor, parent);
+  }
 }
 
 #[cfg(test)]
@@ -122,11 +216,11 @@ class Foo {
     assert_lint_err::<DefaultParamLast>("const f = function (a = 2, b) {}", 20);
     assert_lint_err_n::<DefaultParamLast>(
       "function f(a = 5, b = 6, c) {}",
-      vec![18, 11],
+      vec![11, 18],
     );
     assert_lint_err_n::<DefaultParamLast>(
       "function f(a = 5, b, c = 6, d) {}",
-      vec![21, 11],
+      vec![11, 21],
     );
     assert_lint_err::<DefaultParamLast>("function f(a = 5, b, c = 5) {}", 11);
     assert_lint_err::<DefaultParamLast>("const f = (a = 5, b, ...c) => {}", 11);
@@ -204,4 +298,48 @@ class Foo {
       8,
     );
   }
+
+  #[test]
+  fn default_param_last_optional() {
+    assert_lint_ok_n::<DefaultParamLast>(vec![
+      "function f(a: number, b?: number) {}",
+      "function f(a?: number, b = 1) {}",
+      "function f(a: number, b?: number, ...c: number[]) {}",
+      "const f = (a: number, b?: number): void => {}",
+    ]);
+    assert_lint_err::<DefaultParamLast>("function f(a?: number, b) {}", 11);
+    assert_lint_err_n::<DefaultParamLast>(
+      "function f(a?: number, b = 1, c: number) {}",
+      vec![11, 23],
+    );
+    assert_lint_err::<DefaultParamLast>(
+      "const f = (a?: string, b: string) => {}",
+      11,
+    );
+  }
+
+  #[test]
+  fn default_param_last_constructor() {
+    assert_lint_ok_n::<DefaultParamLast>(vec![
+      "class Foo { constructor(a, b = 1) {} }",
+      "class Foo { constructor(private a: number, public b = 1) {} }",
+      "class Foo { constructor(readonly a?: number) {} }",
+    ]);
+    assert_lint_err::<DefaultParamLast>(
+      "class Foo { constructor(a = 1, b) {} }",
+      24,
+    );
+    assert_lint_err::<DefaultParamLast>(
+      "class Foo { constructor(private a = 1, b: number) {} }",
+      32,
+    );
+    assert_lint_err::<DefaultParamLast>(
+      "class Foo { constructor(public a?: number, private b: number) {} }",
+      31,
+    );
+    assert_lint_err_on_line_n::<DefaultParamLast>(
+      "class Foo {\n  constructor(a = 1, b) {\n    const f = (c = 1, d) => {};\n  }\n}",
+      vec![(2, 14), (3, 15)],
+    );
+  }
 }
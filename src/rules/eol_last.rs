@@ -0,0 +1,115 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use std::sync::Arc;
+use swc_common::{BytePos, Span, SyntaxContext};
+use swc_ecmascript::ast::Module;
+
+declare_rule! {
+  /// Reports files which don't end with exactly one newline: either the last
+  /// line isn't terminated, or it's followed by blank lines. Lines with only
+  /// whitespace count as blank. Empty files are never reported.
+  EolLast, "eol-last", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Requires files to end with exactly one newline.
+
+Files whose last line isn't terminated, or which end with blank lines, produce noisy diffs when lines are appended. Lines containing only whitespace count as blank, and empty files are never reported.
+
+### Valid:
+```typescript
+a();
+```
+
+### Invalid:
+```typescript
+a();
+
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let file = context.source_map.lookup_char_pos(module.span.lo()).file;
+    let src = &file.src;
+    let content_len = src.trim_end().len();
+    if content_len == 0 {
+      return;
+    }
+    let end = &src[content_len..];
+    let at = |offset: usize| file.start_pos + BytePos(offset as u32);
+
+    match end.find('\n') {
+      None => {
+        // Zero-width, right at the end of the file.
+        let eof = at(src.len());
+        context.add_diagnostic_with_hint(
+          Span::new(eof, eof, SyntaxContext::empty()),
+          "eol-last",
+          "Newline required at end of file but not found",
+          "Add a newline at the end of the file",
+        );
+      }
+      Some(first) if content_len + first + 1 < src.len() => {
+        context.add_diagnostic_with_hint(
+          Span::new(
+            at(content_len + first + 1),
+            at(src.len()),
+            SyntaxContext::empty(),
+          ),
+          "eol-last",
+          "Too many newlines at end of file",
+          "Remove the blank lines at the end of the file",
+        );
+      }
+      Some(_) => {}
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn eol_last_valid() {
+    assert_lint_ok_n::<EolLast>(vec![
+      "",
+      "\n",
+      "a();\n",
+      "a();\r\n",
+      "a();\n// comment\n",
+      "const s = `\n\n`;\n",
+    ]);
+  }
+
+  #[test]
+  fn eol_last_invalid() {
+    let diagnostics = lint(EolLast::new(), "a();\nb();");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Newline required at end of file but not found"
+    );
+    assert_eq!(diagnostics[0].range.start, diagnostics[0].range.end);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(diagnostics[0].range.start.col, 4);
+
+    let diagnostics = lint(EolLast::new(), "a(); // comment");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 15);
+
+    let diagnostics = lint(EolLast::new(), "a();  ");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 6);
+
+    let diagnostics = lint(EolLast::new(), "a();\n\n\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "Too many newlines at end of file");
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(diagnostics[0].range.start.col, 0);
+
+    assert_lint_err_on_line::<EolLast>("a();\n  \n", 2, 0);
+    assert_lint_err_on_line::<EolLast>("a();\n  ", 2, 0);
+  }
+}
@@ -1,57 +1,188 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::ast::{BinExpr, BinaryOp};
+use crate::swc_util::operator_span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  BinExpr, BinaryOp, Expr, Lit, ParenExpr, UnaryExpr, UnaryOp,
+};
 use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
-pub struct Eqeqeq;
+#[derive(Clone, Copy)]
+pub struct EqeqeqOptions {
+  /// Allows `x == null` and `x != null`, which intentionally match both
+  /// `null` and `undefined`.
+  pub allow_null_comparison: bool,
+}
+
+impl Default for EqeqeqOptions {
+  fn default() -> Self {
+    EqeqeqOptions {
+      allow_null_comparison: true,
+    }
+  }
+}
+
+pub struct Eqeqeq {
+  options: EqeqeqOptions,
+}
+
+impl Eqeqeq {
+  pub fn with_options(options: EqeqeqOptions) -> Box<Self> {
+    Box::new(Eqeqeq { options })
+  }
+}
 
 impl LintRule for Eqeqeq {
   fn new() -> Box<Self> {
-    Box::new(Eqeqeq)
+    Self::with_options(EqeqeqOptions::default())
   }
 
   fn code(&self) -> &'static str {
     "eqeqeq"
   }
 
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires the strict equality operators `===` and `!==` instead of `==` and `!=`.
+
+The loose operators convert their operands to the same type first, which gives surprising results like `"" == 0` being `true`. Comparisons whose operands are known to have the same type, like `typeof a == "number"`, are fixed automatically.
+
+### Options
+
+- `EqeqeqOptions::allow_null_comparison`: allows `a == null` and `a != null`, which match both `null` and `undefined`, `true` by default.
+
+### Valid:
+```typescript
+if (a === b) {}
+if (a !== null) {}
+if (a == null) {}
+```
+
+### Invalid:
+```typescript
+if (a == b) {}
+```
+```typescript
+if (typeof a != "number") {}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     module: &swc_ecmascript::ast::Module,
   ) {
-    let mut visitor = EqeqeqVisitor::new(context);
+    let mut visitor = EqeqeqVisitor::new(context, self.options);
     visitor.visit_module(module, module);
   }
 }
 
 struct EqeqeqVisitor {
   context: Arc<Context>,
+  options: EqeqeqOptions,
 }
 
 impl EqeqeqVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
+  fn new(context: Arc<Context>, options: EqeqeqOptions) -> Self {
+    Self { context, options }
   }
 }
 
+fn unparen(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => unparen(expr),
+    _ => expr,
+  }
+}
+
+fn is_null(expr: &Expr) -> bool {
+  matches!(unparen(expr), Expr::Lit(Lit::Null(_)))
+}
+
+fn is_typeof(expr: &Expr) -> bool {
+  matches!(
+    unparen(expr),
+    Expr::Unary(UnaryExpr {
+      op: UnaryOp::TypeOf,
+      ..
+    })
+  )
+}
+
+fn is_string(expr: &Expr) -> bool {
+  match unparen(expr) {
+    Expr::Lit(Lit::Str(_)) => true,
+    Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+    _ => false,
+  }
+}
+
+/// Whether both operands are known to have the same type, in which case the
+/// strict operator gives the same result.
+fn same_type_operands(left: &Expr, right: &Expr) -> bool {
+  if (is_typeof(left) && is_string(right))
+    || (is_string(left) && is_typeof(right))
+    || (is_string(left) && is_string(right))
+  {
+    return true;
+  }
+  matches!(
+    (unparen(left), unparen(right)),
+    (Expr::Lit(Lit::Bool(_)), Expr::Lit(Lit::Bool(_)))
+      | (Expr::Lit(Lit::Num(_)), Expr::Lit(Lit::Num(_)))
+      | (Expr::Lit(Lit::Null(_)), Expr::Lit(Lit::Null(_)))
+  )
+}
+
 impl Visit for EqeqeqVisitor {
   noop_visit_type!();
 
   fn visit_bin_expr(&mut self, bin_expr: &BinExpr, parent: &dyn Node) {
-    if matches!(bin_expr.op, BinaryOp::EqEq | BinaryOp::NotEq) {
-      let message = if bin_expr.op == BinaryOp::EqEq {
-        "expected '===' and instead saw '=='."
+    let (op, strict_op) = match bin_expr.op {
+      BinaryOp::EqEq => ("==", "==="),
+      BinaryOp::NotEq => ("!=", "!=="),
+      _ => {
+        swc_ecmascript::visit::visit_bin_expr(self, bin_expr, parent);
+        return;
+      }
+    };
+    let null_comparison = is_null(&bin_expr.left) || is_null(&bin_expr.right);
+    if !(self.options.allow_null_comparison && null_comparison) {
+      let span = operator_span(
+        &self.context,
+        bin_expr.left.span(),
+        bin_expr.right.span(),
+        op,
+      );
+      let message =
+        format!("expected '{}' and instead saw '{}'.", strict_op, op);
+      if same_type_operands(&bin_expr.left, &bin_expr.right) {
+        self.context.add_diagnostic_with_fix(
+          span,
+          "eqeqeq",
+          &message,
+          &format!("Use `{}`", strict_op),
+          vec![(span, strict_op.to_string())],
+        );
       } else {
-        "expected '!==' and instead saw '!='."
-      };
-      self
-        .context
-        .add_diagnostic(bin_expr.span, "eqeqeq", message)
+        self.context.add_diagnostic_with_hint(
+          span,
+          "eqeqeq",
+          &message,
+          &format!(
+            "Use `{}` after making sure both operands have the same type",
+            strict_op
+          ),
+        );
+      }
     }
     swc_ecmascript::visit::visit_bin_expr(self, bin_expr, parent);
   }
@@ -60,8 +191,15 @@ impl Visit for EqeqeqVisitor {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::diagnostic::apply_edits;
   use crate::test_util::*;
 
+  fn disallow_null() -> Box<Eqeqeq> {
+    Eqeqeq::with_options(EqeqeqOptions {
+      allow_null_comparison: false,
+    })
+  }
+
   #[test]
   fn eqeqeq_valid() {
     assert_lint_ok::<Eqeqeq>("midori === sapphire");
@@ -70,47 +208,131 @@ mod tests {
     assert_lint_ok::<Eqeqeq>("reina !== null");
     assert_lint_ok::<Eqeqeq>("null === null");
     assert_lint_ok::<Eqeqeq>("null !== null");
+    assert_lint_ok::<Eqeqeq>("a == null");
+    assert_lint_ok::<Eqeqeq>("null != a");
+    assert_lint_ok::<Eqeqeq>("a.b != (null)");
   }
 
   #[test]
   fn eqeqeq_invalid() {
-    assert_lint_err::<Eqeqeq>("a == b", 0);
-    assert_lint_err::<Eqeqeq>("a != b", 0);
-    assert_lint_err::<Eqeqeq>("typeof a == 'number'", 0);
-    assert_lint_err::<Eqeqeq>("'string' != typeof a", 0);
-    assert_lint_err::<Eqeqeq>("true == true", 0);
-    assert_lint_err::<Eqeqeq>("2 == 3", 0);
-    assert_lint_err::<Eqeqeq>("'hello' != 'world'", 0);
-    assert_lint_err::<Eqeqeq>("a == null", 0);
-    assert_lint_err::<Eqeqeq>("null != a", 0);
-    assert_lint_err::<Eqeqeq>("true == null", 0);
-    assert_lint_err::<Eqeqeq>("true != null", 0);
-    assert_lint_err::<Eqeqeq>("null == null", 0);
-    assert_lint_err::<Eqeqeq>("null != null", 0);
+    assert_lint_err::<Eqeqeq>("a == b", 2);
+    assert_lint_err::<Eqeqeq>("a != b", 2);
+    assert_lint_err::<Eqeqeq>("typeof a == 'number'", 9);
+    assert_lint_err::<Eqeqeq>("'string' != typeof a", 9);
+    assert_lint_err::<Eqeqeq>("true == true", 5);
+    assert_lint_err::<Eqeqeq>("2 == 3", 2);
+    assert_lint_err::<Eqeqeq>("'hello' != 'world'", 8);
     assert_lint_err_on_line::<Eqeqeq>(
       r#"
 a
 ==
 b"#,
-      2,
+      3,
       0,
     );
-    assert_lint_err::<Eqeqeq>("(a) == b", 0);
-    assert_lint_err::<Eqeqeq>("(a) != b", 0);
-    assert_lint_err::<Eqeqeq>("a == (b)", 0);
-    assert_lint_err::<Eqeqeq>("a != (b)", 0);
-    assert_lint_err::<Eqeqeq>("(a) == (b)", 0);
-    assert_lint_err::<Eqeqeq>("(a) != (b)", 0);
-    assert_lint_err_n::<Eqeqeq>("(a == b) == (c)", vec![0, 1]);
-    assert_lint_err_n::<Eqeqeq>("(a != b) != (c)", vec![0, 1]);
-    assert_lint_err::<Eqeqeq>("(a == b) === (c)", 1);
-    assert_lint_err::<Eqeqeq>("(a == b) !== (c)", 1);
-    assert_lint_err::<Eqeqeq>("(a === b) == (c)", 0);
-    assert_lint_err::<Eqeqeq>("(a === b) != (c)", 0);
-    assert_lint_err::<Eqeqeq>("a == b;", 0);
-    assert_lint_err::<Eqeqeq>("a!=b;", 0);
-    assert_lint_err::<Eqeqeq>("(a + b) == c;", 0);
-    assert_lint_err::<Eqeqeq>("(a + b)  !=  c;", 0);
-    assert_lint_err::<Eqeqeq>("((1) )  ==  (2);", 0);
+    assert_lint_err::<Eqeqeq>("(a) == b", 4);
+    assert_lint_err::<Eqeqeq>("(a) != b", 4);
+    assert_lint_err::<Eqeqeq>("a == (b)", 2);
+    assert_lint_err::<Eqeqeq>("a != (b)", 2);
+    assert_lint_err::<Eqeqeq>("(a) == (b)", 4);
+    assert_lint_err::<Eqeqeq>("(a) != (b)", 4);
+    assert_lint_err::<Eqeqeq>("a == b;", 2);
+    assert_lint_err::<Eqeqeq>("a!=b;", 1);
+    assert_lint_err::<Eqeqeq>("(a + b) == c;", 8);
+    assert_lint_err::<Eqeqeq>("(a + b)  !=  c;", 9);
+    assert_lint_err::<Eqeqeq>("((1) )  ==  (2);", 8);
+    assert_lint_err::<Eqeqeq>("a /* == */ == b", 11);
+  }
+
+  #[test]
+  fn eqeqeq_nested() {
+    assert_lint_err_n::<Eqeqeq>("(a == b) == (c)", vec![3, 9]);
+    assert_lint_err_n::<Eqeqeq>("(a != b) != (c)", vec![3, 9]);
+    assert_lint_err::<Eqeqeq>("(a == b) === (c)", 3);
+    assert_lint_err::<Eqeqeq>("(a == b) !== (c)", 3);
+    assert_lint_err::<Eqeqeq>("(a === b) == (c)", 10);
+    assert_lint_err::<Eqeqeq>("(a === b) != (c)", 10);
+    assert_lint_err::<Eqeqeq>("a == (b == null)", 2);
+    assert_lint_err_n::<Eqeqeq>("f(a == b, c != d)", vec![4, 12]);
+  }
+
+  #[test]
+  fn eqeqeq_null_comparison() {
+    assert_lint_err_on_line_n_with(disallow_null(), "a == null", vec![(1, 2)]);
+    assert_lint_err_on_line_n_with(disallow_null(), "null != a", vec![(1, 5)]);
+    assert_lint_err_on_line_n_with(
+      disallow_null(),
+      "true == null",
+      vec![(1, 5)],
+    );
+    assert_lint_err_on_line_n_with(
+      disallow_null(),
+      "true != null",
+      vec![(1, 5)],
+    );
+    assert_lint_err_on_line_n_with(
+      disallow_null(),
+      "null == null",
+      vec![(1, 5)],
+    );
+    assert_lint_err_on_line_n_with(
+      disallow_null(),
+      "null != null",
+      vec![(1, 5)],
+    );
+    assert_lint_ok_with(disallow_null(), "a === null");
+  }
+
+  #[test]
+  fn eqeqeq_template_literals() {
+    assert_lint_err::<Eqeqeq>("`a` == b", 4);
+    assert_lint_err::<Eqeqeq>("a != `${b}`", 2);
+    assert_lint_err::<Eqeqeq>("`${a}` == `${b}`", 7);
+    assert_lint_err::<Eqeqeq>("typeof a == `string`", 9);
+  }
+
+  #[test]
+  fn eqeqeq_message() {
+    let diagnostics = lint(Eqeqeq::new(), "a != b");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "expected '!==' and instead saw '!='."
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use `!==` after making sure both operands have the same type")
+    );
+    assert_eq!(diagnostics[0].range.end.col, 4);
+  }
+
+  #[test]
+  fn eqeqeq_fix() {
+    let fix = |rule: Box<Eqeqeq>, source: &str| {
+      let diagnostics = lint(rule, source);
+      assert_eq!(diagnostics.len(), 1, "{}", source);
+      diagnostics[0]
+        .fix
+        .as_ref()
+        .map(|edits| apply_edits(source, edits))
+    };
+
+    assert_eq!(
+      fix(Eqeqeq::new(), "typeof a == 'number'"),
+      Some("typeof a === 'number'".to_string())
+    );
+    assert_eq!(
+      fix(Eqeqeq::new(), "`string` != (typeof a)"),
+      Some("`string` !== (typeof a)".to_string())
+    );
+    assert_eq!(fix(Eqeqeq::new(), "1 == 2"), Some("1 === 2".to_string()));
+    assert_eq!(
+      fix(disallow_null(), "null != null"),
+      Some("null !== null".to_string())
+    );
+    assert_eq!(fix(Eqeqeq::new(), "a == b"), None);
+    assert_eq!(fix(Eqeqeq::new(), "1 == '1'"), None);
+    assert_eq!(fix(Eqeqeq::new(), "typeof a == `${b}`"), None);
+    assert_eq!(fix(disallow_null(), "a == null"), None);
   }
 }
@@ -1,61 +1,429 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::visit::noop_visit_type;
+use crate::media_type::MediaType;
+use crate::swc_util::unwrap_paren;
+use std::collections::HashSet;
+use swc_common::{BytePos, Span, Spanned};
+use swc_ecmascript::ast::{
+  ArrowExpr, BlockStmt, BlockStmtOrExpr, CallExpr, ClassMethod, ClassProp,
+  DefaultDecl, ExportDefaultDecl, ExportDefaultExpr, Expr, ExprOrSpread,
+  FnDecl, FnExpr, Function, MethodKind, NewExpr, Pat, PrivateMethod, Prop,
+  PropOrSpread, ReturnStmt, Stmt, TsAsExpr, TsTypeAssertion, VarDeclarator,
+};
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
-pub struct ExplicitFunctionReturnType;
+#[derive(Clone, Copy)]
+pub struct ExplicitFunctionReturnTypeOptions {
+  /// Skips function expressions which aren't the value of a variable, a
+  /// class property or a default export, eg. callbacks.
+  pub allow_expressions: bool,
+  /// Skips function expressions whose type is given by where they are
+  /// written, ie. the value of a variable or class property with a type
+  /// annotation, a call argument, the operand of `as` or a property of an
+  /// object literal in one of these places.
+  pub allow_typed_function_expressions: bool,
+  /// Skips functions whose body only returns another function, which is
+  /// checked itself.
+  pub allow_higher_order_functions: bool,
+}
+
+impl Default for ExplicitFunctionReturnTypeOptions {
+  fn default() -> Self {
+    Self {
+      allow_expressions: false,
+      allow_typed_function_expressions: true,
+      allow_higher_order_functions: true,
+    }
+  }
+}
+
+/// Reports functions and methods without a return type annotation.
+/// Constructors, setters and declaration files are never reported.
+pub struct ExplicitFunctionReturnType {
+  options: ExplicitFunctionReturnTypeOptions,
+}
+
+impl ExplicitFunctionReturnType {
+  pub fn with_options(options: ExplicitFunctionReturnTypeOptions) -> Box<Self> {
+    Box::new(ExplicitFunctionReturnType { options })
+  }
+}
 
 impl LintRule for ExplicitFunctionReturnType {
   fn new() -> Box<Self> {
-    Box::new(ExplicitFunctionReturnType)
+    Self::with_options(ExplicitFunctionReturnTypeOptions::default())
   }
 
   fn code(&self) -> &'static str {
     "explicit-function-return-type"
   }
 
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires functions to have an explicit return type.
+
+Explicit return types make the intent of a function clear and catch returning a value of the wrong type where the function is defined rather than where it's used. Constructors and setters can't have a return type and are not checked, neither are declaration files (`.d.ts`).
+
+### Options
+
+- `ExplicitFunctionReturnTypeOptions::allow_expressions`: skips function expressions which aren't the value of a variable, a class property or a default export, eg. callbacks, `false` by default.
+- `ExplicitFunctionReturnTypeOptions::allow_typed_function_expressions`: skips function expressions whose type is given by where they are written, eg. `const f: Handler = () => {}` or call arguments, `true` by default.
+- `ExplicitFunctionReturnTypeOptions::allow_higher_order_functions`: skips functions whose body only returns another function, `true` by default.
+
+### Valid:
+```typescript
+function fooTyped(): void {}
+```
+```typescript
+const bar = function (): string {
+  return "bar";
+};
+```
+```typescript
+type Handler = (event: string) => void;
+const handler: Handler = (event) => {};
+```
+```typescript
+function adder(a: number) {
+  return (b: number): number => a + b;
+}
+```
+
+### Invalid:
+```typescript
+function foo() {}
+```
+```typescript
+const bar = function () {
+  return "bar";
+};
+```
+```typescript
+class Foo {
+  bar() {}
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     module: &swc_ecmascript::ast::Module,
   ) {
-    let mut visitor = ExplicitFunctionReturnTypeVisitor::new(context);
+    if !context.media_type.is_typescript()
+      || context.media_type == MediaType::Dts
+    {
+      return;
+    }
+    let mut visitor = ExplicitFunctionReturnTypeVisitor {
+      context,
+      options: self.options,
+      declared: HashSet::new(),
+      typed: HashSet::new(),
+    };
     visitor.visit_module(module, module);
   }
 }
 
 struct ExplicitFunctionReturnTypeVisitor {
   context: Arc<Context>,
+  options: ExplicitFunctionReturnTypeOptions,
+  /// Spans of the function expressions which are the value of a variable,
+  /// a class property or a default export.
+  declared: HashSet<Span>,
+  /// Spans of the function expressions in a typed position.
+  typed: HashSet<Span>,
+}
+
+/// Checks if the body only returns a function, eg. `() => () => {}`.
+fn returns_function(body: &BlockStmtOrExpr) -> bool {
+  match body {
+    BlockStmtOrExpr::Expr(expr) => is_function(expr),
+    BlockStmtOrExpr::BlockStmt(block_stmt) => {
+      block_returns_function(block_stmt)
+    }
+  }
+}
+
+fn block_returns_function(block_stmt: &BlockStmt) -> bool {
+  match block_stmt.stmts.as_slice() {
+    [Stmt::Return(ReturnStmt { arg: Some(arg), .. })] => is_function(arg),
+    _ => false,
+  }
+}
+
+fn is_function(expr: &Expr) -> bool {
+  matches!(unwrap_paren(expr), Expr::Fn(_) | Expr::Arrow(_))
 }
 
 impl ExplicitFunctionReturnTypeVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
+  /// Marks the function expressions `expr` consists of as typed, descending
+  /// into object literals.
+  fn mark_typed(&mut self, expr: &Expr) {
+    match unwrap_paren(expr) {
+      Expr::Fn(fn_expr) => {
+        self.typed.insert(fn_expr.function.span);
+      }
+      Expr::Arrow(arrow_expr) => {
+        self.typed.insert(arrow_expr.span);
+      }
+      Expr::Object(object_lit) => {
+        for prop in &object_lit.props {
+          if let PropOrSpread::Prop(prop) = prop {
+            match &**prop {
+              Prop::KeyValue(key_value) => self.mark_typed(&key_value.value),
+              Prop::Method(method) => {
+                self.typed.insert(method.function.span);
+              }
+              _ => {}
+            }
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+
+  fn mark_declared(&mut self, expr: &Expr) {
+    match unwrap_paren(expr) {
+      Expr::Fn(fn_expr) => {
+        self.declared.insert(fn_expr.function.span);
+      }
+      Expr::Arrow(arrow_expr) => {
+        self.declared.insert(arrow_expr.span);
+      }
+      _ => {}
+    }
+  }
+
+  fn mark_typed_args(&mut self, args: &[ExprOrSpread]) {
+    for arg in args {
+      if arg.spread.is_none() {
+        self.mark_typed(&arg.expr);
+      }
+    }
+  }
+
+  /// Whether the function expression with `span` is skipped by the options.
+  fn is_allowed_expression(&self, span: Span) -> bool {
+    (self.options.allow_expressions && !self.declared.contains(&span))
+      || (self.options.allow_typed_function_expressions
+        && self.typed.contains(&span))
+  }
+
+  fn check_function(&self, function: &Function) {
+    let body = match &function.body {
+      Some(body) => body,
+      // Overloads and abstract methods
+      None => return,
+    };
+    if function.return_type.is_some() {
+      return;
+    }
+    if self.options.allow_higher_order_functions && block_returns_function(body)
+    {
+      return;
+    }
+    let params_end = match function.params.last() {
+      Some(param) => param.span.hi,
+      None => function.span.lo,
+    };
+    self.report(params_end, body.span.lo, function.span);
+  }
+
+  fn check_arrow(&self, arrow_expr: &ArrowExpr) {
+    if arrow_expr.return_type.is_some() {
+      return;
+    }
+    if self.options.allow_higher_order_functions
+      && returns_function(&arrow_expr.body)
+    {
+      return;
+    }
+    let params_end = match arrow_expr.params.last() {
+      Some(param) => param.span().hi,
+      None => arrow_expr.span.lo,
+    };
+    let fallback = match arrow_expr.params.as_slice() {
+      // `a => a` has no parentheses
+      [param] => param.span(),
+      _ => arrow_expr.span,
+    };
+    self.report(params_end, arrow_expr.body.span().lo, fallback);
+  }
+
+  /// Reports the closing parenthesis of the parameters, which is the last
+  /// one between the end of the parameters and the body.
+  fn report(&self, params_end: BytePos, body_start: BytePos, fallback: Span) {
+    let text = self.context.span_text(Span::new(
+      params_end,
+      body_start,
+      Default::default(),
+    ));
+    let span = match text.rfind(')') {
+      Some(index) => {
+        let lo = params_end + BytePos(index as u32);
+        Span::new(lo, lo + BytePos(1), Default::default())
+      }
+      None => fallback,
+    };
+    self.context.add_diagnostic_with_hint(
+      span,
+      "explicit-function-return-type",
+      "Missing return type on function",
+      "Add a return type annotation after the parameters, eg. `: void`",
+    );
   }
 }
 
+// Types aren't skipped, as type assertions contain expressions
 impl Visit for ExplicitFunctionReturnTypeVisitor {
-  noop_visit_type!();
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, parent: &dyn Node) {
+    self.check_function(&fn_decl.function);
+    swc_ecmascript::visit::visit_fn_decl(self, fn_decl, parent);
+  }
 
-  fn visit_function(
+  fn visit_class_method(
     &mut self,
-    function: &swc_ecmascript::ast::Function,
-    _parent: &dyn Node,
+    class_method: &ClassMethod,
+    parent: &dyn Node,
   ) {
-    if function.return_type.is_none() {
-      self.context.add_diagnostic(
-        function.span,
-        "explicit-function-return-type",
-        "Missing return type on function",
-      );
+    if class_method.kind != MethodKind::Setter {
+      self.check_function(&class_method.function);
     }
-    for stmt in &function.body {
-      self.visit_block_stmt(stmt, _parent);
+    swc_ecmascript::visit::visit_class_method(self, class_method, parent);
+  }
+
+  fn visit_private_method(
+    &mut self,
+    private_method: &PrivateMethod,
+    parent: &dyn Node,
+  ) {
+    if private_method.kind != MethodKind::Setter {
+      self.check_function(&private_method.function);
+    }
+    swc_ecmascript::visit::visit_private_method(self, private_method, parent);
+  }
+
+  fn visit_prop(&mut self, prop: &Prop, parent: &dyn Node) {
+    // Methods of object literals are function expressions
+    if let Prop::Method(method) = prop {
+      if !self.is_allowed_expression(method.function.span) {
+        self.check_function(&method.function);
+      }
+    }
+    swc_ecmascript::visit::visit_prop(self, prop, parent);
+  }
+
+  fn visit_fn_expr(&mut self, fn_expr: &FnExpr, parent: &dyn Node) {
+    if !self.is_allowed_expression(fn_expr.function.span) {
+      self.check_function(&fn_expr.function);
+    }
+    swc_ecmascript::visit::visit_fn_expr(self, fn_expr, parent);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+    if !self.is_allowed_expression(arrow_expr.span) {
+      self.check_arrow(arrow_expr);
+    }
+    swc_ecmascript::visit::visit_arrow_expr(self, arrow_expr, parent);
+  }
+
+  fn visit_var_declarator(
+    &mut self,
+    var_declarator: &VarDeclarator,
+    parent: &dyn Node,
+  ) {
+    if let Some(init) = &var_declarator.init {
+      self.mark_declared(init);
+      let has_type_ann = match &var_declarator.name {
+        Pat::Ident(ident) => ident.type_ann.is_some(),
+        Pat::Array(array_pat) => array_pat.type_ann.is_some(),
+        Pat::Object(object_pat) => object_pat.type_ann.is_some(),
+        _ => false,
+      };
+      if has_type_ann {
+        self.mark_typed(init);
+      }
     }
+    swc_ecmascript::visit::visit_var_declarator(self, var_declarator, parent);
+  }
+
+  fn visit_class_prop(&mut self, class_prop: &ClassProp, parent: &dyn Node) {
+    if let Some(value) = &class_prop.value {
+      self.mark_declared(value);
+      if class_prop.type_ann.is_some() {
+        self.mark_typed(value);
+      }
+    }
+    swc_ecmascript::visit::visit_class_prop(self, class_prop, parent);
+  }
+
+  fn visit_export_default_expr(
+    &mut self,
+    export_default_expr: &ExportDefaultExpr,
+    parent: &dyn Node,
+  ) {
+    self.mark_declared(&export_default_expr.expr);
+    swc_ecmascript::visit::visit_export_default_expr(
+      self,
+      export_default_expr,
+      parent,
+    );
+  }
+
+  fn visit_export_default_decl(
+    &mut self,
+    export_default_decl: &ExportDefaultDecl,
+    parent: &dyn Node,
+  ) {
+    // `export default function () {}` is a declaration
+    if let DefaultDecl::Fn(fn_expr) = &export_default_decl.decl {
+      self.declared.insert(fn_expr.function.span);
+    }
+    swc_ecmascript::visit::visit_export_default_decl(
+      self,
+      export_default_decl,
+      parent,
+    );
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    // The callee of an IIFE isn't typed
+    self.mark_typed_args(&call_expr.args);
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+    if let Some(args) = &new_expr.args {
+      self.mark_typed_args(args);
+    }
+    swc_ecmascript::visit::visit_new_expr(self, new_expr, parent);
+  }
+
+  fn visit_ts_as_expr(&mut self, ts_as_expr: &TsAsExpr, parent: &dyn Node) {
+    self.mark_typed(&ts_as_expr.expr);
+    swc_ecmascript::visit::visit_ts_as_expr(self, ts_as_expr, parent);
+  }
+
+  fn visit_ts_type_assertion(
+    &mut self,
+    type_assertion: &TsTypeAssertion,
+    parent: &dyn Node,
+  ) {
+    self.mark_typed(&type_assertion.expr);
+    swc_ecmascript::visit::visit_ts_type_assertion(
+      self,
+      type_assertion,
+      parent,
+    );
   }
 }
 
@@ -64,25 +432,201 @@ mod tests {
   use super::*;
   use crate::test_util::*;
 
+  fn with_options(
+    options: ExplicitFunctionReturnTypeOptions,
+  ) -> Box<ExplicitFunctionReturnType> {
+    ExplicitFunctionReturnType::with_options(options)
+  }
+
   #[test]
   fn explicit_function_return_type_valid() {
     assert_lint_ok_n::<ExplicitFunctionReturnType>(vec![
       "function fooTyped(): void { }",
-      "const bar = (a: string) => { }",
       "const barTyped = (a: string): Promise<void> => { }",
+      "const bar = function (): string { return 'bar'; };",
+      "class Foo { constructor() {} set a(value: number) {} }",
+      "class Foo { #a(): void {} get b(): number { return 1; } }",
+      "function foo(a: string): void;\nfunction foo(a: string): void {}",
+      "abstract class Foo { abstract bar(); }",
+      "const a = { b(): void {} };",
     ]);
   }
 
   #[test]
   fn explicit_function_return_type_invalid() {
-    assert_lint_err::<ExplicitFunctionReturnType>("function foo() { }", 0);
+    assert_lint_err::<ExplicitFunctionReturnType>("function foo() { }", 13);
     assert_lint_err_on_line_n::<ExplicitFunctionReturnType>(
       r#"
 function a() {
   function b() {}
 }
       "#,
-      vec![(2, 0), (3, 2)],
+      vec![(2, 11), (3, 13)],
+    );
+    assert_lint_err::<ExplicitFunctionReturnType>(
+      "const bar = (a: string) => { }",
+      22,
+    );
+    assert_lint_err::<ExplicitFunctionReturnType>(
+      "const bar = function (a: string, b = (1)) { }",
+      40,
+    );
+    assert_lint_err::<ExplicitFunctionReturnType>("const bar = a => a;", 12);
+    assert_lint_err::<ExplicitFunctionReturnType>(
+      "class Foo { bar<T>(a: T) {} }",
+      23,
+    );
+    assert_lint_err::<ExplicitFunctionReturnType>(
+      "class Foo { get bar() { return 1; } }",
+      20,
+    );
+    assert_lint_err::<ExplicitFunctionReturnType>(
+      "class Foo { #bar() {} }",
+      17,
+    );
+    assert_lint_err::<ExplicitFunctionReturnType>(
+      "export default function () {}",
+      25,
+    );
+    assert_lint_err::<ExplicitFunctionReturnType>("const a = { b() {} };", 14);
+
+    let diagnostics =
+      lint(ExplicitFunctionReturnType::new(), "function foo(a, b) {}");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 17);
+    assert_eq!(diagnostics[0].range.end.col, 18);
+    assert_eq!(diagnostics[0].message, "Missing return type on function");
+  }
+
+  #[test]
+  fn explicit_function_return_type_declaration_files() {
+    assert!(lint_as(
+      ExplicitFunctionReturnType::new(),
+      "foo.d.ts",
+      "declare function foo();\ndeclare class Foo { bar(); }",
+    )
+    .is_empty());
+    assert!(lint_as(
+      ExplicitFunctionReturnType::new(),
+      "foo.js",
+      "function foo() {}"
+    )
+    .is_empty());
+  }
+
+  #[test]
+  fn explicit_function_return_type_typed_expressions() {
+    assert_lint_ok_n::<ExplicitFunctionReturnType>(vec![
+      "type Handler = () => void;\nconst f: Handler = () => {};",
+      "const f: Handler = function () {};",
+      "const f: Handler = (() => {});",
+      "const { a }: Handlers = { a: () => {} };",
+      "const handlers: Handlers = { a() {}, b: () => {}, c: { d() {} } };",
+      "class Foo { handler: Handler = () => {}; }",
+      "const f = (() => {}) as Handler;",
+      "const f = <Handler>(() => {});",
+      "[1, 2].map((n) => n * 2);",
+      "foo(function () {});",
+      "new Promise((resolve) => resolve(1));",
+      "app.get('/', { handler: (req) => {} });",
+      "function foo(cb: (n: number) => void): void {}\nfoo((n) => {});",
+    ]);
+
+    let untyped = || {
+      with_options(ExplicitFunctionReturnTypeOptions {
+        allow_typed_function_expressions: false,
+        ..Default::default()
+      })
+    };
+    assert_lint_err_on_line_n_with(
+      untyped(),
+      "const f: Handler = () => {};",
+      vec![(1, 20)],
+    );
+    assert_lint_err_on_line_n_with(
+      untyped(),
+      "[1, 2].map((n) => n * 2);",
+      vec![(1, 13)],
+    );
+    assert_lint_err_on_line_n_with(
+      untyped(),
+      "const handlers: Handlers = { a() {} };",
+      vec![(1, 31)],
+    );
+  }
+
+  #[test]
+  fn explicit_function_return_type_iife() {
+    assert_lint_err::<ExplicitFunctionReturnType>("(() => {})();", 2);
+    assert_lint_err::<ExplicitFunctionReturnType>("(function () {})();", 11);
+    assert_lint_err::<ExplicitFunctionReturnType>("(async () => {})();", 8);
+
+    let allow_expressions = with_options(ExplicitFunctionReturnTypeOptions {
+      allow_expressions: true,
+      ..Default::default()
+    });
+    assert_lint_ok_with(allow_expressions, "(() => {})();");
+  }
+
+  #[test]
+  fn explicit_function_return_type_allow_expressions() {
+    let allow_expressions = || {
+      with_options(ExplicitFunctionReturnTypeOptions {
+        allow_expressions: true,
+        allow_typed_function_expressions: false,
+        ..Default::default()
+      })
+    };
+    assert_lint_ok_with(allow_expressions(), "foo(() => {});");
+    assert_lint_ok_with(allow_expressions(), "const a = { b() {} };");
+    assert_lint_ok_with(allow_expressions(), "node.on('a', function () {});");
+    assert_lint_err_on_line_n_with(
+      allow_expressions(),
+      "const foo = () => {};",
+      vec![(1, 13)],
+    );
+    assert_lint_err_on_line_n_with(
+      allow_expressions(),
+      "class Foo { bar = function () {}; }",
+      vec![(1, 28)],
+    );
+    assert_lint_err_on_line_n_with(
+      allow_expressions(),
+      "export default () => {};",
+      vec![(1, 16)],
+    );
+    assert_lint_err_on_line_n_with(
+      allow_expressions(),
+      "function foo() {}",
+      vec![(1, 13)],
+    );
+  }
+
+  #[test]
+  fn explicit_function_return_type_higher_order_functions() {
+    assert_lint_ok_n::<ExplicitFunctionReturnType>(vec![
+      "const adder = (a: number) => (b: number): number => a + b;",
+      "function adder(a: number) { return (b: number): number => a + b; }",
+      "function adder(a: number) { return function (b: number): number { return a + b; }; }",
+      "const curried = () => () => (): void => {};",
+    ]);
+    assert_lint_err::<ExplicitFunctionReturnType>(
+      "const adder = (a: number) => (b: number) => a + b;",
+      39,
+    );
+    assert_lint_err::<ExplicitFunctionReturnType>(
+      "function foo() { bar(); return (): void => {}; }",
+      13,
+    );
+
+    let disallowed = with_options(ExplicitFunctionReturnTypeOptions {
+      allow_higher_order_functions: false,
+      ..Default::default()
+    });
+    assert_lint_err_on_line_n_with(
+      disallowed,
+      "const adder = (a: number) => (b: number): number => a + b;",
+      vec![(1, 24)],
     );
   }
 }
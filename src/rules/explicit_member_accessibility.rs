@@ -0,0 +1,504 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::sync::Arc;
+use swc_common::BytePos;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  Accessibility, ClassMember, ClassMethod, Constructor, MethodKind,
+  ParamOrTsParamProp, TsParamProp, TsParamPropParam,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExplicitMemberAccessibilityMode {
+  /// Every member needs `public`, `protected` or `private`.
+  Explicit,
+  /// The `public` keyword is redundant and reported.
+  NoPublic,
+  /// Members are not checked.
+  Off,
+}
+
+/// Modes for specific kinds of members, taking precedence over
+/// `ExplicitMemberAccessibilityOptions::accessibility`.
+#[derive(Default)]
+pub struct ExplicitMemberAccessibilityOverrides {
+  pub constructors: Option<ExplicitMemberAccessibilityMode>,
+  pub parameter_properties: Option<ExplicitMemberAccessibilityMode>,
+  pub accessors: Option<ExplicitMemberAccessibilityMode>,
+  pub properties: Option<ExplicitMemberAccessibilityMode>,
+  pub methods: Option<ExplicitMemberAccessibilityMode>,
+}
+
+pub struct ExplicitMemberAccessibilityOptions {
+  pub accessibility: ExplicitMemberAccessibilityMode,
+  pub overrides: ExplicitMemberAccessibilityOverrides,
+}
+
+impl Default for ExplicitMemberAccessibilityOptions {
+  fn default() -> Self {
+    Self {
+      accessibility: ExplicitMemberAccessibilityMode::Explicit,
+      overrides: Default::default(),
+    }
+  }
+}
+
+pub struct ExplicitMemberAccessibility {
+  options: ExplicitMemberAccessibilityOptions,
+}
+
+impl ExplicitMemberAccessibility {
+  pub fn with_options(
+    options: ExplicitMemberAccessibilityOptions,
+  ) -> Box<Self> {
+    Box::new(ExplicitMemberAccessibility { options })
+  }
+}
+
+impl LintRule for ExplicitMemberAccessibility {
+  fn new() -> Box<Self> {
+    Self::with_options(ExplicitMemberAccessibilityOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "explicit-member-accessibility"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires an explicit accessibility modifier, `public`, `protected` or `private`, on class members.
+
+Private names like `#x` already have their accessibility and are not checked.
+
+### Options
+
+- `ExplicitMemberAccessibilityOptions::accessibility`:
+  - `ExplicitMemberAccessibilityMode::Explicit` (default): every member needs a modifier.
+  - `ExplicitMemberAccessibilityMode::NoPublic`: the redundant `public` keyword is reported.
+  - `ExplicitMemberAccessibilityMode::Off`: members are not checked.
+- `ExplicitMemberAccessibilityOptions::overrides`: modes for `constructors`, `parameter_properties`, `accessors`, `properties` and `methods`, taking precedence over `accessibility`.
+
+### Valid:
+```typescript
+class A {
+  public constructor(private readonly x: number) {}
+  protected static y: string;
+  public foo() {}
+  #bar = 2;
+}
+```
+
+### Invalid:
+```typescript
+class A {
+  x = 1;
+}
+```
+```typescript
+class A {
+  foo() {}
+}
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor =
+      ExplicitMemberAccessibilityVisitor::new(context, &self.options);
+    visitor.visit_module(module, module);
+  }
+}
+
+#[derive(Clone, Copy)]
+enum MemberKind {
+  Constructor,
+  ParameterProperty,
+  Accessor,
+  Property,
+  Method,
+}
+
+impl MemberKind {
+  fn as_str(self) -> &'static str {
+    match self {
+      MemberKind::Constructor => "constructor",
+      MemberKind::ParameterProperty => "parameter property",
+      MemberKind::Accessor => "accessor",
+      MemberKind::Property => "property",
+      MemberKind::Method => "method",
+    }
+  }
+}
+
+/// A member which can have an accessibility modifier.
+struct Member {
+  kind: MemberKind,
+  name: String,
+  span: Span,
+  /// Start of the member name, the modifiers come before it.
+  key_start: BytePos,
+  accessibility: Option<Accessibility>,
+}
+
+struct ExplicitMemberAccessibilityVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a ExplicitMemberAccessibilityOptions,
+}
+
+impl<'a> ExplicitMemberAccessibilityVisitor<'a> {
+  fn new(
+    context: Arc<Context>,
+    options: &'a ExplicitMemberAccessibilityOptions,
+  ) -> Self {
+    Self { context, options }
+  }
+
+  fn mode(&self, kind: MemberKind) -> ExplicitMemberAccessibilityMode {
+    let overrides = &self.options.overrides;
+    let mode = match kind {
+      MemberKind::Constructor => overrides.constructors,
+      MemberKind::ParameterProperty => overrides.parameter_properties,
+      MemberKind::Accessor => overrides.accessors,
+      MemberKind::Property => overrides.properties,
+      MemberKind::Method => overrides.methods,
+    };
+    mode.unwrap_or(self.options.accessibility)
+  }
+
+  /// Finds the `public` keyword among the modifiers of a member. The AST
+  /// only records that the modifier is present, so it's looked up in the
+  /// source text between the start of the member and its name.
+  fn public_keyword_span(&self, member: &Member) -> Option<Span> {
+    let modifiers_span =
+      Span::new(member.span.lo(), member.key_start, Default::default());
+    let text = self.context.span_text(modifiers_span);
+    text.match_indices("public").find_map(|(start, keyword)| {
+      let end = start + keyword.len();
+      let before = text[..start].chars().next_back();
+      let after = text[end..].chars().next();
+      let is_word = matches!(before, None | Some(' ' | '\t' | '\n' | '\r'))
+        && matches!(after, Some(' ' | '\t' | '\n' | '\r'));
+      if is_word {
+        Some(Span::new(
+          member.span.lo() + BytePos(start as u32),
+          member.span.lo() + BytePos(end as u32),
+          Default::default(),
+        ))
+      } else {
+        None
+      }
+    })
+  }
+
+  fn check(&self, member: Member) {
+    match self.mode(member.kind) {
+      ExplicitMemberAccessibilityMode::Explicit => {
+        if member.accessibility.is_none() {
+          self.context.add_diagnostic_with_hint(
+            member.span,
+            "explicit-member-accessibility",
+            &format!(
+              "Missing accessibility modifier on {} `{}`",
+              member.kind.as_str(),
+              member.name
+            ),
+            &format!(
+              "Add `public`, `protected` or `private` to `{}`",
+              member.name
+            ),
+          );
+        }
+      }
+      ExplicitMemberAccessibilityMode::NoPublic => {
+        if member.accessibility == Some(Accessibility::Public) {
+          let span = self.public_keyword_span(&member).unwrap_or(member.span);
+          self.context.add_diagnostic_with_hint(
+            span,
+            "explicit-member-accessibility",
+            &format!(
+              "Public accessibility modifier on {} `{}` is redundant",
+              member.kind.as_str(),
+              member.name
+            ),
+            &format!("Remove `public` from `{}`", member.name),
+          );
+        }
+      }
+      ExplicitMemberAccessibilityMode::Off => {}
+    }
+  }
+
+  fn check_constructor(&self, constructor: &Constructor) {
+    self.check(Member {
+      kind: MemberKind::Constructor,
+      name: "constructor".to_string(),
+      span: constructor.span,
+      key_start: constructor.key.span().lo(),
+      accessibility: constructor.accessibility,
+    });
+
+    for param in &constructor.params {
+      if let ParamOrTsParamProp::TsParamProp(prop) = param {
+        self.check_param_prop(prop);
+      }
+    }
+  }
+
+  fn check_param_prop(&self, prop: &TsParamProp) {
+    let (name, key_start) = match &prop.param {
+      TsParamPropParam::Ident(ident) => {
+        (ident.sym.to_string(), ident.span.lo())
+      }
+      TsParamPropParam::Assign(assign) => {
+        (self.context.span_text(assign.left.span()), assign.span.lo())
+      }
+    };
+    // `constructor(public x)` is only a property because of the modifier,
+    // removing it would turn `x` into a plain parameter.
+    if prop.accessibility == Some(Accessibility::Public)
+      && !prop.readonly
+      && self.mode(MemberKind::ParameterProperty)
+        == ExplicitMemberAccessibilityMode::NoPublic
+    {
+      return;
+    }
+    self.check(Member {
+      kind: MemberKind::ParameterProperty,
+      name,
+      span: prop.span,
+      key_start,
+      accessibility: prop.accessibility,
+    });
+  }
+
+  fn check_method(&self, method: &ClassMethod) {
+    let kind = match method.kind {
+      MethodKind::Method => MemberKind::Method,
+      MethodKind::Getter | MethodKind::Setter => MemberKind::Accessor,
+    };
+    self.check(Member {
+      kind,
+      name: self.context.span_text(method.key.span()),
+      span: method.span,
+      key_start: method.key.span().lo(),
+      accessibility: method.accessibility,
+    });
+  }
+}
+
+impl<'a> Visit for ExplicitMemberAccessibilityVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_class_member(&mut self, member: &ClassMember, parent: &dyn Node) {
+    match member {
+      ClassMember::Constructor(constructor) => {
+        self.check_constructor(constructor)
+      }
+      ClassMember::Method(method) => self.check_method(method),
+      ClassMember::ClassProp(prop) => self.check(Member {
+        kind: MemberKind::Property,
+        name: self.context.span_text(prop.key.span()),
+        span: prop.span,
+        key_start: prop.key.span().lo(),
+        accessibility: prop.accessibility,
+      }),
+      // `#private` members can't have an accessibility modifier.
+      ClassMember::PrivateMethod(_)
+      | ClassMember::PrivateProp(_)
+      | ClassMember::TsIndexSignature(_)
+      | ClassMember::Empty(_) => {}
+    }
+    swc_ecmascript::visit::visit_class_member(self, member, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn no_public() -> Box<ExplicitMemberAccessibility> {
+    ExplicitMemberAccessibility::with_options(
+      ExplicitMemberAccessibilityOptions {
+        accessibility: ExplicitMemberAccessibilityMode::NoPublic,
+        ..Default::default()
+      },
+    )
+  }
+
+  #[test]
+  fn explicit_member_accessibility_valid() {
+    assert_lint_ok_n::<ExplicitMemberAccessibility>(vec![
+      r#"
+class A {
+  public constructor(x: number) {}
+  private x = 1;
+  protected static y: string;
+  public get z() { return 1; }
+  public set z(value) {}
+  public foo() {}
+  #bar = 2;
+  #baz() {}
+}
+      "#,
+      r#"
+abstract class A {
+  public abstract foo(): void;
+  protected abstract bar: number;
+}
+      "#,
+      r#"
+class A {
+  public constructor(private readonly x: number, y: string) {}
+}
+      "#,
+      r#"
+class A {
+  [key: string]: unknown;
+}
+      "#,
+    ]);
+  }
+
+  #[test]
+  fn explicit_member_accessibility_invalid() {
+    assert_lint_err_on_line_n::<ExplicitMemberAccessibility>(
+      r#"
+class A {
+  constructor() {}
+  x = 1;
+  static y: string;
+  get z() { return 1; }
+  foo() {}
+}
+      "#,
+      vec![(3, 2), (4, 2), (5, 2), (6, 2), (7, 2)],
+    );
+    assert_lint_err_on_line::<ExplicitMemberAccessibility>(
+      r#"
+abstract class A {
+  abstract foo(): void;
+}
+      "#,
+      3,
+      2,
+    );
+
+    let diagnostics = lint(
+      ExplicitMemberAccessibility::new(),
+      "class A { static foo() {} }",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "Missing accessibility modifier on method `foo`"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Add `public`, `protected` or `private` to `foo`")
+    );
+  }
+
+  #[test]
+  fn explicit_member_accessibility_parameter_properties() {
+    // Only `readonly x` is reported, the constructor has a modifier and
+    // `y` isn't a property.
+    assert_lint_err_on_line_n::<ExplicitMemberAccessibility>(
+      r#"
+class A {
+  public constructor(readonly x: number, y: string) {}
+}
+      "#,
+      vec![(3, 21)],
+    );
+    let diagnostics = lint(
+      ExplicitMemberAccessibility::new(),
+      "class A { public constructor(readonly x = 1) {} }",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Missing accessibility modifier on parameter property `x`"
+    );
+  }
+
+  #[test]
+  fn explicit_member_accessibility_no_public() {
+    for source in &[
+      "class A { constructor() {} x = 1; foo() {} }",
+      "class A { private x = 1; protected foo() {} }",
+      "class A { constructor(public x: number) {} }",
+    ] {
+      assert_lint_ok_with(no_public(), source);
+    }
+    // `public` is reserved in modules, but still parsed as a decorator
+    with_syntax_errors(|| {
+      assert_lint_ok_with(no_public(), "class A { @public() foo() {} }");
+    });
+
+    assert_lint_err_on_line_n_with(
+      no_public(),
+      r#"
+class A {
+  public constructor() {}
+  public static x = 1;
+  public abstract foo(): void;
+  public get z() { return 1; }
+}
+      "#,
+      vec![(3, 2), (4, 2), (5, 2), (6, 2)],
+    );
+    assert_lint_err_on_line_n_with(
+      no_public(),
+      "class A { constructor(public readonly x: number) {} }",
+      vec![(1, 22)],
+    );
+
+    let diagnostics = lint(no_public(), "class A { public foo() {} }");
+    assert_eq!(diagnostics[0].range.start.col, 10);
+    assert_eq!(diagnostics[0].range.end.col, 16);
+    assert_eq!(
+      diagnostics[0].message,
+      "Public accessibility modifier on method `foo` is redundant"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Remove `public` from `foo`")
+    );
+  }
+
+  #[test]
+  fn explicit_member_accessibility_overrides() {
+    let rule = || {
+      ExplicitMemberAccessibility::with_options(
+        ExplicitMemberAccessibilityOptions {
+          accessibility: ExplicitMemberAccessibilityMode::Explicit,
+          overrides: ExplicitMemberAccessibilityOverrides {
+            constructors: Some(ExplicitMemberAccessibilityMode::Off),
+            accessors: Some(ExplicitMemberAccessibilityMode::NoPublic),
+            ..Default::default()
+          },
+        },
+      )
+    };
+    assert_lint_ok_with(
+      rule(),
+      "class A { constructor() {} get x() { return 1; } private y = 1; }",
+    );
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "class A { public get x() { return 1; } foo() {} }",
+      vec![(1, 10), (1, 39)],
+    );
+  }
+}
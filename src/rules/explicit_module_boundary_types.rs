@@ -23,6 +23,37 @@ impl LintRule for ExplicitModuleBoundaryTypes {
     "explicit-module-boundary-types"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Requires the parameters and the return type of exported functions to be typed explicitly.
+
+The types of a module's exports are its interface; spelling them out keeps it from changing by accident when the implementation changes.
+
+### Valid:
+```typescript
+export function test(): void {
+  return;
+}
+```
+```typescript
+export var arrowFn = (arg: string): string => `test ${arg}`;
+```
+```typescript
+function test() {
+  return;
+}
+```
+
+### Invalid:
+```typescript
+export function test() {
+  return;
+}
+```
+```typescript
+export var arrowFn = (arg): string => `test ${arg}`;
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = ExplicitModuleBoundaryTypesVisitor::new(context);
     visitor.visit_module(module, module);
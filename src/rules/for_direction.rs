@@ -29,6 +29,32 @@ impl LintRule for ForDirection {
     "for-direction"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires the update clause of a `for` loop to move the counter towards the loop's condition.
+
+A counter moving in the wrong direction means the loop never ends, or never runs.
+
+### Valid:
+```typescript
+for (let i = 0; i < 2; i++) {}
+```
+```typescript
+for (let i = 2; i > 0; i--) {}
+```
+
+### Invalid:
+```typescript
+for (let i = 0; i < 2; i--) {}
+```
+```typescript
+for (let i = 2; i > 0; i++) {}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
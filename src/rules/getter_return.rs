@@ -1,14 +1,14 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::ast::BlockStmt;
-use swc_ecmascript::ast::Class;
-use swc_ecmascript::ast::ClassMember;
-use swc_ecmascript::ast::Expr;
-use swc_ecmascript::ast::ExprOrSuper;
-use swc_ecmascript::ast::GetterProp;
-use swc_ecmascript::ast::MethodKind;
-use swc_ecmascript::ast::Stmt;
+use crate::control_flow::terminator::always_terminates;
+use crate::swc_util::unwrap_paren;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  BlockStmt, BlockStmtOrExpr, CallExpr, Class, ClassMember, Expr, ExprOrSuper,
+  GetterProp, Lit, MethodKind, Module, ObjectLit, Prop, PropName, PropOrSpread,
+  ReturnStmt,
+};
 use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
@@ -16,113 +16,260 @@ use swc_ecmascript::visit::VisitWith;
 
 use std::sync::Arc;
 
-pub struct GetterReturn;
+#[derive(Clone, Copy, Default)]
+pub struct GetterReturnOptions {
+  /// Allows a bare `return;`, which returns `undefined` explicitly.
+  pub allow_implicit: bool,
+}
+
+/// Reports getters with a path which ends without returning a value. The
+/// getters of classes, object literals and the descriptors passed to
+/// `Object.defineProperty()` are checked.
+pub struct GetterReturn {
+  options: GetterReturnOptions,
+}
+
+impl GetterReturn {
+  pub fn with_options(options: GetterReturnOptions) -> Box<Self> {
+    Box::new(GetterReturn { options })
+  }
+}
 
 impl LintRule for GetterReturn {
   fn new() -> Box<Self> {
-    Box::new(GetterReturn)
+    Self::with_options(GetterReturnOptions::default())
   }
 
   fn code(&self) -> &'static str {
     "getter-return"
   }
 
-  fn lint_module(
-    &self,
-    context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = GetterReturnVisitor::new(context);
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires getters to return a value on every path.
+
+A getter which doesn't return is almost always a mistake, since reading the property gives `undefined`. A path may also end by throwing, but loops which may run zero times don't count as returning.
+
+### Options
+
+- `GetterReturnOptions::allow_implicit`: allows a bare `return;`, `false` by default.
+
+### Valid:
+```typescript
+let foo = {
+  get bar() {
+    return true;
+  },
+};
+```
+```typescript
+class Foo {
+  get bar() {
+    if (baz) {
+      return true;
+    } else {
+      return false;
+    }
+  }
+}
+```
+```typescript
+class Foo {
+  get bar(): number {
+    throw new Error("not implemented");
+  }
+}
+```
+
+### Invalid:
+```typescript
+const foo = { get getter() {} };
+```
+```typescript
+class Foo {
+  get bar() {
+    if (baz) {
+      return true;
+    }
+  }
+}
+```
+```typescript
+Object.defineProperty(foo, "bar", {
+  get() {
+    for (const item of items) {
+      return item;
+    }
+  },
+});
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = GetterReturnVisitor {
+      context,
+      options: self.options,
+    };
     visitor.visit_module(module, module);
   }
 }
 
 struct GetterReturnVisitor {
   context: Arc<Context>,
+  options: GetterReturnOptions,
 }
 
 impl GetterReturnVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
-  }
-
-  fn return_has_arg(
-    &self,
-    return_stmt: &swc_ecmascript::ast::ReturnStmt,
-  ) -> bool {
-    return_stmt.arg.is_some()
-  }
-
-  fn stmts_have_return(&self, stmts: &[Stmt]) -> bool {
-    for stmt in stmts {
-      if let Stmt::Return(return_stmt) = stmt {
-        return self.return_has_arg(return_stmt);
-      }
+  fn check_body(&self, body: &BlockStmt, span: Span) {
+    if !always_terminates(&body.stmts) {
+      self.context.add_diagnostic_with_hint(
+        span,
+        "getter-return",
+        "Getter requires a return",
+        "Return a value on every path through the getter",
+      );
+    } else if !self.options.allow_implicit && has_bare_return(body) {
+      self.context.add_diagnostic_with_hint(
+        span,
+        "getter-return",
+        "Getter requires a return",
+        "Return a value instead of `undefined` with a bare `return;`",
+      );
     }
-    false
   }
 
-  fn check_if_stmt(&self, if_stmt: &swc_ecmascript::ast::IfStmt) -> bool {
-    if if_stmt.alt.is_none() {
-      return false;
-    }
-    if let Stmt::Block(if_block) = &*if_stmt.cons {
-      if !self.stmts_have_return(&if_block.stmts) {
-        return false;
-      }
-      let mut alt_stmt = &if_stmt.alt;
-      loop {
-        if let Some(if_alt_block) = alt_stmt {
-          if let Stmt::If(else_if) = &**if_alt_block {
-            if else_if.alt.is_none() {
-              return false;
-            } else {
-              alt_stmt = &else_if.alt;
-            }
-            if let Stmt::Block(else_if_block) = &*else_if.cons {
-              if !self.stmts_have_return(&else_if_block.stmts) {
-                return false;
+  /// Checks the `get` function of a property descriptor, reported at the
+  /// `get` key.
+  fn check_descriptor(&self, descriptor: &ObjectLit) {
+    for prop in &descriptor.props {
+      let prop = match prop {
+        PropOrSpread::Prop(prop) => prop,
+        PropOrSpread::Spread(_) => continue,
+      };
+      match &**prop {
+        Prop::KeyValue(key_value) if is_get_key(&key_value.key) => {
+          let span = prop_name_span(&key_value.key);
+          match unwrap_paren(&key_value.value) {
+            Expr::Fn(fn_expr) => {
+              if let Some(body) = &fn_expr.function.body {
+                self.check_body(body, span);
               }
             }
-          } else if let Stmt::Block(else_block) = &**if_alt_block {
-            if self.stmts_have_return(&else_block.stmts) {
-              break;
-            } else {
-              return false;
+            Expr::Arrow(arrow_expr) => {
+              if let BlockStmtOrExpr::BlockStmt(body) = &arrow_expr.body {
+                self.check_body(body, span);
+              }
             }
+            _ => {}
           }
         }
+        Prop::Method(method_prop) if is_get_key(&method_prop.key) => {
+          if let Some(body) = &method_prop.function.body {
+            self.check_body(body, prop_name_span(&method_prop.key));
+          }
+        }
+        _ => {}
       }
     }
-    true
   }
+}
 
-  fn check_switch_stmt(
-    &self,
-    switch_stmt: &swc_ecmascript::ast::SwitchStmt,
-  ) -> bool {
-    for case in &switch_stmt.cases {
-      if !case.cons.is_empty() && !self.stmts_have_return(&case.cons) {
-        return false;
-      }
-    }
-    true
+fn is_get_key(key: &PropName) -> bool {
+  match key {
+    PropName::Ident(ident) => ident.sym == *"get",
+    PropName::Str(str) => str.value == *"get",
+    _ => false,
   }
+}
 
-  fn check_block_stmt(&self, block_stmt: &BlockStmt, span: swc_common::Span) {
-    if !block_stmt.stmts.iter().any(|s| match s {
-      Stmt::If(if_stmt) => self.check_if_stmt(if_stmt),
-      Stmt::Switch(switch_stmt) => self.check_switch_stmt(switch_stmt),
-      Stmt::Return(return_stmt) => self.return_has_arg(return_stmt),
-      _ => false,
-    }) {
-      self.context.add_diagnostic(
-        span,
-        "getter-return",
-        "Getter requires a return",
-      );
+fn prop_name_span(key: &PropName) -> Span {
+  match key {
+    PropName::Ident(ident) => ident.span,
+    PropName::Str(str) => str.span,
+    PropName::Num(num) => num.span,
+    PropName::Computed(computed) => computed.span,
+  }
+}
+
+/// Returns the descriptor of `Object.defineProperty(obj, key, descriptor)`.
+fn define_property_descriptor(call_expr: &CallExpr) -> Option<&ObjectLit> {
+  let callee = match &call_expr.callee {
+    ExprOrSuper::Expr(callee) => unwrap_paren(callee),
+    ExprOrSuper::Super(_) => return None,
+  };
+  let callee = match callee {
+    Expr::OptChain(opt_chain) => unwrap_paren(&opt_chain.expr),
+    _ => callee,
+  };
+  let member_expr = match callee {
+    Expr::Member(member_expr) => member_expr,
+    _ => return None,
+  };
+  let is_object = matches!(
+    &member_expr.obj,
+    ExprOrSuper::Expr(obj) if matches!(&**obj, Expr::Ident(ident) if ident.sym == *"Object")
+  );
+  let is_define_property = match (&*member_expr.prop, member_expr.computed) {
+    (Expr::Ident(ident), false) => ident.sym == *"defineProperty",
+    (Expr::Lit(Lit::Str(str)), true) => str.value == *"defineProperty",
+    _ => false,
+  };
+  if !is_object || !is_define_property {
+    return None;
+  }
+  match unwrap_paren(&call_expr.args.get(2)?.expr) {
+    Expr::Object(descriptor) => Some(descriptor),
+    _ => None,
+  }
+}
+
+/// Checks if a function body contains a `return;` without a value, not
+/// counting the ones of nested functions.
+fn has_bare_return(body: &BlockStmt) -> bool {
+  let mut finder = BareReturnFinder { found: false };
+  body.visit_with(body, &mut finder);
+  finder.found
+}
+
+struct BareReturnFinder {
+  found: bool,
+}
+
+impl Visit for BareReturnFinder {
+  noop_visit_type!();
+
+  fn visit_return_stmt(
+    &mut self,
+    return_stmt: &ReturnStmt,
+    _parent: &dyn Node,
+  ) {
+    if return_stmt.arg.is_none() {
+      self.found = true;
     }
   }
+
+  fn visit_function(
+    &mut self,
+    _function: &swc_ecmascript::ast::Function,
+    _parent: &dyn Node,
+  ) {
+  }
+
+  fn visit_arrow_expr(
+    &mut self,
+    _arrow_expr: &swc_ecmascript::ast::ArrowExpr,
+    _parent: &dyn Node,
+  ) {
+  }
+
+  fn visit_class(&mut self, _class: &Class, _parent: &dyn Node) {}
 }
 
 impl Visit for GetterReturnVisitor {
@@ -135,14 +282,14 @@ impl Visit for GetterReturnVisitor {
         ClassMember::Method(class_method) => {
           if class_method.kind == MethodKind::Getter {
             if let Some(block_stmt) = &class_method.function.body {
-              self.check_block_stmt(block_stmt, class_method.span);
+              self.check_body(block_stmt, class_method.span);
             }
           }
         }
         ClassMember::PrivateMethod(private_method) => {
           if private_method.kind == MethodKind::Getter {
             if let Some(block_stmt) = &private_method.function.body {
-              self.check_block_stmt(block_stmt, private_method.span);
+              self.check_body(block_stmt, private_method.span);
             }
           }
         }
@@ -158,72 +305,14 @@ impl Visit for GetterReturnVisitor {
   ) {
     getter_prop.visit_children_with(self);
     if let Some(block_stmt) = &getter_prop.body {
-      self.check_block_stmt(block_stmt, getter_prop.span);
+      self.check_body(block_stmt, getter_prop.span);
     }
   }
 
-  fn visit_call_expr(
-    &mut self,
-    call_expr: &swc_ecmascript::ast::CallExpr,
-    _parent: &dyn Node,
-  ) {
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _parent: &dyn Node) {
     call_expr.visit_children_with(self);
-    if call_expr.args.len() != 3 {
-      return;
-    }
-    if let ExprOrSuper::Expr(callee_expr) = &call_expr.callee {
-      if let Expr::Member(member) = &**callee_expr {
-        if let ExprOrSuper::Expr(member_obj) = &member.obj {
-          if let Expr::Ident(ident) = &**member_obj {
-            if ident.sym != *"Object" {
-              return;
-            }
-          }
-        }
-        if let Expr::Ident(ident) = &*member.prop {
-          if ident.sym != *"defineProperty" {
-            return;
-          }
-        }
-      }
-    }
-    if let Expr::Object(obj_expr) = &*call_expr.args[2].expr {
-      for prop in obj_expr.props.iter() {
-        if let swc_ecmascript::ast::PropOrSpread::Prop(prop_expr) = prop {
-          if let swc_ecmascript::ast::Prop::KeyValue(kv_prop) = &**prop_expr {
-            if let swc_ecmascript::ast::PropName::Ident(ident) = &kv_prop.key {
-              if ident.sym != *"get" {
-                return;
-              }
-              if let Expr::Fn(fn_expr) = &*kv_prop.value {
-                if let Some(body) = &fn_expr.function.body {
-                  self.check_block_stmt(&body, ident.span);
-                }
-              } else if let Expr::Arrow(arrow_expr) = &*kv_prop.value {
-                if let swc_ecmascript::ast::BlockStmtOrExpr::BlockStmt(
-                  block_stmt,
-                ) = &arrow_expr.body
-                {
-                  self.check_block_stmt(&block_stmt, ident.span);
-                }
-              }
-            }
-          } else if let swc_ecmascript::ast::Prop::Method(method_prop) =
-            &**prop_expr
-          {
-            if let swc_ecmascript::ast::PropName::Ident(ident) =
-              &method_prop.key
-            {
-              if ident.sym != *"get" {
-                return;
-              }
-              if let Some(body) = &method_prop.function.body {
-                self.check_block_stmt(&body, ident.span);
-              }
-            }
-          }
-        }
-      }
+    if let Some(descriptor) = define_property_descriptor(call_expr) {
+      self.check_descriptor(descriptor);
     }
   }
 }
@@ -422,4 +511,114 @@ Object.defineProperty(foo, 'bar', {
       vec![10, 27],
     );
   }
+
+  #[test]
+  fn getter_return_code_paths() {
+    assert_lint_ok_n::<GetterReturn>(vec![
+      "class Foo { get bar() { throw new Error('not implemented'); } }",
+      "const foo = { get bar() { if (a) { return 1; } throw new Error(); } };",
+      "const foo = { get bar() { switch (a) { case 1: return 1; default: return 2; } } };",
+      "const foo = { get bar() { try { return a(); } catch (e) { return 0; } } };",
+      "const foo = { get bar() { try { a(); } finally { return 0; } } };",
+      "const foo = { get bar() { for (;;) { if (a()) { return 1; } } } };",
+      "const foo = { get bar() { while (true) { if (a()) return 1; } } };",
+      "const foo = { get bar() { if (a) return 1; else if (b) return 2; else return 3; } };",
+    ]);
+    assert_lint_err::<GetterReturn>(
+      "class Foo { get bar() { for (const a of b) { return a; } } }",
+      12,
+    );
+    assert_lint_err::<GetterReturn>(
+      "class Foo { get bar() { while (a) { return 1; } } }",
+      12,
+    );
+    assert_lint_err::<GetterReturn>(
+      "class Foo { get bar() { for (;;) { if (a) { break; } } } }",
+      12,
+    );
+    assert_lint_err::<GetterReturn>(
+      "const foo = { get bar() { switch (a) { case 1: return 1; } } };",
+      14,
+    );
+    assert_lint_err::<GetterReturn>(
+      "const foo = { get bar() { switch (a) { case 1: break; default: return 1; } } };",
+      14,
+    );
+    assert_lint_err::<GetterReturn>(
+      "const foo = { get bar() { try { return a(); } catch (e) { log(e); } } };",
+      14,
+    );
+    assert_lint_err::<GetterReturn>(
+      "const foo = { get bar() { if (a) { return 1; } else { b(); } } };",
+      14,
+    );
+  }
+
+  #[test]
+  fn getter_return_allow_implicit() {
+    let allow_implicit = || {
+      GetterReturn::with_options(GetterReturnOptions {
+        allow_implicit: true,
+      })
+    };
+    assert_lint_err::<GetterReturn>("class Foo { get bar() { return; } }", 12);
+    assert_lint_err::<GetterReturn>(
+      "class Foo { get bar() { if (a) { return; } return 1; } }",
+      12,
+    );
+    assert_lint_ok::<GetterReturn>(
+      "class Foo { get bar() { const f = () => { return; }; return f; } }",
+    );
+    assert_lint_ok_with(
+      allow_implicit(),
+      "class Foo { get bar() { return; } }",
+    );
+    assert_lint_ok_with(
+      allow_implicit(),
+      "class Foo { get bar() { if (a) { return; } return 1; } }",
+    );
+    assert_lint_err_on_line_n_with(
+      allow_implicit(),
+      "class Foo { get bar() { if (a) { return; } } }",
+      vec![(1, 12)],
+    );
+
+    let diagnostics = lint(
+      GetterReturn::new(),
+      "const foo = { get bar() { return; } };",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Return a value instead of `undefined` with a bare `return;`")
+    );
+  }
+
+  #[test]
+  fn getter_return_define_property() {
+    assert_lint_ok_n::<GetterReturn>(vec![
+      "Object.defineProperty(foo, 'bar', { get() { throw new Error(); } });",
+      "Object.defineProperty(foo, 'bar', { get: () => 1 });",
+      "Object.defineProperty(foo, 'bar', { set(value) {} });",
+      "Object.defineProperty(foo, 'bar', { value: function () {} });",
+      "Foo.defineProperty(foo, 'bar', { get() {} });",
+      "Object.defineProperty(foo, 'bar', descriptor);",
+    ]);
+    assert_lint_err::<GetterReturn>(
+      "Object.defineProperty(foo, 'bar', { 'get': function () {} });",
+      36,
+    );
+    assert_lint_err::<GetterReturn>(
+      "Object['defineProperty'](foo, 'bar', { get() {} });",
+      39,
+    );
+    assert_lint_err::<GetterReturn>(
+      "Object.defineProperty(foo, 'bar', { get() { for (const a of b) { return a; } } });",
+      36,
+    );
+    assert_lint_err::<GetterReturn>(
+      "Object.defineProperty(foo, 'bar', { enumerable: true, get() { if (a) { return 1; } } });",
+      54,
+    );
+  }
 }
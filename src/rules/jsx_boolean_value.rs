@@ -0,0 +1,221 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::jsx_util::{attr_name, attr_name_span};
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  Bool, Expr, JSXAttr, JSXAttrValue, JSXExpr, JSXExprContainer, Lit,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JsxBooleanValueMode {
+  /// `true` values must be omitted, eg. `<input disabled />`.
+  Never,
+  /// `true` values must be written out, eg. `<input disabled={true} />`.
+  Always,
+}
+
+pub struct JsxBooleanValueOptions {
+  pub mode: JsxBooleanValueMode,
+}
+
+impl Default for JsxBooleanValueOptions {
+  fn default() -> Self {
+    Self {
+      mode: JsxBooleanValueMode::Never,
+    }
+  }
+}
+
+pub struct JsxBooleanValue {
+  options: JsxBooleanValueOptions,
+}
+
+impl JsxBooleanValue {
+  pub fn with_options(options: JsxBooleanValueOptions) -> Box<Self> {
+    Box::new(JsxBooleanValue { options })
+  }
+}
+
+impl LintRule for JsxBooleanValue {
+  fn new() -> Box<Self> {
+    Self::with_options(JsxBooleanValueOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "jsx-boolean-value"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Enforces a consistent style for boolean JSX attributes whose value is `true`.
+
+### Options
+
+`JsxBooleanValueOptions::mode` is one of:
+- `JsxBooleanValueMode::Never` (default): the value must be omitted, `<input disabled />`.
+- `JsxBooleanValueMode::Always`: the value must be written out, `<input disabled={true} />`.
+
+### Valid:
+```tsx
+<input disabled />
+```
+```tsx
+<input disabled={false} />
+```
+
+### Invalid:
+```tsx
+<input disabled={true} />
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = JsxBooleanValueVisitor::new(context, self.options.mode);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct JsxBooleanValueVisitor {
+  context: Arc<Context>,
+  mode: JsxBooleanValueMode,
+}
+
+impl JsxBooleanValueVisitor {
+  fn new(context: Arc<Context>, mode: JsxBooleanValueMode) -> Self {
+    Self { context, mode }
+  }
+}
+
+/// Returns the span of the `{true}` container if that's the attribute value.
+fn true_value_span(value: &JSXAttrValue) -> Option<Span> {
+  match value {
+    JSXAttrValue::JSXExprContainer(JSXExprContainer {
+      span,
+      expr: JSXExpr::Expr(expr),
+    }) => match &**expr {
+      Expr::Lit(Lit::Bool(Bool { value: true, .. })) => Some(*span),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+impl Visit for JsxBooleanValueVisitor {
+  noop_visit_type!();
+
+  fn visit_jsx_attr(&mut self, attr: &JSXAttr, parent: &dyn Node) {
+    let name = attr_name(&attr.name);
+    match (self.mode, &attr.value) {
+      (JsxBooleanValueMode::Never, Some(value)) => {
+        if let Some(span) = true_value_span(value) {
+          self.context.add_diagnostic_with_hint(
+            span,
+            "jsx-boolean-value",
+            &format!("Value must be omitted for boolean attribute `{}`", name),
+            &format!("Use `{}` instead of `{}={{true}}`", name, name),
+          );
+        }
+      }
+      (JsxBooleanValueMode::Always, None) => {
+        self.context.add_diagnostic_with_hint(
+          attr_name_span(&attr.name),
+          "jsx-boolean-value",
+          &format!("Value must be set for boolean attribute `{}`", name),
+          &format!("Use `{}={{true}}` instead of `{}`", name, name),
+        );
+      }
+      _ => {}
+    }
+
+    swc_ecmascript::visit::visit_jsx_attr(self, attr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn always() -> Box<JsxBooleanValue> {
+    JsxBooleanValue::with_options(JsxBooleanValueOptions {
+      mode: JsxBooleanValueMode::Always,
+    })
+  }
+
+  #[test]
+  fn jsx_boolean_value_never_valid() {
+    for source in &[
+      "<input disabled />",
+      "<input disabled={false} />",
+      "<input disabled={isDisabled} />",
+      "<input value='true' />",
+      "<Select<boolean> multiple aria-hidden />",
+    ] {
+      assert_lint_ok_tsx(JsxBooleanValue::new(), source);
+    }
+  }
+
+  #[test]
+  fn jsx_boolean_value_never_invalid() {
+    let rule = JsxBooleanValue::new;
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<input disabled={true} />",
+      vec![(1, 16)],
+    );
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<div data-active={true} aria-hidden={true} />",
+      vec![(1, 17), (1, 36)],
+    );
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<Select<boolean> multiple={true} />",
+      vec![(1, 26)],
+    );
+    let diagnostics = lint_tsx(rule(), "<input disabled={true} />");
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use `disabled` instead of `disabled={true}`")
+    );
+  }
+
+  #[test]
+  fn jsx_boolean_value_always_valid() {
+    for source in &[
+      "<input disabled={true} />",
+      "<input disabled={false} />",
+      "<input value='a' />",
+      "<svg xlink:show={true} />",
+    ] {
+      assert_lint_ok_tsx(always(), source);
+    }
+  }
+
+  #[test]
+  fn jsx_boolean_value_always_invalid() {
+    assert_lint_err_on_line_n_tsx(always(), "<input disabled />", vec![(1, 7)]);
+    assert_lint_err_on_line_n_tsx(
+      always(),
+      "<svg xlink:show data-active />",
+      vec![(1, 5), (1, 16)],
+    );
+    let diagnostics = lint_tsx(always(), "<input disabled />");
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use `disabled={true}` instead of `disabled`")
+    );
+  }
+}
@@ -0,0 +1,172 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::jsx_util::{attr_name, attr_name_span};
+use std::collections::HashMap;
+use std::sync::Arc;
+use swc_ecmascript::ast::{JSXAttrOrSpread, JSXOpeningElement};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  JsxNoDuplicateProps, "jsx-no-duplicate-props", tags: [recommended];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows passing the same attribute to a JSX element more than once, including attributes that differ only in case.
+
+Only the last of the duplicated attributes takes effect, which is almost always a mistake.
+
+### Valid:
+```tsx
+<div id="a" className="b" />
+```
+```tsx
+<div {...props} {...rest} />
+```
+
+### Invalid:
+```tsx
+<List key={1} item={a} key={2} />
+```
+```tsx
+<button onClick={a} onclick={b} />
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = JsxNoDuplicatePropsVisitor::new(context);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct JsxNoDuplicatePropsVisitor {
+  context: Arc<Context>,
+}
+
+impl JsxNoDuplicatePropsVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self { context }
+  }
+}
+
+impl Visit for JsxNoDuplicatePropsVisitor {
+  noop_visit_type!();
+
+  fn visit_jsx_opening_element(
+    &mut self,
+    opening: &JSXOpeningElement,
+    parent: &dyn Node,
+  ) {
+    // Maps lowercased names to the first spelling seen, so both exact
+    // duplicates and names differing only in case can be found.
+    let mut seen: HashMap<String, String> = HashMap::new();
+
+    for attr in &opening.attrs {
+      // Spread attributes are skipped, attributes on both sides of
+      // a spread are still compared with each other.
+      let attr = match attr {
+        JSXAttrOrSpread::JSXAttr(attr) => attr,
+        JSXAttrOrSpread::SpreadElement(_) => continue,
+      };
+      let name = attr_name(&attr.name);
+      match seen.get(&name.to_lowercase()) {
+        Some(first) if *first == name => {
+          self.context.add_diagnostic(
+            attr_name_span(&attr.name),
+            "jsx-no-duplicate-props",
+            &format!("Duplicate JSX attribute `{}`", name),
+          );
+        }
+        Some(first) => {
+          self.context.add_diagnostic_with_hint(
+            attr_name_span(&attr.name),
+            "jsx-no-duplicate-props",
+            &format!(
+              "JSX attribute `{}` differs from `{}` only in case",
+              name, first
+            ),
+            "This is likely a typo, remove one of the attributes",
+          );
+        }
+        None => {
+          seen.insert(name.to_lowercase(), name);
+        }
+      }
+    }
+
+    swc_ecmascript::visit::visit_jsx_opening_element(self, opening, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn jsx_no_duplicate_props_valid() {
+    for source in &[
+      "<div id='a' className='b' />",
+      "<div {...props} {...rest} />",
+      "<List key={1} item={a} />",
+      "<svg xlink:href='#a' href='#b' />",
+      "<div data-id='a' aria-label='b' data-name='c' />",
+      "<Select<string> value={a} onChange={b} />",
+      "<a><b id='a' /><b id='b' /></a>",
+    ] {
+      assert_lint_ok_tsx(JsxNoDuplicateProps::new(), source);
+    }
+  }
+
+  #[test]
+  fn jsx_no_duplicate_props_invalid() {
+    let rule = JsxNoDuplicateProps::new;
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<List key={1} item={a} key={2} />",
+      vec![(1, 23)],
+    );
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<div id='a' {...props} id='b' />",
+      vec![(1, 23)],
+    );
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<button onClick={a} onclick={b} />",
+      vec![(1, 20)],
+    );
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<svg xlink:href='#a' xlink:href='#b' />",
+      vec![(1, 21)],
+    );
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<div aria-label='a' data-id='b' aria-label='c' />",
+      vec![(1, 32)],
+    );
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<Select<string> value={a}\n  value={b} />",
+      vec![(2, 2)],
+    );
+  }
+
+  #[test]
+  fn jsx_no_duplicate_props_case_message() {
+    let diagnostics = lint_tsx(
+      JsxNoDuplicateProps::new(),
+      "<button onClick={a} onclick={b} />",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "JSX attribute `onclick` differs from `onClick` only in case"
+    );
+  }
+}
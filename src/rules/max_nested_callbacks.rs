@@ -0,0 +1,350 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::function_head_span;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  BlockStmtOrExpr, CallExpr, ClassMethod, Constructor, Expr, GetterProp,
+  JSXExpr, JSXExprContainer, MethodProp, Module, PrivateMethod, SetterProp,
+  Stmt,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::{Visit, VisitWith};
+
+pub struct MaxNestedCallbacksOptions {
+  /// The deepest allowed nesting of callbacks.
+  pub max: usize,
+}
+
+impl Default for MaxNestedCallbacksOptions {
+  fn default() -> Self {
+    Self { max: 3 }
+  }
+}
+
+/// Reports callbacks nested deeper than `max` levels.
+///
+/// Only functions in callback positions are counted: arguments of calls,
+/// and JSX children and attributes like render props. Functions assigned
+/// to variables don't count, and method bodies start counting from zero.
+/// Callbacks passed to chained calls, like `.then()` chains, are siblings
+/// and not nested.
+pub struct MaxNestedCallbacks {
+  options: MaxNestedCallbacksOptions,
+}
+
+impl MaxNestedCallbacks {
+  pub fn with_options(options: MaxNestedCallbacksOptions) -> Box<Self> {
+    Box::new(MaxNestedCallbacks { options })
+  }
+}
+
+impl LintRule for MaxNestedCallbacks {
+  fn new() -> Box<Self> {
+    Self::with_options(MaxNestedCallbacksOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "max-nested-callbacks"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Limits how deeply callbacks may be nested.
+
+Deeply nested callbacks are hard to follow; they can usually be flattened with `async` functions and `await`. Only functions in callback positions count: arguments of calls and JSX children and attributes. Callbacks passed to chained calls, like `.then()` chains, are siblings and not nested.
+
+### Options
+
+`MaxNestedCallbacksOptions::max`: the deepest allowed nesting, `3` by default.
+
+### Valid:
+```typescript
+foo(function () {
+  bar(function () {
+    baz(function () {});
+  });
+});
+```
+```typescript
+a().then(() => b()).then(() => c()).then(() => d());
+```
+
+### Invalid:
+```typescript
+foo(function () {
+  bar(function () {
+    baz(function () {
+      qux(function () {});
+    });
+  });
+});
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = MaxNestedCallbacksVisitor {
+      context,
+      max: self.options.max,
+      callbacks: vec![],
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+/// A callback which is being visited.
+struct Callback {
+  /// The call the callback ends with, if any.
+  tail_call: Option<Span>,
+  /// The callback is passed to the call its parent callback ends with.
+  continues_parent: bool,
+}
+
+struct MaxNestedCallbacksVisitor {
+  context: Arc<Context>,
+  max: usize,
+  /// The callbacks enclosing the current node, outermost first.
+  callbacks: Vec<Callback>,
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn call_span(expr: &Expr) -> Option<Span> {
+  match unwrap_paren(expr) {
+    Expr::Call(call_expr) => Some(call_expr.span),
+    Expr::Await(await_expr) => call_span(&await_expr.arg),
+    _ => None,
+  }
+}
+
+/// Returns the span of the call a function ends with, either as the last
+/// statement or as the expression body.
+fn tail_call(function: &Expr) -> Option<Span> {
+  let stmts = match unwrap_paren(function) {
+    Expr::Arrow(arrow) => match &arrow.body {
+      BlockStmtOrExpr::BlockStmt(body) => &body.stmts,
+      BlockStmtOrExpr::Expr(expr) => return call_span(expr),
+    },
+    Expr::Fn(fn_expr) => &fn_expr.function.body.as_ref()?.stmts,
+    _ => return None,
+  };
+  match stmts.last()? {
+    Stmt::Expr(expr_stmt) => call_span(&expr_stmt.expr),
+    Stmt::Return(return_stmt) => call_span(return_stmt.arg.as_ref()?),
+    _ => None,
+  }
+}
+
+impl MaxNestedCallbacksVisitor {
+  /// Visits a function in a callback position, `call` being the call it's
+  /// passed to.
+  fn visit_callback(&mut self, callback: &Expr, call: Option<Span>) {
+    let continues_parent = match (self.callbacks.last(), call) {
+      (Some(enclosing), Some(call)) => enclosing.tail_call == Some(call),
+      _ => false,
+    };
+    self.callbacks.push(Callback {
+      tail_call: tail_call(callback),
+      continues_parent,
+    });
+
+    let depth = self.callbacks.len();
+    if depth == self.max + 1 {
+      self.report(callback, depth);
+    }
+    callback.visit_children_with(self);
+    self.callbacks.pop();
+  }
+
+  fn report(&self, callback: &Expr, depth: usize) {
+    let span = match function_head_span(&self.context, callback) {
+      Some(span) => span,
+      None => return,
+    };
+    let message = format!(
+      "Too many nested callbacks ({}), the maximum allowed is {}",
+      depth, self.max
+    );
+    // Every callback is passed to the call its parent ends with.
+    let is_chain = self.callbacks.len() > 1
+      && self.callbacks.iter().skip(1).all(|c| c.continues_parent);
+    if is_chain {
+      self.context.add_diagnostic_with_hint(
+        span,
+        "max-nested-callbacks",
+        &message,
+        "Each callback continues the previous one; consider refactoring them into an async function using `await`",
+      );
+    } else {
+      self
+        .context
+        .add_diagnostic(span, "max-nested-callbacks", &message);
+    }
+  }
+
+  /// Visits a method body, in which callbacks are counted from zero.
+  fn visit_method<N: VisitWith<Self>>(&mut self, method: &N) {
+    let callbacks = std::mem::take(&mut self.callbacks);
+    method.visit_children_with(self);
+    self.callbacks = callbacks;
+  }
+}
+
+fn is_function(expr: &Expr) -> bool {
+  matches!(unwrap_paren(expr), Expr::Arrow(_) | Expr::Fn(_))
+}
+
+impl Visit for MaxNestedCallbacksVisitor {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    self.visit_expr_or_super(&call_expr.callee, parent);
+    for arg in &call_expr.args {
+      if arg.spread.is_none() && is_function(&arg.expr) {
+        self.visit_callback(&arg.expr, Some(call_expr.span));
+      } else {
+        self.visit_expr_or_spread(arg, parent);
+      }
+    }
+  }
+
+  fn visit_jsx_expr_container(
+    &mut self,
+    container: &JSXExprContainer,
+    parent: &dyn Node,
+  ) {
+    match &container.expr {
+      JSXExpr::Expr(expr) if is_function(expr) => {
+        self.visit_callback(expr, None)
+      }
+      _ => {
+        swc_ecmascript::visit::visit_jsx_expr_container(self, container, parent)
+      }
+    }
+  }
+
+  fn visit_class_method(&mut self, method: &ClassMethod, _: &dyn Node) {
+    self.visit_method(method);
+  }
+
+  fn visit_private_method(&mut self, method: &PrivateMethod, _: &dyn Node) {
+    self.visit_method(method);
+  }
+
+  fn visit_constructor(&mut self, constructor: &Constructor, _: &dyn Node) {
+    self.visit_method(constructor);
+  }
+
+  fn visit_method_prop(&mut self, method: &MethodProp, _: &dyn Node) {
+    self.visit_method(method);
+  }
+
+  fn visit_getter_prop(&mut self, getter: &GetterProp, _: &dyn Node) {
+    self.visit_method(getter);
+  }
+
+  fn visit_setter_prop(&mut self, setter: &SetterProp, _: &dyn Node) {
+    self.visit_method(setter);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn max(max: usize) -> Box<MaxNestedCallbacks> {
+    MaxNestedCallbacks::with_options(MaxNestedCallbacksOptions { max })
+  }
+
+  #[test]
+  fn max_nested_callbacks_valid() {
+    assert_lint_ok_n::<MaxNestedCallbacks>(vec![
+      "foo(function () { bar(function () { baz(function () {}); }); });",
+      // Functions assigned to variables aren't callbacks.
+      "foo(() => { const f = () => { bar(() => { baz(() => {}); }); }; });",
+      // Method bodies start counting from zero.
+      "foo(() => { bar(() => { baz(() => { const o = { m() { a(() => { b(() => {}); }); } }; }); }); });",
+      "foo(() => { bar(() => { baz(() => { class A { m() { a(() => {}); } } }); }); });",
+      // Chained callbacks are siblings.
+      "foo(() => { bar(() => { p.then(() => {}).then(() => {}).catch(() => {}); }); });",
+      "function f() { return () => () => () => () => {}; }",
+    ]);
+  }
+
+  #[test]
+  fn max_nested_callbacks_invalid() {
+    assert_lint_err::<MaxNestedCallbacks>(
+      "foo(function () { bar(function () { baz(function () { qux(function () {}); }); }); });",
+      58,
+    );
+    // Only the callback crossing the threshold is reported.
+    assert_lint_err_on_line_n_with(
+      MaxNestedCallbacks::new(),
+      "a(() => {\n  b(() => {\n    c(() => {\n      d(() => {\n        e(() => {});\n      });\n      d(() => {});\n    });\n  });\n});",
+      vec![(4, 8), (7, 8)],
+    );
+
+    let diagnostics = lint(
+      MaxNestedCallbacks::new(),
+      "foo(function () { bar(function () { baz(function () { qux(function () {}); }); }); });",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "Too many nested callbacks (4), the maximum allowed is 3"
+    );
+    // The diagnostic covers the head of the function.
+    assert_eq!(diagnostics[0].range.end.col, 69);
+    // Each callback is passed to the call its parent ends with.
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Each callback continues the previous one; consider refactoring them into an async function using `await`")
+    );
+
+    let diagnostics = lint(
+      MaxNestedCallbacks::new(),
+      "foo(() => { bar(() => { baz(() => { qux(() => {}); done(); }); }); });",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 40);
+    assert_eq!(diagnostics[0].hint, None);
+  }
+
+  #[test]
+  fn max_nested_callbacks_jsx() {
+    let diagnostics = lint_tsx(
+      MaxNestedCallbacks::new(),
+      "<List render={(item) => <Row>{(cell) => <Cell>{(v) => <Value>{(x) => x}</Value>}</Cell>}</Row>} />;",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 62);
+    assert_eq!(diagnostics[0].hint, None);
+
+    assert_lint_ok_tsx(
+      MaxNestedCallbacks::new(),
+      "<List render={(item) => <Row>{(cell) => <Cell value={cell} />}</Row>} />;",
+    );
+  }
+
+  #[test]
+  fn max_nested_callbacks_max() {
+    assert_lint_ok_with(max(1), "foo(() => {});");
+    assert_lint_ok_with(max(1), "p.then(() => {}).then(() => {});");
+    assert_lint_err_on_line_n_with(
+      max(1),
+      "foo(() => { bar(() => {}); });",
+      vec![(1, 16)],
+    );
+    assert_lint_err_on_line_n_with(max(0), "foo(() => {});", vec![(1, 4)]);
+  }
+}
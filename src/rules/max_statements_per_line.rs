@@ -0,0 +1,177 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{ModuleItem, Stmt};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+pub struct MaxStatementsPerLineOptions {
+  /// Maximum number of statements allowed to start on a single line.
+  pub max: usize,
+}
+
+impl Default for MaxStatementsPerLineOptions {
+  fn default() -> Self {
+    Self { max: 1 }
+  }
+}
+
+pub struct MaxStatementsPerLine {
+  options: MaxStatementsPerLineOptions,
+}
+
+impl MaxStatementsPerLine {
+  pub fn with_options(options: MaxStatementsPerLineOptions) -> Box<Self> {
+    Box::new(MaxStatementsPerLine { options })
+  }
+}
+
+impl LintRule for MaxStatementsPerLine {
+  fn new() -> Box<Self> {
+    Self::with_options(MaxStatementsPerLineOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "max-statements-per-line"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Limits the number of statements that start on a single line.
+
+Several statements on one line are easy to overlook when reading the code.
+
+### Options
+
+`MaxStatementsPerLineOptions::max`: the number of statements allowed per line, `1` by default.
+
+### Valid:
+```typescript
+let a = 1;
+foo();
+```
+
+### Invalid:
+```typescript
+let a = 1; foo();
+```
+```typescript
+if (a) { b(); }
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = MaxStatementsPerLineVisitor::new(context.clone());
+    visitor.visit_module(module, module);
+
+    for spans in visitor.statements_by_line.values_mut() {
+      if spans.len() <= self.options.max {
+        continue;
+      }
+      spans.sort_by_key(|span| span.lo());
+      context.add_diagnostic(
+        spans[self.options.max],
+        "max-statements-per-line",
+        &format!(
+          "This line has {} statements. Maximum allowed is {}",
+          spans.len(),
+          self.options.max
+        ),
+      );
+    }
+  }
+}
+
+struct MaxStatementsPerLineVisitor {
+  context: Arc<Context>,
+  statements_by_line: BTreeMap<usize, Vec<Span>>,
+}
+
+impl MaxStatementsPerLineVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self {
+      context,
+      statements_by_line: BTreeMap::new(),
+    }
+  }
+
+  fn record(&mut self, span: Span) {
+    let line = self.context.source_map.lookup_char_pos(span.lo()).line;
+    self.statements_by_line.entry(line).or_default().push(span);
+  }
+}
+
+impl Visit for MaxStatementsPerLineVisitor {
+  noop_visit_type!();
+
+  fn visit_module_item(&mut self, item: &ModuleItem, parent: &dyn Node) {
+    if let ModuleItem::ModuleDecl(module_decl) = item {
+      self.record(module_decl.span());
+    }
+    swc_ecmascript::visit::visit_module_item(self, item, parent);
+  }
+
+  fn visit_stmt(&mut self, stmt: &Stmt, parent: &dyn Node) {
+    // Braces of a block are not statements on their own, only
+    // the statements inside of them are counted.
+    if !matches!(stmt, Stmt::Block(_) | Stmt::Empty(_)) {
+      self.record(stmt.span());
+    }
+    swc_ecmascript::visit::visit_stmt(self, stmt, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn max_statements_per_line_valid() {
+    assert_lint_ok_n::<MaxStatementsPerLine>(vec![
+      "let a = 1;\nfoo();",
+      "if (a) {\n  b();\n}",
+      "function foo() {\n  return 1;\n}",
+      "import a from 'a.ts';\nexport { a };",
+      "for (let i = 0; i < 10; i++) {\n  foo(i);\n}",
+    ]);
+  }
+
+  #[test]
+  fn max_statements_per_line_invalid() {
+    assert_lint_err::<MaxStatementsPerLine>("let a = 1; foo();", 11);
+    assert_lint_err::<MaxStatementsPerLine>("if (a) b(); else c();", 7);
+    assert_lint_err::<MaxStatementsPerLine>("if (a) { b(); }", 9);
+    assert_lint_err::<MaxStatementsPerLine>("import a from 'a.ts'; a();", 22);
+    assert_lint_err_on_line_n::<MaxStatementsPerLine>(
+      "a(); b(); c();\nd();\ne(); f();",
+      vec![(1, 5), (3, 5)],
+    );
+  }
+
+  #[test]
+  fn max_statements_per_line_max_option() {
+    let rule = || {
+      MaxStatementsPerLine::with_options(MaxStatementsPerLineOptions { max: 2 })
+    };
+    assert_lint_ok_with(rule(), "let a = 1; foo();");
+    assert_lint_ok_with(rule(), "if (a) b(); else {}");
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "if (a) b(); else c();",
+      vec![(1, 17)],
+    );
+  }
+}
@@ -1,37 +1,180 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use crate::diagnostic::Severity;
 use crate::linter::Context;
 use std::sync::Arc;
 
+/// Declares a unit struct implementing `LintRule` for the given code and
+/// tags. The rest of the trait items (at least `lint_module`) are passed
+/// through as the body.
+///
+/// ```ignore
+/// declare_rule! {
+///   NoDebugger, "no-debugger", tags: [recommended];
+///
+///   fn lint_module(&self, context: Arc<Context>, module: &Module) {
+///     // ...
+///   }
+/// }
+/// ```
+macro_rules! declare_rule {
+  (
+    $(#[$attr:meta])*
+    $name:ident, $code:literal, tags: [$($tag:ident),* $(,)?];
+    $($body:tt)*
+  ) => {
+    $(#[$attr])*
+    pub struct $name;
+
+    impl $crate::rules::LintRule for $name {
+      fn new() -> Box<Self> {
+        Box::new($name)
+      }
+
+      fn code(&self) -> &'static str {
+        $code
+      }
+
+      fn tags(&self) -> &'static [&'static str] {
+        &[$(stringify!($tag)),*]
+      }
+
+      $($body)*
+    }
+  };
+}
+
+/// Declares a rule reporting every statement of one kind at its leading
+/// keyword, eg. `debugger` or `with`, which saves writing a visitor. The
+/// rest of the trait items, like `docs`, are passed through as the body.
+///
+/// ```ignore
+/// declare_statement_rule! {
+///   NoDebugger, "no-debugger", tags: [recommended];
+///   visit_debugger_stmt(DebuggerStmt), keyword: "debugger",
+///   message: "`debugger` statement is not allowed",
+///   hint: "Remove the `debugger` statement";
+///
+///   fn docs(&self) -> &'static str {
+///     // ...
+///   }
+/// }
+/// ```
+macro_rules! declare_statement_rule {
+  (
+    $(#[$attr:meta])*
+    $name:ident, $code:literal, tags: [$($tag:ident),* $(,)?];
+    $visit:ident($stmt:ident), keyword: $keyword:literal,
+    message: $message:literal, hint: $hint:literal;
+    $($body:tt)*
+  ) => {
+    declare_rule! {
+      $(#[$attr])*
+      $name, $code, tags: [$($tag),*];
+
+      fn lint_module(
+        &self,
+        context: std::sync::Arc<$crate::linter::Context>,
+        module: &swc_ecmascript::ast::Module,
+      ) {
+        use swc_ecmascript::visit::noop_visit_type;
+
+        struct StatementVisitor {
+          context: std::sync::Arc<$crate::linter::Context>,
+        }
+
+        impl swc_ecmascript::visit::Visit for StatementVisitor {
+          noop_visit_type!();
+
+          fn $visit(
+            &mut self,
+            stmt: &swc_ecmascript::ast::$stmt,
+            parent: &dyn swc_ecmascript::visit::Node,
+          ) {
+            self.context.add_diagnostic_with_hint(
+              $crate::swc_util::keyword_span(stmt.span, $keyword),
+              $code,
+              $message,
+              $hint,
+            );
+            swc_ecmascript::visit::$visit(self, stmt, parent);
+          }
+        }
+
+        let mut visitor = StatementVisitor { context };
+        swc_ecmascript::visit::Visit::visit_module(&mut visitor, module, module);
+      }
+
+      $($body)*
+    }
+  };
+}
+
+/// Generates `get_all_rules()` from the list of rule types, which is the
+/// single place where a rule needs to be registered.
+macro_rules! register_rules {
+  ($($rule:ty),* $(,)?) => {
+    pub fn get_all_rules() -> Vec<Box<dyn LintRule>> {
+      vec![$(<$rule as LintRule>::new()),*]
+    }
+  };
+}
+
 pub mod adjacent_overload_signatures;
+pub mod array_type;
+pub mod ban_commented_out_code;
+pub mod ban_reassigning_imported_namespace_members;
 pub mod ban_ts_comment;
 pub mod ban_ts_ignore;
 pub mod ban_types;
 pub mod ban_untagged_ignore;
 pub mod ban_untagged_todo;
+pub mod camelcase;
+pub mod click_events_have_key_events;
+pub mod consistent_filename_casing;
+pub mod consistent_generic_constructors;
+pub mod consistent_this_arrow_usage;
 pub mod constructor_super;
+pub mod curly;
 pub mod default_param_last;
+pub mod eol_last;
 pub mod eqeqeq;
 pub mod explicit_function_return_type;
+pub mod explicit_member_accessibility;
 pub mod explicit_module_boundary_types;
 pub mod for_direction;
 pub mod getter_return;
+pub mod jsx_boolean_value;
+pub mod jsx_no_duplicate_props;
+pub mod max_nested_callbacks;
+pub mod max_statements_per_line;
 pub mod no_array_constructor;
+pub mod no_async_array_callback_misuse;
+pub mod no_async_executor_race;
 pub mod no_async_promise_executor;
 pub mod no_await_in_loop;
+pub mod no_bigint_and_number_mixing;
+pub mod no_bitwise;
 pub mod no_case_declarations;
+pub mod no_class_as_namespace;
 pub mod no_class_assign;
 pub mod no_compare_neg_zero;
 pub mod no_cond_assign;
+pub mod no_console;
 pub mod no_const_assign;
+pub mod no_constant_binary_expression;
 pub mod no_constant_condition;
 pub mod no_control_regex;
 pub mod no_debugger;
 pub mod no_delete_var;
+pub mod no_done_callback;
 pub mod no_dupe_args;
 pub mod no_dupe_class_members;
 pub mod no_dupe_else_if;
 pub mod no_dupe_keys;
 pub mod no_duplicate_case;
+pub mod no_duplicate_imports;
+pub mod no_duplicate_spread_overrides;
+pub mod no_duplicate_union_discriminants_in_switch;
 pub mod no_empty;
 pub mod no_empty_character_class;
 pub mod no_empty_interface;
@@ -39,54 +182,100 @@ pub mod no_empty_pattern;
 pub mod no_eval;
 pub mod no_ex_assign;
 pub mod no_explicit_any;
+pub mod no_external_mutable_state_capture;
 pub mod no_extra_boolean_cast;
 pub mod no_extra_non_null_assertion;
 pub mod no_extra_semi;
 pub mod no_fallthrough;
+pub mod no_floating_void_operator;
 pub mod no_func_assign;
 pub mod no_global_assign;
+pub mod no_global_timer_leaks;
 pub mod no_import_assign;
+pub mod no_import_map_unmapped_bare_specifiers;
 pub mod no_inferrable_types;
+pub mod no_inline_enum_values_duplication;
 pub mod no_inner_declarations;
 pub mod no_invalid_regexp;
 pub mod no_irregular_whitespace;
+pub mod no_misordered_await_parallelism;
 pub mod no_misused_new;
+pub mod no_misused_spread;
 pub mod no_mixed_spaces_and_tabs;
+pub mod no_mutable_default_parameters;
 pub mod no_namespace;
+pub mod no_new;
+pub mod no_new_date_arithmetic;
 pub mod no_new_symbol;
 pub mod no_non_null_asserted_optional_chain;
 pub mod no_non_null_assertion;
+pub mod no_nonoctal_decimal_escape;
 pub mod no_obj_calls;
 pub mod no_octal;
+pub mod no_octal_escape;
 pub mod no_prototype_builtins;
 pub mod no_redeclare;
+pub mod no_redundant_await;
 pub mod no_regex_spaces;
+pub mod no_restricted_syntax;
+pub mod no_return_assign;
+pub mod no_return_in_finally_adjacent;
 pub mod no_self_assign;
+pub mod no_sequences;
 pub mod no_setter_return;
+pub mod no_shadow;
 pub mod no_shadow_restricted_names;
+pub mod no_shorthand_property_overwrite;
 pub mod no_sparse_arrays;
+pub mod no_test_only_left_behind;
 pub mod no_this_alias;
 pub mod no_this_before_super;
 pub mod no_throw_literal;
 pub mod no_undef;
+pub mod no_unordered_overload_specificity;
 pub mod no_unreachable;
 pub mod no_unsafe_finally;
+pub mod no_unsafe_inner_html;
 pub mod no_unsafe_negation;
+pub mod no_unsanitized_dynamic_import;
+pub mod no_unused_expressions;
 pub mod no_unused_labels;
 pub mod no_unused_vars;
+pub mod no_unversioned_lockstep_exports;
+pub mod no_useless_spread;
 pub mod no_var;
 pub mod no_with;
+pub mod padded_blocks;
+pub mod prefer_array_some_over_filter_length;
 pub mod prefer_as_const;
 pub mod prefer_const;
+pub mod prefer_date_now;
+pub mod prefer_exponentiation_operator;
 pub mod prefer_namespace_keyword;
+pub mod prefer_object_has_own;
+pub mod prefer_structured_clone;
+pub mod prefer_top_level_await;
+pub mod require_array_sort_compare;
+pub mod require_await;
+pub mod require_cleanup_in_add_event_listener_returning_components;
+pub mod require_deno_permissions_comment;
+pub mod require_env_var_prefix;
+pub mod require_jsdoc_for_public_api;
+pub mod require_unicode_regexp;
 pub mod require_yield;
 pub mod single_var_declarator;
 pub mod sort_imports;
+pub mod sort_interface_members;
+pub mod sort_type_union_members;
+pub mod tabindex_no_positive;
 pub mod triple_slash_reference;
 pub mod use_isnan;
 pub mod valid_typeof;
 
-pub trait LintRule {
+/// A lint rule. `Linter::lint_files` shares the rules between the threads
+/// linting the files, hence `Send + Sync`: state needed while linting a file
+/// belongs to the visitor created in `lint_module`, not to the rule.
+pub trait LintRule: Send + Sync {
   fn new() -> Box<Self>
   where
     Self: Sized;
@@ -96,167 +285,315 @@ pub trait LintRule {
     module: &swc_ecmascript::ast::Module,
   );
   fn code(&self) -> &'static str;
+  fn tags(&self) -> &'static [&'static str] {
+    &[]
+  }
+  /// Documentation of the rule in markdown: a short description, the
+  /// options if it has any, and examples in `### Valid:` and `### Invalid:`
+  /// sections, which the tests lint with the rule.
   fn docs(&self) -> &'static str {
     ""
   }
+  /// Whether the rule can be configured through a `with_options`
+  /// constructor, whose options are listed in the `### Options` section of
+  /// the docs.
+  fn has_options(&self) -> bool {
+    false
+  }
+  /// Whether the rule is slow on large files, eg. because it parses
+  /// comments as code. `LinterBuilder::node_budget` skips expensive rules on
+  /// files with more nodes than the budget.
+  fn is_expensive(&self) -> bool {
+    false
+  }
+  /// Severity of the rule's diagnostics, which `LinterBuilder::severities`
+  /// can override.
+  fn severity(&self) -> Severity {
+    Severity::Error
+  }
 }
 
+/// Returns the rules tagged `recommended`, sorted by code.
 pub fn get_recommended_rules() -> Vec<Box<dyn LintRule>> {
-  vec![
-    adjacent_overload_signatures::AdjacentOverloadSignatures::new(),
-    ban_ts_comment::BanTsComment::new(),
-    ban_types::BanTypes::new(),
-    ban_untagged_ignore::BanUntaggedIgnore::new(),
-    constructor_super::ConstructorSuper::new(),
-    for_direction::ForDirection::new(),
-    getter_return::GetterReturn::new(),
-    no_array_constructor::NoArrayConstructor::new(),
-    no_async_promise_executor::NoAsyncPromiseExecutor::new(),
-    no_case_declarations::NoCaseDeclarations::new(),
-    no_class_assign::NoClassAssign::new(),
-    no_compare_neg_zero::NoCompareNegZero::new(),
-    no_cond_assign::NoCondAssign::new(),
-    no_constant_condition::NoConstantCondition::new(),
-    no_control_regex::NoControlRegex::new(),
-    no_debugger::NoDebugger::new(),
-    no_delete_var::NoDeleteVar::new(),
-    no_dupe_args::NoDupeArgs::new(),
-    no_dupe_class_members::NoDupeClassMembers::new(),
-    no_dupe_else_if::NoDupeElseIf::new(),
-    no_dupe_keys::NoDupeKeys::new(),
-    no_duplicate_case::NoDuplicateCase::new(),
-    no_empty::NoEmpty::new(),
-    no_empty_character_class::NoEmptyCharacterClass::new(),
-    no_empty_interface::NoEmptyInterface::new(),
-    no_empty_pattern::NoEmptyPattern::new(),
-    no_ex_assign::NoExAssign::new(),
-    no_explicit_any::NoExplicitAny::new(),
-    no_extra_boolean_cast::NoExtraBooleanCast::new(),
-    no_extra_non_null_assertion::NoExtraNonNullAssertion::new(),
-    no_extra_semi::NoExtraSemi::new(),
-    no_fallthrough::NoFallthrough::new(),
-    no_func_assign::NoFuncAssign::new(),
-    no_global_assign::NoGlobalAssign::new(),
-    no_import_assign::NoImportAssign::new(),
-    no_inferrable_types::NoInferrableTypes::new(),
-    no_inner_declarations::NoInnerDeclarations::new(),
-    no_invalid_regexp::NoInvalidRegexp::new(),
-    no_irregular_whitespace::NoIrregularWhitespace::new(),
-    no_misused_new::NoMisusedNew::new(),
-    no_mixed_spaces_and_tabs::NoMixedSpacesAndTabs::new(),
-    no_namespace::NoNamespace::new(),
-    no_new_symbol::NoNewSymbol::new(),
-    no_obj_calls::NoObjCalls::new(),
-    no_octal::NoOctal::new(),
-    no_prototype_builtins::NoPrototypeBuiltins::new(),
-    no_redeclare::NoRedeclare::new(),
-    no_regex_spaces::NoRegexSpaces::new(),
-    no_self_assign::NoSelfAssign::new(),
-    no_setter_return::NoSetterReturn::new(),
-    no_shadow_restricted_names::NoShadowRestrictedNames::new(),
-    no_this_alias::NoThisAlias::new(),
-    no_this_before_super::NoThisBeforeSuper::new(),
-    no_undef::NoUndef::new(),
-    no_unreachable::NoUnreachable::new(),
-    no_unsafe_finally::NoUnsafeFinally::new(),
-    no_unsafe_negation::NoUnsafeNegation::new(),
-    no_unused_labels::NoUnusedLabels::new(),
-    no_with::NoWith::new(),
-    prefer_as_const::PreferAsConst::new(),
-    prefer_namespace_keyword::PreferNamespaceKeyword::new(),
-    require_yield::RequireYield::new(),
-    triple_slash_reference::TripleSlashReference::new(),
-    use_isnan::UseIsNaN::new(),
-    valid_typeof::ValidTypeof::new(),
-  ]
+  get_rules_tagged("recommended")
+}
+
+/// Rules which are recommended in addition to `get_recommended_rules()` for
+/// test files.
+pub fn get_recommended_test_rules() -> Vec<Box<dyn LintRule>> {
+  get_rules_tagged("recommended_for_tests")
+}
+
+fn get_rules_tagged(tag: &str) -> Vec<Box<dyn LintRule>> {
+  let mut rules = get_all_rules()
+    .into_iter()
+    .filter(|rule| rule.tags().contains(&tag))
+    .collect::<Vec<_>>();
+  rules.sort_by_key(|rule| rule.code());
+  rules
+}
+
+/// Checks if the file is a test file by the naming convention of
+/// `deno test`: `test.ts`, `*_test.ts` or `*.test.ts`, with any JavaScript
+/// or TypeScript extension.
+pub fn is_test_file(file_name: &str) -> bool {
+  let name = file_name.rsplit(&['/', '\\'][..]).next().unwrap();
+  let index = match name.rfind('.') {
+    Some(index) => index,
+    None => return false,
+  };
+  let (stem, extension) = (&name[..index], &name[index + 1..]);
+  if !["js", "mjs", "jsx", "ts", "tsx"].contains(&extension) {
+    return false;
+  }
+  stem == "test" || stem.ends_with("_test") || stem.ends_with(".test")
+}
+
+/// Returns the recommended rules for the file, which include
+/// `get_recommended_test_rules()` if it's a test file.
+pub fn get_recommended_rules_for_file(
+  file_name: &str,
+) -> Vec<Box<dyn LintRule>> {
+  let mut rules = get_recommended_rules();
+  if is_test_file(file_name) {
+    rules.extend(get_recommended_test_rules());
+    rules.sort_by_key(|r| r.code());
+  }
+  rules
+}
+
+/// Error returned by `get_filtered_rules()` for rule codes which aren't
+/// registered, which are most likely typos.
+#[derive(Debug, PartialEq)]
+pub struct UnknownRuleCodes {
+  pub codes: Vec<String>,
+}
+
+impl std::fmt::Display for UnknownRuleCodes {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Unknown rule codes: {}", self.codes.join(", "))
+  }
+}
+
+impl std::error::Error for UnknownRuleCodes {}
+
+/// Returns the rules listed in `include`, or the recommended rules if it's
+/// empty, without the ones listed in `exclude`. The rules are sorted by
+/// code.
+pub fn get_filtered_rules(
+  include: &[&str],
+  exclude: &[&str],
+) -> Result<Vec<Box<dyn LintRule>>, UnknownRuleCodes> {
+  let all_rules = get_all_rules();
+  let unknown_codes = include
+    .iter()
+    .chain(exclude.iter())
+    .filter(|code| !all_rules.iter().any(|rule| rule.code() == **code))
+    .map(|code| code.to_string())
+    .collect::<Vec<_>>();
+  if !unknown_codes.is_empty() {
+    return Err(UnknownRuleCodes {
+      codes: unknown_codes,
+    });
+  }
+
+  let mut rules = if include.is_empty() {
+    get_recommended_rules()
+  } else {
+    all_rules
+      .into_iter()
+      .filter(|rule| include.contains(&rule.code()))
+      .collect()
+  };
+  rules.retain(|rule| !exclude.contains(&rule.code()));
+  rules.sort_by_key(|rule| rule.code());
+  Ok(rules)
+}
+
+/// Returns the documentation of the rule with the given code, see
+/// `LintRule::docs`.
+pub fn get_rule_docs(code: &str) -> Option<&'static str> {
+  get_all_rules()
+    .into_iter()
+    .find(|rule| rule.code() == code)
+    .map(|rule| rule.docs())
 }
 
-pub fn get_all_rules() -> Vec<Box<dyn LintRule>> {
-  vec![
-    adjacent_overload_signatures::AdjacentOverloadSignatures::new(),
-    ban_ts_comment::BanTsComment::new(),
-    ban_ts_ignore::BanTsIgnore::new(),
-    ban_types::BanTypes::new(),
-    ban_untagged_ignore::BanUntaggedIgnore::new(),
-    ban_untagged_todo::BanUntaggedTodo::new(),
-    constructor_super::ConstructorSuper::new(),
-    default_param_last::DefaultParamLast::new(),
-    eqeqeq::Eqeqeq::new(),
-    explicit_function_return_type::ExplicitFunctionReturnType::new(),
-    explicit_module_boundary_types::ExplicitModuleBoundaryTypes::new(),
-    for_direction::ForDirection::new(),
-    getter_return::GetterReturn::new(),
-    no_array_constructor::NoArrayConstructor::new(),
-    no_async_promise_executor::NoAsyncPromiseExecutor::new(),
-    no_await_in_loop::NoAwaitInLoop::new(),
-    no_case_declarations::NoCaseDeclarations::new(),
-    no_class_assign::NoClassAssign::new(),
-    no_compare_neg_zero::NoCompareNegZero::new(),
-    no_cond_assign::NoCondAssign::new(),
-    no_const_assign::NoConstAssign::new(),
-    no_constant_condition::NoConstantCondition::new(),
-    no_control_regex::NoControlRegex::new(),
-    no_debugger::NoDebugger::new(),
-    no_delete_var::NoDeleteVar::new(),
-    no_dupe_args::NoDupeArgs::new(),
-    no_dupe_class_members::NoDupeClassMembers::new(),
-    no_dupe_else_if::NoDupeElseIf::new(),
-    no_dupe_keys::NoDupeKeys::new(),
-    no_duplicate_case::NoDuplicateCase::new(),
-    no_empty::NoEmpty::new(),
-    no_empty_character_class::NoEmptyCharacterClass::new(),
-    no_empty_interface::NoEmptyInterface::new(),
-    no_empty_pattern::NoEmptyPattern::new(),
-    no_eval::NoEval::new(),
-    no_ex_assign::NoExAssign::new(),
-    no_explicit_any::NoExplicitAny::new(),
-    no_extra_boolean_cast::NoExtraBooleanCast::new(),
-    no_extra_non_null_assertion::NoExtraNonNullAssertion::new(),
-    no_extra_semi::NoExtraSemi::new(),
-    no_fallthrough::NoFallthrough::new(),
-    no_func_assign::NoFuncAssign::new(),
-    no_global_assign::NoGlobalAssign::new(),
-    no_import_assign::NoImportAssign::new(),
-    no_inferrable_types::NoInferrableTypes::new(),
-    no_inner_declarations::NoInnerDeclarations::new(),
-    no_invalid_regexp::NoInvalidRegexp::new(),
-    no_irregular_whitespace::NoIrregularWhitespace::new(),
-    no_misused_new::NoMisusedNew::new(),
-    no_mixed_spaces_and_tabs::NoMixedSpacesAndTabs::new(),
-    no_namespace::NoNamespace::new(),
-    no_new_symbol::NoNewSymbol::new(),
-    no_non_null_asserted_optional_chain::NoNonNullAssertedOptionalChain::new(),
-    no_non_null_assertion::NoNonNullAssertion::new(),
-    no_obj_calls::NoObjCalls::new(),
-    no_octal::NoOctal::new(),
-    no_prototype_builtins::NoPrototypeBuiltins::new(),
-    no_redeclare::NoRedeclare::new(),
-    no_regex_spaces::NoRegexSpaces::new(),
-    no_self_assign::NoSelfAssign::new(),
-    no_setter_return::NoSetterReturn::new(),
-    no_shadow_restricted_names::NoShadowRestrictedNames::new(),
-    no_sparse_arrays::NoSparseArrays::new(),
-    no_this_alias::NoThisAlias::new(),
-    no_this_before_super::NoThisBeforeSuper::new(),
-    no_throw_literal::NoThrowLiteral::new(),
-    no_undef::NoUndef::new(),
-    no_unreachable::NoUnreachable::new(),
-    no_unsafe_finally::NoUnsafeFinally::new(),
-    no_unsafe_negation::NoUnsafeNegation::new(),
-    no_unused_labels::NoUnusedLabels::new(),
-    no_unused_vars::NoUnusedVars::new(),
-    no_var::NoVar::new(),
-    no_with::NoWith::new(),
-    prefer_as_const::PreferAsConst::new(),
-    prefer_const::PreferConst::new(),
-    prefer_namespace_keyword::PreferNamespaceKeyword::new(),
-    require_yield::RequireYield::new(),
-    single_var_declarator::SingleVarDeclarator::new(),
-    sort_imports::SortImports::new(),
-    triple_slash_reference::TripleSlashReference::new(),
-    use_isnan::UseIsNaN::new(),
-    valid_typeof::ValidTypeof::new(),
-  ]
+register_rules![
+  adjacent_overload_signatures::AdjacentOverloadSignatures,
+  array_type::ArrayType,
+  ban_commented_out_code::BanCommentedOutCode,
+  ban_reassigning_imported_namespace_members::BanReassigningImportedNamespaceMembers,
+  ban_ts_comment::BanTsComment,
+  ban_ts_ignore::BanTsIgnore,
+  ban_types::BanTypes,
+  ban_untagged_ignore::BanUntaggedIgnore,
+  ban_untagged_todo::BanUntaggedTodo,
+  camelcase::Camelcase,
+  click_events_have_key_events::ClickEventsHaveKeyEvents,
+  consistent_filename_casing::ConsistentFilenameCasing,
+  consistent_generic_constructors::ConsistentGenericConstructors,
+  consistent_this_arrow_usage::ConsistentThisArrowUsage,
+  constructor_super::ConstructorSuper,
+  curly::Curly,
+  default_param_last::DefaultParamLast,
+  eol_last::EolLast,
+  eqeqeq::Eqeqeq,
+  explicit_function_return_type::ExplicitFunctionReturnType,
+  explicit_member_accessibility::ExplicitMemberAccessibility,
+  explicit_module_boundary_types::ExplicitModuleBoundaryTypes,
+  for_direction::ForDirection,
+  getter_return::GetterReturn,
+  jsx_boolean_value::JsxBooleanValue,
+  jsx_no_duplicate_props::JsxNoDuplicateProps,
+  max_nested_callbacks::MaxNestedCallbacks,
+  max_statements_per_line::MaxStatementsPerLine,
+  no_array_constructor::NoArrayConstructor,
+  no_async_array_callback_misuse::NoAsyncArrayCallbackMisuse,
+  no_async_executor_race::NoAsyncExecutorRace,
+  no_async_promise_executor::NoAsyncPromiseExecutor,
+  no_await_in_loop::NoAwaitInLoop,
+  no_bigint_and_number_mixing::NoBigintAndNumberMixing,
+  no_bitwise::NoBitwise,
+  no_case_declarations::NoCaseDeclarations,
+  no_class_as_namespace::NoClassAsNamespace,
+  no_class_assign::NoClassAssign,
+  no_compare_neg_zero::NoCompareNegZero,
+  no_cond_assign::NoCondAssign,
+  no_console::NoConsole,
+  no_const_assign::NoConstAssign,
+  no_constant_binary_expression::NoConstantBinaryExpression,
+  no_constant_condition::NoConstantCondition,
+  no_control_regex::NoControlRegex,
+  no_debugger::NoDebugger,
+  no_delete_var::NoDeleteVar,
+  no_done_callback::NoDoneCallback,
+  no_dupe_args::NoDupeArgs,
+  no_dupe_class_members::NoDupeClassMembers,
+  no_dupe_else_if::NoDupeElseIf,
+  no_dupe_keys::NoDupeKeys,
+  no_duplicate_case::NoDuplicateCase,
+  no_duplicate_imports::NoDuplicateImports,
+  no_duplicate_spread_overrides::NoDuplicateSpreadOverrides,
+  no_duplicate_union_discriminants_in_switch::NoDuplicateUnionDiscriminantsInSwitch,
+  no_empty::NoEmpty,
+  no_empty_character_class::NoEmptyCharacterClass,
+  no_empty_interface::NoEmptyInterface,
+  no_empty_pattern::NoEmptyPattern,
+  no_eval::NoEval,
+  no_ex_assign::NoExAssign,
+  no_explicit_any::NoExplicitAny,
+  no_external_mutable_state_capture::NoExternalMutableStateCapture,
+  no_extra_boolean_cast::NoExtraBooleanCast,
+  no_extra_non_null_assertion::NoExtraNonNullAssertion,
+  no_extra_semi::NoExtraSemi,
+  no_fallthrough::NoFallthrough,
+  no_floating_void_operator::NoFloatingVoidOperator,
+  no_func_assign::NoFuncAssign,
+  no_global_assign::NoGlobalAssign,
+  no_global_timer_leaks::NoGlobalTimerLeaks,
+  no_import_assign::NoImportAssign,
+  no_import_map_unmapped_bare_specifiers::NoImportMapUnmappedBareSpecifiers,
+  no_inferrable_types::NoInferrableTypes,
+  no_inline_enum_values_duplication::NoInlineEnumValuesDuplication,
+  no_inner_declarations::NoInnerDeclarations,
+  no_invalid_regexp::NoInvalidRegexp,
+  no_irregular_whitespace::NoIrregularWhitespace,
+  no_misordered_await_parallelism::NoMisorderedAwaitParallelism,
+  no_misused_new::NoMisusedNew,
+  no_misused_spread::NoMisusedSpread,
+  no_mixed_spaces_and_tabs::NoMixedSpacesAndTabs,
+  no_mutable_default_parameters::NoMutableDefaultParameters,
+  no_namespace::NoNamespace,
+  no_new::NoNew,
+  no_new_date_arithmetic::NoNewDateArithmetic,
+  no_new_symbol::NoNewSymbol,
+  no_non_null_asserted_optional_chain::NoNonNullAssertedOptionalChain,
+  no_non_null_assertion::NoNonNullAssertion,
+  no_nonoctal_decimal_escape::NoNonoctalDecimalEscape,
+  no_obj_calls::NoObjCalls,
+  no_octal::NoOctal,
+  no_octal_escape::NoOctalEscape,
+  no_prototype_builtins::NoPrototypeBuiltins,
+  no_redeclare::NoRedeclare,
+  no_redundant_await::NoRedundantAwait,
+  no_regex_spaces::NoRegexSpaces,
+  no_restricted_syntax::NoRestrictedSyntax,
+  no_return_assign::NoReturnAssign,
+  no_return_in_finally_adjacent::NoReturnInFinallyAdjacent,
+  no_self_assign::NoSelfAssign,
+  no_sequences::NoSequences,
+  no_setter_return::NoSetterReturn,
+  no_shadow::NoShadow,
+  no_shadow_restricted_names::NoShadowRestrictedNames,
+  no_shorthand_property_overwrite::NoShorthandPropertyOverwrite,
+  no_sparse_arrays::NoSparseArrays,
+  no_test_only_left_behind::NoTestOnlyLeftBehind,
+  no_this_alias::NoThisAlias,
+  no_this_before_super::NoThisBeforeSuper,
+  no_throw_literal::NoThrowLiteral,
+  no_undef::NoUndef,
+  no_unordered_overload_specificity::NoUnorderedOverloadSpecificity,
+  no_unreachable::NoUnreachable,
+  no_unsafe_finally::NoUnsafeFinally,
+  no_unsafe_inner_html::NoUnsafeInnerHtml,
+  no_unsafe_negation::NoUnsafeNegation,
+  no_unsanitized_dynamic_import::NoUnsanitizedDynamicImport,
+  no_unused_expressions::NoUnusedExpressions,
+  no_unused_labels::NoUnusedLabels,
+  no_unused_vars::NoUnusedVars,
+  no_unversioned_lockstep_exports::NoUnversionedLockstepExports,
+  no_useless_spread::NoUselessSpread,
+  no_var::NoVar,
+  no_with::NoWith,
+  padded_blocks::PaddedBlocks,
+  prefer_array_some_over_filter_length::PreferArraySomeOverFilterLength,
+  prefer_as_const::PreferAsConst,
+  prefer_const::PreferConst,
+  prefer_date_now::PreferDateNow,
+  prefer_exponentiation_operator::PreferExponentiationOperator,
+  prefer_namespace_keyword::PreferNamespaceKeyword,
+  prefer_object_has_own::PreferObjectHasOwn,
+  prefer_structured_clone::PreferStructuredClone,
+  prefer_top_level_await::PreferTopLevelAwait,
+  require_array_sort_compare::RequireArraySortCompare,
+  require_await::RequireAwait,
+  require_cleanup_in_add_event_listener_returning_components::RequireCleanupInAddEventListenerReturningComponents,
+  require_deno_permissions_comment::RequireDenoPermissionsComment,
+  require_env_var_prefix::RequireEnvVarPrefix,
+  require_jsdoc_for_public_api::RequireJsdocForPublicApi,
+  require_unicode_regexp::RequireUnicodeRegexp,
+  require_yield::RequireYield,
+  single_var_declarator::SingleVarDeclarator,
+  sort_imports::SortImports,
+  sort_interface_members::SortInterfaceMembers,
+  sort_type_union_members::SortTypeUnionMembers,
+  tabindex_no_positive::TabindexNoPositive,
+  triple_slash_reference::TripleSlashReference,
+  use_isnan::UseIsNaN,
+  valid_typeof::ValidTypeof,
+];
+
+/// Serializes what is known about every registered rule, sorted by code,
+/// so tooling can list the rules without linting anything: the `code`,
+/// `tags`, default `severity`, whether it `hasOptions` and its `docs`.
+#[cfg(feature = "json")]
+pub fn metadata_to_json() -> String {
+  let mut rules = get_all_rules();
+  rules.sort_by_key(|rule| rule.code());
+  let metadata = rules
+    .iter()
+    .map(|rule| {
+      serde_json::json!({
+        "code": rule.code(),
+        "tags": rule.tags(),
+        "severity": rule.severity(),
+        "hasOptions": rule.has_options(),
+        "docs": rule.docs(),
+      })
+    })
+    .collect::<Vec<_>>();
+  serde_json::to_string_pretty(&metadata).unwrap()
 }
 
 #[cfg(test)]
@@ -274,6 +611,131 @@ mod tests {
     }
   }
 
+  #[test]
+  fn all_rule_files_registered() {
+    let rules_dir =
+      std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/rules");
+    let rule_files = std::fs::read_dir(rules_dir)
+      .unwrap()
+      .map(|entry| entry.unwrap().path())
+      .filter(|path| !path.ends_with("mod.rs"))
+      .map(|path| std::fs::read_to_string(path).unwrap())
+      .filter(|source| {
+        source.contains("impl LintRule for")
+          || source.contains("declare_rule!")
+          || source.contains("declare_statement_rule!")
+      })
+      .count();
+    assert_eq!(get_all_rules().len(), rule_files);
+  }
+
+  #[test]
+  fn recommended_rules_by_tag() {
+    let codes = |rules: Vec<Box<dyn LintRule>>| {
+      rules.iter().map(|r| r.code()).collect::<Vec<_>>()
+    };
+    let recommended = codes(get_recommended_rules());
+    assert!(recommended.contains(&"no-debugger"));
+    assert!(!recommended.contains(&"no-test-only-left-behind"));
+    assert!(!recommended.contains(&"eqeqeq"));
+    assert_eq!(
+      codes(get_recommended_test_rules()),
+      vec!["no-test-only-left-behind"]
+    );
+  }
+
+  #[test]
+  fn options_are_documented() {
+    for rule in get_all_rules() {
+      assert_eq!(
+        rule.has_options(),
+        rule.docs().contains("### Options"),
+        "{}",
+        rule.code()
+      );
+    }
+  }
+
+  #[test]
+  fn recommended_rules_for_test_files() {
+    for file_name in &[
+      "test.ts",
+      "copy_file_test.ts",
+      "copy_file.test.js",
+      "/project/fs/copy_file_test.tsx",
+      "C:\\project\\fs\\copy_file_test.mjs",
+    ] {
+      assert!(is_test_file(file_name), "{}", file_name);
+    }
+    for file_name in &[
+      "copy_file.ts",
+      "testing.ts",
+      "latest.ts",
+      "copy_file_test.json",
+      "/project/test/copy_file.ts",
+    ] {
+      assert!(!is_test_file(file_name), "{}", file_name);
+    }
+
+    let codes = |file_name| {
+      get_recommended_rules_for_file(file_name)
+        .iter()
+        .map(|r| r.code())
+        .collect::<Vec<_>>()
+    };
+    assert!(codes("copy_file_test.ts").contains(&"no-test-only-left-behind"));
+    assert!(!codes("copy_file.ts").contains(&"no-test-only-left-behind"));
+    assert_eq!(codes("copy_file.ts").len(), get_recommended_rules().len());
+    let mut sorted = codes("copy_file_test.ts");
+    sorted.sort_unstable();
+    assert_eq!(codes("copy_file_test.ts"), sorted);
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn metadata_to_json_snapshot() {
+    let metadata: serde_json::Value =
+      serde_json::from_str(&metadata_to_json()).unwrap();
+    let rules = metadata.as_array().unwrap();
+    assert_eq!(rules.len(), get_all_rules().len());
+
+    let find = |code: &str| {
+      rules
+        .iter()
+        .find(|rule| rule["code"] == code)
+        .unwrap()
+        .clone()
+    };
+    assert_eq!(
+      find("no-debugger"),
+      serde_json::json!({
+        "code": "no-debugger",
+        "tags": ["recommended"],
+        "severity": "error",
+        "hasOptions": false,
+        "docs": get_rule_docs("no-debugger").unwrap(),
+      })
+    );
+    assert_eq!(
+      find("sort-imports"),
+      serde_json::json!({
+        "code": "sort-imports",
+        "tags": [],
+        "severity": "error",
+        "hasOptions": true,
+        "docs": get_rule_docs("sort-imports").unwrap(),
+      })
+    );
+
+    let codes = rules
+      .iter()
+      .map(|rule| rule["code"].as_str().unwrap())
+      .collect::<Vec<_>>();
+    let mut sorted_codes = codes.clone();
+    sorted_codes.sort_unstable();
+    assert_eq!(codes, sorted_codes);
+  }
+
   #[test]
   fn all_rules_sorted_alphabetically() {
     let mut all_rules = get_all_rules();
@@ -282,4 +744,145 @@ mod tests {
       assert_eq!(sorted.code(), unsorted.code());
     }
   }
+
+  #[test]
+  fn all_rules_have_unique_codes() {
+    let mut codes = std::collections::HashSet::new();
+    for rule in get_all_rules() {
+      assert!(!rule.code().is_empty());
+      assert!(
+        codes.insert(rule.code()),
+        "{} is registered twice",
+        rule.code()
+      );
+    }
+  }
+
+  #[test]
+  fn filtered_rules() {
+    let codes = |include: &[&str], exclude: &[&str]| {
+      get_filtered_rules(include, exclude)
+        .unwrap()
+        .iter()
+        .map(|r| r.code())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(
+      codes(&["sort-imports", "no-debugger"], &[]),
+      vec!["no-debugger", "sort-imports"]
+    );
+    assert_eq!(
+      codes(&["sort-imports", "no-debugger"], &["no-debugger"]),
+      vec!["sort-imports"]
+    );
+    let recommended = codes(&[], &["no-debugger"]);
+    assert_eq!(recommended.len(), get_recommended_rules().len() - 1);
+    assert!(!recommended.contains(&"no-debugger"));
+  }
+
+  #[test]
+  fn filtered_rules_unknown_codes() {
+    let err = get_filtered_rules(&["sort-import", "no-debugger"], &["no-vars"])
+      .err()
+      .unwrap();
+    assert_eq!(
+      err,
+      UnknownRuleCodes {
+        codes: vec!["sort-import".to_string(), "no-vars".to_string()]
+      }
+    );
+    assert_eq!(err.to_string(), "Unknown rule codes: sort-import, no-vars");
+  }
+
+  /// The source of an example, and whether it's JSX.
+  type DocExample = (String, bool);
+
+  /// Returns the code blocks of the `### Valid:` and `### Invalid:`
+  /// sections of `docs`.
+  fn doc_examples(docs: &str) -> (Vec<DocExample>, Vec<DocExample>) {
+    let mut valid = vec![];
+    let mut invalid = vec![];
+    let mut section = "";
+    let mut block: Option<DocExample> = None;
+    for line in docs.lines() {
+      if let Some(mut current) = block.take() {
+        if line.starts_with("```") {
+          match section {
+            "### Valid:" => valid.push(current),
+            "### Invalid:" => invalid.push(current),
+            _ => {}
+          }
+        } else {
+          current.0.push_str(line);
+          current.0.push('\n');
+          block = Some(current);
+        }
+      } else if let Some(language) = line.strip_prefix("```") {
+        block = Some((String::new(), language == "tsx"));
+      } else if line.starts_with("### ") {
+        section = line;
+      }
+    }
+    (valid, invalid)
+  }
+
+  #[test]
+  fn all_rules_documented() {
+    for rule in get_all_rules() {
+      let docs = rule.docs();
+      assert!(!docs.trim().is_empty(), "{} has no docs", rule.code());
+      assert!(docs.contains("```"), "{} has no examples", rule.code());
+      assert_eq!(get_rule_docs(rule.code()), Some(docs));
+    }
+    assert_eq!(get_rule_docs("no-such-rule"), None);
+  }
+
+  #[test]
+  fn rule_docs_examples() {
+    use crate::test_util::{lint, lint_tsx, with_syntax_errors};
+
+    let rule = |code: &str| {
+      get_all_rules()
+        .into_iter()
+        .find(|rule| rule.code() == code)
+        .unwrap()
+    };
+    let lint_example = |code: &str, (source, tsx): &DocExample| {
+      if *tsx {
+        lint_tsx(rule(code), source)
+      } else {
+        lint(rule(code), source)
+      }
+    };
+    // Their invalid examples are only allowed in scripts, not in modules
+    let script_only = ["no-delete-var", "no-octal", "no-with"];
+    for code in get_all_rules().iter().map(|rule| rule.code()) {
+      let (valid, invalid) = doc_examples(rule(code).docs());
+      for example in &valid {
+        let diagnostics = lint_example(code, example);
+        assert!(
+          diagnostics.is_empty(),
+          "valid example of {} is reported:\n{}\n{:#?}",
+          code,
+          example.0,
+          diagnostics
+        );
+      }
+      for example in &invalid {
+        let assert_reported = || {
+          assert!(
+            !lint_example(code, example).is_empty(),
+            "invalid example of {} isn't reported:\n{}",
+            code,
+            example.0
+          );
+        };
+        if script_only.contains(&code) {
+          with_syntax_errors(assert_reported);
+        } else {
+          assert_reported();
+        }
+      }
+    }
+  }
 }
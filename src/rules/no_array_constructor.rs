@@ -21,6 +21,31 @@ impl LintRule for NoArrayConstructor {
     "no-array-constructor"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows the `Array` constructor, except for creating sparse arrays of a given length.
+
+Array literals are shorter and don't have the special case of `new Array(n)` creating an array of length `n`.
+
+### Valid:
+```typescript
+const a = new Array(100);
+const b = [];
+const c = [0, 1, 2];
+```
+
+### Invalid:
+```typescript
+const a = new Array();
+```
+```typescript
+const b = new Array(0, 1, 2);
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -0,0 +1,360 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::{
+  array_bindings, async_functions, function_head_span, method_call,
+  ArrayBindings,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{CallExpr, Expr, ExprOrSpread, Module};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+pub struct NoAsyncArrayCallbackMisuseOptions {
+  /// Methods whose callback is reported when it's async.
+  pub methods: Vec<String>,
+  /// Only reports calls on array literals and on `const` bindings which are
+  /// initialized to an array, instead of on any receiver.
+  pub require_array_like_receiver: bool,
+}
+
+impl Default for NoAsyncArrayCallbackMisuseOptions {
+  fn default() -> Self {
+    Self {
+      methods: ["forEach", "filter", "some", "every", "find", "findIndex"]
+        .iter()
+        .map(|method| method.to_string())
+        .collect(),
+      require_array_like_receiver: false,
+    }
+  }
+}
+
+/// Reports async callbacks passed to array methods which don't wait for
+/// the returned promises: `forEach` leaves their rejections unhandled, and
+/// `filter`, `some`, `every`, `find` and `findIndex` treat the promises as
+/// truthy values.
+///
+/// `map` isn't in the default list, since mapping to promises and passing
+/// them to `Promise.all()` is the usual way to run async callbacks.
+/// Callbacks passed by name are reported when they refer to an async
+/// function declared in the same file.
+pub struct NoAsyncArrayCallbackMisuse {
+  options: NoAsyncArrayCallbackMisuseOptions,
+}
+
+impl NoAsyncArrayCallbackMisuse {
+  pub fn with_options(options: NoAsyncArrayCallbackMisuseOptions) -> Box<Self> {
+    Box::new(NoAsyncArrayCallbackMisuse { options })
+  }
+}
+
+impl LintRule for NoAsyncArrayCallbackMisuse {
+  fn new() -> Box<Self> {
+    Self::with_options(NoAsyncArrayCallbackMisuseOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-async-array-callback-misuse"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows async callbacks for array methods which don't wait for the returned promises.
+
+`forEach` leaves the rejections of the promises unhandled and continues before they settle, and `filter`, `some`, `every`, `find` and `findIndex` treat the promises as truthy values. `map` is allowed, since mapping to promises and passing them to `Promise.all()` is the usual way to run async callbacks.
+
+### Options
+
+- `NoAsyncArrayCallbackMisuseOptions::methods`: the methods to check, `["forEach", "filter", "some", "every", "find", "findIndex"]` by default.
+- `NoAsyncArrayCallbackMisuseOptions::require_array_like_receiver`: only checks calls on array literals and on `const` bindings initialized to an array, `false` by default.
+
+### Valid:
+```typescript
+items.forEach((item) => save(item));
+```
+```typescript
+await Promise.all(items.map(async (item) => await save(item)));
+```
+
+### Invalid:
+```typescript
+items.forEach(async (item) => {
+  await save(item);
+});
+```
+```typescript
+items.filter(async (item) => await check(item));
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoAsyncArrayCallbackMisuseVisitor {
+      context,
+      options: &self.options,
+      async_functions: async_functions(module),
+      arrays: array_bindings(module),
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn message_and_hint(method: &str) -> (String, &'static str) {
+  let for_of = "Use `for...of` with `await` to run the callbacks one by one, or `await Promise.all(array.map(...))` to run them concurrently";
+  let await_first = "Await the results first, e.g. with `await Promise.all(array.map(...))`, and then use the resolved values";
+  match method {
+    "forEach" => (
+      "`forEach` doesn't wait for async callbacks, so their rejections are unhandled and the code after it runs before they finish".to_string(),
+      for_of,
+    ),
+    "filter" => (
+      "`filter` treats the promise returned by an async callback as truthy, so every element is kept".to_string(),
+      await_first,
+    ),
+    "some" => (
+      "`some` treats the promise returned by an async callback as truthy, so it returns `true` for any non-empty array".to_string(),
+      await_first,
+    ),
+    "every" => (
+      "`every` treats the promise returned by an async callback as truthy, so it always returns `true`".to_string(),
+      await_first,
+    ),
+    "find" => (
+      "`find` treats the promise returned by an async callback as truthy, so it always returns the first element".to_string(),
+      await_first,
+    ),
+    "findIndex" => (
+      "`findIndex` treats the promise returned by an async callback as truthy, so it always returns `0` for a non-empty array".to_string(),
+      await_first,
+    ),
+    _ => (
+      format!(
+        "`{}` doesn't wait for the promises returned by async callbacks",
+        method
+      ),
+      for_of,
+    ),
+  }
+}
+
+struct NoAsyncArrayCallbackMisuseVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a NoAsyncArrayCallbackMisuseOptions,
+  async_functions: HashSet<Id>,
+  arrays: ArrayBindings,
+}
+
+impl<'a> NoAsyncArrayCallbackMisuseVisitor<'a> {
+  /// Returns the span of the callback up to its body, e.g. `async (x) =>`,
+  /// or of the whole expression when it's a reference to an async function.
+  fn async_callback_head(&self, callback: &Expr) -> Option<Span> {
+    match unwrap_paren(callback) {
+      Expr::Arrow(arrow) if arrow.is_async => {
+        function_head_span(&self.context, callback)
+      }
+      Expr::Fn(fn_expr) if fn_expr.function.is_async => {
+        function_head_span(&self.context, callback)
+      }
+      Expr::Ident(ident) if self.async_functions.contains(&ident.to_id()) => {
+        Some(ident.span)
+      }
+      _ => None,
+    }
+  }
+
+  fn check(&self, call_expr: &CallExpr) {
+    let (method, receiver) = match method_call(call_expr) {
+      Some(method_call) => method_call,
+      None => return,
+    };
+    if !self.options.methods.iter().any(|m| m == method) {
+      return;
+    }
+    if self.options.require_array_like_receiver
+      && !self.arrays.is_array(receiver)
+    {
+      return;
+    }
+    let callback = match call_expr.args.first() {
+      Some(ExprOrSpread { spread: None, expr }) => expr,
+      _ => return,
+    };
+    let span = match self.async_callback_head(callback) {
+      Some(span) => span,
+      None => return,
+    };
+
+    let (message, hint) = message_and_hint(method);
+    self.context.add_diagnostic_with_hint(
+      span,
+      "no-async-array-callback-misuse",
+      &message,
+      hint,
+    );
+  }
+}
+
+impl<'a> Visit for NoAsyncArrayCallbackMisuseVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    self.check(call_expr);
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_async_array_callback_misuse_valid() {
+    assert_lint_ok_n::<NoAsyncArrayCallbackMisuse>(vec![
+      "items.forEach((item) => save(item));",
+      "items.filter(function (item) { return item.ok; });",
+      "await Promise.all(items.map(async (item) => save(item)));",
+      "items.map(async function (item) { await save(item); });",
+      "for (const item of items) { await save(item); }",
+      "forEach(async (item) => save(item));",
+      "items.forEach(...callbacks);",
+      "items.then(async () => {});",
+      // `save` isn't known to be async.
+      "items.forEach(save);",
+      "function save(item) {}\nitems.forEach(save);",
+      "let save = async (item) => {};\nsave = (item) => {};\nitems.forEach(save);",
+    ]);
+  }
+
+  #[test]
+  fn no_async_array_callback_misuse_invalid() {
+    assert_lint_err::<NoAsyncArrayCallbackMisuse>(
+      "items.forEach(async (item) => { await save(item); });",
+      14,
+    );
+    assert_lint_err::<NoAsyncArrayCallbackMisuse>(
+      "items.filter(async function (item) { return await check(item); });",
+      13,
+    );
+    assert_lint_err::<NoAsyncArrayCallbackMisuse>(
+      "items.some(async item => await check(item));",
+      11,
+    );
+    assert_lint_err::<NoAsyncArrayCallbackMisuse>(
+      "items.every((async (item) => check(item)));",
+      13,
+    );
+    assert_lint_err::<NoAsyncArrayCallbackMisuse>(
+      "getItems().find(async (item) => check(item));",
+      16,
+    );
+    assert_lint_err::<NoAsyncArrayCallbackMisuse>(
+      "items.findIndex(async (item) => check(item));",
+      16,
+    );
+    assert_lint_err_on_line::<NoAsyncArrayCallbackMisuse>(
+      "async function save(item) {}\nitems.forEach(save);",
+      2,
+      14,
+    );
+    assert_lint_err_on_line::<NoAsyncArrayCallbackMisuse>(
+      "const check = async (item) => true;\nitems.filter(check);",
+      2,
+      13,
+    );
+
+    let diagnostics = lint(
+      NoAsyncArrayCallbackMisuse::new(),
+      "items.filter(async (item) => check(item));",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "`filter` treats the promise returned by an async callback as truthy, so every element is kept"
+    );
+    // The diagnostic covers the head of the callback.
+    assert_eq!(diagnostics[0].range.start.col, 13);
+    assert_eq!(diagnostics[0].range.end.col, 28);
+
+    let diagnostics = lint(
+      NoAsyncArrayCallbackMisuse::new(),
+      "items.forEach(async function (item) { await save(item); });",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "`forEach` doesn't wait for async callbacks, so their rejections are unhandled and the code after it runs before they finish"
+    );
+    assert_eq!(diagnostics[0].range.start.col, 14);
+    assert_eq!(diagnostics[0].range.end.col, 35);
+  }
+
+  #[test]
+  fn no_async_array_callback_misuse_methods() {
+    let rule = || {
+      NoAsyncArrayCallbackMisuse::with_options(
+        NoAsyncArrayCallbackMisuseOptions {
+          methods: vec!["map".to_string(), "reduce".to_string()],
+          ..Default::default()
+        },
+      )
+    };
+    assert_lint_ok_with(rule(), "items.forEach(async (item) => {});");
+    let diagnostics = lint(rule(), "items.map(async (item) => save(item));");
+    assert_eq!(
+      diagnostics[0].message,
+      "`map` doesn't wait for the promises returned by async callbacks"
+    );
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "items.reduce(async (acc, item) => {}, 0);",
+      vec![(1, 13)],
+    );
+  }
+
+  #[test]
+  fn no_async_array_callback_misuse_array_like_receiver() {
+    let rule = || {
+      NoAsyncArrayCallbackMisuse::with_options(
+        NoAsyncArrayCallbackMisuseOptions {
+          require_array_like_receiver: true,
+          ..Default::default()
+        },
+      )
+    };
+    assert_lint_ok_with(rule(), "items.forEach(async (item) => {});");
+    assert_lint_ok_with(
+      rule(),
+      "let items = [];\nitems.forEach(async (item) => {});",
+    );
+    assert_lint_ok_with(rule(), "map.forEach(async (value, key) => {});");
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "[a, b].forEach(async (item) => {});",
+      vec![(1, 15)],
+    );
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "const items = [a, b];\nitems.forEach(async (item) => {});\nitems.map(f).filter(async (item) => true);",
+      vec![(2, 14), (3, 20)],
+    );
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "Array.from(set).some(async (item) => true);",
+      vec![(1, 21)],
+    );
+  }
+}
@@ -0,0 +1,433 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::scopes::{ScopeId, ScopeKind, ScopeManager};
+use std::sync::Arc;
+use swc_common::{BytePos, Span, Spanned};
+use swc_ecmascript::ast::{
+  ArrowExpr, AwaitExpr, BlockStmt, CallExpr, Expr, ExprOrSuper, Function,
+  Module, ModuleItem, Stmt, UnaryOp,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+const PROMISE_METHODS: &[&str] = &["then", "catch", "finally"];
+
+declare_rule! {
+  /// Reports writes to outer bindings from promise callbacks which are not
+  /// awaited, when the binding is read synchronously by one of the following
+  /// statements, ie. most likely before the callback has run:
+  ///
+  /// ```js
+  /// let data;
+  /// fetchData().then((res) => { data = res; });
+  /// render(data);
+  /// ```
+  ///
+  /// Reads inside of other functions (event handlers, other callbacks) and
+  /// reads after an `await` are not reported.
+  NoAsyncExecutorRace, "no-async-executor-race", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows reading a variable which is assigned in a promise callback before the callback has run.
+
+The callbacks of `then`, `catch` and `finally` and async functions run later, so statements following them still see the old value. Reads inside of other functions, like event handlers, and reads after an `await` are allowed.
+
+### Valid:
+```typescript
+let data;
+await fetchData().then((res) => {
+  data = res;
+});
+render(data);
+```
+
+### Invalid:
+```typescript
+let data;
+fetchData().then((res) => {
+  data = res;
+});
+render(data);
+```
+```typescript
+let loaded = false;
+load().finally(() => {
+  loaded = true;
+});
+if (!loaded) {
+  showSpinner();
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoAsyncExecutorRaceVisitor { context };
+    visitor.visit_module(module, module);
+  }
+}
+
+/// A callback which runs after the current synchronous code, only writes
+/// after `after` (the first `await` of an async IIFE) are deferred.
+struct DeferredCallback<'a> {
+  function: &'a Expr,
+  after: Option<BytePos>,
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn is_function(expr: &Expr) -> bool {
+  matches!(expr, Expr::Fn(_) | Expr::Arrow(_))
+}
+
+/// Matches `(async () => { ... })()` and returns the function, along with
+/// the position of its first `await`.
+fn async_iife(expr: &Expr) -> Option<DeferredCallback<'_>> {
+  let callee = match unwrap_paren(expr) {
+    Expr::Call(CallExpr {
+      callee: ExprOrSuper::Expr(callee),
+      ..
+    }) => unwrap_paren(callee),
+    _ => return None,
+  };
+  let is_async = match callee {
+    Expr::Fn(fn_expr) => fn_expr.function.is_async,
+    Expr::Arrow(arrow) => arrow.is_async,
+    _ => false,
+  };
+  if !is_async {
+    return None;
+  }
+
+  // Everything up to the first `await` runs synchronously.
+  let mut finder = AwaitFinder { first: None };
+  match callee {
+    Expr::Fn(fn_expr) => {
+      fn_expr.function.visit_children_with(&mut finder);
+    }
+    Expr::Arrow(arrow) => arrow.visit_children_with(&mut finder),
+    _ => {}
+  }
+  finder.first.map(|after| DeferredCallback {
+    function: callee,
+    after: Some(after),
+  })
+}
+
+/// Collects the callbacks of an expression statement which are not awaited.
+fn deferred_callbacks(stmt: &Stmt) -> Vec<DeferredCallback<'_>> {
+  let mut expr = match stmt {
+    Stmt::Expr(expr_stmt) => unwrap_paren(&expr_stmt.expr),
+    _ => return vec![],
+  };
+  if let Expr::Unary(unary) = expr {
+    if unary.op == UnaryOp::Void {
+      expr = unwrap_paren(&unary.arg);
+    }
+  }
+
+  let mut callbacks = vec![];
+  loop {
+    if let Some(iife) = async_iife(expr) {
+      callbacks.push(iife);
+      break;
+    }
+    let call = match expr {
+      Expr::Call(call) => call,
+      _ => break,
+    };
+    let member = match &call.callee {
+      ExprOrSuper::Expr(callee) => match unwrap_paren(callee) {
+        Expr::Member(member) if !member.computed => member,
+        _ => break,
+      },
+      ExprOrSuper::Super(_) => break,
+    };
+    let is_promise_method = match &*member.prop {
+      Expr::Ident(prop) => PROMISE_METHODS.contains(&prop.sym.as_ref()),
+      _ => false,
+    };
+    if is_promise_method {
+      callbacks.extend(
+        call
+          .args
+          .iter()
+          .map(|arg| unwrap_paren(&arg.expr))
+          .filter(|arg| is_function(arg))
+          .map(|function| DeferredCallback {
+            function,
+            after: None,
+          }),
+      );
+    }
+    expr = match &member.obj {
+      ExprOrSuper::Expr(obj) => unwrap_paren(obj),
+      ExprOrSuper::Super(_) => break,
+    };
+  }
+  callbacks
+}
+
+fn contains_await(stmt: &Stmt) -> bool {
+  let mut finder = AwaitFinder { first: None };
+  stmt.visit_with(stmt, &mut finder);
+  finder.first.is_some()
+}
+
+struct NoAsyncExecutorRaceVisitor {
+  context: Arc<Context>,
+}
+
+impl NoAsyncExecutorRaceVisitor {
+  fn check_stmts(&self, stmts: &[&Stmt]) {
+    let scope_manager = &self.context.scope_manager;
+    for (i, stmt) in stmts.iter().enumerate() {
+      let callbacks = deferred_callbacks(stmt);
+      if callbacks.is_empty() {
+        continue;
+      }
+      // The callbacks might have run once the code awaits something.
+      let later: Vec<Span> = stmts[i + 1..]
+        .iter()
+        .take_while(|later| !contains_await(later))
+        .map(|later| later.span())
+        .collect();
+
+      for callback in callbacks {
+        let function_span = callback.function.span();
+        // Writes before the first `await` of an async IIFE run right away.
+        let deferred_from =
+          callback.after.unwrap_or_else(|| function_span.lo());
+        let writes = scope_manager.references().iter().filter(|reference| {
+          reference.kind().is_write()
+            && function_span.contains(reference.span())
+            && reference.span().lo() >= deferred_from
+            && matches!(
+              scope_manager.binding(reference.id()),
+              Some(binding) if !function_span.contains(binding.span())
+            )
+        });
+        for write in writes {
+          let first_read = scope_manager
+            .references_of(write.id())
+            .filter(|reference| {
+              reference.kind().is_read()
+                && later.iter().any(|stmt| {
+                  stmt.contains(reference.span())
+                    && !is_in_nested_function(
+                      scope_manager,
+                      reference.scope(),
+                      *stmt,
+                    )
+                })
+            })
+            .min_by_key(|reference| reference.span().lo());
+          if let Some(read) = first_read {
+            let name = &write.id().0;
+            self.context.add_diagnostic_with_related(
+              write.span(),
+              "no-async-executor-race",
+              &format!(
+                "`{}` is assigned in a promise callback, but read before the callback has run",
+                name
+              ),
+              "`await` the promise before reading it",
+              vec![(read.span(), format!("`{}` is read here", name))],
+            );
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Checks if `scope` is a function or class nested in `stmt`, or inside of
+/// one, whose code might run at any time.
+fn is_in_nested_function(
+  scope_manager: &ScopeManager,
+  scope: ScopeId,
+  stmt: Span,
+) -> bool {
+  let mut current = Some(scope);
+  while let Some(scope) = current {
+    let scope = scope_manager.scope(scope);
+    if !stmt.contains(scope.span()) {
+      return false;
+    }
+    if matches!(
+      scope.kind(),
+      ScopeKind::Function | ScopeKind::Arrow | ScopeKind::Class
+    ) {
+      return true;
+    }
+    current = scope.parent();
+  }
+  false
+}
+
+impl Visit for NoAsyncExecutorRaceVisitor {
+  noop_visit_type!();
+
+  fn visit_module(&mut self, module: &Module, parent: &dyn Node) {
+    let stmts: Vec<&Stmt> = module
+      .body
+      .iter()
+      .filter_map(|item| match item {
+        ModuleItem::Stmt(stmt) => Some(stmt),
+        ModuleItem::ModuleDecl(_) => None,
+      })
+      .collect();
+    self.check_stmts(&stmts);
+    swc_ecmascript::visit::visit_module(self, module, parent);
+  }
+
+  fn visit_block_stmt(&mut self, block: &BlockStmt, parent: &dyn Node) {
+    let stmts: Vec<&Stmt> = block.stmts.iter().collect();
+    self.check_stmts(&stmts);
+    swc_ecmascript::visit::visit_block_stmt(self, block, parent);
+  }
+}
+
+/// Finds the first `await` outside of nested functions.
+struct AwaitFinder {
+  first: Option<BytePos>,
+}
+
+impl Visit for AwaitFinder {
+  noop_visit_type!();
+
+  fn visit_await_expr(&mut self, await_expr: &AwaitExpr, parent: &dyn Node) {
+    if self.first.is_none() {
+      self.first = Some(await_expr.span.lo());
+    }
+    swc_ecmascript::visit::visit_await_expr(self, await_expr, parent);
+  }
+
+  fn visit_function(&mut self, _function: &Function, _parent: &dyn Node) {}
+
+  fn visit_arrow_expr(&mut self, _arrow: &ArrowExpr, _parent: &dyn Node) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostic::Position;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_async_executor_race_valid() {
+    assert_lint_ok_n::<NoAsyncExecutorRace>(vec![
+      r#"
+let data;
+await fetchData().then((res) => { data = res; });
+render(data);
+      "#,
+      r#"
+let data;
+const p = fetchData().then((res) => { data = res; });
+await p;
+render(data);
+      "#,
+      r#"
+let data;
+fetchData().then((res) => { data = res; });
+button.addEventListener("click", () => render(data));
+      "#,
+      r#"
+let data;
+fetchData().then((res) => { data = res; });
+setTimeout(function () { render(data); }, 1000);
+      "#,
+      r#"
+let data;
+fetchData().then((res) => { let data = res; render(data); });
+render(data);
+      "#,
+      r#"
+let data;
+render(data);
+fetchData().then((res) => { data = res; });
+      "#,
+      r#"
+let ready = false;
+(async () => { ready = true; })();
+console.log(ready);
+      "#,
+      r#"
+let data;
+fetchData().then((res) => { data = res; });
+data = {};
+      "#,
+    ]);
+  }
+
+  #[test]
+  fn no_async_executor_race_invalid() {
+    assert_lint_err_on_line::<NoAsyncExecutorRace>(
+      r#"
+let data;
+fetchData().then((res) => { data = res; });
+render(data);
+      "#,
+      3,
+      28,
+    );
+    assert_lint_err_on_line::<NoAsyncExecutorRace>(
+      r#"
+let loaded = false;
+load().catch(console.error).finally(() => { loaded = true; });
+if (!loaded) { showSpinner(); }
+      "#,
+      3,
+      44,
+    );
+    assert_lint_err_on_line::<NoAsyncExecutorRace>(
+      r#"
+function init() {
+  let count = 0;
+  (async () => {
+    await ready;
+    count++;
+  })();
+  return count;
+}
+      "#,
+      6,
+      4,
+    );
+    assert_lint_err_on_line::<NoAsyncExecutorRace>(
+      r#"
+let config;
+void loadConfig().then(function (c) { config = c; });
+export const debug = config.debug;
+start(config);
+      "#,
+      3,
+      38,
+    );
+  }
+
+  #[test]
+  fn no_async_executor_race_hint() {
+    let diagnostics = lint(
+      NoAsyncExecutorRace::new(),
+      "let a;\nfoo().then((b) => { a = b; });\nuse(a);",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("`await` the promise before reading it")
+    );
+    let related = &diagnostics[0].related;
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].range.start, Position { line: 3, col: 4 });
+    assert_eq!(related[0].message, "`a` is read here");
+  }
+}
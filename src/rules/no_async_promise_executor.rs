@@ -1,7 +1,9 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::ast::{Expr, NewExpr, ParenExpr};
+use crate::swc_util::promise_executor;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{Expr, NewExpr};
 use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
@@ -20,6 +22,32 @@ impl LintRule for NoAsyncPromiseExecutor {
     "no-async-promise-executor"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows async functions as the executor of `new Promise()`.
+
+Errors thrown by an async executor are lost instead of rejecting the promise, and an executor which needs `await` usually means the `Promise` constructor isn't needed. Only executors written inline are checked.
+
+### Valid:
+```typescript
+new Promise(function (resolve, reject) {});
+```
+```typescript
+new Promise((resolve, reject) => {});
+```
+
+### Invalid:
+```typescript
+new Promise(async function (resolve, reject) {});
+```
+```typescript
+new Promise(async (resolve, reject) => {});
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -44,7 +72,6 @@ fn is_async_function(expr: &Expr) -> bool {
   match expr {
     Expr::Fn(fn_expr) => fn_expr.function.is_async,
     Expr::Arrow(arrow_expr) => arrow_expr.is_async,
-    Expr::Paren(ParenExpr { ref expr, .. }) => is_async_function(&**expr),
     _ => false,
   }
 }
@@ -54,22 +81,14 @@ impl Visit for NoAsyncPromiseExecutorVisitor {
 
   fn visit_new_expr(&mut self, new_expr: &NewExpr, _parent: &dyn Node) {
     new_expr.visit_children_with(self);
-    if let Expr::Ident(ident) = &*new_expr.callee {
-      let name = ident.sym.as_ref();
-      if name != "Promise" {
-        return;
-      }
-
-      if let Some(args) = &new_expr.args {
-        if let Some(first_arg) = args.get(0) {
-          if is_async_function(&*first_arg.expr) {
-            self.context.add_diagnostic(
-              new_expr.span,
-              "no-async-promise-executor",
-              "Async promise executors are not allowed",
-            );
-          }
-        }
+    if let Some(executor) = promise_executor(new_expr) {
+      if is_async_function(executor) {
+        self.context.add_diagnostic_with_hint(
+          executor.span(),
+          "no-async-promise-executor",
+          "Async promise executors are not allowed",
+          "Remove `async` from the executor, or await in an async function outside of it",
+        );
       }
     }
   }
@@ -95,19 +114,19 @@ mod tests {
   fn no_async_promise_executor_invalid() {
     assert_lint_err::<NoAsyncPromiseExecutor>(
       "new Promise(async function(resolve, reject) {});",
-      0,
+      12,
     );
     assert_lint_err::<NoAsyncPromiseExecutor>(
       "new Promise(async function foo(resolve, reject) {});",
-      0,
+      12,
     );
     assert_lint_err::<NoAsyncPromiseExecutor>(
       "new Promise(async (resolve, reject) => {});",
-      0,
+      12,
     );
     assert_lint_err::<NoAsyncPromiseExecutor>(
       "new Promise(((((async () => {})))));",
-      0,
+      16,
     );
     // nested
     assert_lint_err_on_line::<NoAsyncPromiseExecutor>(
@@ -119,7 +138,40 @@ const a = new class {
 }
       "#,
       4,
+      24,
+    );
+  }
+
+  #[test]
+  fn no_async_promise_executor_identifiers() {
+    assert_lint_ok_n::<NoAsyncPromiseExecutor>(vec![
+      "const executor = async (resolve, reject) => {};\nnew Promise(executor);",
+      "async function executor(resolve, reject) {}\nnew Promise(executor);",
+      "new Promise(executors.async);",
+    ]);
+  }
+
+  #[test]
+  fn no_async_promise_executor_nested() {
+    assert_lint_err_n::<NoAsyncPromiseExecutor>(
+      "new Promise(async (resolve) => { resolve(new Promise(async (r) => {})); });",
+      vec![12, 53],
+    );
+    assert_lint_err::<NoAsyncPromiseExecutor>(
+      "new Promise((resolve) => { resolve(new Promise(async (r) => {})); });",
+      47,
+    );
+    assert_lint_err::<NoAsyncPromiseExecutor>(
+      "new Promise(async (resolve) => { new Promise((r) => r()); });",
       12,
     );
+
+    let diagnostics = lint(
+      NoAsyncPromiseExecutor::new(),
+      "new Promise(async function () {});",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 12);
+    assert_eq!(diagnostics[0].range.end.col, 32);
   }
 }
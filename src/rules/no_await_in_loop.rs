@@ -21,13 +21,43 @@ impl LintRule for NoAwaitInLoop {
     "no-await-in-loop"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Disallows `await` inside of loop bodies.
+
+Awaiting in a loop runs the operations one after another; they can often be started together and awaited with `Promise.all()`. Functions defined in a loop and the body of `for await` loops are not reported.
+
+### Valid:
+```typescript
+async function foo(things) {
+  const results = [];
+  for (const thing of things) {
+    results.push(bar(thing));
+  }
+  return baz(await Promise.all(results));
+}
+```
+
+### Invalid:
+```typescript
+async function foo(things) {
+  const results = [];
+  for (const thing of things) {
+    results.push(await bar(thing));
+  }
+  return baz(results);
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     module: &swc_ecmascript::ast::Module,
   ) {
-    let mut visitor = NoAwaitInLoopVisitor::new(context);
-    visitor.visit_module(module, module);
+    let root_visitor = NoAwaitInLoopVisitor::new(context);
+    // Top-level await makes the module body behave like an async function
+    let mut func_visitor = FunctionVisitor::new(&root_visitor);
+    func_visitor.visit_module(module, module);
   }
 }
 
@@ -41,50 +71,72 @@ impl NoAwaitInLoopVisitor {
   }
 
   fn add_diagnostic(&self, span: Span) {
-    self.context.add_diagnostic(
+    self.context.add_diagnostic_with_hint(
       span,
       "no-await-in-loop",
       "Unexpected `await` inside a loop.",
+      "Start the operations in the loop and await them together with `Promise.all()` after it",
     );
   }
 }
 
-impl Visit for NoAwaitInLoopVisitor {
-  noop_visit_type!();
+/// Visits the code of a function which runs once per call, and hands the
+/// parts of loops which run on each iteration to a `LoopVisitor`.
+struct FunctionVisitor<'a> {
+  root_visitor: &'a NoAwaitInLoopVisitor,
+}
 
-  fn visit_function(&mut self, func: &Function, parent: &dyn Node) {
-    let mut func_visitor = FunctionVisitor::new(&self, func.is_async);
-    func_visitor.visit_function(func, parent);
+impl<'a> FunctionVisitor<'a> {
+  fn new(root_visitor: &'a NoAwaitInLoopVisitor) -> Self {
+    Self { root_visitor }
   }
 
-  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
-    let mut func_visitor = FunctionVisitor::new(&self, arrow_expr.is_async);
-    func_visitor.visit_arrow_expr(arrow_expr, parent);
+  fn loop_visitor(&self) -> LoopVisitor<'a> {
+    LoopVisitor::new(self.root_visitor)
   }
+}
 
-  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
-    let mut loop_visitor = LoopVisitor::new(&self);
-    loop_visitor.visit_for_stmt(for_stmt, parent);
+impl<'a> Visit for FunctionVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _parent: &dyn Node) {
+    // The initializer runs once before the loop
+    if let Some(init) = &for_stmt.init {
+      self.visit_var_decl_or_expr(init, for_stmt);
+    }
+    let mut loop_visitor = self.loop_visitor();
+    if let Some(test) = &for_stmt.test {
+      loop_visitor.visit_expr(test, for_stmt);
+    }
+    if let Some(update) = &for_stmt.update {
+      loop_visitor.visit_expr(update, for_stmt);
+    }
+    loop_visitor.visit_stmt(&for_stmt.body, for_stmt);
   }
 
-  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, parent: &dyn Node) {
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, _parent: &dyn Node) {
+    self.visit_var_decl_or_pat(&for_of_stmt.left, for_of_stmt);
+    self.visit_expr(&for_of_stmt.right, for_of_stmt);
+    // Awaiting each element is what `for await` is for
     if for_of_stmt.await_token.is_some() {
-      let mut func_visitor = FunctionVisitor::new(&self, true);
-      func_visitor.visit_for_of_stmt(for_of_stmt, parent);
+      self.visit_stmt(&for_of_stmt.body, for_of_stmt);
     } else {
-      let mut loop_visitor = LoopVisitor::new(&self);
-      loop_visitor.visit_for_of_stmt(for_of_stmt, parent);
+      self
+        .loop_visitor()
+        .visit_stmt(&for_of_stmt.body, for_of_stmt);
     }
   }
 
-  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, parent: &dyn Node) {
-    let mut loop_visitor = LoopVisitor::new(&self);
-    loop_visitor.visit_for_in_stmt(for_in_stmt, parent);
+  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, _parent: &dyn Node) {
+    self.visit_var_decl_or_pat(&for_in_stmt.left, for_in_stmt);
+    self.visit_expr(&for_in_stmt.right, for_in_stmt);
+    self
+      .loop_visitor()
+      .visit_stmt(&for_in_stmt.body, for_in_stmt);
   }
 
   fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, parent: &dyn Node) {
-    let mut loop_visitor = LoopVisitor::new(&self);
-    loop_visitor.visit_while_stmt(while_stmt, parent);
+    self.loop_visitor().visit_while_stmt(while_stmt, parent);
   }
 
   fn visit_do_while_stmt(
@@ -92,11 +144,16 @@ impl Visit for NoAwaitInLoopVisitor {
     do_while_stmt: &DoWhileStmt,
     parent: &dyn Node,
   ) {
-    let mut loop_visitor = LoopVisitor::new(&self);
-    loop_visitor.visit_do_while_stmt(do_while_stmt, parent);
+    self
+      .loop_visitor()
+      .visit_do_while_stmt(do_while_stmt, parent);
   }
 }
 
+/// Visits code which runs on each iteration of a loop, where every `await`
+/// is reported. Nested loops run on each iteration too, only functions
+/// defined in the loop start over with a `FunctionVisitor`, since they
+/// aren't awaited sequentially.
 struct LoopVisitor<'a> {
   root_visitor: &'a NoAwaitInLoopVisitor,
 }
@@ -108,132 +165,24 @@ impl<'a> LoopVisitor<'a> {
 }
 
 impl<'a> Visit for LoopVisitor<'a> {
+  noop_visit_type!();
+
   fn visit_function(&mut self, func: &Function, parent: &dyn Node) {
-    let mut func_visitor = if func.is_async {
-      FunctionVisitor::new(&self.root_visitor, true)
-    } else {
-      FunctionVisitor::new(&self.root_visitor, false)
-    };
+    let mut func_visitor = FunctionVisitor::new(self.root_visitor);
     func_visitor.visit_function(func, parent);
   }
 
   fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
-    let mut func_visitor = if arrow_expr.is_async {
-      FunctionVisitor::new(&self.root_visitor, true)
-    } else {
-      FunctionVisitor::new(&self.root_visitor, false)
-    };
+    let mut func_visitor = FunctionVisitor::new(self.root_visitor);
     func_visitor.visit_arrow_expr(arrow_expr, parent);
   }
 
-  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
-    if let Some(test) = for_stmt.test.as_ref() {
-      self.visit_expr(&**test, parent);
-    }
-    if let Some(update) = for_stmt.update.as_ref() {
-      self.visit_expr(&**update, parent);
-    }
-    let body = &*for_stmt.body;
-    self.visit_stmt(body, parent);
-  }
-
-  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, parent: &dyn Node) {
-    let body = &*for_of_stmt.body;
-    self.visit_stmt(body, parent);
-  }
-
-  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, parent: &dyn Node) {
-    let body = &*for_in_stmt.body;
-    self.visit_stmt(body, parent);
-  }
-
   fn visit_await_expr(&mut self, await_expr: &AwaitExpr, parent: &dyn Node) {
     self.root_visitor.add_diagnostic(await_expr.span);
     swc_ecmascript::visit::visit_await_expr(self, await_expr, parent);
   }
 }
 
-struct FunctionVisitor<'a> {
-  root_visitor: &'a NoAwaitInLoopVisitor,
-  is_async: bool,
-}
-
-impl<'a> FunctionVisitor<'a> {
-  fn new(root_visitor: &'a NoAwaitInLoopVisitor, is_async: bool) -> Self {
-    Self {
-      root_visitor,
-      is_async,
-    }
-  }
-}
-
-impl<'a> Visit for FunctionVisitor<'a> {
-  fn visit_function(&mut self, func: &Function, parent: &dyn Node) {
-    let mut func_visitor =
-      FunctionVisitor::new(&self.root_visitor, func.is_async);
-    swc_ecmascript::visit::visit_function(&mut func_visitor, func, parent);
-  }
-
-  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
-    let mut func_visitor =
-      FunctionVisitor::new(&self.root_visitor, arrow_expr.is_async);
-    swc_ecmascript::visit::visit_arrow_expr(
-      &mut func_visitor,
-      arrow_expr,
-      parent,
-    );
-  }
-
-  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
-    if self.is_async {
-      let mut loop_visitor = LoopVisitor::new(&self.root_visitor);
-      loop_visitor.visit_for_stmt(for_stmt, parent);
-    } else {
-      swc_ecmascript::visit::visit_for_stmt(self, for_stmt, parent);
-    }
-  }
-
-  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, parent: &dyn Node) {
-    if self.is_async && for_of_stmt.await_token.is_none() {
-      let mut loop_visitor = LoopVisitor::new(&self.root_visitor);
-      loop_visitor.visit_for_of_stmt(for_of_stmt, parent);
-    } else {
-      swc_ecmascript::visit::visit_for_of_stmt(self, for_of_stmt, parent);
-    }
-  }
-
-  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, parent: &dyn Node) {
-    if self.is_async {
-      let mut loop_visitor = LoopVisitor::new(&self.root_visitor);
-      loop_visitor.visit_for_in_stmt(for_in_stmt, parent);
-    } else {
-      swc_ecmascript::visit::visit_for_in_stmt(self, for_in_stmt, parent);
-    }
-  }
-
-  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, parent: &dyn Node) {
-    if self.is_async {
-      let mut loop_visitor = LoopVisitor::new(&self.root_visitor);
-      loop_visitor.visit_while_stmt(while_stmt, parent);
-    } else {
-      swc_ecmascript::visit::visit_while_stmt(self, while_stmt, parent);
-    }
-  }
-
-  fn visit_do_while_stmt(
-    &mut self,
-    do_while_stmt: &DoWhileStmt,
-    parent: &dyn Node,
-  ) {
-    if self.is_async {
-      let mut loop_visitor = LoopVisitor::new(&self.root_visitor);
-      loop_visitor.visit_do_while_stmt(do_while_stmt, parent);
-    } else {
-      swc_ecmascript::visit::visit_do_while_stmt(self, do_while_stmt, parent);
-    }
-  }
-}
-
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -579,4 +528,115 @@ async function foo() {
       6,
     );
   }
+
+  #[test]
+  fn no_await_in_loop_nested_functions() {
+    assert_lint_ok::<NoAwaitInLoop>(
+      r#"
+async function foo(things) {
+  while (things.length) {
+    const results = things.map(async (thing) => {
+      const nested = async () => await bar(thing);
+      return await nested();
+    });
+    things = results.slice(1);
+  }
+}
+      "#,
+    );
+
+    assert_lint_ok::<NoAwaitInLoop>(
+      r#"
+for (const thing of things) {
+  class Foo {
+    async bar() {
+      await baz(thing);
+    }
+  }
+}
+      "#,
+    );
+
+    assert_lint_err_on_line_n::<NoAwaitInLoop>(
+      r#"
+for (const thing of things) {
+  const a = async () => {
+    for (const x of thing) {
+      await bar(x);
+    }
+  };
+  await a();
+}
+      "#,
+      vec![(5, 6), (8, 2)],
+    );
+  }
+
+  #[test]
+  fn no_await_in_loop_labeled() {
+    assert_lint_err_on_line::<NoAwaitInLoop>(
+      r#"
+outer: for (const thing of things) {
+  inner: for (const x of thing) {
+    if (await bar(x)) continue outer;
+  }
+}
+      "#,
+      4,
+      8,
+    );
+
+    assert_lint_err_on_line::<NoAwaitInLoop>(
+      r#"
+loop: while (await foo()) {
+  break loop;
+}
+      "#,
+      2,
+      13,
+    );
+
+    assert_lint_ok::<NoAwaitInLoop>(
+      r#"
+outer: for await (const thing of things) {
+  await bar(thing);
+}
+      "#,
+    );
+  }
+
+  #[test]
+  fn no_await_in_loop_nested_loop_heads() {
+    // The heads of nested loops run on each iteration of the outer loop
+    assert_lint_err_on_line_n::<NoAwaitInLoop>(
+      r#"
+while (true) {
+  for (let i = await foo(); i < 10; i++) {}
+  for (const x of await bar()) {}
+  for (const x in await baz()) {}
+}
+      "#,
+      vec![(3, 15), (4, 18), (5, 18)],
+    );
+
+    assert_lint_err_on_line::<NoAwaitInLoop>(
+      r#"
+for (const thing of things) {
+  for await (const x of thing) {
+    await bar(x);
+  }
+}
+      "#,
+      4,
+      4,
+    );
+
+    let diagnostics =
+      lint(NoAwaitInLoop::new(), "while (true) { await foo(); }");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Start the operations in the loop and await them together with `Promise.all()` after it")
+    );
+  }
 }
@@ -0,0 +1,227 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::is_number;
+use std::sync::Arc;
+use swc_ecmascript::ast::{
+  BinExpr, BinaryOp, Expr, ExprOrSuper, Lit, Module, ParenExpr, UnaryOp,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  /// Reports binary expressions mixing a BigInt literal with a value which is
+  /// definitely a Number: arithmetic throws a `TypeError`, comparisons are
+  /// lossy since large Numbers aren't exact, and strict equality is always
+  /// false.
+  ///
+  /// Operands whose type isn't known from the syntax, like `1n + x`, are not
+  /// reported.
+  NoBigintAndNumberMixing, "no-bigint-and-number-mixing", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows binary expressions mixing a BigInt literal with a value which is definitely a Number.
+
+Arithmetic on a BigInt and a Number throws a `TypeError`, comparisons are lossy since large Numbers aren't exact, and strict equality is always false. Operands whose type isn't known from the syntax, like `1n + x`, are not reported.
+
+### Valid:
+```typescript
+const a = 1n + 2n;
+const b = 1n + BigInt(x);
+```
+
+### Invalid:
+```typescript
+const a = 1n + 1;
+```
+```typescript
+if (1n === 1) {}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoBigintAndNumberMixingVisitor { context };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoBigintAndNumberMixingVisitor {
+  context: Arc<Context>,
+}
+
+fn is_bigint_lit(expr: &Expr) -> bool {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => is_bigint_lit(expr),
+    Expr::Lit(Lit::BigInt(_)) => true,
+    Expr::Unary(unary) if unary.op == UnaryOp::Minus => {
+      is_bigint_lit(&unary.arg)
+    }
+    _ => false,
+  }
+}
+
+/// Describes an operand which is known to be a Number, for messages.
+fn number_kind(expr: &Expr) -> &'static str {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => number_kind(expr),
+    Expr::Lit(_) => "a number literal",
+    Expr::Unary(unary) if unary.op == UnaryOp::Plus => "a unary `+` expression",
+    Expr::Unary(unary) => number_kind(&unary.arg),
+    Expr::Bin(_) => "arithmetic on numbers",
+    Expr::Call(call_expr) => match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => match &**callee {
+        Expr::Ident(ident) if ident.sym == *"Number" => "a `Number()` call",
+        Expr::Ident(_) => "a number parsed from a string",
+        _ => "a `Math` method call",
+      },
+      ExprOrSuper::Super(_) => "a number",
+    },
+    _ => "a number",
+  }
+}
+
+fn is_strict_equality(op: BinaryOp) -> bool {
+  matches!(op, BinaryOp::EqEqEq | BinaryOp::NotEqEq)
+}
+
+fn is_comparison(op: BinaryOp) -> bool {
+  matches!(
+    op,
+    BinaryOp::EqEq
+      | BinaryOp::NotEq
+      | BinaryOp::Lt
+      | BinaryOp::LtEq
+      | BinaryOp::Gt
+      | BinaryOp::GtEq
+  )
+}
+
+fn is_arithmetic(op: BinaryOp) -> bool {
+  matches!(
+    op,
+    BinaryOp::Add
+      | BinaryOp::Sub
+      | BinaryOp::Mul
+      | BinaryOp::Div
+      | BinaryOp::Mod
+      | BinaryOp::Exp
+      | BinaryOp::BitAnd
+      | BinaryOp::BitOr
+      | BinaryOp::BitXor
+      | BinaryOp::LShift
+      | BinaryOp::RShift
+      | BinaryOp::ZeroFillRShift
+  )
+}
+
+impl NoBigintAndNumberMixingVisitor {
+  fn check(&self, bin_expr: &BinExpr) {
+    let number = if is_bigint_lit(&bin_expr.left) {
+      &bin_expr.right
+    } else if is_bigint_lit(&bin_expr.right) {
+      &bin_expr.left
+    } else {
+      return;
+    };
+    if !is_number(&self.context, number) {
+      return;
+    }
+    let kind = number_kind(number);
+
+    let (message, hint) = if is_strict_equality(bin_expr.op) {
+      let result = if bin_expr.op == BinaryOp::EqEqEq {
+        "false"
+      } else {
+        "true"
+      };
+      (
+        format!(
+          "`{}` between a BigInt literal and {} is always {}",
+          bin_expr.op, kind, result
+        ),
+        "Values of different types are never strictly equal, convert the Number with `BigInt()` first",
+      )
+    } else if is_comparison(bin_expr.op) {
+      (
+        format!("Comparing a BigInt literal with {} is lossy", kind),
+        "Numbers beyond `Number.MAX_SAFE_INTEGER` aren't exact, compare values of the same type instead",
+      )
+    } else if is_arithmetic(bin_expr.op) {
+      (
+        format!(
+          "Mixing a BigInt literal with {} in `{}` throws a TypeError",
+          kind, bin_expr.op
+        ),
+        "Convert one of the operands with `BigInt()` or `Number()`",
+      )
+    } else {
+      return;
+    };
+
+    self.context.add_diagnostic_with_hint(
+      bin_expr.span,
+      "no-bigint-and-number-mixing",
+      &message,
+      hint,
+    );
+  }
+}
+
+impl Visit for NoBigintAndNumberMixingVisitor {
+  noop_visit_type!();
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, parent: &dyn Node) {
+    self.check(bin_expr);
+    swc_ecmascript::visit::visit_bin_expr(self, bin_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_bigint_and_number_mixing_valid() {
+    assert_lint_ok_n::<NoBigintAndNumberMixing>(vec![
+      "1n + 2n;",
+      "1n + x;",
+      "x * 2n;",
+      "1n === x;",
+      "1n + BigInt(x);",
+      "1n < -2n;",
+      "1 + 2;",
+      "1n + -x;",
+      "1n && 1;",
+      "1n ?? 1;",
+      "function f(Number) { return 1n + Number(x); }",
+      "const Math = { big: () => 1n };\n1n + Math.big();",
+    ]);
+  }
+
+  #[test]
+  fn no_bigint_and_number_mixing_invalid() {
+    let cases = vec![
+      ("1n + 1;", "Mixing a BigInt literal with a number literal in `+` throws a TypeError"),
+      ("(2 * 3) - 1n;", "Mixing a BigInt literal with arithmetic on numbers in `-` throws a TypeError"),
+      ("-1n * Number(x);", "Mixing a BigInt literal with a `Number()` call in `*` throws a TypeError"),
+      ("1n << Math.floor(x);", "Mixing a BigInt literal with a `Math` method call in `<<` throws a TypeError"),
+      ("1n + +x;", "Mixing a BigInt literal with a unary `+` expression in `+` throws a TypeError"),
+      ("parseInt(s) % 10n;", "Mixing a BigInt literal with a number parsed from a string in `%` throws a TypeError"),
+      ("1n < 2;", "Comparing a BigInt literal with a number literal is lossy"),
+      ("Number(x) >= 9007199254740993n;", "Comparing a BigInt literal with a `Number()` call is lossy"),
+      ("1n == 1;", "Comparing a BigInt literal with a number literal is lossy"),
+      ("1n === 1;", "`===` between a BigInt literal and a number literal is always false"),
+      ("Math.abs(x) !== 0n;", "`!==` between a BigInt literal and a `Math` method call is always true"),
+    ];
+    for (source, message) in cases {
+      let diagnostics = lint(NoBigintAndNumberMixing::new(), source);
+      assert_eq!(diagnostics.len(), 1, "{}", source);
+      assert_eq!(diagnostics[0].message, message, "{}", source);
+      assert_eq!(diagnostics[0].range.start.col, 0, "{}", source);
+    }
+
+    assert_lint_err::<NoBigintAndNumberMixing>("const a = b(1n + 1);", 12);
+  }
+}
@@ -0,0 +1,305 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::operator_span;
+use std::sync::Arc;
+use swc_common::{BytePos, Span, Spanned, SyntaxContext};
+use swc_ecmascript::ast::{
+  AssignExpr, AssignOp, BinExpr, BinaryOp, Expr, Lit, Module, UnaryExpr,
+  UnaryOp,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Default)]
+pub struct NoBitwiseOptions {
+  /// Operators which are allowed, eg. `"|"` or `"<<="`.
+  pub allow: Vec<String>,
+  /// Allows `x | 0`, which is commonly used to truncate to a 32-bit
+  /// integer.
+  pub int32_hint: bool,
+}
+
+/// Reports bitwise operators and their assignment forms, which are rare in
+/// JavaScript and often typos of `&&` or `||`.
+pub struct NoBitwise {
+  options: NoBitwiseOptions,
+}
+
+impl NoBitwise {
+  pub fn with_options(options: NoBitwiseOptions) -> Box<Self> {
+    Box::new(NoBitwise { options })
+  }
+}
+
+impl LintRule for NoBitwise {
+  fn new() -> Box<Self> {
+    Self::with_options(NoBitwiseOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-bitwise"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows bitwise operators and their assignment forms.
+
+Bitwise operators are rare in JavaScript and often typos of `&&` or `||`.
+
+### Options
+
+- `NoBitwiseOptions::allow`: operators which are allowed, eg. `"|"` or `"<<="`, none by default.
+- `NoBitwiseOptions::int32_hint`: allows `x | 0`, which truncates to a 32-bit integer, `false` by default.
+
+### Valid:
+```typescript
+if (a && b) {}
+```
+
+### Invalid:
+```typescript
+if (a & b) {}
+```
+```typescript
+const c = a << b;
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoBitwiseVisitor {
+      context,
+      options: &self.options,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoBitwiseVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a NoBitwiseOptions,
+}
+
+fn is_bitwise(op: BinaryOp) -> bool {
+  matches!(
+    op,
+    BinaryOp::BitAnd
+      | BinaryOp::BitOr
+      | BinaryOp::BitXor
+      | BinaryOp::LShift
+      | BinaryOp::RShift
+      | BinaryOp::ZeroFillRShift
+  )
+}
+
+fn is_bitwise_assign(op: AssignOp) -> bool {
+  matches!(
+    op,
+    AssignOp::BitAndAssign
+      | AssignOp::BitOrAssign
+      | AssignOp::BitXorAssign
+      | AssignOp::LShiftAssign
+      | AssignOp::RShiftAssign
+      | AssignOp::ZeroFillRShiftAssign
+  )
+}
+
+fn is_tilde(expr: &Expr) -> bool {
+  matches!(
+    expr,
+    Expr::Unary(UnaryExpr {
+      op: UnaryOp::Tilde,
+      ..
+    })
+  )
+}
+
+impl<'a> NoBitwiseVisitor<'a> {
+  fn is_allowed(&self, op: &str) -> bool {
+    self.options.allow.iter().any(|allowed| allowed == op)
+  }
+
+  fn report(&self, span: Span, op: &str) {
+    let hint = match op {
+      "&" => "Did you mean `&&`? If not, allow `&` in the options",
+      "|" => "Did you mean `||`? If not, allow `|` in the options",
+      _ => "If the bitwise operation is intended, allow the operator in the options",
+    };
+    self.context.add_diagnostic_with_hint(
+      span,
+      "no-bitwise",
+      &format!("Unexpected use of the bitwise operator `{}`", op),
+      hint,
+    );
+  }
+}
+
+impl<'a> Visit for NoBitwiseVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, parent: &dyn Node) {
+    let op = bin_expr.op.as_str();
+    let is_int32_hint = self.options.int32_hint
+      && bin_expr.op == BinaryOp::BitOr
+      && matches!(&*bin_expr.right, Expr::Lit(Lit::Num(num)) if num.value == 0.0);
+    if is_bitwise(bin_expr.op) && !is_int32_hint && !self.is_allowed(op) {
+      self.report(
+        operator_span(
+          &self.context,
+          bin_expr.left.span(),
+          bin_expr.right.span(),
+          op,
+        ),
+        op,
+      );
+    }
+    swc_ecmascript::visit::visit_bin_expr(self, bin_expr, parent);
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, parent: &dyn Node) {
+    let op = assign_expr.op.as_str();
+    if is_bitwise_assign(assign_expr.op) && !self.is_allowed(op) {
+      self.report(
+        operator_span(
+          &self.context,
+          assign_expr.left.span(),
+          assign_expr.right.span(),
+          op,
+        ),
+        op,
+      );
+    }
+    swc_ecmascript::visit::visit_assign_expr(self, assign_expr, parent);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, parent: &dyn Node) {
+    if unary_expr.op != UnaryOp::Tilde || self.is_allowed("~") {
+      swc_ecmascript::visit::visit_unary_expr(self, unary_expr, parent);
+      return;
+    }
+    let span = Span::new(
+      unary_expr.span.lo(),
+      unary_expr.span.lo() + BytePos(1),
+      SyntaxContext::empty(),
+    );
+    if let Expr::Unary(inner) = &*unary_expr.arg {
+      if is_tilde(&unary_expr.arg) {
+        // `~~x` is reported once, as a single truncation.
+        self.context.add_diagnostic_with_hint(
+          Span::new(span.lo(), inner.span.lo() + BytePos(1), span.ctxt()),
+          "no-bitwise",
+          "Unexpected use of the bitwise operator `~~`",
+          &format!(
+            "Use `Math.trunc({})` to truncate a number",
+            self.context.span_text(inner.arg.span())
+          ),
+        );
+        self.visit_expr(&inner.arg, parent);
+        return;
+      }
+    }
+    self.report(span, "~");
+    swc_ecmascript::visit::visit_unary_expr(self, unary_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_bitwise_valid() {
+    assert_lint_ok_n::<NoBitwise>(vec![
+      "a && b;",
+      "a || b;",
+      "a += b;",
+      "!a;",
+      "a ** b;",
+      "const s = 'a | b';",
+    ]);
+  }
+
+  #[test]
+  fn no_bitwise_invalid() {
+    assert_lint_err::<NoBitwise>("a & b;", 2);
+    assert_lint_err::<NoBitwise>("a | b;", 2);
+    assert_lint_err::<NoBitwise>("a ^ b;", 2);
+    assert_lint_err::<NoBitwise>("a << b;", 2);
+    assert_lint_err::<NoBitwise>("a >> b;", 2);
+    assert_lint_err::<NoBitwise>("a >>> b;", 2);
+    assert_lint_err::<NoBitwise>("~a;", 0);
+    assert_lint_err::<NoBitwise>("a &= b;", 2);
+    assert_lint_err::<NoBitwise>("a |= b;", 2);
+    assert_lint_err::<NoBitwise>("a ^= b;", 2);
+    assert_lint_err::<NoBitwise>("a <<= b;", 2);
+    assert_lint_err::<NoBitwise>("a >>= b;", 2);
+    assert_lint_err::<NoBitwise>("a >>>= b;", 2);
+    assert_lint_err::<NoBitwise>("(a) /* | */ | (b);", 12);
+    assert_lint_err_on_line::<NoBitwise>("a // |\n  | b;", 2, 2);
+    assert_lint_err::<NoBitwise>("x = a | 0;", 6);
+    assert_lint_err_n::<NoBitwise>("a | b & c;", vec![2, 6]);
+
+    let diagnostics = lint(NoBitwise::new(), "a >>> b;");
+    assert_eq!(
+      diagnostics[0].message,
+      "Unexpected use of the bitwise operator `>>>`"
+    );
+    assert_eq!(diagnostics[0].range.end.col, 5);
+
+    let diagnostics = lint(NoBitwise::new(), "if (a | b) {}");
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Did you mean `||`? If not, allow `|` in the options")
+    );
+  }
+
+  #[test]
+  fn no_bitwise_allow() {
+    let rule = || {
+      NoBitwise::with_options(NoBitwiseOptions {
+        allow: vec!["|".to_string(), "<<=".to_string()],
+        int32_hint: false,
+      })
+    };
+    assert_lint_ok_with(rule(), "a | b;\na <<= 1;");
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "a |= b;\na << 1;",
+      vec![(1, 2), (2, 2)],
+    );
+  }
+
+  #[test]
+  fn no_bitwise_int32_hint() {
+    let rule = || {
+      NoBitwise::with_options(NoBitwiseOptions {
+        allow: vec![],
+        int32_hint: true,
+      })
+    };
+    assert_lint_ok_with(rule(), "const i = x | 0;");
+    assert_lint_err_on_line_n_with(rule(), "const i = x | 1;", vec![(1, 12)]);
+    assert_lint_err_on_line_n_with(rule(), "x |= 0;", vec![(1, 2)]);
+
+    // Double tilde truncation isn't exempt, but is reported once.
+    let diagnostics = lint(rule(), "const i = ~~x;");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Unexpected use of the bitwise operator `~~`"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use `Math.trunc(x)` to truncate a number")
+    );
+    assert_eq!(diagnostics[0].range.start.col, 10);
+    assert_eq!(diagnostics[0].range.end.col, 12);
+
+    assert_lint_err_n::<NoBitwise>("~~~x;", vec![0, 2]);
+  }
+}
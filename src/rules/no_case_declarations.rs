@@ -23,6 +23,46 @@ impl LintRule for NoCaseDeclarations {
     "no-case-declarations"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows lexical declarations, `let`, `const`, `function` and `class`, directly in `case` and `default` clauses.
+
+The declaration is visible in the whole `switch` but only initialized when its clause runs. Wrap the clause in a block instead.
+
+### Valid:
+```typescript
+switch (foo) {
+  case 1: {
+    let a = "a";
+    break;
+  }
+  default: {
+    const b = "b";
+    break;
+  }
+}
+```
+
+### Invalid:
+```typescript
+switch (foo) {
+  case 1:
+    let a = "a";
+    break;
+}
+```
+```typescript
+switch (bar) {
+  default:
+    const a = "a";
+    break;
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -0,0 +1,315 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::session::ValueUses;
+use std::collections::HashMap;
+use std::sync::Arc;
+use swc_ecmascript::ast::{
+  Class, ClassMember, Decl, DefaultDecl, ExportDecl, ExportSpecifier, Expr,
+  Ident, Module, ModuleDecl, ModuleItem, Stmt,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+
+declare_rule! {
+  /// Reports classes which only have static members and are never
+  /// instantiated, extended or otherwise used as a value, so they only serve
+  /// as a namespace for their members:
+  ///
+  /// ```ts
+  /// export class StringUtils {
+  ///   static capitalize(s: string) {}
+  ///   static DEFAULT_LOCALE = "en";
+  /// }
+  /// ```
+  ///
+  /// Module-level exports do the same job and can be imported selectively.
+  ///
+  /// Classes not exported are checked on their own. Exported ones are only
+  /// reported when the `LintSession` is available and no module of the
+  /// session imports them as a value either. Modules re-exporting the class
+  /// are not followed.
+  ///
+  /// Decorated classes are exempt since dependency injection frameworks
+  /// instantiate them, and so are classes with a constructor, like a private
+  /// one paired with a static factory.
+  NoClassAsNamespace, "no-class-as-namespace", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows classes which only have static members and are never instantiated, extended or otherwise used as a value.
+
+Such classes only serve as a namespace for their members; module-level exports do the same job and can be imported selectively. Exported classes are only reported when the lint session is available and no module of the session uses them as a value. Decorated classes and classes with a constructor are allowed.
+
+### Valid:
+```typescript
+class A {
+  static a() {}
+}
+new A();
+```
+```typescript
+export function capitalize(s: string) {}
+export const DEFAULT_LOCALE = "en";
+```
+
+### Invalid:
+```typescript
+class StringUtils {
+  static capitalize(s: string) {}
+  static DEFAULT_LOCALE = "en";
+}
+StringUtils.capitalize("a");
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let classes = top_level_classes(module);
+    if classes.is_empty() {
+      return;
+    }
+    let exports = exported_names(module);
+    let uses = ValueUses::collect(module);
+
+    for (ident, class) in classes {
+      let static_members = match static_member_count(class) {
+        Some(count) => count,
+        None => continue,
+      };
+      let id = ident.to_id();
+      if uses.idents.contains(&id) {
+        continue;
+      }
+      if let Some(export_names) = exports.get(&id) {
+        let session = match &context.session {
+          Some(session) => session,
+          None => continue,
+        };
+        if export_names
+          .iter()
+          .any(|name| session.is_imported_as_value(&context.file_name, name))
+        {
+          continue;
+        }
+      }
+
+      context.add_diagnostic_with_hint(
+        ident.span,
+        "no-class-as-namespace",
+        &format!(
+          "Class `{}` only has static members ({}) and is never instantiated",
+          ident.sym, static_members
+        ),
+        "Export the members as module-level functions and constants instead",
+      );
+    }
+  }
+}
+
+/// Returns the number of members of `class` if it is only a namespace for
+/// static members, `None` otherwise.
+fn static_member_count(class: &Class) -> Option<usize> {
+  if !class.decorators.is_empty() || class.super_class.is_some() {
+    return None;
+  }
+  let mut count = 0;
+  for member in &class.body {
+    let is_static = match member {
+      ClassMember::Method(method) => method.is_static,
+      ClassMember::PrivateMethod(method) => method.is_static,
+      ClassMember::ClassProp(prop) => prop.is_static,
+      ClassMember::PrivateProp(prop) => prop.is_static,
+      ClassMember::Empty(_) => continue,
+      ClassMember::Constructor(_) | ClassMember::TsIndexSignature(_) => false,
+    };
+    if !is_static {
+      return None;
+    }
+    count += 1;
+  }
+  if count == 0 {
+    None
+  } else {
+    Some(count)
+  }
+}
+
+/// Named class declarations at the top level of the module, exported or
+/// not.
+fn top_level_classes(module: &Module) -> Vec<(&Ident, &Class)> {
+  module
+    .body
+    .iter()
+    .filter_map(|item| match item {
+      ModuleItem::Stmt(Stmt::Decl(Decl::Class(class_decl)))
+      | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+        decl: Decl::Class(class_decl),
+        ..
+      })) => Some((&class_decl.ident, &class_decl.class)),
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(default_decl)) => {
+        match &default_decl.decl {
+          DefaultDecl::Class(class_expr) => class_expr
+            .ident
+            .as_ref()
+            .map(|ident| (ident, &class_expr.class)),
+          _ => None,
+        }
+      }
+      _ => None,
+    })
+    .collect()
+}
+
+/// Maps local bindings to the names the module exports them as.
+fn exported_names(module: &Module) -> HashMap<Id, Vec<String>> {
+  let mut exports: HashMap<Id, Vec<String>> = HashMap::new();
+  for item in &module.body {
+    let module_decl = match item {
+      ModuleItem::ModuleDecl(module_decl) => module_decl,
+      ModuleItem::Stmt(_) => continue,
+    };
+    match module_decl {
+      ModuleDecl::ExportDecl(export_decl) => {
+        if let Decl::Class(class_decl) = &export_decl.decl {
+          exports
+            .entry(class_decl.ident.to_id())
+            .or_default()
+            .push(class_decl.ident.sym.to_string());
+        }
+      }
+      ModuleDecl::ExportDefaultDecl(default_decl) => {
+        if let DefaultDecl::Class(class_expr) = &default_decl.decl {
+          if let Some(ident) = &class_expr.ident {
+            exports
+              .entry(ident.to_id())
+              .or_default()
+              .push("default".to_string());
+          }
+        }
+      }
+      ModuleDecl::ExportDefaultExpr(default_expr) => {
+        if let Expr::Ident(ident) = &*default_expr.expr {
+          exports
+            .entry(ident.to_id())
+            .or_default()
+            .push("default".to_string());
+        }
+      }
+      ModuleDecl::ExportNamed(named_export) if named_export.src.is_none() => {
+        for specifier in &named_export.specifiers {
+          if let ExportSpecifier::Named(named) = specifier {
+            let exported = named.exported.as_ref().unwrap_or(&named.orig);
+            exports
+              .entry(named.orig.to_id())
+              .or_default()
+              .push(exported.sym.to_string());
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+  exports
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::session::LintSession;
+  use crate::test_util::*;
+
+  const UTILS: &str = r#"
+export class Utils {
+  static capitalize(s: string) { return s; }
+  static LOCALE = "en";
+}
+  "#;
+
+  fn lint_utils(importer: &str) -> Vec<crate::diagnostic::LintDiagnostic> {
+    let mut session = LintSession::new();
+    session.add_module("/project/utils.ts", UTILS).unwrap();
+    session.add_module("/project/main.ts", importer).unwrap();
+    lint_with_session(
+      NoClassAsNamespace::new(),
+      session,
+      "/project/utils.ts",
+      UTILS,
+    )
+  }
+
+  #[test]
+  fn no_class_as_namespace_valid() {
+    assert_lint_ok_n::<NoClassAsNamespace>(vec![
+      "class A { static a() {} }\nnew A();",
+      "class A { static a() {} }\nclass B extends A {}",
+      "class A { static a() {} }\nx instanceof A;",
+      "class A { static a() {} }\nregister(A);",
+      "class A { static a() {} }\nregister({ A });",
+      "class A { static a() {} b() {} }",
+      "class A {}",
+      "class A extends B { static a() {} }",
+      "@Injectable()\nclass A { static a() {} }",
+      r#"
+class Connection {
+  private constructor() {}
+  static create() { return new Connection(); }
+}
+      "#,
+      r#"
+class Config {
+  private constructor() {}
+  static load() {}
+}
+      "#,
+      // Without a session exported classes may be instantiated elsewhere.
+      "export class A { static a() {} }",
+      "class A { static a() {} }\nexport { A as B };",
+      "export default class A { static a() {} }",
+    ]);
+  }
+
+  #[test]
+  fn no_class_as_namespace_invalid() {
+    assert_lint_err_on_line::<NoClassAsNamespace>(
+      "\nclass A {\n  static a() {}\n  static b = 1;\n  static #c = 2;\n}\nA.a();",
+      2,
+      6,
+    );
+    assert_lint_err::<NoClassAsNamespace>(
+      "abstract class A { static a() {} }",
+      15,
+    );
+
+    let diagnostics = lint(
+      NoClassAsNamespace::new(),
+      "class A { static a() {}; static b() {} }",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Class `A` only has static members (2) and is never instantiated"
+    );
+  }
+
+  #[test]
+  fn no_class_as_namespace_cross_file() {
+    for importer in &[
+      "import { Utils } from \"./utils.ts\";\nUtils.capitalize(\"a\");",
+      "import * as utils from \"./utils.ts\";\nutils.Utils.capitalize(\"a\");",
+      "import { Utils } from \"./lib/utils.ts\";\nnew Utils();",
+    ] {
+      let diagnostics = lint_utils(importer);
+      assert_eq!(diagnostics.len(), 1, "{}", importer);
+      assert_eq!(diagnostics[0].range.start.line, 2);
+      assert_eq!(diagnostics[0].range.start.col, 13);
+    }
+
+    for importer in &[
+      "import { Utils } from \"./utils.ts\";\nnew Utils();",
+      "import { Utils as U } from \"./utils.ts\";\nclass V extends U {}",
+      "import * as utils from \"./utils.ts\";\nx instanceof utils.Utils;",
+    ] {
+      let diagnostics = lint_utils(importer);
+      assert!(diagnostics.is_empty(), "{:?}\n{}", diagnostics, importer);
+    }
+  }
+}
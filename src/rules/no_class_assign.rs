@@ -21,6 +21,33 @@ impl LintRule for NoClassAssign {
     "no-class-assign"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows reassigning class declarations.
+
+A class declaration creates a binding which can be reassigned, which is almost always a mistake.
+
+### Valid:
+```typescript
+class A {}
+let b = A;
+b = 0;
+```
+
+### Invalid:
+```typescript
+class A {}
+A = 0;
+```
+```typescript
+class A {}
+({ A } = {});
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -49,7 +76,7 @@ impl Visit for NoClassAssignVisitor {
 
     let ids = find_lhs_ids(&assign_expr.left);
     for id in ids {
-      let var = self.context.scope.var(&id);
+      let var = self.context.scope_manager.binding(&id);
       if let Some(var) = var {
         if let BindingKind::Class = var.kind() {
           self.context.add_diagnostic(
@@ -21,6 +21,30 @@ impl LintRule for NoCompareNegZero {
     "no-compare-neg-zero"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows comparing against `-0`.
+
+`x === -0` is also true for `+0`; use `Object.is(x, -0)` to check for negative zero.
+
+### Valid:
+```typescript
+if (x === 0) {}
+if (Object.is(x, -0)) {}
+```
+
+### Invalid:
+```typescript
+if (x === -0) {}
+```
+```typescript
+if (-0 > x) {}
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = NoCompareNegZeroVisitor::new(context);
     visitor.visit_module(module, module);
@@ -132,8 +156,11 @@ mod tests {
       r#"0 != x"#,
       r#"x !== 0"#,
       r#"0 !== x"#,
-      r#"{} == { foo: x === 0 }"#,
     ]);
+    // The leading `{}` is parsed as a block
+    with_syntax_errors(|| {
+      assert_lint_ok::<NoCompareNegZero>(r#"{} == { foo: x === 0 }"#);
+    });
   }
 
   #[test]
@@ -159,6 +186,8 @@ mod tests {
     assert_lint_err::<NoCompareNegZero>("if (x === -0.0) { }", 4);
     assert_lint_err::<NoCompareNegZero>("if (-0.0 === x) { }", 4);
     // nested
-    assert_lint_err::<NoCompareNegZero>("{} == { foo: x === -0 }", 13);
+    with_syntax_errors(|| {
+      assert_lint_err::<NoCompareNegZero>("{} == { foo: x === -0 }", 13);
+    });
   }
 }
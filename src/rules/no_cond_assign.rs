@@ -19,6 +19,10 @@ impl LintRule for NoCondAssign {
     "no-cond-assign"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = NoCondAssignVisitor::new(context);
     visitor.visit_module(module, module);
@@ -0,0 +1,196 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::sync::Arc;
+use swc_ecmascript::ast::{Expr, ExprOrSuper, Lit, MemberExpr, Module};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Default)]
+pub struct NoConsoleOptions {
+  /// Methods which may be used, eg. `"error"` or `"warn"`.
+  pub allow: Vec<String>,
+}
+
+/// Reports uses of the methods of the global `console`, which are often
+/// left over from debugging. A local binding named `console` is not
+/// reported.
+pub struct NoConsole {
+  options: NoConsoleOptions,
+}
+
+impl NoConsole {
+  pub fn with_options(options: NoConsoleOptions) -> Box<Self> {
+    Box::new(NoConsole { options })
+  }
+}
+
+impl LintRule for NoConsole {
+  fn new() -> Box<Self> {
+    Self::with_options(NoConsoleOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-console"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows the methods of the global `console`.
+
+Calls like `console.log()` are often left over from debugging. Programs which are meant to write to the console can allow the methods they use.
+
+### Options
+
+- `NoConsoleOptions::allow`: methods which may be used, eg. `["error", "warn"]`, empty by default.
+
+### Valid:
+```typescript
+function log(console: { log(message: string): void }) {
+  console.log("hello");
+}
+```
+
+### Invalid:
+```typescript
+console.log("hello");
+```
+```typescript
+const log = console["log"];
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoConsoleVisitor {
+      context,
+      options: &self.options,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoConsoleVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a NoConsoleOptions,
+}
+
+impl<'a> Visit for NoConsoleVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr, parent: &dyn Node) {
+    let is_console = matches!(
+      &member_expr.obj,
+      ExprOrSuper::Expr(obj) if matches!(&**obj, Expr::Ident(ident) if self.context.is_global(ident, "console"))
+    );
+    if is_console {
+      // `None` if the method is computed at runtime
+      let method = match (&*member_expr.prop, member_expr.computed) {
+        (Expr::Ident(ident), false) => Some(ident.sym.to_string()),
+        (Expr::Lit(Lit::Str(str)), true) => Some(str.value.to_string()),
+        (Expr::Tpl(tpl), true) if tpl.exprs.is_empty() => {
+          tpl.quasis.first().map(|quasi| quasi.raw.value.to_string())
+        }
+        _ => None,
+      };
+      let allowed = match &method {
+        Some(method) => {
+          self.options.allow.iter().any(|allowed| allowed == method)
+        }
+        None => false,
+      };
+      if !allowed {
+        let message = match &method {
+          Some(method) => format!("`console.{}` is not allowed", method),
+          None => "`console` methods are not allowed".to_string(),
+        };
+        self.context.add_diagnostic_with_hint(
+          member_expr.span,
+          "no-console",
+          &message,
+          "Remove it, or allow the method in the options if the output is intended",
+        );
+      }
+    }
+    swc_ecmascript::visit::visit_member_expr(self, member_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn allow(methods: &[&str]) -> Box<NoConsole> {
+    NoConsole::with_options(NoConsoleOptions {
+      allow: methods.iter().map(|method| method.to_string()).collect(),
+    })
+  }
+
+  #[test]
+  fn no_console_valid() {
+    assert_lint_ok_n::<NoConsole>(vec![
+      "foo.log('a');",
+      "log('a');",
+      "const console = { log() {} };\nconsole.log('a');",
+      "function foo(console) { console.log('a'); }",
+      "import { console } from './logger.ts';\nconsole.error('a');",
+      "import console from './logger.ts';\nconsole.error('a');",
+      "const a = { console: 1 };",
+      "foo.console.log('a');",
+    ]);
+  }
+
+  #[test]
+  fn no_console_invalid() {
+    assert_lint_err::<NoConsole>("console.log('a');", 0);
+    assert_lint_err::<NoConsole>("const log = console.log;", 12);
+    assert_lint_err::<NoConsole>("console['log']('a');", 0);
+    assert_lint_err::<NoConsole>("console[`log`]('a');", 0);
+    assert_lint_err::<NoConsole>("console[method]('a');", 0);
+    assert_lint_err::<NoConsole>("console.log.bind(console);", 0);
+    assert_lint_err_on_line::<NoConsole>(
+      "function foo(console) {}\nconsole.info('a');",
+      2,
+      0,
+    );
+    assert_lint_err_on_line::<NoConsole>(
+      "function foo() {\n  if (a) {\n    console.error(a);\n  }\n}",
+      3,
+      4,
+    );
+
+    let diagnostics = lint(NoConsole::new(), "foo(console.table(a));");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "`console.table` is not allowed");
+    assert_eq!(diagnostics[0].range.start.col, 4);
+    assert_eq!(diagnostics[0].range.end.col, 17);
+  }
+
+  #[test]
+  fn no_console_optional_chaining() {
+    assert_lint_err::<NoConsole>("console?.log('a');", 0);
+    assert_lint_err::<NoConsole>("console.log?.('a');", 0);
+    assert_lint_err::<NoConsole>("console?.['log']('a');", 0);
+  }
+
+  #[test]
+  fn no_console_allow() {
+    assert_lint_ok_with(allow(&["error", "warn"]), "console.error('a');");
+    assert_lint_ok_with(allow(&["error", "warn"]), "console['warn']('a');");
+    assert_lint_ok_with(allow(&["error"]), "console?.error('a');");
+    assert_lint_err_on_line_n_with(
+      allow(&["error", "warn"]),
+      "console.error('a');\nconsole.log('a');",
+      vec![(2, 0)],
+    );
+    assert_lint_err_on_line_n_with(
+      allow(&["log"]),
+      "console[method]('a');",
+      vec![(1, 0)],
+    );
+  }
+}
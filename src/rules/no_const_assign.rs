@@ -25,6 +25,34 @@ impl LintRule for NoConstAssign {
     "no-const-assign"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Disallows reassigning variables declared with `const`.
+
+Assigning to a constant throws a `TypeError` at runtime.
+
+### Valid:
+```typescript
+const a = 0;
+let b = a;
+b = 1;
+```
+```typescript
+for (const x of [1, 2, 3]) {
+  foo(x);
+}
+```
+
+### Invalid:
+```typescript
+const a = 0;
+a = 1;
+```
+```typescript
+const a = 0;
+a += 1;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -94,7 +122,7 @@ impl NoConstAssignVisitor {
 
   fn check_scope_for_const(&mut self, span: Span, name: &Ident) {
     let id = name.to_id();
-    if let Some(v) = self.context.scope.var(&id) {
+    if let Some(v) = self.context.scope_manager.binding(&id) {
       if let BindingKind::Const = v.kind() {
         self.context.add_diagnostic(
           span,
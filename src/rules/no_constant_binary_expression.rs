@@ -0,0 +1,383 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::constant_util::{
+  constant_nullishness, constant_truthiness, is_always_new,
+  is_boolean_producing,
+};
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{BinExpr, BinaryOp, Expr, Lit, Module, UnaryOp};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  /// Reports comparisons and logical expressions whose result is known
+  /// statically: `x === []`, `"a" ?? b`, `(a < b) === 3`, and negations which
+  /// bind tighter than intended, eg. `!foo == bar`.
+  NoConstantBinaryExpression, "no-constant-binary-expression", tags: [recommended];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows comparisons and logical expressions whose result is known statically.
+
+Comparing with a newly created object like `[]`, using a value that can't be nullish on the left of `??`, or negating only the left side of a comparison, as in `!foo == bar`, are usually mistakes.
+
+### Valid:
+```typescript
+const a = x === y;
+const b = x ?? y;
+const c = !(foo == bar);
+```
+
+### Invalid:
+```typescript
+if (x === []) {}
+```
+```typescript
+const a = "a" ?? b;
+```
+```typescript
+if (!foo == bar) {}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoConstantBinaryExpressionVisitor { context };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoConstantBinaryExpressionVisitor {
+  context: Arc<Context>,
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn is_equality(op: BinaryOp) -> bool {
+  matches!(
+    op,
+    BinaryOp::EqEq | BinaryOp::NotEq | BinaryOp::EqEqEq | BinaryOp::NotEqEq
+  )
+}
+
+fn is_comparison(op: BinaryOp) -> bool {
+  is_equality(op)
+    || matches!(
+      op,
+      BinaryOp::Lt | BinaryOp::LtEq | BinaryOp::Gt | BinaryOp::GtEq
+    )
+}
+
+fn is_nullish_literal(expr: &Expr) -> bool {
+  match unwrap_paren(expr) {
+    Expr::Lit(Lit::Null(_)) => true,
+    Expr::Ident(ident) => ident.sym == *"undefined",
+    _ => false,
+  }
+}
+
+/// Literals which are never equal to a boolean, even loosely when they're
+/// nullish.
+fn is_non_boolean_literal(expr: &Expr, strict: bool) -> bool {
+  if is_nullish_literal(expr) {
+    return true;
+  }
+  strict
+    && matches!(
+      unwrap_paren(expr),
+      Expr::Lit(Lit::Num(_))
+        | Expr::Lit(Lit::Str(_))
+        | Expr::Lit(Lit::BigInt(_))
+        | Expr::Tpl(_)
+    )
+}
+
+impl NoConstantBinaryExpressionVisitor {
+  fn report(&self, bin_expr: &BinExpr, message: String) {
+    self.context.add_diagnostic(
+      bin_expr.span,
+      "no-constant-binary-expression",
+      &message,
+    );
+  }
+
+  fn check_short_circuit(&self, bin_expr: &BinExpr) {
+    let op = bin_expr.op.as_str();
+    let message = match bin_expr.op {
+      BinaryOp::NullishCoalescing => match constant_nullishness(&bin_expr.left)
+      {
+        Some(false) => "never nullish, so the right side is never evaluated",
+        Some(true) => {
+          "always nullish, so `??` always evaluates to the right side"
+        }
+        None => return,
+      },
+      BinaryOp::LogicalOr => match constant_truthiness(&bin_expr.left) {
+        Some(true) => "always truthy, so the right side is never evaluated",
+        Some(false) => {
+          "always falsy, so `||` always evaluates to the right side"
+        }
+        None => return,
+      },
+      BinaryOp::LogicalAnd => match constant_truthiness(&bin_expr.left) {
+        Some(false) => "always falsy, so the right side is never evaluated",
+        Some(true) => {
+          "always truthy, so `&&` always evaluates to the right side"
+        }
+        None => return,
+      },
+      _ => return,
+    };
+    self.report(
+      bin_expr,
+      format!("The left side of `{}` is {}", op, message),
+    );
+  }
+
+  fn check_negation(&self, bin_expr: &BinExpr) -> bool {
+    let negated = match &*bin_expr.left {
+      Expr::Unary(unary) if unary.op == UnaryOp::Bang => &unary.arg,
+      _ => return false,
+    };
+    // `!!foo == bar` casts on purpose
+    if matches!(&**negated, Expr::Unary(unary) if unary.op == UnaryOp::Bang)
+      || is_boolean_producing(&bin_expr.right)
+    {
+      return false;
+    }
+    let negated = self.context.span_text(negated.span());
+    let right = self.context.span_text(bin_expr.right.span());
+    self.context.add_diagnostic_with_hint(
+      bin_expr.span,
+      "no-constant-binary-expression",
+      &format!(
+        "`!` only negates `{}`, so this compares a boolean with `{}`",
+        negated, right
+      ),
+      &format!(
+        "Wrap the comparison in parentheses: `!({} {} {})`",
+        negated,
+        bin_expr.op.as_str(),
+        right
+      ),
+    );
+    true
+  }
+
+  fn check_equality(&self, bin_expr: &BinExpr) {
+    let strict = matches!(bin_expr.op, BinaryOp::EqEqEq | BinaryOp::NotEqEq);
+    let negated = matches!(bin_expr.op, BinaryOp::NotEq | BinaryOp::NotEqEq);
+    let result = if negated { "true" } else { "false" };
+    let left_new = is_always_new(&bin_expr.left);
+    let right_new = is_always_new(&bin_expr.right);
+
+    if left_new && right_new {
+      self.report(
+        bin_expr,
+        format!(
+          "This comparison is always {}, because both sides create new objects",
+          result
+        ),
+      );
+      return;
+    }
+    if strict && (left_new || right_new) {
+      let new = if left_new {
+        &bin_expr.left
+      } else {
+        &bin_expr.right
+      };
+      self.report(
+        bin_expr,
+        format!(
+          "This comparison is always {}, because `{}` creates a new object which nothing else is identical to",
+          result,
+          self.context.span_text(new.span())
+        ),
+      );
+      return;
+    }
+
+    let (boolean, other) = if is_boolean_producing(&bin_expr.left) {
+      (&bin_expr.left, &bin_expr.right)
+    } else if is_boolean_producing(&bin_expr.right) {
+      (&bin_expr.right, &bin_expr.left)
+    } else {
+      return;
+    };
+    if is_non_boolean_literal(other, strict) {
+      self.report(
+        bin_expr,
+        format!(
+          "This comparison is always {}, because `{}` is a boolean and `{}` can't equal a boolean",
+          result,
+          self.context.span_text(boolean.span()),
+          self.context.span_text(other.span())
+        ),
+      );
+    }
+  }
+}
+
+impl Visit for NoConstantBinaryExpressionVisitor {
+  noop_visit_type!();
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, parent: &dyn Node) {
+    self.check_short_circuit(bin_expr);
+    if is_comparison(bin_expr.op)
+      && !self.check_negation(bin_expr)
+      && is_equality(bin_expr.op)
+    {
+      self.check_equality(bin_expr);
+    }
+    swc_ecmascript::visit::visit_bin_expr(self, bin_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_constant_binary_expression_valid() {
+    assert_lint_ok_n::<NoConstantBinaryExpression>(vec![
+      "x === y;",
+      "x === [].length;",
+      "x == [];",
+      "a ?? b;",
+      "a || b;",
+      "a && b;",
+      "foo() ?? 'default';",
+      // Only the left side matters
+      "x ?? 'never null';",
+      "x || {};",
+      "x.y ?? null;",
+      // typeof
+      "typeof x === 'undefined';",
+      "typeof x === typeof y;",
+      "typeof x === 'string' || typeof x === 'number';",
+      "typeof x === 'object' && x !== null;",
+      // Boolean comparisons
+      "(a < b) === true;",
+      "(a < b) === c;",
+      "!a === !b;",
+      "(a in b) == 1;",
+      "(a < b) == 0;",
+      // Negations
+      "!!foo == bar;",
+      "!foo === true;",
+      "!foo === (a < b);",
+      "!(foo == bar);",
+      "foo == !bar;",
+    ]);
+  }
+
+  #[test]
+  fn no_constant_binary_expression_invalid() {
+    let cases = [
+      ("x === [];", 0),
+      ("[] !== x;", 0),
+      ("x === {};", 0),
+      ("x === (() => {});", 0),
+      ("x === /a/;", 0),
+      ("x === new Foo();", 0),
+      ("new Boolean(x) === true;", 0),
+      ("[] == [];", 0),
+      ("'a' ?? b;", 0),
+      ("null ?? b;", 0),
+      ("void x ?? b;", 0),
+      ("typeof x ?? 'undefined';", 0),
+      ("(a + b) ?? c;", 0),
+      ("({}) ?? c;", 0),
+      ("1 || b;", 0),
+      ("'' || b;", 0),
+      ("[] && b;", 0),
+      ("undefined && b;", 0),
+      ("`a${b}` || c;", 0),
+      ("typeof x || 'undefined';", 0),
+      ("(a < b) === 3;", 0),
+      ("(a < b) !== 'true';", 0),
+      ("(x instanceof Y) === null;", 0),
+      ("(a == b) == undefined;", 0),
+      ("!foo == bar;", 0),
+      ("!foo === null;", 0),
+      ("!foo < 3;", 0),
+      ("if (!a !== b) {}", 4),
+    ];
+    for (source, col) in cases.iter() {
+      assert_lint_err::<NoConstantBinaryExpression>(source, *col);
+    }
+  }
+
+  #[test]
+  fn no_constant_binary_expression_nested() {
+    assert_lint_err_on_line_n::<NoConstantBinaryExpression>(
+      "if (x) {\n  f(a === [] || b);\n}",
+      vec![(2, 4)],
+    );
+    assert_lint_err_on_line_n::<NoConstantBinaryExpression>(
+      "const f = () => (x ?? y) || (1 ?? z);",
+      vec![(1, 29)],
+    );
+  }
+
+  #[test]
+  fn no_constant_binary_expression_messages() {
+    let cases = [
+      (
+        "x === [];",
+        "This comparison is always false, because `[]` creates a new object which nothing else is identical to",
+      ),
+      (
+        "new Boolean(x) !== true;",
+        "This comparison is always true, because `new Boolean(x)` creates a new object which nothing else is identical to",
+      ),
+      (
+        "[] == {};",
+        "This comparison is always false, because both sides create new objects",
+      ),
+      (
+        "'a' ?? b;",
+        "The left side of `??` is never nullish, so the right side is never evaluated",
+      ),
+      (
+        "void 0 ?? b;",
+        "The left side of `??` is always nullish, so `??` always evaluates to the right side",
+      ),
+      (
+        "0 || b;",
+        "The left side of `||` is always falsy, so `||` always evaluates to the right side",
+      ),
+      (
+        "({}) && b;",
+        "The left side of `&&` is always truthy, so `&&` always evaluates to the right side",
+      ),
+      (
+        "(a < b) === 3;",
+        "This comparison is always false, because `(a < b)` is a boolean and `3` can't equal a boolean",
+      ),
+      (
+        "!foo == bar;",
+        "`!` only negates `foo`, so this compares a boolean with `bar`",
+      ),
+    ];
+    for (source, message) in cases.iter() {
+      let diagnostics = lint(NoConstantBinaryExpression::new(), source);
+      assert_eq!(diagnostics.len(), 1, "{}", source);
+      assert_eq!(&diagnostics[0].message, message);
+    }
+
+    let diagnostics = lint(NoConstantBinaryExpression::new(), "!foo == bar;");
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Wrap the comparison in parentheses: `!(foo == bar)`")
+    );
+  }
+}
@@ -1,11 +1,11 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::constant_util::is_constant_expr;
 use std::sync::Arc;
 use swc_common::Span;
 use swc_common::Spanned;
 use swc_ecmascript::ast::Expr;
-use swc_ecmascript::ast::Lit;
 use swc_ecmascript::ast::Module;
 use swc_ecmascript::visit::{noop_visit_type, Node, Visit};
 
@@ -20,6 +20,31 @@ impl LintRule for NoConstantCondition {
     "no-constant-condition"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows constant expressions as the condition of `if`, `while`, `for` and `do` statements and of the conditional operator.
+
+A constant condition makes one branch unreachable, which is usually a leftover from debugging.
+
+### Valid:
+```typescript
+if (a) {}
+if (a == 0) {}
+const b = c ? 1 : 2;
+```
+
+### Invalid:
+```typescript
+if (true) {}
+```
+```typescript
+const b = 0 ? 1 : 2;
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = NoConstantConditionVisitor::new(context);
     visitor.visit_module(module, module);
@@ -43,122 +68,8 @@ impl NoConstantConditionVisitor {
     );
   }
 
-  fn check_short_circuit(
-    &self,
-    expr: &Expr,
-    operator: swc_ecmascript::ast::BinaryOp,
-  ) -> bool {
-    match expr {
-      Expr::Lit(lit) => match lit {
-        Lit::Bool(boolean) => {
-          (operator == swc_ecmascript::ast::BinaryOp::LogicalOr
-            && boolean.value)
-            || (operator == swc_ecmascript::ast::BinaryOp::LogicalAnd
-              && !boolean.value)
-        }
-        _ => false,
-      },
-      Expr::Unary(unary) => {
-        operator == swc_ecmascript::ast::BinaryOp::LogicalAnd
-          && unary.op == swc_ecmascript::ast::UnaryOp::Void
-      }
-      Expr::Bin(bin)
-        if bin.op == swc_ecmascript::ast::BinaryOp::LogicalAnd
-          || bin.op == swc_ecmascript::ast::BinaryOp::LogicalOr =>
-      {
-        self.check_short_circuit(&bin.left, bin.op)
-          || self.check_short_circuit(&bin.right, bin.op)
-      }
-      _ => false,
-    }
-  }
-
-  fn is_constant(
-    &self,
-    node: &Expr,
-    parent_node: Option<&Expr>,
-    in_boolean_position: bool,
-  ) -> bool {
-    match node {
-      Expr::Lit(_) | Expr::Arrow(_) | Expr::Fn(_) | Expr::Object(_) => true,
-      Expr::Tpl(tpl) => {
-        (in_boolean_position
-          && tpl.quasis.iter().any(|quasi| match &quasi.cooked {
-            Some(str) => !str.is_empty(),
-            None => false,
-          }))
-          || tpl.exprs.iter().all(|expr| {
-            self.is_constant(expr, parent_node, in_boolean_position)
-          })
-      }
-      // TODO(humancalico) confirm in_boolean_position here
-      Expr::Paren(paren) => self.is_constant(&paren.expr, Some(node), false),
-      Expr::Array(arr) => match parent_node {
-        Some(Expr::Bin(bin)) => {
-          if bin.op == swc_ecmascript::ast::BinaryOp::Add {
-            arr.elems.iter().all(|element| {
-              self.is_constant(
-                &element.as_ref().unwrap().expr,
-                parent_node,
-                false,
-              )
-            })
-          } else {
-            true
-          }
-        }
-        _ => true,
-      },
-      Expr::Unary(unary) => {
-        if unary.op == swc_ecmascript::ast::UnaryOp::Void {
-          true
-        } else {
-          (unary.op == swc_ecmascript::ast::UnaryOp::TypeOf
-            && in_boolean_position)
-            || self.is_constant(&unary.arg, Some(node), true)
-        }
-      }
-      Expr::Bin(bin) => {
-        // This is for LogicalExpression
-        if bin.op == swc_ecmascript::ast::BinaryOp::LogicalOr
-          || bin.op == swc_ecmascript::ast::BinaryOp::LogicalAnd
-        {
-          let is_left_constant =
-            self.is_constant(&bin.left, Some(node), in_boolean_position);
-          let is_right_constant =
-            self.is_constant(&bin.right, Some(node), in_boolean_position);
-          let is_left_short_circuit =
-            is_left_constant && self.check_short_circuit(&bin.left, bin.op);
-          let is_right_short_circuit =
-            is_right_constant && self.check_short_circuit(&bin.right, bin.op);
-          (is_left_constant && is_right_constant)
-          // TODO(humancalico) add more condiitons here from https://github.com/eslint/eslint/blob/f4d7b9e1a599346b2f21ff9de003b311b51411e6/lib/rules/no-constant-condition.js#L135-L146
-            || is_left_short_circuit
-            || is_right_short_circuit
-        }
-        // These are fo regular BinaryExpression
-        else if bin.op != swc_ecmascript::ast::BinaryOp::In {
-          self.is_constant(&bin.left, Some(node), false)
-            && self.is_constant(&bin.right, Some(node), false)
-        } else {
-          false
-        }
-      }
-      Expr::Assign(assign) => {
-        assign.op == swc_ecmascript::ast::AssignOp::Assign
-          && self.is_constant(&assign.right, Some(node), in_boolean_position)
-      }
-      Expr::Seq(seq) => self.is_constant(
-        &seq.exprs[seq.exprs.len() - 1],
-        Some(node),
-        in_boolean_position,
-      ),
-      _ => false,
-    }
-  }
-
   fn report(&self, condition: &Expr) {
-    if self.is_constant(condition, None, true) {
+    if is_constant_expr(condition, None, true) {
       let span = condition.span();
       self.add_diagnostic(span);
     }
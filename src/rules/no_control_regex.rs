@@ -23,6 +23,30 @@ impl LintRule for NoControlRegex {
     "no-control-regex"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows control characters, `\x00` to `\x1f`, in regular expressions.
+
+Control characters rarely appear in text, so matching them is usually a mistake.
+
+### Valid:
+```typescript
+const pattern = /x1f/;
+const pattern2 = /\t/;
+```
+
+### Invalid:
+```typescript
+const pattern = /\x1f/;
+```
+```typescript
+const pattern = /\u001f/;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -122,7 +146,9 @@ impl Visit for NoControlRegexVisitor {
   fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
     if let Expr::Ident(ident) = &*new_expr.callee {
       if let Some(args) = &new_expr.args {
-        if let Some(regex) = extract_regex(&self.context.scope, ident, args) {
+        if let Some(regex) =
+          extract_regex(&self.context.scope_manager, ident, args)
+        {
           self.check_regex(regex.as_str(), new_expr.span);
         }
       }
@@ -134,7 +160,7 @@ impl Visit for NoControlRegexVisitor {
     if let ExprOrSuper::Expr(expr) = &call_expr.callee {
       if let Expr::Ident(ident) = expr.as_ref() {
         if let Some(regex) =
-          extract_regex(&self.context.scope, ident, &call_expr.args)
+          extract_regex(&self.context.scope_manager, ident, &call_expr.args)
         {
           self.check_regex(regex.as_str(), call_expr.span);
         }
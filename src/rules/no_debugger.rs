@@ -1,62 +1,37 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
-use super::Context;
-use super::LintRule;
-use swc_ecmascript::ast::DebuggerStmt;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
-use std::sync::Arc;
+declare_statement_rule! {
+  NoDebugger, "no-debugger", tags: [recommended];
+  visit_debugger_stmt(DebuggerStmt), keyword: "debugger",
+  message: "`debugger` statement is not allowed",
+  hint: "Remove the `debugger` statement";
 
-pub struct NoDebugger;
+  fn docs(&self) -> &'static str {
+    r#"Disallows `debugger` statements.
 
-impl LintRule for NoDebugger {
-  fn new() -> Box<Self> {
-    Box::new(NoDebugger)
-  }
-
-  fn code(&self) -> &'static str {
-    "no-debugger"
-  }
+`debugger` statements pause the execution whenever developer tools are open, and are leftovers from debugging in committed code.
 
-  fn lint_module(
-    &self,
-    context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoDebuggerVisitor::new(context);
-    visitor.visit_module(module, module);
-  }
-}
-struct NoDebuggerVisitor {
-  context: Arc<Context>,
+### Valid:
+```typescript
+function isLongString(x: string) {
+  return x.length > 100;
 }
+```
 
-impl NoDebuggerVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
-  }
+### Invalid:
+```typescript
+function isLongString(x: string) {
+  debugger;
+  return x.length > 100;
 }
-
-impl Visit for NoDebuggerVisitor {
-  noop_visit_type!();
-
-  fn visit_debugger_stmt(
-    &mut self,
-    debugger_stmt: &DebuggerStmt,
-    _parent: &dyn Node,
-  ) {
-    self.context.add_diagnostic(
-      debugger_stmt.span,
-      "no-debugger",
-      "`debugger` statement is not allowed",
-    );
+```"#
   }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::rules::LintRule;
   use crate::test_util::*;
 
   #[test]
@@ -64,6 +39,32 @@ mod tests {
     assert_lint_err::<NoDebugger>(
       r#"function asdf(): number { console.log("asdf"); debugger; return 1; }"#,
       47,
-    )
+    );
+    assert_lint_err_on_line_n::<NoDebugger>(
+      "debugger;\nif (a) debugger\nelse { debugger; }",
+      vec![(1, 0), (2, 7), (3, 7)],
+    );
+  }
+
+  #[test]
+  fn no_debugger_keyword_span() {
+    let diagnostics = lint(NoDebugger::new(), "  debugger;");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 2);
+    assert_eq!(diagnostics[0].range.end.col, 10);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Remove the `debugger` statement")
+    );
+  }
+
+  #[test]
+  fn no_debugger_valid() {
+    assert_lint_ok_n::<NoDebugger>(vec![
+      "const a = { debugger: 1 };",
+      "a.debugger();",
+      "class A { debugger() {} }",
+      "const a = 'debugger';",
+    ]);
   }
 }
@@ -21,6 +21,28 @@ impl LintRule for NoDeleteVar {
     "no-delete-var"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows using `delete` on variables.
+
+`delete` only removes properties; on a variable it does nothing, and it's a syntax error in strict mode.
+
+### Valid:
+```typescript
+const a = { b: 1 };
+delete a.b;
+```
+
+### Invalid:
+```typescript
+var someVar = "someVar";
+delete someVar;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -66,9 +88,12 @@ mod tests {
 
   #[test]
   fn no_delete_var_test() {
-    assert_lint_err::<NoDeleteVar>(
-      r#"var someVar = "someVar"; delete someVar;"#,
-      25,
-    );
+    // Deleting a variable isn't allowed in modules
+    with_syntax_errors(|| {
+      assert_lint_err::<NoDeleteVar>(
+        r#"var someVar = "someVar"; delete someVar;"#,
+        25,
+      );
+    });
   }
 }
@@ -0,0 +1,357 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::deno_test_util::test_callback;
+use crate::swc_util::promise_executor;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ArrowExpr, BlockStmt, BlockStmtOrExpr, CallExpr, Expr, ExprOrSpread,
+  ExprOrSuper, Ident, NewExpr, Pat, Stmt,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+declare_rule! {
+  NoDoneCallback, "no-done-callback", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows callbacks signalling completion where a promise would do.
+
+`new Promise` executors which only resolve with a single expression can use the expression or `Promise.resolve()` directly, and `Deno.test` functions don't receive a completion callback: make the test function `async` instead.
+
+### Valid:
+```typescript
+new Promise((resolve) => setTimeout(resolve, 100));
+```
+```typescript
+Deno.test("foo", async () => {
+  await bar();
+});
+```
+
+### Invalid:
+```typescript
+const p = new Promise((resolve) => resolve(foo()));
+```
+```typescript
+Deno.test("foo", (done) => {
+  assert(true);
+  done();
+});
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = NoDoneCallbackVisitor::new(context);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoDoneCallbackVisitor {
+  context: Arc<Context>,
+}
+
+impl NoDoneCallbackVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self { context }
+  }
+
+  fn check_executor(&self, new_expr: &NewExpr, executor: &Expr) {
+    let (params, body) = match function_parts(executor) {
+      Some(parts) => parts,
+      None => return,
+    };
+    let resolve = match params.first() {
+      Some(Pat::Ident(ident)) => ident.to_id(),
+      _ => return,
+    };
+
+    let arg = match single_call_arg(sole_expr(body), &resolve) {
+      Some(arg) => arg,
+      None => return,
+    };
+    let (value, awaited) = match arg {
+      Expr::Await(await_expr) => (&*await_expr.arg, true),
+      _ => (arg, false),
+    };
+
+    // `resolve(await fetch(url).catch(reject))` still depends on the
+    // executor's arguments, so it can't be unwrapped.
+    let ids: Vec<Id> = params
+      .iter()
+      .filter_map(|param| match param {
+        Pat::Ident(ident) => Some(ident.to_id()),
+        _ => None,
+      })
+      .collect();
+    if references_any(value, &ids) {
+      return;
+    }
+
+    let value_text = self.context.span_text(value.span());
+    let hint = if awaited {
+      format!(
+        "Use `{}` instead of wrapping it in `new Promise`",
+        value_text
+      )
+    } else {
+      format!("Use `Promise.resolve({})` instead", value_text)
+    };
+    self.context.add_diagnostic_with_hint(
+      new_expr.span,
+      "no-done-callback",
+      "Promise executor only resolves a single expression",
+      &hint,
+    );
+  }
+
+  fn check_test_callback(&self, callback: &Expr) {
+    let (params, body) = match function_parts(callback) {
+      Some(parts) => parts,
+      None => return,
+    };
+    let done = match params.first() {
+      Some(Pat::Ident(ident)) => ident,
+      _ => return,
+    };
+
+    let mut finder = CompletionCallFinder {
+      id: done.to_id(),
+      found: false,
+    };
+    match body {
+      FunctionBody::Block(block) => block.visit_with(callback, &mut finder),
+      FunctionBody::Expr(expr) => {
+        finder.check_tail(expr);
+        expr.visit_with(callback, &mut finder);
+      }
+    }
+    if !finder.found {
+      return;
+    }
+
+    self.context.add_diagnostic_with_hint(
+      done.span,
+      "no-done-callback",
+      "Deno.test functions don't receive a completion callback",
+      &format!(
+        "Remove the `{}` parameter and make the test function `async` or return a promise instead",
+        done.sym
+      ),
+    );
+  }
+}
+
+enum FunctionBody<'a> {
+  Block(&'a BlockStmt),
+  Expr(&'a Expr),
+}
+
+fn function_parts(expr: &Expr) -> Option<(Vec<&Pat>, FunctionBody<'_>)> {
+  match expr {
+    Expr::Fn(fn_expr) => Some((
+      fn_expr
+        .function
+        .params
+        .iter()
+        .map(|param| &param.pat)
+        .collect(),
+      FunctionBody::Block(fn_expr.function.body.as_ref()?),
+    )),
+    Expr::Arrow(ArrowExpr { params, body, .. }) => Some((
+      params.iter().collect(),
+      match body {
+        BlockStmtOrExpr::BlockStmt(block) => FunctionBody::Block(block),
+        BlockStmtOrExpr::Expr(expr) => FunctionBody::Expr(expr),
+      },
+    )),
+    _ => None,
+  }
+}
+
+/// Returns the expression of a body consisting of nothing else.
+fn sole_expr<'a>(body: FunctionBody<'a>) -> Option<&'a Expr> {
+  match body {
+    FunctionBody::Block(block) => match block.stmts.as_slice() {
+      [Stmt::Expr(expr_stmt)] => Some(&expr_stmt.expr),
+      _ => None,
+    },
+    FunctionBody::Expr(expr) => Some(expr),
+  }
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn is_call_of(call: &CallExpr, id: &Id) -> bool {
+  match &call.callee {
+    ExprOrSuper::Expr(callee) => match unwrap_paren(callee) {
+      Expr::Ident(ident) => ident.to_id() == *id,
+      _ => false,
+    },
+    ExprOrSuper::Super(_) => false,
+  }
+}
+
+/// Matches `id(arg)` and returns the argument.
+fn single_call_arg<'a>(expr: Option<&'a Expr>, id: &Id) -> Option<&'a Expr> {
+  match unwrap_paren(expr?) {
+    Expr::Call(call) if is_call_of(call, id) => match call.args.as_slice() {
+      [ExprOrSpread { spread: None, expr }] => Some(unwrap_paren(expr)),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+fn references_any(expr: &Expr, ids: &[Id]) -> bool {
+  struct Finder<'a> {
+    ids: &'a [Id],
+    found: bool,
+  }
+
+  impl<'a> Visit for Finder<'a> {
+    noop_visit_type!();
+
+    fn visit_ident(&mut self, ident: &Ident, _parent: &dyn Node) {
+      if self.ids.contains(&ident.to_id()) {
+        self.found = true;
+      }
+    }
+  }
+
+  let mut finder = Finder { ids, found: false };
+  expr.visit_with(expr, &mut finder);
+  finder.found
+}
+
+/// Looks for the parameter being called with at most one argument as the
+/// last thing a block (or an arrow function) does.
+struct CompletionCallFinder {
+  id: Id,
+  found: bool,
+}
+
+impl CompletionCallFinder {
+  fn check_tail(&mut self, expr: &Expr) {
+    if let Expr::Call(call) = unwrap_paren(expr) {
+      if is_call_of(call, &self.id) && call.args.len() <= 1 {
+        self.found = true;
+      }
+    }
+  }
+}
+
+impl Visit for CompletionCallFinder {
+  noop_visit_type!();
+
+  fn visit_block_stmt(&mut self, block: &BlockStmt, parent: &dyn Node) {
+    if let Some(Stmt::Expr(expr_stmt)) = block.stmts.last() {
+      self.check_tail(&expr_stmt.expr);
+    }
+    swc_ecmascript::visit::visit_block_stmt(self, block, parent);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow: &ArrowExpr, parent: &dyn Node) {
+    if let BlockStmtOrExpr::Expr(expr) = &arrow.body {
+      self.check_tail(expr);
+    }
+    swc_ecmascript::visit::visit_arrow_expr(self, arrow, parent);
+  }
+}
+
+impl Visit for NoDoneCallbackVisitor {
+  noop_visit_type!();
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+    if let Some(executor) = promise_executor(new_expr) {
+      self.check_executor(new_expr, executor);
+    }
+    swc_ecmascript::visit::visit_new_expr(self, new_expr, parent);
+  }
+
+  fn visit_call_expr(&mut self, call: &CallExpr, parent: &dyn Node) {
+    if let Some(callback) = test_callback(call) {
+      self.check_test_callback(callback);
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_done_callback_valid() {
+    assert_lint_ok_n::<NoDoneCallback>(vec![
+      "new Promise((resolve) => setTimeout(resolve, 100));",
+      "new Promise((resolve) => { foo(); resolve(1); });",
+      "new Promise((resolve, reject) => { if (!ok) reject(err); resolve(1); });",
+      "new Promise(async (resolve, reject) => { const res = await fetch(url); if (!res.ok) { reject(res); } resolve(res); });",
+      "new Promise(async (resolve, reject) => resolve(await fetch(url).catch(reject)));",
+      "new Promise((resolve) => resolve());",
+      "new Promise((resolve) => emitter.on('done', resolve));",
+      "new Foo((resolve) => resolve(1));",
+      r#"Deno.test("foo", () => { assert(true); });"#,
+      r#"Deno.test("foo", async () => { await foo(); });"#,
+      r#"Deno.test("foo", async (t) => { await t.step("bar", () => {}); });"#,
+      r#"Deno.test({ name: "foo", fn: async (t) => { await t.step("bar", () => {}); } });"#,
+      r#"Deno.test("foo", (t) => { const done = t.step; something(done); });"#,
+      r#"test("foo", (done) => { done(); });"#,
+    ]);
+  }
+
+  #[test]
+  fn no_done_callback_invalid() {
+    assert_lint_err::<NoDoneCallback>(
+      "const p = new Promise(async (resolve) => { resolve(await fetch(url)); });",
+      10,
+    );
+    assert_lint_err::<NoDoneCallback>(
+      "const p = new Promise(async function (resolve, reject) { resolve(await foo()); });",
+      10,
+    );
+    assert_lint_err::<NoDoneCallback>(
+      "const p = new Promise((resolve) => resolve(foo()));",
+      10,
+    );
+    assert_lint_err::<NoDoneCallback>(
+      r#"Deno.test("foo", (done) => { assert(true); done(); });"#,
+      18,
+    );
+    assert_lint_err::<NoDoneCallback>(
+      r#"Deno.test("foo", function (cb) { setTimeout(() => cb(), 10); });"#,
+      27,
+    );
+    assert_lint_err::<NoDoneCallback>(
+      r#"Deno.test({ name: "foo", fn: (done) => { setTimeout(() => { done(); }, 10); } });"#,
+      30,
+    );
+    assert_lint_err_on_line::<NoDoneCallback>(
+      r#"
+Deno.test("foo", { permissions: { read: true } }, (done) => {
+  readFile("a.txt", (err) => {
+    assert(!err);
+    done(err);
+  });
+});
+      "#,
+      2,
+      51,
+    );
+  }
+}
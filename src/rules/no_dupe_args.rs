@@ -24,6 +24,29 @@ impl LintRule for NoDupeArgs {
     "no-dupe-args"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows duplicate parameter names in function definitions.
+
+The last parameter with the name shadows the earlier ones, and duplicates are a syntax error in strict mode.
+
+### Valid:
+```typescript
+function withoutDupes(a, b, c) {}
+```
+
+### Invalid:
+```typescript
+function withDupes(a, b, a) {}
+```
+```typescript
+const withDupes = (a, b, a) => {};
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
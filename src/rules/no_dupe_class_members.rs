@@ -25,6 +25,40 @@ impl LintRule for NoDupeClassMembers {
     "no-dupe-class-members"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows duplicate class member names.
+
+The last member with a name overwrites the earlier ones. A getter and a setter of the same name, and static and instance members of the same name, are allowed.
+
+### Valid:
+```typescript
+class Foo {
+  bar() {}
+  qux() {}
+}
+```
+```typescript
+class Foo {
+  get bar() {
+    return 1;
+  }
+  set bar(value: number) {}
+}
+```
+
+### Invalid:
+```typescript
+class Foo {
+  bar() {}
+  bar() {}
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -21,6 +21,43 @@ impl LintRule for NoDupeElseIf {
     "no-dupe-else-if"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows conditions in an `if`-`else if` chain which duplicate, or are covered by, an earlier condition.
+
+The branch of such a condition can never run, which is usually a copy and paste mistake.
+
+### Valid:
+```typescript
+if (a) {
+  foo();
+} else if (b) {
+  bar();
+}
+```
+
+### Invalid:
+```typescript
+if (a) {
+  foo();
+} else if (b) {
+  bar();
+} else if (a) {
+  baz();
+}
+```
+```typescript
+if (a || b) {
+  foo();
+} else if (a) {
+  bar();
+}
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = NoDupeElseIfVisitor::new(context);
     visitor.visit_module(module, module);
@@ -19,6 +19,29 @@ impl LintRule for NoDupeKeys {
     "no-dupe-keys"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows duplicate keys in object literals.
+
+The last value with a key overwrites the earlier ones, which is usually a mistake.
+
+### Valid:
+```typescript
+var foo = { bar: "baz", boo: "bang" };
+```
+
+### Invalid:
+```typescript
+var foo = { bar: "baz", bar: "qux" };
+```
+```typescript
+var foo = { 1: "baz", 0x1: "qux" };
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = NoDupeKeysVisitor::new(context);
     visitor.visit_module(module, module);
@@ -118,9 +141,12 @@ mod tests {
 
   #[test]
   fn it_fails_when_there_are_duplicate_setter_keys() {
-    assert_lint_err::<NoDupeKeys>(
-      r#"var foo = { bar: "baz", set bar() {} };"#,
-      10,
-    );
+    // The setter is missing its parameter
+    with_syntax_errors(|| {
+      assert_lint_err::<NoDupeKeys>(
+        r#"var foo = { bar: "baz", set bar() {} };"#,
+        10,
+      );
+    });
   }
 }
@@ -20,6 +20,38 @@ impl LintRule for NoDuplicateCase {
     "no-duplicate-case"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows duplicate test expressions in the `case` clauses of a `switch` statement.
+
+Only the first clause with the value can ever run.
+
+### Valid:
+```typescript
+switch (someText) {
+  case "a":
+    break;
+  case "b":
+    break;
+}
+```
+
+### Invalid:
+```typescript
+switch (someText) {
+  case "a":
+    break;
+  case "b":
+    break;
+  case "a":
+    break;
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -0,0 +1,283 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::collections::HashMap;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{Module, ModuleDecl, ModuleItem, Str};
+
+#[derive(Clone, Copy, Default)]
+pub struct NoDuplicateImportsOptions {
+  /// Also reports modules which are re-exported with `export ... from`
+  /// more than once, or both imported and re-exported.
+  pub include_exports: bool,
+  /// Treats type-only imports and exports like value ones, so
+  /// `import type { A } from "./a.ts"` next to `import { b } from "./a.ts"`
+  /// is reported.
+  pub include_type_imports: bool,
+}
+
+/// Reports module specifiers which appear in more than one import
+/// declaration. Specifiers are compared by their value, so the quotes they
+/// are written with don't matter.
+pub struct NoDuplicateImports {
+  options: NoDuplicateImportsOptions,
+}
+
+impl NoDuplicateImports {
+  pub fn with_options(options: NoDuplicateImportsOptions) -> Box<Self> {
+    Box::new(NoDuplicateImports { options })
+  }
+}
+
+impl LintRule for NoDuplicateImports {
+  fn new() -> Box<Self> {
+    Self::with_options(NoDuplicateImportsOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-duplicate-imports"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows importing the same module in more than one import declaration.
+
+Imports of one module spread over several declarations are harder to read, and a constant source of merge conflicts. Type-only imports are tracked separately from value imports by default.
+
+### Options
+
+- `NoDuplicateImportsOptions::include_exports`: also reports modules which are re-exported with `export ... from` more than once, or both imported and re-exported, `false` by default.
+- `NoDuplicateImportsOptions::include_type_imports`: treats type-only imports like value imports, `false` by default.
+
+### Valid:
+```typescript
+import { a, b } from "./mod.ts";
+```
+```typescript
+import { A } from "./mod.ts";
+import type { B } from "./mod.ts";
+```
+
+### Invalid:
+```typescript
+import { a } from "./mod.ts";
+import { b } from "./mod.ts";
+```
+```typescript
+import * as mod from "./mod.ts";
+import { b } from './mod.ts';
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut seen: HashMap<(String, bool), (Span, Kind)> = HashMap::new();
+    for item in &module.body {
+      let (src, type_only, kind) = match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+          (&import_decl.src, import_decl.type_only, Kind::Import)
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named_export))
+          if self.options.include_exports =>
+        {
+          match &named_export.src {
+            Some(src) => (src, named_export.type_only, Kind::Export),
+            None => continue,
+          }
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all))
+          if self.options.include_exports =>
+        {
+          (&export_all.src, false, Kind::Export)
+        }
+        _ => continue,
+      };
+      let type_only = type_only && !self.options.include_type_imports;
+      let key = (src.value.to_string(), type_only);
+      match seen.get(&key) {
+        Some(&(first_span, first_kind)) => {
+          report(&context, src, kind, first_span, first_kind)
+        }
+        None => {
+          seen.insert(key, (src.span, kind));
+        }
+      }
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+  Import,
+  Export,
+}
+
+impl Kind {
+  fn describe(self) -> &'static str {
+    match self {
+      Kind::Import => "import",
+      Kind::Export => "re-export",
+    }
+  }
+}
+
+fn report(
+  context: &Context,
+  src: &Str,
+  kind: Kind,
+  first_span: Span,
+  first_kind: Kind,
+) {
+  let message = match (first_kind, kind) {
+    (Kind::Import, Kind::Import) => {
+      format!("Module `{}` is imported more than once", src.value)
+    }
+    (Kind::Export, Kind::Export) => {
+      format!("Module `{}` is re-exported more than once", src.value)
+    }
+    _ => format!("Module `{}` is both imported and re-exported", src.value),
+  };
+  let location = context.source_map.lookup_char_pos(first_span.lo);
+  context.add_diagnostic_with_hint(
+    src.span,
+    "no-duplicate-imports",
+    &message,
+    &format!(
+      "Merge it with the {} at {}:{}",
+      first_kind.describe(),
+      location.line,
+      location.col.0
+    ),
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn with_exports() -> Box<NoDuplicateImports> {
+    NoDuplicateImports::with_options(NoDuplicateImportsOptions {
+      include_exports: true,
+      ..Default::default()
+    })
+  }
+
+  #[test]
+  fn no_duplicate_imports_valid() {
+    assert_lint_ok_n::<NoDuplicateImports>(vec![
+      "import { a, b } from './a.ts';",
+      "import a from './a.ts';\nimport b from './b.ts';",
+      "import { a } from './a.ts';\nimport { a as b } from './a.tsx';",
+      "import { A } from './a.ts';\nimport type { B } from './a.ts';",
+      "import a from './a.ts';\nexport { b } from './a.ts';",
+      "import a from './a.ts';\nexport * from './a.ts';",
+      "export { a } from './a.ts';\nexport { b } from './a.ts';",
+      "import { a } from './a.ts';\nexport { a };",
+    ]);
+  }
+
+  #[test]
+  fn no_duplicate_imports_invalid() {
+    assert_lint_err_on_line::<NoDuplicateImports>(
+      "import { a } from './a.ts';\nimport { b } from './a.ts';",
+      2,
+      18,
+    );
+    assert_lint_err_on_line_n::<NoDuplicateImports>(
+      "import a from './a.ts';\nimport { b } from './a.ts';\nimport * as c from './a.ts';",
+      vec![(2, 18), (3, 19)],
+    );
+    assert_lint_err_on_line::<NoDuplicateImports>(
+      "import type { A } from './a.ts';\nimport type { B } from './a.ts';",
+      2,
+      23,
+    );
+    assert_lint_err_on_line::<NoDuplicateImports>(
+      "import './a.ts';\nimport a from './a.ts';",
+      2,
+      14,
+    );
+  }
+
+  #[test]
+  fn no_duplicate_imports_quote_styles() {
+    assert_lint_err_on_line::<NoDuplicateImports>(
+      "import { a } from './a.ts';\nimport { b } from \"./a.ts\";",
+      2,
+      18,
+    );
+    assert_lint_ok::<NoDuplicateImports>(
+      "import { a } from './a.ts';\nimport { b } from 'a.ts';",
+    );
+
+    let diagnostics = lint(
+      NoDuplicateImports::new(),
+      "import { a } from \"./a.ts\";\nimport { b } from './a.ts';",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Module `./a.ts` is imported more than once"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Merge it with the import at 1:18")
+    );
+  }
+
+  #[test]
+  fn no_duplicate_imports_exports() {
+    assert_lint_ok_with(with_exports(), "export { a } from './a.ts';");
+    assert_lint_ok_with(
+      with_exports(),
+      "import a from './a.ts';\nexport { b } from './b.ts';",
+    );
+    assert_lint_err_on_line_n_with(
+      with_exports(),
+      "export { a } from './a.ts';\nexport { b } from './a.ts';",
+      vec![(2, 18)],
+    );
+    assert_lint_err_on_line_n_with(
+      with_exports(),
+      "import a from './a.ts';\nexport * from './a.ts';",
+      vec![(2, 14)],
+    );
+
+    let diagnostics = lint(
+      with_exports(),
+      "export * from './a.ts';\nimport { a } from './a.ts';",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Module `./a.ts` is both imported and re-exported"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Merge it with the re-export at 1:14")
+    );
+  }
+
+  #[test]
+  fn no_duplicate_imports_type_imports() {
+    let rule = || {
+      NoDuplicateImports::with_options(NoDuplicateImportsOptions {
+        include_type_imports: true,
+        ..Default::default()
+      })
+    };
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "import { A } from './a.ts';\nimport type { B } from './a.ts';",
+      vec![(2, 23)],
+    );
+    assert_lint_ok_with(
+      rule(),
+      "import { a } from './a.ts';\nexport type { B } from './a.ts';",
+    );
+  }
+}
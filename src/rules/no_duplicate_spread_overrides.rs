@@ -0,0 +1,133 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::overwritten_props;
+use std::sync::Arc;
+use swc_ecmascript::ast::{Module, ObjectLit};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  /// Reports properties which override a spread, but are themselves
+  /// overwritten by a second spread of an inline object literal with the same
+  /// key, like `a` in `{ ...defaults, a: 1, ...{ a: 2 } }`.
+  ///
+  /// Spreads of anything but object literals reset the analysis, so the
+  /// second spread has to be an object literal.
+  NoDuplicateSpreadOverrides, "no-duplicate-spread-overrides", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows properties which override a spread, but are themselves overwritten by a later spread of an object literal with the same key.
+
+In `{ ...defaults, a: 1, ...{ a: 2 } }` the override `a: 1` has no effect. Spreads of anything but object literals reset the analysis, since their keys aren't known.
+
+### Valid:
+```typescript
+const o = { ...defaults, a: 1 };
+```
+```typescript
+const o = { ...defaults, ...{ a: 2 }, a: 1 };
+```
+
+### Invalid:
+```typescript
+const o = { ...defaults, a: 1, ...{ a: 2 } };
+```
+```typescript
+const o = { ...defaults, a: 1, ...{ b: 2 }, ...{ a: 3 } };
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoDuplicateSpreadOverridesVisitor { context };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoDuplicateSpreadOverridesVisitor {
+  context: Arc<Context>,
+}
+
+impl Visit for NoDuplicateSpreadOverridesVisitor {
+  noop_visit_type!();
+
+  fn visit_object_lit(&mut self, obj_lit: &ObjectLit, parent: &dyn Node) {
+    for prop in overwritten_props(obj_lit) {
+      if !prop.after_spread {
+        continue;
+      }
+      let location = self
+        .context
+        .source_map
+        .lookup_char_pos(prop.overwritten_by.lo());
+      self.context.add_diagnostic_with_hint(
+        prop.span,
+        "no-duplicate-spread-overrides",
+        &format!(
+          "Override of `{}` is overwritten again by a later spread, so it has no effect",
+          prop.key
+        ),
+        &format!(
+          "`{}` is overwritten by the spread at {}:{}; move the override after it or remove it",
+          prop.key, location.line, location.col.0
+        ),
+      );
+    }
+    swc_ecmascript::visit::visit_object_lit(self, obj_lit, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_duplicate_spread_overrides_valid() {
+    assert_lint_ok_n::<NoDuplicateSpreadOverrides>(vec![
+      "const o = { ...defaults, a: 1 };",
+      "const o = { ...defaults, a: 1, ...{ b: 2 } };",
+      "const o = { ...defaults, a: 1, ...options };",
+      "const o = { ...{ a: 1 }, a: 2 };",
+      "const o = { ...defaults, get a() { return 1; }, ...{ a: 2 } };",
+      "const o = { ...defaults, a: 1, ...(cond ? { a: 2 } : { b: 2 }) };",
+      "const o = { ...defaults, a: 1, ...options, ...{ b: 2 } };",
+      // Reported by `no-shorthand-property-overwrite`.
+      "const o = { a: 1, ...{ a: 2 } };",
+    ]);
+  }
+
+  #[test]
+  fn no_duplicate_spread_overrides_invalid() {
+    assert_lint_err::<NoDuplicateSpreadOverrides>(
+      "const o = { ...defaults, a: 1, ...{ a: 2 } };",
+      25,
+    );
+    assert_lint_err::<NoDuplicateSpreadOverrides>(
+      "const o = { ...{ a: 1 }, a, ...{ a: 2 } };",
+      25,
+    );
+    assert_lint_err::<NoDuplicateSpreadOverrides>(
+      "const o = { ...defaults, a: 1, ...{ b: 2 }, ...{ a: 3 } };",
+      25,
+    );
+    assert_lint_err_n::<NoDuplicateSpreadOverrides>(
+      "const o = { ...defaults, a: 1, b: 2, ...{ a: 3, b: 4 } };",
+      vec![25, 31],
+    );
+
+    let diagnostics = lint(
+      NoDuplicateSpreadOverrides::new(),
+      "const o = { ...defaults, a: 1, ...{ a: 2 } };",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "Override of `a` is overwritten again by a later spread, so it has no effect"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("`a` is overwritten by the spread at 1:31; move the override after it or remove it")
+    );
+  }
+}
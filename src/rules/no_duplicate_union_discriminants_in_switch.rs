@@ -0,0 +1,682 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::edit_distance;
+use std::collections::HashMap;
+use std::sync::Arc;
+use swc_ecmascript::ast::{
+  Decl, Expr, ExprOrSuper, Lit, Module, ModuleDecl, ModuleItem, Pat, Stmt,
+  SwitchCase, SwitchStmt, TsEntityName, TsInterfaceDecl, TsLit, TsType,
+  TsTypeElement, TsUnionOrIntersectionType,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+pub struct NoDuplicateUnionDiscriminantsInSwitchOptions {
+  /// Property names which discriminate the members of a union.
+  pub discriminants: Vec<String>,
+}
+
+impl Default for NoDuplicateUnionDiscriminantsInSwitchOptions {
+  fn default() -> Self {
+    Self {
+      discriminants: vec![
+        "kind".to_string(),
+        "type".to_string(),
+        "tag".to_string(),
+      ],
+    }
+  }
+}
+
+/// Checks `switch (value.kind)` statements over a discriminated union
+/// declared in the same file: case labels which aren't a discriminant of
+/// the union (likely typos), and `default` clauses which only throw
+/// although the cases cover every member.
+///
+/// The rule is silent unless `value` is annotated with a union declared in
+/// the file, whose members all have a string literal discriminant.
+pub struct NoDuplicateUnionDiscriminantsInSwitch {
+  options: NoDuplicateUnionDiscriminantsInSwitchOptions,
+}
+
+impl NoDuplicateUnionDiscriminantsInSwitch {
+  pub fn with_options(
+    options: NoDuplicateUnionDiscriminantsInSwitchOptions,
+  ) -> Box<Self> {
+    Box::new(NoDuplicateUnionDiscriminantsInSwitch { options })
+  }
+}
+
+impl LintRule for NoDuplicateUnionDiscriminantsInSwitch {
+  fn new() -> Box<Self> {
+    Self::with_options(NoDuplicateUnionDiscriminantsInSwitchOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-duplicate-union-discriminants-in-switch"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Checks `switch` statements over the discriminant of a union declared in the same file.
+
+Case labels which aren't a discriminant of the union are likely typos, and `default` clauses which only throw although the cases cover every member of the union are unreachable. The rule only checks values annotated with a union whose members all have a string literal discriminant.
+
+### Options
+
+`NoDuplicateUnionDiscriminantsInSwitchOptions::discriminants`: the property names which discriminate the members of a union, `["kind", "type", "tag"]` by default.
+
+### Valid:
+```typescript
+interface Circle {
+  kind: "circle";
+  radius: number;
+}
+interface Square {
+  kind: "square";
+  size: number;
+}
+type Shape = Circle | Square;
+
+function area(shape: Shape) {
+  switch (shape.kind) {
+    case "circle":
+      return 1;
+    case "square":
+      return 2;
+  }
+}
+```
+
+### Invalid:
+```typescript
+interface Circle {
+  kind: "circle";
+  radius: number;
+}
+interface Square {
+  kind: "square";
+  size: number;
+}
+type Shape = Circle | Square;
+
+function area(shape: Shape) {
+  switch (shape.kind) {
+    case "circel":
+      return 1;
+    case "square":
+      return 2;
+  }
+}
+```
+```typescript
+interface Circle {
+  kind: "circle";
+  radius: number;
+}
+interface Square {
+  kind: "square";
+  size: number;
+}
+type Shape = Circle | Square;
+
+function area(shape: Shape) {
+  switch (shape.kind) {
+    case "circle":
+      return 1;
+    case "square":
+      return 2;
+    default:
+      throw new Error("unknown shape");
+  }
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let types = LocalTypes::collect(module);
+    if types.aliases.is_empty() {
+      return;
+    }
+    let mut annotations = AnnotationCollector {
+      annotations: HashMap::new(),
+    };
+    annotations.visit_module(module, module);
+    let mut visitor = NoDuplicateUnionDiscriminantsInSwitchVisitor {
+      context,
+      options: &self.options,
+      types,
+      annotations: annotations.annotations,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+/// The type aliases and interfaces declared at the top level of the file.
+struct LocalTypes<'a> {
+  aliases: HashMap<&'a str, &'a TsType>,
+  interfaces: HashMap<&'a str, Vec<&'a TsInterfaceDecl>>,
+}
+
+impl<'a> LocalTypes<'a> {
+  fn collect(module: &'a Module) -> Self {
+    let mut types = LocalTypes {
+      aliases: HashMap::new(),
+      interfaces: HashMap::new(),
+    };
+    for item in &module.body {
+      let decl = match item {
+        ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+        _ => continue,
+      };
+      match decl {
+        Decl::TsTypeAlias(alias) => {
+          types.aliases.insert(&*alias.id.sym, &*alias.type_ann);
+        }
+        // Interfaces are merged with their other declarations
+        Decl::TsInterface(interface) => types
+          .interfaces
+          .entry(&*interface.id.sym)
+          .or_default()
+          .push(interface),
+        _ => {}
+      }
+    }
+    types
+  }
+
+  /// Returns the discriminant literals of the union named `name`, if every
+  /// member of it has literal ones for `property`.
+  fn union_literals(&self, name: &str, property: &str) -> Option<Vec<String>> {
+    let ty = self.aliases.get(name)?;
+    if !matches!(
+      unwrap_ts_paren(ty),
+      TsType::TsUnionOrIntersectionType(
+        TsUnionOrIntersectionType::TsUnionType(_)
+      )
+    ) {
+      return None;
+    }
+    let mut literals = vec![];
+    for literal in self.member_literals(ty, property, 0)? {
+      if !literals.contains(&literal) {
+        literals.push(literal);
+      }
+    }
+    Some(literals)
+  }
+
+  fn member_literals(
+    &self,
+    ty: &TsType,
+    property: &str,
+    depth: usize,
+  ) -> Option<Vec<String>> {
+    // Guards against aliases referring to each other
+    if depth > 8 {
+      return None;
+    }
+    match unwrap_ts_paren(ty) {
+      TsType::TsUnionOrIntersectionType(
+        TsUnionOrIntersectionType::TsUnionType(union),
+      ) => {
+        let mut literals = vec![];
+        for member in &union.types {
+          literals.extend(self.member_literals(member, property, depth + 1)?);
+        }
+        Some(literals)
+      }
+      TsType::TsTypeLit(type_lit) => {
+        property_literals(&type_lit.members, property)
+      }
+      TsType::TsTypeRef(type_ref) if type_ref.type_params.is_none() => {
+        let name = match &type_ref.type_name {
+          TsEntityName::Ident(ident) => &*ident.sym,
+          TsEntityName::TsQualifiedName(_) => return None,
+        };
+        if let Some(interfaces) = self.interfaces.get(name) {
+          // Any of the merged declarations may declare the discriminant
+          return interfaces.iter().find_map(|interface| {
+            property_literals(&interface.body.body, property)
+          });
+        }
+        self.member_literals(self.aliases.get(name)?, property, depth + 1)
+      }
+      _ => None,
+    }
+  }
+}
+
+fn unwrap_ts_paren(ty: &TsType) -> &TsType {
+  match ty {
+    TsType::TsParenthesizedType(paren) => unwrap_ts_paren(&paren.type_ann),
+    _ => ty,
+  }
+}
+
+/// Returns the string literals the type of `property` consists of, in an
+/// interface or type literal.
+fn property_literals(
+  members: &[TsTypeElement],
+  property: &str,
+) -> Option<Vec<String>> {
+  members.iter().find_map(|member| {
+    let signature = match member {
+      TsTypeElement::TsPropertySignature(signature) if !signature.computed => {
+        signature
+      }
+      _ => return None,
+    };
+    let key = match &*signature.key {
+      Expr::Ident(ident) => ident.sym.to_string(),
+      Expr::Lit(Lit::Str(str)) => str.value.to_string(),
+      _ => return None,
+    };
+    if key != property {
+      return None;
+    }
+    literal_strings(&signature.type_ann.as_ref()?.type_ann)
+  })
+}
+
+fn literal_strings(ty: &TsType) -> Option<Vec<String>> {
+  match unwrap_ts_paren(ty) {
+    TsType::TsLitType(lit_type) => match &lit_type.lit {
+      TsLit::Str(str) => Some(vec![str.value.to_string()]),
+      _ => None,
+    },
+    TsType::TsUnionOrIntersectionType(
+      TsUnionOrIntersectionType::TsUnionType(union),
+    ) => {
+      let mut literals = vec![];
+      for member in &union.types {
+        literals.extend(literal_strings(member)?);
+      }
+      Some(literals)
+    }
+    _ => None,
+  }
+}
+
+/// Collects the bindings annotated with a plain type reference, eg.
+/// `shape: Shape`.
+struct AnnotationCollector {
+  annotations: HashMap<Id, String>,
+}
+
+impl Visit for AnnotationCollector {
+  fn visit_pat(&mut self, pat: &Pat, parent: &dyn Node) {
+    if let Pat::Ident(ident) = pat {
+      if let Some(type_ann) = &ident.type_ann {
+        if let TsType::TsTypeRef(type_ref) = &*type_ann.type_ann {
+          if let (TsEntityName::Ident(name), None) =
+            (&type_ref.type_name, &type_ref.type_params)
+          {
+            self.annotations.insert(ident.to_id(), name.sym.to_string());
+          }
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_pat(self, pat, parent);
+  }
+}
+
+/// Tells whether the clause only throws an `Error`, eg.
+/// `default: throw new Error("unreachable");`.
+fn only_throws_error(clause: &SwitchCase) -> bool {
+  let stmts = match clause.cons.as_slice() {
+    [Stmt::Block(block)] => &block.stmts,
+    stmts => stmts,
+  };
+  let arg = match stmts {
+    [Stmt::Throw(throw)] => &*throw.arg,
+    _ => return false,
+  };
+  let callee = match arg {
+    Expr::New(new) => &*new.callee,
+    Expr::Call(call) => match &call.callee {
+      ExprOrSuper::Expr(callee) => &**callee,
+      ExprOrSuper::Super(_) => return false,
+    },
+    _ => return false,
+  };
+  matches!(callee, Expr::Ident(ident) if ident.sym == *"Error")
+}
+
+fn quoted(literals: &[String]) -> String {
+  literals
+    .iter()
+    .map(|literal| format!("\"{}\"", literal))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+struct NoDuplicateUnionDiscriminantsInSwitchVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a NoDuplicateUnionDiscriminantsInSwitchOptions,
+  types: LocalTypes<'a>,
+  annotations: HashMap<Id, String>,
+}
+
+impl<'a> NoDuplicateUnionDiscriminantsInSwitchVisitor<'a> {
+  fn check_switch(&self, switch_stmt: &SwitchStmt) -> Option<()> {
+    let member = match &*switch_stmt.discriminant {
+      Expr::Member(member) => member,
+      Expr::Paren(paren) => match &*paren.expr {
+        Expr::Member(member) => member,
+        _ => return None,
+      },
+      _ => return None,
+    };
+    let object = match &member.obj {
+      ExprOrSuper::Expr(obj) => match &**obj {
+        Expr::Ident(ident) => ident,
+        _ => return None,
+      },
+      ExprOrSuper::Super(_) => return None,
+    };
+    let property = match (&*member.prop, member.computed) {
+      (Expr::Ident(ident), false) => ident.sym.to_string(),
+      (Expr::Lit(Lit::Str(str)), true) => str.value.to_string(),
+      _ => return None,
+    };
+    if !self.options.discriminants.contains(&property) {
+      return None;
+    }
+    let union = self.annotations.get(&object.to_id())?;
+    let literals = self.types.union_literals(union, &property)?;
+
+    let mut covered = vec![];
+    for case in &switch_stmt.cases {
+      let (value, span) = match case.test.as_deref() {
+        Some(Expr::Lit(Lit::Str(str))) => (str.value.to_string(), str.span),
+        _ => continue,
+      };
+      if literals.contains(&value) {
+        covered.push(value);
+        continue;
+      }
+      let closest = literals
+        .iter()
+        .map(|literal| (edit_distance(literal, &value), literal))
+        .filter(|(distance, literal)| *distance <= 2.max(literal.len() / 3))
+        .min();
+      let hint = match closest {
+        Some((_, literal)) => format!("Did you mean \"{}\"?", literal),
+        None => {
+          format!(
+            "The `{}` of `{}` is one of {}",
+            property,
+            union,
+            quoted(&literals)
+          )
+        }
+      };
+      self.context.add_diagnostic_with_hint(
+        span,
+        "no-duplicate-union-discriminants-in-switch",
+        &format!(
+          "\"{}\" is not a `{}` of `{}`, so this case never matches",
+          value, property, union
+        ),
+        &hint,
+      );
+    }
+
+    if literals.iter().all(|literal| covered.contains(literal)) {
+      let default = switch_stmt
+        .cases
+        .iter()
+        .find(|case| case.test.is_none() && only_throws_error(case))?;
+      self.context.add_diagnostic_with_hint(
+        default.span,
+        "no-duplicate-union-discriminants-in-switch",
+        &format!(
+          "This `default` clause is unreachable, because the cases cover every `{}` of `{}`",
+          property, union
+        ),
+        &format!(
+          "Check exhaustiveness instead, so adding a member to `{}` fails to type-check: `const exhaustive: never = {};`",
+          union, object.sym
+        ),
+      );
+    }
+    Some(())
+  }
+}
+
+impl<'a> Visit for NoDuplicateUnionDiscriminantsInSwitchVisitor<'a> {
+  fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt, parent: &dyn Node) {
+    self.check_switch(switch_stmt);
+    swc_ecmascript::visit::visit_switch_stmt(self, switch_stmt, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  const SHAPE: &str = r#"
+interface Circle {
+  kind: "circle";
+  radius: number;
+}
+interface Square {
+  kind: "square";
+  size: number;
+}
+type Triangle = { kind: "triangle"; base: number; height: number };
+type Shape = Circle | Square | Triangle;
+"#;
+
+  fn with_shape(source: &str) -> String {
+    format!("{}{}", SHAPE, source)
+  }
+
+  #[test]
+  fn no_duplicate_union_discriminants_in_switch_valid() {
+    assert_lint_ok_n::<NoDuplicateUnionDiscriminantsInSwitch>(vec![
+      &with_shape(
+        r#"
+function area(shape: Shape) {
+  switch (shape.kind) {
+    case "circle":
+      return 1;
+    case "square":
+      return 2;
+    default:
+      throw new Error("unknown shape");
+  }
+}
+"#,
+      ),
+      // Exhaustiveness check
+      &with_shape(
+        r#"
+function area(shape: Shape) {
+  switch (shape.kind) {
+    case "circle":
+    case "square":
+    case "triangle":
+      return 1;
+    default: {
+      const exhaustive: never = shape;
+      throw new Error(exhaustive);
+    }
+  }
+}
+"#,
+      ),
+      // Not a discriminant
+      &with_shape(
+        r#"
+function f(shape: Shape) {
+  switch (shape.name) {
+    case "circel":
+      return 1;
+  }
+}
+"#,
+      ),
+      // Not annotated
+      &with_shape(
+        r#"
+function f(shape) {
+  switch (shape.kind) {
+    case "circel":
+      return 1;
+  }
+}
+"#,
+      ),
+      // Imported union
+      r#"
+import type { Shape } from "./shape.ts";
+function f(shape: Shape) {
+  switch (shape.kind) {
+    case "circel":
+      return 1;
+    case "square":
+      return 2;
+    default:
+      throw new Error("unknown shape");
+  }
+}
+"#,
+      // A member without a literal discriminant
+      r#"
+type Event = { type: "click" } | { type: string };
+function f(event: Event) {
+  switch (event.type) {
+    case "keydown":
+      return 1;
+  }
+}
+"#,
+      // Shadowed binding
+      &with_shape(
+        r#"
+function f(shape: Shape) {
+  return (shape) => {
+    switch (shape.kind) {
+      case "circel":
+        return 1;
+    }
+  };
+}
+"#,
+      ),
+    ]);
+  }
+
+  #[test]
+  fn no_duplicate_union_discriminants_in_switch_typo() {
+    let source = with_shape(
+      r#"function area(shape: Shape) {
+  switch (shape.kind) {
+    case "circel":
+      return 1;
+    case "square":
+      return 2;
+  }
+}
+"#,
+    );
+    let diagnostics =
+      lint(NoDuplicateUnionDiscriminantsInSwitch::new(), &source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 14);
+    assert_eq!(diagnostics[0].range.start.col, 9);
+    assert_eq!(
+      diagnostics[0].message,
+      "\"circel\" is not a `kind` of `Shape`, so this case never matches"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Did you mean \"circle\"?")
+    );
+
+    let source = with_shape(
+      r#"function area(shape: Shape) {
+  switch (shape.kind) {
+    case "hexagon":
+      return 1;
+  }
+}
+"#,
+    );
+    let diagnostics =
+      lint(NoDuplicateUnionDiscriminantsInSwitch::new(), &source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some(
+        "The `kind` of `Shape` is one of \"circle\", \"square\", \"triangle\""
+      )
+    );
+  }
+
+  #[test]
+  fn no_duplicate_union_discriminants_in_switch_unreachable_default() {
+    let source = with_shape(
+      r#"function area(shape: Shape) {
+  switch (shape.kind) {
+    case "circle":
+      return 1;
+    case "square":
+    case "triangle":
+      return 2;
+    default:
+      throw new Error("unknown shape");
+  }
+}
+"#,
+    );
+    let diagnostics =
+      lint(NoDuplicateUnionDiscriminantsInSwitch::new(), &source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 19);
+    assert_eq!(diagnostics[0].range.start.col, 4);
+    assert_eq!(
+      diagnostics[0].message,
+      "This `default` clause is unreachable, because the cases cover every `kind` of `Shape`"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Check exhaustiveness instead, so adding a member to `Shape` fails to type-check: `const exhaustive: never = shape;`")
+    );
+  }
+
+  #[test]
+  fn no_duplicate_union_discriminants_in_switch_options() {
+    let source = r#"
+export type Action =
+  | { action: "add"; value: number }
+  | { action: "remove" };
+function reduce(a: Action) {
+  switch (a["action"]) {
+    case "ad":
+      return 1;
+  }
+}
+"#;
+    assert_lint_ok::<NoDuplicateUnionDiscriminantsInSwitch>(source);
+    assert_lint_err_on_line_n_with(
+      NoDuplicateUnionDiscriminantsInSwitch::with_options(
+        NoDuplicateUnionDiscriminantsInSwitchOptions {
+          discriminants: vec!["action".to_string()],
+        },
+      ),
+      source,
+      vec![(7, 9)],
+    );
+  }
+}
@@ -1,8 +1,10 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule};
+use crate::swc_util::keyword_span;
+use swc_common::{BytePos, Spanned};
 use swc_ecmascript::ast::{
-  ArrowExpr, BlockStmt, BlockStmtOrExpr, Constructor, Function, Module,
-  SwitchStmt,
+  BlockStmt, CatchClause, DoWhileStmt, ForInStmt, ForOfStmt, ForStmt, IfStmt,
+  Module, ModuleItem, Stmt, SwitchStmt, TryStmt, WhileStmt,
 };
 use swc_ecmascript::visit::{noop_visit_type, Node, Visit};
 
@@ -19,6 +21,10 @@ impl LintRule for NoEmpty {
     "no-empty"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = NoEmptyVisitor::new(context);
     visitor.visit_module(module, module);
@@ -88,79 +94,126 @@ impl NoEmptyVisitor {
   fn new(context: Arc<Context>) -> Self {
     Self { context }
   }
+
+  fn check_block(&self, block_stmt: &BlockStmt, construct: &str) {
+    if block_stmt.stmts.is_empty()
+      && self.context.comments_in(block_stmt.span).next().is_none()
+    {
+      self.context.add_diagnostic_with_hint(
+        keyword_span(block_stmt.span, "{"),
+        "no-empty",
+        &format!("Empty {}", construct),
+        "Add code or a comment explaining why it is empty",
+      );
+    }
+  }
+
+  fn check_body(&self, body: &Stmt, construct: &str) {
+    if let Stmt::Block(block_stmt) = body {
+      self.check_block(block_stmt, construct);
+    }
+  }
 }
 
 impl Visit for NoEmptyVisitor {
   noop_visit_type!();
 
-  fn visit_function(&mut self, function: &Function, _parent: &dyn Node) {
-    // Empty functions shouldn't be caught by this rule.
-    // Because function's body is a block statement, we're gonna
-    // manually visit each member; otherwise rule would produce errors
-    // for empty function body.
-    if let Some(body) = &function.body {
-      for stmt in &body.stmts {
-        swc_ecmascript::visit::visit_stmt(self, stmt, body);
+  // Function and constructor bodies are only visited through their
+  // statements, since empty functions are covered by `no-empty-function`.
+
+  fn visit_module_items(&mut self, items: &[ModuleItem], parent: &dyn Node) {
+    for item in items {
+      if let ModuleItem::Stmt(stmt) = item {
+        self.check_body(stmt, "block statement");
       }
     }
+    swc_ecmascript::visit::visit_module_items(self, items, parent);
   }
 
-  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _parent: &dyn Node) {
-    // Similar to the above, empty arrow expressions shouldn't be caught.
-    if let BlockStmtOrExpr::BlockStmt(block_stmt) = &arrow_expr.body {
-      for stmt in &block_stmt.stmts {
-        swc_ecmascript::visit::visit_stmt(self, stmt, block_stmt);
-      }
+  fn visit_stmts(&mut self, stmts: &[Stmt], parent: &dyn Node) {
+    for stmt in stmts {
+      self.check_body(stmt, "block statement");
     }
+    swc_ecmascript::visit::visit_stmts(self, stmts, parent);
   }
 
-  fn visit_constructor(&mut self, cons: &Constructor, _parent: &dyn Node) {
-    // Similar to the above, empty constructors shouldn't be caught.
-    if let Some(body) = &cons.body {
-      for stmt in &body.stmts {
-        swc_ecmascript::visit::visit_stmt(self, stmt, body);
-      }
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, parent: &dyn Node) {
+    self.check_body(&if_stmt.cons, "if block");
+    if let Some(alt) = &if_stmt.alt {
+      self.check_body(alt, "else block");
     }
+    swc_ecmascript::visit::visit_if_stmt(self, if_stmt, parent);
   }
 
-  fn visit_block_stmt(&mut self, block_stmt: &BlockStmt, _parent: &dyn Node) {
-    if block_stmt.stmts.is_empty() {
-      if !block_stmt.contains_comments(&self.context) {
-        self.context.add_diagnostic(
-          block_stmt.span,
-          "no-empty",
-          "Empty block statement",
-        );
-      }
-    } else {
-      for stmt in &block_stmt.stmts {
-        self.visit_stmt(stmt, _parent);
-      }
+  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, parent: &dyn Node) {
+    self.check_body(&while_stmt.body, "while block");
+    swc_ecmascript::visit::visit_while_stmt(self, while_stmt, parent);
+  }
+
+  fn visit_do_while_stmt(
+    &mut self,
+    do_while_stmt: &DoWhileStmt,
+    parent: &dyn Node,
+  ) {
+    self.check_body(&do_while_stmt.body, "do-while block");
+    swc_ecmascript::visit::visit_do_while_stmt(self, do_while_stmt, parent);
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
+    self.check_body(&for_stmt.body, "for block");
+    swc_ecmascript::visit::visit_for_stmt(self, for_stmt, parent);
+  }
+
+  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, parent: &dyn Node) {
+    self.check_body(&for_in_stmt.body, "for-in block");
+    swc_ecmascript::visit::visit_for_in_stmt(self, for_in_stmt, parent);
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, parent: &dyn Node) {
+    self.check_body(&for_of_stmt.body, "for-of block");
+    swc_ecmascript::visit::visit_for_of_stmt(self, for_of_stmt, parent);
+  }
+
+  fn visit_try_stmt(&mut self, try_stmt: &TryStmt, parent: &dyn Node) {
+    self.check_block(&try_stmt.block, "try block");
+    if let Some(finalizer) = &try_stmt.finalizer {
+      self.check_block(finalizer, "finally block");
     }
+    swc_ecmascript::visit::visit_try_stmt(self, try_stmt, parent);
+  }
+
+  fn visit_catch_clause(
+    &mut self,
+    catch_clause: &CatchClause,
+    parent: &dyn Node,
+  ) {
+    self.check_block(&catch_clause.body, "catch block");
+    swc_ecmascript::visit::visit_catch_clause(self, catch_clause, parent);
   }
 
-  fn visit_switch_stmt(&mut self, switch: &SwitchStmt, _parent: &dyn Node) {
-    if switch.cases.is_empty() {
-      self.context.add_diagnostic(
-        switch.span,
+  fn visit_switch_stmt(&mut self, switch: &SwitchStmt, parent: &dyn Node) {
+    if switch.cases.is_empty()
+      && self.context.comments_in(switch.span).next().is_none()
+    {
+      // The opening brace is the first one after the discriminant
+      let after_discriminant =
+        switch.span.with_lo(switch.discriminant.span().hi);
+      let brace = self.context.span_text(after_discriminant).find('{');
+      let span = match brace {
+        Some(offset) => {
+          let lo = after_discriminant.lo + BytePos(offset as u32);
+          after_discriminant.with_lo(lo).with_hi(lo + BytePos(1))
+        }
+        None => switch.span,
+      };
+      self.context.add_diagnostic_with_hint(
+        span,
         "no-empty",
         "Empty switch statement",
+        "Add cases or a comment explaining why it is empty",
       );
     }
-  }
-}
-
-trait ContainsComments {
-  fn contains_comments(&self, context: &Context) -> bool;
-}
-
-impl ContainsComments for BlockStmt {
-  fn contains_comments(&self, context: &Context) -> bool {
-    context
-      .leading_comments
-      .values()
-      .flatten()
-      .any(|comment| self.span.contains(comment.span))
+    swc_ecmascript::visit::visit_switch_stmt(self, switch, parent);
   }
 }
 
@@ -251,7 +304,7 @@ if (foo) { }
 
   #[test]
   fn it_fails_for_an_empty_switch_block() {
-    assert_lint_err::<NoEmpty>("switch (foo) { }", 0);
+    assert_lint_err::<NoEmpty>("switch (foo) { }", 13);
   }
 
   #[test]
@@ -299,6 +352,91 @@ if (foo) { }
 
   #[test]
   fn it_fails_for_a_nested_empty_switch() {
-    assert_lint_err::<NoEmpty>("if (foo) { switch (foo) { } }", 11);
+    assert_lint_err::<NoEmpty>("if (foo) { switch (foo) { } }", 24);
+  }
+
+  #[test]
+  fn it_allows_blocks_with_only_comments() {
+    assert_lint_ok_n::<NoEmpty>(vec![
+      "try {\n  foo();\n} catch (err) {\n  // intentionally ignored\n}",
+      "try {\n  foo();\n} catch {\n  /* intentionally ignored */\n}",
+      "try {\n  foo();\n} finally { /* nothing to clean up */ }",
+      "while (foo()) {\n  // busy wait\n}",
+      "if (foo) {\n  // empty\n} else {\n  // empty\n}",
+      "switch (foo) {\n  // no cases yet\n}",
+      "{\n  // empty\n}",
+    ]);
+  }
+
+  #[test]
+  fn it_reports_at_the_opening_brace_per_construct() {
+    let diagnostics = lint(NoEmpty::new(), "try {\n  foo();\n} catch (err) {}");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "Empty catch block");
+    assert_eq!(diagnostics[0].range.start.line, 3);
+    assert_eq!(diagnostics[0].range.start.col, 14);
+    assert_eq!(diagnostics[0].range.end.col, 15);
+
+    let messages = |src: &str| {
+      lint(NoEmpty::new(), src)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(
+      messages("if (a) {} else {}"),
+      vec!["Empty if block", "Empty else block"]
+    );
+    assert_eq!(
+      messages("try {} finally {}"),
+      vec!["Empty try block", "Empty finally block"]
+    );
+    assert_eq!(messages("do {} while (a);"), vec!["Empty do-while block"]);
+    assert_eq!(
+      messages("for (const a of b) {}"),
+      vec!["Empty for-of block"]
+    );
+    assert_eq!(messages("switch (a) {}"), vec!["Empty switch statement"]);
+    assert_eq!(messages("{}"), vec!["Empty block statement"]);
+  }
+
+  #[test]
+  fn it_ignores_empty_function_bodies() {
+    assert_lint_ok_n::<NoEmpty>(vec![
+      "const foo = function () {};",
+      "const foo = () => {};",
+      "class Foo { bar() {} }",
+      "class Foo { constructor() {} }",
+      "const foo = { get bar() { return 1; }, set bar(value) {} };",
+    ]);
+    assert_lint_err::<NoEmpty>("function foo() { if (a) {} }", 24);
+    assert_lint_err::<NoEmpty>("const foo = () => { {} };", 20);
+  }
+
+  #[test]
+  fn it_fails_for_deeply_nested_empty_blocks() {
+    assert_lint_err_on_line_n::<NoEmpty>(
+      r#"
+function foo() {
+  for (const a of b) {
+    try {
+      if (a) {
+        while (c) {}
+      }
+    } catch (err) {
+      // ignored
+    }
+  }
+  return () => {
+    switch (d) {}
+  };
+}
+"#,
+      vec![(6, 18), (13, 15)],
+    );
+    assert_lint_err::<NoEmpty>(
+      "if (a) { if (b) { if (c) { if (d) {} } } }",
+      34,
+    );
   }
 }
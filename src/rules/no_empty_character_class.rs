@@ -19,6 +19,30 @@ impl LintRule for NoEmptyCharacterClass {
     "no-empty-character-class"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows empty character classes, `[]`, in regular expressions.
+
+An empty character class matches nothing, so the whole regular expression can never match.
+
+### Valid:
+```typescript
+const foo = /^abc[a-zA-Z]/;
+const bar = /[\[]/;
+```
+
+### Invalid:
+```typescript
+const foo = /^abc[]/;
+```
+```typescript
+const foo = /foo[]bar/;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -39,35 +63,37 @@ impl NoEmptyCharacterClassVisitor {
   }
 }
 
+/// Returns the byte offset of the first character class in `pattern`
+/// that can't match anything, ie. `[]`.
+///
+/// `[^]` matches any character and is fine, escaped brackets like `\[`
+/// and `[\]]` don't open or close a class.
+fn find_empty_character_class(pattern: &str) -> Option<usize> {
+  let mut chars = pattern.char_indices().peekable();
+  let mut in_class = false;
+  while let Some((offset, ch)) = chars.next() {
+    match ch {
+      '\\' => {
+        chars.next();
+      }
+      '[' if !in_class => {
+        if let Some((_, ']')) = chars.peek() {
+          return Some(offset);
+        }
+        in_class = true;
+      }
+      ']' if in_class => in_class = false,
+      _ => {}
+    }
+  }
+  None
+}
+
 impl Visit for NoEmptyCharacterClassVisitor {
   noop_visit_type!();
 
   fn visit_regex(&mut self, regex: &Regex, _parent: &dyn Node) {
-    let raw_regex = self
-      .context
-      .source_map
-      .span_to_snippet(regex.span)
-      .expect("error in loading snippet");
-
-    lazy_static! {
-      /* reference : [eslint no-empty-character-class](https://github.com/eslint/eslint/blob/master/lib/rules/no-empty-character-class.js#L13)
-      * plain-English description of the following regexp:
-      * 0. `^` fix the match at the beginning of the string
-      * 1. `\/`: the `/` that begins the regexp
-      * 2. `([^\\[]|\\.|\[([^\\\]]|\\.)+\])*`: regexp contents; 0 or more of the following
-      * 2.0. `[^\\[]`: any character that's not a `\` or a `[` (anything but escape sequences and character classes)
-      * 2.1. `\\.`: an escape sequence
-      * 2.2. `\[([^\\\]]|\\.)+\]`: a character class that isn't empty
-      * 3. `\/` the `/` that ends the regexp
-      * 4. `[gimuy]*`: optional regexp flags
-      * 5. `$`: fix the match at the end of the string
-      */
-      static ref RULE_REGEX: regex::Regex = regex::Regex::new(
-        r"(?u)^/([^\\\[]|\\.|\[([^\\\]]|\\.)+\])*/[gimuys]*$"
-      )
-      .unwrap();
-    }
-    if !RULE_REGEX.is_match(&raw_regex) {
+    if find_empty_character_class(&regex.exp).is_some() {
       self.context.add_diagnostic(
         regex.span,
         "no-empty-character-class",
@@ -115,6 +141,17 @@ mod tests {
     );
   }
 
+  #[test]
+  fn find_empty_character_class_offsets() {
+    assert_eq!(find_empty_character_class("[]"), Some(0));
+    assert_eq!(find_empty_character_class("a[b][]"), Some(4));
+    assert_eq!(find_empty_character_class("\\[[]"), Some(2));
+    assert_eq!(find_empty_character_class("[\\]]"), None);
+    assert_eq!(find_empty_character_class("[^]"), None);
+    assert_eq!(find_empty_character_class("[[]]"), None);
+    assert_eq!(find_empty_character_class("\\[]"), None);
+  }
+
   #[test]
   fn no_empty_character_class_valid() {
     assert_lint_ok::<NoEmptyCharacterClass>(
@@ -130,6 +167,8 @@ mod tests {
     const foo = /[\-\[\]\/\{\}\(\)\*\+\?\.\\^\$\|]/g;
     const foo = /\[/g;
     const foo = /\]/i;
+    const foo = /[^]/;
+    const foo = /[\]]/u;
     "#,
     );
   }
@@ -18,11 +18,42 @@ impl LintRule for NoEmptyInterface {
     "no-empty-interface"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows interfaces without members.
+
+An empty interface is equivalent to `{}`, and an interface extending a single other interface without adding members is equivalent to its supertype; use a type alias instead.
+
+### Valid:
+```typescript
+interface Foo {
+  a: string;
+}
+```
+```typescript
+interface Foo extends Bar, Baz {}
+```
+
+### Invalid:
+```typescript
+interface Foo {}
+```
+```typescript
+interface Foo extends Bar {}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     module: &swc_ecmascript::ast::Module,
   ) {
+    if !context.media_type.is_typescript() {
+      return;
+    }
     let mut visitor = NoEmptyInterfaceVisitor::new(context);
     visitor.visit_module(module, module);
   }
@@ -42,20 +73,42 @@ impl Visit for NoEmptyInterfaceVisitor {
   fn visit_ts_interface_decl(
     &mut self,
     interface_decl: &TsInterfaceDecl,
-    _parent: &dyn Node,
+    parent: &dyn Node,
   ) {
-    if interface_decl.extends.len() <= 1 && interface_decl.body.body.is_empty()
-    {
-      self.context.add_diagnostic(
-        interface_decl.span,
-        "no-empty-interface",
-        if interface_decl.extends.is_empty() {
-          "An empty interface is equivalent to `{}`."
-        } else {
-          "An interface declaring no members is equivalent to its supertype."
-        },
-      );
+    if interface_decl.body.body.is_empty() {
+      match interface_decl.extends.as_slice() {
+        [] => self.context.add_diagnostic_with_hint(
+          interface_decl.span,
+          "no-empty-interface",
+          "An empty interface is equivalent to `{}`.",
+          "Add members to the interface, or use `{}` or `object` instead",
+        ),
+        [supertype] => {
+          let type_params = match &interface_decl.type_params {
+            Some(type_params) => self.context.span_text(type_params.span),
+            None => String::new(),
+          };
+          self.context.add_diagnostic_with_hint(
+            interface_decl.span,
+            "no-empty-interface",
+            "An interface declaring no members is equivalent to its supertype.",
+            &format!(
+              "Use a type alias instead, ie. `type {}{} = {};`",
+              interface_decl.id.sym,
+              type_params,
+              self.context.span_text(supertype.span)
+            ),
+          )
+        }
+        // An interface extending several types combines them
+        _ => {}
+      }
     }
+    swc_ecmascript::visit::visit_ts_interface_decl(
+      self,
+      interface_decl,
+      parent,
+    );
   }
 }
 
@@ -131,4 +184,37 @@ declare module FooBar {
       9,
     );
   }
+
+  #[test]
+  fn no_empty_interface_hints() {
+    let diagnostics = lint(NoEmptyInterface::new(), "interface Foo {}");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Add members to the interface, or use `{}` or `object` instead")
+    );
+
+    let diagnostics = lint(
+      NoEmptyInterface::new(),
+      "interface Foo<T> extends Array<T> {}",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use a type alias instead, ie. `type Foo<T> = Array<T>;`")
+    );
+  }
+
+  #[test]
+  fn no_empty_interface_multiple_supertypes() {
+    assert_lint_ok::<NoEmptyInterface>(
+      "interface Foo { a: string }\ninterface Bar { b: number }\ninterface Baz extends Foo, Bar {}",
+    );
+    assert_lint_ok::<NoEmptyInterface>(
+      "interface Foo extends Array<number>, Iterable<string> {}",
+    );
+    assert!(
+      lint_as(NoEmptyInterface::new(), "foo.js", "const a = 1;").is_empty()
+    );
+  }
 }
@@ -19,6 +19,30 @@ impl LintRule for NoEmptyPattern {
     "no-empty-pattern"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows empty destructuring patterns.
+
+An empty pattern like `const {} = foo` binds nothing, and `const { a: {} } = foo` is usually a typo of a default value, `const { a = {} } = foo`.
+
+### Valid:
+```typescript
+const { a = {} } = foo;
+const [b] = bar;
+```
+
+### Invalid:
+```typescript
+const {} = foo;
+```
+```typescript
+const { a: {} } = foo;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -107,8 +131,13 @@ mod tests {
       "function foo({a = {}}) {}",
       "function foo({a = []}) {}",
       "var [a] = foo",
-      "async function startFileServerAsLibrary({}: FileServerCfg = {}): Promise<void>",
     ]);
+    // An overload signature can't have a parameter initializer
+    with_syntax_errors(|| {
+      assert_lint_ok::<NoEmptyPattern>(
+        "async function startFileServerAsLibrary({}: FileServerCfg = {}): Promise<void>",
+      );
+    });
   }
 
   #[test]
@@ -21,6 +21,25 @@ impl LintRule for NoEval {
     "no-eval"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Disallows calling `eval`.
+
+`eval` runs arbitrary code, which is slow, hard to reason about and a security risk when the code comes from outside.
+
+### Valid:
+```typescript
+const value = JSON.parse(text);
+```
+
+### Invalid:
+```typescript
+eval("123");
+```
+```typescript
+const value = eval(text);
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -19,6 +19,36 @@ impl LintRule for NoExAssign {
     "no-ex-assign"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows reassigning the exception parameter of a `catch` clause.
+
+The original error is lost after the reassignment, which makes debugging harder.
+
+### Valid:
+```typescript
+try {
+  foo();
+} catch (e) {
+  const error = new Error("failed", { cause: e });
+  throw error;
+}
+```
+
+### Invalid:
+```typescript
+try {
+  foo();
+} catch (e) {
+  e = new Error("failed");
+  throw e;
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -46,7 +76,7 @@ impl Visit for NoExAssignVisitor {
     let ids = find_lhs_ids(&assign_expr.left);
 
     for id in ids {
-      let var = self.context.scope.var(&id);
+      let var = self.context.scope_manager.binding(&id);
 
       if let Some(var) = var {
         if let BindingKind::CatchClause = var.kind() {
@@ -66,17 +96,21 @@ mod tests {
   use super::*;
   use crate::test_util::assert_lint_err_on_line_n;
   use crate::test_util::assert_lint_ok;
+  use crate::test_util::with_syntax_errors;
 
   #[test]
   fn no_ex_assign_valid() {
-    assert_lint_ok::<NoExAssign>(
-      r#"
+    // Parsed as a module, so the top-level `return` is recovered from
+    with_syntax_errors(|| {
+      assert_lint_ok::<NoExAssign>(
+        r#"
 try {} catch { e = 1; }
 try {} catch (ex) { something = 1; }
 try {} catch (ex) { return 1; }
 function foo() { try { } catch (e) { return false; } }
       "#,
-    );
+      );
+    });
   }
 
   #[test]
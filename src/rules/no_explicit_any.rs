@@ -1,56 +1,158 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::ast::TsKeywordType;
+use swc_ecmascript::ast::{
+  RestPat, TsEntityName, TsKeywordType, TsKeywordTypeKind, TsType,
+  TsTypeOperatorOp,
+};
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 
 use std::sync::Arc;
 
-pub struct NoExplicitAny;
+#[derive(Clone, Copy, Default)]
+pub struct NoExplicitAnyOptions {
+  /// Allows `any` as the type of rest parameters, as in
+  /// `...args: any[]`, which is common in wrappers forwarding their
+  /// arguments.
+  pub ignore_rest_args: bool,
+}
+
+pub struct NoExplicitAny {
+  options: NoExplicitAnyOptions,
+}
+
+impl NoExplicitAny {
+  pub fn with_options(options: NoExplicitAnyOptions) -> Box<Self> {
+    Box::new(NoExplicitAny { options })
+  }
+}
 
 impl LintRule for NoExplicitAny {
   fn new() -> Box<Self> {
-    Box::new(NoExplicitAny)
+    Self::with_options(NoExplicitAnyOptions::default())
   }
 
   fn code(&self) -> &'static str {
     "no-explicit-any"
   }
 
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows the `any` type.
+
+`any` turns off type checking for everything it touches; `unknown` is the type-safe alternative for values whose type isn't known. JavaScript files are not checked.
+
+### Options
+
+- `NoExplicitAnyOptions::ignore_rest_args`: allows `any` as the type of rest parameters, like `...args: any[]`, `false` by default.
+
+### Valid:
+```typescript
+function foo(): unknown {
+  return undefined;
+}
+const a: Record<string, unknown> = {};
+```
+
+### Invalid:
+```typescript
+function foo(): any {
+  return undefined;
+}
+```
+```typescript
+const a: any = {};
+```
+```typescript
+const b = JSON.parse("{}") as any;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     module: &swc_ecmascript::ast::Module,
   ) {
-    let mut visitor = NoExplicitAnyVisitor::new(context);
+    // `any` can't be written in JavaScript
+    if !context.media_type.is_typescript() {
+      return;
+    }
+    let mut visitor = NoExplicitAnyVisitor::new(context, self.options);
     visitor.visit_module(module, module);
   }
 }
 
 struct NoExplicitAnyVisitor {
   context: Arc<Context>,
+  options: NoExplicitAnyOptions,
 }
 
 impl NoExplicitAnyVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
+  fn new(context: Arc<Context>, options: NoExplicitAnyOptions) -> Self {
+    Self { context, options }
+  }
+}
+
+/// Whether `ts_type` is `any`, or an array of `any` such as `any[]`,
+/// `Array<any>` or `readonly any[]`.
+fn is_any_or_any_array(ts_type: &TsType) -> bool {
+  match ts_type {
+    TsType::TsKeywordType(keyword_type) => {
+      keyword_type.kind == TsKeywordTypeKind::TsAnyKeyword
+    }
+    TsType::TsArrayType(array_type) => {
+      is_any_or_any_array(&array_type.elem_type)
+    }
+    TsType::TsTypeOperator(type_operator) => {
+      type_operator.op == TsTypeOperatorOp::ReadOnly
+        && is_any_or_any_array(&type_operator.type_ann)
+    }
+    TsType::TsTypeRef(type_ref) => {
+      match (&type_ref.type_name, &type_ref.type_params) {
+        (TsEntityName::Ident(ident), Some(type_params))
+          if (ident.sym == *"Array" || ident.sym == *"ReadonlyArray")
+            && type_params.params.len() == 1 =>
+        {
+          is_any_or_any_array(&type_params.params[0])
+        }
+        _ => false,
+      }
+    }
+    _ => false,
   }
 }
 
 impl Visit for NoExplicitAnyVisitor {
+  fn visit_rest_pat(&mut self, rest_pat: &RestPat, parent: &dyn Node) {
+    if self.options.ignore_rest_args {
+      if let Some(type_ann) = &rest_pat.type_ann {
+        if is_any_or_any_array(&type_ann.type_ann) {
+          self.visit_pat(&rest_pat.arg, rest_pat);
+          return;
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_rest_pat(self, rest_pat, parent);
+  }
+
   fn visit_ts_keyword_type(
     &mut self,
     ts_keyword_type: &TsKeywordType,
     _parent: &dyn Node,
   ) {
-    use swc_ecmascript::ast::TsKeywordTypeKind::*;
-
-    if ts_keyword_type.kind == TsAnyKeyword {
+    if ts_keyword_type.kind == TsKeywordTypeKind::TsAnyKeyword {
       self.context.add_diagnostic(
         ts_keyword_type.span,
         "no-explicit-any",
-        "`any` type is not allowed",
+        "Unexpected any. Specify a different type",
       );
     }
   }
@@ -122,4 +224,86 @@ type RequireWrapper = (
       vec![(3, 11), (4, 11)],
     );
   }
+
+  #[test]
+  fn no_explicit_any_message() {
+    let diagnostics = lint(NoExplicitAny::new(), "let a: Array<any> = [];");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Unexpected any. Specify a different type"
+    );
+    assert_eq!(diagnostics[0].range.start.col, 13);
+    assert_eq!(diagnostics[0].range.end.col, 16);
+  }
+
+  #[test]
+  fn no_explicit_any_positions() {
+    assert_lint_err_on_line_n::<NoExplicitAny>(
+      r#"
+interface Foo {
+  bar: any;
+  baz(qux: any): void;
+  [key: string]: any;
+}"#,
+      vec![(3, 7), (4, 11), (5, 17)],
+    );
+    assert_lint_err_n::<NoExplicitAny>(
+      "function foo(a: any, b: string | any): Map<string, any> { return a; }",
+      vec![16, 33, 51],
+    );
+    assert_lint_err::<NoExplicitAny>(
+      "type Foo<T> = { [K in keyof T]: any };",
+      32,
+    );
+    assert_lint_err::<NoExplicitAny>("const a = b as any;", 15);
+    assert_lint_err::<NoExplicitAny>("const a = <any>b;", 11);
+    assert_lint_err::<NoExplicitAny>("function foo(...args: any[]) {}", 22);
+  }
+
+  #[test]
+  fn no_explicit_any_ignore_rest_args() {
+    let rule = || {
+      NoExplicitAny::with_options(NoExplicitAnyOptions {
+        ignore_rest_args: true,
+      })
+    };
+    assert_lint_ok_with(rule(), "function foo(...args: any[]) {}");
+    assert_lint_ok_with(rule(), "function foo(a: string, ...args: any) {}");
+    assert_lint_ok_with(rule(), "const foo = (...args: Array<any>) => {};");
+    assert_lint_ok_with(
+      rule(),
+      "type Foo = (...args: readonly any[]) => void;",
+    );
+    assert_lint_ok_with(
+      rule(),
+      "interface Foo { bar(...args: ReadonlyArray<any>): void; }",
+    );
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "function foo(a: any, ...args: any[]): any {}",
+      vec![(1, 16), (1, 38)],
+    );
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "function foo(...args: Map<string, any>[]) {}",
+      vec![(1, 34)],
+    );
+  }
+
+  #[test]
+  fn no_explicit_any_javascript() {
+    assert!(lint_as(NoExplicitAny::new(), "mod.js", "const a = 1;").is_empty());
+    assert!(
+      lint_as(NoExplicitAny::new(), "mod.jsx", "const a = <div />;").is_empty()
+    );
+    assert_eq!(
+      lint_as(NoExplicitAny::new(), "mod.tsx", "const a: any = <div />;").len(),
+      1
+    );
+    assert_eq!(
+      lint_as(NoExplicitAny::new(), "mod.d.ts", "declare const a: any;").len(),
+      1
+    );
+  }
 }
@@ -0,0 +1,342 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::scopes::{
+  BindingKind, ReferenceKind, ScopeId, ScopeKind, ScopeManager,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  Decl, DefaultDecl, ExportSpecifier, Expr, Ident, Module, ModuleDecl,
+  ModuleItem, Pat, Stmt, VarDecl,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+
+declare_rule! {
+  /// Reports reads of module level `let` and `var` bindings from inside of
+  /// exported functions, if those bindings are reassigned from within any
+  /// function.
+  ///
+  /// Lazily initialized caches (`let cache; export function get() { ... }`)
+  /// are reported as well, which is intended - such a capture should be
+  /// reviewed and, if it's fine, suppressed with `// deno-lint-ignore`.
+  NoExternalMutableStateCapture, "no-external-mutable-state-capture", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows exported functions reading module level `let` and `var` bindings which are reassigned from inside of a function.
+
+The result of such a function depends on hidden state which changes between calls. Lazily initialized caches are reported as well; review them and suppress the diagnostic if the capture is intended.
+
+### Valid:
+```typescript
+const counter = { value: 0 };
+export function next() {
+  return counter.value;
+}
+```
+
+### Invalid:
+```typescript
+let counter = 0;
+setInterval(() => {
+  counter += 1;
+}, 1000);
+export function current() {
+  return counter;
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let exported_functions = collect_exported_functions(module);
+    if exported_functions.is_empty() {
+      return;
+    }
+
+    let scope_manager = &context.scope_manager;
+    for (id, binding) in scope_manager.bindings() {
+      if binding.scope() != ScopeManager::MODULE
+        || !matches!(binding.kind(), BindingKind::Let | BindingKind::Var)
+      {
+        continue;
+      }
+      let write_in_function =
+        scope_manager.references_of(id).find(|reference| {
+          reference.kind().is_write()
+            && is_in_function(scope_manager, reference.scope())
+        });
+      let write_span = match write_in_function {
+        Some(reference) => reference.span(),
+        None => continue,
+      };
+      // Compound assignments and updates only change the binding
+      let reads = scope_manager.references_of(id).filter(|reference| {
+        reference.kind() == ReferenceKind::Read
+          && exported_functions
+            .iter()
+            .any(|function| function.contains(reference.span()))
+      });
+      for read in reads {
+        report(&context, &id.0, read.span(), write_span);
+      }
+    }
+  }
+}
+
+/// Checks if `scope` is, or is nested in, the scope of a function.
+fn is_in_function(scope_manager: &ScopeManager, scope: ScopeId) -> bool {
+  let mut current = Some(scope);
+  while let Some(scope) = current {
+    let scope = scope_manager.scope(scope);
+    if matches!(scope.kind(), ScopeKind::Function | ScopeKind::Arrow) {
+      return true;
+    }
+    current = scope.parent();
+  }
+  false
+}
+
+fn report(context: &Context, name: &str, read_span: Span, write_span: Span) {
+  context.add_diagnostic_with_related(
+    read_span,
+    "no-external-mutable-state-capture",
+    &format!(
+      "Exported function reads module level `{}`, which is reassigned from inside of a function",
+      name
+    ),
+    "Pass the state explicitly or keep it in a `const` binding",
+    vec![(write_span, format!("`{}` is reassigned here", name))],
+  );
+}
+
+/// Span of the function or arrow function `expr`, if it's one.
+fn fn_from_expr(expr: &Expr) -> Option<Span> {
+  match expr {
+    Expr::Fn(fn_expr) => Some(fn_expr.function.span),
+    Expr::Arrow(arrow) => Some(arrow.span),
+    Expr::Paren(paren) => fn_from_expr(&paren.expr),
+    _ => None,
+  }
+}
+
+/// Returns the spans of the functions exported from the module.
+fn collect_exported_functions(module: &Module) -> Vec<Span> {
+  // Names exported with `export { foo }` declared elsewhere in the module.
+  let mut exported_names = HashSet::new();
+  for item in &module.body {
+    if let ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named)) = item {
+      if named.src.is_some() {
+        continue;
+      }
+      for specifier in &named.specifiers {
+        if let ExportSpecifier::Named(named_specifier) = specifier {
+          exported_names.insert(named_specifier.orig.to_id());
+        }
+      }
+    }
+  }
+
+  let mut functions = vec![];
+  for item in &module.body {
+    match item {
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+        match &export_decl.decl {
+          Decl::Fn(fn_decl) => functions.push(fn_decl.function.span),
+          Decl::Var(var_decl) => {
+            functions.extend(var_decl_functions(var_decl, |_| true))
+          }
+          _ => {}
+        }
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(default_decl)) => {
+        if let DefaultDecl::Fn(fn_expr) = &default_decl.decl {
+          functions.push(fn_expr.function.span);
+        }
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(default_expr)) => {
+        functions.extend(fn_from_expr(&default_expr.expr));
+      }
+      ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl)))
+        if exported_names.contains(&fn_decl.ident.to_id()) =>
+      {
+        functions.push(fn_decl.function.span);
+      }
+      ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => {
+        functions.extend(var_decl_functions(var_decl, |ident| {
+          exported_names.contains(&ident.to_id())
+        }))
+      }
+      _ => {}
+    }
+  }
+
+  functions
+}
+
+/// Functions and arrow functions assigned to declared variables
+/// accepted by `filter`.
+fn var_decl_functions(
+  var_decl: &VarDecl,
+  filter: impl Fn(&Ident) -> bool,
+) -> Vec<Span> {
+  var_decl
+    .decls
+    .iter()
+    .filter_map(|decl| match (&decl.name, &decl.init) {
+      (Pat::Ident(ident), Some(init)) if filter(ident) => fn_from_expr(init),
+      _ => None,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostic::Position;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_external_mutable_state_capture_valid() {
+    assert_lint_ok_n::<NoExternalMutableStateCapture>(vec![
+      r#"
+const counter = { value: 0 };
+export function next() {
+  return counter.value;
+}
+      "#,
+      r#"
+let config = {};
+config = { debug: true };
+export function isDebug() {
+  return config.debug;
+}
+      "#,
+      r#"
+let counter = 0;
+function increment() {
+  counter++;
+}
+function current() {
+  return counter;
+}
+      "#,
+      r#"
+let counter = 0;
+export function increment() {
+  counter++;
+}
+      "#,
+      r#"
+let counter = 0;
+export function read() {
+  let counter = 1;
+  return counter;
+}
+      "#,
+      r#"
+let counter = 0;
+const increment = () => counter++;
+export function read(counter) {
+  if (counter) {
+    const counter = 2;
+    return counter;
+  }
+  return counter;
+}
+      "#,
+    ]);
+  }
+
+  #[test]
+  fn no_external_mutable_state_capture_invalid() {
+    assert_lint_err_on_line::<NoExternalMutableStateCapture>(
+      r#"
+let counter = 0;
+setInterval(() => {
+  counter += 1;
+}, 1000);
+export function current() {
+  return counter;
+}
+      "#,
+      7,
+      9,
+    );
+    assert_lint_err_on_line_n::<NoExternalMutableStateCapture>(
+      r#"
+let counter = 0;
+export async function next() {
+  const value = counter;
+  await Promise.resolve();
+  counter = value + 1;
+  return counter;
+}
+      "#,
+      vec![(4, 16), (7, 9)],
+    );
+    assert_lint_err_on_line::<NoExternalMutableStateCapture>(
+      r#"
+let cache;
+async function load() {
+  cache = await fetch("https://deno.land");
+}
+export const get = () => cache;
+      "#,
+      6,
+      25,
+    );
+    assert_lint_err_on_line::<NoExternalMutableStateCapture>(
+      r#"
+var state = [];
+function reset() {
+  state = [];
+}
+function size() {
+  return state.length;
+}
+export { size };
+      "#,
+      7,
+      9,
+    );
+    assert_lint_err_on_line::<NoExternalMutableStateCapture>(
+      r#"
+let count = 0;
+const inc = () => count++;
+export default function () {
+  return count;
+}
+      "#,
+      5,
+      9,
+    );
+
+    assert_lint_err_on_line::<NoExternalMutableStateCapture>(
+      r#"
+let instance;
+class Service {
+  constructor() {
+    instance = this;
+  }
+}
+export const get = () => instance;
+      "#,
+      8,
+      25,
+    );
+  }
+
+  #[test]
+  fn no_external_mutable_state_capture_related() {
+    let diagnostics = lint(
+      NoExternalMutableStateCapture::new(),
+      "let n = 0;\nfunction inc() { n++; }\nexport const get = () => n;",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    let related = &diagnostics[0].related;
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].range.start, Position { line: 2, col: 17 });
+    assert_eq!(related[0].message, "`n` is reassigned here");
+  }
+}
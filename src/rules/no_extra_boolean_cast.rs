@@ -23,6 +23,30 @@ impl LintRule for NoExtraBooleanCast {
     "no-extra-boolean-cast"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows unnecessary boolean casts.
+
+Conditions of `if`, `while`, `do` and `for` statements, of the conditional operator and operands of `!` are converted to booleans anyway, so `!!foo` and `Boolean(foo)` are redundant there.
+
+### Valid:
+```typescript
+if (foo) {}
+var bar = !!baz;
+```
+
+### Invalid:
+```typescript
+if (!!foo) {}
+```
+```typescript
+while (Boolean(foo)) {}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -22,6 +22,35 @@ impl LintRule for NoExtraNonNullAssertion {
     "no-extra-non-null-assertion"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows unnecessary non-null assertions.
+
+A second non-null assertion, or one before optional chaining, like `foo!!` or `foo!?.bar`, has no effect.
+
+### Valid:
+```typescript
+function foo(bar: undefined | string) {
+  return bar!;
+}
+```
+
+### Invalid:
+```typescript
+function foo(bar: undefined | string) {
+  return bar!!;
+}
+```
+```typescript
+function foo(bar?: { str: string }) {
+  return bar!?.str;
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -22,6 +22,30 @@ impl LintRule for NoExtraSemi {
     "no-extra-semi"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows unnecessary semicolons.
+
+Semicolons after function declarations, blocks and other semicolons are empty statements which only add noise.
+
+### Valid:
+```typescript
+var x = 5;
+function foo() {}
+```
+
+### Invalid:
+```typescript
+var x = 5;;
+```
+```typescript
+function foo() {};
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -172,7 +196,10 @@ mod tests {
     assert_lint_ok::<NoExtraSemi>("if(true); else;");
     assert_lint_ok::<NoExtraSemi>("foo: ;");
     assert_lint_ok::<NoExtraSemi>("foo: bar: ;");
-    assert_lint_ok::<NoExtraSemi>("with(foo);");
+    // `with` is a syntax error in modules
+    with_syntax_errors(|| {
+      assert_lint_ok::<NoExtraSemi>("with(foo);");
+    });
     assert_lint_ok::<NoExtraSemi>("class A { }");
     assert_lint_ok::<NoExtraSemi>("var A = class { };");
     assert_lint_ok::<NoExtraSemi>("class A { a() { this; } }");
@@ -193,8 +220,10 @@ mod tests {
     assert_lint_err::<NoExtraSemi>("if(true){} else;;", 16);
     assert_lint_err_n::<NoExtraSemi>("if(true){;} else {;}", vec![9, 18]);
     assert_lint_err::<NoExtraSemi>("foo:;;", 5);
-    assert_lint_err::<NoExtraSemi>("with(foo);;", 10);
-    assert_lint_err::<NoExtraSemi>("with(foo){;}", 10);
+    with_syntax_errors(|| {
+      assert_lint_err::<NoExtraSemi>("with(foo);;", 10);
+      assert_lint_err::<NoExtraSemi>("with(foo){;}", 10);
+    });
     assert_lint_err::<NoExtraSemi>("class A { ; }", 10);
     assert_lint_err::<NoExtraSemi>("class A { /*a*/; }", 15);
     assert_lint_err::<NoExtraSemi>("class A { ; a() {} }", 10);
@@ -235,17 +264,19 @@ do {
       4,
       4,
     );
-    assert_lint_err_on_line::<NoExtraSemi>(
-      r#"
+    with_syntax_errors(|| {
+      assert_lint_err_on_line::<NoExtraSemi>(
+        r#"
 with(a) {
   with(b) {
     ;
   }
 }
 "#,
-      4,
-      4,
-    );
+        4,
+        4,
+      );
+    });
     assert_lint_err_on_line::<NoExtraSemi>(
       r#"
 for (const a of b) {
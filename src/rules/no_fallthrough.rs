@@ -1,6 +1,8 @@
 use super::LintRule;
 use crate::linter::Context;
-use swc_common::{comments::Comment, Spanned, DUMMY_SP};
+use crate::swc_util::keyword_span;
+use regex::Regex;
+use swc_common::Spanned;
 use swc_ecmascript::{
   ast::*,
   visit::{noop_visit_type, Node, Visit, VisitWith},
@@ -19,6 +21,56 @@ impl LintRule for NoFallthrough {
     "no-fallthrough"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows falling through from one `case` clause of a `switch` statement to the next.
+
+A clause which doesn't end with `break`, `return`, `throw` or `continue` also runs the code of the following clause, which is often unintended. Empty clauses sharing the code of the next one are allowed, and a comment like `// falls through` marks an intended fallthrough.
+
+### Valid:
+```typescript
+switch (foo) {
+  case 0:
+    a();
+    break;
+  case 1:
+    b();
+}
+```
+```typescript
+switch (foo) {
+  case 0:
+  case 1:
+    a();
+    break;
+  default:
+    b();
+}
+```
+```typescript
+switch (foo) {
+  case 0:
+    a();
+    // falls through
+  case 1:
+    b();
+}
+```
+
+### Invalid:
+```typescript
+switch (foo) {
+  case 0:
+    a();
+  case 1:
+    b();
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -29,100 +81,85 @@ impl LintRule for NoFallthrough {
   }
 }
 
+lazy_static! {
+  static ref FALLTHROUGH_COMMENT: Regex =
+    Regex::new(r"(?i)falls?\s*through").unwrap();
+}
+
 struct NoFallthroughVisitor {
   context: Arc<Context>,
 }
 
+impl NoFallthroughVisitor {
+  /// Checks if the end of `case` can be reached, ie. it has statements and
+  /// none of them stops the execution with `break`, `return`, `throw` or
+  /// `continue`, including from both branches of an `if` or a nested
+  /// `switch`.
+  fn falls_through(&self, case: &SwitchCase) -> bool {
+    let empty = match case.cons.as_slice() {
+      [] => true,
+      [Stmt::Block(block)] => block.stmts.is_empty(),
+      _ => false,
+    };
+    !empty
+      && !case.cons.iter().any(|stmt| {
+        self
+          .context
+          .control_flow
+          .meta(stmt.span().lo)
+          .map_or(false, |meta| meta.stops_execution())
+      })
+  }
+
+  /// Checks if a comment like `// falls through` ends `case` or precedes
+  /// `next`.
+  fn has_fallthrough_comment(
+    &self,
+    case: &SwitchCase,
+    next: &SwitchCase,
+  ) -> bool {
+    let trailing = case
+      .cons
+      .last()
+      .and_then(|stmt| self.context.trailing_comments.get(&stmt.span().hi));
+    let leading = self.context.leading_comments.get(&next.span.lo);
+    trailing
+      .into_iter()
+      .chain(leading)
+      .flatten()
+      .any(|comment| FALLTHROUGH_COMMENT.is_match(&comment.text))
+  }
+}
+
 impl Visit for NoFallthroughVisitor {
   noop_visit_type!();
 
   fn visit_switch_cases(&mut self, cases: &[SwitchCase], parent: &dyn Node) {
-    let mut should_emit_err = false;
-    let mut prev_span = DUMMY_SP;
-
-    'cases: for (case_idx, case) in cases.iter().enumerate() {
+    for (idx, case) in cases.iter().enumerate() {
       case.visit_with(parent, self);
 
-      if should_emit_err {
-        let mut emit = true;
-        if let Some(comments) = self.context.leading_comments.get(&case.span.lo)
-        {
-          if allow_fall_through(&comments) {
-            emit = false;
-          }
-        }
-        if emit {
-          self.context.add_diagnostic(
-            prev_span,
-            "no-fallthrough",
-            "Fallthrough is not allowed",
-          );
-        }
-      }
-      should_emit_err = true;
-      let mut stops_exec = false;
-
-      // Handle return / throw / break / continue
-      for (idx, stmt) in case.cons.iter().enumerate() {
-        let last = idx + 1 == case.cons.len();
-        let metadata = self.context.control_flow.meta(stmt.span().lo);
-        stops_exec |= metadata.map(|v| v.stops_execution()).unwrap_or(false);
-        if stops_exec {
-          should_emit_err = false;
-        }
-
-        if last {
-          if let Some(comments) =
-            self.context.trailing_comments.get(&stmt.span().hi)
-          {
-            if allow_fall_through(&comments) {
-              should_emit_err = false;
-              // User comment beats everything
-              prev_span = case.span;
-              continue 'cases;
-            }
-          }
-        }
-      }
-
-      let empty = case.cons.is_empty()
-        || match &case.cons[0] {
-          Stmt::Block(b) => b.stmts.is_empty(),
-          _ => false,
-        };
-
-      if case_idx + 1 < cases.len() {
-        // A case is not allowed to fall through to default handler
-        if cases[case_idx + 1].test.is_none() {
-          if empty {
-            should_emit_err = true;
-          }
+      let next = match cases.get(idx + 1) {
+        Some(next) => next,
+        None => continue,
+      };
+      if self.falls_through(case) && !self.has_fallthrough_comment(case, next) {
+        // The `case` or `default` keyword of the clause fallen into
+        let keyword = if next.test.is_some() {
+          "case"
         } else {
-          // Fallthrough
-          if empty {
-            should_emit_err = false;
-          }
-        }
+          "default"
+        };
+        self.context.add_diagnostic_with_hint(
+          keyword_span(next.span, keyword),
+          "no-fallthrough",
+          "Fallthrough is not allowed",
+          "Add `break` to the end of the previous case, or a `// falls through` comment if falling through is intended",
+        );
       }
-
-      prev_span = case.span;
     }
   }
 }
 
-fn allow_fall_through(comments: &[Comment]) -> bool {
-  for comment in comments {
-    let l = comment.text.to_ascii_lowercase();
-    if l.contains("fallthrough")
-      || l.contains("falls through")
-      || l.contains("fall through")
-    {
-      return true;
-    }
-  }
-  false
-}
-
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -276,19 +313,21 @@ mod tests {
 
   #[test]
   fn err_1() {
-    assert_lint_err::<NoFallthrough>(
+    assert_lint_err_on_line::<NoFallthrough>(
       "switch(foo) { case 0: a();\ncase 1: b() }",
-      14,
+      2,
+      0,
     );
 
-    assert_lint_err::<NoFallthrough>(
+    assert_lint_err_on_line::<NoFallthrough>(
       "switch(foo) { case 0: a();\ndefault: b() }",
-      14,
+      2,
+      0,
     );
 
     assert_lint_err::<NoFallthrough>(
       "switch(foo) { case 0: a(); default: b() }",
-      14,
+      27,
     );
   }
 
@@ -296,17 +335,17 @@ mod tests {
   fn err_2() {
     assert_lint_err::<NoFallthrough>(
       "switch(foo) { case 0: if (a) { break; } default: b() }",
-      14,
+      40,
     );
 
     assert_lint_err::<NoFallthrough>(
       "switch(foo) { case 0: try { throw 0; } catch (err) {} default: b() }",
-      14,
+      54,
     );
 
     assert_lint_err::<NoFallthrough>(
       "switch(foo) { case 0: while (a) { break; } default: b() }",
-      14,
+      43,
     );
   }
 
@@ -319,24 +358,83 @@ mod tests {
     );
   }
 
+  #[test]
+  fn ok_13() {
+    assert_lint_ok::<NoFallthrough>("switch(foo) { case 0:\n\n default: b() }");
+
+    assert_lint_ok::<NoFallthrough>(
+      "switch(foo) { case 0:\n // comment\n default: b() }",
+    );
+
+    assert_lint_ok::<NoFallthrough>(
+      "switch(foo) { case 0: case 1: case 2: {} default: b() }",
+    );
+  }
+
+  #[test]
+  fn ok_14() {
+    assert_lint_ok::<NoFallthrough>(
+      "switch(foo) { case 0: a();\n // Fall through\n case 1: b() }",
+    );
+
+    assert_lint_ok::<NoFallthrough>(
+      "switch(foo) { case 0: a(); // fallsthrough\n default: b() }",
+    );
+
+    // A top-level `return` is a syntax error
+    with_syntax_errors(|| {
+      assert_lint_ok::<NoFallthrough>(
+        "switch(foo) { case 0: if (a) { return; } else { break; } case 1: b() }",
+      );
+    });
+  }
+
+  #[test]
+  fn ok_15() {
+    assert_lint_ok::<NoFallthrough>(
+      "function f() { switch(foo) { case 0: switch (bar) { case 1: return; default: throw 0; } case 1: b() } }",
+    );
+
+    assert_lint_ok::<NoFallthrough>(
+      "switch(foo) { case 0: { a(); break; } case 1: b() }",
+    );
+  }
+
   #[test]
   fn err_3() {
-    assert_lint_err::<NoFallthrough>(
-      "switch(foo) { case 0:\n\n default: b() }",
-      14,
+    assert_lint_err_on_line::<NoFallthrough>(
+      "switch(foo) {\n  case 0:\n    if (a) {\n      b();\n      break;\n    }\n  case 1:\n    c();\n}",
+      7,
+      2,
     );
 
     assert_lint_err::<NoFallthrough>(
-      "switch(foo) { case 0:\n // comment\n default: b() }",
-      14,
+      "switch(foo) { case 0: switch (bar) { case 1: break; } case 1: b() }",
+      54,
+    );
+
+    assert_lint_err_n::<NoFallthrough>(
+      "switch(foo) { case 0: a(); case 1: b(); case 2: c(); }",
+      vec![27, 40],
+    );
+  }
+
+  #[test]
+  fn err_5() {
+    let diagnostics = lint(
+      NoFallthrough::new(),
+      "switch(foo) { case 0: a(); default: b() }",
     );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 27);
+    assert_eq!(diagnostics[0].range.end.col, 34);
   }
 
   #[test]
   fn err_4() {
     assert_lint_err::<NoFallthrough>(
       "switch(foo) { case 0: a(); /* falling through */ default: b() }",
-      14,
+      49,
     );
   }
 }
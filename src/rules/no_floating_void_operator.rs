@@ -0,0 +1,288 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::async_functions;
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSuper, ExprStmt, Lit, Module, UnaryExpr, UnaryOp,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoFloatingVoidOperatorMode {
+  /// Calls of async functions whose result is ignored must be marked with
+  /// `void`: `void save();`
+  Require,
+  /// The `void` operator isn't allowed, except for `void 0`.
+  Forbid,
+}
+
+pub struct NoFloatingVoidOperatorOptions {
+  pub mode: NoFloatingVoidOperatorMode,
+}
+
+impl Default for NoFloatingVoidOperatorOptions {
+  fn default() -> Self {
+    Self {
+      mode: NoFloatingVoidOperatorMode::Require,
+    }
+  }
+}
+
+/// Enforces a consistent use of `void` as the marker of promises which are
+/// intentionally not awaited.
+///
+/// In the "require" mode, expression statements calling an async function
+/// declared in the same file have to be awaited, handled with `.catch()`
+/// or wrapped in `void`. In the "forbid" mode, `void` is reported anywhere
+/// except in `void 0`. `void` applied to a literal is reported in both
+/// modes.
+pub struct NoFloatingVoidOperator {
+  options: NoFloatingVoidOperatorOptions,
+}
+
+impl NoFloatingVoidOperator {
+  pub fn with_options(options: NoFloatingVoidOperatorOptions) -> Box<Self> {
+    Box::new(NoFloatingVoidOperator { options })
+  }
+}
+
+impl LintRule for NoFloatingVoidOperator {
+  fn new() -> Box<Self> {
+    Self::with_options(NoFloatingVoidOperatorOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-floating-void-operator"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Enforces a consistent use of `void` as the marker of promises which are intentionally not awaited.
+
+`void` applied to a literal is always reported.
+
+### Options
+
+`NoFloatingVoidOperatorOptions::mode` is one of:
+- `NoFloatingVoidOperatorMode::Require` (default): calls of async functions declared in the same file have to be awaited, handled with `.catch()` or marked with `void`.
+- `NoFloatingVoidOperatorMode::Forbid`: the `void` operator is reported anywhere except in `void 0`.
+
+### Valid:
+```typescript
+async function save() {}
+void save();
+```
+```typescript
+async function save() {}
+save().catch(console.error);
+```
+
+### Invalid:
+```typescript
+async function save() {}
+save();
+```
+```typescript
+void "a";
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoFloatingVoidOperatorVisitor {
+      context,
+      mode: self.options.mode,
+      async_functions: async_functions(module),
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoFloatingVoidOperatorVisitor {
+  context: Arc<Context>,
+  mode: NoFloatingVoidOperatorMode,
+  async_functions: HashSet<Id>,
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn is_void_zero(arg: &Expr) -> bool {
+  matches!(unwrap_paren(arg), Expr::Lit(Lit::Num(num)) if num.value == 0.0)
+}
+
+fn is_literal(arg: &Expr) -> bool {
+  match unwrap_paren(arg) {
+    Expr::Lit(_) => true,
+    Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+    _ => false,
+  }
+}
+
+impl NoFloatingVoidOperatorVisitor {
+  fn is_async_call(&self, call_expr: &CallExpr) -> bool {
+    match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => match unwrap_paren(callee) {
+        Expr::Ident(ident) => self.async_functions.contains(&ident.to_id()),
+        _ => false,
+      },
+      ExprOrSuper::Super(_) => false,
+    }
+  }
+
+  fn check_expr_stmt(&self, expr_stmt: &ExprStmt) {
+    if self.mode != NoFloatingVoidOperatorMode::Require {
+      return;
+    }
+    match unwrap_paren(&expr_stmt.expr) {
+      Expr::Call(call_expr) if self.is_async_call(call_expr) => {
+        self.context.add_diagnostic_with_hint(
+          expr_stmt.span,
+          "no-floating-void-operator",
+          "The promise returned by this async function call is neither awaited nor marked as intentionally ignored",
+          "Add `await`, handle rejections with `.catch()`, or mark it with `void` if it's intentionally not awaited",
+        );
+      }
+      _ => {}
+    }
+  }
+
+  fn check_void(&self, unary_expr: &UnaryExpr) {
+    if unary_expr.op != UnaryOp::Void || is_void_zero(&unary_expr.arg) {
+      return;
+    }
+    let literal = is_literal(&unary_expr.arg);
+    let (message, hint) = match self.mode {
+      NoFloatingVoidOperatorMode::Require if literal => (
+        "`void` marks promises which are intentionally not awaited, but this one is applied to a literal",
+        "Remove the `void`, or use `undefined` if that's the intended value",
+      ),
+      NoFloatingVoidOperatorMode::Require => return,
+      NoFloatingVoidOperatorMode::Forbid if literal => (
+        "The `void` operator is not allowed",
+        "Use `undefined` instead",
+      ),
+      NoFloatingVoidOperatorMode::Forbid => (
+        "The `void` operator is not allowed",
+        "Await the promise or handle its rejections with `.catch()` instead of ignoring it with `void`",
+      ),
+    };
+    self.context.add_diagnostic_with_hint(
+      unary_expr.span,
+      "no-floating-void-operator",
+      message,
+      hint,
+    );
+  }
+}
+
+impl Visit for NoFloatingVoidOperatorVisitor {
+  noop_visit_type!();
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt, parent: &dyn Node) {
+    self.check_expr_stmt(expr_stmt);
+    swc_ecmascript::visit::visit_expr_stmt(self, expr_stmt, parent);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, parent: &dyn Node) {
+    self.check_void(unary_expr);
+    swc_ecmascript::visit::visit_unary_expr(self, unary_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn forbid() -> Box<NoFloatingVoidOperator> {
+    NoFloatingVoidOperator::with_options(NoFloatingVoidOperatorOptions {
+      mode: NoFloatingVoidOperatorMode::Forbid,
+    })
+  }
+
+  #[test]
+  fn no_floating_void_operator_require_valid() {
+    assert_lint_ok_n::<NoFloatingVoidOperator>(vec![
+      "async function save() {}\nvoid save();",
+      "async function save() {}\nasync function f() { await save(); }",
+      "async function save() {}\nsave().catch(console.error);",
+      "async function save() {}\nconst p = save();",
+      "const save = async () => {};\nvoid save();",
+      "function save() {}\nsave();",
+      // Not known to be async.
+      "save();",
+      "let save = async () => {};\nsave = () => {};\nsave();",
+      "void 0;",
+      "const x = void 0;",
+      "void fetch(url);",
+      "async function f() {}\nfunction g(f) { f(); }",
+    ]);
+  }
+
+  #[test]
+  fn no_floating_void_operator_require_invalid() {
+    assert_lint_err_on_line::<NoFloatingVoidOperator>(
+      "async function save() {}\nsave();",
+      2,
+      0,
+    );
+    assert_lint_err_on_line::<NoFloatingVoidOperator>(
+      "const save = async () => {};\nfunction f() {\n  (save)(1);\n}",
+      3,
+      2,
+    );
+    assert_lint_err_on_line::<NoFloatingVoidOperator>(
+      "const save = async function () {};\nsave();",
+      2,
+      0,
+    );
+
+    let diagnostics = lint(NoFloatingVoidOperator::new(), "void \"ignored\";");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "`void` marks promises which are intentionally not awaited, but this one is applied to a literal"
+    );
+  }
+
+  #[test]
+  fn no_floating_void_operator_forbid_valid() {
+    assert_lint_ok_with(forbid(), "void 0;");
+    assert_lint_ok_with(forbid(), "const x = void (0);");
+    assert_lint_ok_with(forbid(), "async function save() {}\nsave();");
+    assert_lint_ok_with(
+      forbid(),
+      "async function save() {}\nsave().catch(console.error);",
+    );
+  }
+
+  #[test]
+  fn no_floating_void_operator_forbid_invalid() {
+    assert_lint_err_on_line_n_with(
+      forbid(),
+      "async function save() {}\nvoid save();\nconst x = void f();",
+      vec![(2, 0), (3, 10)],
+    );
+
+    let diagnostics = lint(forbid(), "void \"ignored\";");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "The `void` operator is not allowed");
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use `undefined` instead")
+    );
+  }
+}
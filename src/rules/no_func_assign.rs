@@ -20,6 +20,28 @@ impl LintRule for NoFuncAssign {
     "no-func-assign"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows reassigning function declarations.
+
+A function declaration creates a binding which can be reassigned, which is almost always a mistake.
+
+### Valid:
+```typescript
+let a = function foo() {};
+a = "bar";
+```
+
+### Invalid:
+```typescript
+function foo() {}
+foo = "bar";
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -47,7 +69,7 @@ impl Visit for NoFuncAssignVisitor {
     let ids = find_lhs_ids(&assign_expr.left);
 
     for id in ids {
-      let var = self.context.scope.var(&id);
+      let var = self.context.scope_manager.binding(&id);
       if let Some(var) = var {
         if let BindingKind::Function = var.kind() {
           self.context.add_diagnostic(
@@ -22,6 +22,30 @@ impl LintRule for NoGlobalAssign {
     "no-global-assign"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows assigning to built-in global objects like `String`, `Object` or `Array`.
+
+Replacing built-ins changes the behavior of all code in the program.
+
+### Valid:
+```typescript
+string = "hello world";
+var String2 = String;
+```
+
+### Invalid:
+```typescript
+String = "hello world";
+```
+```typescript
+Array = 1;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -0,0 +1,396 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::member_path;
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  AssignExpr, Expr, ExprOrSuper, ExprStmt, Ident, MemberExpr, Module, Pat,
+  PatOrExpr, PropName, VarDeclarator,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Default)]
+pub struct NoGlobalTimerLeaksOptions {
+  /// Reports intervals stored in a binding which is never passed to
+  /// `clearInterval()` in the same file.
+  pub require_clear: bool,
+  /// Reports timeouts stored in a binding which is never passed to
+  /// `clearTimeout()` too, when `require_clear` is set.
+  pub include_set_timeout: bool,
+}
+
+/// Reports `setInterval()` calls whose handle is discarded, so the interval
+/// can never be cleared.
+///
+/// With the `require_clear` option, intervals stored in a binding which is
+/// never passed to `clearInterval()` are reported too. Handles used in any
+/// other way, eg. passed to another function or returned, are assumed to
+/// be cleared elsewhere.
+pub struct NoGlobalTimerLeaks {
+  options: NoGlobalTimerLeaksOptions,
+}
+
+impl NoGlobalTimerLeaks {
+  pub fn with_options(options: NoGlobalTimerLeaksOptions) -> Box<Self> {
+    Box::new(NoGlobalTimerLeaks { options })
+  }
+}
+
+impl LintRule for NoGlobalTimerLeaks {
+  fn new() -> Box<Self> {
+    Self::with_options(NoGlobalTimerLeaksOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-global-timer-leaks"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows `setInterval()` calls whose handle is discarded, so the interval can never be cleared.
+
+### Options
+
+- `NoGlobalTimerLeaksOptions::require_clear`: also reports intervals stored in a binding which is never passed to `clearInterval()` in the same file, `false` by default. Handles used in any other way, eg. passed to another function, are assumed to be cleared elsewhere.
+- `NoGlobalTimerLeaksOptions::include_set_timeout`: also checks `setTimeout()` and `clearTimeout()` when `require_clear` is set, `false` by default.
+
+### Valid:
+```typescript
+const id = setInterval(tick, 1000);
+```
+
+### Invalid:
+```typescript
+setInterval(tick, 1000);
+```
+```typescript
+function start() {
+  setInterval(tick, 1000);
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoGlobalTimerLeaksVisitor {
+      context,
+      options: &self.options,
+      stored: vec![],
+      references: HashSet::new(),
+    };
+    visitor.visit_module(module, module);
+    visitor.report_uncleared();
+  }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Timer {
+  Interval,
+  Timeout,
+}
+
+/// A timer whose handle is stored in a binding.
+struct StoredTimer {
+  timer: Timer,
+  call_span: Span,
+  handle: Id,
+  handle_span: Span,
+}
+
+struct NoGlobalTimerLeaksVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a NoGlobalTimerLeaksOptions,
+  stored: Vec<StoredTimer>,
+  /// Bindings which are read anywhere, apart from their assignments.
+  references: HashSet<Id>,
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+impl<'a> NoGlobalTimerLeaksVisitor<'a> {
+  /// Checks if the callee is the global `name`, or `globalThis.name`.
+  fn is_global_fn(&self, callee: &ExprOrSuper, name: &str) -> bool {
+    let callee = match callee {
+      ExprOrSuper::Expr(callee) => callee,
+      ExprOrSuper::Super(_) => return false,
+    };
+    match member_path(callee) {
+      (Expr::Ident(ident), path) if path.is_empty() => {
+        self.context.is_global(ident, name)
+      }
+      (Expr::Ident(ident), path) if path.len() == 1 && path[0] == name => {
+        self.context.is_global(ident, "globalThis")
+      }
+      _ => false,
+    }
+  }
+
+  fn timer_call(&self, expr: &Expr) -> Option<(Timer, Span)> {
+    let call_expr = match unwrap_paren(expr) {
+      Expr::Call(call_expr) => call_expr,
+      _ => return None,
+    };
+    if self.is_global_fn(&call_expr.callee, "setInterval") {
+      Some((Timer::Interval, call_expr.span))
+    } else if self.is_global_fn(&call_expr.callee, "setTimeout") {
+      Some((Timer::Timeout, call_expr.span))
+    } else {
+      None
+    }
+  }
+
+  fn store(&mut self, ident: &Ident, value: &Expr) {
+    if let Some((timer, call_span)) = self.timer_call(value) {
+      self.stored.push(StoredTimer {
+        timer,
+        call_span,
+        handle: ident.to_id(),
+        handle_span: ident.span,
+      });
+    }
+  }
+
+  fn report_uncleared(&self) {
+    if !self.options.require_clear {
+      return;
+    }
+    for stored in &self.stored {
+      if stored.timer == Timer::Timeout && !self.options.include_set_timeout {
+        continue;
+      }
+      // The handle is either cleared, or passed on to code which might
+      // clear it.
+      if self.references.contains(&stored.handle) {
+        continue;
+      }
+
+      let (message, clear) = match stored.timer {
+        Timer::Interval => (
+          "The handle of this interval is never passed to `clearInterval()`",
+          "clearInterval",
+        ),
+        Timer::Timeout => (
+          "The handle of this timeout is never passed to `clearTimeout()`",
+          "clearTimeout",
+        ),
+      };
+      let location = self
+        .context
+        .source_map
+        .lookup_char_pos(stored.handle_span.lo());
+      self.context.add_diagnostic_with_hint(
+        stored.call_span,
+        "no-global-timer-leaks",
+        message,
+        &format!(
+          "The handle is stored in `{}` at {}:{}; call `{}({})` when it's no longer needed",
+          stored.handle.0,
+          location.line,
+          location.col.0,
+          clear,
+          stored.handle.0
+        ),
+      );
+    }
+  }
+}
+
+impl<'a> Visit for NoGlobalTimerLeaksVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt, parent: &dyn Node) {
+    if let Some((Timer::Interval, call_span)) = self.timer_call(&expr_stmt.expr)
+    {
+      self.context.add_diagnostic_with_hint(
+        call_span,
+        "no-global-timer-leaks",
+        "The handle returned by `setInterval()` is discarded, so the interval can never be cleared",
+        "Store the handle and pass it to `clearInterval()` when the interval is no longer needed",
+      );
+    }
+    swc_ecmascript::visit::visit_expr_stmt(self, expr_stmt, parent);
+  }
+
+  fn visit_var_declarator(
+    &mut self,
+    declarator: &VarDeclarator,
+    parent: &dyn Node,
+  ) {
+    match (&declarator.name, &declarator.init) {
+      (Pat::Ident(ident), Some(init)) => {
+        self.store(ident, init);
+        self.visit_expr(init, parent);
+      }
+      (Pat::Ident(_), None) => {}
+      _ => {
+        swc_ecmascript::visit::visit_var_declarator(self, declarator, parent)
+      }
+    }
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, parent: &dyn Node) {
+    let target = match &assign_expr.left {
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Ident(ident) => Some(ident),
+        _ => None,
+      },
+      PatOrExpr::Expr(expr) => match &**expr {
+        Expr::Ident(ident) => Some(ident),
+        _ => None,
+      },
+    };
+    match target {
+      Some(ident) => {
+        self.store(ident, &assign_expr.right);
+        self.visit_expr(&assign_expr.right, parent);
+      }
+      None => {
+        swc_ecmascript::visit::visit_assign_expr(self, assign_expr, parent)
+      }
+    }
+  }
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr, parent: &dyn Node) {
+    self.visit_expr_or_super(&member_expr.obj, parent);
+    if member_expr.computed {
+      self.visit_expr(&member_expr.prop, parent);
+    }
+  }
+
+  fn visit_prop_name(&mut self, prop_name: &PropName, parent: &dyn Node) {
+    if let PropName::Computed(computed) = prop_name {
+      self.visit_expr(&computed.expr, parent);
+    }
+  }
+
+  fn visit_ident(&mut self, ident: &Ident, _: &dyn Node) {
+    self.references.insert(ident.to_id());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn require_clear() -> Box<NoGlobalTimerLeaks> {
+    NoGlobalTimerLeaks::with_options(NoGlobalTimerLeaksOptions {
+      require_clear: true,
+      include_set_timeout: false,
+    })
+  }
+
+  #[test]
+  fn no_global_timer_leaks_valid() {
+    assert_lint_ok_n::<NoGlobalTimerLeaks>(vec![
+      "const id = setInterval(tick, 1000);",
+      "setTimeout(tick, 1000);",
+      "function setInterval() {}\nsetInterval(tick, 1000);",
+      "timers.setInterval(tick, 1000);",
+      "this.id = setInterval(tick, 1000);",
+    ]);
+  }
+
+  #[test]
+  fn no_global_timer_leaks_invalid() {
+    assert_lint_err::<NoGlobalTimerLeaks>("setInterval(tick, 1000);", 0);
+    assert_lint_err::<NoGlobalTimerLeaks>(
+      "globalThis.setInterval(tick, 1000);",
+      0,
+    );
+    assert_lint_err::<NoGlobalTimerLeaks>("(setInterval(() => {}, 1000));", 1);
+    assert_lint_err_on_line::<NoGlobalTimerLeaks>(
+      "function start() {\n  setInterval(tick, 1000);\n}",
+      2,
+      2,
+    );
+  }
+
+  #[test]
+  fn no_global_timer_leaks_require_clear_valid() {
+    assert_lint_ok_with(
+      require_clear(),
+      "const id = setInterval(tick, 1000);\nclearInterval(id);",
+    );
+    assert_lint_ok_with(
+      require_clear(),
+      "let id;\nid = setInterval(tick, 1000);\nglobalThis.clearInterval(id);",
+    );
+    // Cleared in the returned cleanup function.
+    assert_lint_ok_with(
+      require_clear(),
+      "function start() {\n  const id = setInterval(tick, 1000);\n  return () => clearInterval(id);\n}",
+    );
+    // The handle is passed on, so it might be cleared elsewhere.
+    assert_lint_ok_with(
+      require_clear(),
+      "const id = setInterval(tick, 1000);\nregister(id);",
+    );
+    assert_lint_ok_with(
+      require_clear(),
+      "function start() {\n  const id = setInterval(tick, 1000);\n  return id;\n}",
+    );
+    assert_lint_ok_with(
+      require_clear(),
+      "const id = setInterval(tick, 1000);\nexport { id };",
+    );
+    assert_lint_ok_with(require_clear(), "const id = setTimeout(tick, 1000);");
+  }
+
+  #[test]
+  fn no_global_timer_leaks_require_clear_invalid() {
+    assert_lint_err_on_line_n_with(
+      require_clear(),
+      "const id = setInterval(tick, 1000);",
+      vec![(1, 11)],
+    );
+    // `id` in the other function is a different binding.
+    assert_lint_err_on_line_n_with(
+      require_clear(),
+      "const id = setInterval(tick, 1000);\nfunction stop(id) { clearInterval(id); }",
+      vec![(1, 11)],
+    );
+    assert_lint_err_on_line_n_with(
+      require_clear(),
+      "const id = setInterval(tick, 1000);\nconst o = { id: 1 };\no.id;",
+      vec![(1, 11)],
+    );
+
+    let diagnostics = lint(
+      require_clear(),
+      "function start() {\n  const id = setInterval(tick, 1000);\n}",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "The handle of this interval is never passed to `clearInterval()`"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("The handle is stored in `id` at 2:8; call `clearInterval(id)` when it's no longer needed")
+    );
+
+    let rule = NoGlobalTimerLeaks::with_options(NoGlobalTimerLeaksOptions {
+      require_clear: true,
+      include_set_timeout: true,
+    });
+    assert_lint_err_on_line_n_with(
+      rule,
+      "const id = setTimeout(tick, 1000);\nsetTimeout(tick, 1000);",
+      vec![(1, 11)],
+    );
+  }
+}
@@ -24,6 +24,32 @@ impl LintRule for NoImportAssign {
     "no-import-assign"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows assigning to imported bindings.
+
+Imports are read-only; assigning to them throws a `TypeError`. Properties of a default import may be changed, while those of a namespace import may not.
+
+### Valid:
+```typescript
+import mod from "./mod.ts";
+mod.prop = 0;
+```
+
+### Invalid:
+```typescript
+import mod from "./mod.ts";
+mod = 0;
+```
+```typescript
+import * as mod from "./mod.ts";
+mod.prop = 0;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -0,0 +1,405 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::{constant_string, edit_distance};
+use std::sync::Arc;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  CallExpr, ExportAll, Expr, ExprOrSuper, ImportDecl, Module, NamedExport, Str,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+/// Keys of the import map the linted files are resolved with.
+#[derive(Default)]
+pub struct NoImportMapUnmappedBareSpecifiersOptions {
+  /// Keys of the `imports` object.
+  pub imports: Vec<String>,
+  /// Prefixes of the `scopes` object, with the keys mapped in each scope.
+  pub scopes: Vec<(String, Vec<String>)>,
+}
+
+#[cfg(feature = "json")]
+impl NoImportMapUnmappedBareSpecifiersOptions {
+  /// Reads the keys of a parsed import map like
+  /// `{ "imports": { "std/": "https://deno.land/std/" } }`. Anything which
+  /// isn't an object is treated as empty.
+  pub fn from_import_map(import_map: &serde_json::Value) -> Self {
+    fn keys(value: &serde_json::Value) -> Vec<String> {
+      value
+        .as_object()
+        .map(|object| object.keys().cloned().collect())
+        .unwrap_or_default()
+    }
+
+    Self {
+      imports: keys(&import_map["imports"]),
+      scopes: import_map["scopes"]
+        .as_object()
+        .map(|scopes| {
+          scopes
+            .iter()
+            .map(|(scope, imports)| (scope.clone(), keys(imports)))
+            .collect()
+        })
+        .unwrap_or_default(),
+    }
+  }
+}
+
+/// Reports bare specifiers like `"lodash"` in imports and re-exports which
+/// no key of the import map matches, since Deno can't resolve them.
+///
+/// A key matches a specifier equal to it, or starting with it if the key
+/// ends with `/`. Keys of a scope only apply to files whose name matches
+/// the scope the same way. Specifiers with a scheme, like `npm:` or
+/// `https:`, are not bare.
+pub struct NoImportMapUnmappedBareSpecifiers {
+  options: NoImportMapUnmappedBareSpecifiersOptions,
+}
+
+impl NoImportMapUnmappedBareSpecifiers {
+  pub fn with_options(
+    options: NoImportMapUnmappedBareSpecifiersOptions,
+  ) -> Box<Self> {
+    Box::new(NoImportMapUnmappedBareSpecifiers { options })
+  }
+}
+
+impl LintRule for NoImportMapUnmappedBareSpecifiers {
+  fn new() -> Box<Self> {
+    Self::with_options(NoImportMapUnmappedBareSpecifiersOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-import-map-unmapped-bare-specifiers"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows bare specifiers like `"lodash"` in imports and re-exports which no key of the import map matches, since Deno can't resolve them.
+
+A key matches a specifier equal to it, or starting with it if the key ends with `/`. Keys of a scope only apply to files whose name matches the scope. Specifiers with a scheme, like `npm:` or `https:`, are not bare.
+
+### Options
+
+- `NoImportMapUnmappedBareSpecifiersOptions::imports`: the keys of the `imports` object of the import map.
+- `NoImportMapUnmappedBareSpecifiersOptions::scopes`: the prefixes of the `scopes` object, with the keys mapped in each scope.
+
+With the `json` feature, `NoImportMapUnmappedBareSpecifiersOptions::from_import_map` reads them from a parsed import map.
+
+### Valid:
+```typescript
+import { h } from "npm:preact";
+import { assert } from "https://deno.land/std/testing/asserts.ts";
+import { util } from "./util.ts";
+```
+
+### Invalid:
+```typescript
+import { h } from "preact";
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let options = &self.options;
+    let keys: Vec<&str> = options
+      .imports
+      .iter()
+      .chain(
+        options
+          .scopes
+          .iter()
+          .filter(|(scope, _)| key_matches(scope, &context.file_name))
+          .flat_map(|(_, imports)| imports),
+      )
+      .map(String::as_str)
+      .collect();
+
+    let mut visitor = NoImportMapUnmappedBareSpecifiersVisitor {
+      context: &context,
+      keys,
+    };
+    module.visit_with(module, &mut visitor);
+  }
+}
+
+struct NoImportMapUnmappedBareSpecifiersVisitor<'a> {
+  context: &'a Context,
+  /// Keys applying to the linted file.
+  keys: Vec<&'a str>,
+}
+
+impl<'a> NoImportMapUnmappedBareSpecifiersVisitor<'a> {
+  fn check(&self, span: Span, specifier: &str) {
+    if !is_bare(specifier)
+      || self.keys.iter().any(|key| key_matches(key, specifier))
+    {
+      return;
+    }
+
+    let hint = match closest_key(&self.keys, specifier) {
+      Some(key) => format!("Did you mean `{}`?", key),
+      None => format!("Add `{}` to the import map", specifier),
+    };
+    self.context.add_diagnostic_with_hint(
+      span,
+      "no-import-map-unmapped-bare-specifiers",
+      &format!("Bare specifier `{}` is not in the import map", specifier),
+      &hint,
+    );
+  }
+
+  fn check_str(&self, src: &Str) {
+    self.check(src.span, &src.value);
+  }
+}
+
+impl<'a> Visit for NoImportMapUnmappedBareSpecifiersVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_import_decl(&mut self, import_decl: &ImportDecl, _: &dyn Node) {
+    self.check_str(&import_decl.src);
+  }
+
+  fn visit_named_export(&mut self, named_export: &NamedExport, _: &dyn Node) {
+    if let Some(src) = &named_export.src {
+      self.check_str(src);
+    }
+  }
+
+  fn visit_export_all(&mut self, export_all: &ExportAll, _: &dyn Node) {
+    self.check_str(&export_all.src);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Expr::Ident(ident) = &**callee {
+        if ident.sym == *"import" {
+          if let Some(arg) = call_expr.args.first() {
+            if let Some(specifier) = constant_string(&arg.expr) {
+              self.check(arg.expr.span(), &specifier);
+            }
+          }
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+/// Checks if `specifier` is neither relative, absolute nor a URL.
+fn is_bare(specifier: &str) -> bool {
+  if specifier.starts_with("./")
+    || specifier.starts_with("../")
+    || specifier.starts_with('/')
+  {
+    return false;
+  }
+  // Anything with a scheme, like `https:`, `file:`, `npm:` or `jsr:`.
+  let scheme_len = specifier
+    .find(|c: char| !(c.is_ascii_alphanumeric() || "+-.".contains(c)))
+    .unwrap_or(specifier.len());
+  let has_scheme = scheme_len > 0
+    && specifier[..1].chars().all(|c| c.is_ascii_alphabetic())
+    && specifier[scheme_len..].starts_with(':');
+  !has_scheme
+}
+
+/// Import map matching: `key` is either equal to `value`, or a prefix of it
+/// ending with `/`.
+fn key_matches(key: &str, value: &str) -> bool {
+  key == value || (key.ends_with('/') && value.starts_with(key))
+}
+
+/// Finds the key closest to `specifier`, comparing keys ending with `/`
+/// to the package part of the specifier (`std/` for `std/fs/mod.ts`).
+/// Keys too different to be a typo are not suggested.
+fn closest_key<'a>(keys: &[&'a str], specifier: &str) -> Option<&'a str> {
+  keys
+    .iter()
+    .map(|key| {
+      let compared = if key.ends_with('/') {
+        package_prefix(specifier)
+      } else {
+        specifier
+      };
+      (edit_distance(key, compared), *key)
+    })
+    .filter(|(distance, key)| *distance <= 2.max(key.len() / 3))
+    .min()
+    .map(|(_, key)| key)
+}
+
+/// Returns the package part of a bare specifier including the trailing
+/// `/`: `std/` for `std/fs/mod.ts` and `@scope/pkg/` for `@scope/pkg/a`.
+fn package_prefix(specifier: &str) -> &str {
+  let segments = if specifier.starts_with('@') { 2 } else { 1 };
+  specifier
+    .match_indices('/')
+    .nth(segments - 1)
+    .map_or(specifier, |(index, _)| &specifier[..=index])
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::linter::LinterBuilder;
+  use crate::test_util::*;
+
+  fn options() -> NoImportMapUnmappedBareSpecifiersOptions {
+    NoImportMapUnmappedBareSpecifiersOptions {
+      imports: vec![
+        "std/".to_string(),
+        "preact".to_string(),
+        "@scope/pkg/".to_string(),
+      ],
+      scopes: vec![(
+        "/project/vendor/".to_string(),
+        vec!["lodash".to_string()],
+      )],
+    }
+  }
+
+  fn lint_file(
+    file_name: &str,
+    source: &str,
+  ) -> Vec<crate::diagnostic::LintDiagnostic> {
+    let mut linter = LinterBuilder::default()
+      .lint_unused_ignore_directives(false)
+      .lint_unknown_rules(false)
+      .rules(vec![NoImportMapUnmappedBareSpecifiers::with_options(
+        options(),
+      )])
+      .build();
+    linter
+      .lint(file_name.to_string(), source.to_string())
+      .expect("Failed to lint")
+  }
+
+  #[test]
+  fn no_import_map_unmapped_bare_specifiers_valid() {
+    for source in &[
+      "import { join } from \"std/path/mod.ts\";",
+      "import { h } from \"preact\";",
+      "import { a } from \"@scope/pkg/mod.ts\";",
+      "import a from \"./a.ts\";\nimport b from \"../b.ts\";\nimport c from \"/c.ts\";",
+      "import a from \"https://deno.land/x/a/mod.ts\";",
+      "import a from \"file:///a.ts\";",
+      "import chalk from \"npm:chalk@5\";\nimport { assert } from \"jsr:@std/assert\";",
+      "export * from \"std/fs/mod.ts\";\nexport { h } from \"preact\";",
+      "const a = await import(\"std/fs/mod.ts\");",
+      "const a = await import(name);",
+    ] {
+      let diagnostics = lint_file("/project/mod.ts", source);
+      assert!(diagnostics.is_empty(), "{:?}\n{}", diagnostics, source);
+    }
+
+    // `lodash` is only mapped for files in the scope.
+    let diagnostics = lint_file(
+      "/project/vendor/a.ts",
+      "import _ from \"lodash\";\nimport { h } from \"preact\";",
+    );
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+  }
+
+  #[test]
+  fn no_import_map_unmapped_bare_specifiers_invalid() {
+    let diagnostics = lint_file(
+      "/project/mod.ts",
+      r#"import _ from "lodash";
+import { h } from "preact/hooks";
+export * from "stdd/fs/mod.ts";
+export { a } from "@scope/pgk/a.ts";
+const b = import(`preat`);"#,
+    );
+    let found: Vec<(usize, usize, &str, Option<&str>)> = diagnostics
+      .iter()
+      .map(|diagnostic| {
+        (
+          diagnostic.range.start.line,
+          diagnostic.range.start.col,
+          diagnostic.message.as_str(),
+          diagnostic.hint.as_deref(),
+        )
+      })
+      .collect();
+    assert_eq!(
+      found,
+      vec![
+        (
+          1,
+          14,
+          "Bare specifier `lodash` is not in the import map",
+          Some("Add `lodash` to the import map")
+        ),
+        (
+          2,
+          18,
+          "Bare specifier `preact/hooks` is not in the import map",
+          Some("Add `preact/hooks` to the import map")
+        ),
+        (
+          3,
+          14,
+          "Bare specifier `stdd/fs/mod.ts` is not in the import map",
+          Some("Did you mean `std/`?")
+        ),
+        (
+          4,
+          18,
+          "Bare specifier `@scope/pgk/a.ts` is not in the import map",
+          Some("Did you mean `@scope/pkg/`?")
+        ),
+        (
+          5,
+          17,
+          "Bare specifier `preat` is not in the import map",
+          Some("Did you mean `preact`?")
+        ),
+      ]
+    );
+
+    // Without an import map every bare specifier is unmapped.
+    assert_lint_err::<NoImportMapUnmappedBareSpecifiers>(
+      "import { h } from \"preact\";",
+      18,
+    );
+  }
+
+  #[test]
+  fn package_prefixes() {
+    assert_eq!(package_prefix("std/fs/mod.ts"), "std/");
+    assert_eq!(package_prefix("@scope/pkg/a.ts"), "@scope/pkg/");
+    assert_eq!(package_prefix("lodash"), "lodash");
+    assert_eq!(edit_distance("preact", "preat"), 1);
+    assert_eq!(edit_distance("", "abc"), 3);
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn options_from_import_map() {
+    let options = NoImportMapUnmappedBareSpecifiersOptions::from_import_map(
+      &serde_json::json!({
+        "imports": { "std/": "https://deno.land/std/" },
+        "scopes": { "/vendor/": { "lodash": "./vendor/lodash.ts" } },
+      }),
+    );
+    assert_eq!(options.imports, vec!["std/"]);
+    assert_eq!(
+      options.scopes,
+      vec![("/vendor/".to_string(), vec!["lodash".to_string()])]
+    );
+
+    let options = NoImportMapUnmappedBareSpecifiersOptions::from_import_map(
+      &serde_json::json!({}),
+    );
+    assert!(options.imports.is_empty() && options.scopes.is_empty());
+  }
+}
@@ -19,6 +19,31 @@ impl LintRule for NoInferrableTypes {
     "no-inferrable-types"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows type annotations on variables, parameters and properties initialized with a literal of that type.
+
+The type is inferred from the initializer, so the annotation is redundant.
+
+### Valid:
+```typescript
+const a = 10n;
+const b = "str";
+function f(c = true) {}
+```
+
+### Invalid:
+```typescript
+const a: bigint = 10n;
+```
+```typescript
+function f(c: boolean = true) {}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -0,0 +1,430 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  BinaryOp, Expr, ExprOrSuper, Lit, Module, TsEnumDecl, TsEnumMemberId, UnaryOp,
+};
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Default)]
+pub struct NoInlineEnumValuesDuplicationOptions {
+  /// Reports every member without an initializer, not only those in enums
+  /// which initialize other members explicitly.
+  pub require_explicit: bool,
+}
+
+/// Reports members of an enum which have the same value as an earlier
+/// member, and members with implicit values in enums which initialize
+/// other members explicitly, since inserting or reordering members then
+/// silently renumbers them.
+///
+/// Initializers are evaluated when they are made of literals, constant
+/// arithmetic, string concatenation and references to earlier members.
+/// Members whose value can't be evaluated are never reported as
+/// duplicates.
+pub struct NoInlineEnumValuesDuplication {
+  options: NoInlineEnumValuesDuplicationOptions,
+}
+
+impl NoInlineEnumValuesDuplication {
+  pub fn with_options(
+    options: NoInlineEnumValuesDuplicationOptions,
+  ) -> Box<Self> {
+    Box::new(NoInlineEnumValuesDuplication { options })
+  }
+}
+
+impl LintRule for NoInlineEnumValuesDuplication {
+  fn new() -> Box<Self> {
+    Self::with_options(NoInlineEnumValuesDuplicationOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-inline-enum-values-duplication"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows enum members with the same value as an earlier member, and members with implicit values in enums which initialize other members explicitly.
+
+Inserting or reordering members of such an enum silently renumbers the implicit ones. Initializers made of literals, constant arithmetic, string concatenation and references to earlier members are evaluated; members whose value can't be evaluated are never reported as duplicates.
+
+### Options
+
+`NoInlineEnumValuesDuplicationOptions::require_explicit`: reports every member without an initializer, `false` by default.
+
+### Valid:
+```typescript
+enum E {
+  A,
+  B,
+  C,
+}
+```
+```typescript
+enum E {
+  A = "a",
+  B = "b",
+}
+```
+
+### Invalid:
+```typescript
+enum E {
+  A = 1,
+  B = 1,
+}
+```
+```typescript
+enum E {
+  A = "ab",
+  B = "a" + "b",
+}
+```
+```typescript
+enum E {
+  A = 1,
+  B,
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoInlineEnumValuesDuplicationVisitor {
+      context,
+      options: &self.options,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoInlineEnumValuesDuplicationVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a NoInlineEnumValuesDuplicationOptions,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+  Number(f64),
+  Str(String),
+}
+
+impl fmt::Display for Value {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Value::Number(n) => write!(f, "{}", n),
+      Value::Str(s) => write!(f, "{:?}", s),
+    }
+  }
+}
+
+/// Converts a number like the bitwise operators do.
+fn to_int32(n: f64) -> i32 {
+  if !n.is_finite() {
+    return 0;
+  }
+  n.trunc().rem_euclid(4294967296.0) as u32 as i32
+}
+
+/// Evaluates an enum initializer, resolving bare and qualified references
+/// to the members evaluated so far.
+fn eval(
+  expr: &Expr,
+  enum_name: &str,
+  members: &HashMap<String, Value>,
+) -> Option<Value> {
+  match expr {
+    Expr::Paren(paren) => eval(&paren.expr, enum_name, members),
+    Expr::Lit(Lit::Num(num)) => Some(Value::Number(num.value)),
+    Expr::Lit(Lit::Str(s)) => Some(Value::Str(s.value.to_string())),
+    Expr::Tpl(tpl) if tpl.exprs.is_empty() => Some(Value::Str(
+      tpl.quasis.iter().map(|q| &*q.raw.value).collect(),
+    )),
+    Expr::Ident(ident) => members.get(&*ident.sym).cloned(),
+    Expr::Member(member_expr) if !member_expr.computed => {
+      match (&member_expr.obj, &*member_expr.prop) {
+        (ExprOrSuper::Expr(obj), Expr::Ident(prop)) => match &**obj {
+          Expr::Ident(obj) if obj.sym == *enum_name => {
+            members.get(&*prop.sym).cloned()
+          }
+          _ => None,
+        },
+        _ => None,
+      }
+    }
+    Expr::Unary(unary) => {
+      let arg = match eval(&unary.arg, enum_name, members)? {
+        Value::Number(n) => n,
+        Value::Str(_) => return None,
+      };
+      match unary.op {
+        UnaryOp::Minus => Some(Value::Number(-arg)),
+        UnaryOp::Plus => Some(Value::Number(arg)),
+        UnaryOp::Tilde => Some(Value::Number(!to_int32(arg) as f64)),
+        _ => None,
+      }
+    }
+    Expr::Bin(bin_expr) => {
+      let left = eval(&bin_expr.left, enum_name, members)?;
+      let right = eval(&bin_expr.right, enum_name, members)?;
+      let (l, r) = match (left, right) {
+        (Value::Number(l), Value::Number(r)) => (l, r),
+        (left, right) if bin_expr.op == BinaryOp::Add => {
+          return Some(Value::Str(format!(
+            "{}{}",
+            to_string(left),
+            to_string(right)
+          )));
+        }
+        _ => return None,
+      };
+      let (li, ri) = (to_int32(l), to_int32(r));
+      let shift = (r as u32) & 31;
+      let value = match bin_expr.op {
+        BinaryOp::Add => l + r,
+        BinaryOp::Sub => l - r,
+        BinaryOp::Mul => l * r,
+        BinaryOp::Div => l / r,
+        BinaryOp::Mod => l % r,
+        BinaryOp::Exp => l.powf(r),
+        BinaryOp::BitOr => (li | ri) as f64,
+        BinaryOp::BitAnd => (li & ri) as f64,
+        BinaryOp::BitXor => (li ^ ri) as f64,
+        BinaryOp::LShift => li.wrapping_shl(shift) as f64,
+        BinaryOp::RShift => li.wrapping_shr(shift) as f64,
+        BinaryOp::ZeroFillRShift => (li as u32).wrapping_shr(shift) as f64,
+        _ => return None,
+      };
+      Some(Value::Number(value))
+    }
+    _ => None,
+  }
+}
+
+fn to_string(value: Value) -> String {
+  match value {
+    Value::Number(n) => n.to_string(),
+    Value::Str(s) => s,
+  }
+}
+
+fn member_name(id: &TsEnumMemberId) -> (String, Span) {
+  match id {
+    TsEnumMemberId::Ident(ident) => (ident.sym.to_string(), ident.span),
+    TsEnumMemberId::Str(s) => (s.value.to_string(), s.span),
+  }
+}
+
+impl<'a> NoInlineEnumValuesDuplicationVisitor<'a> {
+  fn check(&self, enum_decl: &TsEnumDecl) {
+    let enum_name = &*enum_decl.id.sym;
+    let has_explicit = enum_decl.members.iter().any(|m| m.init.is_some());
+    let mut members = HashMap::new();
+    let mut seen: Vec<(Value, String, Span)> = vec![];
+    // The value of the next implicit member, if it's known.
+    let mut next_implicit = Some(0.0);
+
+    for member in &enum_decl.members {
+      let (name, span) = member_name(&member.id);
+      let value = match &member.init {
+        Some(init) => eval(init, enum_name, &members),
+        None => {
+          if self.options.require_explicit {
+            self.context.add_diagnostic_with_hint(
+              span,
+              "no-inline-enum-values-duplication",
+              &format!("Enum member `{}` must be initialized explicitly", name),
+              "Add an initializer to the member",
+            );
+          } else if has_explicit {
+            self.context.add_diagnostic_with_hint(
+              span,
+              "no-inline-enum-values-duplication",
+              &format!(
+                "Enum member `{}` has an implicit value while other members are initialized explicitly",
+                name
+              ),
+              "Initialize all members explicitly, so inserting or reordering members doesn't change their values",
+            );
+          }
+          next_implicit.map(Value::Number)
+        }
+      };
+
+      if let Some(value) = &value {
+        match seen.iter().find(|(v, _, _)| v == value) {
+          Some((_, first_name, first_span)) => {
+            let location =
+              self.context.source_map.lookup_char_pos(first_span.lo());
+            self.context.add_diagnostic_with_hint(
+              span,
+              "no-inline-enum-values-duplication",
+              &format!(
+                "Enum member `{}` has the same value as `{}`",
+                name, first_name
+              ),
+              &format!(
+                "`{}` is {} at {}:{}; give each member a distinct value",
+                first_name, value, location.line, location.col.0
+              ),
+            );
+          }
+          None => seen.push((value.clone(), name.clone(), span)),
+        }
+        members.insert(name, value.clone());
+      }
+      next_implicit = match value {
+        Some(Value::Number(n)) => Some(n + 1.0),
+        _ => None,
+      };
+    }
+  }
+}
+
+impl<'a> Visit for NoInlineEnumValuesDuplicationVisitor<'a> {
+  fn visit_ts_enum_decl(&mut self, enum_decl: &TsEnumDecl, parent: &dyn Node) {
+    self.check(enum_decl);
+    swc_ecmascript::visit::visit_ts_enum_decl(self, enum_decl, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_inline_enum_values_duplication_valid() {
+    assert_lint_ok_n::<NoInlineEnumValuesDuplication>(vec![
+      "enum E { A, B, C }",
+      "enum E { A = 0, B = 1, C = 2 }",
+      "enum E { A = 'a', B = 'b' }",
+      "enum E { A = 1, B = '1' }",
+      "enum E { A = -1, B = 1 }",
+      "enum E { A = 1 << 0, B = 1 << 1, C = 1 << 2, All = A | B | C }",
+      "enum E { A = 'a', B = A + 'b', C = E.A + 'c' }",
+      "const enum E { A = 1, B = 2 }",
+      "enum E { A = f(), B = f() }",
+      "enum E { A = NaN, B = NaN }",
+      "enum E { A = 1 }\nenum F { A = 1 }",
+    ]);
+  }
+
+  #[test]
+  fn no_inline_enum_values_duplication_invalid() {
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum E { A = 1, B = 1 }",
+      16,
+    );
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum E { A = 'a', B = 'a' }",
+      18,
+    );
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum E { A = 'ab', B = 'a' + 'b' }",
+      19,
+    );
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum E { A = -1, B = -(1) }",
+      17,
+    );
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum E { A = 4, B = 1 << 2 }",
+      16,
+    );
+    // Shifts are duplicates of the numbers they evaluate to.
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum Flags { A = 1 << 0, B = 1 << 1, C = 2 }",
+      37,
+    );
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum E { A = 1, B = 2, C = B - A }",
+      23,
+    );
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum E { A = 'x', B = 1, C = E.A }",
+      25,
+    );
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "const enum E { A = 1, B = 1 }",
+      22,
+    );
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum E { 'a-b' = 1, 'c-d' = 1 }",
+      20,
+    );
+    assert_lint_err_on_line::<NoInlineEnumValuesDuplication>(
+      "function f() {\n  enum E { A = 1, B = 1 }\n}",
+      2,
+      18,
+    );
+  }
+
+  #[test]
+  fn no_inline_enum_values_duplication_hint() {
+    let diagnostics = lint(
+      NoInlineEnumValuesDuplication::new(),
+      "enum E {\n  A = 'a',\n  B = 'a',\n}",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Enum member `B` has the same value as `A`"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("`A` is \"a\" at 2:2; give each member a distinct value")
+    );
+    assert_eq!(diagnostics[0].range.start.line, 3);
+    assert_eq!(diagnostics[0].range.start.col, 2);
+  }
+
+  #[test]
+  fn no_inline_enum_values_duplication_mixed() {
+    // Heterogeneous enums count implicit members from the last number.
+    assert_lint_err_n::<NoInlineEnumValuesDuplication>(
+      "enum E { A = 1, B, C = 2 }",
+      vec![16, 19],
+    );
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum E { A = 'a', B = 5, C }",
+      25,
+    );
+    // The value of `C` is unknown, so it isn't a duplicate of `A`.
+    assert_lint_err::<NoInlineEnumValuesDuplication>(
+      "enum E { A = 0, B = f(), C }",
+      25,
+    );
+    assert_lint_err_n::<NoInlineEnumValuesDuplication>(
+      "enum E { A, B = 0 }",
+      vec![9, 12],
+    );
+
+    let diagnostics =
+      lint(NoInlineEnumValuesDuplication::new(), "enum E { A = 2, B }");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Enum member `B` has an implicit value while other members are initialized explicitly"
+    );
+
+    let rule = NoInlineEnumValuesDuplication::with_options(
+      NoInlineEnumValuesDuplicationOptions {
+        require_explicit: true,
+      },
+    );
+    assert_lint_err_on_line_n_with(
+      rule,
+      "enum E { A, B }",
+      vec![(1, 9), (1, 12)],
+    );
+  }
+}
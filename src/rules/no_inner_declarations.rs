@@ -20,6 +20,40 @@ impl LintRule for NoInnerDeclarations {
     "no-inner-declarations"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows function and `var` declarations in nested blocks.
+
+Such declarations are hoisted out of the block, which makes them behave differently than they look; declare them at the root of the module or function body instead.
+
+### Valid:
+```typescript
+function doSomething() {}
+```
+```typescript
+if (test) {
+  const doSomething = () => {};
+}
+```
+
+### Invalid:
+```typescript
+if (test) {
+  function doSomething() {}
+}
+```
+```typescript
+function doSomething() {
+  do {
+    function somethingElse() {}
+  } while (test);
+}
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &ast::Module) {
     let mut valid_visitor = ValidDeclsVisitor::new();
     valid_visitor.visit_module(module, module);
@@ -244,11 +278,14 @@ mod tests {
       "if (test) { function doSomething() { } }",
       12,
     );
-    assert_lint_err::<NoInnerDeclarations>("if (foo)  function f(){} ", 10);
-    assert_lint_err::<NoInnerDeclarations>(
-      "function bar() { if (foo) function f(){}; }",
-      26,
-    );
+    // A function declaration can't be the body of an `if` in a module
+    with_syntax_errors(|| {
+      assert_lint_err::<NoInnerDeclarations>("if (foo)  function f(){} ", 10);
+      assert_lint_err::<NoInnerDeclarations>(
+        "function bar() { if (foo) function f(){}; }",
+        26,
+      );
+    });
     assert_lint_err::<NoInnerDeclarations>("function doSomething() { do { function somethingElse() { } } while (test); }", 30);
     assert_lint_err::<NoInnerDeclarations>(
       "(function() { if (test) { function doSomething() { } } }());",
@@ -285,9 +322,11 @@ mod tests {
       "if (foo){ function f(){ if(bar){ var a; } } }",
       vec![10, 33],
     );
-    assert_lint_err_n::<NoInnerDeclarations>(
-      "if (foo) function f(){ if(bar) var a; } ",
-      vec![9, 31],
-    );
+    with_syntax_errors(|| {
+      assert_lint_err_n::<NoInnerDeclarations>(
+        "if (foo) function f(){ if(bar) var a; } ",
+        vec![9, 31],
+      );
+    });
   }
 }
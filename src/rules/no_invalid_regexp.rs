@@ -22,6 +22,30 @@ impl LintRule for NoInvalidRegexp {
     "no-invalid-regexp"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows invalid regular expressions in calls of the `RegExp` constructor.
+
+Invalid patterns or flags throw a `SyntaxError` when the code runs.
+
+### Valid:
+```typescript
+RegExp(".");
+new RegExp(".", "im");
+```
+
+### Invalid:
+```typescript
+RegExp("[");
+```
+```typescript
+new RegExp(".", "z");
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -1,96 +1,149 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule};
-use regex::{Matches, Regex};
+use regex::Regex;
 use std::sync::Arc;
-use swc_common::{hygiene::SyntaxContext, BytePos, Span};
-use swc_ecmascript::ast::Module;
-use swc_ecmascript::ast::Str;
+use swc_common::Span;
+use swc_ecmascript::ast::{Module, Str, TplElement};
+use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 
-pub struct NoIrregularWhitespace;
-
 lazy_static! {
-  static ref IRREGULAR_WHITESPACE: Regex = Regex::new(r"[\f\v\u0085\ufeff\u00a0\u1680\u180e\u2000\u2001\u2002\u2003\u2004\u2005\u2006\u2007\u2008\u2009\u200a\u200b\u202f\u205f\u3000]+").unwrap();
-  static ref IRREGULAR_LINE_TERMINATORS: Regex = Regex::new(r"[\u2028\u2029]").unwrap();
+  static ref IRREGULAR_WHITESPACE: Regex =
+    Regex::new(r"[\f\v\u0085\ufeff\u00a0\u1680\u180e\u2000\u2001\u2002\u2003\u2004\u2005\u2006\u2007\u2008\u2009\u200a\u200b\u2028\u2029\u202f\u205f\u3000]").unwrap();
 }
 
-fn test_for_whitespace(value: &str) -> Option<Vec<Matches>> {
-  let mut matches_vector: Vec<Matches> = vec![];
-  if IRREGULAR_WHITESPACE.is_match(value) {
-    let matches = IRREGULAR_WHITESPACE.find_iter(value);
-    matches_vector.push(matches);
-  }
-  if IRREGULAR_LINE_TERMINATORS.is_match(value) {
-    let matches = IRREGULAR_LINE_TERMINATORS.find_iter(value);
-    matches_vector.push(matches);
+#[derive(Clone, Copy)]
+pub struct NoIrregularWhitespaceOptions {
+  /// Allows irregular whitespace in string literals.
+  pub skip_strings: bool,
+  /// Allows irregular whitespace in comments.
+  pub skip_comments: bool,
+  /// Allows irregular whitespace in the text of template literals, but not
+  /// in their expressions.
+  pub skip_templates: bool,
+}
+
+impl Default for NoIrregularWhitespaceOptions {
+  fn default() -> Self {
+    Self {
+      skip_strings: true,
+      skip_comments: true,
+      skip_templates: true,
+    }
   }
-  if !matches_vector.is_empty() {
-    Some(matches_vector)
-  } else {
-    None
+}
+
+/// Reports irregular whitespace characters in the source text. Strings,
+/// template literals and comments are found through the AST and the
+/// comments of the parse.
+pub struct NoIrregularWhitespace {
+  options: NoIrregularWhitespaceOptions,
+}
+
+impl NoIrregularWhitespace {
+  pub fn with_options(options: NoIrregularWhitespaceOptions) -> Box<Self> {
+    Box::new(NoIrregularWhitespace { options })
   }
 }
 
 impl LintRule for NoIrregularWhitespace {
   fn new() -> Box<Self> {
-    Box::new(NoIrregularWhitespace)
+    Self::with_options(NoIrregularWhitespaceOptions::default())
   }
 
   fn code(&self) -> &'static str {
     "no-irregular-whitespace"
   }
 
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows irregular whitespace characters, like the no-break space `U+00A0` or the byte order mark `U+FEFF`, outside of strings, template literals and comments.
+
+These characters are invisible in most editors and treated inconsistently by tools. Escape them in strings instead.
+
+### Options
+
+- `NoIrregularWhitespaceOptions::skip_strings`: allows them in string literals, `true` by default.
+- `NoIrregularWhitespaceOptions::skip_comments`: allows them in comments, `true` by default.
+- `NoIrregularWhitespaceOptions::skip_templates`: allows them in the text of template literals, `true` by default.
+
+### Valid:
+```typescript
+const a = "\u{00A0}";
+```
+
+### Invalid:
+
+The following contains a no-break space before `=`:
+
+```typescript
+var any = "thing";
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
-    let mut visitor = NoIrregularWhitespaceVisitor::default();
+    let mut visitor = NoIrregularWhitespaceVisitor {
+      options: self.options,
+      ranges: vec![],
+    };
     visitor.visit_module(module, module);
 
-    let excluded_ranges = visitor.ranges.iter();
-
-    let file_and_lines = context.source_map.span_to_lines(module.span).unwrap();
-    let file = file_and_lines.file;
-
-    for line_index in 0..file.count_lines() {
-      let line = file.get_line(line_index).unwrap();
-      let (byte_pos, _hi) = file.line_bounds(line_index);
-
-      if let Some(whitespace_results) = test_for_whitespace(&line) {
-        for whitespace_matches in whitespace_results.into_iter() {
-          for whitespace_match in whitespace_matches {
-            let range = whitespace_match.range();
-            let span = Span::new(
-              byte_pos + BytePos(range.start as u32),
-              byte_pos + BytePos(range.end as u32),
-              SyntaxContext::empty(),
-            );
-            let is_excluded =
-              excluded_ranges.clone().any(|range| range.contains(span));
-            if !is_excluded {
-              context.add_diagnostic(
-                span,
-                "no-irregular-whitespace",
-                "Irregular whitespace not allowed.",
-              );
-            }
-          }
-        }
+    let mut excluded_ranges = visitor.ranges;
+    if self.options.skip_comments {
+      excluded_ranges
+        .extend(context.all_comments().map(|comment| comment.span));
+    }
+
+    for whitespace_match in IRREGULAR_WHITESPACE.find_iter(context.source()) {
+      let span = context.byte_range_to_span(whitespace_match.range());
+      let is_excluded =
+        excluded_ranges.iter().any(|range| range.contains(span));
+      if !is_excluded {
+        let c = whitespace_match.as_str().chars().next().unwrap() as u32;
+        context.add_diagnostic_with_hint(
+          span,
+          "no-irregular-whitespace",
+          "Irregular whitespace not allowed.",
+          &format!(
+            "Replace U+{:04X} with a regular space, or escape it as `\\u{{{:04X}}}` in strings",
+            c, c
+          ),
+        );
       }
     }
   }
 }
 
 struct NoIrregularWhitespaceVisitor {
+  options: NoIrregularWhitespaceOptions,
   ranges: Vec<Span>,
 }
 
-impl NoIrregularWhitespaceVisitor {
-  fn default() -> Self {
-    Self { ranges: vec![] }
-  }
-}
-
 impl Visit for NoIrregularWhitespaceVisitor {
+  noop_visit_type!();
+
   fn visit_str(&mut self, string_literal: &Str, _parent: &dyn Node) {
-    self.ranges.push(string_literal.span);
+    if self.options.skip_strings {
+      self.ranges.push(string_literal.span);
+    }
+  }
+
+  fn visit_tpl_element(
+    &mut self,
+    tpl_element: &TplElement,
+    _parent: &dyn Node,
+  ) {
+    if self.options.skip_templates {
+      self.ranges.push(tpl_element.span);
+    }
   }
 }
 
@@ -180,4 +233,56 @@ mod tests {
       vec![(1, 8), (1, 27), (2, 10)],
     );
   }
+  #[test]
+  fn no_irregular_whitespace_code_strings_and_templates() {
+    let src = "const a\u{00A0}= 'b\u{00A0}c';\nconst d = `e\u{00A0}f${a}`;";
+    assert_lint_err_on_line_n::<NoIrregularWhitespace>(src, vec![(1, 7)]);
+
+    let diagnostics = lint(NoIrregularWhitespace::new(), src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.end.col, 8);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Replace U+00A0 with a regular space, or escape it as `\\u{00A0}` in strings")
+    );
+
+    assert_lint_err::<NoIrregularWhitespace>("const a = `b${\u{00A0}c}`;", 14);
+    assert_lint_err_on_line_n::<NoIrregularWhitespace>(
+      "const a = 1;\u{2003}\nconst b\u{FEFF} = 2;",
+      vec![(1, 12), (2, 7)],
+    );
+  }
+
+  #[test]
+  fn no_irregular_whitespace_comments() {
+    assert_lint_ok_n::<NoIrregularWhitespace>(vec![
+      "// a\u{00A0}comment\nconst a = 1;",
+      "/* a\u{00A0}comment */\nconst a = 1;",
+      "const a = 1; // a\u{3000}comment",
+    ]);
+  }
+
+  #[test]
+  fn no_irregular_whitespace_options() {
+    let rule =
+      |skip_strings: bool, skip_comments: bool, skip_templates: bool| {
+        NoIrregularWhitespace::with_options(NoIrregularWhitespaceOptions {
+          skip_strings,
+          skip_comments,
+          skip_templates,
+        })
+      };
+    let src =
+      "const a = 'b\u{00A0}c';\nconst d = `e\u{00A0}f`;\n// g\u{00A0}h\nconst i = 1;";
+
+    assert_lint_ok_with(rule(true, true, true), src);
+    assert_lint_err_on_line_n_with(rule(false, true, true), src, vec![(1, 12)]);
+    assert_lint_err_on_line_n_with(rule(true, true, false), src, vec![(2, 12)]);
+    assert_lint_err_on_line_n_with(rule(true, false, true), src, vec![(3, 4)]);
+    assert_lint_err_on_line_n_with(
+      rule(false, false, false),
+      src,
+      vec![(1, 12), (2, 12), (3, 4)],
+    );
+  }
 }
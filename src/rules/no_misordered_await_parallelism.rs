@@ -0,0 +1,339 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  BlockStmt, Decl, Expr, Ident, Module, ModuleItem, Pat, Stmt,
+};
+use swc_ecmascript::utils::find_ids;
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+declare_rule! {
+  /// Suggests `Promise.all` for consecutive `await`s which don't depend on
+  /// the results of each other:
+  ///
+  /// ```js
+  /// const user = await getUser(id);
+  /// const posts = await getPosts(id);
+  /// ```
+  ///
+  /// Only data dependencies are taken into account, awaits which have to run
+  /// in order because of side effects should be marked with an ignore
+  /// directive.
+  NoMisorderedAwaitParallelism, "no-misordered-await-parallelism", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Suggests `Promise.all` for consecutive `await`s which don't depend on the results of each other.
+
+Independent awaits run one after another although they could run in parallel. Only data dependencies are taken into account; awaits which have to run in order because of side effects should be marked with an ignore directive.
+
+### Valid:
+```typescript
+async function f(id) {
+  const user = await getUser(id);
+  const posts = await getPosts(user.id);
+}
+```
+```typescript
+async function f(id) {
+  const [user, posts] = await Promise.all([getUser(id), getPosts(id)]);
+}
+```
+
+### Invalid:
+```typescript
+async function f(id) {
+  const user = await getUser(id);
+  const posts = await getPosts(id);
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoMisorderedAwaitParallelismVisitor { context };
+    visitor.visit_module(module, module);
+  }
+}
+
+/// `const a = await foo();` or `await foo();`
+struct AwaitStmt<'a> {
+  stmt: &'a Stmt,
+  binding: Option<&'a Pat>,
+  awaited: &'a Expr,
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn awaited_expr(expr: &Expr) -> Option<&Expr> {
+  match unwrap_paren(expr) {
+    Expr::Await(await_expr) => Some(&await_expr.arg),
+    _ => None,
+  }
+}
+
+fn await_stmt(stmt: &Stmt) -> Option<AwaitStmt<'_>> {
+  match stmt {
+    Stmt::Expr(expr_stmt) => Some(AwaitStmt {
+      stmt,
+      binding: None,
+      awaited: awaited_expr(&expr_stmt.expr)?,
+    }),
+    Stmt::Decl(Decl::Var(var_decl)) => match var_decl.decls.as_slice() {
+      [decl] => Some(AwaitStmt {
+        stmt,
+        binding: Some(&decl.name),
+        awaited: awaited_expr(decl.init.as_ref()?)?,
+      }),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+/// Collects all identifiers referenced by an expression, including the ones
+/// inside of nested functions.
+fn referenced_ids(expr: &Expr) -> HashSet<Id> {
+  struct Collector {
+    ids: HashSet<Id>,
+  }
+
+  impl Visit for Collector {
+    noop_visit_type!();
+
+    fn visit_ident(&mut self, ident: &Ident, _parent: &dyn Node) {
+      self.ids.insert(ident.to_id());
+    }
+  }
+
+  let mut collector = Collector {
+    ids: HashSet::new(),
+  };
+  expr.visit_with(expr, &mut collector);
+  collector.ids
+}
+
+struct NoMisorderedAwaitParallelismVisitor {
+  context: Arc<Context>,
+}
+
+impl NoMisorderedAwaitParallelismVisitor {
+  fn check_stmts(&self, stmts: &[&Stmt]) {
+    let mut group: Vec<AwaitStmt> = vec![];
+    let mut declared: HashSet<Id> = HashSet::new();
+
+    for stmt in stmts {
+      let current = match await_stmt(stmt) {
+        Some(current) => current,
+        None => {
+          self.report(&group);
+          group.clear();
+          declared.clear();
+          continue;
+        }
+      };
+
+      let depends_on_group = referenced_ids(current.awaited)
+        .iter()
+        .any(|id| declared.contains(id));
+      if depends_on_group {
+        self.report(&group);
+        group.clear();
+        declared.clear();
+      }
+
+      if let Some(binding) = current.binding {
+        let ids: Vec<Id> = find_ids(binding);
+        declared.extend(ids);
+      }
+      group.push(current);
+    }
+    self.report(&group);
+  }
+
+  fn report(&self, group: &[AwaitStmt]) {
+    let (first, last) = match group {
+      [first, .., last] => (first, last),
+      _ => return,
+    };
+
+    let bindings: Vec<String> = group
+      .iter()
+      .map(|await_stmt| match await_stmt.binding {
+        Some(binding) => self.context.span_text(binding.span()),
+        None => String::new(),
+      })
+      .collect();
+    let promises: Vec<String> = group
+      .iter()
+      .map(|await_stmt| self.context.span_text(await_stmt.awaited.span()))
+      .collect();
+    let promise_all = format!("await Promise.all([{}])", promises.join(", "));
+    let sketch = if bindings.iter().all(String::is_empty) {
+      promise_all
+    } else {
+      format!("const [{}] = {}", bindings.join(", "), promise_all)
+    };
+
+    self.context.add_diagnostic_with_hint(
+      first.stmt.span().to(last.stmt.span()),
+      "no-misordered-await-parallelism",
+      &format!("{} independent `await`s run one after another", group.len()),
+      &format!("Run them in parallel with `{}`", sketch),
+    );
+  }
+}
+
+impl Visit for NoMisorderedAwaitParallelismVisitor {
+  noop_visit_type!();
+
+  fn visit_module(&mut self, module: &Module, parent: &dyn Node) {
+    let stmts: Vec<&Stmt> = module
+      .body
+      .iter()
+      .filter_map(|item| match item {
+        ModuleItem::Stmt(stmt) => Some(stmt),
+        ModuleItem::ModuleDecl(_) => None,
+      })
+      .collect();
+    self.check_stmts(&stmts);
+    swc_ecmascript::visit::visit_module(self, module, parent);
+  }
+
+  fn visit_block_stmt(&mut self, block: &BlockStmt, parent: &dyn Node) {
+    let stmts: Vec<&Stmt> = block.stmts.iter().collect();
+    self.check_stmts(&stmts);
+    swc_ecmascript::visit::visit_block_stmt(self, block, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_misordered_await_parallelism_valid() {
+    assert_lint_ok_n::<NoMisorderedAwaitParallelism>(vec![
+      r#"
+async function f(id) {
+  const user = await getUser(id);
+  const posts = await getPosts(user.id);
+}
+      "#,
+      r#"
+async function f(id) {
+  const res = await fetch(url);
+  const { data } = await res.json();
+  const more = await load(data);
+}
+      "#,
+      r#"
+async function f() {
+  const a = await foo();
+  log(a);
+  const b = await bar();
+}
+      "#,
+      r#"
+async function f() {
+  const a = await foo();
+  if (a) {
+    const b = await bar();
+  }
+}
+      "#,
+      r#"
+async function f() {
+  const a = await foo();
+  const b = await bar(() => a);
+}
+      "#,
+      r#"
+async function f() {
+  const a = foo();
+  const b = bar();
+}
+      "#,
+      r#"
+async function f() {
+  // deno-lint-ignore no-misordered-await-parallelism
+  await writeFile(path, data);
+  await readFile(path);
+}
+      "#,
+    ]);
+  }
+
+  #[test]
+  fn no_misordered_await_parallelism_invalid() {
+    assert_lint_err_on_line::<NoMisorderedAwaitParallelism>(
+      r#"
+async function f(id) {
+  const user = await getUser(id);
+  const posts = await getPosts(id);
+}
+      "#,
+      3,
+      2,
+    );
+    assert_lint_err_on_line::<NoMisorderedAwaitParallelism>(
+      r#"
+const config = await loadConfig();
+const [a] = await readA();
+await warmUp();
+      "#,
+      2,
+      0,
+    );
+    assert_lint_err_on_line_n::<NoMisorderedAwaitParallelism>(
+      r#"
+async function f() {
+  const a = await foo();
+  const b = await bar(a);
+  const c = await baz();
+}
+      "#,
+      vec![(4, 2)],
+    );
+  }
+
+  #[test]
+  fn no_misordered_await_parallelism_group() {
+    let diagnostics = lint(
+      NoMisorderedAwaitParallelism::new(),
+      "const a = await foo();\nconst b = await bar();\nconst c = await baz(1);",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 1);
+    assert_eq!(diagnostics[0].range.end.line, 3);
+    assert_eq!(
+      diagnostics[0].message,
+      "3 independent `await`s run one after another"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Run them in parallel with `const [a, b, c] = await Promise.all([foo(), bar(), baz(1)])`")
+    );
+
+    let diagnostics = lint(
+      NoMisorderedAwaitParallelism::new(),
+      "await foo();\nconst b = await bar();",
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Run them in parallel with `const [, b] = await Promise.all([foo(), bar()])`")
+    );
+  }
+}
@@ -26,6 +26,41 @@ impl LintRule for NoMisusedNew {
   fn code(&self) -> &'static str {
     "no-misused-new"
   }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows `new` and `constructor` where they don't declare a constructor.
+
+Interfaces and type aliases can't be constructed, so a `constructor` member or a `new()` signature returning the type itself is a mistake, as is a class method named `new`.
+
+### Valid:
+```typescript
+class C {
+  constructor() {}
+}
+```
+```typescript
+interface I {
+  new (): C;
+}
+```
+
+### Invalid:
+```typescript
+interface I {
+  new (): I;
+  constructor(): void;
+}
+```
+```typescript
+class C {
+  new(): C;
+}
+```"#
+  }
 }
 
 struct NoMisusedNewVisitor {
@@ -257,8 +292,10 @@ declare abstract class C {
     }
       "#,
     );
-    assert_lint_ok::<NoMisusedNew>(
-      r#"
+    // Methods of an ambient class can't have a body
+    with_syntax_errors(|| {
+      assert_lint_ok::<NoMisusedNew>(
+        r#"
     declare class DC {
         foo() {
 
@@ -267,6 +304,7 @@ declare abstract class C {
         bar();
     }
       "#,
-    )
+      );
+    });
   }
 }
@@ -0,0 +1,214 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ArrayLit, CallExpr, Expr, ExprOrSpread, Lit, NewExpr, ParenExpr,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Default)]
+pub struct NoMisusedSpreadOptions {
+  /// Also reports spreading string literals, which splits them into
+  /// characters, eg. `[..."abc"]`.
+  pub check_strings: bool,
+}
+
+pub struct NoMisusedSpread {
+  options: NoMisusedSpreadOptions,
+}
+
+impl NoMisusedSpread {
+  pub fn with_options(options: NoMisusedSpreadOptions) -> Box<Self> {
+    Box::new(NoMisusedSpread { options })
+  }
+}
+
+impl LintRule for NoMisusedSpread {
+  fn new() -> Box<Self> {
+    Self::with_options(NoMisusedSpreadOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-misused-spread"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows spreading values which aren't iterable, like `null`, `undefined`, numbers and booleans, into arrays and function calls.
+
+Spreading them throws a `TypeError`.
+
+### Options
+
+`NoMisusedSpreadOptions::check_strings`: also reports spreading string literals, which splits them into characters, `false` by default.
+
+### Valid:
+```typescript
+const a = [...new Set(x)];
+foo(...args);
+```
+
+### Invalid:
+```typescript
+const a = [...null];
+```
+```typescript
+foo(a, ...true);
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = NoMisusedSpreadVisitor::new(context, self.options);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoMisusedSpreadVisitor {
+  context: Arc<Context>,
+  options: NoMisusedSpreadOptions,
+}
+
+impl NoMisusedSpreadVisitor {
+  fn new(context: Arc<Context>, options: NoMisusedSpreadOptions) -> Self {
+    Self { context, options }
+  }
+
+  /// Describes the spread value if it's a literal that is not iterable.
+  fn non_iterable(&self, expr: &Expr) -> Option<&'static str> {
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => self.non_iterable(expr),
+      Expr::Lit(Lit::Null(_)) => Some("`null`"),
+      Expr::Lit(Lit::Num(_)) | Expr::Lit(Lit::BigInt(_)) => Some("number"),
+      Expr::Lit(Lit::Bool(_)) => Some("boolean"),
+      Expr::Lit(Lit::Regex(_)) => Some("regular expression"),
+      Expr::Ident(ident)
+        if ident.sym == *"undefined"
+          && self.context.scope_manager.binding(&ident.to_id()).is_none() =>
+      {
+        Some("`undefined`")
+      }
+      _ => None,
+    }
+  }
+
+  fn is_string(expr: &Expr) -> bool {
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => Self::is_string(expr),
+      Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) => true,
+      _ => false,
+    }
+  }
+
+  /// Checks a spread in an iterable position: array literals and
+  /// call arguments.
+  fn check_spread(&self, arg: &ExprOrSpread) {
+    let dot3_token = match arg.spread {
+      Some(span) => span,
+      None => return,
+    };
+    let span =
+      Span::new(dot3_token.lo(), arg.expr.span().hi(), dot3_token.ctxt);
+
+    if let Some(kind) = self.non_iterable(&arg.expr) {
+      self.context.add_diagnostic(
+        span,
+        "no-misused-spread",
+        &format!("Spreading a {} throws a TypeError, it's not iterable", kind),
+      );
+    } else if self.options.check_strings && Self::is_string(&arg.expr) {
+      self.context.add_diagnostic_with_hint(
+        span,
+        "no-misused-spread",
+        "Spreading a string splits it into characters",
+        "Use `Array.from()` or `.split(\"\")` if that's intended",
+      );
+    }
+  }
+}
+
+impl Visit for NoMisusedSpreadVisitor {
+  noop_visit_type!();
+
+  fn visit_array_lit(&mut self, array_lit: &ArrayLit, parent: &dyn Node) {
+    for elem in array_lit.elems.iter().flatten() {
+      self.check_spread(elem);
+    }
+    swc_ecmascript::visit::visit_array_lit(self, array_lit, parent);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    for arg in &call_expr.args {
+      self.check_spread(arg);
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+    for arg in new_expr.args.iter().flatten() {
+      self.check_spread(arg);
+    }
+    swc_ecmascript::visit::visit_new_expr(self, new_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_misused_spread_valid() {
+    assert_lint_ok_n::<NoMisusedSpread>(vec![
+      "[...new Set(x)];",
+      "[...items, 1];",
+      "foo(...args);",
+      "const a = { ...null };",
+      "const a = { ...undefined, ...1, ...true };",
+      "[...'abc'];",
+      "function f(undefined) { return [...undefined]; }",
+      "[...(items || [])];",
+      "[null, undefined, 1];",
+    ]);
+  }
+
+  #[test]
+  fn no_misused_spread_invalid() {
+    assert_lint_err::<NoMisusedSpread>("[...null];", 1);
+    assert_lint_err::<NoMisusedSpread>("[1, ...undefined];", 4);
+    assert_lint_err::<NoMisusedSpread>("foo(...1);", 4);
+    assert_lint_err::<NoMisusedSpread>("foo(a, ...true);", 7);
+    assert_lint_err::<NoMisusedSpread>("new Foo(.../a/);", 8);
+    assert_lint_err::<NoMisusedSpread>("foo(...(null));", 4);
+    assert_lint_err_on_line::<NoMisusedSpread>(
+      "const a = [\n  ...10n,\n];",
+      2,
+      2,
+    );
+  }
+
+  #[test]
+  fn no_misused_spread_check_strings() {
+    let rule = || {
+      NoMisusedSpread::with_options(NoMisusedSpreadOptions {
+        check_strings: true,
+      })
+    };
+    assert_lint_ok_with(rule(), "const a = { ...'abc' };");
+    assert_lint_ok_with(rule(), "[...chars];");
+    assert_lint_err_on_line_n_with(rule(), "[...'abc'];", vec![(1, 1)]);
+    assert_lint_err_on_line_n_with(rule(), "foo(...`${a}b`);", vec![(1, 4)]);
+  }
+}
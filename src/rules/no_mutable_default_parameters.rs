@@ -0,0 +1,490 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::scopes::BindingKind;
+use crate::swc_util::{member_path, MUTATING_METHODS};
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ArrowExpr, AssignExpr, AssignPat, BlockStmtOrExpr, CallExpr, Constructor,
+  Decl, Expr, ExprOrSuper, Function, Ident, MemberExpr, Module, ModuleDecl,
+  ModuleItem, ParamOrTsParamProp, Pat, PatOrExpr, ReturnStmt, Stmt,
+  TsParamPropParam, UnaryExpr, UnaryOp, UpdateExpr, VarDeclKind,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::{Visit, VisitWith};
+
+declare_rule! {
+  /// Reports parameter defaults which share state between calls:
+  ///
+  /// - references to module-level mutable bindings, ie. `let` and `var`
+  ///   bindings, and `const` bindings of array and object literals, like
+  ///   `function f(x = sharedArray)`
+  /// - array and object literals which the function mutates and then returns
+  ///   or stores on `this`, so they outlive the call
+  ///
+  /// Defaults which are only read, copied, or created by calling a factory
+  /// function are fine.
+  NoMutableDefaultParameters, "no-mutable-default-parameters", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows parameter defaults which share state between calls.
+
+Defaults referring to module-level mutable bindings are shared by every call that omits the argument, and array and object literal defaults which the function mutates and then returns or stores on `this` outlive the call. Defaults which are only read, copied or created by a factory function are allowed.
+
+### Valid:
+```typescript
+function f(opts = {}) {
+  return { ...defaults, ...opts };
+}
+```
+```typescript
+function f(list = createList()) {}
+```
+
+### Invalid:
+```typescript
+const sharedArray = [];
+function f(x = sharedArray) {}
+```
+```typescript
+function f(list = []) {
+  list.push(x);
+  return list;
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoMutableDefaultParametersVisitor {
+      context,
+      mutable_consts: mutable_consts(module),
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn is_mutable_literal(expr: &Expr) -> bool {
+  matches!(unwrap_paren(expr), Expr::Array(_) | Expr::Object(_))
+}
+
+/// Collects the module-level `const` bindings of array and object literals.
+fn mutable_consts(module: &Module) -> HashSet<Id> {
+  let mut consts = HashSet::new();
+  for item in &module.body {
+    let var_decl = match item {
+      ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => var_decl,
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+        match &export_decl.decl {
+          Decl::Var(var_decl) => var_decl,
+          _ => continue,
+        }
+      }
+      _ => continue,
+    };
+    if var_decl.kind != VarDeclKind::Const {
+      continue;
+    }
+    for decl in &var_decl.decls {
+      if let (Pat::Ident(ident), Some(init)) = (&decl.name, &decl.init) {
+        if is_mutable_literal(init) {
+          consts.insert(ident.to_id());
+        }
+      }
+    }
+  }
+  consts
+}
+
+/// How a default literal escapes the function after being mutated.
+#[derive(Clone, Copy)]
+enum Escape {
+  Returned,
+  StoredOnThis,
+}
+
+/// Looks at the uses of a parameter in the body of its function. Returns
+/// in nested arrow functions are not counted, other nested functions are
+/// skipped.
+struct ParamUsage {
+  param: Id,
+  mutated: bool,
+  escape: Option<Escape>,
+  arrow_depth: usize,
+}
+
+impl ParamUsage {
+  fn is_param(&self, expr: &Expr) -> bool {
+    matches!(unwrap_paren(expr), Expr::Ident(ident) if ident.to_id() == self.param)
+  }
+
+  /// Checks if the expression is a member of the parameter, like `p.a` or
+  /// `p[0].b`.
+  fn is_param_member(&self, expr: &Expr) -> bool {
+    match unwrap_paren(expr) {
+      Expr::Member(_) => self.is_param(member_path(expr).0),
+      _ => false,
+    }
+  }
+
+  /// Checks if the value of the expression is the parameter, also through
+  /// sequence expressions like `(p.push(x), p)`.
+  fn evaluates_to_param(&self, expr: &Expr) -> bool {
+    match unwrap_paren(expr) {
+      Expr::Seq(seq) => {
+        matches!(seq.exprs.last(), Some(last) if self.evaluates_to_param(last))
+      }
+      expr => self.is_param(expr),
+    }
+  }
+
+  fn mark_escape(&mut self, escape: Escape) {
+    if self.escape.is_none() {
+      self.escape = Some(escape);
+    }
+  }
+}
+
+fn is_this_member(expr: &Expr) -> bool {
+  match expr {
+    Expr::Member(MemberExpr {
+      obj: ExprOrSuper::Expr(obj),
+      ..
+    }) => matches!(unwrap_paren(obj), Expr::This(_)),
+    _ => false,
+  }
+}
+
+impl Visit for ParamUsage {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Expr::Member(MemberExpr {
+        obj: ExprOrSuper::Expr(obj),
+        prop,
+        computed: false,
+        ..
+      }) = unwrap_paren(callee)
+      {
+        let method = match &**prop {
+          Expr::Ident(ident) => &*ident.sym,
+          _ => "",
+        };
+        if MUTATING_METHODS.contains(&method) && self.is_param(obj) {
+          self.mutated = true;
+        }
+        // `Object.assign(p, ...)`
+        if method == "assign"
+          && matches!(unwrap_paren(obj), Expr::Ident(ident) if ident.sym == *"Object")
+          && matches!(call_expr.args.first(), Some(arg) if self.is_param(&arg.expr))
+        {
+          self.mutated = true;
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, parent: &dyn Node) {
+    let left = match &assign_expr.left {
+      PatOrExpr::Expr(expr) => Some(&**expr),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => Some(&**expr),
+        _ => None,
+      },
+    };
+    if let Some(left) = left {
+      if self.is_param_member(left) {
+        self.mutated = true;
+      }
+      if is_this_member(left) && self.evaluates_to_param(&assign_expr.right) {
+        self.mark_escape(Escape::StoredOnThis);
+      }
+    }
+    swc_ecmascript::visit::visit_assign_expr(self, assign_expr, parent);
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, parent: &dyn Node) {
+    if self.is_param_member(&update_expr.arg) {
+      self.mutated = true;
+    }
+    swc_ecmascript::visit::visit_update_expr(self, update_expr, parent);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, parent: &dyn Node) {
+    if unary_expr.op == UnaryOp::Delete && self.is_param_member(&unary_expr.arg)
+    {
+      self.mutated = true;
+    }
+    swc_ecmascript::visit::visit_unary_expr(self, unary_expr, parent);
+  }
+
+  fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt, parent: &dyn Node) {
+    if self.arrow_depth == 0 {
+      if let Some(arg) = &return_stmt.arg {
+        if self.evaluates_to_param(arg) {
+          self.mark_escape(Escape::Returned);
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_return_stmt(self, return_stmt, parent);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+    self.arrow_depth += 1;
+    swc_ecmascript::visit::visit_arrow_expr(self, arrow_expr, parent);
+    self.arrow_depth -= 1;
+  }
+
+  fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
+}
+
+struct NoMutableDefaultParametersVisitor {
+  context: Arc<Context>,
+  mutable_consts: HashSet<Id>,
+}
+
+impl NoMutableDefaultParametersVisitor {
+  /// Returns the module-level mutable binding the default refers to.
+  fn shared_binding<'a>(&self, default: &'a Expr) -> Option<&'a Ident> {
+    let ident = match unwrap_paren(default) {
+      Expr::Ident(ident) => ident,
+      _ => return None,
+    };
+    let var = self.context.scope_manager.binding(&ident.to_id())?;
+    if !var.path().is_empty() {
+      return None;
+    }
+    match var.kind() {
+      BindingKind::Let | BindingKind::Var => Some(ident),
+      BindingKind::Const if self.mutable_consts.contains(&ident.to_id()) => {
+        Some(ident)
+      }
+      _ => None,
+    }
+  }
+
+  /// Checks a parameter with a default, where `visit_body` looks at the
+  /// uses of the parameter in the body of the function.
+  fn check_param<F>(&self, assign_pat: &AssignPat, visit_body: F)
+  where
+    F: FnOnce(&mut ParamUsage),
+  {
+    let name = match &*assign_pat.left {
+      Pat::Ident(ident) => ident,
+      _ => return,
+    };
+
+    if let Some(shared) = self.shared_binding(&assign_pat.right) {
+      self.context.add_diagnostic_with_hint(
+        assign_pat.right.span(),
+        "no-mutable-default-parameters",
+        &format!(
+          "The default of `{}` aliases the module-level mutable binding `{}`",
+          name.sym, shared.sym
+        ),
+        &format!(
+          "Every call without the argument shares `{}` and can change it; copy it, or create the default with a factory function",
+          shared.sym
+        ),
+      );
+      return;
+    }
+
+    if !is_mutable_literal(&assign_pat.right) {
+      return;
+    }
+    let mut usage = ParamUsage {
+      param: name.to_id(),
+      mutated: false,
+      escape: None,
+      arrow_depth: 0,
+    };
+    visit_body(&mut usage);
+    let escape = match (usage.mutated, usage.escape) {
+      (true, Some(escape)) => escape,
+      _ => return,
+    };
+    let reason = match escape {
+      Escape::Returned => "returned",
+      Escape::StoredOnThis => "stored on `this`",
+    };
+    self.context.add_diagnostic_with_hint(
+      assign_pat.right.span(),
+      "no-mutable-default-parameters",
+      &format!(
+        "The default of `{}` is mutated and {} by the function",
+        name.sym, reason
+      ),
+      "Callers which omit the argument get back a value the function changed; create the value in the function body instead of mutating the parameter",
+    );
+  }
+}
+
+impl Visit for NoMutableDefaultParametersVisitor {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, function: &Function, parent: &dyn Node) {
+    if let Some(body) = &function.body {
+      for param in &function.params {
+        if let Pat::Assign(assign_pat) = &param.pat {
+          self.check_param(assign_pat, |usage| body.visit_with(body, usage));
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_function(self, function, parent);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+    for param in &arrow_expr.params {
+      if let Pat::Assign(assign_pat) = param {
+        match &arrow_expr.body {
+          BlockStmtOrExpr::BlockStmt(body) => {
+            self.check_param(assign_pat, |usage| body.visit_with(body, usage))
+          }
+          BlockStmtOrExpr::Expr(expr) => {
+            self.check_param(assign_pat, |usage| {
+              // An expression body is returned.
+              if usage.evaluates_to_param(expr) {
+                usage.mark_escape(Escape::Returned);
+              }
+              expr.visit_with(&**expr, usage);
+            })
+          }
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_arrow_expr(self, arrow_expr, parent);
+  }
+
+  fn visit_constructor(
+    &mut self,
+    constructor: &Constructor,
+    parent: &dyn Node,
+  ) {
+    if let Some(body) = &constructor.body {
+      for param in &constructor.params {
+        let assign_pat = match param {
+          ParamOrTsParamProp::Param(param) => match &param.pat {
+            Pat::Assign(assign_pat) => assign_pat,
+            _ => continue,
+          },
+          ParamOrTsParamProp::TsParamProp(prop) => match &prop.param {
+            TsParamPropParam::Assign(assign_pat) => assign_pat,
+            _ => continue,
+          },
+        };
+        self.check_param(assign_pat, |usage| body.visit_with(body, usage));
+      }
+    }
+    swc_ecmascript::visit::visit_constructor(self, constructor, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_mutable_default_parameters_valid() {
+    assert_lint_ok_n::<NoMutableDefaultParameters>(vec![
+      "function f(opts = {}) { return { ...defaults, ...opts }; }",
+      "function f(opts = {}) { opts.cache = true; return { ...opts }; }",
+      "function f(opts = {}) { opts.cache = true; use(opts); }",
+      "function f(list = []) { return list.concat(x); }",
+      "function f(list = []) { list.push(x); return list.length; }",
+      "function f(x = createDefault()) { x.push(1); return x; }",
+      "function f(x = 1) { return x; }",
+      "const LIMIT = 10;\nfunction f(x = LIMIT) {}",
+      "const DEFAULTS = Object.freeze({ a: 1 });\nfunction f(x = DEFAULTS) {}",
+      "function f(x = inner) { let inner = []; }",
+      "function g() { let local = []; function f(x = local) {} }",
+      // Returned from a nested function, not the function itself.
+      "function f(list = []) { list.push(1); return () => { return list; }; }",
+      "function f(list = []) { return function () { list.push(1); return list; }; }",
+    ]);
+  }
+
+  #[test]
+  fn no_mutable_default_parameters_invalid() {
+    assert_lint_err_on_line::<NoMutableDefaultParameters>(
+      "const sharedArray = [];\nfunction f(x = sharedArray) {}",
+      2,
+      15,
+    );
+    assert_lint_err_on_line::<NoMutableDefaultParameters>(
+      "let cache = new Map();\nconst f = (c = cache) => c;",
+      2,
+      15,
+    );
+    assert_lint_err_on_line::<NoMutableDefaultParameters>(
+      "export var state = { count: 0 };\nclass A { m(s = state) {} }",
+      2,
+      16,
+    );
+    assert_lint_err::<NoMutableDefaultParameters>(
+      "function f(list = []) { list.push(x); return list; }",
+      18,
+    );
+    assert_lint_err::<NoMutableDefaultParameters>(
+      "function f(opts = {}) { opts.cache = true; if (a) { return opts; } }",
+      18,
+    );
+    assert_lint_err::<NoMutableDefaultParameters>(
+      "const f = (list = []) => (list.push(1), list);",
+      18,
+    );
+    assert_lint_err::<NoMutableDefaultParameters>(
+      "function f(list = []) { xs.forEach((x) => list.push(x)); return list; }",
+      18,
+    );
+    assert_lint_err::<NoMutableDefaultParameters>(
+      "function f(o = {}) { Object.assign(o, extra); return o; }",
+      15,
+    );
+    assert_lint_err::<NoMutableDefaultParameters>(
+      "class A { constructor(items = []) { items.push(1); this.items = items; } }",
+      30,
+    );
+    assert_lint_err::<NoMutableDefaultParameters>(
+      "class A { constructor(private items = []) { items.sort(); this.sorted = items; } }",
+      38,
+    );
+  }
+
+  #[test]
+  fn no_mutable_default_parameters_messages() {
+    let cases = vec![
+      (
+        "let shared = [];\nfunction f(x = shared) {}",
+        "The default of `x` aliases the module-level mutable binding `shared`",
+      ),
+      (
+        "function f(list = []) { list.push(x); return list; }",
+        "The default of `list` is mutated and returned by the function",
+      ),
+      (
+        "function F(o = {}) { o.a = 1; this.o = o; }",
+        "The default of `o` is mutated and stored on `this` by the function",
+      ),
+    ];
+    for (source, message) in cases {
+      let diagnostics = lint(NoMutableDefaultParameters::new(), source);
+      assert_eq!(diagnostics.len(), 1, "{}", source);
+      assert_eq!(diagnostics[0].message, message);
+    }
+  }
+}
@@ -1,6 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::media_type::MediaType;
 use swc_ecmascript::ast::{TsModuleDecl, TsModuleName};
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
@@ -18,11 +19,40 @@ impl LintRule for NoNamespace {
     "no-namespace"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows TypeScript namespaces and internal modules.
+
+ES modules are the standard way of organizing code; namespaces are a TypeScript-specific, legacy alternative. `declare global {}` and ambient module declarations with a string name are allowed, and so are ambient namespaces in declaration files (`.d.ts`), which describe the globals of scripts.
+
+### Valid:
+```typescript
+declare global {}
+```
+```typescript
+declare module "foo" {}
+```
+
+### Invalid:
+```typescript
+namespace foo {}
+```
+```typescript
+declare module foo {}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     module: &swc_ecmascript::ast::Module,
   ) {
+    if !context.media_type.is_typescript() {
+      return;
+    }
     let mut visitor = NoNamespaceVisitor::new(context);
     visitor.visit_module(module, module);
   }
@@ -30,11 +60,16 @@ impl LintRule for NoNamespace {
 
 struct NoNamespaceVisitor {
   context: Arc<Context>,
+  /// Whether the visited node is inside a `declare`d namespace or module.
+  in_ambient: bool,
 }
 
 impl NoNamespaceVisitor {
   fn new(context: Arc<Context>) -> Self {
-    Self { context }
+    Self {
+      context,
+      in_ambient: false,
+    }
   }
 }
 
@@ -44,18 +79,24 @@ impl Visit for NoNamespaceVisitor {
     mod_decl: &TsModuleDecl,
     parent: &dyn Node,
   ) {
-    if !mod_decl.global {
+    let is_ambient = self.in_ambient || mod_decl.declare;
+    let is_allowed = is_ambient && self.context.media_type == MediaType::Dts;
+    if !mod_decl.global && !is_allowed {
       if let TsModuleName::Ident(_) = mod_decl.id {
-        self.context.add_diagnostic(
+        self.context.add_diagnostic_with_hint(
           mod_decl.span,
           "no-namespace",
           "custom typescript modules are outdated",
+          "Use ES modules, ie. `import` and `export`, instead",
         );
       }
     }
+    let was_ambient = self.in_ambient;
+    self.in_ambient = is_ambient;
     for stmt in &mod_decl.body {
       self.visit_ts_namespace_body(stmt, parent);
     }
+    self.in_ambient = was_ambient;
   }
 }
 
@@ -80,4 +121,39 @@ mod tests {
       vec![0, 20],
     );
   }
+
+  #[test]
+  fn no_namespace_dts() {
+    for source in &[
+      "declare namespace foo {}",
+      "declare module foo {}",
+      "declare namespace foo { namespace bar {} }",
+      "declare namespace Foo.Bar { const a: number; }",
+      "declare module 'foo' { namespace bar {} }",
+    ] {
+      assert!(
+        lint_as(NoNamespace::new(), "foo.d.ts", source).is_empty(),
+        "{}",
+        source
+      );
+    }
+
+    let diagnostics =
+      lint_as(NoNamespace::new(), "foo.d.ts", "namespace foo {}");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 0);
+
+    let diagnostics =
+      lint_as(NoNamespace::new(), "foo.ts", "declare namespace foo {}");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use ES modules, ie. `import` and `export`, instead")
+    );
+  }
+
+  #[test]
+  fn no_namespace_javascript() {
+    assert!(lint_as(NoNamespace::new(), "foo.js", "const a = 1;").is_empty());
+  }
 }
@@ -0,0 +1,155 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::sync::Arc;
+use swc_ecmascript::ast::{Expr, ExprStmt, ParenExpr};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+pub struct NoNewOptions {
+  /// Constructors that are known to be called for their side effects,
+  /// eg. `new Worker(url)` starts a worker even if it's never used.
+  pub allow: Vec<String>,
+}
+
+impl Default for NoNewOptions {
+  fn default() -> Self {
+    Self {
+      allow: vec!["Worker".to_string(), "MutationObserver".to_string()],
+    }
+  }
+}
+
+pub struct NoNew {
+  options: NoNewOptions,
+}
+
+impl NoNew {
+  pub fn with_options(options: NoNewOptions) -> Box<Self> {
+    Box::new(NoNew { options })
+  }
+}
+
+impl LintRule for NoNew {
+  fn new() -> Box<Self> {
+    Self::with_options(NoNewOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-new"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows `new` expressions whose result is discarded.
+
+A constructor called only for its side effects is better written as a function.
+
+### Options
+
+`NoNewOptions::allow`: the constructors which are known to be called for their side effects, `["Worker", "MutationObserver"]` by default.
+
+### Valid:
+```typescript
+const foo = new Foo();
+```
+```typescript
+new Worker(url);
+```
+
+### Invalid:
+```typescript
+new Foo();
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = NoNewVisitor::new(context, &self.options.allow);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoNewVisitor<'a> {
+  context: Arc<Context>,
+  allow: &'a [String],
+}
+
+impl<'a> NoNewVisitor<'a> {
+  fn new(context: Arc<Context>, allow: &'a [String]) -> Self {
+    Self { context, allow }
+  }
+}
+
+impl<'a> Visit for NoNewVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt, parent: &dyn Node) {
+    let mut expr = &*expr_stmt.expr;
+    while let Expr::Paren(ParenExpr { expr: inner, .. }) = expr {
+      expr = inner;
+    }
+
+    if let Expr::New(new_expr) = expr {
+      let allowed = match &*new_expr.callee {
+        Expr::Ident(ident) => self.allow.iter().any(|name| ident.sym == **name),
+        _ => false,
+      };
+      if !allowed {
+        self.context.add_diagnostic_with_hint(
+          expr_stmt.span,
+          "no-new",
+          "Do not use `new` for side effects",
+          "Assign the created object to a variable, or call a function instead",
+        );
+      }
+    }
+
+    swc_ecmascript::visit::visit_expr_stmt(self, expr_stmt, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_new_valid() {
+    assert_lint_ok_n::<NoNew>(vec![
+      "const foo = new Foo();",
+      "foo = new Foo();",
+      "foo(new Foo());",
+      "const bar = new Foo().bar;",
+      "new Worker(url);",
+      "new MutationObserver(callback);",
+      "function f() { return new Foo(); }",
+    ]);
+  }
+
+  #[test]
+  fn no_new_invalid() {
+    assert_lint_err::<NoNew>("new Foo();", 0);
+    assert_lint_err::<NoNew>("(new Foo());", 0);
+    assert_lint_err::<NoNew>("new foo.Bar(1, 2);", 0);
+    assert_lint_err_on_line::<NoNew>("function f() {\n  new Foo();\n}", 2, 2);
+  }
+
+  #[test]
+  fn no_new_allow_option() {
+    let rule = || {
+      NoNew::with_options(NoNewOptions {
+        allow: vec!["Vue".to_string()],
+      })
+    };
+    assert_lint_ok_with(rule(), "new Vue({ el: '#app' });");
+    assert_lint_err_on_line_n_with(rule(), "new Worker(url);", vec![(1, 0)]);
+  }
+}
@@ -0,0 +1,148 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{BinExpr, BinaryOp, Expr};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  /// Reports arithmetic directly on two `new Date(...)` expressions, which
+  /// relies on the dates being converted to numbers implicitly (or, for `+`,
+  /// concatenates them as strings).
+  NoNewDateArithmetic, "no-new-date-arithmetic", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows arithmetic directly on two `new Date()` expressions.
+
+The subtraction relies on the dates being converted to numbers implicitly, and `+` concatenates them as strings. Call `.getTime()` to make the conversion explicit.
+
+### Valid:
+```typescript
+const ms = new Date(b).getTime() - new Date(a).getTime();
+```
+
+### Invalid:
+```typescript
+const ms = new Date(b) - new Date(a);
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = NoNewDateArithmeticVisitor::new(context);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoNewDateArithmeticVisitor {
+  context: Arc<Context>,
+}
+
+impl NoNewDateArithmeticVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self { context }
+  }
+
+  fn is_new_date(&self, expr: &Expr) -> bool {
+    match expr {
+      Expr::Paren(paren) => self.is_new_date(&paren.expr),
+      Expr::New(new_expr) => {
+        matches!(&*new_expr.callee, Expr::Ident(ident) if self.context.is_global(ident, "Date"))
+      }
+      _ => false,
+    }
+  }
+
+  fn with_get_time(&self, expr: &Expr) -> String {
+    let text = self.context.span_text(expr.span());
+    if let Expr::New(new_expr) = expr {
+      // `new Date.getTime()` would call `Date.getTime` instead.
+      if new_expr.args.is_none() {
+        return format!("new {}().getTime()", text.trim_start_matches("new "));
+      }
+    }
+    format!("{}.getTime()", text)
+  }
+}
+
+impl Visit for NoNewDateArithmeticVisitor {
+  noop_visit_type!();
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, parent: &dyn Node) {
+    let is_arithmetic = matches!(
+      bin_expr.op,
+      BinaryOp::Add
+        | BinaryOp::Sub
+        | BinaryOp::Mul
+        | BinaryOp::Div
+        | BinaryOp::Mod
+    );
+    if is_arithmetic
+      && self.is_new_date(&bin_expr.left)
+      && self.is_new_date(&bin_expr.right)
+    {
+      self.context.add_diagnostic_with_hint(
+        bin_expr.span,
+        "no-new-date-arithmetic",
+        "Arithmetic on `Date` objects relies on implicit conversion",
+        &format!(
+          "Rewrite as `{} {} {}` to make the conversion explicit",
+          self.with_get_time(&bin_expr.left),
+          bin_expr.op,
+          self.with_get_time(&bin_expr.right)
+        ),
+      );
+    }
+    swc_ecmascript::visit::visit_bin_expr(self, bin_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_new_date_arithmetic_valid() {
+    assert_lint_ok_n::<NoNewDateArithmetic>(vec![
+      "const ms = new Date(b).getTime() - new Date(a).getTime();",
+      "const ms = b - a;",
+      "const ms = new Date(b) - start;",
+      "const ms = new MyDate(b) - new MyDate(a);",
+      "const later = new Date(b) > new Date(a);",
+      "function f(Date) { return new Date(b) - new Date(a); }",
+    ]);
+  }
+
+  #[test]
+  fn no_new_date_arithmetic_invalid() {
+    assert_lint_err::<NoNewDateArithmetic>(
+      "const ms = new Date(b) - new Date(a);",
+      11,
+    );
+    assert_lint_err::<NoNewDateArithmetic>(
+      "const ms = (new Date(b)) + new Date;",
+      11,
+    );
+    assert_lint_err_on_line::<NoNewDateArithmetic>(
+      "const days =\n  (new Date(end) - new Date(start)) / 86400000;",
+      2,
+      3,
+    );
+
+    let diagnostics = lint(
+      NoNewDateArithmetic::new(),
+      "const ms = new Date(b) - new Date;",
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Rewrite as `new Date(b).getTime() - new Date().getTime()` to make the conversion explicit")
+    );
+  }
+}
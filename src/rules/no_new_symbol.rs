@@ -19,6 +19,26 @@ impl LintRule for NoNewSymbol {
     "no-new-symbol"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows using `new` with `Symbol`.
+
+`Symbol` is not a constructor; `new Symbol()` throws a `TypeError`.
+
+### Valid:
+```typescript
+const foo = Symbol("foo");
+```
+
+### Invalid:
+```typescript
+const foo = new Symbol("foo");
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
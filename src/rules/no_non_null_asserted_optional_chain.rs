@@ -19,6 +19,26 @@ impl LintRule for NoNonNullAssertedOptionalChain {
     "no-non-null-asserted-optional-chain"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Disallows non-null assertions after optional chaining.
+
+Optional chains return `undefined` by design, so asserting that they don't is wrong.
+
+### Valid:
+```typescript
+foo?.bar;
+foo.bar!;
+```
+
+### Invalid:
+```typescript
+foo?.bar!;
+```
+```typescript
+foo?.bar()!;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
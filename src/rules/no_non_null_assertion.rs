@@ -16,11 +16,30 @@ impl LintRule for NoNonNullAssertion {
     "no-non-null-assertion"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Disallows non-null assertions with the `!` operator.
+
+Non-null assertions silence the type checker instead of handling the `null` or `undefined` case. Every assertion of a chain like `foo!.bar!` is reported.
+
+### Valid:
+```typescript
+instance?.doWork();
+```
+
+### Invalid:
+```typescript
+instance!.doWork();
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     module: &swc_ecmascript::ast::Module,
   ) {
+    if !context.media_type.is_typescript() {
+      return;
+    }
     let mut visitor = NoNonNullAssertionVisitor::new(context);
     visitor.visit_module(module, module);
   }
@@ -40,13 +59,15 @@ impl Visit for NoNonNullAssertionVisitor {
   fn visit_ts_non_null_expr(
     &mut self,
     non_null_expr: &swc_ecmascript::ast::TsNonNullExpr,
-    _parent: &dyn Node,
+    parent: &dyn Node,
   ) {
-    self.context.add_diagnostic(
+    self.context.add_diagnostic_with_hint(
       non_null_expr.span,
       "no-non-null-assertion",
       "do not use non-null assertion",
+      "Handle the `null` or `undefined` case, eg. with optional chaining (`?.`)",
     );
+    swc_ecmascript::visit::visit_ts_non_null_expr(self, non_null_expr, parent);
   }
 }
 
@@ -74,10 +95,10 @@ mod tests {
     assert_lint_err::<NoNonNullAssertion>("x.y.z!?.();", 0);
     assert_lint_err::<NoNonNullAssertion>("x!?.y.z;", 0);
     assert_lint_err::<NoNonNullAssertion>("x!?.[y].z;", 0);
-    assert_lint_err::<NoNonNullAssertion>("x.y.z!!();", 0);
-    assert_lint_err::<NoNonNullAssertion>("x.y!!;", 0);
-    assert_lint_err::<NoNonNullAssertion>("x!!.y;", 0);
-    assert_lint_err::<NoNonNullAssertion>("x!!!;", 0);
+    assert_lint_err_n::<NoNonNullAssertion>("x.y.z!!();", vec![0, 0]);
+    assert_lint_err_n::<NoNonNullAssertion>("x.y!!;", vec![0, 0]);
+    assert_lint_err_n::<NoNonNullAssertion>("x!!.y;", vec![0, 0]);
+    assert_lint_err_n::<NoNonNullAssertion>("x!!!;", vec![0, 0, 0]);
     assert_lint_err::<NoNonNullAssertion>("x.y?.z!();", 0);
     assert_lint_err::<NoNonNullAssertion>("x.y.z!();", 0);
     assert_lint_err::<NoNonNullAssertion>("x![y]?.z;", 0);
@@ -88,4 +109,24 @@ mod tests {
     assert_lint_err::<NoNonNullAssertion>("x!.y;", 0);
     assert_lint_err::<NoNonNullAssertion>("x!;", 0);
   }
+
+  #[test]
+  fn chained_assertions() {
+    assert_lint_err_n::<NoNonNullAssertion>("foo!.bar!.baz;", vec![0, 0]);
+    assert_lint_err_n::<NoNonNullAssertion>("foo.bar!.baz!();", vec![0, 0]);
+    assert_lint_err_n::<NoNonNullAssertion>("a![b!];", vec![0, 3]);
+    assert_lint_err_n::<NoNonNullAssertion>("f(a!, b!.c);", vec![2, 6]);
+    assert_lint_err_on_line::<NoNonNullAssertion>(
+      "function foo() {\n  return () => bar!;\n}",
+      2,
+      15,
+    );
+  }
+
+  #[test]
+  fn javascript() {
+    assert!(
+      lint_as(NoNonNullAssertion::new(), "foo.js", "const a = b;").is_empty()
+    );
+  }
 }
@@ -0,0 +1,122 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::{find_legacy_escapes, LegacyEscape};
+use std::sync::Arc;
+use swc_common::BytePos;
+use swc_ecmascript::ast::{Str, TplElement};
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  NoNonoctalDecimalEscape, "no-nonoctal-decimal-escape", tags: [recommended];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows the escape sequences `\8` and `\9` in string literals.
+
+They are equivalent to the digits themselves and only allowed for legacy reasons.
+
+### Valid:
+```typescript
+const a = "8";
+const b = "9";
+```
+
+### Invalid:
+```typescript
+const a = "\8";
+```
+```typescript
+const b = "foo\9";
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = NoNonoctalDecimalEscapeVisitor::new(context);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoNonoctalDecimalEscapeVisitor {
+  context: Arc<Context>,
+}
+
+impl NoNonoctalDecimalEscapeVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self { context }
+  }
+}
+
+impl Visit for NoNonoctalDecimalEscapeVisitor {
+  // `\8` and `\9` are a syntax error in untagged templates, tagged
+  // templates can read the raw text.
+  fn visit_tpl_element(
+    &mut self,
+    _tpl_element: &TplElement,
+    _parent: &dyn Node,
+  ) {
+  }
+
+  fn visit_str(&mut self, str: &Str, _parent: &dyn Node) {
+    let raw = self.context.span_text(str.span);
+    for escape in find_legacy_escapes(&raw) {
+      if let LegacyEscape::NonOctalDecimal { offset, digit } = escape {
+        let lo = str.span.lo() + BytePos(offset as u32);
+        let span = str.span.with_lo(lo).with_hi(lo + BytePos(2));
+        self.context.add_diagnostic_with_hint(
+          span,
+          "no-nonoctal-decimal-escape",
+          &format!("Don't use `\\{}` escape sequence", digit),
+          &format!(
+            "Use `{}` to keep the digit, or `\\u{:04X}` to make the code point explicit",
+            digit, digit as u32
+          ),
+        );
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_nonoctal_decimal_escape_valid() {
+    assert_lint_ok_n::<NoNonoctalDecimalEscape>(vec![
+      r#"const a = "8";"#,
+      r#"const a = "\\8";"#,
+      r#"const a = "\0";"#,
+      r#"const a = "\08";"#,
+      r#"const a = `8`;"#,
+      r#"const a = String.raw`\8`;"#,
+    ]);
+  }
+
+  #[test]
+  fn no_nonoctal_decimal_escape_invalid() {
+    assert_lint_err::<NoNonoctalDecimalEscape>(r#"const a = "\8";"#, 11);
+    assert_lint_err::<NoNonoctalDecimalEscape>(r#"const a = 'foo\9';"#, 14);
+    assert_lint_err::<NoNonoctalDecimalEscape>(r#"const a = "\\\8";"#, 13);
+    assert_lint_err_n::<NoNonoctalDecimalEscape>(
+      r#"const a = "\8" + "\9";"#,
+      vec![11, 18],
+    );
+  }
+
+  #[test]
+  fn no_nonoctal_decimal_escape_hint() {
+    let diagnostics =
+      lint_tsx(NoNonoctalDecimalEscape::new(), r#"const a = "\9";"#);
+    assert_eq!(diagnostics[0].message, "Don't use `\\9` escape sequence");
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use `9` to keep the digit, or `\\u0039` to make the code point explicit")
+    );
+  }
+}
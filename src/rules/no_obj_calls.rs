@@ -23,6 +23,30 @@ impl LintRule for NoObjCalls {
     "no-obj-calls"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows calling global objects like `Math`, `JSON`, `Reflect` and `Atomics` as functions or constructors.
+
+These objects only hold static members; calling them throws a `TypeError`.
+
+### Valid:
+```typescript
+Math.PI * 2 * 3;
+JSON.parse("{}");
+```
+
+### Invalid:
+```typescript
+Math();
+```
+```typescript
+new JSON();
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
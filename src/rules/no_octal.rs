@@ -18,6 +18,27 @@ impl LintRule for NoOctal {
     "no-octal"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows legacy octal literals like `07`.
+
+The leading zero is easy to miss, and legacy octal literals are a syntax error in strict mode. Use the `0o` prefix instead.
+
+### Valid:
+```typescript
+const a = 7;
+const b = 0o7;
+```
+
+### Invalid:
+```typescript
+const a = 07;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -44,17 +65,16 @@ impl Visit for NoOctalVisitor {
       static ref OCTAL: regex::Regex = regex::Regex::new(r"^0[0-9]").unwrap();
     }
 
-    let raw_number = self
-      .context
-      .source_map
-      .span_to_snippet(literal_num.span)
-      .expect("error in loading snippet");
+    // The parsed value is the same for `0644` and `0o644`, only the raw
+    // text tells the legacy form apart.
+    let raw_number = self.context.span_text(literal_num.span);
 
     if OCTAL.is_match(&raw_number) {
-      self.context.add_diagnostic(
+      self.context.add_diagnostic_with_hint(
         literal_num.span,
         "no-octal",
         "`Octal number` is not allowed",
+        &format!("Use `0o{}` instead", raw_number.trim_start_matches('0')),
       );
     }
   }
@@ -67,16 +87,31 @@ mod tests {
 
   #[test]
   fn test_literal_octal() {
-    assert_lint_err::<NoOctal>("07", 0);
+    // Legacy octal literals aren't allowed in modules
+    with_syntax_errors(|| {
+      assert_lint_err::<NoOctal>("07", 0);
+    });
   }
 
   #[test]
   fn test_operand_octal() {
-    assert_lint_err::<NoOctal>("let x = 7 + 07", 12);
+    with_syntax_errors(|| {
+      assert_lint_err::<NoOctal>("let x = 7 + 07", 12);
+    });
   }
 
   #[test]
   fn test_octals_valid() {
-    assert_lint_ok_n::<NoOctal>(vec!["7", "\"07\"", "0x08", "-0.01"]);
+    assert_lint_ok_n::<NoOctal>(vec![
+      "7", "\"07\"", "0x08", "-0.01", "0o644", "0", "0.5",
+    ]);
+  }
+
+  #[test]
+  fn test_octal_hint() {
+    with_syntax_errors(|| {
+      let diagnostics = lint_tsx(NoOctal::new(), "const mode = 0644;");
+      assert_eq!(diagnostics[0].hint.as_deref(), Some("Use `0o644` instead"));
+    });
   }
 }
@@ -0,0 +1,128 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::{find_legacy_escapes, LegacyEscape};
+use std::sync::Arc;
+use swc_common::BytePos;
+use swc_ecmascript::ast::{Str, TplElement};
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  /// Module code is strict, so the parser rejects most octal escapes before
+  /// this rule runs; the ones that get through are `\0` followed by `8`
+  /// or `9`, eg. `"\08"`, which is `"\0"` followed by the digit.
+  NoOctalEscape, "no-octal-escape", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows octal escape sequences in string literals.
+
+Module code is strict, so the parser rejects most octal escapes; the ones that get through are `\0` followed by `8` or `9`, which is `"\0"` followed by the digit and easy to misread. Use a `\u` escape instead.
+
+### Valid:
+```typescript
+const a = "\u0000" + "8";
+```
+
+### Invalid:
+```typescript
+const a = "\08";
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = NoOctalEscapeVisitor::new(context);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoOctalEscapeVisitor {
+  context: Arc<Context>,
+}
+
+impl NoOctalEscapeVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self { context }
+  }
+}
+
+impl Visit for NoOctalEscapeVisitor {
+  // Octal escapes are a syntax error in untagged templates and the parser
+  // already reports them, tagged templates can read the raw text.
+  fn visit_tpl_element(
+    &mut self,
+    _tpl_element: &TplElement,
+    _parent: &dyn Node,
+  ) {
+  }
+
+  fn visit_str(&mut self, str: &Str, _parent: &dyn Node) {
+    let raw = self.context.span_text(str.span);
+    for escape in find_legacy_escapes(&raw) {
+      if let LegacyEscape::Octal { offset, len, value } = escape {
+        let lo = str.span.lo() + BytePos(offset as u32);
+        let span = str.span.with_lo(lo).with_hi(lo + BytePos(len as u32));
+        self.context.add_diagnostic_with_hint(
+          span,
+          "no-octal-escape",
+          &format!(
+            "Don't use octal escape sequence `{}`",
+            &raw[offset..offset + len]
+          ),
+          &format!("Use `\\u{:04X}` instead", value),
+        );
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_octal_escape_valid() {
+    assert_lint_ok_n::<NoOctalEscape>(vec![
+      r#"const a = "©";"#,
+      r#"const a = "\xA9";"#,
+      r#"const a = "\u00A9";"#,
+      r#"const a = "\0";"#,
+      r#"const a = "a\0b";"#,
+      r#"const a = "\\251";"#,
+      r#"const a = "\\08";"#,
+      r#"const a = "\8";"#,
+      r#"const a = String.raw`\08`;"#,
+    ]);
+  }
+
+  #[test]
+  fn no_octal_escape_invalid() {
+    assert_lint_err::<NoOctalEscape>(r#"const a = "\08";"#, 11);
+    assert_lint_err::<NoOctalEscape>(r#"const a = 'foo \09 bar';"#, 15);
+    assert_lint_err::<NoOctalEscape>(r#"const a = "\\\08";"#, 13);
+    assert_lint_err_n::<NoOctalEscape>(
+      r#"const a = "\0" + "8" + "\08";"#,
+      vec![24],
+    );
+    assert_lint_err_n::<NoOctalEscape>(r#"const a = "\08\09";"#, vec![11, 14]);
+  }
+
+  #[test]
+  fn no_octal_escape_hint() {
+    let diagnostics = lint_tsx(NoOctalEscape::new(), r#"const a = "\08";"#);
+    assert_eq!(
+      diagnostics[0].message,
+      "Don't use octal escape sequence `\\0`"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use `\\u0000` instead")
+    );
+    assert_eq!(diagnostics[0].range.end.col, 13);
+  }
+}
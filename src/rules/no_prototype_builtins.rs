@@ -1,6 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::swc_util::constant_string;
 use std::sync::Arc;
 use swc_ecmascript::ast::CallExpr;
 use swc_ecmascript::ast::Expr;
@@ -10,7 +11,7 @@ use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 
 const BANNED_PROPERTIES: &[&str] =
-  &["hasOwnProperty", "isPrototypeOf", "propertyIsEnumberable"];
+  &["hasOwnProperty", "isPrototypeOf", "propertyIsEnumerable"];
 
 pub struct NoPrototypeBuiltins;
 
@@ -23,6 +24,33 @@ impl LintRule for NoPrototypeBuiltins {
     "no-prototype-builtins"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows calling `Object.prototype` methods like `hasOwnProperty` directly on objects.
+
+Objects may not inherit from `Object.prototype`, like `Object.create(null)`, or may shadow the method with a property of the same name. Calls through `Object.prototype`, eg. `Object.prototype.hasOwnProperty.call(foo, "bar")`, are allowed.
+
+### Valid:
+```typescript
+Object.prototype.hasOwnProperty.call(foo, "bar");
+Object.hasOwn(foo, "bar");
+```
+
+### Invalid:
+```typescript
+foo.hasOwnProperty("bar");
+```
+```typescript
+foo.isPrototypeOf(bar);
+```
+```typescript
+foo?.["propertyIsEnumerable"]("bar");
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -46,33 +74,47 @@ impl NoPrototypeBuiltinsVisitor {
 impl Visit for NoPrototypeBuiltinsVisitor {
   noop_visit_type!();
 
-  fn visit_call_expr(&mut self, call_expr: &CallExpr, _parent: &dyn Node) {
-    let member_expr = match &call_expr.callee {
-      ExprOrSuper::Expr(boxed_expr) => match &**boxed_expr {
-        Expr::Member(member_expr) => {
-          if member_expr.computed {
-            return;
-          }
-          member_expr
-        }
-        _ => return,
-      },
-      ExprOrSuper::Super(_) => return,
-    };
-
-    if let Expr::Ident(ident) = &*member_expr.prop {
-      let prop_name = ident.sym.as_ref();
-      if BANNED_PROPERTIES.contains(&prop_name) {
-        self.context.add_diagnostic(
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let Some(method) = called_method(call_expr) {
+      if BANNED_PROPERTIES.contains(&method.as_str()) {
+        self.context.add_diagnostic_with_hint(
           call_expr.span,
           "no-prototype-builtins",
           &format!(
             "Access to Object.prototype.{} is not allowed from target object",
-            prop_name
+            method
           ),
+          &format!("Use `Object.prototype.{}.call(...)` instead", method),
         );
       }
     }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+/// Returns the name of the method called by `obj.method()`, `obj?.method()`
+/// or `obj["method"]()`, unless it's computed at runtime.
+fn called_method(call_expr: &CallExpr) -> Option<String> {
+  let mut callee = match &call_expr.callee {
+    ExprOrSuper::Expr(callee) => &**callee,
+    ExprOrSuper::Super(_) => return None,
+  };
+  loop {
+    match callee {
+      Expr::Paren(paren_expr) => callee = &paren_expr.expr,
+      Expr::OptChain(opt_chain_expr) => callee = &opt_chain_expr.expr,
+      _ => break,
+    }
+  }
+  match callee {
+    Expr::Member(member_expr) if member_expr.computed => {
+      constant_string(&member_expr.prop)
+    }
+    Expr::Member(member_expr) => match &*member_expr.prop {
+      Expr::Ident(ident) => Some(ident.sym.to_string()),
+      _ => None,
+    },
+    _ => None,
   }
 }
 
@@ -87,19 +129,19 @@ mod tests {
       r#"
   Object.prototype.hasOwnProperty.call(foo, "bar");
   Object.prototype.isPrototypeOf.call(foo, "bar");
-  Object.prototype.propertyIsEnumberable.call(foo, "bar");
+  Object.prototype.propertyIsEnumerable.call(foo, "bar");
   Object.prototype.hasOwnProperty.apply(foo, ["bar"]);
   Object.prototype.isPrototypeOf.apply(foo, ["bar"]);
-  Object.prototype.propertyIsEnumberable.apply(foo, ["bar"]);
+  Object.prototype.propertyIsEnumerable.apply(foo, ["bar"]);
   hasOwnProperty(foo, "bar");
   isPrototypeOf(foo, "bar");
-  propertyIsEnumberable(foo, "bar");
+  propertyIsEnumerable(foo, "bar");
   ({}.hasOwnProperty.call(foo, "bar"));
   ({}.isPrototypeOf.call(foo, "bar"));
-  ({}.propertyIsEnumberable.call(foo, "bar"));
+  ({}.propertyIsEnumerable.call(foo, "bar"));
   ({}.hasOwnProperty.apply(foo, ["bar"]));
   ({}.isPrototypeOf.apply(foo, ["bar"]));
-  ({}.propertyIsEnumberable.apply(foo, ["bar"]));
+  ({}.propertyIsEnumerable.apply(foo, ["bar"]));
       "#,
     );
   }
@@ -109,7 +151,7 @@ mod tests {
     assert_lint_err::<NoPrototypeBuiltins>(r#"foo.hasOwnProperty("bar");"#, 0);
     assert_lint_err::<NoPrototypeBuiltins>(r#"foo.isPrototypeOf("bar");"#, 0);
     assert_lint_err::<NoPrototypeBuiltins>(
-      r#"foo.propertyIsEnumberable("bar");"#,
+      r#"foo.propertyIsEnumerable("bar");"#,
       0,
     );
     assert_lint_err::<NoPrototypeBuiltins>(
@@ -117,4 +159,64 @@ mod tests {
       0,
     );
   }
+
+  #[test]
+  fn no_prototype_builtins_computed() {
+    assert_lint_ok_n::<NoPrototypeBuiltins>(vec![
+      "foo[hasOwnProperty]('bar');",
+      "foo[method]('bar');",
+      "foo['hasOwn' + suffix]('bar');",
+      "Object.prototype['hasOwnProperty'].call(foo, 'bar');",
+      "foo.hasOwnProperty;",
+    ]);
+    assert_lint_err::<NoPrototypeBuiltins>("foo['hasOwnProperty']('bar');", 0);
+    assert_lint_err::<NoPrototypeBuiltins>("foo[`isPrototypeOf`](bar);", 0);
+    assert_lint_err::<NoPrototypeBuiltins>(
+      "foo['propertyIsEnumerable']('bar');",
+      0,
+    );
+
+    let diagnostics =
+      lint(NoPrototypeBuiltins::new(), "foo['hasOwnProperty']('bar');");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Access to Object.prototype.hasOwnProperty is not allowed from target object"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use `Object.prototype.hasOwnProperty.call(...)` instead")
+    );
+  }
+
+  #[test]
+  fn no_prototype_builtins_optional_chaining() {
+    assert_lint_err::<NoPrototypeBuiltins>("foo?.hasOwnProperty('bar');", 0);
+    assert_lint_err::<NoPrototypeBuiltins>("foo?.['isPrototypeOf'](bar);", 0);
+    assert_lint_err::<NoPrototypeBuiltins>(
+      "foo.bar?.hasOwnProperty('baz');",
+      0,
+    );
+    assert_lint_err::<NoPrototypeBuiltins>("(foo?.hasOwnProperty)('bar');", 0);
+    assert_lint_ok::<NoPrototypeBuiltins>(
+      "Object.prototype.hasOwnProperty?.call(foo, 'bar');",
+    );
+  }
+
+  #[test]
+  fn no_prototype_builtins_nested() {
+    assert_lint_err::<NoPrototypeBuiltins>(
+      "if (foo(bar.hasOwnProperty('baz'))) {}",
+      8,
+    );
+    assert_lint_err_n::<NoPrototypeBuiltins>(
+      "a.hasOwnProperty(b.isPrototypeOf(c));",
+      vec![0, 17],
+    );
+    assert_lint_err_on_line::<NoPrototypeBuiltins>(
+      "function foo(obj) {\n  return () => obj.hasOwnProperty('a');\n}",
+      2,
+      15,
+    );
+  }
 }
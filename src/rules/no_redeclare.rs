@@ -21,6 +21,38 @@ impl LintRule for NoRedeclare {
     "no-redeclare"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows declaring the same variable more than once in a scope.
+
+A redeclaration with `var` silently reuses the existing variable, which is usually a mistake.
+
+### Valid:
+```typescript
+var a = 3;
+var b = function () {
+  var a = 10;
+};
+```
+```typescript
+var a = 3;
+a = 10;
+```
+
+### Invalid:
+```typescript
+var a = 3;
+var a = 10;
+```
+```typescript
+let b = 1;
+function b() {}
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = NoRedeclareVisitor {
       context,
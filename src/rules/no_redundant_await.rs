@@ -0,0 +1,360 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  AwaitExpr, BinaryOp, CallExpr, Expr, ExprOrSuper, Module, ParenExpr, Prop,
+  PropName, PropOrSpread, UnaryOp,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+declare_rule! {
+  /// Reports `await` applied to values which are definitely not promises,
+  /// like `await await p`, `await 1` or `await (() => {})`, and
+  /// `await Promise.resolve(x)` where `await x` does the same.
+  ///
+  /// Identifiers and calls are never reported since their value isn't known
+  /// without type information. Removing the `await` of a double await or a
+  /// literal is offered as a fix.
+  NoRedundantAwait, "no-redundant-await", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows `await` applied to values which are definitely not promises.
+
+`await await p`, `await 1` and `await (() => {})` await values which aren't promises, and `await Promise.resolve(x)` does the same as `await x`. Identifiers and calls are never reported since their value isn't known without type information. Removing the `await` of a double await or a literal is offered as a fix.
+
+### Valid:
+```typescript
+async function f() {
+  await p;
+  await fetchData();
+}
+```
+
+### Invalid:
+```typescript
+async function f() {
+  await await p;
+}
+```
+```typescript
+async function f() {
+  await 1;
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoRedundantAwaitVisitor { context };
+    module.visit_with(module, &mut visitor);
+  }
+}
+
+struct NoRedundantAwaitVisitor {
+  context: Arc<Context>,
+}
+
+impl NoRedundantAwaitVisitor {
+  fn check(&self, await_expr: &AwaitExpr) {
+    let (message, hint, fixable) = match unparen(&await_expr.arg) {
+      Expr::Await(_) => (
+        "Awaiting the result of `await` is redundant",
+        "Remove the extra `await`",
+        true,
+      ),
+      Expr::Lit(_) | Expr::Tpl(_) => (
+        "Awaiting a literal is redundant",
+        "Remove the `await`",
+        true,
+      ),
+      Expr::Ident(ident) if self.context.is_global(ident, "undefined") => (
+        "Awaiting `undefined` is redundant",
+        "Remove the `await`",
+        true,
+      ),
+      Expr::Array(_) => (
+        "Awaiting an array literal doesn't wait for its elements",
+        "Use `await Promise.all([...])` to wait for all the elements",
+        false,
+      ),
+      Expr::Object(object) if !has_then_property(&object.props) => (
+        "Awaiting an object literal is redundant",
+        "Remove the `await`",
+        false,
+      ),
+      Expr::Arrow(_) | Expr::Fn(_) | Expr::Class(_) => (
+        "Awaiting a function doesn't call it",
+        "Call the function and await its result: `await (...)()`",
+        false,
+      ),
+      Expr::Unary(unary) if unary.op == UnaryOp::TypeOf => (
+        "Awaiting `typeof` is redundant, it always results in a string",
+        "Remove the `await`",
+        false,
+      ),
+      Expr::Unary(unary) if unary.op == UnaryOp::Void => (
+        "Awaiting `void` is redundant, it always results in `undefined`",
+        "Remove the `await` or await the operand of `void`",
+        false,
+      ),
+      Expr::Bin(bin) if is_comparison(bin.op) => (
+        "Awaiting a comparison is redundant, it always results in a boolean",
+        "Await the operands of the comparison instead",
+        false,
+      ),
+      Expr::Call(call_expr) if self.is_promise_resolve(call_expr) => (
+        "Wrapping the awaited value in `Promise.resolve()` is redundant",
+        "Await the value directly",
+        false,
+      ),
+      _ => return,
+    };
+
+    if fixable {
+      // `await` and the whitespace or comments up to its operand, which
+      // starts with its opening parenthesis if it has any.
+      let fix_span = Span::new(
+        await_expr.span.lo(),
+        await_expr.arg.span().lo(),
+        Default::default(),
+      );
+      self.context.add_diagnostic_with_fix(
+        await_expr.span,
+        "no-redundant-await",
+        message,
+        hint,
+        vec![(fix_span, String::new())],
+      );
+    } else {
+      self.context.add_diagnostic_with_hint(
+        await_expr.span,
+        "no-redundant-await",
+        message,
+        hint,
+      );
+    }
+  }
+
+  /// Checks for `Promise.resolve(x)` with the global `Promise`.
+  fn is_promise_resolve(&self, call_expr: &CallExpr) -> bool {
+    let callee = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => callee,
+      ExprOrSuper::Super(_) => return false,
+    };
+    let member_expr = match &**callee {
+      Expr::Member(member_expr) if !member_expr.computed => member_expr,
+      _ => return false,
+    };
+    let is_promise = match &member_expr.obj {
+      ExprOrSuper::Expr(obj) => match &**obj {
+        Expr::Ident(ident) => self.context.is_global(ident, "Promise"),
+        _ => false,
+      },
+      ExprOrSuper::Super(_) => false,
+    };
+    let is_resolve = match &*member_expr.prop {
+      Expr::Ident(ident) => ident.sym == *"resolve",
+      _ => false,
+    };
+    is_promise
+      && is_resolve
+      && call_expr.args.len() == 1
+      && call_expr.args[0].spread.is_none()
+  }
+}
+
+impl Visit for NoRedundantAwaitVisitor {
+  noop_visit_type!();
+
+  fn visit_await_expr(&mut self, await_expr: &AwaitExpr, _: &dyn Node) {
+    self.check(await_expr);
+    await_expr.visit_children_with(self);
+  }
+}
+
+fn unparen(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => unparen(expr),
+    _ => expr,
+  }
+}
+
+fn is_comparison(op: BinaryOp) -> bool {
+  matches!(
+    op,
+    BinaryOp::EqEq
+      | BinaryOp::NotEq
+      | BinaryOp::EqEqEq
+      | BinaryOp::NotEqEq
+      | BinaryOp::Lt
+      | BinaryOp::LtEq
+      | BinaryOp::Gt
+      | BinaryOp::GtEq
+      | BinaryOp::InstanceOf
+      | BinaryOp::In
+  )
+}
+
+/// An object with a `then` method is a thenable, so awaiting it calls the
+/// method. Spread properties may add one too.
+fn has_then_property(props: &[PropOrSpread]) -> bool {
+  props.iter().any(|prop| {
+    let prop = match prop {
+      PropOrSpread::Spread(_) => return true,
+      PropOrSpread::Prop(prop) => prop,
+    };
+    let key = match &**prop {
+      Prop::Shorthand(ident) => return ident.sym == *"then",
+      Prop::KeyValue(key_value) => &key_value.key,
+      Prop::Getter(getter) => &getter.key,
+      Prop::Method(method) => &method.key,
+      Prop::Setter(_) | Prop::Assign(_) => return false,
+    };
+    match key {
+      PropName::Ident(ident) => ident.sym == *"then",
+      PropName::Str(str) => str.value == *"then",
+      PropName::Computed(_) => true,
+      PropName::Num(_) => false,
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostic::ByteRange;
+  use crate::diagnostic::LintEdit;
+  use crate::diagnostic::Position;
+  use crate::diagnostic::Range;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_redundant_await_valid() {
+    assert_lint_ok_n::<NoRedundantAwait>(vec![
+      "async function f() { await p; }",
+      "async function f() { await fetch(url); }",
+      "async function f() { await (async () => {})(); }",
+      "async function f() { await Promise.all([a, b]); }",
+      "async function f() { await Promise.resolve(); }",
+      "async function f() { await Promise.resolve(...args); }",
+      "async function f(Promise) { await Promise.resolve(x); }",
+      "async function f() { await { then(resolve) { resolve(1); } }; }",
+      "async function f() { await { ...thenable }; }",
+      "async function f() { await !p; }",
+      "async function f() { await (a + b); }",
+      "async function f(undefined) { await undefined; }",
+    ]);
+  }
+
+  #[test]
+  fn no_redundant_await_invalid() {
+    let cases = vec![
+      (
+        "async function f() { await await p; }",
+        "Awaiting the result of `await` is redundant",
+      ),
+      (
+        "async function f() { await (await p); }",
+        "Awaiting the result of `await` is redundant",
+      ),
+      (
+        "async function f() { await 1; }",
+        "Awaiting a literal is redundant",
+      ),
+      (
+        "async function f() { await \"a\"; }",
+        "Awaiting a literal is redundant",
+      ),
+      (
+        "async function f() { await `a${b}`; }",
+        "Awaiting a literal is redundant",
+      ),
+      (
+        "async function f() { await null; }",
+        "Awaiting a literal is redundant",
+      ),
+      (
+        "async function f() { await undefined; }",
+        "Awaiting `undefined` is redundant",
+      ),
+      (
+        "async function f() { await [a, b]; }",
+        "Awaiting an array literal doesn't wait for its elements",
+      ),
+      (
+        "async function f() { await { a: 1 }; }",
+        "Awaiting an object literal is redundant",
+      ),
+      (
+        "async function f() { await (async () => {}); }",
+        "Awaiting a function doesn't call it",
+      ),
+      (
+        "async function f() { await function () {}; }",
+        "Awaiting a function doesn't call it",
+      ),
+      (
+        "async function f() { await typeof p; }",
+        "Awaiting `typeof` is redundant, it always results in a string",
+      ),
+      (
+        "async function f() { await void p; }",
+        "Awaiting `void` is redundant, it always results in `undefined`",
+      ),
+      (
+        "async function f() { await (p === q); }",
+        "Awaiting a comparison is redundant, it always results in a boolean",
+      ),
+      (
+        "async function f() { await Promise.resolve(x); }",
+        "Wrapping the awaited value in `Promise.resolve()` is redundant",
+      ),
+    ];
+    for (source, message) in cases {
+      let diagnostics = lint(NoRedundantAwait::new(), source);
+      assert_eq!(diagnostics.len(), 1, "{}", source);
+      assert_eq!(diagnostics[0].message, message, "{}", source);
+      assert_eq!(diagnostics[0].range.start.col, 21, "{}", source);
+    }
+
+    // Each redundant `await` is reported.
+    let diagnostics =
+      lint(NoRedundantAwait::new(), "const a = await await await p;");
+    assert_eq!(diagnostics.len(), 2);
+  }
+
+  #[test]
+  fn no_redundant_await_fix() {
+    let fix = |source: &str| {
+      let diagnostics = lint(NoRedundantAwait::new(), source);
+      assert_eq!(diagnostics.len(), 1, "{}", source);
+      diagnostics[0].fix.clone()
+    };
+    let removal = |start: usize, end: usize| {
+      Some(vec![LintEdit {
+        range: Range {
+          start: Position {
+            line: 1,
+            col: start,
+          },
+          end: Position { line: 1, col: end },
+        },
+        byte_range: ByteRange { start, end },
+        replacement: "".to_string(),
+      }])
+    };
+
+    assert_eq!(fix("const a = await await p;"), removal(10, 16));
+    assert_eq!(fix("const a = await   (await p);"), removal(10, 18));
+    assert_eq!(fix("const a = await /* 1 */ 1;"), removal(10, 24));
+    assert_eq!(fix("const a = await(1);"), removal(10, 15));
+    assert_eq!(fix("const a = await undefined;"), removal(10, 16));
+    assert_eq!(fix("const a = await [p];"), None);
+    assert_eq!(fix("const a = await Promise.resolve(p);"), None);
+  }
+}
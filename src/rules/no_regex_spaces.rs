@@ -21,6 +21,29 @@ impl LintRule for NoRegexSpaces {
     "no-regex-spaces"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows multiple consecutive spaces in regular expressions.
+
+Several spaces in a row are hard to count; use a quantifier like ` {3}` instead.
+
+### Valid:
+```typescript
+const re = /foo {3}bar/;
+```
+
+### Invalid:
+```typescript
+const re = /foo   bar/;
+```
+```typescript
+const re = new RegExp("foo   bar");
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -85,7 +108,9 @@ impl Visit for NoRegexSpacesVisitor {
   fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
     if let Expr::Ident(ident) = &*new_expr.callee {
       if let Some(args) = &new_expr.args {
-        if let Some(regex) = extract_regex(&self.context.scope, ident, args) {
+        if let Some(regex) =
+          extract_regex(&self.context.scope_manager, ident, args)
+        {
           self.check_regex(regex.as_str(), new_expr.span);
         }
       }
@@ -97,7 +122,7 @@ impl Visit for NoRegexSpacesVisitor {
     if let ExprOrSuper::Expr(expr) = &call_expr.callee {
       if let Expr::Ident(ident) = expr.as_ref() {
         if let Some(regex) =
-          extract_regex(&self.context.scope, ident, &call_expr.args)
+          extract_regex(&self.context.scope_manager, ident, &call_expr.args)
         {
           self.check_regex(regex.as_str(), call_expr.span);
         }
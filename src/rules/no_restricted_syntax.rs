@@ -0,0 +1,984 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ArrayLit, ArrowExpr, AssignExpr, AwaitExpr, BinExpr, BinaryOp, BlockStmt,
+  BreakStmt, CallExpr, CatchClause, ClassDecl, ClassExpr, CondExpr,
+  ContinueStmt, DebuggerStmt, DoWhileStmt, EmptyStmt, ExportAll, ExportDecl,
+  ExportDefaultDecl, ExportDefaultExpr, Expr, ExprOrSuper, ExprStmt, FnDecl,
+  FnExpr, ForInStmt, ForOfStmt, ForStmt, Ident, IfStmt, ImportDecl,
+  LabeledStmt, Lit, MemberExpr, Module, NamedExport, NewExpr, ObjectLit,
+  ParenExpr, Pat, PatOrExpr, ReturnStmt, SeqExpr, SwitchCase, SwitchStmt,
+  TaggedTpl, ThisExpr, ThrowStmt, Tpl, TryStmt, TsAsExpr, TsEnumDecl,
+  TsInterfaceDecl, TsModuleDecl, TsModuleName, TsNonNullExpr, TsTypeAliasDecl,
+  UnaryExpr, UpdateExpr, VarDecl, VarDeclarator, WhileStmt, WithStmt,
+  YieldExpr,
+};
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+/// Node kinds selectors can refer to, named after ESTree and
+/// typescript-estree, with the attributes each of them supports.
+const NODE_KINDS: &[(&str, &[&str])] = &[
+  ("ArrayExpression", &[]),
+  ("ArrowFunctionExpression", &[]),
+  ("AssignmentExpression", &["operator", "left.name"]),
+  ("AwaitExpression", &[]),
+  ("BinaryExpression", &["operator"]),
+  ("BlockStatement", &[]),
+  ("BreakStatement", &["label.name"]),
+  ("CallExpression", &["callee.name"]),
+  ("CatchClause", &[]),
+  ("ClassDeclaration", &["id.name"]),
+  ("ClassExpression", &["id.name"]),
+  ("ConditionalExpression", &[]),
+  ("ContinueStatement", &["label.name"]),
+  ("DebuggerStatement", &[]),
+  ("DoWhileStatement", &[]),
+  ("EmptyStatement", &[]),
+  ("ExportAllDeclaration", &["source.value"]),
+  ("ExportDefaultDeclaration", &[]),
+  ("ExportNamedDeclaration", &["source.value"]),
+  ("ExpressionStatement", &[]),
+  ("ForInStatement", &[]),
+  ("ForOfStatement", &[]),
+  ("ForStatement", &[]),
+  ("FunctionDeclaration", &["id.name"]),
+  ("FunctionExpression", &["id.name"]),
+  ("IfStatement", &[]),
+  ("ImportDeclaration", &["source.value"]),
+  ("LabeledStatement", &["label.name"]),
+  ("Literal", &["value"]),
+  ("LogicalExpression", &["operator"]),
+  ("MemberExpression", &["object.name", "property.name"]),
+  ("NewExpression", &["callee.name"]),
+  ("ObjectExpression", &[]),
+  ("ReturnStatement", &[]),
+  ("SequenceExpression", &[]),
+  ("SwitchCase", &[]),
+  ("SwitchStatement", &[]),
+  ("TaggedTemplateExpression", &["tag.name"]),
+  ("TemplateLiteral", &[]),
+  ("ThisExpression", &[]),
+  ("ThrowStatement", &[]),
+  ("TryStatement", &[]),
+  ("TSAsExpression", &[]),
+  ("TSEnumDeclaration", &["id.name"]),
+  ("TSInterfaceDeclaration", &["id.name"]),
+  ("TSModuleDeclaration", &["id.name", "id.value"]),
+  ("TSNonNullExpression", &[]),
+  ("TSTypeAliasDeclaration", &["id.name"]),
+  ("UnaryExpression", &["operator"]),
+  ("UpdateExpression", &["operator"]),
+  ("VariableDeclaration", &["kind"]),
+  ("VariableDeclarator", &["id.name"]),
+  ("WhileStatement", &[]),
+  ("WithStatement", &[]),
+  ("YieldExpression", &[]),
+];
+
+/// A construct to ban, and the message reported where it's used.
+pub struct RestrictedSyntax {
+  pub selector: String,
+  pub message: String,
+}
+
+#[derive(Default)]
+pub struct NoRestrictedSyntaxOptions {
+  pub restrictions: Vec<RestrictedSyntax>,
+}
+
+/// Returned by `NoRestrictedSyntax::with_options` for a selector which
+/// can't be parsed or refers to unknown node kinds or attributes.
+#[derive(Debug, PartialEq)]
+pub struct InvalidSelector {
+  pub selector: String,
+  pub reason: String,
+}
+
+impl fmt::Display for InvalidSelector {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Invalid selector `{}`: {}", self.selector, self.reason)
+  }
+}
+
+impl Error for InvalidSelector {}
+
+/// Reports the constructs matching the selectors it's configured with, for
+/// bans no dedicated rule covers.
+///
+/// Selectors support a subset of the ESLint selector syntax:
+///
+/// - node kinds, named after ESTree, eg. `ForInStatement` or
+///   `TSEnumDeclaration` (see `NODE_KINDS` for the full list),
+/// - attribute equality on the identifier and string fields listed for each
+///   kind, one level deep, eg. `CallExpression[callee.name='require']`
+///   or `VariableDeclaration[kind=var]`,
+/// - the child combinator, eg. `LabeledStatement > ForStatement`. The
+///   parent is the closest ancestor of one of the supported kinds.
+///
+/// Descendant and sibling combinators, attribute operators other than `=`,
+/// regular expressions and pseudo-classes are not supported.
+pub struct NoRestrictedSyntax {
+  selectors: Vec<(Selector, String)>,
+}
+
+impl NoRestrictedSyntax {
+  pub fn with_options(
+    options: NoRestrictedSyntaxOptions,
+  ) -> Result<Box<Self>, InvalidSelector> {
+    let selectors = options
+      .restrictions
+      .into_iter()
+      .map(|restriction| {
+        Ok((parse_selector(&restriction.selector)?, restriction.message))
+      })
+      .collect::<Result<_, _>>()?;
+    Ok(Box::new(NoRestrictedSyntax { selectors }))
+  }
+}
+
+impl LintRule for NoRestrictedSyntax {
+  fn new() -> Box<Self> {
+    Box::new(NoRestrictedSyntax { selectors: vec![] })
+  }
+
+  fn code(&self) -> &'static str {
+    "no-restricted-syntax"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows the constructs matching the selectors it's configured with, for bans no dedicated rule covers. Without options, nothing is reported.
+
+Selectors support a subset of the ESLint selector syntax:
+
+- node kinds named after ESTree, eg. `ForInStatement` or `TSEnumDeclaration`
+- attribute equality on identifier and string fields, one level deep, eg. `CallExpression[callee.name='require']` or `VariableDeclaration[kind=var]`
+- the child combinator, eg. `LabeledStatement > ForStatement`
+
+Descendant and sibling combinators, other attribute operators, regular expressions and pseudo-classes are not supported. `NoRestrictedSyntax::with_options` returns an `InvalidSelector` error for unsupported selectors.
+
+### Options
+
+`NoRestrictedSyntaxOptions::restrictions`: a list of `RestrictedSyntax { selector, message }`, where `message` is reported for each construct the selector matches.
+
+### Example
+
+With the restriction:
+
+```rust
+RestrictedSyntax {
+  selector: "CallExpression[callee.name='require']".to_string(),
+  message: "Use `import` instead of `require`".to_string(),
+}
+```
+
+this is reported:
+
+```typescript
+const fs = require("fs");
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    if self.selectors.is_empty() {
+      return;
+    }
+    let mut visitor = NoRestrictedSyntaxVisitor {
+      context,
+      selectors: &self.selectors,
+      ancestors: vec![],
+    };
+    module.visit_with(module, &mut visitor);
+  }
+}
+
+/// Compound selectors, each one matching the parent of the next.
+#[derive(Debug)]
+struct Selector(Vec<Compound>);
+
+#[derive(Debug)]
+struct Compound {
+  kind: &'static str,
+  attributes: Vec<(&'static str, String)>,
+}
+
+impl Compound {
+  fn matches(&self, node: &NodeInfo) -> bool {
+    self.kind == node.kind
+      && self.attributes.iter().all(|(path, value)| {
+        node.attributes.iter().any(|(node_path, node_value)| {
+          node_path == path && node_value == value
+        })
+      })
+  }
+}
+
+fn parse_selector(source: &str) -> Result<Selector, InvalidSelector> {
+  let mut parser = SelectorParser {
+    chars: source.chars().collect(),
+    pos: 0,
+  };
+  parser.parse().map_err(|reason| InvalidSelector {
+    selector: source.to_string(),
+    reason,
+  })
+}
+
+struct SelectorParser {
+  chars: Vec<char>,
+  pos: usize,
+}
+
+impl SelectorParser {
+  fn peek(&self) -> Option<char> {
+    self.chars.get(self.pos).copied()
+  }
+
+  fn skip_whitespace(&mut self) {
+    while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+      self.pos += 1;
+    }
+  }
+
+  fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+    let start = self.pos;
+    while matches!(self.peek(), Some(c) if predicate(c)) {
+      self.pos += 1;
+    }
+    self.chars[start..self.pos].iter().collect()
+  }
+
+  fn expect(&mut self, expected: char) -> Result<(), String> {
+    match self.peek() {
+      Some(c) if c == expected => {
+        self.pos += 1;
+        Ok(())
+      }
+      Some(c) => Err(format!("expected `{}`, found `{}`", expected, c)),
+      None => Err(format!("expected `{}`, found the end", expected)),
+    }
+  }
+
+  fn parse(&mut self) -> Result<Selector, String> {
+    let mut compounds = vec![];
+    loop {
+      self.skip_whitespace();
+      compounds.push(self.parse_compound()?);
+      self.skip_whitespace();
+      match self.peek() {
+        None => return Ok(Selector(compounds)),
+        Some('>') => self.pos += 1,
+        Some(c) if c.is_ascii_alphabetic() => {
+          return Err("only the `>` combinator is supported".to_string())
+        }
+        Some(c) => return Err(format!("unexpected `{}`", c)),
+      }
+    }
+  }
+
+  fn parse_compound(&mut self) -> Result<Compound, String> {
+    let name = self.take_while(|c| c.is_ascii_alphanumeric());
+    if name.is_empty() {
+      return Err(match self.peek() {
+        Some(c) => format!("expected a node kind, found `{}`", c),
+        None => "expected a node kind, found the end".to_string(),
+      });
+    }
+    let (kind, allowed) = NODE_KINDS
+      .iter()
+      .find(|(kind, _)| *kind == name)
+      .ok_or_else(|| format!("unknown node kind `{}`", name))?;
+
+    let mut attributes = vec![];
+    while self.peek() == Some('[') {
+      self.pos += 1;
+      let path = self.take_while(|c| c.is_ascii_alphanumeric() || c == '.');
+      let path = allowed
+        .iter()
+        .find(|allowed| **allowed == path)
+        .ok_or_else(|| format!("`{}` has no attribute `{}`", kind, path))?;
+      self.expect('=')?;
+      let value = match self.peek() {
+        Some(quote) if quote == '\'' || quote == '"' => {
+          self.pos += 1;
+          let value = self.take_while(|c| c != quote);
+          self.expect(quote)?;
+          value
+        }
+        _ => self.take_while(|c| c != ']').trim().to_string(),
+      };
+      self.expect(']')?;
+      attributes.push((*path, value));
+    }
+
+    Ok(Compound { kind, attributes })
+  }
+}
+
+/// A node of one of the supported kinds, with the values of its
+/// attributes.
+struct NodeInfo {
+  kind: &'static str,
+  attributes: Vec<(&'static str, String)>,
+}
+
+struct NoRestrictedSyntaxVisitor<'a> {
+  context: Arc<Context>,
+  selectors: &'a [(Selector, String)],
+  ancestors: Vec<NodeInfo>,
+}
+
+impl<'a> NoRestrictedSyntaxVisitor<'a> {
+  fn check<N>(&mut self, node: &N, span: Span, info: NodeInfo)
+  where
+    N: VisitWith<Self>,
+  {
+    for (Selector(compounds), message) in self.selectors {
+      let (last, parents) = compounds.split_last().unwrap();
+      if !last.matches(&info) || parents.len() > self.ancestors.len() {
+        continue;
+      }
+      let matches_parents = parents
+        .iter()
+        .rev()
+        .zip(self.ancestors.iter().rev())
+        .all(|(compound, ancestor)| compound.matches(ancestor));
+      if matches_parents {
+        self
+          .context
+          .add_diagnostic(span, "no-restricted-syntax", message);
+      }
+    }
+
+    self.ancestors.push(info);
+    node.visit_children_with(self);
+    self.ancestors.pop();
+  }
+
+  fn visit_kind<N>(&mut self, node: &N, span: Span, kind: &'static str)
+  where
+    N: VisitWith<Self>,
+  {
+    self.check(
+      node,
+      span,
+      NodeInfo {
+        kind,
+        attributes: vec![],
+      },
+    );
+  }
+
+  fn visit_kind_with<N>(
+    &mut self,
+    node: &N,
+    span: Span,
+    kind: &'static str,
+    attributes: Vec<(&'static str, Option<String>)>,
+  ) where
+    N: VisitWith<Self>,
+  {
+    let attributes = attributes
+      .into_iter()
+      .filter_map(|(path, value)| Some((path, value?)))
+      .collect();
+    self.check(node, span, NodeInfo { kind, attributes });
+  }
+}
+
+fn name(ident: &Ident) -> Option<String> {
+  Some(ident.sym.to_string())
+}
+
+fn expr_name(expr: &Expr) -> Option<String> {
+  match expr {
+    Expr::Ident(ident) => name(ident),
+    Expr::Paren(ParenExpr { expr, .. }) => expr_name(expr),
+    _ => None,
+  }
+}
+
+fn callee_name(callee: &ExprOrSuper) -> Option<String> {
+  match callee {
+    ExprOrSuper::Expr(expr) => expr_name(expr),
+    ExprOrSuper::Super(_) => None,
+  }
+}
+
+impl<'a> Visit for NoRestrictedSyntaxVisitor<'a> {
+  fn visit_array_lit(&mut self, n: &ArrayLit, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ArrayExpression");
+  }
+
+  fn visit_arrow_expr(&mut self, n: &ArrowExpr, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ArrowFunctionExpression");
+  }
+
+  fn visit_assign_expr(&mut self, n: &AssignExpr, _: &dyn Node) {
+    let left_name = match &n.left {
+      PatOrExpr::Expr(expr) => expr_name(expr),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Ident(ident) => name(ident),
+        Pat::Expr(expr) => expr_name(expr),
+        _ => None,
+      },
+    };
+    self.visit_kind_with(
+      n,
+      n.span,
+      "AssignmentExpression",
+      vec![
+        ("operator", Some(n.op.to_string())),
+        ("left.name", left_name),
+      ],
+    );
+  }
+
+  fn visit_await_expr(&mut self, n: &AwaitExpr, _: &dyn Node) {
+    self.visit_kind(n, n.span, "AwaitExpression");
+  }
+
+  fn visit_bin_expr(&mut self, n: &BinExpr, _: &dyn Node) {
+    let kind = match n.op {
+      BinaryOp::LogicalAnd
+      | BinaryOp::LogicalOr
+      | BinaryOp::NullishCoalescing => "LogicalExpression",
+      _ => "BinaryExpression",
+    };
+    self.visit_kind_with(
+      n,
+      n.span,
+      kind,
+      vec![("operator", Some(n.op.to_string()))],
+    );
+  }
+
+  fn visit_block_stmt(&mut self, n: &BlockStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "BlockStatement");
+  }
+
+  fn visit_break_stmt(&mut self, n: &BreakStmt, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "BreakStatement",
+      vec![("label.name", n.label.as_ref().and_then(name))],
+    );
+  }
+
+  fn visit_call_expr(&mut self, n: &CallExpr, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "CallExpression",
+      vec![("callee.name", callee_name(&n.callee))],
+    );
+  }
+
+  fn visit_catch_clause(&mut self, n: &CatchClause, _: &dyn Node) {
+    self.visit_kind(n, n.span, "CatchClause");
+  }
+
+  fn visit_class_decl(&mut self, n: &ClassDecl, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.class.span,
+      "ClassDeclaration",
+      vec![("id.name", name(&n.ident))],
+    );
+  }
+
+  fn visit_class_expr(&mut self, n: &ClassExpr, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.class.span,
+      "ClassExpression",
+      vec![("id.name", n.ident.as_ref().and_then(name))],
+    );
+  }
+
+  fn visit_cond_expr(&mut self, n: &CondExpr, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ConditionalExpression");
+  }
+
+  fn visit_continue_stmt(&mut self, n: &ContinueStmt, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "ContinueStatement",
+      vec![("label.name", n.label.as_ref().and_then(name))],
+    );
+  }
+
+  fn visit_debugger_stmt(&mut self, n: &DebuggerStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "DebuggerStatement");
+  }
+
+  fn visit_do_while_stmt(&mut self, n: &DoWhileStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "DoWhileStatement");
+  }
+
+  fn visit_empty_stmt(&mut self, n: &EmptyStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "EmptyStatement");
+  }
+
+  fn visit_export_all(&mut self, n: &ExportAll, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "ExportAllDeclaration",
+      vec![("source.value", Some(n.src.value.to_string()))],
+    );
+  }
+
+  fn visit_export_decl(&mut self, n: &ExportDecl, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ExportNamedDeclaration");
+  }
+
+  fn visit_export_default_decl(&mut self, n: &ExportDefaultDecl, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ExportDefaultDeclaration");
+  }
+
+  fn visit_export_default_expr(&mut self, n: &ExportDefaultExpr, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ExportDefaultDeclaration");
+  }
+
+  fn visit_expr_stmt(&mut self, n: &ExprStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ExpressionStatement");
+  }
+
+  fn visit_fn_decl(&mut self, n: &FnDecl, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.function.span,
+      "FunctionDeclaration",
+      vec![("id.name", name(&n.ident))],
+    );
+  }
+
+  fn visit_fn_expr(&mut self, n: &FnExpr, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.function.span,
+      "FunctionExpression",
+      vec![("id.name", n.ident.as_ref().and_then(name))],
+    );
+  }
+
+  fn visit_for_in_stmt(&mut self, n: &ForInStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ForInStatement");
+  }
+
+  fn visit_for_of_stmt(&mut self, n: &ForOfStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ForOfStatement");
+  }
+
+  fn visit_for_stmt(&mut self, n: &ForStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ForStatement");
+  }
+
+  fn visit_if_stmt(&mut self, n: &IfStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "IfStatement");
+  }
+
+  fn visit_import_decl(&mut self, n: &ImportDecl, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "ImportDeclaration",
+      vec![("source.value", Some(n.src.value.to_string()))],
+    );
+  }
+
+  fn visit_labeled_stmt(&mut self, n: &LabeledStmt, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "LabeledStatement",
+      vec![("label.name", name(&n.label))],
+    );
+  }
+
+  fn visit_lit(&mut self, n: &Lit, _: &dyn Node) {
+    let value = match n {
+      Lit::Str(str) => Some(str.value.to_string()),
+      Lit::Num(num) => Some(num.value.to_string()),
+      Lit::Bool(bool) => Some(bool.value.to_string()),
+      Lit::BigInt(big_int) => Some(big_int.value.to_string()),
+      Lit::Null(_) | Lit::Regex(_) | Lit::JSXText(_) => None,
+    };
+    self.visit_kind_with(n, n.span(), "Literal", vec![("value", value)]);
+  }
+
+  fn visit_member_expr(&mut self, n: &MemberExpr, _: &dyn Node) {
+    let property_name = if n.computed { None } else { expr_name(&n.prop) };
+    self.visit_kind_with(
+      n,
+      n.span,
+      "MemberExpression",
+      vec![
+        ("object.name", callee_name(&n.obj)),
+        ("property.name", property_name),
+      ],
+    );
+  }
+
+  fn visit_new_expr(&mut self, n: &NewExpr, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "NewExpression",
+      vec![("callee.name", expr_name(&n.callee))],
+    );
+  }
+
+  fn visit_named_export(&mut self, n: &NamedExport, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "ExportNamedDeclaration",
+      vec![(
+        "source.value",
+        n.src.as_ref().map(|src| src.value.to_string()),
+      )],
+    );
+  }
+
+  fn visit_object_lit(&mut self, n: &ObjectLit, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ObjectExpression");
+  }
+
+  fn visit_return_stmt(&mut self, n: &ReturnStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ReturnStatement");
+  }
+
+  fn visit_seq_expr(&mut self, n: &SeqExpr, _: &dyn Node) {
+    self.visit_kind(n, n.span, "SequenceExpression");
+  }
+
+  fn visit_switch_case(&mut self, n: &SwitchCase, _: &dyn Node) {
+    self.visit_kind(n, n.span, "SwitchCase");
+  }
+
+  fn visit_switch_stmt(&mut self, n: &SwitchStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "SwitchStatement");
+  }
+
+  fn visit_tagged_tpl(&mut self, n: &TaggedTpl, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "TaggedTemplateExpression",
+      vec![("tag.name", expr_name(&n.tag))],
+    );
+  }
+
+  fn visit_tpl(&mut self, n: &Tpl, _: &dyn Node) {
+    self.visit_kind(n, n.span, "TemplateLiteral");
+  }
+
+  fn visit_this_expr(&mut self, n: &ThisExpr, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ThisExpression");
+  }
+
+  fn visit_throw_stmt(&mut self, n: &ThrowStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "ThrowStatement");
+  }
+
+  fn visit_try_stmt(&mut self, n: &TryStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "TryStatement");
+  }
+
+  fn visit_ts_as_expr(&mut self, n: &TsAsExpr, _: &dyn Node) {
+    self.visit_kind(n, n.span, "TSAsExpression");
+  }
+
+  fn visit_ts_enum_decl(&mut self, n: &TsEnumDecl, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "TSEnumDeclaration",
+      vec![("id.name", name(&n.id))],
+    );
+  }
+
+  fn visit_ts_interface_decl(&mut self, n: &TsInterfaceDecl, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "TSInterfaceDeclaration",
+      vec![("id.name", name(&n.id))],
+    );
+  }
+
+  fn visit_ts_module_decl(&mut self, n: &TsModuleDecl, _: &dyn Node) {
+    let (id_name, id_value) = match &n.id {
+      TsModuleName::Ident(ident) => (name(ident), None),
+      TsModuleName::Str(str) => (None, Some(str.value.to_string())),
+    };
+    self.visit_kind_with(
+      n,
+      n.span,
+      "TSModuleDeclaration",
+      vec![("id.name", id_name), ("id.value", id_value)],
+    );
+  }
+
+  fn visit_ts_non_null_expr(&mut self, n: &TsNonNullExpr, _: &dyn Node) {
+    self.visit_kind(n, n.span, "TSNonNullExpression");
+  }
+
+  fn visit_ts_type_alias_decl(&mut self, n: &TsTypeAliasDecl, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "TSTypeAliasDeclaration",
+      vec![("id.name", name(&n.id))],
+    );
+  }
+
+  fn visit_unary_expr(&mut self, n: &UnaryExpr, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "UnaryExpression",
+      vec![("operator", Some(n.op.to_string()))],
+    );
+  }
+
+  fn visit_update_expr(&mut self, n: &UpdateExpr, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "UpdateExpression",
+      vec![("operator", Some(n.op.to_string()))],
+    );
+  }
+
+  fn visit_var_decl(&mut self, n: &VarDecl, _: &dyn Node) {
+    self.visit_kind_with(
+      n,
+      n.span,
+      "VariableDeclaration",
+      vec![("kind", Some(n.kind.to_string()))],
+    );
+  }
+
+  fn visit_var_declarator(&mut self, n: &VarDeclarator, _: &dyn Node) {
+    let id_name = match &n.name {
+      Pat::Ident(ident) => name(ident),
+      _ => None,
+    };
+    self.visit_kind_with(
+      n,
+      n.span,
+      "VariableDeclarator",
+      vec![("id.name", id_name)],
+    );
+  }
+
+  fn visit_while_stmt(&mut self, n: &WhileStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "WhileStatement");
+  }
+
+  fn visit_with_stmt(&mut self, n: &WithStmt, _: &dyn Node) {
+    self.visit_kind(n, n.span, "WithStatement");
+  }
+
+  fn visit_yield_expr(&mut self, n: &YieldExpr, _: &dyn Node) {
+    self.visit_kind(n, n.span, "YieldExpression");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn rule(selectors: &[&str]) -> Box<NoRestrictedSyntax> {
+    NoRestrictedSyntax::with_options(NoRestrictedSyntaxOptions {
+      restrictions: selectors
+        .iter()
+        .map(|selector| RestrictedSyntax {
+          selector: selector.to_string(),
+          message: format!("`{}` is not allowed", selector),
+        })
+        .collect(),
+    })
+    .unwrap()
+  }
+
+  /// Returns the line, column and message of each diagnostic.
+  fn lint_selectors(
+    selectors: &[&str],
+    source: &str,
+  ) -> Vec<(usize, usize, String)> {
+    lint(rule(selectors), source)
+      .into_iter()
+      .map(|diagnostic| {
+        (
+          diagnostic.range.start.line,
+          diagnostic.range.start.col,
+          diagnostic.message,
+        )
+      })
+      .collect()
+  }
+
+  fn invalid(selector: &str) -> String {
+    NoRestrictedSyntax::with_options(NoRestrictedSyntaxOptions {
+      restrictions: vec![RestrictedSyntax {
+        selector: selector.to_string(),
+        message: "".to_string(),
+      }],
+    })
+    .err()
+    .expect("selector should be invalid")
+    .to_string()
+  }
+
+  #[test]
+  fn no_restricted_syntax_node_kinds() {
+    assert_lint_ok_with(
+      NoRestrictedSyntax::new(),
+      "for (const key in obj) {}\nenum E {}",
+    );
+    assert_eq!(
+      lint_selectors(
+        &["ForInStatement", "TSEnumDeclaration"],
+        "for (const key in obj) {}\nfor (const v of obj) {}\nenum E { A }",
+      ),
+      vec![
+        (1, 0, "`ForInStatement` is not allowed".to_string()),
+        (3, 0, "`TSEnumDeclaration` is not allowed".to_string()),
+      ]
+    );
+    assert_eq!(
+      lint_selectors(&["LogicalExpression"], "a && b;\na + b;\na ?? b;"),
+      vec![
+        (1, 0, "`LogicalExpression` is not allowed".to_string()),
+        (3, 0, "`LogicalExpression` is not allowed".to_string()),
+      ]
+    );
+  }
+
+  #[test]
+  fn no_restricted_syntax_attributes() {
+    let selector = "CallExpression[callee.name='require']";
+    assert_eq!(
+      lint_selectors(
+        &[selector],
+        "const a = require(\"a\");\nconst b = requireAll(\"b\");\nmod.require(\"c\");",
+      ),
+      vec![(1, 10, format!("`{}` is not allowed", selector))]
+    );
+    assert_eq!(
+      lint_selectors(
+        &["VariableDeclaration[kind=var]"],
+        "var a = 1;\nlet b = 2;",
+      )
+      .len(),
+      1
+    );
+    assert_eq!(
+      lint_selectors(
+        &["BinaryExpression[operator=\"==\"]"],
+        "a == b;\na === b;",
+      )
+      .len(),
+      1
+    );
+    assert_eq!(
+      lint_selectors(
+        &["MemberExpression[object.name='console'][property.name='log']"],
+        "console.log(a);\nconsole.error(a);\nconsole[log](a);",
+      )
+      .len(),
+      1
+    );
+    assert_eq!(
+      lint_selectors(
+        &["ImportDeclaration[source.value='lodash']"],
+        "import _ from \"lodash\";\nimport a from \"./a.ts\";",
+      )
+      .len(),
+      1
+    );
+  }
+
+  #[test]
+  fn no_restricted_syntax_child_combinator() {
+    let selector = "LabeledStatement > ForStatement";
+    assert_eq!(
+      lint_selectors(
+        &[selector],
+        r#"
+outer: for (;;) {}
+for (;;) {}
+block: {
+  for (;;) {}
+}
+        "#,
+      ),
+      vec![(2, 7, format!("`{}` is not allowed", selector))]
+    );
+    assert_eq!(
+      lint_selectors(
+        &["FunctionDeclaration[id.name='main'] > BlockStatement > ThrowStatement"],
+        "function main() { throw e; }\nfunction other() { throw e; }",
+      )
+      .len(),
+      1
+    );
+    assert!(
+      lint_selectors(&["IfStatement > ForStatement"], "for (;;) {}").is_empty()
+    );
+  }
+
+  #[test]
+  fn no_restricted_syntax_invalid_selectors() {
+    assert_eq!(
+      invalid("ForInStatment"),
+      "Invalid selector `ForInStatment`: unknown node kind `ForInStatment`"
+    );
+    assert_eq!(
+      invalid("CallExpression[callee.object.name='a']"),
+      "Invalid selector `CallExpression[callee.object.name='a']`: `CallExpression` has no attribute `callee.object.name`"
+    );
+    assert_eq!(
+      invalid("CallExpression[callee.name='a'"),
+      "Invalid selector `CallExpression[callee.name='a'`: expected `]`, found the end"
+    );
+    assert_eq!(
+      invalid("CallExpression[callee.name='a]"),
+      "Invalid selector `CallExpression[callee.name='a]`: expected `'`, found the end"
+    );
+    assert_eq!(
+      invalid("LabeledStatement ForStatement"),
+      "Invalid selector `LabeledStatement ForStatement`: only the `>` combinator is supported"
+    );
+    assert_eq!(
+      invalid("LabeledStatement >"),
+      "Invalid selector `LabeledStatement >`: expected a node kind, found the end"
+    );
+    assert_eq!(
+      invalid(""),
+      "Invalid selector ``: expected a node kind, found the end"
+    );
+    assert_eq!(
+      invalid("IfStatement:first-child"),
+      "Invalid selector `IfStatement:first-child`: unexpected `:`"
+    );
+  }
+}
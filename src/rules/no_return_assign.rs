@@ -0,0 +1,228 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::sync::Arc;
+use swc_ecmascript::ast::{
+  ArrowExpr, AssignExpr, BlockStmtOrExpr, Class, Expr, Function, ParenExpr,
+  ReturnStmt,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoReturnAssignMode {
+  /// Assignments wrapped in parentheses are allowed, eg. `return (a = b)`.
+  ExceptParens,
+  /// Every assignment is reported, parenthesized or not.
+  Always,
+}
+
+pub struct NoReturnAssignOptions {
+  pub mode: NoReturnAssignMode,
+}
+
+impl Default for NoReturnAssignOptions {
+  fn default() -> Self {
+    Self {
+      mode: NoReturnAssignMode::ExceptParens,
+    }
+  }
+}
+
+pub struct NoReturnAssign {
+  options: NoReturnAssignOptions,
+}
+
+impl NoReturnAssign {
+  pub fn with_options(options: NoReturnAssignOptions) -> Box<Self> {
+    Box::new(NoReturnAssign { options })
+  }
+}
+
+impl LintRule for NoReturnAssign {
+  fn new() -> Box<Self> {
+    Self::with_options(NoReturnAssignOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-return-assign"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows assignments in `return` statements and arrow function bodies.
+
+An assignment there is often a typo of a comparison, like `return a = b` instead of `return a === b`.
+
+### Options
+
+`NoReturnAssignOptions::mode` is one of:
+- `NoReturnAssignMode::ExceptParens` (default): assignments wrapped in parentheses, like `return (a = b)`, are allowed.
+- `NoReturnAssignMode::Always`: every assignment is reported.
+
+### Valid:
+```typescript
+function f() {
+  return a === b;
+}
+```
+```typescript
+function f() {
+  return (a = b);
+}
+```
+
+### Invalid:
+```typescript
+function f() {
+  return x = y;
+}
+```
+```typescript
+const f = (v) => total += v;
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = NoReturnAssignVisitor::new(context, self.options.mode);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoReturnAssignVisitor {
+  context: Arc<Context>,
+  mode: NoReturnAssignMode,
+}
+
+impl NoReturnAssignVisitor {
+  fn new(context: Arc<Context>, mode: NoReturnAssignMode) -> Self {
+    Self { context, mode }
+  }
+
+  fn check_returned_expr(&self, expr: &Expr) {
+    let mut finder = AssignFinder {
+      context: &self.context,
+      mode: self.mode,
+    };
+    finder.visit_expr(expr, expr);
+  }
+}
+
+impl Visit for NoReturnAssignVisitor {
+  noop_visit_type!();
+
+  fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt, parent: &dyn Node) {
+    if let Some(arg) = &return_stmt.arg {
+      self.check_returned_expr(arg);
+    }
+    swc_ecmascript::visit::visit_return_stmt(self, return_stmt, parent);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+    if let BlockStmtOrExpr::Expr(expr) = &arrow_expr.body {
+      self.check_returned_expr(expr);
+    }
+    swc_ecmascript::visit::visit_arrow_expr(self, arrow_expr, parent);
+  }
+}
+
+/// Looks for assignments in a returned expression, without descending
+/// into nested functions; those have their own returns.
+struct AssignFinder<'a> {
+  context: &'a Context,
+  mode: NoReturnAssignMode,
+}
+
+impl<'a> Visit for AssignFinder<'a> {
+  noop_visit_type!();
+
+  fn visit_paren_expr(&mut self, paren_expr: &ParenExpr, parent: &dyn Node) {
+    if self.mode == NoReturnAssignMode::ExceptParens {
+      if let Expr::Assign(assign_expr) = &*paren_expr.expr {
+        swc_ecmascript::visit::visit_assign_expr(self, assign_expr, parent);
+        return;
+      }
+    }
+    swc_ecmascript::visit::visit_paren_expr(self, paren_expr, parent);
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, parent: &dyn Node) {
+    self.context.add_diagnostic(
+      assign_expr.span,
+      "no-return-assign",
+      "Return statement should not contain an assignment",
+    );
+    swc_ecmascript::visit::visit_assign_expr(self, assign_expr, parent);
+  }
+
+  fn visit_function(&mut self, _function: &Function, _parent: &dyn Node) {}
+
+  fn visit_arrow_expr(&mut self, _arrow_expr: &ArrowExpr, _parent: &dyn Node) {}
+
+  fn visit_class(&mut self, _class: &Class, _parent: &dyn Node) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn always() -> Box<NoReturnAssign> {
+    NoReturnAssign::with_options(NoReturnAssignOptions {
+      mode: NoReturnAssignMode::Always,
+    })
+  }
+
+  #[test]
+  fn no_return_assign_valid() {
+    assert_lint_ok_n::<NoReturnAssign>(vec![
+      "function f() { return a === b; }",
+      "function f() { a = b; return a; }",
+      "function f() { return (a = b); }",
+      "function f() { return (a = b) && c; }",
+      "const f = (v) => (total += v);",
+      "const f = () => { a = b; };",
+      "function f() { return () => { a = b; }; }",
+      "function f() { return function () { a = b; }; }",
+      "function f() { return class { m() { a = b; } }; }",
+    ]);
+  }
+
+  #[test]
+  fn no_return_assign_invalid() {
+    assert_lint_err::<NoReturnAssign>("function f() { return x = y; }", 22);
+    assert_lint_err::<NoReturnAssign>("const f = (v) => total += v;", 17);
+    assert_lint_err::<NoReturnAssign>(
+      "function f() { return a ? b = 1 : c; }",
+      26,
+    );
+    assert_lint_err_on_line::<NoReturnAssign>(
+      "function f() {\n  return a += 1;\n}",
+      2,
+      9,
+    );
+  }
+
+  #[test]
+  fn no_return_assign_always() {
+    assert_lint_ok_with(always(), "function f() { return a === b; }");
+    assert_lint_err_on_line_n_with(
+      always(),
+      "function f() { return (a = b); }",
+      vec![(1, 23)],
+    );
+    assert_lint_err_on_line_n_with(
+      always(),
+      "const f = (v) => (total += v);",
+      vec![(1, 18)],
+    );
+  }
+}
@@ -0,0 +1,310 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  ArrowExpr, AwaitExpr, Function, GetterProp, Module, SetterProp, TryStmt,
+  YieldExpr,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::{Visit, VisitWith};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoReturnInFinallyAdjacentMode {
+  /// Only reports `finally` blocks whose `try` block awaits too, so there
+  /// can be an in-flight rejection to mask.
+  InFlight,
+  /// Reports every `await` and `yield` in `finally` blocks.
+  Always,
+}
+
+pub struct NoReturnInFinallyAdjacentOptions {
+  pub mode: NoReturnInFinallyAdjacentMode,
+}
+
+impl Default for NoReturnInFinallyAdjacentOptions {
+  fn default() -> Self {
+    Self {
+      mode: NoReturnInFinallyAdjacentMode::InFlight,
+    }
+  }
+}
+
+/// Reports `await` and `yield` expressions in `finally` blocks. While the
+/// `finally` block is suspended, the rejection of the `try` block is held
+/// back, and it's lost if the `finally` block throws.
+///
+/// Expressions in functions nested in the `finally` block are not
+/// reported. By default, only `finally` blocks whose `try` block contains
+/// an `await` are looked at.
+pub struct NoReturnInFinallyAdjacent {
+  options: NoReturnInFinallyAdjacentOptions,
+}
+
+impl NoReturnInFinallyAdjacent {
+  pub fn with_options(options: NoReturnInFinallyAdjacentOptions) -> Box<Self> {
+    Box::new(NoReturnInFinallyAdjacent { options })
+  }
+}
+
+impl LintRule for NoReturnInFinallyAdjacent {
+  fn new() -> Box<Self> {
+    Self::with_options(NoReturnInFinallyAdjacentOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-return-in-finally-adjacent"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows `await` and `yield` expressions in `finally` blocks.
+
+While the `finally` block is suspended, the rejection of the `try` block is held back, and it's lost if the `finally` block throws. Expressions in functions nested in the `finally` block are allowed.
+
+### Options
+
+`NoReturnInFinallyAdjacentOptions::mode` is one of:
+- `NoReturnInFinallyAdjacentMode::InFlight` (default): only checks `finally` blocks whose `try` block awaits too.
+- `NoReturnInFinallyAdjacentMode::Always`: checks every `finally` block.
+
+### Valid:
+```typescript
+async function f() {
+  try {
+    await a();
+  } finally {
+    b();
+  }
+}
+```
+
+### Invalid:
+```typescript
+async function f() {
+  try {
+    await a();
+  } finally {
+    await b();
+  }
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoReturnInFinallyAdjacentVisitor {
+      context,
+      mode: self.options.mode,
+      reported: HashSet::new(),
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoReturnInFinallyAdjacentVisitor {
+  context: Arc<Context>,
+  mode: NoReturnInFinallyAdjacentMode,
+  /// Suspensions already reported for an enclosing `finally` block.
+  reported: HashSet<Span>,
+}
+
+#[derive(Clone, Copy)]
+enum Suspension {
+  Await(Span),
+  Yield(Span),
+}
+
+/// Collects the `await` and `yield` expressions of a block, not counting
+/// the ones of nested functions.
+#[derive(Default)]
+struct SuspensionCollector {
+  suspensions: Vec<Suspension>,
+}
+
+impl Visit for SuspensionCollector {
+  noop_visit_type!();
+
+  fn visit_await_expr(&mut self, await_expr: &AwaitExpr, parent: &dyn Node) {
+    self.suspensions.push(Suspension::Await(await_expr.span));
+    swc_ecmascript::visit::visit_await_expr(self, await_expr, parent);
+  }
+
+  fn visit_yield_expr(&mut self, yield_expr: &YieldExpr, parent: &dyn Node) {
+    self.suspensions.push(Suspension::Yield(yield_expr.span));
+    swc_ecmascript::visit::visit_yield_expr(self, yield_expr, parent);
+  }
+
+  fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
+  fn visit_arrow_expr(&mut self, _: &ArrowExpr, _: &dyn Node) {}
+  fn visit_getter_prop(&mut self, _: &GetterProp, _: &dyn Node) {}
+  fn visit_setter_prop(&mut self, _: &SetterProp, _: &dyn Node) {}
+}
+
+impl NoReturnInFinallyAdjacentVisitor {
+  fn check(&mut self, try_stmt: &TryStmt) {
+    let finalizer = match &try_stmt.finalizer {
+      Some(finalizer) => finalizer,
+      None => return,
+    };
+
+    let mut in_try = SuspensionCollector::default();
+    try_stmt.block.visit_children_with(&mut in_try);
+    let try_await = in_try.suspensions.iter().find_map(|s| match s {
+      Suspension::Await(span) => Some(*span),
+      Suspension::Yield(_) => None,
+    });
+    if try_await.is_none()
+      && self.mode == NoReturnInFinallyAdjacentMode::InFlight
+    {
+      return;
+    }
+
+    let mut in_finally = SuspensionCollector::default();
+    finalizer.visit_children_with(&mut in_finally);
+    for suspension in in_finally.suspensions {
+      let (span, keyword, error) = match suspension {
+        Suspension::Await(span) => (span, "await", "rejection"),
+        Suspension::Yield(span) => (span, "yield", "error"),
+      };
+      if !self.reported.insert(span) {
+        continue;
+      }
+      let cause = match try_await {
+        Some(try_await) => {
+          let location =
+            self.context.source_map.lookup_char_pos(try_await.lo());
+          format!(
+            "The `try` block awaits at {}:{}; if it rejects,",
+            location.line, location.col.0
+          )
+        }
+        None => "If the `try` block throws,".to_string(),
+      };
+      self.context.add_diagnostic_with_hint(
+        span,
+        "no-return-in-finally-adjacent",
+        &format!(
+          "`{}` in a `finally` block can mask the {} of the `try` block",
+          keyword, error
+        ),
+        &format!(
+          "{} the {} is held back while the `finally` block is suspended, and lost if it throws; move the cleanup out of the `finally` block, or use `.finally()`",
+          cause, error
+        ),
+      );
+    }
+  }
+}
+
+impl Visit for NoReturnInFinallyAdjacentVisitor {
+  noop_visit_type!();
+
+  fn visit_try_stmt(&mut self, try_stmt: &TryStmt, parent: &dyn Node) {
+    self.check(try_stmt);
+    swc_ecmascript::visit::visit_try_stmt(self, try_stmt, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn always() -> Box<NoReturnInFinallyAdjacent> {
+    NoReturnInFinallyAdjacent::with_options(NoReturnInFinallyAdjacentOptions {
+      mode: NoReturnInFinallyAdjacentMode::Always,
+    })
+  }
+
+  #[test]
+  fn no_return_in_finally_adjacent_valid() {
+    assert_lint_ok_n::<NoReturnInFinallyAdjacent>(vec![
+      "async function f() { try { await a(); } finally { b(); } }",
+      "async function f() { try { await a(); } catch { await b(); } }",
+      // Nothing in flight in the `try` block.
+      "async function f() { try { a(); } finally { await b(); } }",
+      "function* f() { try { yield a(); } finally { yield b(); } }",
+      // The `await` in the `try` block belongs to a nested function.
+      "async function f() { try { g(async () => await a()); } finally { await b(); } }",
+      // Nested functions in the `finally` block are exempt.
+      "async function f() { try { await a(); } finally { setTimeout(async () => { await b(); }); } }",
+      "async function f() { try { await a(); } finally { queue.push(async function () { await b(); }); } }",
+      // Synchronous disposal, like with explicit resource management.
+      "async function f() { const res = open(); try { await res.read(); } finally { res[Symbol.dispose](); } }",
+      "async function f() { try { await a(); } finally { b().finally(() => c()); } }",
+    ]);
+  }
+
+  #[test]
+  fn no_return_in_finally_adjacent_invalid() {
+    assert_lint_err::<NoReturnInFinallyAdjacent>(
+      "async function f() { try { await a(); } finally { await b(); } }",
+      50,
+    );
+    assert_lint_err::<NoReturnInFinallyAdjacent>(
+      "async function* f() { try { await a(); } finally { yield b(); } }",
+      51,
+    );
+    assert_lint_err_on_line::<NoReturnInFinallyAdjacent>(
+      "async function f() {\n  try {\n    for (const x of xs) await x;\n  } finally {\n    if (c) {\n      await close();\n    }\n  }\n}",
+      6,
+      6,
+    );
+    // Reported once, for the outer `finally` block.
+    assert_lint_err_n::<NoReturnInFinallyAdjacent>(
+      "async function f() { try { await a(); } finally { try { await b(); } finally { await c(); } } }",
+      vec![56, 79],
+    );
+
+    let diagnostics = lint(
+      NoReturnInFinallyAdjacent::new(),
+      "async function f() {\n  try {\n    await a();\n  } finally {\n    await b();\n  }\n}",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "`await` in a `finally` block can mask the rejection of the `try` block"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("The `try` block awaits at 3:4; if it rejects, the rejection is held back while the `finally` block is suspended, and lost if it throws; move the cleanup out of the `finally` block, or use `.finally()`")
+    );
+  }
+
+  #[test]
+  fn no_return_in_finally_adjacent_always() {
+    assert_lint_ok_with(
+      always(),
+      "async function f() { try { a(); } finally { b(); } }",
+    );
+    assert_lint_err_on_line_n_with(
+      always(),
+      "async function f() { try { a(); } finally { await b(); } }",
+      vec![(1, 44)],
+    );
+    assert_lint_err_on_line_n_with(
+      always(),
+      "function* f() { try { a(); } finally { yield b(); } }",
+      vec![(1, 39)],
+    );
+
+    let diagnostics = lint(
+      always(),
+      "function* f() { try { a(); } finally { yield b(); } }",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "`yield` in a `finally` block can mask the error of the `try` block"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("If the `try` block throws, the error is held back while the `finally` block is suspended, and lost if it throws; move the cleanup out of the `finally` block, or use `.finally()`")
+    );
+  }
+}
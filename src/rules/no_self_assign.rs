@@ -20,36 +20,104 @@ use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 
-pub struct NoSelfAssign;
+#[derive(Clone, Copy)]
+pub struct NoSelfAssignOptions {
+  /// Also reports member expressions assigned to themselves, like
+  /// `a.b = a.b`.
+  pub props: bool,
+}
+
+impl Default for NoSelfAssignOptions {
+  fn default() -> Self {
+    Self { props: true }
+  }
+}
+
+pub struct NoSelfAssign {
+  options: NoSelfAssignOptions,
+}
+
+impl NoSelfAssign {
+  pub fn with_options(options: NoSelfAssignOptions) -> Box<Self> {
+    Box::new(NoSelfAssign { options })
+  }
+}
 
 impl LintRule for NoSelfAssign {
   fn new() -> Box<Self> {
-    Box::new(NoSelfAssign)
+    Self::with_options(NoSelfAssignOptions::default())
   }
 
   fn code(&self) -> &'static str {
     "no-self-assign"
   }
 
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows assigning a variable to itself.
+
+Self assignments have no effect and are usually an incomplete refactoring. Member expressions are the same if their objects are and they access the same property, where a computed property is only known if it is a literal, eg. `a["b"]` but not `a[b]`.
+
+### Options
+
+- `NoSelfAssignOptions::props`: also reports member expressions, `true` by default.
+
+### Valid:
+```typescript
+var a = a;
+a = b;
+[a, b] = [b, a];
+```
+
+### Invalid:
+```typescript
+a = a;
+```
+```typescript
+[a, b] = [a, b];
+```
+```typescript
+a.b = a["b"];
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     module: &swc_ecmascript::ast::Module,
   ) {
-    let mut visitor = NoSelfAssignVisitor::new(context);
+    let mut visitor = NoSelfAssignVisitor {
+      context,
+      options: self.options,
+    };
     visitor.visit_module(module, module);
   }
 }
 
 struct NoSelfAssignVisitor {
   context: Arc<Context>,
+  options: NoSelfAssignOptions,
 }
 
-impl NoSelfAssignVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
+/// Returns the name of the property accessed by `member_expr`, unless it is
+/// computed from something other than a literal.
+fn static_property_name(member_expr: &MemberExpr) -> Option<String> {
+  match &*member_expr.prop {
+    Expr::Ident(ident) if !member_expr.computed => Some(ident.sym.to_string()),
+    Expr::Lit(lit) if member_expr.computed => lit.get_key(),
+    Expr::Tpl(tpl) if member_expr.computed => tpl.get_key(),
+    _ => None,
   }
+}
 
+impl NoSelfAssignVisitor {
   fn add_diagnostic(&mut self, span: Span, name: &str) {
     self.context.add_diagnostic(
       span,
@@ -58,41 +126,11 @@ impl NoSelfAssignVisitor {
     );
   }
 
-  fn is_same_property(
-    &mut self,
-    left: &MemberExpr,
-    right: &MemberExpr,
-  ) -> bool {
-    if left.computed == right.computed {
-      match (&*left.prop, &*right.prop) {
-        (Expr::Ident(l_ident), Expr::Ident(r_ident)) => {
-          if self.is_same_ident(l_ident, r_ident) {
-            return true;
-          }
-        }
-        (Expr::Lit(l_lit), Expr::Lit(r_lit)) => {
-          if l_lit.get_key() == r_lit.get_key() {
-            return true;
-          }
-        }
-        _ => {}
-      };
-    }
-
-    let left_name = if left.computed { None } else { left.get_key() };
-    let right_name = if right.computed {
-      None
-    } else {
-      right.get_key()
-    };
-
-    if let Some(lname) = left_name {
-      if let Some(rname) = right_name {
-        return lname == rname;
-      }
+  fn is_same_property(&self, left: &MemberExpr, right: &MemberExpr) -> bool {
+    match (static_property_name(left), static_property_name(right)) {
+      (Some(left_name), Some(right_name)) => left_name == right_name,
+      _ => false,
     }
-
-    false
   }
 
   fn is_same_member(&mut self, left: &MemberExpr, right: &MemberExpr) -> bool {
@@ -123,8 +161,8 @@ impl NoSelfAssignVisitor {
   }
 
   fn check_same_member(&mut self, left: &MemberExpr, right: &MemberExpr) {
-    if self.is_same_member(left, right) {
-      let name = (&*right.prop).get_key().expect("Should be identifier");
+    if self.options.props && self.is_same_member(left, right) {
+      let name = static_property_name(right).expect("Should be static");
       self.add_diagnostic(right.span, &name);
     }
   }
@@ -267,11 +305,7 @@ impl NoSelfAssignVisitor {
 impl Visit for NoSelfAssignVisitor {
   noop_visit_type!();
 
-  fn visit_assign_expr(
-    &mut self,
-    assign_expr: &AssignExpr,
-    _parent: &dyn Node,
-  ) {
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, parent: &dyn Node) {
     if assign_expr.op == AssignOp::Assign {
       match &assign_expr.left {
         PatOrExpr::Pat(l_pat) => {
@@ -282,6 +316,7 @@ impl Visit for NoSelfAssignVisitor {
         }
       }
     }
+    swc_ecmascript::visit::visit_assign_expr(self, assign_expr, parent);
   }
 }
 
@@ -320,6 +355,8 @@ mod tests {
       "a.b = c.b",
       "a.b = a[b]",
       "a[b] = a.b",
+      "a[b] = a[b]",
+      "a[b.c] = a[b.c]",
       "a.b().c = a.b().c",
       "b().c = b().c",
       "a.null = a[/(?<zero>0)/]",
@@ -351,7 +388,7 @@ mod tests {
     assert_lint_err::<NoSelfAssign>("({'a': b} = {[`a`]: b})", 20);
     assert_lint_err::<NoSelfAssign>("({1: b} = {[1]: b})", 16);
     assert_lint_err_n::<NoSelfAssign>("({a, b} = {a, b})", vec![11, 14]);
-    assert_lint_err_n::<NoSelfAssign>("({a, b} = {b, a})", vec![14, 11]);
+    assert_lint_err_n::<NoSelfAssign>("({a, b} = {b, a})", vec![11, 14]);
     assert_lint_err::<NoSelfAssign>("({a, b} = {c, a})", 14);
     assert_lint_err_n::<NoSelfAssign>(
       "({a: {b}, c: [d]} = {a: {b}, c: [d]})",
@@ -360,7 +397,6 @@ mod tests {
     assert_lint_err::<NoSelfAssign>("({a, b} = {a, ...x, b})", 20);
     assert_lint_err::<NoSelfAssign>("a.b = a.b", 6);
     assert_lint_err::<NoSelfAssign>("a.b.c = a.b.c", 8);
-    assert_lint_err::<NoSelfAssign>("a[b] = a[b]", 7);
     assert_lint_err::<NoSelfAssign>("a['b'] = a['b']", 9);
     assert_lint_err_on_line::<NoSelfAssign>(
       "a[\n    'b'] = a[\n    'b']",
@@ -370,4 +406,54 @@ mod tests {
     assert_lint_err::<NoSelfAssign>("this.x = this.x", 9);
     assert_lint_err::<NoSelfAssign>("a['/(?<zero>0)/'] = a[/(?<zero>0)/]", 20);
   }
+
+  #[test]
+  fn no_self_assign_props() {
+    let without_props =
+      || NoSelfAssign::with_options(NoSelfAssignOptions { props: false });
+    assert_lint_err::<NoSelfAssign>("a.b = a['b']", 6);
+    assert_lint_err::<NoSelfAssign>("a['b'] = a.b", 9);
+    assert_lint_err::<NoSelfAssign>("a[0] = a['0']", 7);
+    assert_lint_err::<NoSelfAssign>("a.b[`c`] = a.b.c", 11);
+    assert_lint_err::<NoSelfAssign>("[a.b] = [a['b']]", 9);
+
+    let diagnostics = lint(NoSelfAssign::new(), "a.b = a['b']");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "\"b\" is assigned to itself");
+
+    assert_lint_ok_with(without_props(), "a.b = a['b']");
+    assert_lint_ok_with(without_props(), "this.x = this.x");
+    assert_lint_ok_with(without_props(), "[a.b] = [a.b]");
+    assert_lint_err_on_line_n_with(without_props(), "a = a", vec![(1, 4)]);
+    assert_lint_err_on_line_n_with(
+      without_props(),
+      "[a, b.c] = [a, b.c]",
+      vec![(1, 12)],
+    );
+  }
+
+  #[test]
+  fn no_self_assign_destructuring() {
+    assert_lint_err_n::<NoSelfAssign>(
+      "[a, [b, c]] = [a, [b, d]]",
+      vec![15, 19],
+    );
+    assert_lint_err::<NoSelfAssign>("({a: [b]} = {a: [b]})", 17);
+    assert_lint_err::<NoSelfAssign>("[{a}] = [{a}]", 10);
+    assert_lint_ok_n::<NoSelfAssign>(vec![
+      "[a, [b, c]] = [a2, [c, b]]",
+      "({a: [b]} = {a: [c]})",
+    ]);
+  }
+
+  #[test]
+  fn no_self_assign_nested() {
+    assert_lint_err::<NoSelfAssign>("foo(() => { a = a; });", 16);
+    assert_lint_err::<NoSelfAssign>("b = (a = a);", 9);
+    assert_lint_err_on_line::<NoSelfAssign>(
+      "function foo() {\n  if (a) {\n    this.a = this.a;\n  }\n}",
+      3,
+      13,
+    );
+  }
 }
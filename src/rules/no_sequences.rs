@@ -0,0 +1,256 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::sync::Arc;
+use swc_ecmascript::ast::{
+  ArrowExpr, BlockStmtOrExpr, CallExpr, Expr, ExprOrSpread, ForStmt, NewExpr,
+  ParenExpr, SeqExpr, VarDeclOrExpr,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+pub struct NoSequencesOptions {
+  /// Allows sequences wrapped in parentheses, eg. `foo = (a, b)`.
+  ///
+  /// In positions where the grammar already requires parentheses around
+  /// the sequence, like call arguments and arrow bodies, a second pair is
+  /// needed, eg. `foo(((a, b)))`.
+  pub allow_in_parens: bool,
+}
+
+impl Default for NoSequencesOptions {
+  fn default() -> Self {
+    Self {
+      allow_in_parens: true,
+    }
+  }
+}
+
+pub struct NoSequences {
+  options: NoSequencesOptions,
+}
+
+impl NoSequences {
+  pub fn with_options(options: NoSequencesOptions) -> Box<Self> {
+    Box::new(NoSequences { options })
+  }
+}
+
+impl LintRule for NoSequences {
+  fn new() -> Box<Self> {
+    Self::with_options(NoSequencesOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-sequences"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows the comma operator.
+
+The comma operator evaluates every operand but only returns the last one, which hides side effects and is often a typo.
+
+### Options
+
+`NoSequencesOptions::allow_in_parens`: allows sequences wrapped in parentheses, `true` by default. Where the grammar already requires parentheses around the sequence, like call arguments and arrow bodies, a second pair is needed, eg. `foo(((a, b)))`.
+
+### Valid:
+```typescript
+for (let i = 0, j = 1; i < j; i++, j--) {}
+```
+```typescript
+foo = (a, b);
+```
+
+### Invalid:
+```typescript
+a, b;
+```
+```typescript
+foo((a, b));
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor =
+      NoSequencesVisitor::new(context, self.options.allow_in_parens);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoSequencesVisitor {
+  context: Arc<Context>,
+  allow_in_parens: bool,
+}
+
+impl NoSequencesVisitor {
+  fn new(context: Arc<Context>, allow_in_parens: bool) -> Self {
+    Self {
+      context,
+      allow_in_parens,
+    }
+  }
+
+  /// Visits the elements of a sequence without reporting the sequence.
+  fn visit_seq_elements(&mut self, seq_expr: &SeqExpr, parent: &dyn Node) {
+    for expr in &seq_expr.exprs {
+      self.visit_expr(expr, parent);
+    }
+  }
+
+  /// Visits an expression in a position where a single pair of
+  /// parentheses is required by the grammar and doesn't count as
+  /// explicitly wrapping a sequence.
+  fn visit_required_parens(&mut self, expr: &Expr, parent: &dyn Node) {
+    match expr {
+      Expr::Paren(ParenExpr { expr: inner, .. }) => match &**inner {
+        Expr::Seq(seq_expr) => self.visit_seq_expr(seq_expr, parent),
+        _ => self.visit_expr(expr, parent),
+      },
+      _ => self.visit_expr(expr, parent),
+    }
+  }
+
+  fn visit_args(&mut self, args: &[ExprOrSpread], parent: &dyn Node) {
+    for arg in args {
+      self.visit_required_parens(&arg.expr, parent);
+    }
+  }
+}
+
+impl Visit for NoSequencesVisitor {
+  noop_visit_type!();
+
+  fn visit_seq_expr(&mut self, seq_expr: &SeqExpr, parent: &dyn Node) {
+    self.context.add_diagnostic_with_hint(
+      seq_expr.span,
+      "no-sequences",
+      "Unexpected use of comma operator",
+      "Did you mean to write an array literal or use a different operator?",
+    );
+    self.visit_seq_elements(seq_expr, parent);
+  }
+
+  fn visit_paren_expr(&mut self, paren_expr: &ParenExpr, parent: &dyn Node) {
+    if self.allow_in_parens {
+      if let Expr::Seq(seq_expr) = &*paren_expr.expr {
+        self.visit_seq_elements(seq_expr, parent);
+        return;
+      }
+    }
+    swc_ecmascript::visit::visit_paren_expr(self, paren_expr, parent);
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
+    // Sequences are idiomatic in the head of a `for` loop,
+    // eg. `for (i = 0, j = 1; i < j; i++, j--)`.
+    match &for_stmt.init {
+      Some(VarDeclOrExpr::Expr(expr)) => match &**expr {
+        Expr::Seq(seq_expr) => self.visit_seq_elements(seq_expr, parent),
+        _ => self.visit_expr(expr, parent),
+      },
+      Some(VarDeclOrExpr::VarDecl(var_decl)) => {
+        self.visit_var_decl(var_decl, parent)
+      }
+      None => {}
+    }
+    if let Some(test) = &for_stmt.test {
+      self.visit_expr(test, parent);
+    }
+    if let Some(update) = &for_stmt.update {
+      match &**update {
+        Expr::Seq(seq_expr) => self.visit_seq_elements(seq_expr, parent),
+        _ => self.visit_expr(update, parent),
+      }
+    }
+    self.visit_stmt(&for_stmt.body, parent);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    self.visit_expr_or_super(&call_expr.callee, parent);
+    self.visit_args(&call_expr.args, parent);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+    self.visit_expr(&new_expr.callee, parent);
+    if let Some(args) = &new_expr.args {
+      self.visit_args(args, parent);
+    }
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+    for param in &arrow_expr.params {
+      self.visit_pat(param, parent);
+    }
+    match &arrow_expr.body {
+      BlockStmtOrExpr::BlockStmt(block_stmt) => {
+        self.visit_block_stmt(block_stmt, parent)
+      }
+      BlockStmtOrExpr::Expr(expr) => self.visit_required_parens(expr, parent),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn disallow_parens() -> Box<NoSequences> {
+    NoSequences::with_options(NoSequencesOptions {
+      allow_in_parens: false,
+    })
+  }
+
+  #[test]
+  fn no_sequences_valid() {
+    assert_lint_ok_n::<NoSequences>(vec![
+      "for (let i = 0, j = 1;;) {}",
+      "for (i = 0, j = 1; i < j; i++, j--) {}",
+      "foo(a, b);",
+      "const x = (a, b);",
+      "foo(((a, b)));",
+      "const f = () => ((a, b));",
+      "const arr = [a, b];",
+    ]);
+  }
+
+  #[test]
+  fn no_sequences_invalid() {
+    assert_lint_err::<NoSequences>("a, b;", 0);
+    assert_lint_err::<NoSequences>("foo((a, b));", 5);
+    assert_lint_err::<NoSequences>("new Foo((a, b));", 9);
+    assert_lint_err::<NoSequences>("const f = () => (a, b);", 17);
+    assert_lint_err::<NoSequences>("for (;; i++) { a(), b(); }", 15);
+    assert_lint_err::<NoSequences>("for (a(), b();;) { c, d; }", 19);
+    assert_lint_err_on_line::<NoSequences>(
+      "function f() {\n  return a, b;\n}",
+      2,
+      9,
+    );
+  }
+
+  #[test]
+  fn no_sequences_disallow_parens() {
+    assert_lint_ok_with(disallow_parens(), "for (let i = 0, j = 1;;) {}");
+    assert_lint_err_on_line_n_with(
+      disallow_parens(),
+      "const x = (a, b);",
+      vec![(1, 11)],
+    );
+    assert_lint_err_on_line_n_with(
+      disallow_parens(),
+      "foo(((a, b)));",
+      vec![(1, 6)],
+    );
+  }
+}
@@ -24,6 +24,34 @@ impl LintRule for NoSetterReturn {
     "no-setter-return"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows returning values from setters.
+
+The value returned by a setter is ignored.
+
+### Valid:
+```typescript
+const a = {
+  set setter(value) {
+    this.value = value;
+  },
+};
+```
+
+### Invalid:
+```typescript
+const a = {
+  set setter(value) {
+    return "something";
+  },
+};
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -0,0 +1,289 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::scopes::BindingKind;
+use std::sync::Arc;
+use swc_ecmascript::ast::Module;
+
+/// Which bindings declared after the scope shadowing them are reported.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Hoist {
+  /// Only functions, since calling them before their declaration works.
+  Functions,
+  /// All bindings.
+  All,
+  /// None, only bindings declared before the shadowing one are reported.
+  Never,
+}
+
+pub struct NoShadowOptions {
+  pub hoist: Hoist,
+  /// Names which may be shadowed, eg. `["err", "resolve", "reject"]`.
+  pub allow: Vec<String>,
+}
+
+impl Default for NoShadowOptions {
+  fn default() -> Self {
+    Self {
+      hoist: Hoist::Functions,
+      allow: vec![],
+    }
+  }
+}
+
+/// Reports declarations which have the same name as a binding of an
+/// enclosing scope, which makes the outer binding inaccessible and is easy
+/// to confuse with it, eg. a callback parameter named like an import.
+pub struct NoShadow {
+  options: NoShadowOptions,
+}
+
+impl NoShadow {
+  pub fn with_options(options: NoShadowOptions) -> Box<Self> {
+    Box::new(NoShadow { options })
+  }
+}
+
+impl LintRule for NoShadow {
+  fn new() -> Box<Self> {
+    Self::with_options(NoShadowOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-shadow"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows declaring variables with the same name as a variable of an enclosing scope.
+
+The shadowed variable can't be used in the inner scope, and the two are easily mixed up when reading the code, eg. a callback parameter named like an import.
+
+### Options
+
+- `NoShadowOptions::hoist`: which outer bindings declared after the shadowing one are reported, `Hoist::Functions` (default), `Hoist::All` or `Hoist::Never`.
+- `NoShadowOptions::allow`: names which may be shadowed, empty by default.
+
+### Valid:
+```typescript
+import { readFile } from "./fs.ts";
+const contents = files.map((file) => readFile(file));
+```
+```typescript
+function foo(a: number) {}
+function bar(a: number) {}
+```
+
+### Invalid:
+```typescript
+import { path } from "./path.ts";
+files.forEach((path) => console.log(path));
+```
+```typescript
+const a = 1;
+if (true) {
+  let a = 2;
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, _module: &Module) {
+    let mut vars = context.scope_manager.bindings().collect::<Vec<_>>();
+    vars.sort_by_key(|(_, var)| var.span().lo);
+    for (id, var) in vars {
+      if self.options.allow.iter().any(|name| *name == *id.0) {
+        continue;
+      }
+      let shadowed = match context.scope_manager.shadowed_binding(id) {
+        Some(shadowed) => shadowed,
+        None => continue,
+      };
+      if shadowed.span().lo > var.span().lo {
+        let hoisted = match self.options.hoist {
+          Hoist::All => true,
+          Hoist::Functions => shadowed.kind() == BindingKind::Function,
+          Hoist::Never => false,
+        };
+        if !hoisted {
+          continue;
+        }
+      }
+      let line = context.source_map.lookup_char_pos(shadowed.span().lo).line;
+      context.add_diagnostic(
+        var.span(),
+        "no-shadow",
+        &format!(
+          "'{}' is already declared in the upper scope on line {}",
+          id.0, line
+        ),
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_shadow_valid() {
+    assert_lint_ok_n::<NoShadow>(vec![
+      "function foo(a) {}\nfunction bar(a) {}",
+      "const a = 1;\nfunction foo(b) { return a + b; }",
+      "{ let a = 1; }\n{ let a = 2; }",
+      "var a = 1;\nvar a = 2;",
+      "function foo() { var a; if (b) { var a; } }",
+      "for (let i = 0; i < 1; i++) {}\nfor (let i = 0; i < 1; i++) {}",
+      "try {} catch (e) {}\ntry {} catch (e) {}",
+      "const a = (b) => b;\nconst c = (b) => b;",
+    ]);
+  }
+
+  #[test]
+  fn no_shadow_params() {
+    assert_lint_err_on_line::<NoShadow>(
+      "import { path } from './path.ts';\nfiles.forEach((path) => path);",
+      2,
+      15,
+    );
+    assert_lint_err_on_line::<NoShadow>(
+      "import * as fs from './fs.ts';\nfunction read(fs) {}",
+      2,
+      14,
+    );
+    assert_lint_err_on_line::<NoShadow>(
+      "import readFile from './fs.ts';\nconst f = function ({ readFile }) {};",
+      2,
+      22,
+    );
+    assert_lint_err::<NoShadow>("function a(a) {}", 11);
+
+    let diagnostics = lint(
+      NoShadow::new(),
+      "const a = 1;\n\nfunction foo() {\n  const a = 2;\n}",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "'a' is already declared in the upper scope on line 1"
+    );
+    assert_eq!(diagnostics[0].range.start.line, 4);
+  }
+
+  #[test]
+  fn no_shadow_catch_clause() {
+    assert_lint_err_on_line::<NoShadow>(
+      "const err = null;\ntry {} catch (err) {}",
+      2,
+      14,
+    );
+    assert_lint_err_on_line::<NoShadow>(
+      "try {} catch (e) {\n  try {} catch (e) {}\n}",
+      2,
+      16,
+    );
+    assert_lint_err_on_line::<NoShadow>(
+      "try {} catch ({ message }) {\n  const message = '';\n}",
+      2,
+      8,
+    );
+  }
+
+  #[test]
+  fn no_shadow_class_expressions() {
+    assert_lint_err_on_line::<NoShadow>(
+      "const a = 1;\nconst Foo = class {\n  bar(a) {}\n};",
+      3,
+      6,
+    );
+    assert_lint_err_on_line::<NoShadow>(
+      "const a = 1;\nconst Foo = class {\n  bar = () => { let a; };\n};",
+      3,
+      20,
+    );
+    assert_lint_err_on_line::<NoShadow>(
+      "class Foo {}\nconst bar = class { baz(Foo) {} };",
+      2,
+      24,
+    );
+  }
+
+  #[test]
+  fn no_shadow_block_scoped() {
+    assert_lint_err_on_line::<NoShadow>(
+      "const a = 1;\nif (true) {\n  let a = 2;\n}",
+      3,
+      6,
+    );
+    assert_lint_err_on_line::<NoShadow>(
+      "let a;\nfor (let a = 0; a < 1; a++) {}",
+      2,
+      9,
+    );
+    assert_lint_err_on_line::<NoShadow>("let a;\nfor (const a of b) {}", 2, 11);
+    assert_lint_err_on_line_n::<NoShadow>(
+      "let a;\n{\n  let a;\n  {\n    let a;\n  }\n}",
+      vec![(3, 6), (5, 8)],
+    );
+  }
+
+  #[test]
+  fn no_shadow_hoist() {
+    let with_hoist = |hoist: Hoist| {
+      NoShadow::with_options(NoShadowOptions {
+        hoist,
+        ..Default::default()
+      })
+    };
+    let function_later = "function foo() { let bar; }\nfunction bar() {}";
+    let variable_later = "function foo() { let bar; }\nconst bar = 1;";
+
+    assert_lint_err::<NoShadow>(function_later, 21);
+    assert_lint_ok::<NoShadow>(variable_later);
+
+    assert_lint_err_on_line_n_with(
+      with_hoist(Hoist::All),
+      function_later,
+      vec![(1, 21)],
+    );
+    assert_lint_err_on_line_n_with(
+      with_hoist(Hoist::All),
+      variable_later,
+      vec![(1, 21)],
+    );
+
+    assert_lint_ok_with(with_hoist(Hoist::Never), function_later);
+    assert_lint_ok_with(with_hoist(Hoist::Never), variable_later);
+  }
+
+  #[test]
+  fn no_shadow_allow() {
+    let rule = || {
+      NoShadow::with_options(NoShadowOptions {
+        allow: vec![
+          "err".to_string(),
+          "resolve".to_string(),
+          "reject".to_string(),
+        ],
+        ..Default::default()
+      })
+    };
+    assert_lint_ok_with(
+      rule(),
+      "function foo(resolve, reject) {\n  new Promise((resolve, reject) => {});\n}",
+    );
+    assert_lint_ok_with(
+      rule(),
+      "try {} catch (err) {\n  try {} catch (err) {}\n}",
+    );
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "const error = 1;\ntry {} catch (error) {}",
+      vec![(2, 14)],
+    );
+  }
+}
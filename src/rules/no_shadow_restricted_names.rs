@@ -27,6 +27,31 @@ impl LintRule for NoShadowRestrictedNames {
   fn code(&self) -> &'static str {
     "no-shadow-restricted-names"
   }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows shadowing restricted global names like `NaN`, `Infinity`, `undefined`, `eval` and `arguments`.
+
+Code expects these names to refer to the built-in values.
+
+### Valid:
+```typescript
+function foo(bar) {
+  var baz;
+}
+```
+
+### Invalid:
+```typescript
+function NaN() {}
+```
+```typescript
+const undefined = 1;
+```"#
+  }
 }
 
 struct NoShadowRestrictedNamesVisitor {
@@ -51,7 +76,9 @@ impl NoShadowRestrictedNamesVisitor {
         // trying to assign `undefined`
         // Check is scope is valid for current pattern
         if &ident.sym == "undefined" && check_scope {
-          if let Some(_binding) = self.context.scope.var(&ident.to_id()) {
+          if let Some(_binding) =
+            self.context.scope_manager.binding(&ident.to_id())
+          {
             self.report_shadowing(&ident);
           }
           return;
@@ -229,41 +256,44 @@ mod tests {
       ]
     );
 
-    assert_lint_err_on_line_n::<NoShadowRestrictedNames>(
-      "function arguments(arguments) { var arguments; !function arguments(arguments) { try {} catch(arguments) {} }; }",
-      vec![
-        (1, 9),
-        (1, 19),
-        (1, 36),
-        (1, 57),
-        (1, 67),
-        (1, 93),
-      ]
-    );
-
-    assert_lint_err_on_line_n::<NoShadowRestrictedNames>(
-      "function eval(eval) { var eval; !function eval(eval) { try {} catch(eval) {} }; }",
-      vec![
-        (1, 9),
-        (1, 14),
-        (1, 26),
-        (1, 42),
-        (1, 47),
-        (1, 68),
-      ]
-    );
-
-    assert_lint_err_on_line_n::<NoShadowRestrictedNames>(
-      "var eval = (eval) => { var eval; !function eval(eval) { try {} catch(eval) {} }; }",
-      vec![
-        (1, 4),
-        (1, 12),
-        (1, 27),
-        (1, 43),
-        (1, 48),
-        (1, 69),
-      ]
-    );
+    // `arguments` and `eval` can't be bound in modules
+    with_syntax_errors(|| {
+      assert_lint_err_on_line_n::<NoShadowRestrictedNames>(
+        "function arguments(arguments) { var arguments; !function arguments(arguments) { try {} catch(arguments) {} }; }",
+        vec![
+          (1, 9),
+          (1, 19),
+          (1, 36),
+          (1, 57),
+          (1, 67),
+          (1, 93),
+        ]
+      );
+
+      assert_lint_err_on_line_n::<NoShadowRestrictedNames>(
+        "function eval(eval) { var eval; !function eval(eval) { try {} catch(eval) {} }; }",
+        vec![
+          (1, 9),
+          (1, 14),
+          (1, 26),
+          (1, 42),
+          (1, 47),
+          (1, 68),
+        ]
+      );
+
+      assert_lint_err_on_line_n::<NoShadowRestrictedNames>(
+        "var eval = (eval) => { var eval; !function eval(eval) { try {} catch(eval) {} }; }",
+        vec![
+          (1, 4),
+          (1, 12),
+          (1, 27),
+          (1, 43),
+          (1, 48),
+          (1, 69),
+        ]
+      );
+    });
 
     assert_lint_err_on_line_n::<NoShadowRestrictedNames>(
       "var {undefined} = obj; var {a: undefined} = obj; var {a: {b: {undefined}}} = obj; var {a, ...undefined} = obj;",
@@ -286,15 +316,17 @@ mod tests {
       27,
     );
 
-    assert_lint_err_on_line_n::<NoShadowRestrictedNames>(
-      r#"
+    with_syntax_errors(|| {
+      assert_lint_err_on_line_n::<NoShadowRestrictedNames>(
+        r#"
 function foo1(...undefined) {}
 function foo2(...NaN) {}
 function foo3(...arguments) {}
 function foo4(...Infinity) {}
 function foo5(...eval) {}
       "#,
-      vec![(2, 17), (3, 17), (4, 17), (5, 17), (6, 17)],
-    )
+        vec![(2, 17), (3, 17), (4, 17), (5, 17), (6, 17)],
+      );
+    });
   }
 }
@@ -0,0 +1,151 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::overwritten_props;
+use std::sync::Arc;
+use swc_ecmascript::ast::{Module, ObjectLit};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  /// Reports properties of object literals which are overwritten by a later
+  /// spread of an inline object literal with the same key, like `a` in
+  /// `{ a, ...{ a: 2 } }`, so their value is never used.
+  ///
+  /// Properties written after another spread are reported by
+  /// `no-duplicate-spread-overrides` instead.
+  NoShorthandPropertyOverwrite, "no-shorthand-property-overwrite", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows properties of object literals which are overwritten by a later spread of an object literal with the same key.
+
+In `{ a, ...{ a: 2 } }` the value of the property `a` is never used. Properties written after another spread are reported by `no-duplicate-spread-overrides` instead.
+
+### Valid:
+```typescript
+const o = { a, b: 1 };
+```
+```typescript
+const o = { ...{ a: 2 }, a };
+```
+
+### Invalid:
+```typescript
+const o = { a, ...{ a: 2 } };
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoShorthandPropertyOverwriteVisitor { context };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoShorthandPropertyOverwriteVisitor {
+  context: Arc<Context>,
+}
+
+impl Visit for NoShorthandPropertyOverwriteVisitor {
+  noop_visit_type!();
+
+  fn visit_object_lit(&mut self, obj_lit: &ObjectLit, parent: &dyn Node) {
+    for prop in overwritten_props(obj_lit) {
+      if prop.after_spread {
+        continue;
+      }
+      let location = self
+        .context
+        .source_map
+        .lookup_char_pos(prop.overwritten_by.lo());
+      self.context.add_diagnostic_with_hint(
+        prop.span,
+        "no-shorthand-property-overwrite",
+        &format!(
+          "Property `{}` is overwritten by a later spread, so its value is never used",
+          prop.key
+        ),
+        &format!(
+          "`{}` is overwritten by the spread at {}:{}; remove the property or the key from the spread",
+          prop.key, location.line, location.col.0
+        ),
+      );
+    }
+    swc_ecmascript::visit::visit_object_lit(self, obj_lit, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_shorthand_property_overwrite_valid() {
+    assert_lint_ok_n::<NoShorthandPropertyOverwrite>(vec![
+      "const o = { a, b: 1 };",
+      "const o = { ...{ a: 2 }, a };",
+      "const o = { a, ...{ b: 2 } };",
+      "const o = { a, ...rest };",
+      "const o = { a, ...{ [a]: 2 } };",
+      "const o = { [key]: 1, ...{ a: 2 } };",
+      "const o = { get a() { return 1; }, ...{ a: 2 } };",
+      "const o = { set a(value) {}, ...{ a: 2 } };",
+      "const o = { a, ...(cond ? { a: 2 } : {}) };",
+      "const o = { a, ...(cond && { a: 2 }) };",
+      "const o = { __proto__: proto, ...{ __proto__: null } };",
+      // Reported by `no-duplicate-spread-overrides`.
+      "const o = { ...defaults, a, ...{ a: 2 } };",
+    ]);
+  }
+
+  #[test]
+  fn no_shorthand_property_overwrite_invalid() {
+    assert_lint_err::<NoShorthandPropertyOverwrite>(
+      "const o = { a, ...{ a: 2 } };",
+      12,
+    );
+    assert_lint_err::<NoShorthandPropertyOverwrite>(
+      "const o = { a: 1, ...{ a: 2 } };",
+      12,
+    );
+    assert_lint_err::<NoShorthandPropertyOverwrite>(
+      "const o = { \"a\": 1, ...({ 'a': 2 }) };",
+      12,
+    );
+    assert_lint_err::<NoShorthandPropertyOverwrite>(
+      "const o = { a() {}, ...{ a } };",
+      12,
+    );
+    assert_lint_err::<NoShorthandPropertyOverwrite>(
+      "const o = { a, ...{ ...{ a: 2 } } };",
+      12,
+    );
+    assert_lint_err::<NoShorthandPropertyOverwrite>(
+      "const o = { a, ...{ get a() { return 2; } } };",
+      12,
+    );
+    assert_lint_err_n::<NoShorthandPropertyOverwrite>(
+      "const o = { a, b, c, ...{ a: 1, b: 2 } };",
+      vec![12, 15],
+    );
+    assert_lint_err_on_line::<NoShorthandPropertyOverwrite>(
+      "const o = {\n  a,\n  b: { c, ...{ c: 1 } },\n};",
+      3,
+      7,
+    );
+
+    let diagnostics = lint(
+      NoShorthandPropertyOverwrite::new(),
+      "const o = { a, ...{ a: 2 } };",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "Property `a` is overwritten by a later spread, so its value is never used"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("`a` is overwritten by the spread at 1:15; remove the property or the key from the spread")
+    );
+  }
+}
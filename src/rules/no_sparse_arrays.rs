@@ -18,6 +18,31 @@ impl LintRule for NoSparseArrays {
     "no-sparse-arrays"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Disallows sparse arrays, ie. array literals with empty slots.
+
+`[1, , 3]` is easy to misread and usually a typo. A single trailing comma, as in `[1, 2, 3,]`, doesn't make a hole and is allowed.
+
+### Valid:
+```typescript
+const a = [1, null, 3];
+```
+```typescript
+const a = [
+  1,
+  2,
+];
+```
+
+### Invalid:
+```typescript
+const a = [1, , 3];
+```
+```typescript
+const a = [1, 2, ,];
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -44,15 +69,17 @@ impl Visit for NoSparseArraysVisitor {
   fn visit_array_lit(
     &mut self,
     array_lit: &swc_ecmascript::ast::ArrayLit,
-    _parent: &dyn Node,
+    parent: &dyn Node,
   ) {
     if array_lit.elems.iter().any(|e| e.is_none()) {
-      self.context.add_diagnostic(
+      self.context.add_diagnostic_with_hint(
         array_lit.span,
         "no-sparse-arrays",
         "Sparse arrays are not allowed",
+        "Fill the empty slots, eg. with `undefined`, or remove the extra commas",
       );
     }
+    swc_ecmascript::visit::visit_array_lit(self, array_lit, parent);
   }
 }
 
@@ -66,4 +93,29 @@ mod tests {
     assert_lint_ok::<NoSparseArrays>("const sparseArray1 = [1,null,3];");
     assert_lint_err::<NoSparseArrays>("const sparseArray = [1,,3];", 20);
   }
+
+  #[test]
+  fn no_sparse_arrays_trailing_commas() {
+    assert_lint_ok_n::<NoSparseArrays>(vec![
+      "const a = [1, 2, 3,];",
+      "const a = [\n  1,\n  2,\n];",
+      "const a = [];",
+      "const [, b] = c;",
+    ]);
+    assert_lint_err::<NoSparseArrays>("const a = [1, 2, ,];", 10);
+    assert_lint_err::<NoSparseArrays>("const a = [,];", 10);
+    assert_lint_err::<NoSparseArrays>("const a = [, 1];", 10);
+  }
+
+  #[test]
+  fn no_sparse_arrays_nested() {
+    assert_lint_err::<NoSparseArrays>("const a = [[1, , 3]];", 11);
+    assert_lint_err_n::<NoSparseArrays>(
+      "const a = [, [1, , 3]];",
+      vec![10, 13],
+    );
+    assert_lint_err::<NoSparseArrays>("foo([1, [2, [3, , 4]]]);", 12);
+    assert_lint_err::<NoSparseArrays>("const a = { b: [1, , 2] };", 15);
+    assert_lint_err::<NoSparseArrays>("const a = () => [1, , 2];", 16);
+  }
 }
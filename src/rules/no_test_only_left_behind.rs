@@ -0,0 +1,532 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::deno_test_util::{step_callback, test_callback};
+use crate::swc_util::member_path;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use swc_common::{Span, Spanned, SyntaxContext};
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSpread, ExprOrSuper, ImportSpecifier, Lit, MemberExpr,
+  Module, ModuleDecl, ModuleItem, ObjectLit, Pat, Prop, PropName, PropOrSpread,
+  VarDecl, VarDeclKind,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::{Visit, VisitWith};
+
+pub struct NoTestOnlyLeftBehindOptions {
+  /// The modules whose imports are test functions. A module matches if its
+  /// specifier is equal to one of them, or ends with `/` followed by one of
+  /// them, so `testing/bdd.ts` matches any version of the standard library.
+  pub frameworks: Vec<String>,
+  /// The imported test functions whose `.only` member is reported.
+  pub functions: Vec<String>,
+}
+
+impl Default for NoTestOnlyLeftBehindOptions {
+  fn default() -> Self {
+    Self {
+      frameworks: vec![
+        "@jest/globals".to_string(),
+        "@std/testing/bdd".to_string(),
+        "bun:test".to_string(),
+        "jsr:@std/testing/bdd".to_string(),
+        "mocha".to_string(),
+        "node:test".to_string(),
+        "testing/bdd.ts".to_string(),
+        "vitest".to_string(),
+      ],
+      functions: vec![
+        "context".to_string(),
+        "describe".to_string(),
+        "it".to_string(),
+        "suite".to_string(),
+        "test".to_string(),
+      ],
+    }
+  }
+}
+
+/// Reports tests which are focused with `only`, which makes the test runner
+/// skip every other test:
+///
+/// - `Deno.test.only(...)`
+/// - `Deno.test({ only: true, ... })`, also when the options are stored in a
+///   `const` binding
+/// - `t.step({ only: true, ... })`, where `t` is a test context
+/// - `it.only(...)` and `describe.only(...)`, where the function is imported
+///   from a known test framework
+///
+/// `ignore` and `skip` markers are not reported.
+pub struct NoTestOnlyLeftBehind {
+  options: NoTestOnlyLeftBehindOptions,
+}
+
+impl NoTestOnlyLeftBehind {
+  pub fn with_options(options: NoTestOnlyLeftBehindOptions) -> Box<Self> {
+    Box::new(NoTestOnlyLeftBehind { options })
+  }
+
+  fn is_framework(&self, specifier: &str) -> bool {
+    self.options.frameworks.iter().any(|framework| {
+      specifier == framework
+        || (specifier.ends_with(framework.as_str())
+          && specifier[..specifier.len() - framework.len()].ends_with('/'))
+    })
+  }
+
+  /// Collects the bindings of the test functions and the namespaces imported
+  /// from known test frameworks.
+  fn imports(&self, module: &Module) -> (HashSet<Id>, HashSet<Id>) {
+    let mut functions = HashSet::new();
+    let mut namespaces = HashSet::new();
+    for item in &module.body {
+      let import_decl = match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => import_decl,
+        _ => continue,
+      };
+      if !self.is_framework(&import_decl.src.value) {
+        continue;
+      }
+      for specifier in &import_decl.specifiers {
+        match specifier {
+          ImportSpecifier::Named(named) => {
+            let imported = named.imported.as_ref().unwrap_or(&named.local);
+            if self.options.functions.iter().any(|f| *f == *imported.sym) {
+              functions.insert(named.local.to_id());
+            }
+          }
+          ImportSpecifier::Namespace(namespace) => {
+            namespaces.insert(namespace.local.to_id());
+          }
+          ImportSpecifier::Default(_) => {}
+        }
+      }
+    }
+    (functions, namespaces)
+  }
+}
+
+impl LintRule for NoTestOnlyLeftBehind {
+  fn new() -> Box<Self> {
+    Self::with_options(NoTestOnlyLeftBehindOptions::default())
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended_for_tests"]
+  }
+
+  fn code(&self) -> &'static str {
+    "no-test-only-left-behind"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows tests which are focused with `only`.
+
+While a test is focused, the test runner skips every other test, so a passing run doesn't cover them. `Deno.test.only()`, `Deno.test({ only: true })`, `t.step({ only: true })` and `.only` of functions imported from known test frameworks, like `it.only()`, are reported.
+
+### Options
+
+- `NoTestOnlyLeftBehindOptions::frameworks`: the modules whose imports are test functions. A module matches if its specifier is equal to one of them, or ends with `/` followed by one of them. By default `@jest/globals`, `@std/testing/bdd`, `bun:test`, `jsr:@std/testing/bdd`, `mocha`, `node:test`, `testing/bdd.ts` and `vitest`.
+- `NoTestOnlyLeftBehindOptions::functions`: the imported test functions whose `.only` member is reported, `context`, `describe`, `it`, `suite` and `test` by default.
+
+### Valid:
+```typescript
+Deno.test("a", () => {});
+```
+
+### Invalid:
+```typescript
+Deno.test.only("a", () => {});
+```
+```typescript
+Deno.test({ name: "a", only: true, fn() {} });
+```
+```typescript
+import { it } from "jsr:@std/testing/bdd";
+it.only("a", () => {});
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let (functions, namespaces) = self.imports(module);
+    let mut const_objects = ConstObjectCollector::default();
+    module.visit_with(module, &mut const_objects);
+    let mut visitor = NoTestOnlyLeftBehindVisitor {
+      context,
+      options: &self.options,
+      functions,
+      namespaces,
+      const_objects: const_objects.objects,
+      test_contexts: HashSet::new(),
+      reported: HashSet::new(),
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+/// Collects the object literals which `const` bindings are initialized to.
+#[derive(Default)]
+struct ConstObjectCollector {
+  objects: HashMap<Id, ObjectLit>,
+}
+
+impl Visit for ConstObjectCollector {
+  noop_visit_type!();
+
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, parent: &dyn Node) {
+    if var_decl.kind == VarDeclKind::Const {
+      for decl in &var_decl.decls {
+        if let (Pat::Ident(ident), Some(init)) = (&decl.name, &decl.init) {
+          if let Expr::Object(object) = unwrap_paren(init) {
+            self.objects.insert(ident.to_id(), object.clone());
+          }
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_var_decl(self, var_decl, parent);
+  }
+}
+
+struct NoTestOnlyLeftBehindVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a NoTestOnlyLeftBehindOptions,
+  functions: HashSet<Id>,
+  namespaces: HashSet<Id>,
+  const_objects: HashMap<Id, ObjectLit>,
+  /// The first parameters of test and step functions.
+  test_contexts: HashSet<Id>,
+  /// `only` properties already reported, for options objects stored in a
+  /// binding which is used more than once.
+  reported: HashSet<Span>,
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn is_only_key(key: &PropName) -> bool {
+  match key {
+    PropName::Ident(ident) => ident.sym == *"only",
+    PropName::Str(s) => s.value == *"only",
+    _ => false,
+  }
+}
+
+/// Finds the `only: true` property of an options object.
+fn only_property(object: &ObjectLit) -> Option<Span> {
+  object.props.iter().find_map(|prop| match prop {
+    PropOrSpread::Prop(prop) => match &**prop {
+      Prop::KeyValue(kv) if is_only_key(&kv.key) => {
+        match unwrap_paren(&kv.value) {
+          Expr::Lit(Lit::Bool(b)) if b.value => Some(kv.span()),
+          _ => None,
+        }
+      }
+      _ => None,
+    },
+    PropOrSpread::Spread(_) => None,
+  })
+}
+
+/// Returns the first parameter of a test or step function, which is the
+/// test context.
+fn context_param(callback: &Expr) -> Option<Id> {
+  let param = match callback {
+    Expr::Fn(fn_expr) => fn_expr.function.params.first().map(|p| &p.pat),
+    Expr::Arrow(arrow_expr) => arrow_expr.params.first(),
+    _ => None,
+  };
+  match param {
+    Some(Pat::Ident(ident)) => Some(ident.to_id()),
+    _ => None,
+  }
+}
+
+/// Returns the span of `.only` in a member expression, from the end of the
+/// object to the end of the property.
+fn only_member_span(member_expr: &MemberExpr) -> Span {
+  Span::new(
+    member_expr.obj.span().hi(),
+    member_expr.span.hi(),
+    SyntaxContext::empty(),
+  )
+}
+
+impl<'a> NoTestOnlyLeftBehindVisitor<'a> {
+  fn report(&mut self, span: Span) {
+    if !self.reported.insert(span) {
+      return;
+    }
+    self.context.add_diagnostic_with_hint(
+      span,
+      "no-test-only-left-behind",
+      "Remove 'only' before committing",
+      "Every other test is skipped while a test is focused with `only`, so a passing run doesn't cover them",
+    );
+  }
+
+  /// Reports `only: true` in the options objects among the arguments,
+  /// following `const` bindings one level.
+  fn check_options(&mut self, args: &[ExprOrSpread]) {
+    for arg in args {
+      if arg.spread.is_some() {
+        continue;
+      }
+      let span = match unwrap_paren(&arg.expr) {
+        Expr::Object(object) => only_property(object),
+        Expr::Ident(ident) => self
+          .const_objects
+          .get(&ident.to_id())
+          .and_then(only_property),
+        _ => None,
+      };
+      if let Some(span) = span {
+        self.report(span);
+      }
+    }
+  }
+
+  fn is_test_function(&self, expr: &Expr) -> bool {
+    match unwrap_paren(expr) {
+      Expr::Ident(ident) => self.functions.contains(&ident.to_id()),
+      Expr::Member(member_expr) => match (&member_expr.obj, &*member_expr.prop)
+      {
+        (ExprOrSuper::Expr(obj), Expr::Ident(prop))
+          if !member_expr.computed =>
+        {
+          match unwrap_paren(obj) {
+            Expr::Ident(ns) => {
+              self.namespaces.contains(&ns.to_id())
+                && self.options.functions.iter().any(|f| *f == *prop.sym)
+            }
+            _ => false,
+          }
+        }
+        _ => false,
+      },
+      _ => false,
+    }
+  }
+
+  /// Checks if the callee is `t.step`, where `t` is a test context.
+  fn is_step(&self, callee: &Expr) -> bool {
+    match member_path(callee) {
+      (Expr::Ident(t), path) => {
+        path.len() == 1
+          && path[0] == "step"
+          && self.test_contexts.contains(&t.to_id())
+      }
+      _ => false,
+    }
+  }
+
+  fn check(&mut self, call_expr: &CallExpr) {
+    let callee = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => unwrap_paren(callee),
+      ExprOrSuper::Super(_) => return,
+    };
+
+    if let Some(callback) = test_callback(call_expr) {
+      if let Some(id) = context_param(unwrap_paren(callback)) {
+        self.test_contexts.insert(id);
+      }
+    }
+    let is_deno_test = match member_path(callee) {
+      (Expr::Ident(ident), path) if ident.sym == *"Deno" => {
+        match path
+          .iter()
+          .map(String::as_str)
+          .collect::<Vec<_>>()
+          .as_slice()
+        {
+          ["test"] | ["test", "ignore"] => true,
+          ["test", "only"] => {
+            if let Expr::Member(member_expr) = callee {
+              self.report(only_member_span(member_expr));
+            }
+            true
+          }
+          _ => false,
+        }
+      }
+      _ => false,
+    };
+    if is_deno_test {
+      self.check_options(&call_expr.args);
+      return;
+    }
+
+    if self.is_step(callee) {
+      if let Some(callback) = step_callback(call_expr) {
+        if let Some(id) = context_param(unwrap_paren(callback)) {
+          self.test_contexts.insert(id);
+        }
+      }
+      self.check_options(&call_expr.args);
+      return;
+    }
+
+    if let Expr::Member(member_expr) = callee {
+      let is_only = match &*member_expr.prop {
+        Expr::Ident(prop) if !member_expr.computed => prop.sym == *"only",
+        Expr::Lit(Lit::Str(s)) if member_expr.computed => s.value == *"only",
+        _ => false,
+      };
+      if !is_only {
+        return;
+      }
+      if let ExprOrSuper::Expr(obj) = &member_expr.obj {
+        if self.is_test_function(obj) {
+          self.report(only_member_span(member_expr));
+        }
+      }
+    }
+  }
+}
+
+impl<'a> Visit for NoTestOnlyLeftBehindVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    self.check(call_expr);
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_test_only_left_behind_valid() {
+    assert_lint_ok_n::<NoTestOnlyLeftBehind>(vec![
+      "Deno.test('a', () => {});",
+      "Deno.test({ name: 'a', fn() {} });",
+      "Deno.test({ name: 'a', only: false, fn() {} });",
+      "Deno.test('a', { only: false }, () => {});",
+      "const options = { only: false };\nDeno.test('a', options, () => {});",
+      "Deno.test({ name: 'a', only, fn() {} });",
+      // `ignore` and `skip` have different semantics.
+      "Deno.test.ignore('a', () => {});",
+      "Deno.test({ name: 'a', ignore: true, fn() {} });",
+      "Deno.test('a', async (t) => { await t.step({ name: 'b', ignore: true, fn() {} }); });",
+      "import { it } from 'mocha';\nit.skip('a', () => {});",
+      // Not a test framework.
+      "import { it } from './utils.ts';\nit.only('a', () => {});",
+      "it.only('a', () => {});",
+      "import { describe } from 'vitest';\nfoo.only();",
+      // Not a test context.
+      "t.step({ name: 'a', only: true, fn() {} });",
+      "query.only(true);",
+    ]);
+  }
+
+  #[test]
+  fn no_test_only_left_behind_invalid() {
+    assert_lint_err::<NoTestOnlyLeftBehind>(
+      "Deno.test.only('a', () => {});",
+      9,
+    );
+    assert_lint_err::<NoTestOnlyLeftBehind>(
+      "Deno.test({ name: 'a', only: true, fn() {} });",
+      23,
+    );
+    assert_lint_err::<NoTestOnlyLeftBehind>(
+      "Deno.test('a', { only: true }, () => {});",
+      17,
+    );
+    assert_lint_err::<NoTestOnlyLeftBehind>(
+      "Deno.test({ name: 'a', 'only': true, fn() {} });",
+      23,
+    );
+    assert_lint_err_on_line::<NoTestOnlyLeftBehind>(
+      "Deno.test('a', async (t) => {\n  await t.step({ name: 'b', only: true, fn() {} });\n});",
+      2,
+      28,
+    );
+    assert_lint_err_on_line::<NoTestOnlyLeftBehind>(
+      "Deno.test('a', async function (ctx) {\n  await ctx.step('b', async (t) => {\n    await t.step({ name: 'c', only: true, fn() {} });\n  });\n});",
+      3,
+      30,
+    );
+  }
+
+  #[test]
+  fn no_test_only_left_behind_const_options() {
+    assert_lint_err_on_line::<NoTestOnlyLeftBehind>(
+      "const options = { sanitizeOps: false, only: true };\nDeno.test('a', options, () => {});",
+      1,
+      38,
+    );
+    // Reported once, however often the options are used.
+    assert_lint_err_on_line_n::<NoTestOnlyLeftBehind>(
+      "const options = { only: true };\nDeno.test('a', options, () => {});\nDeno.test('b', options, () => {});",
+      vec![(1, 18)],
+    );
+    // Only one level is followed.
+    assert_lint_ok::<NoTestOnlyLeftBehind>(
+      "const base = { only: true };\nconst options = base;\nDeno.test('a', options, () => {});",
+    );
+  }
+
+  #[test]
+  fn no_test_only_left_behind_frameworks() {
+    assert_lint_err_on_line::<NoTestOnlyLeftBehind>(
+      "import { describe, it } from 'mocha';\nit.only('a', () => {});",
+      2,
+      2,
+    );
+    assert_lint_err_on_line::<NoTestOnlyLeftBehind>(
+      "import { describe, it } from 'https://deno.land/std@0.200.0/testing/bdd.ts';\ndescribe.only('a', () => {});",
+      2,
+      8,
+    );
+    assert_lint_err_on_line::<NoTestOnlyLeftBehind>(
+      "import { test as spec } from 'vitest';\nspec.only('a', () => {});",
+      2,
+      4,
+    );
+    assert_lint_err_on_line::<NoTestOnlyLeftBehind>(
+      "import * as bdd from 'jsr:@std/testing/bdd';\nbdd.it.only('a', () => {});",
+      2,
+      6,
+    );
+    assert_lint_ok::<NoTestOnlyLeftBehind>(
+      "import { it } from 'my-testing/bdd.ts.bak';\nit.only('a', () => {});",
+    );
+
+    let rule =
+      NoTestOnlyLeftBehind::with_options(NoTestOnlyLeftBehindOptions {
+        frameworks: vec!["@company/testing".to_string()],
+        functions: vec!["scenario".to_string()],
+      });
+    let diagnostics = lint(
+      rule,
+      "import { scenario, it } from '@company/testing';\nscenario.only('a', () => {});\nit.only('b', () => {});",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+  }
+
+  #[test]
+  fn no_test_only_left_behind_message() {
+    let diagnostics = lint(
+      NoTestOnlyLeftBehind::new(),
+      "Deno.test.only('a', () => {});",
+    );
+    assert_eq!(diagnostics[0].message, "Remove 'only' before committing");
+    assert_eq!(diagnostics[0].range.end.col, 14);
+  }
+}
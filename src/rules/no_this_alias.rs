@@ -19,6 +19,29 @@ impl LintRule for NoThisAlias {
     "no-this-alias"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows assigning `this` to a variable.
+
+Arrow functions keep the `this` of their surrounding scope, which makes aliases like `const self = this` unnecessary.
+
+### Valid:
+```typescript
+const { props, state } = this;
+```
+```typescript
+foo(() => this.bar());
+```
+
+### Invalid:
+```typescript
+const self = this;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
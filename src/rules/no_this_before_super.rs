@@ -19,6 +19,43 @@ impl LintRule for NoThisBeforeSuper {
     "no-this-before-super"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows using `this` or `super` in the constructor of a derived class before calling `super()`.
+
+`this` isn't initialized until `super()` has been called; accessing it throws a `ReferenceError`.
+
+### Valid:
+```typescript
+class A extends B {
+  constructor() {
+    super();
+    this.a = 0;
+  }
+}
+```
+
+### Invalid:
+```typescript
+class A extends B {
+  constructor() {
+    this.a = 0;
+    super();
+  }
+}
+```
+```typescript
+class A extends B {
+  constructor() {
+    super(this.foo());
+  }
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
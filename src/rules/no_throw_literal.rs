@@ -1,7 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
-use swc_ecmascript::ast::{Expr, ThrowStmt};
+use swc_ecmascript::ast::{BinaryOp, Expr, ThrowStmt};
 use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
@@ -19,6 +19,37 @@ impl LintRule for NoThrowLiteral {
     "no-throw-literal"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Disallows throwing literals and other values that aren't errors.
+
+Only `Error` objects capture a stack trace, which makes the origin of the exception traceable. Values which can't be errors, like literals, template strings, object and array literals, are reported, while values which may be errors, like `new Foo()`, identifiers and the results of calls, are allowed.
+
+### Valid:
+```typescript
+throw new Error("kumiko");
+```
+```typescript
+throw e;
+```
+```typescript
+throw cause ? new Error("kumiko", { cause }) : createError();
+```
+
+### Invalid:
+```typescript
+throw "kumiko";
+```
+```typescript
+throw null;
+```
+```typescript
+throw `${code}: ${message}`;
+```
+```typescript
+throw { code, message };
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -39,16 +70,45 @@ impl NoThrowLiteralVisitor {
   }
 }
 
+/// Checks if `expr` may evaluate to an error object. Only values which are
+/// never objects with a stack trace are considered not to be errors.
+fn may_be_error(expr: &Expr) -> bool {
+  match expr {
+    Expr::Lit(_)
+    | Expr::Tpl(_)
+    | Expr::Object(_)
+    | Expr::Array(_)
+    | Expr::Unary(_)
+    | Expr::Update(_) => false,
+    Expr::Ident(ident) => ident.sym != *"undefined",
+    Expr::Bin(bin_expr) => match bin_expr.op {
+      BinaryOp::LogicalOr | BinaryOp::NullishCoalescing => {
+        may_be_error(&bin_expr.left) || may_be_error(&bin_expr.right)
+      }
+      BinaryOp::LogicalAnd => may_be_error(&bin_expr.right),
+      _ => false,
+    },
+    Expr::Cond(cond_expr) => {
+      may_be_error(&cond_expr.cons) && may_be_error(&cond_expr.alt)
+    }
+    Expr::Assign(assign_expr) => may_be_error(&assign_expr.right),
+    Expr::Seq(seq_expr) => match seq_expr.exprs.last() {
+      Some(last) => may_be_error(last),
+      None => true,
+    },
+    Expr::Paren(paren_expr) => may_be_error(&paren_expr.expr),
+    Expr::TsAs(ts_as) => may_be_error(&ts_as.expr),
+    Expr::TsNonNull(ts_non_null) => may_be_error(&ts_non_null.expr),
+    Expr::TsTypeAssertion(assertion) => may_be_error(&assertion.expr),
+    _ => true,
+  }
+}
+
 impl Visit for NoThrowLiteralVisitor {
   noop_visit_type!();
 
-  fn visit_throw_stmt(&mut self, throw_stmt: &ThrowStmt, _parent: &dyn Node) {
+  fn visit_throw_stmt(&mut self, throw_stmt: &ThrowStmt, parent: &dyn Node) {
     match &*throw_stmt.arg {
-      Expr::Lit(_) => self.context.add_diagnostic(
-        throw_stmt.span,
-        "no-throw-literal",
-        "expected an error object to be thrown",
-      ),
       Expr::Ident(ident) if ident.sym == *"undefined" => {
         self.context.add_diagnostic(
           throw_stmt.span,
@@ -56,8 +116,15 @@ impl Visit for NoThrowLiteralVisitor {
           "do not throw undefined",
         )
       }
+      arg if !may_be_error(arg) => self.context.add_diagnostic_with_hint(
+        throw_stmt.span,
+        "no-throw-literal",
+        "expected an error object to be thrown",
+        "Wrap the value in an error, eg. `new Error(message)`",
+      ),
       _ => {}
     }
+    swc_ecmascript::visit::visit_throw_stmt(self, throw_stmt, parent);
   }
 }
 
@@ -83,4 +150,45 @@ mod tests {
   fn check_variable() {
     assert_lint_ok::<NoThrowLiteral>("throw e");
   }
+
+  #[test]
+  fn check_non_error_values() {
+    assert_lint_err::<NoThrowLiteral>("throw `kumiko`", 0);
+    assert_lint_err::<NoThrowLiteral>("throw `${code}: ${message}`", 0);
+    assert_lint_err::<NoThrowLiteral>("throw { code, message }", 0);
+    assert_lint_err::<NoThrowLiteral>("throw [error]", 0);
+    assert_lint_err::<NoThrowLiteral>("throw 'a' + b", 0);
+    assert_lint_err::<NoThrowLiteral>("throw !a", 0);
+    assert_lint_err::<NoThrowLiteral>("throw (1, 'a')", 0);
+    assert_lint_err::<NoThrowLiteral>("throw a ? new Error() : 'kumiko'", 0);
+    assert_lint_err::<NoThrowLiteral>("throw a ? 'kumiko' : b", 0);
+    assert_lint_err::<NoThrowLiteral>("throw a && 'kumiko'", 0);
+    assert_lint_err::<NoThrowLiteral>("throw 'kumiko' as unknown", 0);
+    assert_lint_err_on_line::<NoThrowLiteral>(
+      "function foo() {\n  if (a) {\n    throw { code: 1 };\n  }\n}",
+      3,
+      4,
+    );
+  }
+
+  #[test]
+  fn check_possible_errors() {
+    assert_lint_ok_n::<NoThrowLiteral>(vec![
+      "throw new Error('kumiko')",
+      "throw new CustomError()",
+      "throw createError()",
+      "throw this.error",
+      "throw error.cause",
+      "throw a ? new Error() : b",
+      "throw a || new Error()",
+      "throw a ?? 'kumiko'",
+      "throw a && b",
+      "throw (error = new Error())",
+      "throw (foo(), error)",
+      "throw error!",
+      "throw error as Error",
+      "async function foo() { throw await foo(); }",
+      "function* foo() { throw yield; }",
+    ]);
+  }
 }
@@ -26,6 +26,36 @@ impl LintRule for NoUndef {
     "no-undef"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows references to variables which are never declared, unless they are known globals.
+
+An undeclared variable is usually a typo, or a missing import, and throws a `ReferenceError` at runtime.
+
+### Valid:
+```typescript
+var a = 1;
+a++;
+console.log(a);
+```
+```typescript
+function f(b) {
+  b;
+}
+```
+
+### Invalid:
+```typescript
+a = 1;
+```
+```typescript
+var a = b;
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut collector = BindingCollector {
       top_level_ctxt: context.top_level_ctxt,
@@ -0,0 +1,486 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::{overload_groups, ExtractMethod, Method};
+use std::sync::Arc;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  Class, ClassMember, Decl, ExportDecl, Function, Module, ModuleDecl,
+  ModuleItem, ParamOrTsParamProp, Pat, Stmt, TsFnParam, TsInterfaceBody,
+  TsKeywordTypeKind, TsLit, TsModuleBlock, TsParamPropParam, TsType, TsTypeAnn,
+  TsTypeElement, TsTypeLit, TsTypeParamDecl, TsUnionOrIntersectionType,
+};
+use swc_ecmascript::visit::{Node, Visit};
+
+declare_rule! {
+  /// Reports overload signatures which can never be picked, because an
+  /// earlier overload of the same group accepts every argument list they
+  /// accept. TypeScript resolves a call to the first matching overload, so
+  /// the more specific overloads have to come first.
+  ///
+  /// There's no type checker, so the parameter types are compared by their
+  /// syntax, going by this lattice:
+  ///
+  /// - a type is as wide as itself (compared by its text), and an omitted
+  ///   type is as wide as `any`
+  /// - `any` and `unknown` are wider than every type, and `never` is narrower
+  ///   than every type
+  /// - a literal type is narrower than its primitive, eg. `"a"` than `string`
+  /// - type references, object and function types, arrays and tuples are
+  ///   narrower than `object`
+  /// - `A[]` is narrower than `B[]` if `A` is narrower than `B`
+  /// - a union is narrower than a type if all its members are, and a type is
+  ///   narrower than a union if it's narrower than one of its members
+  ///
+  /// Type references are never assumed to be narrower than each other, since
+  /// they can't be resolved. Signatures with type parameters, optional or
+  /// rest parameters, or a different number of parameters are not compared.
+  NoUnorderedOverloadSpecificity, "no-unordered-overload-specificity", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows overload signatures which can never be picked, because an earlier overload of the same group accepts every argument list they accept.
+
+TypeScript resolves a call to the first matching overload, so the more specific overloads have to come first. Without a type checker, parameter types are compared by their syntax: `any` and `unknown` are wider than every type, a literal type is narrower than its primitive, and a union is wider than each of its members. Signatures with type parameters, optional or rest parameters, or a different number of parameters are not compared.
+
+### Valid:
+```typescript
+function f(x: string): string;
+function f(x: unknown): void;
+function f(x: unknown) {}
+```
+
+### Invalid:
+```typescript
+function f(x: unknown): void;
+function f(x: string): string;
+function f(x: unknown) {}
+```
+```typescript
+declare function f(x: string | number): void;
+declare function f(x: "a"): void;
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoUnorderedOverloadSpecificityVisitor { context };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoUnorderedOverloadSpecificityVisitor {
+  context: Arc<Context>,
+}
+
+/// The parameters of an overload signature, or `None` if the signature
+/// can't be compared.
+struct Signature<'a> {
+  params: Option<Vec<Option<&'a TsType>>>,
+}
+
+impl<'a> Signature<'a> {
+  fn new<I>(type_params: &Option<TsTypeParamDecl>, params: I) -> Self
+  where
+    I: IntoIterator<Item = Option<Option<&'a TsType>>>,
+  {
+    if type_params.is_some() {
+      return Signature { params: None };
+    }
+    Signature {
+      params: params.into_iter().collect(),
+    }
+  }
+
+  fn of_function(function: &'a Function) -> Option<Self> {
+    if function.body.is_some() {
+      return None;
+    }
+    Some(Signature::new(
+      &function.type_params,
+      function.params.iter().map(|param| pat_type(&param.pat)),
+    ))
+  }
+
+  fn of_ts_fn(
+    type_params: &Option<TsTypeParamDecl>,
+    params: &'a [TsFnParam],
+  ) -> Self {
+    Signature::new(
+      type_params,
+      params.iter().map(|param| match param {
+        TsFnParam::Ident(ident) if !ident.optional => {
+          Some(type_ann(&ident.type_ann))
+        }
+        TsFnParam::Array(array) if !array.optional => {
+          Some(type_ann(&array.type_ann))
+        }
+        TsFnParam::Object(object) if !object.optional => {
+          Some(type_ann(&object.type_ann))
+        }
+        _ => None,
+      }),
+    )
+  }
+}
+
+fn type_ann(type_ann: &Option<TsTypeAnn>) -> Option<&TsType> {
+  type_ann.as_ref().map(|type_ann| &*type_ann.type_ann)
+}
+
+/// Returns the type of a required parameter, or `None` for optional and
+/// rest parameters.
+fn pat_type(pat: &Pat) -> Option<Option<&TsType>> {
+  match pat {
+    Pat::Ident(ident) if !ident.optional => Some(type_ann(&ident.type_ann)),
+    Pat::Array(array) if !array.optional => Some(type_ann(&array.type_ann)),
+    Pat::Object(object) if !object.optional => Some(type_ann(&object.type_ann)),
+    _ => None,
+  }
+}
+
+/// Extracts the overload signature of an item, which has no body.
+trait OverloadSignature {
+  fn signature(&self) -> Option<Signature<'_>>;
+}
+
+impl OverloadSignature for ModuleItem {
+  fn signature(&self) -> Option<Signature<'_>> {
+    let decl = match self {
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+        decl,
+        ..
+      })) => decl,
+      ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+      _ => return None,
+    };
+    match decl {
+      Decl::Fn(fn_decl) => Signature::of_function(&fn_decl.function),
+      _ => None,
+    }
+  }
+}
+
+impl OverloadSignature for ClassMember {
+  fn signature(&self) -> Option<Signature<'_>> {
+    match self {
+      ClassMember::Method(method) => Signature::of_function(&method.function),
+      ClassMember::Constructor(constructor) if constructor.body.is_none() => {
+        Some(Signature::new(
+          &None,
+          constructor.params.iter().map(|param| match param {
+            ParamOrTsParamProp::Param(param) => pat_type(&param.pat),
+            ParamOrTsParamProp::TsParamProp(prop) => match &prop.param {
+              TsParamPropParam::Ident(ident) if !ident.optional => {
+                Some(type_ann(&ident.type_ann))
+              }
+              _ => None,
+            },
+          }),
+        ))
+      }
+      _ => None,
+    }
+  }
+}
+
+impl OverloadSignature for TsTypeElement {
+  fn signature(&self) -> Option<Signature<'_>> {
+    match self {
+      TsTypeElement::TsMethodSignature(method) => {
+        Some(Signature::of_ts_fn(&method.type_params, &method.params))
+      }
+      TsTypeElement::TsCallSignatureDecl(call) => {
+        Some(Signature::of_ts_fn(&call.type_params, &call.params))
+      }
+      TsTypeElement::TsConstructSignatureDecl(construct) => Some(
+        Signature::of_ts_fn(&construct.type_params, &construct.params),
+      ),
+      _ => None,
+    }
+  }
+}
+
+fn unwrap_parens(ty: &TsType) -> &TsType {
+  match ty {
+    TsType::TsParenthesizedType(paren) => unwrap_parens(&paren.type_ann),
+    _ => ty,
+  }
+}
+
+fn keyword(ty: &TsType) -> Option<TsKeywordTypeKind> {
+  match ty {
+    TsType::TsKeywordType(keyword) => Some(keyword.kind),
+    _ => None,
+  }
+}
+
+fn union_members(ty: &TsType) -> Option<&[Box<TsType>]> {
+  match ty {
+    TsType::TsUnionOrIntersectionType(
+      TsUnionOrIntersectionType::TsUnionType(union),
+    ) => Some(&union.types),
+    _ => None,
+  }
+}
+
+impl NoUnorderedOverloadSpecificityVisitor {
+  fn type_text(&self, ty: &TsType) -> String {
+    self
+      .context
+      .span_text(ty.span())
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .collect()
+  }
+
+  /// Checks if the written type `sub` is narrower than, or as wide as, the
+  /// written type `sup`.
+  fn is_narrower(&self, sub: &TsType, sup: &TsType) -> bool {
+    use TsKeywordTypeKind::*;
+
+    let (sub, sup) = (unwrap_parens(sub), unwrap_parens(sup));
+    if matches!(keyword(sup), Some(TsAnyKeyword) | Some(TsUnknownKeyword))
+      || keyword(sub) == Some(TsNeverKeyword)
+      || self.type_text(sub) == self.type_text(sup)
+    {
+      return true;
+    }
+    if let Some(members) = union_members(sub) {
+      return members.iter().all(|member| self.is_narrower(member, sup));
+    }
+    if let Some(members) = union_members(sup) {
+      return members.iter().any(|member| self.is_narrower(sub, member));
+    }
+
+    match (sub, keyword(sup)) {
+      (TsType::TsLitType(lit), Some(kind)) => matches!(
+        (&lit.lit, kind),
+        (TsLit::Str(_), TsStringKeyword)
+          | (TsLit::Tpl(_), TsStringKeyword)
+          | (TsLit::Number(_), TsNumberKeyword)
+          | (TsLit::Bool(_), TsBooleanKeyword)
+          | (TsLit::BigInt(_), TsBigIntKeyword)
+      ),
+      (TsType::TsTypeRef(_), Some(TsObjectKeyword))
+      | (TsType::TsTypeLit(_), Some(TsObjectKeyword))
+      | (TsType::TsFnOrConstructorType(_), Some(TsObjectKeyword))
+      | (TsType::TsArrayType(_), Some(TsObjectKeyword))
+      | (TsType::TsTupleType(_), Some(TsObjectKeyword)) => true,
+      (TsType::TsArrayType(sub), None) => match sup {
+        TsType::TsArrayType(sup) => {
+          self.is_narrower(&sub.elem_type, &sup.elem_type)
+        }
+        _ => false,
+      },
+      _ => false,
+    }
+  }
+
+  /// Checks if every argument list accepted by `later` is accepted by
+  /// `earlier` too.
+  fn is_shadowed(&self, later: &Signature, earlier: &Signature) -> bool {
+    let (later, earlier) = match (&later.params, &earlier.params) {
+      (Some(later), Some(earlier)) if later.len() == earlier.len() => {
+        (later, earlier)
+      }
+      _ => return false,
+    };
+    later
+      .iter()
+      .zip(earlier)
+      .all(|(sub, sup)| match (sub, sup) {
+        (_, None) => true,
+        (None, Some(sup)) => matches!(
+          keyword(unwrap_parens(sup)),
+          Some(TsKeywordTypeKind::TsAnyKeyword)
+            | Some(TsKeywordTypeKind::TsUnknownKeyword)
+        ),
+        (Some(sub), Some(sup)) => self.is_narrower(sub, sup),
+      })
+  }
+
+  fn check<'a, T, U>(&self, items: T)
+  where
+    T: IntoIterator<Item = &'a U>,
+    U: ExtractMethod + OverloadSignature + Spanned + 'a,
+  {
+    for (method, group) in overload_groups(items) {
+      let overloads: Vec<(Span, Signature)> = group
+        .iter()
+        .filter_map(|item| item.signature().map(|sig| (item.span(), sig)))
+        .collect();
+      for (i, (span, later)) in overloads.iter().enumerate() {
+        let shadowing = overloads[..i]
+          .iter()
+          .find(|(_, earlier)| self.is_shadowed(later, earlier));
+        if let Some((earlier_span, _)) = shadowing {
+          self.report(*span, *earlier_span, &method);
+        }
+      }
+    }
+  }
+
+  fn report(&self, span: Span, earlier_span: Span, method: &Method) {
+    let location = self.context.source_map.lookup_char_pos(earlier_span.lo());
+    self.context.add_diagnostic_with_hint(
+      span,
+      "no-unordered-overload-specificity",
+      &format!(
+        "This overload of '{}' is unreachable, because an earlier overload accepts the same arguments",
+        method.get_name()
+      ),
+      &format!(
+        "The overload at {}:{} is picked first for every call this one matches; move the more specific overload above it",
+        location.line, location.col.0
+      ),
+    );
+  }
+}
+
+impl Visit for NoUnorderedOverloadSpecificityVisitor {
+  fn visit_module(&mut self, module: &Module, parent: &dyn Node) {
+    self.check(&module.body);
+    swc_ecmascript::visit::visit_module(self, module, parent);
+  }
+
+  fn visit_ts_module_block(
+    &mut self,
+    ts_module_block: &TsModuleBlock,
+    parent: &dyn Node,
+  ) {
+    self.check(&ts_module_block.body);
+    swc_ecmascript::visit::visit_ts_module_block(self, ts_module_block, parent);
+  }
+
+  fn visit_class(&mut self, class: &Class, parent: &dyn Node) {
+    self.check(&class.body);
+    swc_ecmascript::visit::visit_class(self, class, parent);
+  }
+
+  fn visit_ts_type_lit(&mut self, ts_type_lit: &TsTypeLit, parent: &dyn Node) {
+    self.check(&ts_type_lit.members);
+    swc_ecmascript::visit::visit_ts_type_lit(self, ts_type_lit, parent);
+  }
+
+  fn visit_ts_interface_body(
+    &mut self,
+    ts_interface_body: &TsInterfaceBody,
+    parent: &dyn Node,
+  ) {
+    self.check(&ts_interface_body.body);
+    swc_ecmascript::visit::visit_ts_interface_body(
+      self,
+      ts_interface_body,
+      parent,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_unordered_overload_specificity_valid() {
+    assert_lint_ok_n::<NoUnorderedOverloadSpecificity>(vec![
+      "function f(x: string): string;\nfunction f(x: unknown): void;\nfunction f(x: unknown) {}",
+      "function f(x: 'a'): 1;\nfunction f(x: string): 2;\nfunction f(x: string) {}",
+      "function f(x: string): void;\nfunction f(x: string | number): void;\nfunction f(x: any) {}",
+      "function f(x: string): void;\nfunction f(x: number): void;\nfunction f(x: any) {}",
+      // Type references can't be compared.
+      "function f(x: Foo): void;\nfunction f(x: Bar): void;\nfunction f(x: any) {}",
+      "function f(x: object): void;\nfunction f(x: string): void;\nfunction f(x: any) {}",
+      // Different arities.
+      "function f(x: unknown): void;\nfunction f(x: string, y: string): void;\nfunction f(x: any, y?: any) {}",
+      // Optional and rest parameters bail out.
+      "function f(x?: unknown): void;\nfunction f(x?: string): void;\nfunction f(x?: any) {}",
+      "function f(...xs: unknown[]): void;\nfunction f(...xs: string[]): void;\nfunction f(...xs: any[]) {}",
+      // Generics bail out.
+      "function f<T>(x: T): T;\nfunction f(x: string): string;\nfunction f(x: any) {}",
+      "function f(x: unknown): void;\nfunction f<T extends string>(x: T): T;\nfunction f(x: any) {}",
+      // The implementation is not an overload.
+      "function f(x: string): string;\nfunction f(x: unknown) {}",
+      // Not adjacent.
+      "function f(x: unknown): void;\nconst a = 1;\nfunction f(x: string): void;",
+      "interface I {\n  f(x: string): string;\n  f(x: unknown): void;\n}",
+      "class C {\n  f(x: string[]): void;\n  f(x: unknown[]): void;\n  f(x: any) {}\n}",
+    ]);
+  }
+
+  #[test]
+  fn no_unordered_overload_specificity_invalid() {
+    assert_lint_err_on_line::<NoUnorderedOverloadSpecificity>(
+      "function f(x: unknown): void;\nfunction f(x: string): string;\nfunction f(x: unknown) {}",
+      2,
+      0,
+    );
+    // A string literal is narrower than `string`.
+    assert_lint_err_on_line::<NoUnorderedOverloadSpecificity>(
+      "export function f(x: string): 2;\nexport function f(x: 'a'): 1;\nexport function f(x: string) {}",
+      2,
+      0,
+    );
+    // The earlier union contains the later type.
+    assert_lint_err_on_line::<NoUnorderedOverloadSpecificity>(
+      "declare function f(x: string | number, y: boolean): void;\ndeclare function f(x: number, y: true): void;",
+      2,
+      8,
+    );
+    assert_lint_err_on_line::<NoUnorderedOverloadSpecificity>(
+      "declare function f(x: string | number | boolean): void;\ndeclare function f(x: 'a' | 1): void;",
+      2,
+      8,
+    );
+    assert_lint_err_on_line::<NoUnorderedOverloadSpecificity>(
+      "declare function f(x: object): void;\ndeclare function f(x: Foo): void;",
+      2,
+      8,
+    );
+    assert_lint_err_on_line::<NoUnorderedOverloadSpecificity>(
+      "declare function f(x): void;\ndeclare function f(x: string): void;",
+      2,
+      8,
+    );
+    assert_lint_err_on_line::<NoUnorderedOverloadSpecificity>(
+      "interface I {\n  f(x: unknown): void;\n  f(x: string): string;\n}",
+      3,
+      2,
+    );
+    assert_lint_err_on_line::<NoUnorderedOverloadSpecificity>(
+      "type T = {\n  (x: any): void;\n  (x: (string)): string;\n};",
+      3,
+      2,
+    );
+    assert_lint_err_on_line::<NoUnorderedOverloadSpecificity>(
+      "class C {\n  f(x: unknown[]): void;\n  f(x: string[]): void;\n  f(x: any) {}\n}",
+      3,
+      2,
+    );
+    assert_lint_err_on_line::<NoUnorderedOverloadSpecificity>(
+      "class C {\n  constructor(x: unknown);\n  constructor(x: string);\n  constructor(x: any) {}\n}",
+      3,
+      2,
+    );
+    assert_lint_err_on_line_n::<NoUnorderedOverloadSpecificity>(
+      "declare function f(x: unknown): void;\ndeclare function f(x: string): void;\ndeclare function f(x: number): void;",
+      vec![(2, 8), (3, 8)],
+    );
+  }
+
+  #[test]
+  fn no_unordered_overload_specificity_message() {
+    let diagnostics = lint(
+      NoUnorderedOverloadSpecificity::new(),
+      "function f(x: unknown): void;\nfunction f(x: string): string;\nfunction f(x: unknown) {}",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "This overload of 'f' is unreachable, because an earlier overload accepts the same arguments"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("The overload at 1:0 is picked first for every call this one matches; move the more specific overload above it")
+    );
+  }
+}
@@ -20,6 +20,40 @@ impl LintRule for NoUnreachable {
     "no-unreachable"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows statements which can never run, after a `return`, `throw`, `break` or `continue`.
+
+Unreachable code is dead and usually a mistake. Function declarations, which are hoisted, are allowed.
+
+### Valid:
+```typescript
+function foo() {
+  return bar();
+  function bar() {
+    return 1;
+  }
+}
+```
+
+### Invalid:
+```typescript
+function foo() {
+  return;
+  x = 1;
+}
+```
+```typescript
+while (true) {
+  continue;
+  var x = 1;
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -229,8 +263,10 @@ function normalize(type: string): string | undefined {
 
   #[test]
   fn ok_break_labeled() {
-    assert_lint_ok::<NoUnreachable>(
-      "A: {
+    // `return` isn't allowed outside of functions
+    with_syntax_errors(|| {
+      assert_lint_ok::<NoUnreachable>(
+        "A: {
         switch (5) {
           case 1:
             return 'foo';
@@ -240,7 +276,8 @@ function normalize(type: string): string | undefined {
       }
       call();
       ",
-    );
+      );
+    });
 
     assert_lint_ok::<NoUnreachable>(
       "A: {
@@ -461,8 +498,9 @@ function normalize(type: string): string | undefined {
 
   #[test]
   fn deno_ok_1() {
-    assert_lint_ok::<NoUnreachable>(
-      r#"
+    with_syntax_errors(|| {
+      assert_lint_ok::<NoUnreachable>(
+        r#"
       switch (vers) {
         case "HTTP/1.1":
           return [1, 1];
@@ -499,7 +537,8 @@ function normalize(type: string): string | undefined {
       }
 
       throw new Error(`malformed HTTP version ${vers}`);"#,
-    )
+      );
+    });
   }
 
   #[test]
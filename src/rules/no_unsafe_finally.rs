@@ -18,6 +18,42 @@ impl LintRule for NoUnsafeFinally {
     "no-unsafe-finally"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows `return`, `throw`, `break` and `continue` statements in `finally` blocks.
+
+They override the control flow of the `try` and `catch` blocks, so a returned value or a thrown error is silently discarded.
+
+### Valid:
+```typescript
+let foo = function () {
+  try {
+    return 1;
+  } catch (err) {
+    return 2;
+  } finally {
+    console.log("hola!");
+  }
+};
+```
+
+### Invalid:
+```typescript
+let foo = function () {
+  try {
+    return 1;
+  } catch (err) {
+    return 2;
+  } finally {
+    return 3;
+  }
+};
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = NoUnsafeFinallyVisitor::new(context);
     visitor.visit_module(module, module);
@@ -128,8 +164,10 @@ let foo = function(a) {
 
   #[test]
   fn it_fails_for_a_break_in_a_finally_block() {
-    assert_lint_err_on_line::<NoUnsafeFinally>(
-      r#"
+    // `break` isn't allowed outside of a loop or `switch`
+    with_syntax_errors(|| {
+      assert_lint_err_on_line::<NoUnsafeFinally>(
+        r#"
 let foo = function() {
   try {
     return 1;
@@ -140,15 +178,18 @@ let foo = function() {
   }
 };
      "#,
-      7,
-      12,
-    );
+        7,
+        12,
+      );
+    });
   }
 
   #[test]
   fn it_fails_for_a_continue_in_a_finally_block() {
-    assert_lint_err_on_line::<NoUnsafeFinally>(
-      r#"
+    // `continue` isn't allowed outside of a loop
+    with_syntax_errors(|| {
+      assert_lint_err_on_line::<NoUnsafeFinally>(
+        r#"
 let foo = function() {
   try {
     return 1;
@@ -159,9 +200,10 @@ let foo = function() {
   }
 };
      "#,
-      7,
-      12,
-    );
+        7,
+        12,
+      );
+    });
   }
 
   #[test]
@@ -0,0 +1,301 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::constant_string;
+use crate::swc_util::member_path;
+use crate::swc_util::Key;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  AssignExpr, AssignOp, CallExpr, Expr, ExprOrSpread, ExprOrSuper, Module, Pat,
+  PatOrExpr,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+/// Properties which parse the assigned value as HTML.
+const HTML_PROPERTIES: &[&str] = &["innerHTML", "outerHTML", "srcdoc"];
+
+pub struct NoUnsafeInnerHtmlOptions {
+  /// Allows string literals and template literals without substitutions,
+  /// which can't contain user input.
+  pub allow_constant: bool,
+  /// Functions which sanitize HTML, eg. `DOMPurify.sanitize`. Values
+  /// wrapped in a call to one of them are allowed.
+  pub sanitizers: Vec<String>,
+}
+
+impl Default for NoUnsafeInnerHtmlOptions {
+  fn default() -> Self {
+    Self {
+      allow_constant: true,
+      sanitizers: vec!["DOMPurify.sanitize".to_string()],
+    }
+  }
+}
+
+/// Reports values written to DOM APIs which parse them as HTML, like
+/// `el.innerHTML = value`, since they can lead to XSS if the value contains
+/// user input.
+pub struct NoUnsafeInnerHtml {
+  options: NoUnsafeInnerHtmlOptions,
+}
+
+impl NoUnsafeInnerHtml {
+  pub fn with_options(options: NoUnsafeInnerHtmlOptions) -> Box<Self> {
+    Box::new(NoUnsafeInnerHtml { options })
+  }
+}
+
+impl LintRule for NoUnsafeInnerHtml {
+  fn new() -> Box<Self> {
+    Self::with_options(NoUnsafeInnerHtmlOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-unsafe-inner-html"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows writing values to DOM APIs which parse them as HTML, like `innerHTML`, `outerHTML`, `insertAdjacentHTML()` and `document.write()`.
+
+They can lead to cross-site scripting if the value contains user input. Build the elements with DOM APIs and `textContent`, or sanitize the value.
+
+### Options
+
+- `NoUnsafeInnerHtmlOptions::allow_constant`: allows string literals and template literals without substitutions, which can't contain user input, `true` by default.
+- `NoUnsafeInnerHtmlOptions::sanitizers`: functions which sanitize HTML; values wrapped in a call to one of them are allowed. `["DOMPurify.sanitize"]` by default.
+
+### Valid:
+```typescript
+el.innerHTML = "<b>hello</b>";
+el.innerHTML = DOMPurify.sanitize(input);
+el.textContent = input;
+```
+
+### Invalid:
+```typescript
+el.innerHTML = input;
+```
+```typescript
+el.outerHTML = `<b>${name}</b>`;
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoUnsafeInnerHtmlVisitor {
+      context,
+      options: &self.options,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoUnsafeInnerHtmlVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a NoUnsafeInnerHtmlOptions,
+}
+
+impl<'a> NoUnsafeInnerHtmlVisitor<'a> {
+  fn is_sanitized(&self, expr: &Expr) -> bool {
+    match expr {
+      Expr::Paren(paren) => self.is_sanitized(&paren.expr),
+      Expr::Call(CallExpr {
+        callee: ExprOrSuper::Expr(callee),
+        ..
+      }) => {
+        let (obj, mut path) = member_path(callee);
+        let name = match obj {
+          Expr::Ident(ident) => ident.sym.to_string(),
+          _ => return false,
+        };
+        path.insert(0, name);
+        self.options.sanitizers.contains(&path.join("."))
+      }
+      _ => false,
+    }
+  }
+
+  fn is_safe(&self, expr: &Expr) -> bool {
+    (self.options.allow_constant && constant_string(expr).is_some())
+      || self.is_sanitized(expr)
+  }
+
+  fn report(&self, span: Span, sink: &str) {
+    self.context.add_diagnostic_with_hint(
+      span,
+      "no-unsafe-inner-html",
+      &format!("{} parses its value as HTML, which can lead to XSS", sink),
+      "Sanitize the value, or build the elements with DOM APIs and `textContent`",
+    );
+  }
+
+  /// Returns the name of the sink called, if any, and the argument which is
+  /// parsed as HTML.
+  fn sink_call<'c>(
+    &self,
+    call_expr: &'c CallExpr,
+  ) -> Option<(&'static str, Vec<&'c ExprOrSpread>)> {
+    let callee = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => &**callee,
+      ExprOrSuper::Super(_) => return None,
+    };
+    let (obj, path) = member_path(callee);
+    let is_document = match path.as_slice() {
+      [_] => {
+        matches!(obj, Expr::Ident(ident) if self.context.is_global(ident, "document"))
+      }
+      [.., document, _] => document == "document",
+      _ => false,
+    };
+    match path.last().map(String::as_str) {
+      Some("write") if is_document => {
+        Some(("`document.write()`", call_expr.args.iter().collect()))
+      }
+      Some("writeln") if is_document => {
+        Some(("`document.writeln()`", call_expr.args.iter().collect()))
+      }
+      Some("insertAdjacentHTML") => Some((
+        "`insertAdjacentHTML()`",
+        call_expr.args.get(1).into_iter().collect(),
+      )),
+      Some("createContextualFragment") => Some((
+        "`Range.createContextualFragment()`",
+        call_expr.args.first().into_iter().collect(),
+      )),
+      _ => None,
+    }
+  }
+}
+
+impl<'a> Visit for NoUnsafeInnerHtmlVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, parent: &dyn Node) {
+    let target = match &assign_expr.left {
+      PatOrExpr::Expr(expr) => Some(&**expr),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => Some(&**expr),
+        _ => None,
+      },
+    };
+    let property = match target {
+      Some(Expr::Member(member)) => member.get_key(),
+      _ => None,
+    };
+    if let Some(property) = property {
+      if let Some(sink) = HTML_PROPERTIES
+        .iter()
+        .find(|name| **name == property.as_str())
+      {
+        // `+=` serializes the current markup and parses it again, together
+        // with the appended value.
+        if assign_expr.op != AssignOp::Assign
+          || !self.is_safe(&assign_expr.right)
+        {
+          self.report(assign_expr.span, &format!("Assigning to `{}`", sink));
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_assign_expr(self, assign_expr, parent);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let Some((sink, args)) = self.sink_call(call_expr) {
+      let unsafe_arg = args
+        .iter()
+        .any(|arg| arg.spread.is_some() || !self.is_safe(&arg.expr));
+      if unsafe_arg {
+        self.report(call_expr.span, &format!("Calling {}", sink));
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_unsafe_inner_html_valid() {
+    assert_lint_ok_n::<NoUnsafeInnerHtml>(vec![
+      r#"el.innerHTML = "<b>hello</b>";"#,
+      r#"el.innerHTML = `<b>hello</b>`;"#,
+      r#"el["outerHTML"] = "";"#,
+      "el.textContent = input;",
+      "el.innerHTML = DOMPurify.sanitize(input);",
+      r#"document.write("<p>loading</p>");"#,
+      r#"el.insertAdjacentHTML("beforeend", "<hr>");"#,
+      r#"el.insertAdjacentHTML(position, "<hr>");"#,
+      "range.createContextualFragment(DOMPurify.sanitize(html));",
+      "writer.write(data);",
+      "const document = new Doc(); document.write(data);",
+      "const html = el.innerHTML;",
+    ]);
+  }
+
+  #[test]
+  fn no_unsafe_inner_html_invalid() {
+    assert_lint_err::<NoUnsafeInnerHtml>("el.innerHTML = input;", 0);
+    assert_lint_err::<NoUnsafeInnerHtml>("el.outerHTML = `<b>${name}</b>`;", 0);
+    assert_lint_err::<NoUnsafeInnerHtml>(r#"el["innerHTML"] = input;"#, 0);
+    assert_lint_err::<NoUnsafeInnerHtml>("iframe.srcdoc = page;", 0);
+    assert_lint_err::<NoUnsafeInnerHtml>(r#"el.innerHTML += "<br>";"#, 0);
+    assert_lint_err::<NoUnsafeInnerHtml>(
+      "el.innerHTML = sanitizeHtml(input);",
+      0,
+    );
+    assert_lint_err::<NoUnsafeInnerHtml>("document.write(html);", 0);
+    assert_lint_err::<NoUnsafeInnerHtml>(
+      r#"window.document.writeln("<p>", name);"#,
+      0,
+    );
+    assert_lint_err::<NoUnsafeInnerHtml>(
+      r#"el.insertAdjacentHTML("afterbegin", html);"#,
+      0,
+    );
+    assert_lint_err::<NoUnsafeInnerHtml>(
+      "document.createRange().createContextualFragment(html);",
+      0,
+    );
+    assert_lint_err_on_line::<NoUnsafeInnerHtml>(
+      "function render(el, html) {\n  el.innerHTML = html;\n}",
+      2,
+      2,
+    );
+
+    let diagnostics = lint(NoUnsafeInnerHtml::new(), "el.innerHTML = input;");
+    assert_eq!(
+      diagnostics[0].message,
+      "Assigning to `innerHTML` parses its value as HTML, which can lead to XSS"
+    );
+    let diagnostics = lint(NoUnsafeInnerHtml::new(), "document.write(html);");
+    assert_eq!(
+      diagnostics[0].message,
+      "Calling `document.write()` parses its value as HTML, which can lead to XSS"
+    );
+  }
+
+  #[test]
+  fn no_unsafe_inner_html_options() {
+    let rule = || {
+      NoUnsafeInnerHtml::with_options(NoUnsafeInnerHtmlOptions {
+        allow_constant: false,
+        sanitizers: vec!["sanitizeHtml".to_string()],
+      })
+    };
+    assert_lint_ok_with(rule(), "el.innerHTML = sanitizeHtml(input);");
+    assert_lint_err_on_line_n_with(
+      rule(),
+      r#"el.innerHTML = "<b>hello</b>"; el.innerHTML = DOMPurify.sanitize(x);"#,
+      vec![(1, 0), (1, 31)],
+    );
+  }
+}
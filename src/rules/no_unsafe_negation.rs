@@ -22,6 +22,30 @@ impl LintRule for NoUnsafeNegation {
     "no-unsafe-negation"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows negating the left operand of `in` and `instanceof`.
+
+`!key in object` negates `key` rather than the result of `in`, which is almost never intended.
+
+### Valid:
+```typescript
+if (!(key in object)) {}
+if (!(foo instanceof Date)) {}
+```
+
+### Invalid:
+```typescript
+if (!key in object) {}
+```
+```typescript
+if (!foo instanceof Date) {}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
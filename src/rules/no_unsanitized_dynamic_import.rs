@@ -0,0 +1,416 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::scopes::BindingKind;
+use crate::swc_util::{constant_string, member_path};
+use regex::Regex;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  BinaryOp, CallExpr, Expr, ExprOrSpread, ExprOrSuper, Module, NewExpr,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoUnsanitizedDynamicImportMode {
+  /// Every specifier which isn't a constant string is reported.
+  LiteralOnly,
+  /// Specifiers built from parameters which look like user input are
+  /// reported.
+  NoUserInput,
+}
+
+pub struct NoUnsanitizedDynamicImportOptions {
+  pub mode: NoUnsanitizedDynamicImportMode,
+  /// Matches the names of parameters, or of their properties, which hold
+  /// user input, in the "no-user-input" mode.
+  pub user_input_pattern: Regex,
+  /// Checks the specifiers of `new Worker()` and `importScripts()` too.
+  pub include_workers: bool,
+}
+
+impl Default for NoUnsanitizedDynamicImportOptions {
+  fn default() -> Self {
+    Self {
+      mode: NoUnsanitizedDynamicImportMode::NoUserInput,
+      user_input_pattern: Regex::new("(?i)req|params|query|input").unwrap(),
+      include_workers: false,
+    }
+  }
+}
+
+/// Reports dynamic `import()` calls whose specifier isn't a constant
+/// string, which defeats dependency auditing and, with user input in the
+/// specifier, allows loading arbitrary modules.
+///
+/// In the default "no-user-input" mode, only specifiers built from
+/// parameters whose name matches `user_input_pattern`, like `req` or
+/// `query`, are reported. In the "literal-only" mode, every specifier which
+/// isn't a string literal, or a concatenation of those, is reported.
+pub struct NoUnsanitizedDynamicImport {
+  options: NoUnsanitizedDynamicImportOptions,
+}
+
+impl NoUnsanitizedDynamicImport {
+  pub fn with_options(options: NoUnsanitizedDynamicImportOptions) -> Box<Self> {
+    Box::new(NoUnsanitizedDynamicImport { options })
+  }
+}
+
+impl LintRule for NoUnsanitizedDynamicImport {
+  fn new() -> Box<Self> {
+    Self::with_options(NoUnsanitizedDynamicImportOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-unsanitized-dynamic-import"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows dynamic `import()` calls whose specifier isn't a constant string.
+
+Non-constant specifiers defeat dependency auditing and, with user input in the specifier, allow loading arbitrary modules.
+
+### Options
+
+- `NoUnsanitizedDynamicImportOptions::mode`:
+  - `NoUnsanitizedDynamicImportMode::NoUserInput` (default): only specifiers built from parameters matching `user_input_pattern` are reported.
+  - `NoUnsanitizedDynamicImportMode::LiteralOnly`: every specifier which isn't a string literal, or a concatenation of those, is reported.
+- `NoUnsanitizedDynamicImportOptions::user_input_pattern`: matches the names of parameters, or of their properties, which hold user input, `(?i)req|params|query|input` by default.
+- `NoUnsanitizedDynamicImportOptions::include_workers`: also checks `new Worker()` and `importScripts()`, `false` by default.
+
+### Valid:
+```typescript
+import("./mod.ts");
+```
+```typescript
+function load(locale) {
+  switch (locale) {
+    case "en":
+      return import("./locales/en.ts");
+    case "de":
+      return import("./locales/de.ts");
+  }
+}
+```
+
+### Invalid:
+```typescript
+function load(input) {
+  return import(`./locales/${input}.ts`);
+}
+```
+```typescript
+const f = (ctx) => import(ctx.query.plugin);
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoUnsanitizedDynamicImportVisitor {
+      context,
+      options: &self.options,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoUnsanitizedDynamicImportVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a NoUnsanitizedDynamicImportOptions,
+}
+
+/// Checks if the specifier is `new URL(constant, import.meta.url)`, or its
+/// `href`, which resolves a constant path relative to the module.
+fn is_module_relative_url(expr: &Expr) -> bool {
+  let new_expr = match member_path(expr) {
+    (Expr::New(new_expr), path) if path.is_empty() || path == ["href"] => {
+      new_expr
+    }
+    _ => return false,
+  };
+  match (&*new_expr.callee, new_expr.args.as_deref()) {
+    (Expr::Ident(callee), Some([path, base])) if callee.sym == *"URL" => {
+      constant_string(&path.expr).is_some()
+        && matches!(
+          member_path(&base.expr),
+          (Expr::MetaProp(_), path) if path == ["url"]
+        )
+    }
+    _ => false,
+  }
+}
+
+/// Returns the parts of a specifier which aren't constant: the
+/// substitutions of template literals and the operands of concatenations.
+fn dynamic_parts(expr: &Expr) -> Vec<&Expr> {
+  match expr {
+    Expr::Paren(paren) => dynamic_parts(&paren.expr),
+    Expr::Tpl(tpl) => tpl.exprs.iter().flat_map(|e| dynamic_parts(e)).collect(),
+    Expr::Bin(bin) if bin.op == BinaryOp::Add => {
+      let mut parts = dynamic_parts(&bin.left);
+      parts.extend(dynamic_parts(&bin.right));
+      parts
+    }
+    _ if constant_string(expr).is_some() => vec![],
+    _ => vec![expr],
+  }
+}
+
+impl<'a> NoUnsanitizedDynamicImportVisitor<'a> {
+  /// Checks if a part of a specifier comes from a parameter which holds
+  /// user input, eg. `req.params.lang` or `input`.
+  fn is_user_input(&self, part: &Expr) -> bool {
+    let (root, path) = match member_path(part) {
+      (Expr::Ident(root), path) => (root, path),
+      _ => return false,
+    };
+    let is_param = matches!(
+      self
+        .context
+        .scope_manager
+        .binding(&root.to_id())
+        .map(|var| var.kind()),
+      Some(BindingKind::Param)
+    );
+    is_param
+      && (self.options.user_input_pattern.is_match(&root.sym)
+        || path
+          .iter()
+          .any(|prop| self.options.user_input_pattern.is_match(prop)))
+  }
+
+  fn check(&self, specifier: &ExprOrSpread, callee: &str) {
+    let expr = &*specifier.expr;
+    if specifier.spread.is_none()
+      && (constant_string(expr).is_some() || is_module_relative_url(expr))
+    {
+      return;
+    }
+
+    match self.options.mode {
+      NoUnsanitizedDynamicImportMode::LiteralOnly => {
+        self.context.add_diagnostic_with_hint(
+          expr.span(),
+          "no-unsanitized-dynamic-import",
+          &format!("The specifier of `{}` must be a string literal", callee),
+          "Non-literal specifiers can't be audited; choose between literal specifiers instead, eg. with a `switch`",
+        );
+      }
+      NoUnsanitizedDynamicImportMode::NoUserInput => {
+        let part = match dynamic_parts(expr)
+          .into_iter()
+          .find(|part| self.is_user_input(part))
+        {
+          Some(part) => part,
+          None => return,
+        };
+        let input = self.context.span_text(part.span());
+        self.context.add_diagnostic_with_hint(
+          expr.span(),
+          "no-unsanitized-dynamic-import",
+          &format!(
+            "The specifier of `{}` is built from the user input `{}`",
+            callee, input
+          ),
+          &format!(
+            "Check `{}` against a list of allowed values first, as it could load any module",
+            input
+          ),
+        );
+      }
+    }
+  }
+}
+
+impl<'a> Visit for NoUnsanitizedDynamicImportVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Expr::Ident(ident) = &**callee {
+        if ident.sym == *"import" {
+          if let Some(specifier) = call_expr.args.first() {
+            self.check(specifier, "import()");
+          }
+        } else if self.options.include_workers
+          && self.context.is_global(ident, "importScripts")
+        {
+          for specifier in &call_expr.args {
+            self.check(specifier, "importScripts()");
+          }
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+    if self.options.include_workers {
+      if let Expr::Ident(ident) = &*new_expr.callee {
+        if self.context.is_global(ident, "Worker") {
+          if let Some(specifier) =
+            new_expr.args.as_ref().and_then(|args| args.first())
+          {
+            self.check(specifier, "new Worker()");
+          }
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_new_expr(self, new_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn literal_only() -> Box<NoUnsanitizedDynamicImport> {
+    NoUnsanitizedDynamicImport::with_options(
+      NoUnsanitizedDynamicImportOptions {
+        mode: NoUnsanitizedDynamicImportMode::LiteralOnly,
+        ..Default::default()
+      },
+    )
+  }
+
+  fn include_workers() -> Box<NoUnsanitizedDynamicImport> {
+    NoUnsanitizedDynamicImport::with_options(
+      NoUnsanitizedDynamicImportOptions {
+        include_workers: true,
+        ..Default::default()
+      },
+    )
+  }
+
+  #[test]
+  fn no_unsanitized_dynamic_import_valid() {
+    assert_lint_ok_n::<NoUnsanitizedDynamicImport>(vec![
+      r#"import("./mod.ts");"#,
+      "const lang = getLang();\nimport(`./locales/${lang}.ts`);",
+      "function load(lang) { return import(`./locales/${lang}.ts`); }",
+      "function load(path) { return import(path); }",
+      // Not parameters.
+      "const input = getInput();\nimport(`./${input}.ts`);",
+      "import(`./${query}.ts`);",
+      // Not checked without the `include_workers` option.
+      "function f(req) { new Worker(req.url); }",
+    ]);
+  }
+
+  #[test]
+  fn no_unsanitized_dynamic_import_invalid() {
+    assert_lint_err_on_line::<NoUnsanitizedDynamicImport>(
+      "function load(input) {\n  return import(`./locales/${input}.ts`);\n}",
+      2,
+      16,
+    );
+    assert_lint_err_on_line::<NoUnsanitizedDynamicImport>(
+      "app.get('/', (req) => {\n  import('./pages/' + req.params.page + '.ts');\n});",
+      2,
+      9,
+    );
+    assert_lint_err::<NoUnsanitizedDynamicImport>(
+      "const f = (ctx) => import(ctx.query.plugin);",
+      26,
+    );
+    assert_lint_err::<NoUnsanitizedDynamicImport>(
+      "function f({ params }) { import(`./${params.id}.ts`); }",
+      32,
+    );
+
+    let diagnostics = lint(
+      NoUnsanitizedDynamicImport::new(),
+      "function load(userInput) { import(`./${userInput}.ts`); }",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "The specifier of `import()` is built from the user input `userInput`"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Check `userInput` against a list of allowed values first, as it could load any module")
+    );
+
+    let rule = NoUnsanitizedDynamicImport::with_options(
+      NoUnsanitizedDynamicImportOptions {
+        user_input_pattern: Regex::new("^lang$").unwrap(),
+        ..Default::default()
+      },
+    );
+    assert_lint_err_on_line_n_with(
+      rule,
+      "function load(lang, input) { import(`./locales/${lang}.ts`); import(input); }",
+      vec![(1, 36)],
+    );
+  }
+
+  #[test]
+  fn no_unsanitized_dynamic_import_literal_only() {
+    assert_lint_ok_with(literal_only(), r#"import("./mod.ts");"#);
+    assert_lint_ok_with(literal_only(), "import(`./mod.ts`);");
+    // Concatenations of literals are constant.
+    assert_lint_ok_with(
+      literal_only(),
+      r#"import("./locales/" + "en" + ".ts");"#,
+    );
+    assert_lint_err_on_line_n_with(
+      literal_only(),
+      "const lang = getLang();\nimport(`./locales/${lang}.ts`);",
+      vec![(2, 7)],
+    );
+    assert_lint_err_on_line_n_with(
+      literal_only(),
+      "import(path);\nimport('./' + name);",
+      vec![(1, 7), (2, 7)],
+    );
+
+    let diagnostics = lint(literal_only(), "import(path);");
+    assert_eq!(
+      diagnostics[0].message,
+      "The specifier of `import()` must be a string literal"
+    );
+  }
+
+  #[test]
+  fn no_unsanitized_dynamic_import_workers() {
+    assert_lint_ok_with(
+      include_workers(),
+      "new Worker(new URL('./worker.ts', import.meta.url).href, { type: 'module' });",
+    );
+    assert_lint_ok_with(
+      include_workers(),
+      "function f(req) { new Worker(new URL('./worker.ts', import.meta.url)); }",
+    );
+    assert_lint_err_on_line_n_with(
+      include_workers(),
+      "function f(req) {\n  new Worker(req.query.worker);\n  importScripts('./a.js', `./${req.query.b}.js`);\n}",
+      vec![(2, 13), (3, 26)],
+    );
+
+    let rule = || {
+      NoUnsanitizedDynamicImport::with_options(
+        NoUnsanitizedDynamicImportOptions {
+          mode: NoUnsanitizedDynamicImportMode::LiteralOnly,
+          include_workers: true,
+          ..Default::default()
+        },
+      )
+    };
+    assert_lint_ok_with(rule(), "new Worker(url);\nclass Worker {}");
+    let diagnostics = lint(rule(), "new Worker(url);");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "The specifier of `new Worker()` must be a string literal"
+    );
+  }
+}
@@ -0,0 +1,338 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  ArrowExpr, BinaryOp, BlockStmtOrExpr, Expr, ExprOrSuper, ExprStmt, Function,
+  Lit, Module, ModuleItem, Stmt, UnaryOp,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Default)]
+pub struct NoUnusedExpressionsOptions {
+  /// Allows `a && b()` and `a || b()` when the right side has an effect.
+  pub allow_short_circuit: bool,
+  /// Allows `a ? b() : c()` when both branches have an effect.
+  pub allow_ternary: bool,
+  /// Allows tagged templates like `` tag`text` ``.
+  pub allow_tagged_templates: bool,
+}
+
+pub struct NoUnusedExpressions {
+  options: NoUnusedExpressionsOptions,
+}
+
+impl NoUnusedExpressions {
+  pub fn with_options(options: NoUnusedExpressionsOptions) -> Box<Self> {
+    Box::new(NoUnusedExpressions { options })
+  }
+}
+
+impl LintRule for NoUnusedExpressions {
+  fn new() -> Box<Self> {
+    Self::with_options(NoUnusedExpressionsOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "no-unused-expressions"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows expression statements which have no effect.
+
+An expression whose value is discarded, like `a === b;`, is usually a typo of an assignment or a missing call.
+
+### Options
+
+- `NoUnusedExpressionsOptions::allow_short_circuit`: allows `a && b()` and `a || b()` when the right side has an effect, `false` by default.
+- `NoUnusedExpressionsOptions::allow_ternary`: allows `a ? b() : c()` when both branches have an effect, `false` by default.
+- `NoUnusedExpressionsOptions::allow_tagged_templates`: allows tagged templates like `` tag`text` ``, `false` by default.
+
+### Valid:
+```typescript
+foo();
+a = b;
+```
+
+### Invalid:
+```typescript
+a === b;
+```
+```typescript
+obj.prop;
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoUnusedExpressionsVisitor::new(context, self.options);
+    visitor.visit_module(module, module);
+  }
+}
+
+/// Returns true if evaluating `expr` as a statement does something useful,
+/// ie. calls, constructs, assigns, awaits, yields or deletes.
+fn has_side_effect(expr: &Expr, options: &NoUnusedExpressionsOptions) -> bool {
+  match expr {
+    Expr::Call(_)
+    | Expr::New(_)
+    | Expr::Assign(_)
+    | Expr::Update(_)
+    | Expr::Await(_)
+    | Expr::Yield(_) => true,
+    Expr::Unary(unary_expr) => unary_expr.op == UnaryOp::Delete,
+    // An optional call like `foo?.()` still calls.
+    Expr::OptChain(opt_chain) => has_side_effect(&opt_chain.expr, options),
+    Expr::Paren(paren_expr) => has_side_effect(&paren_expr.expr, options),
+    Expr::TsNonNull(non_null) => has_side_effect(&non_null.expr, options),
+    Expr::TsAs(as_expr) => has_side_effect(&as_expr.expr, options),
+    Expr::TsTypeAssertion(assertion) => {
+      has_side_effect(&assertion.expr, options)
+    }
+    Expr::Bin(bin_expr)
+      if matches!(
+        bin_expr.op,
+        BinaryOp::LogicalAnd
+          | BinaryOp::LogicalOr
+          | BinaryOp::NullishCoalescing
+      ) =>
+    {
+      options.allow_short_circuit && has_side_effect(&bin_expr.right, options)
+    }
+    Expr::Cond(cond_expr) => {
+      options.allow_ternary
+        && has_side_effect(&cond_expr.cons, options)
+        && has_side_effect(&cond_expr.alt, options)
+    }
+    Expr::TaggedTpl(_) => options.allow_tagged_templates,
+    _ => false,
+  }
+}
+
+/// `import.meta` and properties on it, eg. `import.meta.main;`.
+fn is_import_meta(expr: &Expr) -> bool {
+  match expr {
+    Expr::MetaProp(meta_prop) => meta_prop.meta.sym == *"import",
+    Expr::Member(member_expr) => match &member_expr.obj {
+      ExprOrSuper::Expr(obj) => is_import_meta(obj),
+      ExprOrSuper::Super(_) => false,
+    },
+    _ => false,
+  }
+}
+
+struct NoUnusedExpressionsVisitor {
+  context: Arc<Context>,
+  options: NoUnusedExpressionsOptions,
+  /// Spans of string statements forming directive prologues,
+  /// eg. `"use strict";`.
+  directives: HashSet<Span>,
+}
+
+impl NoUnusedExpressionsVisitor {
+  fn new(context: Arc<Context>, options: NoUnusedExpressionsOptions) -> Self {
+    Self {
+      context,
+      options,
+      directives: HashSet::new(),
+    }
+  }
+
+  fn collect_directives<'a>(&mut self, stmts: impl Iterator<Item = &'a Stmt>) {
+    for stmt in stmts {
+      match stmt {
+        Stmt::Expr(ExprStmt { span, expr })
+          if matches!(&**expr, Expr::Lit(Lit::Str(_))) =>
+        {
+          self.directives.insert(*span);
+        }
+        _ => break,
+      }
+    }
+  }
+}
+
+impl Visit for NoUnusedExpressionsVisitor {
+  noop_visit_type!();
+
+  fn visit_module(&mut self, module: &Module, parent: &dyn Node) {
+    let stmts = module
+      .body
+      .iter()
+      .take_while(|item| matches!(item, ModuleItem::Stmt(_)))
+      .filter_map(|item| match item {
+        ModuleItem::Stmt(stmt) => Some(stmt),
+        ModuleItem::ModuleDecl(_) => None,
+      });
+    self.collect_directives(stmts);
+    swc_ecmascript::visit::visit_module(self, module, parent);
+  }
+
+  fn visit_function(&mut self, function: &Function, parent: &dyn Node) {
+    if let Some(body) = &function.body {
+      self.collect_directives(body.stmts.iter());
+    }
+    swc_ecmascript::visit::visit_function(self, function, parent);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, parent: &dyn Node) {
+    if let BlockStmtOrExpr::BlockStmt(body) = &arrow_expr.body {
+      self.collect_directives(body.stmts.iter());
+    }
+    swc_ecmascript::visit::visit_arrow_expr(self, arrow_expr, parent);
+  }
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt, parent: &dyn Node) {
+    if !self.directives.contains(&expr_stmt.span)
+      && !is_import_meta(&expr_stmt.expr)
+      && !has_side_effect(&expr_stmt.expr, &self.options)
+    {
+      self.context.add_diagnostic(
+        expr_stmt.span,
+        "no-unused-expressions",
+        "Expected an assignment or function call and instead saw an expression",
+      );
+    }
+    swc_ecmascript::visit::visit_expr_stmt(self, expr_stmt, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::swc_util::{get_default_ts_config, AstParser};
+  use crate::test_util::*;
+
+  fn with_expr<F: FnOnce(&Expr)>(source: &str, f: F) {
+    let ast_parser = AstParser::new();
+    let (module, _) =
+      ast_parser.parse_module("file_name.ts", get_default_ts_config(), source);
+    match &module.unwrap().body[0] {
+      ModuleItem::Stmt(Stmt::Expr(expr_stmt)) => f(&expr_stmt.expr),
+      _ => panic!("expected an expression statement"),
+    }
+  }
+
+  fn assert_side_effect(
+    source: &str,
+    options: NoUnusedExpressionsOptions,
+    expected: bool,
+  ) {
+    with_expr(source, |expr| {
+      assert_eq!(has_side_effect(expr, &options), expected, "{}", source)
+    });
+  }
+
+  #[test]
+  fn has_side_effect_default() {
+    let options = NoUnusedExpressionsOptions::default();
+    for source in &[
+      "foo()",
+      "foo?.()",
+      "a?.b.c()",
+      "new Foo()",
+      "a = b",
+      "a += 1",
+      "i++",
+      "--i",
+      "delete a.b",
+      "(foo())",
+      "foo()!",
+      "foo() as Bar",
+      "import('./a.ts')",
+    ] {
+      assert_side_effect(source, options, true);
+    }
+    for source in &[
+      "a",
+      "a === b",
+      "obj.prop",
+      "a?.b",
+      "x && y()",
+      "x ? y() : z()",
+      "tag`text`",
+      "void foo",
+      "a, b()",
+      "[foo()]",
+      "() => foo()",
+    ] {
+      assert_side_effect(source, options, false);
+    }
+  }
+
+  #[test]
+  fn has_side_effect_options() {
+    let options = NoUnusedExpressionsOptions {
+      allow_short_circuit: true,
+      allow_ternary: true,
+      allow_tagged_templates: true,
+    };
+    assert_side_effect("x && y()", options, true);
+    assert_side_effect("x || (y = 1)", options, true);
+    assert_side_effect("x ?? y()", options, true);
+    assert_side_effect("x && y", options, false);
+    assert_side_effect("x ? y() : z()", options, true);
+    assert_side_effect("x ? y() : z", options, false);
+    assert_side_effect("tag`text`", options, true);
+  }
+
+  #[test]
+  fn no_unused_expressions_valid() {
+    assert_lint_ok_n::<NoUnusedExpressions>(vec![
+      "foo();",
+      "foo?.();",
+      "a = b;",
+      "delete a.b;",
+      "async function f() { await foo; }",
+      "function* f() { yield 1; }",
+      r#""use strict"; foo();"#,
+      r#"function f() { "use strict"; return 1; }"#,
+      r#"const f = () => { "use strict"; };"#,
+      "import.meta;",
+      "import.meta.main;",
+      "class A { x = a === b; y = this.z; }",
+    ]);
+  }
+
+  #[test]
+  fn no_unused_expressions_invalid() {
+    assert_lint_err::<NoUnusedExpressions>("a === b;", 0);
+    assert_lint_err::<NoUnusedExpressions>("obj.prop;", 0);
+    assert_lint_err::<NoUnusedExpressions>("x && y;", 0);
+    assert_lint_err::<NoUnusedExpressions>("x && y();", 0);
+    assert_lint_err::<NoUnusedExpressions>("foo?.bar;", 0);
+    assert_lint_err::<NoUnusedExpressions>("tag`text`;", 0);
+    assert_lint_err::<NoUnusedExpressions>(r#"foo(); "use strict";"#, 7);
+    assert_lint_err::<NoUnusedExpressions>(
+      r#"function f() { if (a) { "use strict"; } }"#,
+      24,
+    );
+    assert_lint_err_on_line::<NoUnusedExpressions>(
+      "function f() {\n  a + b;\n}",
+      2,
+      2,
+    );
+  }
+
+  #[test]
+  fn no_unused_expressions_options() {
+    let rule = || {
+      NoUnusedExpressions::with_options(NoUnusedExpressionsOptions {
+        allow_short_circuit: true,
+        allow_ternary: true,
+        allow_tagged_templates: true,
+      })
+    };
+    assert_lint_ok_with(rule(), "x && y();");
+    assert_lint_ok_with(rule(), "x ? y() : z();");
+    assert_lint_ok_with(rule(), "tag`text`;");
+    assert_lint_err_on_line_n_with(rule(), "x ? y() : z;", vec![(1, 0)]);
+  }
+}
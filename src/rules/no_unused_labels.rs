@@ -22,6 +22,32 @@ impl LintRule for NoUnusedLabels {
     "no-unused-labels"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows labels which are never used by a `break` or `continue` statement.
+
+An unused label is dead code, and usually a leftover of a refactoring.
+
+### Valid:
+```typescript
+LABEL: for (let i = 0; i < 5; i++) {
+  a();
+  break LABEL;
+}
+```
+
+### Invalid:
+```typescript
+LABEL: for (let i = 0; i < 5; i++) {
+  a();
+  b();
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
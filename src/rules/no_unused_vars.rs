@@ -1,6 +1,7 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use regex::Regex;
 use swc_ecmascript::utils::find_ids;
 use swc_ecmascript::utils::ident::IdentLike;
 use swc_ecmascript::utils::Id;
@@ -22,17 +23,71 @@ use swc_ecmascript::{
 use std::collections::HashSet;
 use std::sync::Arc;
 
-pub struct NoUnusedVars;
+pub struct NoUnusedVarsOptions {
+  /// Parameters whose name matches the pattern are not reported.
+  pub args_ignore_pattern: Regex,
+}
+
+impl Default for NoUnusedVarsOptions {
+  fn default() -> Self {
+    Self {
+      args_ignore_pattern: Regex::new("^_").unwrap(),
+    }
+  }
+}
+
+pub struct NoUnusedVars {
+  options: NoUnusedVarsOptions,
+}
+
+impl NoUnusedVars {
+  pub fn with_options(options: NoUnusedVarsOptions) -> Box<Self> {
+    Box::new(NoUnusedVars { options })
+  }
+}
 
 impl LintRule for NoUnusedVars {
   fn new() -> Box<Self> {
-    Box::new(NoUnusedVars)
+    Self::with_options(NoUnusedVarsOptions::default())
   }
 
   fn code(&self) -> &'static str {
     "no-unused-vars"
   }
 
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows variables, functions and parameters which are declared but never used.
+
+Unused declarations are dead code, and often a sign of an incomplete refactoring or a typo in the name of a reference. Names starting with an underscore are allowed. Exported bindings, and in TypeScript bindings only referenced in types, count as used.
+
+### Options
+
+`NoUnusedVarsOptions::args_ignore_pattern`: parameters whose name matches the regular expression are not reported, `^_` by default.
+
+### Valid:
+```typescript
+var a = 1;
+console.log(a);
+```
+```typescript
+export function foo(_bar: number) {}
+```
+
+### Invalid:
+```typescript
+var a = 0;
+```
+```typescript
+function foo() {
+  return foo();
+}
+```"#
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut collector = Collector {
       used_vars: Default::default(),
@@ -45,6 +100,7 @@ impl LintRule for NoUnusedVars {
       context,
       collector.used_vars,
       collector.used_types,
+      &self.options.args_ignore_pattern,
     );
     module.visit_with(module, &mut visitor);
   }
@@ -239,49 +295,62 @@ fn get_id(r: &TsEntityName) -> Id {
   }
 }
 
-struct NoUnusedVarVisitor {
+struct NoUnusedVarVisitor<'a> {
   context: Arc<Context>,
   used_vars: HashSet<Id>,
   used_types: HashSet<Id>,
+  args_ignore_pattern: &'a Regex,
 }
 
-impl NoUnusedVarVisitor {
+impl<'a> NoUnusedVarVisitor<'a> {
   fn new(
     context: Arc<Context>,
     used_vars: HashSet<Id>,
     used_types: HashSet<Id>,
+    args_ignore_pattern: &'a Regex,
   ) -> Self {
     Self {
       context,
       used_vars,
       used_types,
+      args_ignore_pattern,
     }
   }
 }
 
-impl NoUnusedVarVisitor {
+impl<'a> NoUnusedVarVisitor<'a> {
   fn handle_id(&mut self, ident: &Ident) {
     if ident.sym.starts_with('_') {
       return;
     }
+    self.report_unused(ident);
+  }
 
+  fn handle_param_id(&mut self, ident: &Ident) {
+    if self.args_ignore_pattern.is_match(&ident.sym) {
+      return;
+    }
+    self.report_unused(ident);
+  }
+
+  fn report_unused(&mut self, ident: &Ident) {
     if !self.used_vars.contains(&ident.to_id()) {
       // The variable is not used.
       self.context.add_diagnostic(
         ident.span,
         "no-unused-vars",
-        &format!("\"{}\" is never used", ident.sym),
+        &format!("`{}` is never used", ident.sym),
       );
     }
   }
 }
 
-impl Visit for NoUnusedVarVisitor {
+impl<'a> Visit for NoUnusedVarVisitor<'a> {
   fn visit_arrow_expr(&mut self, expr: &ArrowExpr, _: &dyn Node) {
     let declared_idents: Vec<Ident> = find_ids(&expr.params);
 
     for ident in declared_idents {
-      self.handle_id(&ident);
+      self.handle_param_id(&ident);
     }
     expr.body.visit_with(expr, self)
   }
@@ -352,7 +421,7 @@ impl Visit for NoUnusedVarVisitor {
     let declared_idents: Vec<Ident> = find_ids(&param.pat);
 
     for ident in declared_idents {
-      self.handle_id(&ident);
+      self.handle_param_id(&ident);
     }
     param.visit_children_with(self)
   }
@@ -837,6 +906,61 @@ mod tests {
     );
   }
 
+  #[test]
+  fn no_unused_vars_err_import_specifiers() {
+    assert_lint_err_on_line_n::<NoUnusedVars>(
+      "import a, { b, c as d } from './mod.ts';\nconsole.log(b);",
+      vec![(1, 7), (1, 20)],
+    );
+    assert_lint_err::<NoUnusedVars>(
+      "import * as mod from './mod.ts';\nimport { mod as m } from './other.ts';\nm();",
+      12,
+    );
+
+    let diagnostics =
+      lint(NoUnusedVars::new(), "import { foo } from './mod.ts';");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "`foo` is never used");
+  }
+
+  #[test]
+  fn no_unused_vars_shadowed_params() {
+    assert_lint_ok::<NoUnusedVars>(
+      "function f(a) {\n  console.log(a);\n  return (a) => a;\n}\nf(1);",
+    );
+    assert_lint_err_on_line::<NoUnusedVars>(
+      "const a = 1;\nexport function f(a) {\n  return a;\n}",
+      1,
+      6,
+    );
+    assert_lint_err_on_line::<NoUnusedVars>(
+      "export function f(a) {\n  console.log(a);\n  return (a) => 1;\n}",
+      3,
+      10,
+    );
+  }
+
+  #[test]
+  fn no_unused_vars_args_ignore_pattern() {
+    assert_lint_ok::<NoUnusedVars>("export function f(_a, _b) {}");
+    assert_lint_ok::<NoUnusedVars>("export const f = ({ _a }) => 1;");
+
+    let rule = || {
+      NoUnusedVars::with_options(NoUnusedVarsOptions {
+        args_ignore_pattern: Regex::new("^unused").unwrap(),
+      })
+    };
+    assert_lint_ok_with(rule(), "export function f(unusedA) {}");
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "export function f(_a, unusedB) {}",
+      vec![(1, 18)],
+    );
+    // The pattern only applies to parameters.
+    assert_lint_err_on_line_n_with(rule(), "const unusedA = 1;", vec![(1, 6)]);
+    assert_lint_ok_with(rule(), "const _a = 1;");
+  }
+
   #[test]
   fn no_unused_vars_err_6() {
     assert_lint_err::<NoUnusedVars>("const data = { defaults: { x: 0 }, x: 1, y: 2 }; const { defaults: { x }, ...coords } = data;\n console.log(coords)", 69);
@@ -995,15 +1119,18 @@ export class Foo {
       ",
     );
 
-    assert_lint_ok::<NoUnusedVars>(
-      "
+    // A setter must have exactly one parameter
+    with_syntax_errors(|| {
+      assert_lint_ok::<NoUnusedVars>(
+        "
 import { AccessorDecorator } from 'decorators';
 export class Foo {
   @AccessorDecorator
   set bar() {}
 }
       ",
-    );
+      );
+    });
 
     assert_lint_ok::<NoUnusedVars>(
       "
@@ -1728,14 +1855,17 @@ declare var Foo: {
       ",
     );
 
-    assert_lint_ok::<NoUnusedVars>(
-      "
+    // A constructor can't have a return type
+    with_syntax_errors(|| {
+      assert_lint_ok::<NoUnusedVars>(
+        "
 declare class Foo {
   constructor(value?: any): Object;
   foo(): string;
 }
       ",
-    );
+      );
+    });
   }
 
   #[test]
@@ -0,0 +1,237 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::session::LintSession;
+use crate::session::ModuleSummary;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::sync::Arc;
+use swc_ecmascript::ast::{ExportAll, Module, ModuleDecl, ModuleItem};
+
+declare_rule! {
+  /// Reports `export *` declarations re-exporting a name which an earlier
+  /// `export *` of the same module already provides. Such ambiguous names are
+  /// silently left out of the module's exports:
+  ///
+  /// ```ts
+  /// export * from "./a.ts"; // exports `parse`
+  /// export * from "./b.ts"; // exports `parse` too
+  /// ```
+  ///
+  /// The exports of the re-exported modules come from the `LintSession`, so
+  /// the rule does nothing for files linted on their own.
+  NoUnversionedLockstepExports, "no-unversioned-lockstep-exports", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows `export *` declarations re-exporting a name which an earlier `export *` of the same module already provides.
+
+Names exported by two `export *` declarations are ambiguous and silently left out of the module's exports. The exports of the re-exported modules come from the lint session, so the rule does nothing for files linted on their own.
+
+### Valid, when `./a.ts` and `./b.ts` export different names:
+```text
+export * from "./a.ts";
+export * from "./b.ts";
+```
+
+### Invalid, when both `./a.ts` and `./b.ts` export `parse`:
+```text
+export * from "./a.ts";
+export * from "./b.ts";
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let session = match &context.session {
+      Some(session) => session.clone(),
+      None => return,
+    };
+    // Names the module exports explicitly take precedence over the ones
+    // from `export *`, which resolves the ambiguity.
+    let own = ModuleSummary::from_module(module);
+
+    let mut providers: HashMap<String, &ExportAll> = HashMap::new();
+    for item in &module.body {
+      let export_all = match item {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all)) => export_all,
+        _ => continue,
+      };
+      let (file_name, summary) =
+        match session.resolve(&context.file_name, &export_all.src.value) {
+          Some(resolved) => resolved,
+          None => continue,
+        };
+
+      let mut conflicts: Vec<(String, &ExportAll)> = vec![];
+      for name in star_exported_names(&session, file_name, summary) {
+        if own.exports.contains(&name) {
+          continue;
+        }
+        match providers.get(&name) {
+          Some(first) => conflicts.push((name, first)),
+          None => {
+            providers.insert(name, export_all);
+          }
+        }
+      }
+      if !conflicts.is_empty() {
+        report(&context, export_all, &conflicts);
+      }
+    }
+  }
+}
+
+/// Returns the names `export * from` a module provides: its own exports
+/// except `default`, and the ones of the modules it re-exports with
+/// `export *` itself.
+///
+/// Only one level of star re-exports is followed, names which come from
+/// longer chains of `export *` are not taken into account.
+fn star_exported_names(
+  session: &LintSession,
+  file_name: &str,
+  summary: &ModuleSummary,
+) -> BTreeSet<String> {
+  let mut names: BTreeSet<String> = summary.exports.iter().cloned().collect();
+  for specifier in &summary.star_exports {
+    if let Some((_, nested)) = session.resolve(file_name, specifier) {
+      names.extend(nested.exports.iter().cloned());
+    }
+  }
+  names.remove("default");
+  names
+}
+
+fn report(
+  context: &Context,
+  export_all: &ExportAll,
+  conflicts: &[(String, &ExportAll)],
+) {
+  let names = conflicts
+    .iter()
+    .map(|(name, _)| format!("`{}`", name))
+    .collect::<Vec<_>>()
+    .join(", ");
+  let mut firsts: Vec<&ExportAll> =
+    conflicts.iter().map(|(_, first)| *first).collect();
+  firsts.sort_by_key(|first| first.span.lo());
+  firsts.dedup_by_key(|first| first.span.lo());
+  let locations = firsts
+    .iter()
+    .map(|first| {
+      let line = context.source_map.lookup_char_pos(first.span.lo()).line;
+      format!("`export * from \"{}\"` on line {}", first.src.value, line)
+    })
+    .collect::<Vec<_>>()
+    .join(" and ");
+
+  context.add_diagnostic_with_hint(
+    export_all.span,
+    "no-unversioned-lockstep-exports",
+    &format!(
+      "`export * from \"{}\"` conflicts with an earlier `export *` on {}, which won't be exported",
+      export_all.src.value, names
+    ),
+    &format!(
+      "Also exported by {}, re-export the names explicitly to pick one",
+      locations
+    ),
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  fn session() -> LintSession {
+    let mut session = LintSession::new();
+    session
+      .add_module(
+        "/project/a.ts",
+        "export function parse() {}\nexport const VERSION = 1;\nexport const a = 1;\nexport default 1;",
+      )
+      .unwrap();
+    session
+      .add_module(
+        "/project/b.ts",
+        "export function parse() {}\nexport const VERSION = 2;\nexport const b = 1;\nexport default 2;",
+      )
+      .unwrap();
+    session
+      .add_module(
+        "/project/lib/c.ts",
+        "export * from \"../b.ts\";\nexport const c = 1;",
+      )
+      .unwrap();
+    session
+      .add_module("/project/lib/d.ts", "export * from \"./c.ts\";")
+      .unwrap();
+    session
+  }
+
+  fn lint_mod(source: &str) -> Vec<crate::diagnostic::LintDiagnostic> {
+    lint_with_session(
+      NoUnversionedLockstepExports::new(),
+      session(),
+      "/project/mod.ts",
+      source,
+    )
+  }
+
+  #[test]
+  fn no_unversioned_lockstep_exports_valid() {
+    for source in &[
+      "export * from \"./a.ts\";",
+      "export * from \"./a.ts\";\nexport * from \"./lib/c.ts\";\nexport { parse, VERSION } from \"./a.ts\";",
+      "export * from \"./a.ts\";\nexport * from \"./b.ts\";\nexport { parse } from \"./a.ts\";\nexport const VERSION = 3;",
+      "export * from \"./a.ts\";\nexport * from \"./missing.ts\";\nexport * from \"https://deno.land/x/mod.ts\";",
+      // `d.ts` only gets `b.ts` through two levels of `export *`.
+      "export * from \"./a.ts\";\nexport * from \"./lib/d.ts\";",
+    ] {
+      let diagnostics = lint_mod(source);
+      assert!(diagnostics.is_empty(), "{:?}\n{}", diagnostics, source);
+    }
+
+    // Without a session nothing is known about the re-exported modules.
+    assert_lint_ok::<NoUnversionedLockstepExports>(
+      "export * from \"./a.ts\";\nexport * from \"./b.ts\";",
+    );
+  }
+
+  #[test]
+  fn no_unversioned_lockstep_exports_invalid() {
+    let diagnostics = lint_mod(
+      "export * from \"./a.ts\";\nexport * from \"./b.ts\";\nexport { parse } from \"./b.ts\";",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(
+      diagnostics[0].message,
+      "`export * from \"./b.ts\"` conflicts with an earlier `export *` on `VERSION`, which won't be exported"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Also exported by `export * from \"./a.ts\"` on line 1, re-export the names explicitly to pick one")
+    );
+
+    let diagnostics =
+      lint_mod("export * from \"./a.ts\";\nexport * from \"./b.ts\";");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "`export * from \"./b.ts\"` conflicts with an earlier `export *` on `VERSION`, `parse`, which won't be exported"
+    );
+  }
+
+  #[test]
+  fn no_unversioned_lockstep_exports_chain() {
+    let diagnostics =
+      lint_mod("export * from \"./a.ts\";\n\nexport * from \"./lib/c.ts\";");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 3);
+    assert_eq!(
+      diagnostics[0].message,
+      "`export * from \"./lib/c.ts\"` conflicts with an earlier `export *` on `VERSION`, `parse`, which won't be exported"
+    );
+  }
+}
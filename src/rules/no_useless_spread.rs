@@ -0,0 +1,192 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ArrayLit, CallExpr, Expr, ExprOrSpread, NewExpr, ObjectLit, PropOrSpread,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  NoUselessSpread, "no-useless-spread", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows spreading an array literal into an array literal or the arguments of a call, and an object literal into an object literal.
+
+The elements or properties can be written into the outer literal directly.
+
+### Valid:
+```typescript
+const a = [...new Set(x)];
+const b = { ...defaults, c: 1 };
+Math.max(...numbers);
+```
+
+### Invalid:
+```typescript
+const a = [...[1, 2], 3];
+```
+```typescript
+const b = { ...{ c: 1 } };
+```
+```typescript
+Math.max(...[1, 2, 3]);
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = NoUselessSpreadVisitor::new(context);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoUselessSpreadVisitor {
+  context: Arc<Context>,
+}
+
+/// Array literals without holes, holes would turn into `undefined`
+/// elements or arguments when spread.
+fn array_without_holes(expr: &Expr) -> bool {
+  match expr {
+    Expr::Array(array_lit) => array_lit.elems.iter().all(Option::is_some),
+    _ => false,
+  }
+}
+
+impl NoUselessSpreadVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self { context }
+  }
+
+  fn report(&self, dot3_token: Span, expr: &Expr, message: &str, hint: &str) {
+    let span = Span::new(dot3_token.lo(), expr.span().hi(), dot3_token.ctxt);
+    self.context.add_diagnostic_with_hint(
+      span,
+      "no-useless-spread",
+      message,
+      hint,
+    );
+  }
+
+  fn check_args(&self, args: &[ExprOrSpread]) {
+    for arg in args {
+      if let Some(dot3_token) = arg.spread {
+        if array_without_holes(&arg.expr) {
+          self.report(
+            dot3_token,
+            &arg.expr,
+            "Spreading an array literal into arguments is useless",
+            "Pass the elements as arguments directly",
+          );
+        }
+      }
+    }
+  }
+}
+
+impl Visit for NoUselessSpreadVisitor {
+  noop_visit_type!();
+
+  fn visit_array_lit(&mut self, array_lit: &ArrayLit, parent: &dyn Node) {
+    for elem in array_lit.elems.iter().flatten() {
+      if let Some(dot3_token) = elem.spread {
+        if array_without_holes(&elem.expr) {
+          self.report(
+            dot3_token,
+            &elem.expr,
+            "Spreading an array literal into an array literal is useless",
+            "Move the elements into the outer array literal",
+          );
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_array_lit(self, array_lit, parent);
+  }
+
+  fn visit_object_lit(&mut self, object_lit: &ObjectLit, parent: &dyn Node) {
+    for prop in &object_lit.props {
+      if let PropOrSpread::Spread(spread) = prop {
+        if let Expr::Object(_) = &*spread.expr {
+          self.report(
+            spread.dot3_token,
+            &spread.expr,
+            "Spreading an object literal into an object literal is useless",
+            "Move the properties into the outer object literal",
+          );
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_object_lit(self, object_lit, parent);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    self.check_args(&call_expr.args);
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+    if let Some(args) = &new_expr.args {
+      self.check_args(args);
+    }
+    swc_ecmascript::visit::visit_new_expr(self, new_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn no_useless_spread_valid() {
+    assert_lint_ok_n::<NoUselessSpread>(vec![
+      "[...new Set(x)];",
+      "[...items, 1];",
+      "foo(...args);",
+      "const a = { ...b, c: 1 };",
+      "const a = { ...null };",
+      "[...[, 1]];",
+      "foo(...[a, , b]);",
+      "const a = [[1, 2], 3];",
+    ]);
+  }
+
+  #[test]
+  fn no_useless_spread_invalid() {
+    assert_lint_err::<NoUselessSpread>("[...[1, 2], 3];", 1);
+    assert_lint_err::<NoUselessSpread>("[...[]];", 1);
+    assert_lint_err::<NoUselessSpread>("const a = { ...{ b: 1 } };", 12);
+    assert_lint_err::<NoUselessSpread>("Math.max(...[1, 2, 3]);", 9);
+    assert_lint_err::<NoUselessSpread>("foo(a, ...[b, ...c]);", 7);
+    assert_lint_err::<NoUselessSpread>("new Foo(...[a, b]);", 8);
+    assert_lint_err_n::<NoUselessSpread>("[...[...[1]]];", vec![1, 5]);
+    assert_lint_err_on_line::<NoUselessSpread>(
+      "const a = {\n  ...{},\n};",
+      2,
+      2,
+    );
+  }
+
+  #[test]
+  fn no_useless_spread_hint() {
+    let diagnostics =
+      lint_tsx(NoUselessSpread::new(), "Math.max(...[1, 2, 3]);");
+    assert_eq!(
+      diagnostics[0].message,
+      "Spreading an array literal into arguments is useless"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Pass the elements as arguments directly")
+    );
+    assert_eq!(diagnostics[0].range.end.col, 21);
+  }
+}
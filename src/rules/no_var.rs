@@ -1,9 +1,10 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::swc_util::keyword_span;
+use swc_ecmascript::ast::TsModuleDecl;
 use swc_ecmascript::ast::VarDecl;
 use swc_ecmascript::ast::VarDeclKind;
-use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 
@@ -20,6 +21,26 @@ impl LintRule for NoVar {
     "no-var"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Disallows `var` declarations.
+
+`let` and `const` are block scoped and can't be used before their declaration, which avoids the pitfalls of the function scoped and hoisted `var`. Declarations in ambient TypeScript contexts, eg. `declare var a: string;`, only describe existing globals and are not reported.
+
+### Valid:
+```typescript
+let a = "a";
+const b = "b";
+```
+
+### Invalid:
+```typescript
+var someVar = "someString";
+```
+```typescript
+for (var i = 0; i < 10; i++) {}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -41,16 +62,29 @@ impl NoVarVisitor {
 }
 
 impl Visit for NoVarVisitor {
-  noop_visit_type!();
+  fn visit_ts_module_decl(
+    &mut self,
+    module_decl: &TsModuleDecl,
+    parent: &dyn Node,
+  ) {
+    if !module_decl.declare {
+      swc_ecmascript::visit::visit_ts_module_decl(self, module_decl, parent);
+    }
+  }
 
-  fn visit_var_decl(&mut self, var_decl: &VarDecl, _parent: &dyn Node) {
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, parent: &dyn Node) {
+    if var_decl.declare {
+      return;
+    }
     if var_decl.kind == VarDeclKind::Var {
-      self.context.add_diagnostic(
-        var_decl.span,
+      self.context.add_diagnostic_with_hint(
+        keyword_span(var_decl.span, "var"),
         "no-var",
         "`var` keyword is not allowed",
+        "Use `let` or `const` instead",
       );
     }
+    swc_ecmascript::visit::visit_var_decl(self, var_decl, parent);
   }
 }
 
@@ -65,5 +99,47 @@ mod tests {
       r#"var someVar = "someString"; const c = "c"; let a = "a";"#,
       0,
     );
+    assert_lint_err::<NoVar>("export var a = 1;", 7);
+    assert_lint_err::<NoVar>("const a = function () { var b; };", 24);
+    assert_lint_err_on_line::<NoVar>(
+      "function foo() {
+  var a;
+}",
+      2,
+      2,
+    );
+
+    let diagnostics = lint(NoVar::new(), "var a = 1, b = 2;");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 0);
+    assert_eq!(diagnostics[0].range.end.col, 3);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Use `let` or `const` instead")
+    );
+  }
+
+  #[test]
+  fn no_var_loop_heads() {
+    assert_lint_err::<NoVar>("for (var i = 0; i < 1; i++) {}", 5);
+    assert_lint_err::<NoVar>("for (var a in b) {}", 5);
+    assert_lint_err::<NoVar>("for (var a of b) {}", 5);
+    assert_lint_ok_n::<NoVar>(vec![
+      "for (let i = 0; i < 1; i++) {}",
+      "for (const a in b) {}",
+      "for (const a of b) {}",
+    ]);
+  }
+
+  #[test]
+  fn no_var_ambient_contexts() {
+    assert_lint_ok_n::<NoVar>(vec![
+      "declare var a: string;",
+      "declare global {\n  var a: string;\n}",
+      "declare module 'foo' {\n  var a: string;\n}",
+      "declare namespace Foo {\n  var a: string;\n}",
+      "declare namespace Foo.Bar {\n  var a: string;\n}",
+    ]);
+    assert_lint_err_on_line::<NoVar>("namespace Foo {\n  var a = 1;\n}", 2, 2);
   }
 }
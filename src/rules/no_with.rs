@@ -1,63 +1,70 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
-use super::Context;
-use super::LintRule;
-use swc_ecmascript::ast::WithStmt;
-use swc_ecmascript::visit::noop_visit_type;
-use swc_ecmascript::visit::Node;
-use swc_ecmascript::visit::Visit;
 
-use std::sync::Arc;
+declare_statement_rule! {
+  NoWith, "no-with", tags: [recommended];
+  visit_with_stmt(WithStmt), keyword: "with",
+  message: "`with` statement is not allowed",
+  hint: "Use the properties of the object directly, eg. `obj.a` instead of `a`";
 
-pub struct NoWith;
+  fn docs(&self) -> &'static str {
+    r#"Disallows `with` statements.
 
-impl LintRule for NoWith {
-  fn new() -> Box<Self> {
-    Box::new(NoWith)
-  }
-
-  fn code(&self) -> &'static str {
-    "no-with"
-  }
-
-  fn lint_module(
-    &self,
-    context: Arc<Context>,
-    module: &swc_ecmascript::ast::Module,
-  ) {
-    let mut visitor = NoWithVisitor::new(context);
-    visitor.visit_module(module, module);
-  }
-}
+`with` makes it impossible to know which binding an identifier refers to, and is a syntax error in strict mode.
 
-struct NoWithVisitor {
-  context: Arc<Context>,
-}
+### Valid:
+```typescript
+console.log(someVar.a);
+```
 
-impl NoWithVisitor {
-  fn new(context: Arc<Context>) -> Self {
-    Self { context }
-  }
+### Invalid:
+```typescript
+with (someVar) {
+  console.log(a);
 }
-
-impl Visit for NoWithVisitor {
-  noop_visit_type!();
-
-  fn visit_with_stmt(&mut self, with_stmt: &WithStmt, _parent: &dyn Node) {
-    self.context.add_diagnostic(
-      with_stmt.span,
-      "no-with",
-      "`with` statement is not allowed",
-    );
+```"#
   }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::rules::LintRule;
   use crate::test_util::*;
 
   #[test]
   fn no_with() {
-    assert_lint_err::<NoWith>("with (someVar) { console.log('asdf'); }", 0)
+    // `with` statements aren't allowed in modules
+    with_syntax_errors(|| {
+      assert_lint_err::<NoWith>("with (someVar) { console.log('asdf'); }", 0);
+      assert_lint_err_on_line_n::<NoWith>(
+        "with (a) {\n  with (b) {}\n}",
+        vec![(1, 0), (2, 2)],
+      );
+    });
+  }
+
+  #[test]
+  fn no_with_keyword_span() {
+    with_syntax_errors(|| {
+      let diagnostics = lint(NoWith::new(), "with (someVar) {}");
+      assert_eq!(diagnostics.len(), 1);
+      assert_eq!(diagnostics[0].range.end.col, 4);
+      assert_eq!(
+        diagnostics[0].hint.as_deref(),
+        Some(
+          "Use the properties of the object directly, eg. `obj.a` instead of `a`"
+        )
+      );
+    });
+  }
+
+  #[test]
+  fn no_with_valid() {
+    assert_lint_ok_n::<NoWith>(vec![
+      "const a = { with: 1 };",
+      "a.with(b);",
+      "class A { with() {} }",
+      "const { with: b } = a;",
+    ]);
   }
 }
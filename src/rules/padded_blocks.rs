@@ -0,0 +1,302 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use std::sync::Arc;
+use swc_common::{BytePos, Span, Spanned, SyntaxContext};
+use swc_ecmascript::ast::{BlockStmt, Class, Module};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PaddedBlocksMode {
+  /// Blocks must not begin or end with blank lines.
+  Never,
+  /// Blocks must begin and end with a blank line.
+  Always,
+}
+
+pub struct PaddedBlocksOptions {
+  pub mode: PaddedBlocksMode,
+}
+
+impl Default for PaddedBlocksOptions {
+  fn default() -> Self {
+    Self {
+      mode: PaddedBlocksMode::Never,
+    }
+  }
+}
+
+/// Reports blocks, function bodies and class bodies which begin or end
+/// with blank lines, or which don't in the "always" mode.
+///
+/// Only the whitespace between the braces and the first and last content
+/// of the block (a statement, a member or a comment) is looked at, so
+/// blank lines in template literals are never reported. Empty blocks are
+/// never reported.
+pub struct PaddedBlocks {
+  options: PaddedBlocksOptions,
+}
+
+impl PaddedBlocks {
+  pub fn with_options(options: PaddedBlocksOptions) -> Box<Self> {
+    Box::new(PaddedBlocks { options })
+  }
+}
+
+impl LintRule for PaddedBlocks {
+  fn new() -> Box<Self> {
+    Self::with_options(PaddedBlocksOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "padded-blocks"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows blocks, function bodies and class bodies which begin or end with blank lines.
+
+Only the whitespace between the braces and the first and last content of the block is looked at, so blank lines in template literals are never reported. Empty blocks are never reported.
+
+### Options
+
+`PaddedBlocksOptions::mode` is one of:
+- `PaddedBlocksMode::Never` (default): blocks must not begin or end with blank lines.
+- `PaddedBlocksMode::Always`: blocks must begin and end with a blank line.
+
+### Valid:
+```typescript
+function f() {
+  a();
+}
+```
+
+### Invalid:
+```typescript
+function f() {
+
+  a();
+}
+```
+```typescript
+class A {
+  a = 1;
+
+}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = PaddedBlocksVisitor {
+      context,
+      mode: self.options.mode,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct PaddedBlocksVisitor {
+  context: Arc<Context>,
+  mode: PaddedBlocksMode,
+}
+
+fn pos(lo: BytePos, offset: usize) -> BytePos {
+  lo + BytePos(offset as u32)
+}
+
+fn span(lo: BytePos, hi: BytePos) -> Span {
+  Span::new(lo, hi, SyntaxContext::empty())
+}
+
+impl PaddedBlocksVisitor {
+  /// Checks the whitespace inside of the braces at `open` and `close`.
+  fn check(&self, open: BytePos, close: BytePos) {
+    // Only whitespace and comments can come between the braces and the
+    // first and last content.
+    let inner = self.context.span_text(span(open + BytePos(1), close));
+    let inner_lo = open + BytePos(1);
+    let leading = inner.len() - inner.trim_start().len();
+    if leading == inner.len() {
+      return;
+    }
+    let trailing_start = inner.trim_end().len();
+
+    let leading_ws = &inner[..leading];
+    let trailing_ws = &inner[trailing_start..];
+    match self.mode {
+      PaddedBlocksMode::Never => {
+        if let Some(blank) = blank_lines(leading_ws) {
+          self.context.add_diagnostic_with_hint(
+            span(pos(inner_lo, blank.0), pos(inner_lo, blank.1)),
+            "padded-blocks",
+            "Block must not be padded by blank lines",
+            "Remove the blank lines after the opening brace",
+          );
+        }
+        if let Some(blank) = blank_lines(trailing_ws) {
+          let trailing_lo = pos(inner_lo, trailing_start);
+          self.context.add_diagnostic_with_hint(
+            span(pos(trailing_lo, blank.0), pos(trailing_lo, blank.1)),
+            "padded-blocks",
+            "Block must not be padded by blank lines",
+            "Remove the blank lines before the closing brace",
+          );
+        }
+      }
+      PaddedBlocksMode::Always => {
+        if blank_lines(leading_ws).is_none() {
+          self.context.add_diagnostic_with_hint(
+            span(inner_lo, inner_lo),
+            "padded-blocks",
+            "Block must be padded by blank lines",
+            "Add a blank line after the opening brace",
+          );
+        }
+        if blank_lines(trailing_ws).is_none() {
+          self.context.add_diagnostic_with_hint(
+            span(close, close),
+            "padded-blocks",
+            "Block must be padded by blank lines",
+            "Add a blank line before the closing brace",
+          );
+        }
+      }
+    }
+  }
+}
+
+/// Returns the byte range of the blank lines in `whitespace`, from the
+/// start of the first one to the end of the last one, if there are any.
+fn blank_lines(whitespace: &str) -> Option<(usize, usize)> {
+  let first = whitespace.find('\n')?;
+  let last = whitespace.rfind('\n')?;
+  if first == last {
+    return None;
+  }
+  Some((first + 1, last))
+}
+
+impl Visit for PaddedBlocksVisitor {
+  noop_visit_type!();
+
+  fn visit_block_stmt(&mut self, block: &BlockStmt, parent: &dyn Node) {
+    self.check(block.span.lo(), block.span.hi() - BytePos(1));
+    swc_ecmascript::visit::visit_block_stmt(self, block, parent);
+  }
+
+  fn visit_class(&mut self, class: &Class, parent: &dyn Node) {
+    // The body starts at the first brace after the heritage clauses and
+    // the decorators, which may contain braces of their own.
+    let mut header_end = class.span.lo();
+    let mut extend = |hi: BytePos| header_end = header_end.max(hi);
+    class.decorators.iter().for_each(|d| extend(d.span.hi()));
+    class.type_params.iter().for_each(|t| extend(t.span.hi()));
+    class.super_class.iter().for_each(|s| extend(s.span().hi()));
+    class
+      .super_type_params
+      .iter()
+      .for_each(|t| extend(t.span.hi()));
+    class.implements.iter().for_each(|i| extend(i.span.hi()));
+
+    let close = class.span.hi() - BytePos(1);
+    let header = self.context.span_text(span(header_end, close));
+    if let Some(offset) = header.find('{') {
+      self.check(pos(header_end, offset), close);
+    }
+    swc_ecmascript::visit::visit_class(self, class, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn always() -> Box<PaddedBlocks> {
+    PaddedBlocks::with_options(PaddedBlocksOptions {
+      mode: PaddedBlocksMode::Always,
+    })
+  }
+
+  #[test]
+  fn padded_blocks_valid() {
+    assert_lint_ok_n::<PaddedBlocks>(vec![
+      "{}",
+      "{\n\n}",
+      "function f() {\n\n\n}",
+      "if (a) { b(); }",
+      "function f() {\n  a();\n\n  b();\n}",
+      "function f() {\n  // comment\n  a();\n}",
+      "function f() {\n  return `\n\n`;\n}",
+      "function f() {\n  a(); // trailing comment\n}",
+      "class A {\n  a = 1;\n}",
+      "class A extends mixin({\n\n  a: 1,\n\n}) {\n  b = 1;\n}",
+      "@decorate({\n\n})\nclass A {\n  b = 1;\n}",
+      "const o = {\n\n  a: 1,\n\n};",
+    ]);
+  }
+
+  #[test]
+  fn padded_blocks_invalid() {
+    assert_lint_err_on_line::<PaddedBlocks>(
+      "function f() {\n\n  a();\n}",
+      2,
+      0,
+    );
+    assert_lint_err_on_line::<PaddedBlocks>(
+      "function f() {\n  a();\n\n}",
+      3,
+      0,
+    );
+    assert_lint_err_on_line::<PaddedBlocks>("if (a) {\n   \n  b();\n}", 2, 0);
+    assert_lint_err_on_line::<PaddedBlocks>("class A {\n\n  a = 1;\n}", 2, 0);
+    assert_lint_err_on_line::<PaddedBlocks>(
+      "class A extends B<{ a: 1 }> implements C {\n  a = 1;\n\n}",
+      3,
+      0,
+    );
+    // The comment is the first content of the block.
+    assert_lint_err_on_line::<PaddedBlocks>(
+      "function f() {\n\n  // comment\n  a();\n}",
+      2,
+      0,
+    );
+    assert_lint_err_on_line::<PaddedBlocks>(
+      "const f = () => {\n  return `\n\n`;\n\n};",
+      5,
+      0,
+    );
+
+    // The diagnostic covers all the blank lines.
+    let diagnostics = lint(PaddedBlocks::new(), "{\n\n  \n\n  a();\n}");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(diagnostics[0].range.end.line, 4);
+  }
+
+  #[test]
+  fn padded_blocks_always() {
+    assert_lint_ok_with(always(), "function f() {\n\n  a();\n\n}");
+    assert_lint_ok_with(always(), "function f() {}");
+    assert_lint_ok_with(always(), "class A {\n\n  a = 1;\n\n}");
+
+    let diagnostics = lint(always(), "function f() {\n  a();\n}");
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+      diagnostics[0].message,
+      "Block must be padded by blank lines"
+    );
+    // The diagnostics are zero-width, right inside of the braces.
+    assert_eq!(diagnostics[0].range.start, diagnostics[0].range.end);
+    assert_eq!(diagnostics[0].range.start.line, 1);
+    assert_eq!(diagnostics[0].range.start.col, 14);
+    assert_eq!(diagnostics[1].range.start, diagnostics[1].range.end);
+    assert_eq!(diagnostics[1].range.start.line, 3);
+    assert_eq!(diagnostics[1].range.start.col, 0);
+  }
+}
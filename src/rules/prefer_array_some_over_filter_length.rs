@@ -0,0 +1,455 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::{array_bindings, ArrayBindings};
+use std::sync::Arc;
+use swc_common::{Span, Spanned, SyntaxContext};
+use swc_ecmascript::ast::{
+  BinExpr, BinaryOp, CallExpr, Expr, ExprOrSuper, Lit, MemberExpr, Module,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Default)]
+pub struct PreferArraySomeOverFilterLengthOptions {
+  /// Only reports `filter()` calls on array literals and on `const`
+  /// bindings which are initialized to an array, instead of on any
+  /// receiver.
+  pub require_array_like_receiver: bool,
+}
+
+/// Reports `filter()` calls whose result is only used to check if any
+/// element matches, like `arr.filter(fn).length > 0`, which should use
+/// `some()`, or to get the first or last match, like `arr.filter(fn)[0]`,
+/// which should use `find()` or `findLast()`. Those stop at the first
+/// match and don't create an intermediate array.
+///
+/// The hint contains the rewritten expression.
+pub struct PreferArraySomeOverFilterLength {
+  options: PreferArraySomeOverFilterLengthOptions,
+}
+
+impl PreferArraySomeOverFilterLength {
+  pub fn with_options(
+    options: PreferArraySomeOverFilterLengthOptions,
+  ) -> Box<Self> {
+    Box::new(PreferArraySomeOverFilterLength { options })
+  }
+}
+
+impl LintRule for PreferArraySomeOverFilterLength {
+  fn new() -> Box<Self> {
+    Self::with_options(PreferArraySomeOverFilterLengthOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "prefer-array-some-over-filter-length"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires `some()`, `find()` or `findLast()` instead of `filter()` when the result is only used to check if any element matches or to get the first or last match.
+
+`arr.filter(fn).length > 0` and `arr.filter(fn)[0]` create an intermediate array and look at every element, while `some()` and `find()` stop at the first match. The hint contains the rewritten expression.
+
+### Options
+
+`PreferArraySomeOverFilterLengthOptions::require_array_like_receiver`: only checks `filter()` calls on array literals and on `const` bindings initialized to an array, `false` by default.
+
+### Valid:
+```typescript
+if (arr.some(fn)) {}
+const x = arr.find(fn);
+```
+
+### Invalid:
+```typescript
+if (arr.filter(fn).length > 0) {}
+```
+```typescript
+const x = arr.filter(fn)[0];
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let arrays = if self.options.require_array_like_receiver {
+      Some(array_bindings(module))
+    } else {
+      None
+    };
+    let mut visitor =
+      PreferArraySomeOverFilterLengthVisitor { context, arrays };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct PreferArraySomeOverFilterLengthVisitor {
+  context: Arc<Context>,
+  /// The known arrays, when receivers are required to be array-like.
+  arrays: Option<ArrayBindings>,
+}
+
+/// Removes parentheses and optional chaining around an expression.
+fn peel(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => peel(&paren.expr),
+    Expr::OptChain(opt_chain) => peel(&opt_chain.expr),
+    _ => expr,
+  }
+}
+
+/// Returns the object and property name of a static member access.
+fn static_member(member_expr: &MemberExpr) -> Option<(&Expr, &str, Span)> {
+  match (&member_expr.obj, &*member_expr.prop) {
+    (ExprOrSuper::Expr(obj), Expr::Ident(prop)) if !member_expr.computed => {
+      Some((obj, &prop.sym, prop.span))
+    }
+    _ => None,
+  }
+}
+
+/// A call of `receiver.filter(...)`.
+struct FilterCall<'a> {
+  receiver: &'a Expr,
+  /// The span of the `filter` property.
+  method: Span,
+  call_expr: &'a CallExpr,
+}
+
+fn filter_call(expr: &Expr) -> Option<FilterCall<'_>> {
+  let call_expr = match peel(expr) {
+    Expr::Call(call_expr) => call_expr,
+    _ => return None,
+  };
+  let member_expr = match &call_expr.callee {
+    ExprOrSuper::Expr(callee) => match peel(callee) {
+      Expr::Member(member_expr) => member_expr,
+      _ => return None,
+    },
+    ExprOrSuper::Super(_) => return None,
+  };
+  match static_member(member_expr)? {
+    (receiver, "filter", method) if !call_expr.args.is_empty() => {
+      Some(FilterCall {
+        receiver,
+        method,
+        call_expr,
+      })
+    }
+    _ => None,
+  }
+}
+
+/// Returns the `filter()` call of `receiver.filter(...).length`.
+fn filter_length(expr: &Expr) -> Option<FilterCall<'_>> {
+  match peel(expr) {
+    Expr::Member(member_expr) => match static_member(member_expr)? {
+      (obj, "length", _) => filter_call(obj),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+fn number(expr: &Expr) -> Option<f64> {
+  match peel(expr) {
+    Expr::Lit(Lit::Num(num)) => Some(num.value),
+    _ => None,
+  }
+}
+
+/// Checks if comparing `length op value` tests for a non-empty array, or
+/// for an empty one.
+fn checks_non_empty(op: BinaryOp, value: f64) -> Option<bool> {
+  match (op, value as i64) {
+    _ if value.fract() != 0.0 => None,
+    (BinaryOp::Gt, 0)
+    | (BinaryOp::NotEqEq, 0)
+    | (BinaryOp::NotEq, 0)
+    | (BinaryOp::GtEq, 1) => Some(true),
+    (BinaryOp::EqEqEq, 0)
+    | (BinaryOp::EqEq, 0)
+    | (BinaryOp::LtEq, 0)
+    | (BinaryOp::Lt, 1) => Some(false),
+    _ => None,
+  }
+}
+
+/// Returns the operator with its operands swapped, eg. `>` for `0 < x`.
+fn flip(op: BinaryOp) -> BinaryOp {
+  match op {
+    BinaryOp::Gt => BinaryOp::Lt,
+    BinaryOp::GtEq => BinaryOp::LtEq,
+    BinaryOp::Lt => BinaryOp::Gt,
+    BinaryOp::LtEq => BinaryOp::GtEq,
+    _ => op,
+  }
+}
+
+impl PreferArraySomeOverFilterLengthVisitor {
+  fn is_receiver_allowed(&self, filter: &FilterCall) -> bool {
+    match &self.arrays {
+      Some(arrays) => arrays.is_array(filter.receiver),
+      None => true,
+    }
+  }
+
+  /// Rewrites the `filter()` call to call `method` instead, keeping the
+  /// receiver, any optional chaining and the callback as written.
+  fn rewrite(&self, filter: &FilterCall, method: &str) -> String {
+    let args = &filter.call_expr.args;
+    let head = self.context.span_text(Span::new(
+      filter.receiver.span().lo(),
+      filter.method.lo(),
+      SyntaxContext::empty(),
+    ));
+    let callback = self.context.span_text(Span::new(
+      args[0].span().lo(),
+      args[args.len() - 1].span().hi(),
+      SyntaxContext::empty(),
+    ));
+    format!("{}{}({})", head, method, callback)
+  }
+
+  fn report(&self, span: Span, message: &str, replacement: &str, method: &str) {
+    self.context.add_diagnostic_with_hint(
+      span,
+      "prefer-array-some-over-filter-length",
+      message,
+      &format!(
+        "Replace with `{}`; `{}()` stops at the first match and doesn't create an array",
+        replacement, method
+      ),
+    );
+  }
+
+  fn check_bin_expr(&self, bin_expr: &BinExpr) {
+    let (filter, op, value) = match (
+      filter_length(&bin_expr.left),
+      filter_length(&bin_expr.right),
+    ) {
+      (Some(filter), None) => (filter, bin_expr.op, number(&bin_expr.right)),
+      (None, Some(filter)) => {
+        (filter, flip(bin_expr.op), number(&bin_expr.left))
+      }
+      _ => return,
+    };
+    let non_empty = match value.and_then(|value| checks_non_empty(op, value)) {
+      Some(non_empty) => non_empty,
+      None => return,
+    };
+    if !self.is_receiver_allowed(&filter) {
+      return;
+    }
+
+    let some = self.rewrite(&filter, "some");
+    let replacement = if non_empty {
+      some
+    } else {
+      format!("!{}", some)
+    };
+    self.report(
+      bin_expr.span,
+      "Use `some()` instead of checking the length of the result of `filter()`",
+      &replacement,
+      "some",
+    );
+  }
+
+  /// Checks for `filter(...)[0]`.
+  fn check_member_expr(&self, member_expr: &MemberExpr) {
+    if !member_expr.computed || number(&member_expr.prop) != Some(0.0) {
+      return;
+    }
+    let filter = match &member_expr.obj {
+      ExprOrSuper::Expr(obj) => match filter_call(obj) {
+        Some(filter) => filter,
+        None => return,
+      },
+      ExprOrSuper::Super(_) => return,
+    };
+    if !self.is_receiver_allowed(&filter) {
+      return;
+    }
+    self.report(
+      member_expr.span,
+      "Use `find()` instead of taking the first element of the result of `filter()`",
+      &self.rewrite(&filter, "find"),
+      "find",
+    );
+  }
+
+  /// Checks for `filter(...).shift()` and `filter(...).pop()`.
+  fn check_call_expr(&self, call_expr: &CallExpr) {
+    if !call_expr.args.is_empty() {
+      return;
+    }
+    let member_expr = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => match peel(callee) {
+        Expr::Member(member_expr) => member_expr,
+        _ => return,
+      },
+      ExprOrSuper::Super(_) => return,
+    };
+    let (filter, method, message) = match static_member(member_expr) {
+      Some((obj, "shift", _)) => (
+        filter_call(obj),
+        "find",
+        "Use `find()` instead of taking the first element of the result of `filter()`",
+      ),
+      Some((obj, "pop", _)) => (
+        filter_call(obj),
+        "findLast",
+        "Use `findLast()` instead of taking the last element of the result of `filter()`",
+      ),
+      _ => return,
+    };
+    let filter = match filter {
+      Some(filter) if self.is_receiver_allowed(&filter) => filter,
+      _ => return,
+    };
+    self.report(
+      call_expr.span,
+      message,
+      &self.rewrite(&filter, method),
+      method,
+    );
+  }
+}
+
+impl Visit for PreferArraySomeOverFilterLengthVisitor {
+  noop_visit_type!();
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, parent: &dyn Node) {
+    self.check_bin_expr(bin_expr);
+    swc_ecmascript::visit::visit_bin_expr(self, bin_expr, parent);
+  }
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr, parent: &dyn Node) {
+    self.check_member_expr(member_expr);
+    swc_ecmascript::visit::visit_member_expr(self, member_expr, parent);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    self.check_call_expr(call_expr);
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn hint(source: &str) -> Option<String> {
+    let diagnostics = lint(PreferArraySomeOverFilterLength::new(), source);
+    assert_eq!(diagnostics.len(), 1, "{}", source);
+    diagnostics[0].hint.clone()
+  }
+
+  #[test]
+  fn prefer_array_some_over_filter_length_valid() {
+    assert_lint_ok_n::<PreferArraySomeOverFilterLength>(vec![
+      "arr.some(fn);",
+      "arr.find(fn);",
+      // The length is used as a number.
+      "const n = arr.filter(fn).length;",
+      "total += arr.filter(fn).length;",
+      "arr.filter(fn).length > 1;",
+      "arr.filter(fn).length === 2;",
+      "arr.filter(fn).length > x;",
+      "arr.filter(fn)[1];",
+      "arr.filter(fn)[i];",
+      "arr.filter(fn).slice(0, 1);",
+      "arr.filter(fn).pop(x);",
+      "arr.filter().length > 0;",
+      "arr.map(fn).length > 0;",
+      "arr.filter(fn).size > 0;",
+    ]);
+  }
+
+  #[test]
+  fn prefer_array_some_over_filter_length_invalid() {
+    assert_lint_err::<PreferArraySomeOverFilterLength>(
+      "if (arr.filter(fn).length > 0) {}",
+      4,
+    );
+    assert_lint_err::<PreferArraySomeOverFilterLength>(
+      "const x = arr.filter(fn)[0];",
+      10,
+    );
+    assert_lint_err::<PreferArraySomeOverFilterLength>(
+      "const x = arr.filter(fn).shift();",
+      10,
+    );
+    assert_lint_err_n::<PreferArraySomeOverFilterLength>(
+      "a.filter(f).length > 0 && b.filter(g)[0];",
+      vec![0, 26],
+    );
+  }
+
+  #[test]
+  fn prefer_array_some_over_filter_length_hint() {
+    let cases = vec![
+      ("arr.filter(fn).length > 0;", "arr.some(fn)"),
+      ("arr.filter(fn).length !== 0;", "arr.some(fn)"),
+      ("arr.filter(fn).length != 0;", "arr.some(fn)"),
+      ("arr.filter(fn).length >= 1;", "arr.some(fn)"),
+      ("0 < arr.filter(fn).length;", "arr.some(fn)"),
+      ("1 <= arr.filter(fn).length;", "arr.some(fn)"),
+      ("arr.filter(fn).length === 0;", "!arr.some(fn)"),
+      ("arr.filter(fn).length < 1;", "!arr.some(fn)"),
+      ("0 == arr.filter(fn).length;", "!arr.some(fn)"),
+      (
+        "users.filter((u) => u.active, ctx).length > 0;",
+        "users.some((u) => u.active, ctx)",
+      ),
+      ("arr.filter(fn)[0];", "arr.find(fn)"),
+      ("arr.filter(fn).shift();", "arr.find(fn)"),
+      ("get().items.filter(fn).pop();", "get().items.findLast(fn)"),
+      // Optional chaining is kept.
+      ("arr?.filter(fn).length > 0;", "arr?.some(fn)"),
+      ("obj.arr?.filter(fn)[0];", "obj.arr?.find(fn)"),
+    ];
+    for (source, replacement) in cases {
+      let expected = if replacement.contains(".some(") {
+        format!("Replace with `{}`; `some()` stops at the first match and doesn't create an array", replacement)
+      } else if replacement.contains(".findLast(") {
+        format!("Replace with `{}`; `findLast()` stops at the first match and doesn't create an array", replacement)
+      } else {
+        format!("Replace with `{}`; `find()` stops at the first match and doesn't create an array", replacement)
+      };
+      assert_eq!(hint(source), Some(expected), "{}", source);
+    }
+
+    let diagnostics = lint(
+      PreferArraySomeOverFilterLength::new(),
+      "arr.filter(fn).pop();",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "Use `findLast()` instead of taking the last element of the result of `filter()`"
+    );
+  }
+
+  #[test]
+  fn prefer_array_some_over_filter_length_require_array_like_receiver() {
+    let rule = || {
+      PreferArraySomeOverFilterLength::with_options(
+        PreferArraySomeOverFilterLengthOptions {
+          require_array_like_receiver: true,
+        },
+      )
+    };
+    assert_lint_ok_with(rule(), "items.filter(fn).length > 0;");
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "const items = [1, 2, 3];\nitems.filter(fn).length > 0;\n[1, 2].filter(fn)[0];",
+      vec![(2, 0), (3, 0)],
+    );
+  }
+}
@@ -20,6 +20,30 @@ impl LintRule for PreferAsConst {
     "prefer-as-const"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires `as const` instead of repeating a literal type.
+
+`"baz" as "baz"` and `let x: "bar" = "bar"` repeat the literal, which `as const` avoids.
+
+### Valid:
+```typescript
+let foo = "baz" as const;
+let bar = { baz: 1 as const };
+```
+
+### Invalid:
+```typescript
+let foo = "baz" as "baz";
+```
+```typescript
+let bar: 1 = 1;
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
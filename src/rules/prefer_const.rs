@@ -26,6 +26,30 @@ impl LintRule for PreferConst {
     "prefer-const"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Requires `const` for variables which are never reassigned after their declaration.
+
+`const` tells the reader that the binding never changes.
+
+### Valid:
+```typescript
+const a = 1;
+let b = 1;
+b = 2;
+```
+
+### Invalid:
+```typescript
+let a = 1;
+foo(a);
+```
+```typescript
+for (let x of [1, 2, 3]) {
+  foo(x);
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -770,7 +794,7 @@ mod tests {
     );
     assert_lint_err_n::<PreferConst>(
       r#"for (let i in [1,2,3]) { let x = 1; foo(x); }"#,
-      vec![29, 9],
+      vec![9, 29],
     );
     assert_lint_err_on_line::<PreferConst>(
       r#"
@@ -833,7 +857,7 @@ var foo = function() {
       r#"/*eslint use-x:error*/ { let x = 1 }"#,
       29,
     );
-    assert_lint_err_n::<PreferConst>(r#"let { foo, bar } = baz;"#, vec![11, 6]);
+    assert_lint_err_n::<PreferConst>(r#"let { foo, bar } = baz;"#, vec![6, 11]);
     assert_lint_err::<PreferConst>(r#"const x = [1,2]; let [,y] = x;"#, 23);
     assert_lint_err_n::<PreferConst>(
       r#"const x = [1,2,3]; let [y,,z] = x;"#,
@@ -863,11 +887,11 @@ var foo = function() {
     );
     assert_lint_err_n::<PreferConst>(
       r#"let x = 'x', y = 'y'; function someFunc() { let a = 1, b = 2; foo(a, b) }"#,
-      vec![48, 55, 4, 13],
+      vec![4, 13, 48, 55],
     );
     assert_lint_err_n::<PreferConst>(
       r#"let someFunc = () => { let a = 1, b = 2; foo(a, b) }"#,
-      vec![27, 34, 4],
+      vec![4, 27, 34],
     );
     assert_lint_err_n::<PreferConst>(r#"let {a, b} = c, d;"#, vec![5, 8]);
     assert_lint_err_n::<PreferConst>(
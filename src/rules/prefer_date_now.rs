@@ -0,0 +1,172 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  BinExpr, BinaryOp, CallExpr, Expr, ExprOrSuper, Lit, NewExpr, Number,
+  UnaryExpr, UnaryOp,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  PreferDateNow, "prefer-date-now", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Requires `Date.now()` to get the current timestamp.
+
+`new Date().getTime()`, `new Date().valueOf()` and `+new Date()` create a `Date` object only to convert it to a number.
+
+### Valid:
+```typescript
+const now = Date.now();
+```
+
+### Invalid:
+```typescript
+const now = new Date().getTime();
+```
+```typescript
+const now = +new Date();
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = PreferDateNowVisitor::new(context);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct PreferDateNowVisitor {
+  context: Arc<Context>,
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+impl PreferDateNowVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self { context }
+  }
+
+  /// Matches `new Date()` and `new Date`, which create the current date.
+  fn is_new_date(&self, expr: &Expr) -> bool {
+    match unwrap_paren(expr) {
+      Expr::New(NewExpr { callee, args, .. }) => {
+        let no_args = match args {
+          Some(args) => args.is_empty(),
+          None => true,
+        };
+        no_args
+          && matches!(&**callee, Expr::Ident(ident) if self.context.is_global(ident, "Date"))
+      }
+      _ => false,
+    }
+  }
+
+  fn report(&self, span: Span) {
+    self.context.add_diagnostic_with_hint(
+      span,
+      "prefer-date-now",
+      "Use `Date.now()` to get the current timestamp",
+      "Rewrite as `Date.now()`",
+    );
+  }
+}
+
+impl Visit for PreferDateNowVisitor {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      match &**callee {
+        // `new Date().getTime()` and `new Date().valueOf()`
+        Expr::Member(member) if call_expr.args.is_empty() => {
+          let is_timestamp_method = !member.computed
+            && matches!(&*member.prop, Expr::Ident(prop) if prop.sym == *"getTime" || prop.sym == *"valueOf");
+          if let ExprOrSuper::Expr(obj) = &member.obj {
+            if is_timestamp_method && self.is_new_date(obj) {
+              self.report(call_expr.span);
+            }
+          }
+        }
+        // `Number(new Date())`
+        Expr::Ident(ident) if self.context.is_global(ident, "Number") => {
+          if let [arg] = call_expr.args.as_slice() {
+            if arg.spread.is_none() && self.is_new_date(&arg.expr) {
+              self.report(call_expr.span);
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, parent: &dyn Node) {
+    if unary_expr.op == UnaryOp::Plus && self.is_new_date(&unary_expr.arg) {
+      self.report(unary_expr.span);
+    }
+    swc_ecmascript::visit::visit_unary_expr(self, unary_expr, parent);
+  }
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, parent: &dyn Node) {
+    let minus_zero = bin_expr.op == BinaryOp::Sub
+      && matches!(
+        unwrap_paren(&bin_expr.right),
+        Expr::Lit(Lit::Num(Number { value, .. })) if *value == 0.0
+      );
+    if minus_zero && self.is_new_date(&bin_expr.left) {
+      self.report(bin_expr.span);
+    }
+    swc_ecmascript::visit::visit_bin_expr(self, bin_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn prefer_date_now_valid() {
+    assert_lint_ok_n::<PreferDateNow>(vec![
+      "const now = Date.now();",
+      "const then = new Date(someArg).getTime();",
+      "const then = new Date(2020, 1, 1).valueOf();",
+      "const then = +new Date(someArg);",
+      "const date = new Date();",
+      "const now = new MyDate().getTime();",
+      "class Date {}; const now = new Date().getTime();",
+      "const now = new Date().getDay();",
+      "const now = new Date() - 1;",
+      "const now = Number(new Date(value));",
+      "function f(Number) { return Number(new Date()); }",
+    ]);
+  }
+
+  #[test]
+  fn prefer_date_now_invalid() {
+    assert_lint_err::<PreferDateNow>("const now = new Date().getTime();", 12);
+    assert_lint_err::<PreferDateNow>("const now = new Date().valueOf();", 12);
+    assert_lint_err::<PreferDateNow>("const now = (new Date).getTime();", 12);
+    assert_lint_err::<PreferDateNow>("const now = +new Date();", 12);
+    assert_lint_err::<PreferDateNow>("const now = new Date() - 0;", 12);
+    assert_lint_err::<PreferDateNow>("const now = Number(new Date());", 12);
+    assert_lint_err_on_line::<PreferDateNow>(
+      "function elapsed(start) {\n  return +new Date() - start;\n}",
+      2,
+      9,
+    );
+  }
+}
@@ -0,0 +1,159 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::{binary_expr_text, member_path};
+use std::sync::Arc;
+use swc_ecmascript::ast::{BinaryOp, CallExpr, Expr, ExprOrSuper, Module};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  /// Reports calls of `Math.pow()`, which can be written with the `**`
+  /// operator instead. The hint contains the rewritten expression.
+  PreferExponentiationOperator, "prefer-exponentiation-operator", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Requires the `**` operator instead of `Math.pow()`.
+
+The operator is shorter and reads like the math. The hint contains the rewritten expression.
+
+### Valid:
+```typescript
+const a = b ** c;
+```
+
+### Invalid:
+```typescript
+const a = Math.pow(b, c);
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = PreferExponentiationOperatorVisitor { context };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct PreferExponentiationOperatorVisitor {
+  context: Arc<Context>,
+}
+
+impl PreferExponentiationOperatorVisitor {
+  fn is_math_pow(&self, call_expr: &CallExpr) -> bool {
+    let callee = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => callee,
+      ExprOrSuper::Super(_) => return false,
+    };
+    match member_path(callee) {
+      (Expr::Ident(ident), path) => {
+        path.len() == 1
+          && path[0] == "pow"
+          && self.context.is_global(ident, "Math")
+      }
+      _ => false,
+    }
+  }
+
+  fn check(&self, call_expr: &CallExpr) {
+    if !self.is_math_pow(call_expr) || call_expr.args.len() < 2 {
+      return;
+    }
+    let (base, exponent) = (&call_expr.args[0], &call_expr.args[1]);
+    if base.spread.is_some() || exponent.spread.is_some() {
+      return;
+    }
+
+    let replacement = binary_expr_text(
+      &self.context,
+      BinaryOp::Exp,
+      &base.expr,
+      &exponent.expr,
+    );
+    let hint = if call_expr.args.len() > 2 {
+      format!(
+        "Replace with `{}`; the extra arguments are ignored by `Math.pow()`",
+        replacement
+      )
+    } else {
+      format!("Replace with `{}`", replacement)
+    };
+    self.context.add_diagnostic_with_hint(
+      call_expr.span,
+      "prefer-exponentiation-operator",
+      "Use the `**` operator instead of `Math.pow()`",
+      &hint,
+    );
+  }
+}
+
+impl Visit for PreferExponentiationOperatorVisitor {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    self.check(call_expr);
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  fn hint(source: &str) -> Option<String> {
+    let diagnostics = lint(PreferExponentiationOperator::new(), source);
+    assert_eq!(diagnostics.len(), 1, "{}", source);
+    diagnostics[0].hint.clone()
+  }
+
+  #[test]
+  fn prefer_exponentiation_operator_valid() {
+    assert_lint_ok_n::<PreferExponentiationOperator>(vec![
+      "a ** b;",
+      "Math.pow(a);",
+      "Math.pow(...args);",
+      "Math.max(a, b);",
+      "pow(a, b);",
+      "foo.Math.pow(a, b);",
+      "const Math = { pow: (a, b) => a };\nMath.pow(a, b);",
+    ]);
+  }
+
+  #[test]
+  fn prefer_exponentiation_operator_invalid() {
+    assert_lint_err::<PreferExponentiationOperator>("Math.pow(a, b);", 0);
+    assert_lint_err::<PreferExponentiationOperator>(
+      "x = Math['pow'](a, b);",
+      4,
+    );
+    assert_lint_err_n::<PreferExponentiationOperator>(
+      "Math.pow(Math.pow(a, 2), 3);",
+      vec![0, 9],
+    );
+  }
+
+  #[test]
+  fn prefer_exponentiation_operator_hint() {
+    let cases = vec![
+      ("Math.pow(a, b);", "Replace with `a ** b`"),
+      ("Math.pow(2, 0.5);", "Replace with `2 ** 0.5`"),
+      ("Math.pow(-2, 3);", "Replace with `(-2) ** 3`"),
+      ("Math.pow(2, -3);", "Replace with `2 ** -3`"),
+      ("Math.pow(a + 1, b * 2);", "Replace with `(a + 1) ** (b * 2)`"),
+      ("Math.pow(a ** b, c);", "Replace with `(a ** b) ** c`"),
+      ("Math.pow(a, b ** c);", "Replace with `a ** b ** c`"),
+      ("Math.pow(a.b, f(c));", "Replace with `a.b ** f(c)`"),
+      ("Math.pow((a + 1), b);", "Replace with `(a + 1) ** b`"),
+      ("Math.pow(a ? b : c, 2);", "Replace with `(a ? b : c) ** 2`"),
+      ("Math.pow(typeof a, 2);", "Replace with `(typeof a) ** 2`"),
+      (
+        "Math.pow(a, b, extra);",
+        "Replace with `a ** b`; the extra arguments are ignored by `Math.pow()`",
+      ),
+    ];
+    for (source, expected) in cases {
+      assert_eq!(hint(source).as_deref(), Some(expected), "{}", source);
+    }
+  }
+}
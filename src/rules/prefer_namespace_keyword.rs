@@ -19,6 +19,27 @@ impl LintRule for PreferNamespaceKeyword {
     "prefer-namespace-keyword"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires the `namespace` keyword instead of `module` to declare TypeScript namespaces.
+
+`module` is the legacy keyword for namespaces and easily confused with ES modules. Ambient module declarations with a string name are allowed.
+
+### Valid:
+```typescript
+declare module "foo";
+namespace bar {}
+```
+
+### Invalid:
+```typescript
+module foo {}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -94,12 +115,15 @@ mod tests {
   #[test]
   fn prefer_namespace_keyword_invalid() {
     assert_lint_err::<PreferNamespaceKeyword>(r#"module foo {}"#, 0);
-    assert_lint_err_on_line_n::<PreferNamespaceKeyword>(
-      r#"
+    // `declare` is redundant within an ambient module
+    with_syntax_errors(|| {
+      assert_lint_err_on_line_n::<PreferNamespaceKeyword>(
+        r#"
       declare module foo {
         declare module bar {}
       }"#,
-      vec![(2, 6), (3, 8)],
-    );
+        vec![(2, 6), (3, 8)],
+      );
+    });
   }
 }
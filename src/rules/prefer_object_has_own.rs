@@ -0,0 +1,338 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::{member_path, node_equals};
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  BinExpr, BinaryOp, CallExpr, CondExpr, Expr, ExprOrSpread, ExprOrSuper,
+  MemberExpr, ParenExpr,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Default)]
+pub struct PreferObjectHasOwnOptions {
+  /// Also reports `Object.keys(obj).includes(key)` and
+  /// `key in obj ? obj[key] : other`.
+  ///
+  /// These are not exactly the same as `Object.hasOwn`, the former skips
+  /// non-enumerable properties and the latter looks at the prototype chain.
+  pub aggressive: bool,
+}
+
+pub struct PreferObjectHasOwn {
+  options: PreferObjectHasOwnOptions,
+}
+
+impl PreferObjectHasOwn {
+  pub fn with_options(options: PreferObjectHasOwnOptions) -> Box<Self> {
+    Box::new(PreferObjectHasOwn { options })
+  }
+}
+
+impl LintRule for PreferObjectHasOwn {
+  fn new() -> Box<Self> {
+    Self::with_options(PreferObjectHasOwnOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "prefer-object-has-own"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires `Object.hasOwn()` to check for own properties.
+
+`Object.prototype.hasOwnProperty.call(obj, key)` and `({}).hasOwnProperty.call(obj, key)` are longer ways to write `Object.hasOwn(obj, key)`.
+
+### Options
+
+`PreferObjectHasOwnOptions::aggressive`: also reports `Object.keys(obj).includes(key)` and `key in obj ? obj[key] : other`, `false` by default. They are not exactly the same as `Object.hasOwn()`: the former skips non-enumerable properties and the latter looks at the prototype chain.
+
+### Valid:
+```typescript
+Object.hasOwn(obj, key);
+```
+
+### Invalid:
+```typescript
+Object.prototype.hasOwnProperty.call(obj, key);
+```
+```typescript
+const has = ({}).hasOwnProperty.call(obj, "a");
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    // `Object.hasOwn` was added in ES2022, which is newer than any target
+    // that can be configured.
+    if context.target.is_some() {
+      return;
+    }
+
+    let mut visitor =
+      PreferObjectHasOwnVisitor::new(context, self.options.aggressive);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct PreferObjectHasOwnVisitor {
+  context: Arc<Context>,
+  aggressive: bool,
+}
+
+impl PreferObjectHasOwnVisitor {
+  fn new(context: Arc<Context>, aggressive: bool) -> Self {
+    Self {
+      context,
+      aggressive,
+    }
+  }
+
+  fn is_global_object(&self, expr: &Expr) -> bool {
+    match expr {
+      Expr::Ident(ident) => self.context.is_global(ident, "Object"),
+      _ => false,
+    }
+  }
+
+  /// Matches `Object.prototype.hasOwnProperty.call` and
+  /// `{}.hasOwnProperty.call`.
+  fn is_has_own_property_call(&self, callee: &Expr) -> bool {
+    let (object, path) = member_path(callee);
+    match path.as_slice() {
+      [prototype, has_own_property, call]
+        if prototype == "prototype"
+          && has_own_property == "hasOwnProperty"
+          && call == "call" =>
+      {
+        self.is_global_object(object)
+      }
+      [has_own_property, call]
+        if has_own_property == "hasOwnProperty" && call == "call" =>
+      {
+        matches!(object, Expr::Object(lit) if lit.props.is_empty())
+      }
+      _ => false,
+    }
+  }
+
+  /// Matches `Object.keys(obj).includes`, returning `obj`.
+  fn keys_includes_target<'a>(&self, callee: &'a Expr) -> Option<&'a Expr> {
+    let (keys_call, path) = member_path(callee);
+    if path != ["includes"] {
+      return None;
+    }
+    let keys_call = match keys_call {
+      Expr::Call(call_expr) => call_expr,
+      _ => return None,
+    };
+    let keys_callee = match &keys_call.callee {
+      ExprOrSuper::Expr(expr) => expr,
+      ExprOrSuper::Super(_) => return None,
+    };
+    let (object, path) = member_path(keys_callee);
+    if path != ["keys"] || !self.is_global_object(object) {
+      return None;
+    }
+    match plain_args(&keys_call.args)?.as_slice() {
+      [obj] => Some(obj),
+      _ => None,
+    }
+  }
+
+  fn report(&self, node: &dyn Spanned, obj: &Expr, key: &Expr) {
+    self.context.add_diagnostic_with_hint(
+      node.span(),
+      "prefer-object-has-own",
+      "Use `Object.hasOwn` to check for own properties",
+      &format!(
+        "Rewrite as `Object.hasOwn({}, {})`",
+        self.context.span_text(obj.span()),
+        self.context.span_text(key.span())
+      ),
+    );
+  }
+}
+
+/// Returns the arguments of a call, unless any of them is spread.
+fn plain_args(args: &[ExprOrSpread]) -> Option<Vec<&Expr>> {
+  args
+    .iter()
+    .map(|arg| match arg.spread {
+      Some(_) => None,
+      None => Some(&*arg.expr),
+    })
+    .collect()
+}
+
+fn unwrap_parens(mut expr: &Expr) -> &Expr {
+  while let Expr::Paren(ParenExpr { expr: inner, .. }) = expr {
+    expr = inner;
+  }
+  expr
+}
+
+impl Visit for PreferObjectHasOwnVisitor {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      let args = plain_args(&call_expr.args);
+      match args.as_deref() {
+        Some([obj, key]) if self.is_has_own_property_call(callee) => {
+          self.report(call_expr, obj, key);
+        }
+        Some([key]) if self.aggressive => {
+          if let Some(obj) = self.keys_includes_target(callee) {
+            self.report(call_expr, obj, key);
+          }
+        }
+        _ => {}
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+
+  fn visit_cond_expr(&mut self, cond_expr: &CondExpr, parent: &dyn Node) {
+    if self.aggressive {
+      if let Expr::Bin(
+        in_expr @ BinExpr {
+          op: BinaryOp::In, ..
+        },
+      ) = unwrap_parens(&cond_expr.test)
+      {
+        // Only when the property is read right away, `key in obj` alone
+        // may as well be looking for inherited properties.
+        if let Expr::Member(MemberExpr {
+          obj: ExprOrSuper::Expr(obj),
+          prop,
+          computed: true,
+          ..
+        }) = unwrap_parens(&cond_expr.cons)
+        {
+          if node_equals(obj, &in_expr.right)
+            && node_equals(prop, &in_expr.left)
+          {
+            self.report(in_expr, &in_expr.right, &in_expr.left);
+          }
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_cond_expr(self, cond_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::linter::LinterBuilder;
+  use crate::test_util::*;
+  use swc_ecmascript::parser::JscTarget;
+
+  fn aggressive() -> Box<PreferObjectHasOwn> {
+    PreferObjectHasOwn::with_options(PreferObjectHasOwnOptions {
+      aggressive: true,
+    })
+  }
+
+  #[test]
+  fn prefer_object_has_own_valid() {
+    assert_lint_ok_n::<PreferObjectHasOwn>(vec![
+      "Object.hasOwn(obj, key);",
+      "obj.hasOwnProperty(key);",
+      "Object.prototype.hasOwnProperty.call(...args);",
+      "Object.prototype.hasOwnProperty.call(obj, ...keys);",
+      "Object.prototype.hasOwnProperty.call(obj);",
+      "({ a: 1 }).hasOwnProperty.call(obj, key);",
+      "Object.prototype.toString.call(obj);",
+      "function f(Object) { Object.prototype.hasOwnProperty.call(obj, key); }",
+      "Object.keys(obj).includes(key);",
+      "key in obj ? obj[key] : undefined;",
+    ]);
+  }
+
+  #[test]
+  fn prefer_object_has_own_invalid() {
+    assert_lint_err::<PreferObjectHasOwn>(
+      "Object.prototype.hasOwnProperty.call(obj, key);",
+      0,
+    );
+    assert_lint_err::<PreferObjectHasOwn>(
+      "const has = ({}).hasOwnProperty.call(obj, 'a');",
+      12,
+    );
+    assert_lint_err::<PreferObjectHasOwn>(
+      "if (Object.prototype.hasOwnProperty.call(a?.b, key)) {}",
+      4,
+    );
+    assert_lint_err::<PreferObjectHasOwn>(
+      "Object.prototype.hasOwnProperty?.call(obj, key);",
+      0,
+    );
+    assert_lint_err_on_line::<PreferObjectHasOwn>(
+      "for (const key in obj) {\n  if (Object.prototype.hasOwnProperty.call(obj, key)) {}\n}",
+      2,
+      6,
+    );
+  }
+
+  #[test]
+  fn prefer_object_has_own_hint() {
+    let diagnostics = lint_tsx(
+      PreferObjectHasOwn::new(),
+      "Object.prototype.hasOwnProperty.call(foo?.bar, keys[0]);",
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Rewrite as `Object.hasOwn(foo?.bar, keys[0])`")
+    );
+    assert_eq!(diagnostics[0].range.end.col, 55);
+  }
+
+  #[test]
+  fn prefer_object_has_own_aggressive() {
+    assert_lint_ok_with(aggressive(), "key in obj;");
+    assert_lint_ok_with(aggressive(), "key in obj ? other[key] : undefined;");
+    assert_lint_ok_with(aggressive(), "Object.keys(obj).includes(...keys);");
+    assert_lint_err_on_line_n_with(
+      aggressive(),
+      "Object.keys(obj).includes(key);",
+      vec![(1, 0)],
+    );
+    assert_lint_err_on_line_n_with(
+      aggressive(),
+      "const v = key in obj ? obj[key] : undefined;",
+      vec![(1, 10)],
+    );
+    let diagnostics = lint_tsx(aggressive(), "Object.keys(a.b).includes(k);");
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Rewrite as `Object.hasOwn(a.b, k)`")
+    );
+  }
+
+  #[test]
+  fn prefer_object_has_own_old_target() {
+    let mut linter = LinterBuilder::default()
+      .target(JscTarget::Es2020)
+      .rules(vec![PreferObjectHasOwn::new()])
+      .build();
+    let diagnostics = linter
+      .lint(
+        "deno_lint_test.ts".to_string(),
+        "Object.prototype.hasOwnProperty.call(obj, key);".to_string(),
+      )
+      .expect("Failed to lint");
+    assert!(diagnostics.is_empty());
+  }
+}
@@ -0,0 +1,219 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::member_path;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSpread, ExprOrSuper, Module, ParenExpr,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  /// Reports the `JSON.parse(JSON.stringify(value))` deep clone idiom, which
+  /// drops `undefined` values and functions and turns `Date`s, `Map`s and
+  /// `Set`s into strings and empty objects. `structuredClone(value)` handles
+  /// them, and is suggested unless an older target is configured.
+  ///
+  /// Only the nested form is matched, the result of `JSON.stringify` stored
+  /// in a variable before being parsed is not followed.
+  PreferStructuredClone, "prefer-structured-clone", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows deep cloning with `JSON.parse(JSON.stringify(value))`.
+
+The idiom drops `undefined` values and functions and turns `Date`s, `Map`s and `Set`s into strings and empty objects. `structuredClone(value)` handles them, and is suggested unless an older target is configured. Only the nested form is matched.
+
+### Valid:
+```typescript
+const copy = structuredClone(obj);
+```
+
+### Invalid:
+```typescript
+const copy = JSON.parse(JSON.stringify(obj));
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = PreferStructuredCloneVisitor::new(context);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct PreferStructuredCloneVisitor {
+  context: Arc<Context>,
+}
+
+impl PreferStructuredCloneVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self { context }
+  }
+
+  /// Matches a call to `JSON.<method>` or `globalThis.JSON.<method>` with
+  /// a single argument, returning the argument.
+  fn json_call<'a>(
+    &self,
+    call_expr: &'a CallExpr,
+    method: &str,
+  ) -> Option<&'a Expr> {
+    let callee = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => callee,
+      ExprOrSuper::Super(_) => return None,
+    };
+    let (object, path) = member_path(callee);
+    let is_json = match (object, path.as_slice()) {
+      (Expr::Ident(ident), [called]) if called == method => {
+        self.context.is_global(ident, "JSON")
+      }
+      (Expr::Ident(ident), [json, called])
+        if json == "JSON" && called == method =>
+      {
+        self.context.is_global(ident, "globalThis")
+      }
+      _ => false,
+    };
+    if !is_json {
+      return None;
+    }
+    // A reviver or replacer changes the result, so it's not a plain clone.
+    match call_expr.args.as_slice() {
+      [ExprOrSpread { spread: None, expr }] => Some(expr),
+      _ => None,
+    }
+  }
+
+  fn check(&self, call_expr: &CallExpr) {
+    let string = match self.json_call(call_expr, "parse").map(unparen) {
+      Some(Expr::Call(string)) => string,
+      _ => return,
+    };
+    let value = match self.json_call(string, "stringify") {
+      Some(value) => value,
+      None => return,
+    };
+
+    // `structuredClone` is newer than any target that can be configured.
+    if self.context.target.is_some() {
+      self.context.add_diagnostic_with_hint(
+        call_expr.span,
+        "prefer-structured-clone",
+        "Deep cloning with `JSON.parse(JSON.stringify(...))` drops `undefined` values and functions, and breaks `Date`s, `Map`s and `Set`s",
+        "Make sure the value only contains plain objects, arrays, strings, numbers, booleans and `null`",
+      );
+    } else {
+      self.context.add_diagnostic_with_hint(
+        call_expr.span,
+        "prefer-structured-clone",
+        "Use `structuredClone` to deep clone values",
+        &format!(
+          "Rewrite as `structuredClone({})`",
+          self.context.span_text(value.span())
+        ),
+      );
+    }
+  }
+}
+
+impl Visit for PreferStructuredCloneVisitor {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    self.check(call_expr);
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+fn unparen(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => unparen(expr),
+    _ => expr,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::linter::LinterBuilder;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+  use swc_ecmascript::parser::JscTarget;
+
+  #[test]
+  fn prefer_structured_clone_valid() {
+    assert_lint_ok_n::<PreferStructuredClone>(vec![
+      "structuredClone(obj);",
+      "JSON.parse(str);",
+      "JSON.parse(JSON.stringify(obj, replacer));",
+      "JSON.parse(JSON.stringify(obj, null, 2));",
+      "JSON.parse(JSON.stringify(obj), reviver);",
+      "JSON.parse(JSON.stringify(...args));",
+      "JSON.parse(JSON5.stringify(obj));",
+      "JSON5.parse(JSON.stringify(obj));",
+      "function f(JSON) { return JSON.parse(JSON.stringify(obj)); }",
+      "const JSON = json; JSON.parse(JSON.stringify(obj));",
+      // The string isn't followed through variables.
+      "const str = JSON.stringify(obj);\nconst copy = JSON.parse(str);",
+    ]);
+  }
+
+  #[test]
+  fn prefer_structured_clone_invalid() {
+    assert_lint_err::<PreferStructuredClone>(
+      "const copy = JSON.parse(JSON.stringify(obj));",
+      13,
+    );
+    assert_lint_err::<PreferStructuredClone>(
+      "const copy = globalThis.JSON.parse(globalThis.JSON.stringify(obj));",
+      13,
+    );
+    assert_lint_err::<PreferStructuredClone>(
+      "const copy = JSON.parse((JSON.stringify(obj)));",
+      13,
+    );
+    assert_lint_err_on_line::<PreferStructuredClone>(
+      "function deepClone(value) {\n  return JSON.parse(JSON.stringify(value));\n}",
+      2,
+      9,
+    );
+
+    let diagnostics = lint(
+      PreferStructuredClone::new(),
+      "const copy = JSON.parse(JSON.stringify(state.items[0]));",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "Use `structuredClone` to deep clone values"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Rewrite as `structuredClone(state.items[0])`")
+    );
+    assert_eq!(diagnostics[0].range.end.col, 55);
+  }
+
+  #[test]
+  fn prefer_structured_clone_old_target() {
+    let mut linter = LinterBuilder::default()
+      .target(JscTarget::Es2020)
+      .rules(vec![PreferStructuredClone::new()])
+      .build();
+    let diagnostics = linter
+      .lint(
+        "deno_lint_test.ts".to_string(),
+        "const copy = JSON.parse(JSON.stringify(obj));".to_string(),
+      )
+      .expect("Failed to lint");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Deep cloning with `JSON.parse(JSON.stringify(...))` drops `undefined` values and functions, and breaks `Date`s, `Map`s and `Set`s"
+    );
+    assert!(!diagnostics[0]
+      .hint
+      .as_deref()
+      .unwrap()
+      .contains("structuredClone"));
+  }
+}
@@ -0,0 +1,184 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::member_call_chain;
+use regex::Regex;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{ExprStmt, Module, ModuleItem, Stmt};
+
+const PROMISE_METHODS: &[&str] = &["then", "catch", "finally"];
+
+#[derive(Default)]
+pub struct PreferTopLevelAwaitOptions {
+  /// When set, only files whose name matches the pattern are considered
+  /// entry points and linted by this rule.
+  pub only_entry_points: Option<Regex>,
+}
+
+pub struct PreferTopLevelAwait {
+  options: PreferTopLevelAwaitOptions,
+}
+
+impl PreferTopLevelAwait {
+  pub fn with_options(options: PreferTopLevelAwaitOptions) -> Box<Self> {
+    Box::new(PreferTopLevelAwait { options })
+  }
+
+  fn lint_expr_stmt(&self, context: &Context, expr_stmt: &ExprStmt) {
+    let (methods, receiver) = member_call_chain(&expr_stmt.expr);
+    if !methods
+      .iter()
+      .any(|method| PROMISE_METHODS.contains(&method.as_str()))
+    {
+      return;
+    }
+
+    let receiver_text = context
+      .source_map
+      .span_to_snippet(receiver.span())
+      .unwrap_or_else(|_| "promise".to_string());
+
+    context.add_diagnostic_with_hint(
+      expr_stmt.span,
+      "prefer-top-level-await",
+      "Prefer top-level `await` over promise chains at module top level",
+      &format!(
+        "Rewrite as `try {{ await {}; }} catch (err) {{ ... }}`",
+        receiver_text
+      ),
+    );
+  }
+}
+
+impl LintRule for PreferTopLevelAwait {
+  fn new() -> Box<Self> {
+    Self::with_options(PreferTopLevelAwaitOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "prefer-top-level-await"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires top-level `await` instead of promise chains at the top level of a module.
+
+`main().catch(console.error)` at the top level can be written with `await` and `try`/`catch`, which keeps the module from finishing before the promise settles.
+
+### Options
+
+`PreferTopLevelAwaitOptions::only_entry_points`: when set, only files whose name matches the regular expression are considered entry points and checked. Unset by default.
+
+### Valid:
+```typescript
+await main();
+```
+```typescript
+try {
+  await main();
+} catch (err) {
+  console.error(err);
+}
+```
+
+### Invalid:
+```typescript
+main().catch(console.error);
+```
+```typescript
+main().then(() => Deno.exit(0));
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    // CommonJS modules don't support top-level await.
+    if context.media_type.is_commonjs() {
+      return;
+    }
+
+    if let Some(pattern) = &self.options.only_entry_points {
+      if !pattern.is_match(&context.file_name) {
+        return;
+      }
+    }
+
+    for item in &module.body {
+      if let ModuleItem::Stmt(Stmt::Expr(expr_stmt)) = item {
+        self.lint_expr_stmt(&context, expr_stmt);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn prefer_top_level_await_valid() {
+    assert_lint_ok_n::<PreferTopLevelAwait>(vec![
+      "await main();",
+      "try { await main(); } catch (err) { console.error(err); }",
+      "const p = main().then(() => Deno.exit(0));",
+      "let p; p = main().catch(console.error);",
+      "async function run() { main().then(() => Deno.exit(0)); }",
+      "const run = () => { main().catch(console.error); };",
+      "main();",
+      "foo.bar().baz();",
+    ]);
+  }
+
+  #[test]
+  fn prefer_top_level_await_invalid() {
+    assert_lint_err::<PreferTopLevelAwait>(
+      "main().then(() => Deno.exit(0)).catch((err) => { console.error(err); });",
+      0,
+    );
+    assert_lint_err::<PreferTopLevelAwait>("main().catch(console.error);", 0);
+    assert_lint_err::<PreferTopLevelAwait>(
+      "main().finally(() => cleanup());",
+      0,
+    );
+    assert_lint_err::<PreferTopLevelAwait>(
+      "(async () => { await run(); })().catch(console.error);",
+      0,
+    );
+    assert_lint_err_on_line::<PreferTopLevelAwait>(
+      "import { main } from './main.ts';\nmain()\n  .then(() => Deno.exit(0));",
+      2,
+      0,
+    );
+  }
+
+  #[test]
+  fn prefer_top_level_await_only_entry_points() {
+    let rule = |pattern: &str| {
+      PreferTopLevelAwait::with_options(PreferTopLevelAwaitOptions {
+        only_entry_points: Some(Regex::new(pattern).unwrap()),
+      })
+    };
+    assert_lint_ok_with(rule(r"main\.tsx?$"), "main().catch(console.error);");
+    assert_lint_err_on_line_n_with(
+      rule(r"_test\.tsx?$"),
+      "main().catch(console.error);",
+      vec![(1, 0)],
+    );
+  }
+
+  #[test]
+  fn prefer_top_level_await_commonjs() {
+    let source = "main().catch(console.error);";
+    for file_name in &["main.cjs", "main.cts"] {
+      assert!(lint_as(PreferTopLevelAwait::new(), file_name, source).is_empty());
+    }
+    assert_eq!(
+      lint_as(PreferTopLevelAwait::new(), "main.mjs", source).len(),
+      1
+    );
+  }
+}
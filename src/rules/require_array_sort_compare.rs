@@ -0,0 +1,403 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::is_number;
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_ecmascript::ast::{
+  ArrowExpr, AssignExpr, BinaryOp, BlockStmtOrExpr, CallExpr, Expr,
+  ExprOrSuper, Lit, Module, Pat, PatOrExpr, Prop, PropName, PropOrSpread,
+  UnaryOp, UpdateExpr, VarDecl, VarDeclKind,
+};
+use swc_ecmascript::utils::find_ids;
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+#[derive(Default)]
+pub struct RequireArraySortCompareOptions {
+  /// Reports every `sort()` without a comparator, not only the ones on
+  /// arrays which are known to contain numbers.
+  pub require_for_all_arrays: bool,
+}
+
+/// Reports `sort()` and `toSorted()` without a comparator on arrays of
+/// numbers, which are sorted as strings: `[10, 1, 2].sort()` is
+/// `[1, 10, 2]`.
+pub struct RequireArraySortCompare {
+  options: RequireArraySortCompareOptions,
+}
+
+impl RequireArraySortCompare {
+  pub fn with_options(options: RequireArraySortCompareOptions) -> Box<Self> {
+    Box::new(RequireArraySortCompare { options })
+  }
+}
+
+impl LintRule for RequireArraySortCompare {
+  fn new() -> Box<Self> {
+    Self::with_options(RequireArraySortCompareOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "require-array-sort-compare"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires a comparator for `sort()` and `toSorted()` on arrays of numbers.
+
+Without a comparator, the elements are sorted as strings: `[10, 1, 2].sort()` is `[1, 10, 2]`.
+
+### Options
+
+`RequireArraySortCompareOptions::require_for_all_arrays`: reports every `sort()` without a comparator, not only the ones on arrays known to contain numbers, `false` by default.
+
+### Valid:
+```typescript
+[10, 1, 2].sort((a, b) => a - b);
+```
+```typescript
+["b", "a"].sort();
+```
+
+### Invalid:
+```typescript
+[10, 1, 2].sort();
+```
+```typescript
+const sizes = [10, 1, 2];
+sizes.toSorted();
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut reassigned = ReassignmentCollector {
+      ids: HashSet::new(),
+    };
+    module.visit_with(module, &mut reassigned);
+
+    let mut visitor = RequireArraySortCompareVisitor {
+      context,
+      options: &self.options,
+      reassigned: reassigned.ids,
+      number_arrays: HashSet::new(),
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+/// Collects the variables which are assigned to after their declaration.
+struct ReassignmentCollector {
+  ids: HashSet<Id>,
+}
+
+impl Visit for ReassignmentCollector {
+  noop_visit_type!();
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, parent: &dyn Node) {
+    match &assign_expr.left {
+      PatOrExpr::Pat(pat) => {
+        let ids: Vec<Id> = find_ids(pat);
+        self.ids.extend(ids);
+      }
+      PatOrExpr::Expr(expr) => {
+        if let Expr::Ident(ident) = &**expr {
+          self.ids.insert(ident.to_id());
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_assign_expr(self, assign_expr, parent);
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, parent: &dyn Node) {
+    if let Expr::Ident(ident) = &*update_expr.arg {
+      self.ids.insert(ident.to_id());
+    }
+    swc_ecmascript::visit::visit_update_expr(self, update_expr, parent);
+  }
+}
+
+struct RequireArraySortCompareVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a RequireArraySortCompareOptions,
+  reassigned: HashSet<Id>,
+  /// Bindings which are never reassigned and hold an array of numbers.
+  number_arrays: HashSet<Id>,
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+fn is_num_lit(expr: &Expr) -> bool {
+  match unwrap_paren(expr) {
+    Expr::Lit(Lit::Num(_)) => true,
+    Expr::Unary(unary) if unary.op == UnaryOp::Minus => is_num_lit(&unary.arg),
+    _ => false,
+  }
+}
+
+/// Returns the method name and receiver of `receiver.method(...)`.
+fn method_call(call_expr: &CallExpr) -> Option<(&str, &Expr)> {
+  let callee = match &call_expr.callee {
+    ExprOrSuper::Expr(callee) => unwrap_paren(callee),
+    ExprOrSuper::Super(_) => return None,
+  };
+  match callee {
+    Expr::Member(member) if !member.computed => {
+      match (&member.obj, &*member.prop) {
+        (ExprOrSuper::Expr(obj), Expr::Ident(prop)) => Some((&prop.sym, obj)),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+impl<'a> RequireArraySortCompareVisitor<'a> {
+  /// Checks if the expression evaluates to a number, going by its syntax.
+  fn is_numeric(&self, expr: &Expr) -> bool {
+    match unwrap_paren(expr) {
+      Expr::Lit(Lit::Num(_)) => true,
+      Expr::Unary(unary) => {
+        matches!(unary.op, UnaryOp::Minus | UnaryOp::Plus | UnaryOp::Tilde)
+      }
+      Expr::Bin(bin) => match bin.op {
+        BinaryOp::Sub
+        | BinaryOp::Mul
+        | BinaryOp::Div
+        | BinaryOp::Mod
+        | BinaryOp::Exp
+        | BinaryOp::BitAnd
+        | BinaryOp::BitOr
+        | BinaryOp::BitXor
+        | BinaryOp::LShift
+        | BinaryOp::RShift
+        | BinaryOp::ZeroFillRShift => true,
+        // `+` concatenates if either side is a string.
+        BinaryOp::Add => {
+          self.is_numeric(&bin.left) && self.is_numeric(&bin.right)
+        }
+        _ => false,
+      },
+      Expr::Call(_) => is_number(&self.context, expr),
+      _ => false,
+    }
+  }
+
+  /// Checks if the callback of `.map()` or `Array.from()` returns a
+  /// number.
+  fn is_numeric_callback(&self, callback: &Expr) -> bool {
+    match unwrap_paren(callback) {
+      Expr::Ident(ident) => self.context.is_global(ident, "Number"),
+      Expr::Arrow(ArrowExpr {
+        params,
+        body: BlockStmtOrExpr::Expr(body),
+        ..
+      }) => {
+        // The second parameter is the index: `(_, i) => i`
+        let returns_index = match (params.get(1), unwrap_paren(body)) {
+          (Some(Pat::Ident(index)), Expr::Ident(ident)) => {
+            index.to_id() == ident.to_id()
+          }
+          _ => false,
+        };
+        returns_index || self.is_numeric(body)
+      }
+      _ => false,
+    }
+  }
+
+  fn is_number_array(&self, expr: &Expr) -> bool {
+    match unwrap_paren(expr) {
+      Expr::Array(array) => {
+        !array.elems.is_empty()
+          && array.elems.iter().all(|elem| match elem {
+            Some(elem) => elem.spread.is_none() && is_num_lit(&elem.expr),
+            None => false,
+          })
+      }
+      Expr::Ident(ident) => self.number_arrays.contains(&ident.to_id()),
+      Expr::Call(call_expr) => {
+        let (method, receiver) = match method_call(call_expr) {
+          Some(method_call) => method_call,
+          None => return false,
+        };
+        match method {
+          "map" => match call_expr.args.first() {
+            Some(callback) => {
+              callback.spread.is_none()
+                && self.is_numeric_callback(&callback.expr)
+            }
+            None => false,
+          },
+          // `Array.from({ length: n }, (_, i) => i)`
+          "from" => {
+            let is_array = matches!(unwrap_paren(receiver), Expr::Ident(ident) if self.context.is_global(ident, "Array"));
+            let has_length = match call_expr.args.first() {
+              Some(arg) => match unwrap_paren(&arg.expr) {
+                Expr::Object(object) => object.props.iter().any(|prop| {
+                  match prop {
+                    PropOrSpread::Prop(prop) => match &**prop {
+                      Prop::KeyValue(kv) => {
+                        matches!(&kv.key, PropName::Ident(key) if key.sym == *"length")
+                      }
+                      Prop::Shorthand(ident) => ident.sym == *"length",
+                      _ => false,
+                    },
+                    PropOrSpread::Spread(_) => false,
+                  }
+                }),
+                _ => false,
+              },
+              None => false,
+            };
+            let numeric_callback = match call_expr.args.get(1) {
+              Some(callback) => self.is_numeric_callback(&callback.expr),
+              None => true,
+            };
+            is_array && has_length && numeric_callback
+          }
+          // Sorting, filtering and copying keep the elements.
+          "sort" | "toSorted" | "reverse" | "toReversed" | "filter"
+          | "slice" => self.is_number_array(receiver),
+          _ => false,
+        }
+      }
+      _ => false,
+    }
+  }
+}
+
+impl<'a> Visit for RequireArraySortCompareVisitor<'a> {
+  noop_visit_type!();
+
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, parent: &dyn Node) {
+    // `var` can be redeclared, so only block scoped bindings are tracked.
+    if var_decl.kind != VarDeclKind::Var {
+      for decl in &var_decl.decls {
+        if let (Pat::Ident(ident), Some(init)) = (&decl.name, &decl.init) {
+          if !self.reassigned.contains(&ident.to_id())
+            && self.is_number_array(init)
+          {
+            self.number_arrays.insert(ident.to_id());
+          }
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_var_decl(self, var_decl, parent);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let Some((method, receiver)) = method_call(call_expr) {
+      if (method == "sort" || method == "toSorted")
+        && call_expr.args.is_empty()
+        && (self.options.require_for_all_arrays
+          || self.is_number_array(receiver))
+      {
+        self.context.add_diagnostic_with_hint(
+          call_expr.span,
+          "require-array-sort-compare",
+          &format!(
+            "`{}()` without a comparator sorts elements as strings",
+            method
+          ),
+          "Pass a comparator, eg. `(a, b) => a - b` for numbers",
+        );
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn require_array_sort_compare_valid() {
+    assert_lint_ok_n::<RequireArraySortCompare>(vec![
+      "[10, 1, 2].sort((a, b) => a - b);",
+      r#"["b", "a"].sort();"#,
+      "[1, x].sort();",
+      "[].sort();",
+      "items.sort();",
+      "values.map(String).sort();",
+      "values.map((x) => x.name).sort();",
+      "values.map((x) => x + 1).sort();",
+      "let a = [3, 1, 2]; a = names; a.sort();",
+      "var a = [3, 1, 2]; a.sort();",
+      "const Number = String; values.map(Number).sort();",
+      "[10, 1, 2].sort(compare);",
+    ]);
+  }
+
+  #[test]
+  fn require_array_sort_compare_invalid() {
+    assert_lint_err::<RequireArraySortCompare>("[10, 1, 2].sort();", 0);
+    assert_lint_err::<RequireArraySortCompare>("[10, -1, 2.5].toSorted();", 0);
+    assert_lint_err_on_line::<RequireArraySortCompare>(
+      "const sizes = [10, 1, 2];\nsizes.sort();",
+      2,
+      0,
+    );
+    assert_lint_err_on_line::<RequireArraySortCompare>(
+      "let sizes = [10, 1, 2];\nconst copy = sizes.slice();\ncopy.sort();",
+      3,
+      0,
+    );
+    assert_lint_err::<RequireArraySortCompare>(
+      "const a = values.map(Number).sort();",
+      10,
+    );
+    assert_lint_err::<RequireArraySortCompare>(
+      "const a = values.map((x) => x * 2).sort();",
+      10,
+    );
+    assert_lint_err::<RequireArraySortCompare>(
+      "const a = values.map((x) => parseInt(x)).sort();",
+      10,
+    );
+    assert_lint_err::<RequireArraySortCompare>(
+      "const a = Array.from({ length: n }, (_, i) => i).sort();",
+      10,
+    );
+
+    let diagnostics =
+      lint(RequireArraySortCompare::new(), "[10, 1, 2].toSorted();");
+    assert_eq!(
+      diagnostics[0].message,
+      "`toSorted()` without a comparator sorts elements as strings"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Pass a comparator, eg. `(a, b) => a - b` for numbers")
+    );
+  }
+
+  #[test]
+  fn require_array_sort_compare_require_for_all_arrays() {
+    let rule = || {
+      RequireArraySortCompare::with_options(RequireArraySortCompareOptions {
+        require_for_all_arrays: true,
+      })
+    };
+    assert_lint_ok_with(rule(), "items.sort((a, b) => a.localeCompare(b));");
+    assert_lint_err_on_line_n_with(
+      rule(),
+      r#"items.sort(); ["b", "a"].sort(); values.map(String).toSorted();"#,
+      vec![(1, 0), (1, 14), (1, 33)],
+    );
+  }
+}
@@ -0,0 +1,339 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::keyword_span;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ArrowExpr, AwaitExpr, BlockStmtOrExpr, ClassMethod, FnDecl, FnExpr,
+  ForOfStmt, Function, MethodProp, Module, PrivateMethod, YieldExpr,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+declare_rule! {
+  /// Reports async functions which never await, which usually means an
+  /// `await` was forgotten, or the function doesn't need to be async.
+  RequireAwait, "require-await", tags: [recommended];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows async functions which have no `await` expression.
+
+An async function which never awaits usually means an `await` was forgotten. Otherwise it only wraps its result in a promise, which the function doesn't need to be async for. A `for await` loop counts as an `await`, and so does `yield` in async generators. Async functions with an empty body are allowed.
+
+### Valid:
+```typescript
+async function foo() {
+  await bar();
+}
+```
+```typescript
+async function noop() {}
+```
+```typescript
+async function* foo() {
+  yield 1;
+}
+```
+
+### Invalid:
+```typescript
+async function foo() {
+  bar();
+}
+```
+```typescript
+const foo = async () => bar();
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = RequireAwaitVisitor {
+      context,
+      await_stack: vec![],
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct RequireAwaitVisitor {
+  context: Arc<Context>,
+  /// Whether each enclosing function has awaited so far, the innermost
+  /// last. Awaits only count for the function they are directly in.
+  await_stack: Vec<bool>,
+}
+
+impl RequireAwaitVisitor {
+  fn mark_await(&mut self) {
+    if let Some(last) = self.await_stack.last_mut() {
+      *last = true;
+    }
+  }
+
+  /// Visits the children of a function in its own entry of the await
+  /// stack, and reports it at `span` if it is async but never awaits.
+  fn check_function<N: VisitWith<Self>>(
+    &mut self,
+    function: &N,
+    is_async: bool,
+    is_empty: bool,
+    span: Span,
+    name: Option<&str>,
+  ) {
+    self.await_stack.push(false);
+    function.visit_children_with(self);
+    let has_await = self.await_stack.pop().unwrap();
+
+    if is_async && !is_empty && !has_await {
+      let message = match name {
+        Some(name) => {
+          format!("Async function '{}' has no 'await' expression", name)
+        }
+        None => "Async function has no 'await' expression".to_string(),
+      };
+      self.context.add_diagnostic_with_hint(
+        span,
+        "require-await",
+        &message,
+        "Add the missing `await`, or remove `async` from the function",
+      );
+    }
+  }
+
+  fn check_fn(&mut self, function: &Function, span: Span, name: Option<&str>) {
+    let is_empty = match &function.body {
+      Some(body) => body.stmts.is_empty(),
+      None => true,
+    };
+    self.check_function(function, function.is_async, is_empty, span, name);
+  }
+}
+
+impl Visit for RequireAwaitVisitor {
+  noop_visit_type!();
+
+  fn visit_await_expr(&mut self, await_expr: &AwaitExpr, parent: &dyn Node) {
+    self.mark_await();
+    swc_ecmascript::visit::visit_await_expr(self, await_expr, parent);
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, parent: &dyn Node) {
+    if for_of_stmt.await_token.is_some() {
+      self.mark_await();
+    }
+    swc_ecmascript::visit::visit_for_of_stmt(self, for_of_stmt, parent);
+  }
+
+  fn visit_yield_expr(&mut self, yield_expr: &YieldExpr, parent: &dyn Node) {
+    // `yield` in an async generator awaits the yielded value
+    self.mark_await();
+    swc_ecmascript::visit::visit_yield_expr(self, yield_expr, parent);
+  }
+
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, _parent: &dyn Node) {
+    self.check_fn(
+      &fn_decl.function,
+      fn_decl.ident.span,
+      Some(&fn_decl.ident.sym),
+    );
+  }
+
+  fn visit_fn_expr(&mut self, fn_expr: &FnExpr, _parent: &dyn Node) {
+    match &fn_expr.ident {
+      Some(ident) => {
+        self.check_fn(&fn_expr.function, ident.span, Some(&ident.sym))
+      }
+      None => self.check_fn(
+        &fn_expr.function,
+        keyword_span(fn_expr.function.span, "async"),
+        None,
+      ),
+    }
+  }
+
+  fn visit_class_method(
+    &mut self,
+    class_method: &ClassMethod,
+    parent: &dyn Node,
+  ) {
+    class_method.key.visit_with(parent, self);
+    let name = self.context.span_text(class_method.key.span());
+    self.check_fn(&class_method.function, class_method.key.span(), Some(&name));
+  }
+
+  fn visit_private_method(
+    &mut self,
+    private_method: &PrivateMethod,
+    _parent: &dyn Node,
+  ) {
+    let name = self.context.span_text(private_method.key.span);
+    self.check_fn(
+      &private_method.function,
+      private_method.key.span,
+      Some(&name),
+    );
+  }
+
+  fn visit_method_prop(&mut self, method_prop: &MethodProp, parent: &dyn Node) {
+    method_prop.key.visit_with(parent, self);
+    let name = self.context.span_text(method_prop.key.span());
+    self.check_fn(&method_prop.function, method_prop.key.span(), Some(&name));
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _parent: &dyn Node) {
+    let is_empty = match &arrow_expr.body {
+      BlockStmtOrExpr::BlockStmt(body) => body.stmts.is_empty(),
+      BlockStmtOrExpr::Expr(_) => false,
+    };
+    self.check_function(
+      arrow_expr,
+      arrow_expr.is_async,
+      is_empty,
+      keyword_span(arrow_expr.span, "async"),
+      None,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn require_await_valid() {
+    assert_lint_ok_n::<RequireAwait>(vec![
+      "async function foo() { await bar(); }",
+      "async function foo() {}",
+      "const foo = async () => {};",
+      "const foo = async () => await bar();",
+      "const foo = async function () { return await bar(); };",
+      "function foo() { bar(); }",
+      "const foo = () => bar();",
+      "async function foo() { for await (const a of b) {} }",
+      "async function* foo() { yield 1; }",
+      "async function foo() { if (a) { await bar(); } }",
+      "async function foo() { return bar(await baz()); }",
+      "const foo = { async bar() { await baz(); } };",
+      "await foo();",
+    ]);
+  }
+
+  #[test]
+  fn require_await_invalid() {
+    assert_lint_err::<RequireAwait>("async function foo() { bar(); }", 15);
+    assert_lint_err::<RequireAwait>("const foo = async () => bar();", 12);
+    assert_lint_err::<RequireAwait>(
+      "const foo = async (a) => { bar(a); };",
+      12,
+    );
+    assert_lint_err::<RequireAwait>(
+      "const foo = async function () { bar(); };",
+      12,
+    );
+    assert_lint_err::<RequireAwait>(
+      "const foo = async function baz() { bar(); };",
+      27,
+    );
+    assert_lint_err::<RequireAwait>("async function* foo() { bar(); }", 16);
+    assert_lint_err::<RequireAwait>("async function foo() { return bar; }", 15);
+
+    let diagnostics =
+      lint(RequireAwait::new(), "async function foo() { bar(); }");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Async function 'foo' has no 'await' expression"
+    );
+    assert_eq!(diagnostics[0].range.end.col, 18);
+
+    let diagnostics =
+      lint(RequireAwait::new(), "const foo = async () => bar();");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Async function has no 'await' expression"
+    );
+    assert_eq!(diagnostics[0].range.end.col, 17);
+  }
+
+  #[test]
+  fn require_await_nested_functions() {
+    assert_lint_err::<RequireAwait>(
+      "async function foo() { async function bar() { await baz(); } }",
+      15,
+    );
+    assert_lint_err::<RequireAwait>(
+      "async function foo() { return async () => { await bar(); }; }",
+      15,
+    );
+    assert_lint_err::<RequireAwait>(
+      "async function foo() { items.forEach(async (item) => { await bar(item); }); }",
+      15,
+    );
+    assert_lint_err::<RequireAwait>(
+      "async function foo() { await bar(); return async () => baz(); }",
+      43,
+    );
+    assert_lint_ok::<RequireAwait>(
+      "async function foo() { await bar(); function baz() { qux(); } }",
+    );
+    assert_lint_err_on_line_n::<RequireAwait>(
+      "async function foo() {\n  function bar() {\n    return async () => { await baz(); };\n  }\n  return bar;\n}",
+      vec![(1, 15)],
+    );
+  }
+
+  #[test]
+  fn require_await_class_methods() {
+    assert_lint_ok_n::<RequireAwait>(vec![
+      "class Foo { async bar() { await baz(); } }",
+      "class Foo { async bar() {} }",
+      "class Foo { bar() { baz(); } }",
+      "class Foo { async #bar() { await baz(); } }",
+      "class Foo { bar = async () => { await baz(); }; }",
+    ]);
+    assert_lint_err::<RequireAwait>("class Foo { async bar() { baz(); } }", 18);
+    assert_lint_err::<RequireAwait>(
+      "class Foo { static async bar() { baz(); } }",
+      25,
+    );
+    assert_lint_err::<RequireAwait>(
+      "class Foo { async #bar() { baz(); } }",
+      18,
+    );
+    assert_lint_err::<RequireAwait>(
+      "class Foo { bar = async () => { baz(); }; }",
+      18,
+    );
+    assert_lint_err::<RequireAwait>(
+      "const foo = { async bar() { baz(); } };",
+      20,
+    );
+
+    let diagnostics =
+      lint(RequireAwait::new(), "class Foo { async bar() { baz(); } }");
+    assert_eq!(
+      diagnostics[0].message,
+      "Async function 'bar' has no 'await' expression"
+    );
+  }
+
+  #[test]
+  fn require_await_promise_executor() {
+    assert_lint_err::<RequireAwait>(
+      "new Promise(async (resolve) => { resolve(1); });",
+      12,
+    );
+    assert_lint_ok::<RequireAwait>(
+      "new Promise(async (resolve) => { resolve(await foo()); });",
+    );
+    assert_lint_err::<RequireAwait>(
+      "async function foo() { return new Promise((resolve) => { setTimeout(resolve); }); }",
+      15,
+    );
+  }
+}
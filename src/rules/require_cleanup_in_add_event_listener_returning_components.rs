@@ -0,0 +1,822 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::{constant_string, member_path};
+use std::collections::HashSet;
+use std::sync::Arc;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  ArrowExpr, BlockStmtOrExpr, CallExpr, Expr, ExprOrSpread, ExprOrSuper,
+  FnExpr, Function, ImportSpecifier, Module, ModuleDecl, ModuleItem, NewExpr,
+  Pat, Prop, PropName, PropOrSpread, ReturnStmt, VarDeclarator,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::{Visit, VisitWith};
+
+#[derive(Default)]
+pub struct RequireCleanupInAddEventListenerReturningComponentsOptions {
+  /// Requires the cleanup function to undo every registration of the
+  /// effect, instead of only requiring a cleanup function to be returned.
+  pub strict: bool,
+}
+
+/// Reports event listeners, intervals and observers registered in a
+/// `useEffect()` or `useLayoutEffect()` callback which doesn't return a
+/// cleanup function, since they leak when the component is unmounted, eg.
+/// across navigations between islands.
+///
+/// Only the hooks imported from Preact or React are checked, under any
+/// local name. Listeners registered with an abort `signal` are cleaned up
+/// through their `AbortController` and are never reported.
+pub struct RequireCleanupInAddEventListenerReturningComponents {
+  options: RequireCleanupInAddEventListenerReturningComponentsOptions,
+}
+
+impl RequireCleanupInAddEventListenerReturningComponents {
+  pub fn with_options(
+    options: RequireCleanupInAddEventListenerReturningComponentsOptions,
+  ) -> Box<Self> {
+    Box::new(RequireCleanupInAddEventListenerReturningComponents { options })
+  }
+}
+
+impl LintRule for RequireCleanupInAddEventListenerReturningComponents {
+  fn new() -> Box<Self> {
+    Self::with_options(
+      RequireCleanupInAddEventListenerReturningComponentsOptions::default(),
+    )
+  }
+
+  fn code(&self) -> &'static str {
+    "require-cleanup-in-add-event-listener-returning-components"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires `useEffect()` and `useLayoutEffect()` callbacks which register event listeners, intervals or observers to return a cleanup function.
+
+Registrations which are never undone leak when the component is unmounted, eg. across navigations between islands. Only the hooks imported from Preact or React are checked, under any local name. Listeners registered with an abort `signal` are cleaned up through their `AbortController`.
+
+### Options
+
+`RequireCleanupInAddEventListenerReturningComponentsOptions::strict`: requires the cleanup function to undo every registration of the effect, instead of only requiring a cleanup function to be returned, `false` by default.
+
+### Valid:
+```typescript
+import { useEffect } from "preact/hooks";
+useEffect(() => {
+  addEventListener("resize", onResize);
+  return () => removeEventListener("resize", onResize);
+}, []);
+```
+
+### Invalid:
+```typescript
+import { useEffect } from "preact/hooks";
+useEffect(() => {
+  addEventListener("resize", onResize);
+}, []);
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = EffectVisitor {
+      context,
+      strict: self.options.strict,
+      hooks: HashSet::new(),
+      namespaces: HashSet::new(),
+    };
+    visitor.collect_imports(module);
+    if visitor.hooks.is_empty() && visitor.namespaces.is_empty() {
+      return;
+    }
+    visitor.visit_module(module, module);
+  }
+}
+
+const EFFECT_HOOKS: &[&str] = &["useEffect", "useLayoutEffect"];
+
+/// Checks if hooks are imported from `specifier`, eg. `preact/hooks`,
+/// `react` or a CDN URL of either.
+fn is_hooks_module(specifier: &str) -> bool {
+  let name =
+    if specifier.starts_with("http:") || specifier.starts_with("https:") {
+      match specifier
+        .find("/preact")
+        .or_else(|| specifier.find("/react"))
+      {
+        Some(index) => &specifier[index + 1..],
+        None => return false,
+      }
+    } else {
+      specifier
+    };
+  let package = name.split(&['/', '@'][..]).next().unwrap_or("");
+  package == "preact" || package == "react"
+}
+
+struct EffectVisitor {
+  context: Arc<Context>,
+  strict: bool,
+  /// Local bindings of the effect hooks.
+  hooks: HashSet<Id>,
+  /// Local bindings of namespace and default imports of the hooks modules.
+  namespaces: HashSet<Id>,
+}
+
+impl EffectVisitor {
+  fn collect_imports(&mut self, module: &Module) {
+    for item in &module.body {
+      let import = match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => import,
+        _ => continue,
+      };
+      if import.type_only || !is_hooks_module(&import.src.value) {
+        continue;
+      }
+      for specifier in &import.specifiers {
+        match specifier {
+          ImportSpecifier::Named(named) => {
+            let imported = named.imported.as_ref().unwrap_or(&named.local);
+            if EFFECT_HOOKS.contains(&&*imported.sym) {
+              self.hooks.insert(named.local.to_id());
+            }
+          }
+          ImportSpecifier::Default(default) => {
+            self.namespaces.insert(default.local.to_id());
+          }
+          ImportSpecifier::Namespace(namespace) => {
+            self.namespaces.insert(namespace.local.to_id());
+          }
+        }
+      }
+    }
+  }
+
+  fn is_effect_hook(&self, call_expr: &CallExpr) -> bool {
+    let callee = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => callee,
+      ExprOrSuper::Super(_) => return false,
+    };
+    match member_path(callee) {
+      (Expr::Ident(ident), path) if path.is_empty() => {
+        self.hooks.contains(&ident.to_id())
+      }
+      (Expr::Ident(ident), path) if path.len() == 1 => {
+        self.namespaces.contains(&ident.to_id())
+          && EFFECT_HOOKS.contains(&path[0].as_str())
+      }
+      _ => false,
+    }
+  }
+
+  fn check_effect(&self, callback: &Expr) {
+    let mut returns = ReturnCollector::default();
+    match unwrap_paren(callback) {
+      Expr::Arrow(arrow) => match &arrow.body {
+        BlockStmtOrExpr::BlockStmt(body) => {
+          body.visit_children_with(&mut returns)
+        }
+        BlockStmtOrExpr::Expr(expr) => returns.returned(expr),
+      },
+      Expr::Fn(fn_expr) => {
+        if let Some(body) = &fn_expr.function.body {
+          body.visit_children_with(&mut returns);
+        }
+      }
+      _ => return,
+    }
+
+    let mut registrations = RegistrationCollector {
+      context: &self.context,
+      cleanups: &returns.cleanups,
+      observers: HashSet::new(),
+      registrations: vec![],
+      removals: Removals::default(),
+    };
+    callback.visit_children_with(&mut registrations);
+
+    for registration in &registrations.registrations {
+      if !returns.has_cleanup {
+        let (message, hint) = match registration.kind {
+          Kind::Listener => (
+            "Event listener is registered in an effect which doesn't return a cleanup function",
+            "Return a function from the effect which calls `removeEventListener()`, or pass an abort `signal`",
+          ),
+          Kind::Interval => (
+            "Interval is started in an effect which doesn't return a cleanup function",
+            "Return a function from the effect which calls `clearInterval()`",
+          ),
+          Kind::Observer => (
+            "Observer is started in an effect which doesn't return a cleanup function",
+            "Return a function from the effect which calls `disconnect()` on the observer",
+          ),
+        };
+        self.context.add_diagnostic_with_hint(
+          registration.span,
+          "require-cleanup-in-add-event-listener-returning-components",
+          message,
+          hint,
+        );
+      } else if self.strict
+        && !returns.opaque
+        && !registrations.removals.undoes(registration)
+      {
+        let (message, hint) = match registration.kind {
+          Kind::Listener => (
+            "Event listener registered in this effect isn't removed by its cleanup function",
+            format!(
+              "Call `{}.removeEventListener({})` in the cleanup function",
+              registration.receiver,
+              registration
+                .event
+                .as_ref()
+                .map(|event| format!("{:?}, ...", event))
+                .unwrap_or_else(|| "...".to_string())
+            ),
+          ),
+          Kind::Interval => (
+            "Interval started in this effect isn't cleared by its cleanup function",
+            "Call `clearInterval()` in the cleanup function".to_string(),
+          ),
+          Kind::Observer => (
+            "Observer started in this effect isn't disconnected by its cleanup function",
+            format!(
+              "Call `{}.disconnect()` in the cleanup function",
+              registration.receiver
+            ),
+          ),
+        };
+        self.context.add_diagnostic_with_hint(
+          registration.span,
+          "require-cleanup-in-add-event-listener-returning-components",
+          message,
+          &hint,
+        );
+      }
+    }
+  }
+}
+
+impl Visit for EffectVisitor {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if self.is_effect_hook(call_expr) {
+      if let Some(ExprOrSpread { spread: None, expr }) = call_expr.args.first()
+      {
+        self.check_effect(expr);
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+/// Collects what an effect callback returns, not counting the returns of
+/// nested functions.
+#[derive(Default)]
+struct ReturnCollector {
+  has_cleanup: bool,
+  /// The returned cleanup function could not be looked into, eg. it's
+  /// defined elsewhere.
+  opaque: bool,
+  /// Spans of the returned function expressions.
+  cleanups: HashSet<Span>,
+}
+
+impl ReturnCollector {
+  fn returned(&mut self, expr: &Expr) {
+    match unwrap_paren(expr) {
+      Expr::Arrow(ArrowExpr { span, .. })
+      | Expr::Fn(FnExpr {
+        function: Function { span, .. },
+        ..
+      }) => {
+        self.has_cleanup = true;
+        self.cleanups.insert(*span);
+      }
+      Expr::Seq(seq) => {
+        if let Some(last) = seq.exprs.last() {
+          self.returned(last);
+        }
+      }
+      Expr::Ident(ident) if ident.sym != *"undefined" => {
+        self.has_cleanup = true;
+        self.opaque = true;
+      }
+      _ => {}
+    }
+  }
+}
+
+impl Visit for ReturnCollector {
+  noop_visit_type!();
+
+  fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt, _: &dyn Node) {
+    if let Some(arg) = &return_stmt.arg {
+      self.returned(arg);
+    }
+  }
+
+  fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
+  fn visit_arrow_expr(&mut self, _: &ArrowExpr, _: &dyn Node) {}
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+  Listener,
+  Interval,
+  Observer,
+}
+
+struct Registration {
+  kind: Kind,
+  span: Span,
+  /// The source text of the receiver, with the global object as `window`.
+  receiver: String,
+  /// The event type of a listener, if it's a constant.
+  event: Option<String>,
+}
+
+/// What the cleanup functions of an effect undo.
+#[derive(Default)]
+struct Removals {
+  listeners: Vec<(String, Option<String>)>,
+  clears_interval: bool,
+  disconnected: HashSet<String>,
+}
+
+impl Removals {
+  fn undoes(&self, registration: &Registration) -> bool {
+    match registration.kind {
+      Kind::Listener => self.listeners.iter().any(|(receiver, event)| {
+        *receiver == registration.receiver
+          && (event.is_none()
+            || registration.event.is_none()
+            || *event == registration.event)
+      }),
+      Kind::Interval => self.clears_interval,
+      Kind::Observer => self.disconnected.contains(&registration.receiver),
+    }
+  }
+}
+
+/// Collects the registrations of an effect callback, including the ones in
+/// nested callbacks, and what its cleanup functions undo.
+struct RegistrationCollector<'a> {
+  context: &'a Context,
+  cleanups: &'a HashSet<Span>,
+  /// Bindings of observers created in the effect.
+  observers: HashSet<Id>,
+  registrations: Vec<Registration>,
+  removals: Removals,
+}
+
+/// Returns the method name and the receiver of a call, with calls of
+/// global functions having `window` as the receiver.
+fn method_call<'e>(
+  context: &Context,
+  call_expr: &'e CallExpr,
+) -> Option<(String, Option<&'e Expr>)> {
+  let callee = match &call_expr.callee {
+    ExprOrSuper::Expr(callee) => unwrap_paren(callee),
+    ExprOrSuper::Super(_) => return None,
+  };
+  match callee {
+    Expr::Ident(ident) => Some((ident.sym.to_string(), None)),
+    Expr::Member(member_expr) if !member_expr.computed => {
+      match (&member_expr.obj, &*member_expr.prop) {
+        (ExprOrSuper::Expr(obj), Expr::Ident(prop)) => {
+          let obj = unwrap_paren(obj);
+          let is_global_object = match obj {
+            Expr::Ident(ident) => ["window", "globalThis", "self"]
+              .iter()
+              .any(|name| context.is_global(ident, name)),
+            _ => false,
+          };
+          if is_global_object {
+            Some((prop.sym.to_string(), None))
+          } else {
+            Some((prop.sym.to_string(), Some(obj)))
+          }
+        }
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+fn is_observer(new_expr: &NewExpr) -> bool {
+  match &*new_expr.callee {
+    Expr::Ident(ident) => ident.sym.ends_with("Observer"),
+    _ => false,
+  }
+}
+
+/// Checks if listener options are an object literal with a `signal`.
+fn has_signal(options: Option<&ExprOrSpread>) -> bool {
+  let obj_lit = match options.map(|arg| unwrap_paren(&arg.expr)) {
+    Some(Expr::Object(obj_lit)) => obj_lit,
+    _ => return false,
+  };
+  obj_lit.props.iter().any(|prop| match prop {
+    PropOrSpread::Prop(prop) => match &**prop {
+      Prop::Shorthand(ident) => ident.sym == *"signal",
+      Prop::KeyValue(key_value) => match &key_value.key {
+        PropName::Ident(ident) => ident.sym == *"signal",
+        PropName::Str(s) => s.value == *"signal",
+        _ => false,
+      },
+      _ => false,
+    },
+    PropOrSpread::Spread(_) => false,
+  })
+}
+
+impl<'a> RegistrationCollector<'a> {
+  fn receiver(&self, receiver: Option<&Expr>) -> String {
+    match receiver {
+      Some(receiver) => self.context.span_text(receiver.span()).to_string(),
+      None => "window".to_string(),
+    }
+  }
+
+  fn is_observer_receiver(&self, receiver: Option<&Expr>) -> bool {
+    match receiver {
+      Some(Expr::Ident(ident)) => self.observers.contains(&ident.to_id()),
+      Some(Expr::New(new_expr)) => is_observer(new_expr),
+      _ => false,
+    }
+  }
+
+  fn register(&mut self, call_expr: &CallExpr) {
+    let (method, receiver) = match method_call(self.context, call_expr) {
+      Some(method_call) => method_call,
+      None => return,
+    };
+    let kind = match method.as_str() {
+      "addEventListener" => {
+        if has_signal(call_expr.args.get(2)) {
+          return;
+        }
+        Kind::Listener
+      }
+      "setInterval" if receiver.is_none() => Kind::Interval,
+      "observe" if self.is_observer_receiver(receiver) => Kind::Observer,
+      _ => return,
+    };
+    self.registrations.push(Registration {
+      kind,
+      span: call_expr.span,
+      receiver: self.receiver(receiver),
+      event: call_expr
+        .args
+        .first()
+        .and_then(|arg| constant_string(&arg.expr)),
+    });
+  }
+
+  fn remove(&mut self, call_expr: &CallExpr) {
+    let (method, receiver) = match method_call(self.context, call_expr) {
+      Some(method_call) => method_call,
+      None => return,
+    };
+    match method.as_str() {
+      "removeEventListener" => {
+        let event = call_expr
+          .args
+          .first()
+          .and_then(|arg| constant_string(&arg.expr));
+        self
+          .removals
+          .listeners
+          .push((self.receiver(receiver), event));
+      }
+      "clearInterval" if receiver.is_none() => {
+        self.removals.clears_interval = true
+      }
+      "disconnect" | "unobserve" if receiver.is_some() => {
+        self.removals.disconnected.insert(self.receiver(receiver));
+      }
+      _ => {}
+    }
+  }
+}
+
+impl<'a> Visit for RegistrationCollector<'a> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    self.register(call_expr);
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+
+  fn visit_var_declarator(
+    &mut self,
+    declarator: &VarDeclarator,
+    parent: &dyn Node,
+  ) {
+    if let (Pat::Ident(ident), Some(init)) =
+      (&declarator.name, &declarator.init)
+    {
+      if let Expr::New(new_expr) = unwrap_paren(init) {
+        if is_observer(new_expr) {
+          self.observers.insert(ident.to_id());
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_var_declarator(self, declarator, parent);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow: &ArrowExpr, parent: &dyn Node) {
+    if self.cleanups.contains(&arrow.span) {
+      arrow.visit_children_with(&mut CleanupCollector(self));
+    } else {
+      swc_ecmascript::visit::visit_arrow_expr(self, arrow, parent);
+    }
+  }
+
+  fn visit_function(&mut self, function: &Function, parent: &dyn Node) {
+    if self.cleanups.contains(&function.span) {
+      function.visit_children_with(&mut CleanupCollector(self));
+    } else {
+      swc_ecmascript::visit::visit_function(self, function, parent);
+    }
+  }
+}
+
+/// Collects what a cleanup function undoes. Registrations in it are not
+/// counted.
+struct CleanupCollector<'c, 'a>(&'c mut RegistrationCollector<'a>);
+
+impl<'c, 'a> Visit for CleanupCollector<'c, 'a> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    self.0.remove(call_expr);
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn strict() -> Box<RequireCleanupInAddEventListenerReturningComponents> {
+    RequireCleanupInAddEventListenerReturningComponents::with_options(
+      RequireCleanupInAddEventListenerReturningComponentsOptions {
+        strict: true,
+      },
+    )
+  }
+
+  #[test]
+  fn require_cleanup_valid() {
+    assert_lint_ok_n::<RequireCleanupInAddEventListenerReturningComponents>(
+      vec![
+        r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  addEventListener("resize", onResize);
+  return () => removeEventListener("resize", onResize);
+}, []);"#,
+        r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  const id = setInterval(tick, 1000);
+  return function () {
+    clearInterval(id);
+  };
+});"#,
+        r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  const controller = new AbortController();
+  window.addEventListener("resize", onResize, { signal: controller.signal });
+  return () => controller.abort();
+}, []);"#,
+        r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  const controller = new AbortController();
+  const { signal } = controller;
+  window.addEventListener("resize", onResize, { signal });
+}, []);"#,
+        r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  el.addEventListener("click", onClick);
+  return cleanup;
+});"#,
+        r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  document.title = title;
+}, [title]);"#,
+        // Not the hook from Preact or React.
+        r#"import { useEffect } from "./hooks.ts";
+useEffect(() => {
+  addEventListener("resize", onResize);
+});"#,
+        r#"function useEffect(f) { f(); }
+useEffect(() => {
+  addEventListener("resize", onResize);
+});"#,
+        r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  setTimeout(tick, 1000);
+  const observer = { observe() {} };
+  observer.observe(el);
+});"#,
+      ],
+    );
+  }
+
+  #[test]
+  fn require_cleanup_invalid() {
+    assert_lint_err_on_line::<
+      RequireCleanupInAddEventListenerReturningComponents,
+    >(
+      r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  addEventListener("resize", onResize);
+}, []);"#,
+      3,
+      2,
+    );
+    // Aliased imports.
+    assert_lint_err_on_line::<
+      RequireCleanupInAddEventListenerReturningComponents,
+    >(
+      r#"import { useLayoutEffect as useIsomorphicEffect } from "https://esm.sh/preact@10.5.7/hooks";
+useIsomorphicEffect(function () {
+  ref.current.addEventListener("click", onClick);
+});"#,
+      3,
+      2,
+    );
+    assert_lint_err_on_line::<
+      RequireCleanupInAddEventListenerReturningComponents,
+    >(
+      r#"import React from "react";
+React.useEffect(() => {
+  const id = setInterval(tick, 1000);
+});"#,
+      3,
+      13,
+    );
+    assert_lint_err_on_line::<
+      RequireCleanupInAddEventListenerReturningComponents,
+    >(
+      r#"import * as hooks from "preact/hooks";
+hooks.useEffect(() => {
+  const observer = new MutationObserver(onChange);
+  observer.observe(el, { childList: true });
+});"#,
+      4,
+      2,
+    );
+    // Registrations in nested callbacks count.
+    assert_lint_err_on_line::<
+      RequireCleanupInAddEventListenerReturningComponents,
+    >(
+      r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  load().then(() => {
+    addEventListener("message", onMessage);
+  });
+}, []);"#,
+      4,
+      4,
+    );
+    // Returning `undefined` isn't a cleanup function.
+    assert_lint_err_on_line::<
+      RequireCleanupInAddEventListenerReturningComponents,
+    >(
+      r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  addEventListener("resize", onResize);
+  return undefined;
+}, []);"#,
+      3,
+      2,
+    );
+    // The expression body is the registration, not a cleanup.
+    assert_lint_err_on_line::<
+      RequireCleanupInAddEventListenerReturningComponents,
+    >(
+      r#"import { useEffect } from "preact/hooks";
+useEffect(() => addEventListener("resize", onResize), []);"#,
+      2,
+      16,
+    );
+
+    let diagnostics = lint_tsx(
+      RequireCleanupInAddEventListenerReturningComponents::new(),
+      r#"import { useEffect } from "preact/hooks";
+export default function Island() {
+  useEffect(() => {
+    setInterval(tick, 1000);
+  });
+  return <div />;
+}"#,
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Interval is started in an effect which doesn't return a cleanup function"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Return a function from the effect which calls `clearInterval()`")
+    );
+  }
+
+  #[test]
+  fn require_cleanup_returns_function() {
+    // Arrow expression bodies returning an arrow are cleanups too.
+    assert_lint_ok_with(
+      RequireCleanupInAddEventListenerReturningComponents::new(),
+      r#"import { useEffect } from "preact/hooks";
+useEffect(() => (
+  addEventListener("resize", onResize),
+  () => removeEventListener("resize", onResize)
+));
+useEffect(() => {
+  if (!el) return;
+  el.addEventListener("click", onClick);
+  return () => el.removeEventListener("click", onClick);
+});"#,
+    );
+  }
+
+  #[test]
+  fn require_cleanup_strict() {
+    // A cleanup removing a different listener is enough by default.
+    let source = r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  addEventListener("resize", onResize);
+  addEventListener("scroll", onScroll);
+  return () => removeEventListener("resize", onResize);
+}, []);"#;
+    assert_lint_ok_with(
+      RequireCleanupInAddEventListenerReturningComponents::new(),
+      source,
+    );
+    assert_lint_err_on_line_n_with(strict(), source, vec![(4, 2)]);
+
+    let diagnostics = lint(strict(), source);
+    assert_eq!(
+      diagnostics[0].message,
+      "Event listener registered in this effect isn't removed by its cleanup function"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Call `window.removeEventListener(\"scroll\", ...)` in the cleanup function")
+    );
+
+    assert_lint_ok_with(
+      strict(),
+      r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  window.addEventListener("resize", onResize);
+  const id = setInterval(tick, 1000);
+  const observer = new ResizeObserver(onResize);
+  observer.observe(el);
+  return () => {
+    globalThis.removeEventListener("resize", onResize);
+    clearInterval(id);
+    observer.disconnect();
+  };
+}, []);"#,
+    );
+    assert_lint_ok_with(
+      strict(),
+      r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  el.addEventListener("click", onClick);
+  return cleanup;
+});"#,
+    );
+    assert_lint_err_on_line_n_with(
+      strict(),
+      r#"import { useEffect } from "preact/hooks";
+useEffect(() => {
+  el.addEventListener("click", onClick);
+  const observer = new IntersectionObserver(onIntersect);
+  observer.observe(el);
+  return () => other.removeEventListener("click", onClick);
+});"#,
+      vec![(3, 2), (5, 2)],
+    );
+  }
+}
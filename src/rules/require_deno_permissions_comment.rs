@@ -0,0 +1,479 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::deno_api_util::{required_permissions, Permission};
+use crate::swc_util::member_path;
+use std::collections::HashMap;
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSuper, Module, NewExpr, ObjectPatProp, Pat, PropName,
+  VarDecl, VarDeclKind,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::{Visit, VisitWith};
+
+pub struct RequireDenoPermissionsCommentOptions {
+  /// The text which starts an annotation comment. It's followed by the
+  /// names of the permissions, separated by commas, and then the reason,
+  /// eg. `// perm: read, write - caches the downloads`.
+  pub prefix: String,
+}
+
+impl Default for RequireDenoPermissionsCommentOptions {
+  fn default() -> Self {
+    Self {
+      prefix: "perm:".to_string(),
+    }
+  }
+}
+
+/// Requires calls of permission-gated `Deno` APIs, like
+/// `Deno.readTextFile()` or `new Deno.Command()`, to be annotated with a
+/// comment naming the permissions they need, like `// perm: read`, on the
+/// line before the call or on its first or last line. `const` aliases of
+/// the APIs, like `const run = Deno.run;`, are followed one level.
+pub struct RequireDenoPermissionsComment {
+  options: RequireDenoPermissionsCommentOptions,
+}
+
+impl RequireDenoPermissionsComment {
+  pub fn with_options(
+    options: RequireDenoPermissionsCommentOptions,
+  ) -> Box<Self> {
+    Box::new(RequireDenoPermissionsComment { options })
+  }
+}
+
+impl LintRule for RequireDenoPermissionsComment {
+  fn new() -> Box<Self> {
+    Self::with_options(RequireDenoPermissionsCommentOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "require-deno-permissions-comment"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires calls of permission-gated `Deno` APIs, like `Deno.readTextFile()` or `new Deno.Command()`, to be annotated with a comment naming the permissions they need.
+
+The comment goes on the line before the call, or on its first or last line, and names the permissions followed by the reason, eg. `// perm: read, write - caches the downloads`. `const` aliases of the APIs are followed one level.
+
+### Options
+
+`RequireDenoPermissionsCommentOptions::prefix`: the text which starts an annotation comment, `"perm:"` by default.
+
+### Valid:
+```typescript
+// perm: read - loads the config
+const text = await Deno.readTextFile("config.json");
+```
+
+### Invalid:
+```typescript
+const text = await Deno.readTextFile("config.json");
+```
+```typescript
+// perm: read - starts git
+const command = new Deno.Command("git");
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let annotations = annotations(&context, &self.options.prefix);
+    let mut aliases = AliasCollector {
+      context: &context,
+      aliases: HashMap::new(),
+    };
+    module.visit_with(module, &mut aliases);
+    let mut visitor = RequireDenoPermissionsCommentVisitor {
+      context: context.clone(),
+      prefix: &self.options.prefix,
+      aliases: aliases.aliases,
+      annotations,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+/// A comment starting with the prefix.
+struct Annotation {
+  start_line: usize,
+  end_line: usize,
+  /// The permission names at the start of the comment, which may not be
+  /// valid permissions.
+  names: Vec<String>,
+}
+
+fn annotations(context: &Context, prefix: &str) -> Vec<Annotation> {
+  context
+    .all_comments()
+    .filter_map(|comment| {
+      let text = comment.text.trim_start().strip_prefix(prefix)?;
+      let names = text
+        .split(',')
+        .map_while(|part| {
+          let name = part.split_whitespace().next()?;
+          if name.chars().all(|c| c.is_ascii_lowercase()) {
+            Some(name.to_string())
+          } else {
+            None
+          }
+        })
+        .collect();
+      Some(Annotation {
+        start_line: context.source_map.lookup_char_pos(comment.span.lo()).line,
+        end_line: context.source_map.lookup_char_pos(comment.span.hi()).line,
+        names,
+      })
+    })
+    .collect()
+}
+
+/// Follows `const` bindings one level:
+///
+/// - `const run = Deno.run;` and `const env = Deno.env;`
+/// - `const { readTextFile } = Deno;` and `const { get } = Deno.env;`
+struct AliasCollector<'a> {
+  context: &'a Context,
+  /// Maps bindings to the member path in the `Deno` namespace they alias.
+  aliases: HashMap<Id, Vec<String>>,
+}
+
+impl AliasCollector<'_> {
+  fn deno_path(&self, expr: &Expr) -> Option<Vec<String>> {
+    match member_path(expr) {
+      (Expr::Ident(ident), path) if self.context.is_global(ident, "Deno") => {
+        Some(path)
+      }
+      _ => None,
+    }
+  }
+}
+
+impl Visit for AliasCollector<'_> {
+  noop_visit_type!();
+
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, parent: &dyn Node) {
+    if var_decl.kind == VarDeclKind::Const {
+      for decl in &var_decl.decls {
+        let path = match decl.init.as_ref().and_then(|i| self.deno_path(i)) {
+          Some(path) => path,
+          None => continue,
+        };
+        match &decl.name {
+          Pat::Ident(ident) if !path.is_empty() => {
+            self.aliases.insert(ident.to_id(), path);
+          }
+          Pat::Object(object) => {
+            for prop in &object.props {
+              let (key, binding) = match prop {
+                ObjectPatProp::Assign(assign) if assign.value.is_none() => {
+                  (assign.key.sym.to_string(), assign.key.to_id())
+                }
+                ObjectPatProp::KeyValue(kv) => match (&kv.key, &*kv.value) {
+                  (PropName::Ident(key), Pat::Ident(value)) => {
+                    (key.sym.to_string(), value.to_id())
+                  }
+                  (PropName::Str(key), Pat::Ident(value)) => {
+                    (key.value.to_string(), value.to_id())
+                  }
+                  _ => continue,
+                },
+                _ => continue,
+              };
+              let mut alias = path.clone();
+              alias.push(key);
+              self.aliases.insert(binding, alias);
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_var_decl(self, var_decl, parent);
+  }
+}
+
+struct RequireDenoPermissionsCommentVisitor<'a> {
+  context: Arc<Context>,
+  prefix: &'a str,
+  aliases: HashMap<Id, Vec<String>>,
+  annotations: Vec<Annotation>,
+}
+
+fn list(permissions: &[Permission]) -> String {
+  let names: Vec<String> = permissions
+    .iter()
+    .map(|permission| format!("`{}`", permission.name()))
+    .collect();
+  match names.split_last() {
+    Some((last, rest)) if !rest.is_empty() => {
+      format!("{} and {}", rest.join(", "), last)
+    }
+    _ => names.join(""),
+  }
+}
+
+impl RequireDenoPermissionsCommentVisitor<'_> {
+  /// Returns the member path of the callee in the `Deno` namespace, going
+  /// through aliases.
+  fn deno_path(&self, callee: &Expr) -> Option<Vec<String>> {
+    match member_path(callee) {
+      (Expr::Ident(ident), path) if self.context.is_global(ident, "Deno") => {
+        Some(path)
+      }
+      (Expr::Ident(ident), path) => {
+        let mut alias = self.aliases.get(&ident.to_id())?.clone();
+        alias.extend(path);
+        Some(alias)
+      }
+      _ => None,
+    }
+  }
+
+  fn check(&self, span: Span, callee: &Expr) {
+    let path = match self.deno_path(callee) {
+      Some(path) => path,
+      None => return,
+    };
+    let permissions = match required_permissions(&path) {
+      Some(permissions) => permissions,
+      None => return,
+    };
+
+    let first_line = self.context.source_map.lookup_char_pos(span.lo()).line;
+    let last_line = self.context.source_map.lookup_char_pos(span.hi()).line;
+    let adjacent: Vec<&Annotation> = self
+      .annotations
+      .iter()
+      .filter(|annotation| {
+        annotation.end_line + 1 == first_line
+          || annotation.start_line == first_line
+          || annotation.start_line == last_line
+      })
+      .collect();
+    let covers = |annotation: &&Annotation| {
+      permissions.iter().all(|permission| {
+        annotation
+          .names
+          .iter()
+          .any(|name| Permission::from_name(name) == Some(*permission))
+      })
+    };
+    if adjacent.iter().any(covers) {
+      return;
+    }
+
+    let api = format!("Deno.{}", path.join("."));
+    let example = format!(
+      "// {} {}",
+      self.prefix,
+      permissions
+        .iter()
+        .map(|permission| permission.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+    );
+    let plural = if permissions.len() > 1 { "s" } else { "" };
+    match adjacent.first() {
+      None => self.context.add_diagnostic_with_hint(
+        span,
+        "require-deno-permissions-comment",
+        &format!(
+          "`{}` needs the {} permission{}, which should be documented",
+          api,
+          list(permissions),
+          plural
+        ),
+        &format!(
+          "Add `{}` with the reason on the line before the call",
+          example
+        ),
+      ),
+      Some(annotation) => {
+        let cited = if annotation.names.is_empty() {
+          "no permission".to_string()
+        } else {
+          annotation
+            .names
+            .iter()
+            .map(|name| format!("`{}`", name))
+            .collect::<Vec<_>>()
+            .join(", ")
+        };
+        self.context.add_diagnostic_with_hint(
+          span,
+          "require-deno-permissions-comment",
+          &format!(
+            "The permission comment of `{}` cites {}, but it needs the {} permission{}",
+            api,
+            cited,
+            list(permissions),
+            plural
+          ),
+          &format!("Change the comment to `{}`", example),
+        );
+      }
+    }
+  }
+}
+
+impl Visit for RequireDenoPermissionsCommentVisitor<'_> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      self.check(call_expr.span, callee);
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+    self.check(new_expr.span, &new_expr.callee);
+    swc_ecmascript::visit::visit_new_expr(self, new_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn require_deno_permissions_comment_valid() {
+    assert_lint_ok_n::<RequireDenoPermissionsComment>(vec![
+      "// perm: read - loads the config\nconst text = await Deno.readTextFile('config.json');",
+      "const text = await Deno.readTextFile('config.json'); // perm: read",
+      "/* perm: env */ const home = Deno.env.get('HOME');",
+      "// perm: read, write\nawait Deno.copyFile('a', 'b');",
+      "// perm: run\nconst command = new Deno.Command('git', { args: ['status'] });",
+      // A comment before the first line of a multi-line call counts.
+      "// perm: net\nconst conn = await Deno.connect({\n  hostname: 'example.com',\n  port: 80,\n});",
+      "await Deno.writeTextFile(\n  'out.txt',\n  text,\n); // perm: write",
+      // Not permission-gated.
+      "Deno.inspect(value);",
+      "Deno.exit(1);",
+      // `Deno` is shadowed.
+      "const Deno = { readTextFile() {} };\nDeno.readTextFile('a');",
+      "function f(Deno) { Deno.run({ cmd: ['ls'] }); }",
+      // Aliases are only followed one level.
+      "const run = Deno.run;\nconst spawn = run;\nspawn({ cmd: ['ls'] });",
+      "// perm: run\nconst run = Deno.run;\n// perm: run\nrun({ cmd: ['ls'] });",
+    ]);
+  }
+
+  #[test]
+  fn require_deno_permissions_comment_invalid() {
+    assert_lint_err::<RequireDenoPermissionsComment>(
+      "const text = await Deno.readTextFile('config.json');",
+      19,
+    );
+    assert_lint_err_on_line::<RequireDenoPermissionsComment>(
+      "// perm: read\n\nconst text = await Deno.readTextFile('config.json');",
+      3,
+      19,
+    );
+    assert_lint_err::<RequireDenoPermissionsComment>(
+      "const command = new Deno.Command('git');",
+      16,
+    );
+    // The comment has to come before the first line.
+    assert_lint_err_on_line::<RequireDenoPermissionsComment>(
+      "const conn = await Deno.connect({\n  // perm: net\n  hostname: 'example.com',\n  port: 80,\n});",
+      1,
+      19,
+    );
+    assert_lint_err_on_line::<RequireDenoPermissionsComment>(
+      "const run = Deno.run;\nrun({ cmd: ['ls'] });",
+      2,
+      0,
+    );
+    assert_lint_err_on_line::<RequireDenoPermissionsComment>(
+      "const { get } = Deno.env;\nget('HOME');",
+      2,
+      0,
+    );
+    assert_lint_err_on_line::<RequireDenoPermissionsComment>(
+      "const { readTextFile: read } = Deno;\nawait read('a');",
+      2,
+      6,
+    );
+    assert_lint_err_on_line::<RequireDenoPermissionsComment>(
+      "const env = Deno.env;\nenv.set('A', 'b');",
+      2,
+      0,
+    );
+  }
+
+  #[test]
+  fn require_deno_permissions_comment_messages() {
+    let diagnostics = lint(
+      RequireDenoPermissionsComment::new(),
+      "const home = Deno.env.get('HOME');",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "`Deno.env.get` needs the `env` permission, which should be documented"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Add `// perm: env` with the reason on the line before the call")
+    );
+
+    let diagnostics = lint(
+      RequireDenoPermissionsComment::new(),
+      "// perm: read\nawait Deno.copyFile('a', 'b');",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "The permission comment of `Deno.copyFile` cites `read`, but it needs the `read` and `write` permissions"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Change the comment to `// perm: read, write`")
+    );
+
+    let diagnostics = lint(
+      RequireDenoPermissionsComment::new(),
+      "// perm: Needed for the config\nawait Deno.readTextFile('a');",
+    );
+    assert_eq!(
+      diagnostics[0].message,
+      "The permission comment of `Deno.readTextFile` cites no permission, but it needs the `read` permission"
+    );
+  }
+
+  #[test]
+  fn require_deno_permissions_comment_prefix() {
+    let rule = || {
+      RequireDenoPermissionsComment::with_options(
+        RequireDenoPermissionsCommentOptions {
+          prefix: "@permission".to_string(),
+        },
+      )
+    };
+    assert_lint_ok_with(
+      rule(),
+      "// @permission net\nconst conn = await Deno.connect({ port: 80 });",
+    );
+    let diagnostics =
+      lint(rule(), "// perm: net\nawait Deno.connect({ port: 80 });");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some(
+        "Add `// @permission net` with the reason on the line before the call"
+      )
+    );
+  }
+}
@@ -0,0 +1,394 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::constant_string;
+use crate::swc_util::member_path;
+use std::collections::HashMap;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSuper, Module, ObjectPatProp, Pat, PropName, VarDecl,
+  VarDeclKind,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+const KEY_METHODS: &[&str] = &["get", "set", "has", "delete"];
+
+pub struct RequireEnvVarPrefixOptions {
+  /// Prefix every environment variable has to start with, eg. `MYAPP_`.
+  pub required_prefix: Option<String>,
+  /// Names which don't need the prefix.
+  pub allow: Vec<String>,
+  /// Also reports `Deno.env.toObject()`, which reads all variables at once.
+  pub strict: bool,
+}
+
+impl Default for RequireEnvVarPrefixOptions {
+  fn default() -> Self {
+    Self {
+      required_prefix: None,
+      allow: vec![
+        "HOME".to_string(),
+        "PATH".to_string(),
+        "NO_COLOR".to_string(),
+      ],
+      strict: false,
+    }
+  }
+}
+
+/// Requires keys passed to `Deno.env` methods to be string literals, so the
+/// environment variables a program uses can be audited statically, and
+/// optionally to start with a common prefix.
+pub struct RequireEnvVarPrefix {
+  options: RequireEnvVarPrefixOptions,
+}
+
+impl RequireEnvVarPrefix {
+  pub fn with_options(options: RequireEnvVarPrefixOptions) -> Box<Self> {
+    Box::new(RequireEnvVarPrefix { options })
+  }
+}
+
+impl LintRule for RequireEnvVarPrefix {
+  fn new() -> Box<Self> {
+    Self::with_options(RequireEnvVarPrefixOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "require-env-var-prefix"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires keys passed to `Deno.env` methods to be string literals, and optionally to start with a common prefix.
+
+Literal keys let the environment variables a program uses be audited statically.
+
+### Options
+
+- `RequireEnvVarPrefixOptions::required_prefix`: the prefix every environment variable has to start with, eg. `MYAPP_`. Unset by default.
+- `RequireEnvVarPrefixOptions::allow`: names which don't need the prefix, `["HOME", "PATH", "NO_COLOR"]` by default.
+- `RequireEnvVarPrefixOptions::strict`: also reports `Deno.env.toObject()`, which reads all variables at once, `false` by default.
+
+### Valid:
+```typescript
+Deno.env.get("MYAPP_PORT");
+```
+
+### Invalid:
+```typescript
+Deno.env.get(key);
+```
+```typescript
+Deno.env.set(`MYAPP_${name}`, "1");
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut aliases = AliasCollector {
+      context: &context,
+      env_aliases: vec![],
+      method_aliases: HashMap::new(),
+    };
+    module.visit_with(module, &mut aliases);
+
+    let mut visitor = RequireEnvVarPrefixVisitor {
+      env_aliases: aliases.env_aliases,
+      method_aliases: aliases.method_aliases,
+      context: context.clone(),
+      options: &self.options,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+fn prop_name(key: &PropName) -> Option<&str> {
+  match key {
+    PropName::Ident(ident) => Some(&ident.sym),
+    PropName::Str(s) => Some(&s.value),
+    _ => None,
+  }
+}
+
+/// Checks if the expression is `Deno.env`, written out in full.
+fn is_deno_env(context: &Context, expr: &Expr) -> bool {
+  let (obj, path) = member_path(expr);
+  matches!(obj, Expr::Ident(ident) if context.is_global(ident, "Deno"))
+    && path == ["env"]
+}
+
+/// Follows `const` bindings one level:
+///
+/// - `const env = Deno.env;` and `const { env } = Deno;`
+/// - `const { get } = Deno.env;`
+struct AliasCollector<'a> {
+  context: &'a Context,
+  env_aliases: Vec<Id>,
+  /// Maps bindings to the `Deno.env` method they were destructured from.
+  method_aliases: HashMap<Id, String>,
+}
+
+impl Visit for AliasCollector<'_> {
+  noop_visit_type!();
+
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, parent: &dyn Node) {
+    if var_decl.kind == VarDeclKind::Const {
+      for decl in &var_decl.decls {
+        let init = match &decl.init {
+          Some(init) => &**init,
+          None => continue,
+        };
+        match &decl.name {
+          Pat::Ident(ident) if is_deno_env(self.context, init) => {
+            self.env_aliases.push(ident.to_id());
+          }
+          Pat::Object(object) => {
+            let from_deno = matches!(init, Expr::Ident(ident) if self.context.is_global(ident, "Deno"));
+            let from_env = is_deno_env(self.context, init);
+            for prop in &object.props {
+              let (key, binding) = match prop {
+                ObjectPatProp::Assign(assign) => {
+                  (assign.key.sym.to_string(), assign.key.to_id())
+                }
+                ObjectPatProp::KeyValue(kv) => match (&kv.key, &*kv.value) {
+                  (key, Pat::Ident(value)) => match prop_name(key) {
+                    Some(key) => (key.to_string(), value.to_id()),
+                    None => continue,
+                  },
+                  _ => continue,
+                },
+                ObjectPatProp::Rest(_) => continue,
+              };
+              if from_deno && key == "env" {
+                self.env_aliases.push(binding);
+              } else if from_env {
+                self.method_aliases.insert(binding, key);
+              }
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_var_decl(self, var_decl, parent);
+  }
+}
+
+struct RequireEnvVarPrefixVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a RequireEnvVarPrefixOptions,
+  env_aliases: Vec<Id>,
+  method_aliases: HashMap<Id, String>,
+}
+
+impl RequireEnvVarPrefixVisitor<'_> {
+  /// Returns the name of the `Deno.env` method called, if any.
+  fn env_method(&self, callee: &Expr) -> Option<String> {
+    match callee {
+      Expr::Ident(ident) => self.method_aliases.get(&ident.to_id()).cloned(),
+      Expr::Member(member) if !member.computed => {
+        let method = match &*member.prop {
+          Expr::Ident(prop) => prop.sym.to_string(),
+          _ => return None,
+        };
+        let obj = match &member.obj {
+          ExprOrSuper::Expr(obj) => &**obj,
+          ExprOrSuper::Super(_) => return None,
+        };
+        let is_env = match obj {
+          Expr::Ident(ident) => self.env_aliases.contains(&ident.to_id()),
+          _ => is_deno_env(&self.context, obj),
+        };
+        if is_env {
+          Some(method)
+        } else {
+          None
+        }
+      }
+      _ => None,
+    }
+  }
+
+  fn check_key(&self, key: &Expr) {
+    let name = match constant_string(key) {
+      Some(name) => name,
+      None => {
+        self.context.add_diagnostic_with_hint(
+          key.span(),
+          "require-env-var-prefix",
+          "Environment variable keys should be string literals",
+          "Use a literal key so the environment variables used can be audited",
+        );
+        return;
+      }
+    };
+
+    if let Some(prefix) = &self.options.required_prefix {
+      if !name.starts_with(prefix.as_str())
+        && !self.options.allow.contains(&name)
+      {
+        self.context.add_diagnostic_with_hint(
+          key.span(),
+          "require-env-var-prefix",
+          &format!(
+            "Environment variable `{}` is missing the `{}` prefix",
+            name, prefix
+          ),
+          &format!(
+            "Rename it to `{}{}` or add it to the allowed variables",
+            prefix, name
+          ),
+        );
+      }
+    }
+  }
+}
+
+impl Visit for RequireEnvVarPrefixVisitor<'_> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      match self.env_method(callee).as_deref() {
+        Some(method) if KEY_METHODS.contains(&method) => {
+          if let Some(key) = call_expr.args.first() {
+            self.check_key(&key.expr);
+          }
+        }
+        Some("toObject") if self.options.strict => {
+          self.context.add_diagnostic_with_hint(
+            call_expr.span,
+            "require-env-var-prefix",
+            "`Deno.env.toObject()` reads all environment variables, which can't be audited",
+            "Read the variables you need with `Deno.env.get()` instead",
+          );
+        }
+        _ => {}
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  fn with_prefix() -> Box<RequireEnvVarPrefix> {
+    RequireEnvVarPrefix::with_options(RequireEnvVarPrefixOptions {
+      required_prefix: Some("MYAPP_".to_string()),
+      ..Default::default()
+    })
+  }
+
+  #[test]
+  fn require_env_var_prefix_valid() {
+    assert_lint_ok_n::<RequireEnvVarPrefix>(vec![
+      r#"Deno.env.get("MYAPP_PORT");"#,
+      r#"Deno.env.set("FOO", "1");"#,
+      r#"Deno.env.has(`FOO`);"#,
+      r#"Deno.env.toObject();"#,
+      r#"const Deno = { env: new Map() }; Deno.env.get(key);"#,
+      r#"let env = Deno.env; env.get(key);"#,
+      r#"map.get(key);"#,
+    ]);
+  }
+
+  #[test]
+  fn require_env_var_prefix_invalid() {
+    assert_lint_err::<RequireEnvVarPrefix>("Deno.env.get(key);", 13);
+    assert_lint_err::<RequireEnvVarPrefix>(
+      r#"Deno.env.set(`MYAPP_${name}`, "1");"#,
+      13,
+    );
+    assert_lint_err::<RequireEnvVarPrefix>(
+      r#"Deno.env.has("MYAPP_" + name);"#,
+      13,
+    );
+    assert_lint_err::<RequireEnvVarPrefix>("Deno.env.delete(name);", 16);
+    assert_lint_err_on_line::<RequireEnvVarPrefix>(
+      "const env = Deno.env;\nenv.get(name);",
+      2,
+      8,
+    );
+    assert_lint_err_on_line::<RequireEnvVarPrefix>(
+      "const { env } = Deno;\nenv.get(name);",
+      2,
+      8,
+    );
+    assert_lint_err_on_line::<RequireEnvVarPrefix>(
+      "const { get: getEnv } = Deno.env;\ngetEnv(name);",
+      2,
+      7,
+    );
+  }
+
+  #[test]
+  fn require_env_var_prefix_required_prefix() {
+    for source in &[
+      r#"Deno.env.get("MYAPP_PORT");"#,
+      r#"Deno.env.get(`MYAPP_PORT`);"#,
+      r#"Deno.env.get("HOME");"#,
+      r#"Deno.env.get("NO_COLOR");"#,
+    ] {
+      assert_lint_ok_with(with_prefix(), source);
+    }
+
+    assert_lint_err_on_line_n_with(
+      with_prefix(),
+      r#"Deno.env.get("PORT"); Deno.env.set(`DEBUG`, "1");"#,
+      vec![(1, 13), (1, 35)],
+    );
+    let diagnostics = lint(with_prefix(), r#"Deno.env.get("PORT");"#);
+    assert_eq!(
+      diagnostics[0].message,
+      "Environment variable `PORT` is missing the `MYAPP_` prefix"
+    );
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Rename it to `MYAPP_PORT` or add it to the allowed variables")
+    );
+
+    let rule = RequireEnvVarPrefix::with_options(RequireEnvVarPrefixOptions {
+      required_prefix: Some("MYAPP_".to_string()),
+      allow: vec!["PORT".to_string()],
+      strict: false,
+    });
+    assert_lint_err_on_line_n_with(
+      rule,
+      r#"Deno.env.get("PORT"); Deno.env.get("HOME");"#,
+      vec![(1, 35)],
+    );
+  }
+
+  #[test]
+  fn require_env_var_prefix_strict() {
+    let rule = || {
+      RequireEnvVarPrefix::with_options(RequireEnvVarPrefixOptions {
+        strict: true,
+        ..Default::default()
+      })
+    };
+    assert_lint_ok_with(rule(), r#"Deno.env.get("PORT");"#);
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "const vars = Deno.env.toObject();",
+      vec![(1, 13)],
+    );
+    assert_lint_err_on_line_n_with(
+      rule(),
+      "const { toObject } = Deno.env;\ntoObject();",
+      vec![(2, 0)],
+    );
+  }
+}
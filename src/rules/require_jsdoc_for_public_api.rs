@@ -0,0 +1,599 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::swc_util::returns_value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use swc_common::comments::CommentKind;
+use swc_common::BytePos;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  BlockStmtOrExpr, Class, Decl, DefaultDecl, ExportSpecifier, Expr, Function,
+  Module, ModuleDecl, ModuleItem, Pat, Stmt, VarDeclKind,
+};
+use swc_ecmascript::utils::find_ids;
+use swc_ecmascript::utils::Id;
+
+/// Keywords which may come between a JSDoc comment and the declaration it
+/// documents.
+const MODIFIERS: &[&str] =
+  &["export", "default", "declare", "async", "abstract"];
+
+declare_rule! {
+  /// Reports exported functions, classes, interfaces, type aliases and
+  /// constants which have no `/** ... */` comment right before their
+  /// declaration. For names exported with `export { a }` the comment is
+  /// expected on the declaration of `a`, names re-exported from other modules
+  /// are documented there.
+  ///
+  /// Documented functions are also checked: their `@param` tags, if any,
+  /// must name the parameters in order (any name is accepted for destructured
+  /// parameters), and `@returns` is needed when the function returns a
+  /// value. For overloaded functions the comment belongs on the first
+  /// signature.
+  RequireJsdocForPublicApi, "require-jsdoc-for-public-api", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Requires exported functions, classes, interfaces, type aliases and constants to have a `/** ... */` comment right before their declaration.
+
+For names exported with `export { a }` the comment is expected on the declaration of `a`. Documented functions are also checked: their `@param` tags, if any, must name the parameters in order, and `@returns` is needed when the function returns a value. For overloaded functions the comment belongs on the first signature.
+
+### Valid:
+```typescript
+/**
+ * Adds two numbers.
+ * @param a The first number.
+ * @param b The second number.
+ * @returns The sum.
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+```
+```typescript
+function internal() {}
+```
+
+### Invalid:
+```typescript
+export function f() {}
+```
+```typescript
+/**
+ * Adds two numbers.
+ * @param b The first number.
+ */
+export function add(a: number, b: number) {}
+```"#
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut jsdocs: Vec<(Span, &str)> = context
+      .all_comments()
+      .filter(|comment| {
+        comment.kind == CommentKind::Block && comment.text.starts_with('*')
+      })
+      .map(|comment| (comment.span, &*comment.text))
+      .collect();
+    jsdocs.sort_by_key(|(span, _)| span.lo());
+
+    for declaration in public_declarations(module) {
+      match jsdoc_before(&context, &jsdocs, declaration.start) {
+        Some(jsdoc) => {
+          if let Some(function) = &declaration.function {
+            check_function_doc(&context, &declaration, function, jsdoc);
+          }
+        }
+        None => context.add_diagnostic_with_hint(
+          declaration.name_span,
+          "require-jsdoc-for-public-api",
+          &format!(
+            "Exported {} `{}` has no JSDoc comment",
+            declaration.kind, declaration.name
+          ),
+          "Add a `/** ... */` comment right before the declaration",
+        ),
+      }
+    }
+  }
+}
+
+/// A declaration which is part of the public API of the module.
+struct Declaration {
+  kind: &'static str,
+  name: String,
+  name_span: Span,
+  /// Start of the declaration, including its decorators.
+  start: BytePos,
+  function: Option<FunctionSignature>,
+}
+
+struct FunctionSignature {
+  /// Names of the parameters, `None` for destructured ones.
+  params: Vec<Option<String>>,
+  /// `None` for functions without a body, like overload signatures.
+  returns_value: Option<bool>,
+}
+
+impl FunctionSignature {
+  fn from_function(function: &Function) -> Self {
+    Self {
+      params: function
+        .params
+        .iter()
+        .map(|param| param_name(&param.pat))
+        .collect(),
+      returns_value: function.body.as_ref().map(returns_value),
+    }
+  }
+}
+
+fn param_name(pat: &Pat) -> Option<String> {
+  match pat {
+    Pat::Ident(ident) => Some(ident.sym.to_string()),
+    Pat::Assign(assign) => param_name(&assign.left),
+    Pat::Rest(rest) => param_name(&rest.arg),
+    _ => None,
+  }
+}
+
+/// Finds the JSDoc comment documenting the declaration starting at
+/// `start`: the last one before it, separated only by whitespace and
+/// modifiers like `export`.
+fn jsdoc_before<'a>(
+  context: &Context,
+  jsdocs: &[(Span, &'a str)],
+  start: BytePos,
+) -> Option<&'a str> {
+  let (span, text) =
+    jsdocs.iter().rev().find(|(span, _)| span.hi() <= start)?;
+  let between =
+    context.span_text(Span::new(span.hi(), start, Default::default()));
+  if between
+    .split_whitespace()
+    .all(|word| MODIFIERS.contains(&word))
+  {
+    Some(text)
+  } else {
+    None
+  }
+}
+
+fn check_function_doc(
+  context: &Context,
+  declaration: &Declaration,
+  function: &FunctionSignature,
+  jsdoc: &str,
+) {
+  let mut param_tags = vec![];
+  let mut has_returns = false;
+  for line in jsdoc.lines() {
+    let line = line.trim_start().trim_start_matches('*').trim_start();
+    if let Some(rest) = line.strip_prefix("@param") {
+      if let Some(name) = param_tag_name(rest) {
+        // `@param options.debug` documents a property of a parameter.
+        if !name.contains('.') {
+          param_tags.push(name);
+        }
+      }
+    } else if line.starts_with("@return") {
+      has_returns = true;
+    }
+  }
+
+  let add_diagnostic = |message: String| {
+    context.add_diagnostic(
+      declaration.name_span,
+      "require-jsdoc-for-public-api",
+      &message,
+    )
+  };
+
+  if !param_tags.is_empty() {
+    for (index, param) in function.params.iter().enumerate() {
+      match (param_tags.get(index), param) {
+        (Some(tag), Some(param)) if tag != param => add_diagnostic(format!(
+          "JSDoc `@param {}` of `{}` doesn't match the parameter `{}`",
+          tag, declaration.name, param
+        )),
+        (None, Some(param)) => add_diagnostic(format!(
+          "JSDoc of `{}` is missing `@param {}`",
+          declaration.name, param
+        )),
+        (None, None) => add_diagnostic(format!(
+          "JSDoc of `{}` is missing `@param` for parameter {}",
+          declaration.name,
+          index + 1
+        )),
+        _ => {}
+      }
+    }
+    for tag in param_tags.iter().skip(function.params.len()) {
+      add_diagnostic(format!(
+        "JSDoc `@param {}` of `{}` doesn't match any parameter",
+        tag, declaration.name
+      ));
+    }
+  }
+
+  if function.returns_value == Some(true) && !has_returns {
+    add_diagnostic(format!(
+      "JSDoc of `{}` is missing `@returns`",
+      declaration.name
+    ));
+  }
+}
+
+/// Extracts the parameter name from the text after `@param`, skipping the
+/// type in braces: `{string} [name="a"] description` gives `name`.
+fn param_tag_name(rest: &str) -> Option<String> {
+  let mut rest = rest.trim_start();
+  if rest.starts_with('{') {
+    let mut depth = 0;
+    let end = rest.char_indices().find_map(|(index, c)| {
+      match c {
+        '{' => depth += 1,
+        '}' => depth -= 1,
+        _ => {}
+      }
+      if depth == 0 {
+        Some(index + 1)
+      } else {
+        None
+      }
+    })?;
+    rest = rest[end..].trim_start();
+  }
+  let token = rest.split_whitespace().next()?;
+  let name = token
+    .trim_start_matches('[')
+    .split(&['=', ']'][..])
+    .next()?;
+  if name.is_empty() {
+    None
+  } else {
+    Some(name.to_string())
+  }
+}
+
+/// Decorators may come before `export`, so they can start the declaration.
+fn class_start(start: BytePos, class: &Class) -> BytePos {
+  class
+    .decorators
+    .iter()
+    .map(|decorator| decorator.span.lo())
+    .fold(start, BytePos::min)
+}
+
+/// Collects the declarations of the module which are exported, in source
+/// order.
+fn public_declarations(module: &Module) -> Vec<Declaration> {
+  let mut declarations: Vec<Declaration> = vec![];
+  let mut exported: Vec<bool> = vec![];
+  let mut by_name: HashMap<String, usize> = HashMap::new();
+  let mut exported_names: Vec<String> = vec![];
+
+  for item in &module.body {
+    let (decl, start, is_exported) = match item {
+      ModuleItem::Stmt(Stmt::Decl(decl)) => (decl, decl.span().lo(), false),
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+        (&export_decl.decl, export_decl.span.lo(), true)
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(default_decl)) => {
+        let declaration = match &default_decl.decl {
+          DefaultDecl::Class(class_expr) => Declaration {
+            kind: "class",
+            name: "default".to_string(),
+            name_span: class_expr
+              .ident
+              .as_ref()
+              .map_or(default_decl.span, |ident| ident.span),
+            start: class_start(default_decl.span.lo(), &class_expr.class),
+            function: None,
+          },
+          DefaultDecl::Fn(fn_expr) => Declaration {
+            kind: "function",
+            name: "default".to_string(),
+            name_span: fn_expr
+              .ident
+              .as_ref()
+              .map_or(default_decl.span, |ident| ident.span),
+            start: default_decl.span.lo(),
+            function: Some(FunctionSignature::from_function(&fn_expr.function)),
+          },
+          DefaultDecl::TsInterfaceDecl(interface) => Declaration {
+            kind: "interface",
+            name: "default".to_string(),
+            name_span: interface.id.span,
+            start: default_decl.span.lo(),
+            function: None,
+          },
+        };
+        declarations.push(declaration);
+        exported.push(true);
+        continue;
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(default_expr)) => {
+        if let Expr::Ident(ident) = &*default_expr.expr {
+          exported_names.push(ident.sym.to_string());
+        } else {
+          declarations.push(Declaration {
+            kind: "default value",
+            name: "default".to_string(),
+            name_span: default_expr.span,
+            start: default_expr.span.lo(),
+            function: None,
+          });
+          exported.push(true);
+        }
+        continue;
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named_export)) => {
+        // Names re-exported from other modules are documented there.
+        if named_export.src.is_none() {
+          for specifier in &named_export.specifiers {
+            if let ExportSpecifier::Named(named) = specifier {
+              exported_names.push(named.orig.sym.to_string());
+            }
+          }
+        }
+        continue;
+      }
+      _ => continue,
+    };
+
+    let declaration = match decl {
+      Decl::Fn(fn_decl) => {
+        let name = fn_decl.ident.sym.to_string();
+        let signature = FunctionSignature::from_function(&fn_decl.function);
+        // Overloads share the comment of the first signature, the body of
+        // the implementation tells whether a value is returned.
+        if let Some(&index) = by_name.get(&name) {
+          if let Some(function) = &mut declarations[index].function {
+            if signature.returns_value.is_some() {
+              function.returns_value = signature.returns_value;
+            }
+            exported[index] |= is_exported;
+            continue;
+          }
+        }
+        Declaration {
+          kind: "function",
+          name,
+          name_span: fn_decl.ident.span,
+          start,
+          function: Some(signature),
+        }
+      }
+      Decl::Class(class_decl) => Declaration {
+        kind: "class",
+        name: class_decl.ident.sym.to_string(),
+        name_span: class_decl.ident.span,
+        start: class_start(start, &class_decl.class),
+        function: None,
+      },
+      Decl::TsInterface(interface) => Declaration {
+        kind: "interface",
+        name: interface.id.sym.to_string(),
+        name_span: interface.id.span,
+        start,
+        function: None,
+      },
+      Decl::TsTypeAlias(alias) => Declaration {
+        kind: "type alias",
+        name: alias.id.sym.to_string(),
+        name_span: alias.id.span,
+        start,
+        function: None,
+      },
+      Decl::Var(var_decl) if var_decl.kind == VarDeclKind::Const => {
+        let declarator = match var_decl.decls.first() {
+          Some(declarator) => declarator,
+          None => continue,
+        };
+        // One comment documents all the constants of the statement.
+        for other in &var_decl.decls[1..] {
+          if let Pat::Ident(ident) = &other.name {
+            by_name.insert(ident.sym.to_string(), declarations.len());
+          }
+        }
+        let function = match declarator.init.as_deref() {
+          Some(Expr::Fn(fn_expr)) => {
+            Some(FunctionSignature::from_function(&fn_expr.function))
+          }
+          Some(Expr::Arrow(arrow)) => Some(FunctionSignature {
+            params: arrow.params.iter().map(param_name).collect(),
+            returns_value: Some(match &arrow.body {
+              BlockStmtOrExpr::BlockStmt(body) => returns_value(body),
+              BlockStmtOrExpr::Expr(_) => true,
+            }),
+          }),
+          _ => None,
+        };
+        let name = match find_ids::<_, Id>(&declarator.name).first() {
+          Some((sym, _)) => sym.to_string(),
+          None => continue,
+        };
+        Declaration {
+          kind: "constant",
+          name,
+          name_span: declarator.name.span(),
+          start,
+          function,
+        }
+      }
+      _ => continue,
+    };
+    by_name.insert(declaration.name.clone(), declarations.len());
+    declarations.push(declaration);
+    exported.push(is_exported);
+  }
+
+  for name in exported_names {
+    if let Some(&index) = by_name.get(&name) {
+      exported[index] = true;
+    }
+  }
+  let mut declarations: Vec<Declaration> = declarations
+    .into_iter()
+    .zip(exported)
+    .filter(|(_, is_exported)| *is_exported)
+    .map(|(declaration, _)| declaration)
+    .collect();
+  declarations.sort_by_key(|declaration| declaration.start);
+  declarations
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn require_jsdoc_for_public_api_valid() {
+    assert_lint_ok_n::<RequireJsdocForPublicApi>(vec![
+      "function internal() {}\nconst a = 1;\nclass B {}",
+      "/** Adds. */\nexport function add(a: number, b: number) {}",
+      "/** A. */\nexport class A {}",
+      "/** A. */\n@decorator\nexport class A {}",
+      "/** A. */\nexport abstract class A {}",
+      "/** I. */\nexport interface I {}",
+      "/** T. */\nexport type T = string;",
+      "/** Version. */\nexport const VERSION = \"1.0.0\";",
+      "export let counter = 0;",
+      "/** F. */\nexport async function f() {}",
+      "/** F. */\nexport declare function f(): void;",
+      // Re-exports are documented where they are declared.
+      "/** F. */\nfunction f() {}\n/** G. */\nconst g = 1;\nexport { f, g as h };",
+      "export { a } from \"./a.ts\";\nexport * from \"./b.ts\";",
+      // Default exports.
+      "/** Main. */\nexport default function main() {}",
+      "/** Main. */\nexport default class {}",
+      "/** Main. */\nfunction main() {}\nexport default main;",
+      // Overloads are documented on the first signature.
+      r#"
+/**
+ * Parses.
+ * @param input the input
+ * @returns the result
+ */
+export function parse(input: string): number;
+export function parse(input: number): number;
+export function parse(input: string | number): number { return 1; }
+      "#,
+      // Tags.
+      r#"
+/**
+ * Greets.
+ * @param {string} name who to greet
+ * @param [greeting="Hello"] how
+ * @param options settings
+ * @param options.loud whether to shout
+ * @returns the greeting
+ */
+export function greet(name: string, greeting = "Hello", options = {}) {
+  return `${greeting} ${name}`;
+}
+      "#,
+      r#"
+/**
+ * @param point the point
+ * @param rest the others
+ */
+export function f({ x, y }: Point, ...rest: number[]) {}
+      "#,
+      "/** Returns nothing. */\nexport function f() { return; }",
+      "/** Nested. */\nexport function f() { a.map(() => { return 1; }); }",
+      "/**\n * @returns one\n */\nexport const one = () => 1;",
+    ]);
+  }
+
+  #[test]
+  fn require_jsdoc_for_public_api_invalid() {
+    assert_lint_err::<RequireJsdocForPublicApi>("export function f() {}", 16);
+    assert_lint_err::<RequireJsdocForPublicApi>("export class A {}", 13);
+    assert_lint_err::<RequireJsdocForPublicApi>("export interface I {}", 17);
+    assert_lint_err::<RequireJsdocForPublicApi>("export type T = string;", 12);
+    assert_lint_err::<RequireJsdocForPublicApi>("export const a = 1;", 13);
+    assert_lint_err_on_line::<RequireJsdocForPublicApi>(
+      "// Not JSDoc.\nexport const a = 1;",
+      2,
+      13,
+    );
+    assert_lint_err_on_line::<RequireJsdocForPublicApi>(
+      "/* Not JSDoc. */\nexport const a = 1;",
+      2,
+      13,
+    );
+    assert_lint_err_on_line::<RequireJsdocForPublicApi>(
+      "/** Not adjacent. */\nconst b = 1;\nexport const a = 1;",
+      3,
+      13,
+    );
+    assert_lint_err_on_line::<RequireJsdocForPublicApi>(
+      "export function f(a: string): void;\n/** Too late. */\nexport function f(a) {}",
+      1,
+      16,
+    );
+    assert_lint_err_on_line::<RequireJsdocForPublicApi>(
+      "function f() {}\nexport { f };",
+      1,
+      9,
+    );
+    assert_lint_err_on_line::<RequireJsdocForPublicApi>(
+      "/** Wrong place. */\nexport { f };\nfunction f() {}",
+      3,
+      9,
+    );
+    assert_lint_err::<RequireJsdocForPublicApi>(
+      "export default function main() {}",
+      24,
+    );
+    assert_lint_err_on_line::<RequireJsdocForPublicApi>(
+      "function main() {}\nexport default main;",
+      1,
+      9,
+    );
+    assert_lint_err::<RequireJsdocForPublicApi>("export default {};", 0);
+  }
+
+  #[test]
+  fn require_jsdoc_for_public_api_tags() {
+    let cases = vec![
+      (
+        "/**\n * @param b the first\n */\nexport function f(a = 1) {}",
+        "JSDoc `@param b` of `f` doesn't match the parameter `a`",
+      ),
+      (
+        "/**\n * @param a the first\n */\nexport function f(a, b = 2) {}",
+        "JSDoc of `f` is missing `@param b`",
+      ),
+      (
+        "/**\n * @param a the first\n */\nexport function f(a, { b }) {}",
+        "JSDoc of `f` is missing `@param` for parameter 2",
+      ),
+      (
+        "/**\n * @param a the first\n * @param b the second\n */\nexport function f(a) {}",
+        "JSDoc `@param b` of `f` doesn't match any parameter",
+      ),
+      (
+        "/** Returns one. */\nexport function f() { if (a) { return 1; } }",
+        "JSDoc of `f` is missing `@returns`",
+      ),
+      (
+        "/** Returns one. */\nexport const f = () => 1;",
+        "JSDoc of `f` is missing `@returns`",
+      ),
+      (
+        "/** Parses. */\nexport function f(a: string): number;\nexport function f(a) { return 1; }",
+        "JSDoc of `f` is missing `@returns`",
+      ),
+    ];
+    for (source, message) in cases {
+      let diagnostics = lint(RequireJsdocForPublicApi::new(), source);
+      assert_eq!(diagnostics.len(), 1, "{}", source);
+      assert_eq!(diagnostics[0].message, message);
+    }
+  }
+}
@@ -0,0 +1,221 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::swc_util::{extract_regex, extract_regex_flags};
+use std::sync::Arc;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSpread, ExprOrSuper, Ident, NewExpr, Regex,
+};
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+#[derive(Clone, Copy, Default)]
+pub struct RequireUnicodeRegexpOptions {
+  /// Skips patterns that only contain ASCII characters and no escapes
+  /// that depend on the `u` flag, like `\u{1F600}` or `\p{L}`.
+  pub allow_if_no_unicode_in_pattern: bool,
+}
+
+pub struct RequireUnicodeRegexp {
+  options: RequireUnicodeRegexpOptions,
+}
+
+impl RequireUnicodeRegexp {
+  pub fn with_options(options: RequireUnicodeRegexpOptions) -> Box<Self> {
+    Box::new(RequireUnicodeRegexp { options })
+  }
+}
+
+impl LintRule for RequireUnicodeRegexp {
+  fn new() -> Box<Self> {
+    Self::with_options(RequireUnicodeRegexpOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "require-unicode-regexp"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires the `u` or `v` flag on regular expressions.
+
+Without it, characters outside of the Basic Multilingual Plane, like emoji, are treated as two characters, and the stricter syntax checks of unicode mode don't apply.
+
+### Options
+
+`RequireUnicodeRegexpOptions::allow_if_no_unicode_in_pattern`: skips patterns which only contain ASCII characters and no escapes that depend on the `u` flag, `false` by default.
+
+### Valid:
+```typescript
+const a = /foo/u;
+const b = new RegExp("foo", "v");
+```
+
+### Invalid:
+```typescript
+const a = /foo/;
+```
+```typescript
+const b = new RegExp("foo", "g");
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = RequireUnicodeRegexpVisitor::new(context, self.options);
+    visitor.visit_module(module, module);
+  }
+}
+
+/// Returns true if the pattern has characters or escapes that are
+/// interpreted differently without the `u` flag.
+fn has_unicode(pattern: &str) -> bool {
+  let mut chars = pattern.chars();
+  while let Some(ch) = chars.next() {
+    if !ch.is_ascii() {
+      return true;
+    }
+    if ch == '\\' {
+      match chars.next() {
+        Some('p') | Some('P') => return true,
+        Some('u') if chars.clone().next() == Some('{') => return true,
+        _ => {}
+      }
+    }
+  }
+  false
+}
+
+struct RequireUnicodeRegexpVisitor {
+  context: Arc<Context>,
+  options: RequireUnicodeRegexpOptions,
+}
+
+impl RequireUnicodeRegexpVisitor {
+  fn new(context: Arc<Context>, options: RequireUnicodeRegexpOptions) -> Self {
+    Self { context, options }
+  }
+
+  fn check(&self, span: Span, pattern: &str, flags: &str) {
+    if flags.contains('u') || flags.contains('v') {
+      return;
+    }
+    if self.options.allow_if_no_unicode_in_pattern && !has_unicode(pattern) {
+      return;
+    }
+    self.context.add_diagnostic_with_hint(
+      span,
+      "require-unicode-regexp",
+      "Use the `u` flag with regular expressions",
+      "Add the `u` or `v` flag",
+    );
+  }
+
+  /// Returns true if the arguments were checked, in which case they're all
+  /// literals and don't have to be visited; a regex literal passed as the
+  /// pattern would be reported twice otherwise.
+  fn check_constructor(
+    &self,
+    span: Span,
+    ident: &Ident,
+    args: &[ExprOrSpread],
+  ) -> bool {
+    let pattern = extract_regex(&self.context.scope_manager, ident, args);
+    let flags = extract_regex_flags(args);
+    match (pattern, flags) {
+      (Some(pattern), Some(flags)) => {
+        self.check(span, &pattern, &flags);
+        true
+      }
+      _ => false,
+    }
+  }
+}
+
+impl Visit for RequireUnicodeRegexpVisitor {
+  noop_visit_type!();
+
+  fn visit_regex(&mut self, regex: &Regex, parent: &dyn Node) {
+    self.check(regex.span, &regex.exp, &regex.flags);
+    swc_ecmascript::visit::visit_regex(self, regex, parent);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, parent: &dyn Node) {
+    if let Expr::Ident(ident) = &*new_expr.callee {
+      if let Some(args) = &new_expr.args {
+        if self.check_constructor(new_expr.span, ident, args) {
+          return;
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_new_expr(self, new_expr, parent);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, parent: &dyn Node) {
+    if let ExprOrSuper::Expr(expr) = &call_expr.callee {
+      if let Expr::Ident(ident) = &**expr {
+        if self.check_constructor(call_expr.span, ident, &call_expr.args) {
+          return;
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_call_expr(self, call_expr, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn require_unicode_regexp_valid() {
+    assert_lint_ok_n::<RequireUnicodeRegexp>(vec![
+      "/foo/u;",
+      "/foo/gimuy;",
+      "/foo/v;",
+      "/[\\p{L}--[a-z]]/v;",
+      r#"new RegExp("foo", "u");"#,
+      r#"RegExp("foo", "gu");"#,
+      r#"new RegExp("foo", "v");"#,
+      "new RegExp(/foo/u);",
+      "new RegExp(pattern);",
+      "new RegExp(pattern, flags);",
+      r#"new RegExp("foo", flags);"#,
+      r#"function f(RegExp) { new RegExp("foo"); }"#,
+    ]);
+  }
+
+  #[test]
+  fn require_unicode_regexp_invalid() {
+    assert_lint_err::<RequireUnicodeRegexp>("const a = /foo/;", 10);
+    assert_lint_err::<RequireUnicodeRegexp>("const a = /foo/gimy;", 10);
+    assert_lint_err::<RequireUnicodeRegexp>(r#"new RegExp("foo");"#, 0);
+    assert_lint_err::<RequireUnicodeRegexp>(r#"new RegExp("foo", "g");"#, 0);
+    assert_lint_err::<RequireUnicodeRegexp>(r#"RegExp("foo", "");"#, 0);
+    assert_lint_err::<RequireUnicodeRegexp>("new RegExp(/foo/g);", 0);
+  }
+
+  #[test]
+  fn require_unicode_regexp_allow_if_no_unicode_in_pattern() {
+    let rule = || {
+      RequireUnicodeRegexp::with_options(RequireUnicodeRegexpOptions {
+        allow_if_no_unicode_in_pattern: true,
+      })
+    };
+    assert_lint_ok_with(rule(), "/foo/;");
+    assert_lint_ok_with(rule(), r#"new RegExp("[a-z]+\\d");"#);
+    assert_lint_ok_with(rule(), "/\\u0041/;");
+    assert_lint_err_on_line_n_with(rule(), "/café/;", vec![(1, 0)]);
+    assert_lint_err_on_line_n_with(rule(), "/\\p{L}/;", vec![(1, 0)]);
+    assert_lint_err_on_line_n_with(rule(), "/\\u{1F600}/;", vec![(1, 0)]);
+  }
+}
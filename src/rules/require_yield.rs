@@ -25,6 +25,33 @@ impl LintRule for RequireYield {
     "require-yield"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires generator functions to contain a `yield`.
+
+A generator without `yield` is usually meant to be a regular function. Empty generators are allowed.
+
+### Valid:
+```typescript
+function* foo() {
+  yield "foo";
+}
+```
+```typescript
+function* emptyFoo() {}
+```
+
+### Invalid:
+```typescript
+function* foo() {
+  return "foo";
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -1,10 +1,13 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use swc_ecmascript::ast::ForStmt;
+use swc_ecmascript::ast::TsModuleDecl;
 use swc_ecmascript::ast::VarDecl;
-use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::ast::VarDeclOrExpr;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
 
 use std::sync::Arc;
 
@@ -19,6 +22,26 @@ impl LintRule for SingleVarDeclarator {
     "single-var-declarator"
   }
 
+  fn docs(&self) -> &'static str {
+    r#"Disallows declaring more than one variable in a single declaration.
+
+One variable per declaration is easier to read, reorder and change. The initializer of a `for` loop is not reported, since splitting it changes the meaning of the loop, and neither are declarations in ambient TypeScript contexts.
+
+### Valid:
+```typescript
+const a = "a";
+const b = "b";
+```
+```typescript
+for (let i = 0, j = 10; i < j; i++, j--) {}
+```
+
+### Invalid:
+```typescript
+const a = "a", b = "b";
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
@@ -40,16 +63,42 @@ impl SingleVarDeclaratorVisitor {
 }
 
 impl Visit for SingleVarDeclaratorVisitor {
-  noop_visit_type!();
+  fn visit_ts_module_decl(
+    &mut self,
+    module_decl: &TsModuleDecl,
+    parent: &dyn Node,
+  ) {
+    if !module_decl.declare {
+      swc_ecmascript::visit::visit_ts_module_decl(self, module_decl, parent);
+    }
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, parent: &dyn Node) {
+    match &for_stmt.init {
+      // The declarators of the initializer are visited, but not checked
+      Some(VarDeclOrExpr::VarDecl(var_decl)) => {
+        var_decl.decls.visit_with(var_decl, self);
+        for_stmt.test.visit_with(for_stmt, self);
+        for_stmt.update.visit_with(for_stmt, self);
+        for_stmt.body.visit_with(for_stmt, self);
+      }
+      _ => swc_ecmascript::visit::visit_for_stmt(self, for_stmt, parent),
+    }
+  }
 
-  fn visit_var_decl(&mut self, var_decl: &VarDecl, _parent: &dyn Node) {
-    if var_decl.decls.len() > 1 {
-      self.context.add_diagnostic(
-        var_decl.span,
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, parent: &dyn Node) {
+    if var_decl.declare {
+      return;
+    }
+    if let Some(second) = var_decl.decls.get(1) {
+      self.context.add_diagnostic_with_hint(
+        second.span,
         "single-var-declarator",
         "Multiple variable declarators are not allowed",
+        "Declare each variable in its own statement",
       );
     }
+    swc_ecmascript::visit::visit_var_decl(self, var_decl, parent);
   }
 }
 
@@ -62,15 +111,59 @@ mod tests {
   fn single_var_declarator_test() {
     assert_lint_err::<SingleVarDeclarator>(
       r#"const a1 = "a", b1 = "b", c1 = "c";"#,
-      0,
+      16,
     );
     assert_lint_err::<SingleVarDeclarator>(
       r#"let a2 = "a", b2 = "b", c2 = "c";"#,
-      0,
+      14,
     );
     assert_lint_err::<SingleVarDeclarator>(
       r#"var a3 = "a", b3 = "b", c3 = "c";"#,
-      0,
+      14,
+    );
+    assert_lint_err::<SingleVarDeclarator>("export let a, b;", 14);
+    assert_lint_err_on_line::<SingleVarDeclarator>(
+      "function foo() {\n  return () => { let a, b; };\n}",
+      2,
+      24,
+    );
+
+    let diagnostics = lint(SingleVarDeclarator::new(), "let a = 1, b = 2;");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.col, 11);
+    assert_eq!(diagnostics[0].range.end.col, 16);
+  }
+
+  #[test]
+  fn single_var_declarator_loop_heads() {
+    assert_lint_ok_n::<SingleVarDeclarator>(vec![
+      "for (let i = 0, j = 1; i < j; i++) {}",
+      "for (var i = 0, j = 1; i < j; i++) {}",
+      "for (const a in b) {}",
+      "for (const a of b) {}",
+    ]);
+    assert_lint_err::<SingleVarDeclarator>(
+      "for (let i = 0; i < 1; i++) { let a, b; }",
+      37,
+    );
+    assert_lint_err::<SingleVarDeclarator>(
+      "for (let i = 0, j = () => { let a, b; }; i < 1; i++) {}",
+      35,
+    );
+  }
+
+  #[test]
+  fn single_var_declarator_ambient_contexts() {
+    assert_lint_ok_n::<SingleVarDeclarator>(vec![
+      "declare let a: string, b: number;",
+      "declare global {\n  let a: string, b: number;\n}",
+      "declare module 'foo' {\n  const a: string, b: number;\n}",
+      "declare namespace Foo {\n  var a: string, b: number;\n}",
+    ]);
+    assert_lint_err_on_line::<SingleVarDeclarator>(
+      "namespace Foo {\n  export const a = 1, b = 2;\n}",
+      2,
+      22,
     );
   }
 }
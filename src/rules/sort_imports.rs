@@ -1,10 +1,19 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::sort_util::{compare, SortOrder};
+#[cfg(feature = "json")]
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
 use std::sync::Arc;
 use swc_common::Span;
+use swc_ecmascript::ast::Ident;
 use swc_ecmascript::ast::ImportDecl;
 use swc_ecmascript::ast::ImportSpecifier;
+use swc_ecmascript::ast::ModuleDecl;
+use swc_ecmascript::ast::ModuleItem;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 
@@ -12,7 +21,15 @@ use swc_ecmascript::visit::Visit;
 struct ImportIdent {
   import_decl: String,
   span: Span,
+  // The span of the module specifier of the declaration, where
+  // declaration-order errors are reported
+  src_span: Span,
   import_type: ImportTypes,
+  // With `allow_separated_groups`, declarations are only compared within
+  // their group
+  group: usize,
+  // The span of the whole specifier or declaration, which fixes move
+  node_span: Span,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -23,22 +40,82 @@ enum ImportTypes {
   Single,
 }
 
+/// What import declarations are sorted by.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase"))]
+pub enum SortBy {
+  /// The first local binding, eg. `a` for `import a from "./z.ts"`.
+  LocalName,
+  /// The module specifier, eg. `"./z.ts"` for `import a from "./z.ts"`.
+  Source,
+}
+
+/// The options of `sort-imports`, which mirror the ones of ESLint's rule.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "json", derive(Deserialize))]
+#[cfg_attr(feature = "json", serde(default, rename_all = "camelCase"))]
 pub struct SortImportsOptions {
-  ignore_case: bool,
-  ignore_declaration_sort: bool,
-  ignore_member_sort: bool,
-  member_syntax_sort_order: Vec<ImportTypes>,
+  /// Sorts case-insensitively.
+  pub ignore_case: bool,
+  /// Doesn't check the order of the import declarations.
+  pub ignore_declaration_sort: bool,
+  /// Doesn't check the order of the members within a declaration.
+  pub ignore_member_sort: bool,
+  /// The order of the import syntaxes, a permutation of `"none"`, `"all"`,
+  /// `"multiple"` and `"single"`.
+  pub member_syntax_sort_order: Vec<String>,
+  /// Sorts the declarations separated by a blank line or another statement
+  /// as independent groups.
+  pub allow_separated_groups: bool,
+  /// What declarations are sorted by. Members within a declaration are
+  /// always sorted by their local name.
+  pub sort_by: SortBy,
+  /// Skips type-only imports, eg. `import type { A } from "./a.ts"`.
+  pub ignore_type_imports: bool,
+}
+
+impl Default for SortImportsOptions {
+  fn default() -> Self {
+    SortImportsOptions {
+      ignore_case: false,
+      ignore_declaration_sort: false,
+      ignore_member_sort: false,
+      member_syntax_sort_order: ["none", "all", "multiple", "single"]
+        .iter()
+        .map(|import_type| import_type.to_string())
+        .collect(),
+      allow_separated_groups: false,
+      sort_by: SortBy::LocalName,
+      ignore_type_imports: false,
+    }
+  }
+}
+
+/// Returned by `SortImports::with_options` for options which can't be
+/// deserialized or have an invalid `memberSyntaxSortOrder`.
+#[derive(Debug, PartialEq)]
+pub struct InvalidSortImportsOptions {
+  pub reason: String,
 }
+
+impl fmt::Display for InvalidSortImportsOptions {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Invalid sort-imports options: {}", self.reason)
+  }
+}
+
+impl Error for InvalidSortImportsOptions {}
 // End of structs and enums
 
 // Start of helper functions
-fn str_to_import_types(import_type_str: &str) -> ImportTypes {
+fn str_to_import_types(import_type_str: &str) -> Option<ImportTypes> {
   match import_type_str {
-    "none" => ImportTypes::None,
-    "all" => ImportTypes::All,
-    "multiple" => ImportTypes::Multiple,
-    "single" => ImportTypes::Single,
-    &_ => ImportTypes::None,
+    "none" => Some(ImportTypes::None),
+    "all" => Some(ImportTypes::All),
+    "multiple" => Some(ImportTypes::Multiple),
+    "single" => Some(ImportTypes::Single),
+    _ => None,
   }
 }
 
@@ -51,11 +128,34 @@ fn import_types_to_string(import_type: &ImportTypes) -> String {
   }
 }
 
-fn config_to_enum(config: [&str; 4]) -> Vec<ImportTypes> {
-  config
-    .iter()
-    .map(|str_slice| str_to_import_types(str_slice))
-    .collect::<Vec<ImportTypes>>()
+fn config_to_enum(
+  config: &[String],
+) -> Result<Vec<ImportTypes>, InvalidSortImportsOptions> {
+  let invalid = |reason: String| InvalidSortImportsOptions { reason };
+  let mut import_types = vec![];
+  for str_slice in config {
+    let import_type = str_to_import_types(str_slice).ok_or_else(|| {
+      invalid(format!(
+        "unknown syntax `{}` in memberSyntaxSortOrder, expected one of \
+         `none`, `all`, `multiple` or `single`",
+        str_slice
+      ))
+    })?;
+    if import_types.contains(&import_type) {
+      return Err(invalid(format!(
+        "`{}` is listed twice in memberSyntaxSortOrder",
+        str_slice
+      )));
+    }
+    import_types.push(import_type);
+  }
+  if import_types.len() != 4 {
+    return Err(invalid(format!(
+      "memberSyntaxSortOrder must list all 4 syntaxes, found {}",
+      import_types.len()
+    )));
+  }
+  Ok(import_types)
 }
 // End of helper functions
 
@@ -63,74 +163,169 @@ impl ImportIdent {
   fn new(
     import_decl: String,
     span: Span,
+    src_span: Span,
     import_type: ImportTypes,
   ) -> ImportIdent {
     ImportIdent {
       import_decl,
       span,
+      src_span,
       import_type,
+      group: 0,
+      node_span: span,
     }
   }
 }
 
-pub struct SortImports;
+pub struct SortImports {
+  options: SortImportsOptions,
+  member_syntax_sort_order: Vec<ImportTypes>,
+}
+
+impl SortImports {
+  pub fn with_options(
+    options: SortImportsOptions,
+  ) -> Result<Box<Self>, InvalidSortImportsOptions> {
+    let member_syntax_sort_order =
+      config_to_enum(&options.member_syntax_sort_order)?;
+    Ok(Box::new(SortImports {
+      options,
+      member_syntax_sort_order,
+    }))
+  }
+
+  /// Creates the rule from an ESLint-style configuration, eg.
+  /// `{"ignoreCase": true, "memberSyntaxSortOrder": ["all", "single",
+  /// "multiple", "none"]}`.
+  #[cfg(feature = "json")]
+  pub fn from_json(
+    config: serde_json::Value,
+  ) -> Result<Box<Self>, InvalidSortImportsOptions> {
+    let options = serde_json::from_value(config).map_err(|err| {
+      InvalidSortImportsOptions {
+        reason: err.to_string(),
+      }
+    })?;
+    Self::with_options(options)
+  }
+}
 
 impl LintRule for SortImports {
   fn new() -> Box<Self> {
-    Box::new(SortImports)
+    Self::with_options(SortImportsOptions::default()).unwrap()
   }
 
   fn code(&self) -> &'static str {
     "sort-imports"
   }
 
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires import declarations and the members within them to be sorted.
+
+Declarations are first ordered by their syntax, see `memberSyntaxSortOrder`, and then alphabetically by their first local name. Members within a declaration are sorted by their local name. The first diagnostic of a module carries a fix which sorts all of its imports.
+
+### Options
+
+The options mirror the ones of ESLint's rule and can be passed as JSON with `SortImports::from_json`, or as `SortImportsOptions` with `SortImports::with_options`:
+
+```json
+{
+  "ignoreCase": false,
+  "ignoreDeclarationSort": false,
+  "ignoreMemberSort": false,
+  "memberSyntaxSortOrder": ["none", "all", "multiple", "single"],
+  "allowSeparatedGroups": false,
+  "sortBy": "localName",
+  "ignoreTypeImports": false
+}
+```
+
+- `ignoreCase`: sorts case-insensitively.
+- `ignoreDeclarationSort`: doesn't check the order of the declarations.
+- `ignoreMemberSort`: doesn't check the order of the members within a declaration.
+- `memberSyntaxSortOrder`: the order of the import syntaxes, a permutation of `"none"` (`import "a.ts"`), `"all"` (`import * as a from "a.ts"`), `"multiple"` (`import { a, b } from "a.ts"`) and `"single"` (`import a from "a.ts"`).
+- `allowSeparatedGroups`: sorts the declarations separated by a blank line or another statement as independent groups.
+- `sortBy`: `"localName"` or `"source"`, what declarations are sorted by.
+- `ignoreTypeImports`: skips type-only imports.
+
+### Valid:
+```typescript
+import "./polyfill.ts";
+import * as path from "./path.ts";
+import { a, b } from "./ab.ts";
+import c from "./c.ts";
+```
+
+### Invalid:
+```typescript
+import b from "./b.ts";
+import a from "./a.ts";
+```
+```typescript
+import { b, a } from "./ab.ts";
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     module: &swc_ecmascript::ast::Module,
   ) {
-    let mut visitor = SortImportsVisitor::default(context);
+    let mut visitor = SortImportsVisitor {
+      context,
+      options: &self.options,
+      member_syntax_sort_order: &self.member_syntax_sort_order,
+      line_imports: vec![],
+      diagnostics: vec![],
+      group: 0,
+      last_import_end_line: None,
+      separated: false,
+    };
     visitor.visit_module(module, module);
     visitor.sort_line_imports();
+    visitor.report_diagnostics();
   }
 }
 
-struct SortImportsVisitor {
+struct SortImportsDiagnostic {
+  span: Span,
+  message: String,
+  hint: String,
+  // Edits sorting the whole declaration or all declarations, which are only
+  // attached to the first diagnostic of them
+  fix: Option<Vec<(Span, String)>>,
+}
+
+struct SortImportsVisitor<'a> {
   context: Arc<Context>,
-  options: SortImportsOptions,
+  options: &'a SortImportsOptions,
+  member_syntax_sort_order: &'a [ImportTypes],
   line_imports: Vec<ImportIdent>,
+  // Reported at the end, ordered by span, as members are checked while
+  // visiting but declarations only once all of them are collected
+  diagnostics: Vec<SortImportsDiagnostic>,
+  group: usize,
+  // The line the previous import declaration ends on
+  last_import_end_line: Option<usize>,
+  // Whether another module item appeared since the previous import
+  // declaration
+  separated: bool,
 }
 
-impl SortImportsVisitor {
-  pub fn default(context: Arc<Context>) -> Self {
-    Self {
-      context,
-      options: SortImportsOptions {
-        ignore_case: false,
-        ignore_declaration_sort: false,
-        ignore_member_sort: false,
-        member_syntax_sort_order: config_to_enum([
-          "none", "all", "multiple", "single",
-        ]),
-      },
-      line_imports: vec![],
-    }
-  }
-
+impl<'a> SortImportsVisitor<'a> {
   fn get_err_index(
     &self,
     import_specifiers: &[ImportIdent],
     report_multiple: Option<bool>,
   ) -> (Option<usize>, Option<Vec<usize>>, Option<Vec<usize>>) {
-    let get_sortable_name = if self.options.ignore_case {
-      |specifier: &ImportIdent| specifier.import_decl.to_ascii_lowercase()
-    } else {
-      |specifier: &ImportIdent| specifier.import_decl.to_string()
-    };
     let identifier_names = import_specifiers
       .iter()
-      .map(get_sortable_name)
-      .collect::<Vec<String>>();
+      .map(|specifier| specifier.import_decl.as_str())
+      .collect::<Vec<&str>>();
     // This stored the index of the first member that is found not to be sorted
     let mut first_unsorted_index: Option<usize> = None;
     // This stores indices for all the members that are found not to be sorted
@@ -138,6 +333,11 @@ impl SortImportsVisitor {
     // This stores the indices imports that are not in order as defined by the member_syntax_sort_order option
     let mut unexpected_order_indices: Vec<usize> = vec![];
     for (index, identifier_name) in identifier_names.iter().enumerate() {
+      if index != import_specifiers.len() - 1
+        && import_specifiers[index].group != import_specifiers[index + 1].group
+      {
+        continue;
+      }
       if report_multiple.is_some() && index != &import_specifiers.len() - 1 {
         let current_member_group_index = self
           .get_member_param_grp_index(import_specifiers[index].import_type)
@@ -155,15 +355,26 @@ impl SortImportsVisitor {
         }
       }
 
-      if index != &import_specifiers.len() - 1 {
-        /* This checks the curent identifier and the next one and sorts them.
-        If they are not in the same order after sorting, then those "members"
-        are not sorted and the index needs to be returned to report the error*/
-        let reported_identifier = &identifier_names[index + 1];
-        let mut current_and_next_ident: Vec<String> =
-          vec![reported_identifier.to_string(), identifier_name.to_string()];
-        current_and_next_ident.sort();
-        if &current_and_next_ident[0] != identifier_name {
+      // Side effect imports have no name to compare, they're only checked
+      // for their syntax order
+      if index != &import_specifiers.len() - 1
+        && import_specifiers[index].import_type != ImportTypes::None
+        && import_specifiers[index + 1].import_type != ImportTypes::None
+      {
+        /* This compares the current identifier with the next one, by code
+        point and with Unicode case folding for ignore_case, like ESLint. If
+        the next one comes first, those "members" are not sorted and the
+        index needs to be returned to report the error. Names which fold to
+        the same string are sorted in either order, so they keep their
+        source order. */
+        let reported_identifier = identifier_names[index + 1];
+        let ordering = compare(
+          identifier_name,
+          reported_identifier,
+          SortOrder::Alphabetical,
+          self.options.ignore_case,
+        );
+        if ordering == Ordering::Greater {
           first_unsorted_index = Some(index + 1);
           if report_multiple.is_some() {
             error_indices.push(index + 1)
@@ -194,117 +405,257 @@ impl SortImportsVisitor {
 
   fn get_member_param_grp_index(&self, variant: ImportTypes) -> Option<usize> {
     self
-      .options
       .member_syntax_sort_order
       .iter()
       .position(|import_type| &variant == import_type)
   }
 
+  /// Returns the edits moving `import_specifiers` to their sorted
+  /// positions, by replacing each one with the source text of the one
+  /// sorted there. Whatever is between them, like comments, stays in place.
+  fn sort_fix(&self, import_specifiers: &[ImportIdent]) -> Vec<(Span, String)> {
+    let mut sorted = import_specifiers.iter().collect::<Vec<_>>();
+    // Stable, so specifiers comparing equal keep their order
+    sorted.sort_by(|a, b| {
+      a.group
+        .cmp(&b.group)
+        .then_with(|| {
+          self
+            .get_member_param_grp_index(a.import_type)
+            .cmp(&self.get_member_param_grp_index(b.import_type))
+        })
+        .then_with(|| {
+          if a.import_type == ImportTypes::None {
+            return Ordering::Equal;
+          }
+          compare(
+            &a.import_decl,
+            &b.import_decl,
+            SortOrder::Alphabetical,
+            self.options.ignore_case,
+          )
+        })
+    });
+    import_specifiers
+      .iter()
+      .zip(sorted)
+      .filter(|(original, sorted)| original.node_span != sorted.node_span)
+      .map(|(original, sorted)| {
+        (original.node_span, self.context.span_text(sorted.node_span))
+      })
+      .collect()
+  }
+
   fn sort_import_decl(&mut self, import_specifiers: &[ImportIdent]) {
-    if !self.options.ignore_member_sort {
-      let (first_unsorted_member_index, _, _) =
-        self.get_err_index(&import_specifiers, None);
-      if let Some(index) = first_unsorted_member_index {
+    if self.options.ignore_member_sort {
+      return;
+    }
+    let (_, unsorted_member_indices, _) =
+      self.get_err_index(import_specifiers, Some(true));
+    if let Some(indices) = unsorted_member_indices {
+      let mut fix = Some(self.sort_fix(import_specifiers));
+      for index in indices.into_iter() {
+        let hint = format!(
+          "Run with --fix or reorder the specifiers alphabetically; expected '{}' to come before '{}'",
+          import_specifiers[index].import_decl,
+          import_specifiers[index - 1].import_decl
+        );
         let mut err_string = String::from("Member '");
         err_string.push_str(&import_specifiers[index].import_decl);
         err_string.push_str(
           "' of the import declaration should be sorted alphabetically",
         );
-        self.context.add_diagnostic(
-          import_specifiers[index].span,
-          "sort-imports",
-          &err_string,
-        );
-        return;
+        self.diagnostics.push(SortImportsDiagnostic {
+          span: import_specifiers[index].span,
+          message: err_string,
+          hint,
+          fix: fix.take(),
+        });
       }
     }
   }
 
   fn sort_line_imports(&mut self) {
+    if self.options.ignore_declaration_sort {
+      return;
+    }
     let (_, unsorted_import_indices, unexpected_order_indices) =
       self.get_err_index(&self.line_imports, Some(true));
-    if let Some(vec_n) = unsorted_import_indices {
-      for n in vec_n.into_iter() {
-        self.context.add_diagnostic(
-          self.line_imports[n].span,
-          "sort-imports",
-          "Imports should be sorted alphabetically",
-        );
+    let unsorted_import_indices = unsorted_import_indices.unwrap_or_default();
+    let unexpected_order_indices = unexpected_order_indices.unwrap_or_default();
+    // The fix is attached to the first diagnostic
+    let first_index = unsorted_import_indices
+      .iter()
+      .chain(unexpected_order_indices.iter())
+      .min()
+      .copied();
+    let mut fix = first_index.map(|_| self.sort_fix(&self.line_imports));
+    let mut fix_for = |index: usize| {
+      if Some(index) == first_index {
+        fix.take()
+      } else {
+        None
+      }
+    };
+    for n in unsorted_import_indices.into_iter() {
+      let err_string = match self.options.sort_by {
+        SortBy::LocalName => {
+          String::from("Imports should be sorted alphabetically")
+        }
+        SortBy::Source => format!(
+          "Import of '{}' should be sorted alphabetically by module path",
+          self.line_imports[n].import_decl
+        ),
+      };
+      let hint = format!(
+        "Run with --fix or reorder the import declarations alphabetically; expected '{}' to come before '{}'",
+        self.line_imports[n].import_decl,
+        self.line_imports[n - 1].import_decl
+      );
+      self.diagnostics.push(SortImportsDiagnostic {
+        span: self.line_imports[n].src_span,
+        message: err_string,
+        hint,
+        fix: fix_for(n),
+      });
+    }
+    for index in unexpected_order_indices.into_iter() {
+      let mut err_string = String::from("Expected '");
+      err_string.push_str(&import_types_to_string(
+        &self.line_imports[index].import_type,
+      ));
+      err_string.push_str("' syntax");
+      if self.options.sort_by == SortBy::Source {
+        err_string.push_str(" of '");
+        err_string.push_str(&self.line_imports[index].import_decl);
+        err_string.push('\'');
       }
+      err_string.push_str(" before '");
+      err_string.push_str(&import_types_to_string(
+        &self.line_imports[index - 1].import_type,
+      ));
+      err_string.push_str("' syntax");
+      // Side effect imports have no name, so the imports are named by module
+      let hint = format!(
+        "Run with --fix or reorder the import declarations by syntax; expected the import of '{}' to come before the import of '{}'",
+        self.module_path(&self.line_imports[index]),
+        self.module_path(&self.line_imports[index - 1])
+      );
+      self.diagnostics.push(SortImportsDiagnostic {
+        span: self.line_imports[index].src_span,
+        message: err_string,
+        hint,
+        fix: fix_for(index),
+      });
     }
-    if let Some(indices) = unexpected_order_indices {
-      for index in indices.into_iter() {
-        let mut err_string = String::from("Expected '");
-        err_string.push_str(&import_types_to_string(
-          &self.line_imports[index].import_type,
-        ));
-        err_string.push_str("' syntax before '");
-        err_string.push_str(&import_types_to_string(
-          &self.line_imports[index - 1].import_type,
-        ));
-        err_string.push_str("' syntax");
-        self.context.add_diagnostic(
-          self.line_imports[index].span,
+  }
+
+  fn module_path(&self, import: &ImportIdent) -> String {
+    self
+      .context
+      .span_text(import.src_span)
+      .trim_matches(|c| c == '\'' || c == '"')
+      .to_string()
+  }
+
+  fn report_diagnostics(&mut self) {
+    self
+      .diagnostics
+      .sort_by_key(|diagnostic| diagnostic.span.lo());
+    for diagnostic in self.diagnostics.drain(..) {
+      match diagnostic.fix {
+        Some(edits) => self.context.add_diagnostic_with_fix(
+          diagnostic.span,
           "sort-imports",
-          &err_string,
-        );
+          &diagnostic.message,
+          &diagnostic.hint,
+          edits,
+        ),
+        None => self.context.add_diagnostic_with_hint(
+          diagnostic.span,
+          "sort-imports",
+          &diagnostic.message,
+          &diagnostic.hint,
+        ),
       }
     }
   }
 
   fn handle_import_decl(&mut self, import_stmt: &ImportDecl) {
+    if self.options.ignore_type_imports && import_stmt.type_only {
+      return;
+    }
     let specifiers = &import_stmt.specifiers;
-    let mut import_ident_vec: Vec<ImportIdent> = vec![];
-    let mut import_ident: ImportIdent =
-      ImportIdent::new(String::from(""), import_stmt.span, ImportTypes::None);
-    for (index, specifier) in specifiers.iter().enumerate() {
-      match specifier {
-        ImportSpecifier::Named(named_specifier) => {
-          import_ident_vec.push(ImportIdent::new(
-            named_specifier.local.sym.get(0..).unwrap().to_string(),
+    // Like ESLint, the syntax and name of the declaration come from the
+    // whole list of specifiers, so `import a, {b} from 'c'` is `multiple`
+    let import_type = match specifiers.first() {
+      None => ImportTypes::None,
+      Some(ImportSpecifier::Namespace(_)) => ImportTypes::All,
+      Some(_) if specifiers.len() == 1 => ImportTypes::Single,
+      Some(_) => ImportTypes::Multiple,
+    };
+    let mut import_ident = ImportIdent::new(
+      specifiers
+        .first()
+        .map(|specifier| specifier_local(specifier).sym.to_string())
+        .unwrap_or_default(),
+      import_stmt.span,
+      import_stmt.src.span,
+      import_type,
+    );
+    let import_ident_vec = specifiers
+      .iter()
+      .filter_map(|specifier| match specifier {
+        ImportSpecifier::Named(named_specifier) => Some(ImportIdent {
+          node_span: named_specifier.span,
+          ..ImportIdent::new(
+            named_specifier.local.sym.to_string(),
             named_specifier.local.span,
-            if specifiers.len() > 1 {
-              ImportTypes::Multiple
-            } else {
-              ImportTypes::Single
-            },
-          ));
-          if index == 0 {
-            import_ident = ImportIdent::new(
-              named_specifier.local.sym.get(0..).unwrap().to_string(),
-              import_stmt.span,
-              if specifiers.len() > 1 {
-                ImportTypes::Multiple
-              } else {
-                ImportTypes::Single
-              },
-            );
-          }
-        }
-        ImportSpecifier::Default(specifier) => {
-          import_ident = ImportIdent::new(
-            specifier.local.sym.get(0..).unwrap().to_string(),
-            import_stmt.span,
-            ImportTypes::Single,
-          );
-        }
-        ImportSpecifier::Namespace(specifier) => {
-          import_ident = ImportIdent::new(
-            specifier.local.sym.get(0..).unwrap().to_string(),
-            import_stmt.span,
-            ImportTypes::All,
-          );
-        }
-      }
+            import_stmt.src.span,
+            import_type,
+          )
+        }),
+        ImportSpecifier::Default(_) | ImportSpecifier::Namespace(_) => None,
+      })
+      .collect::<Vec<_>>();
+    if self.options.sort_by == SortBy::Source {
+      import_ident.import_decl = import_stmt.src.value.to_string();
+    }
+    if self.options.allow_separated_groups {
+      self.update_group(import_stmt.span);
+      import_ident.group = self.group;
     }
     self.line_imports.push(import_ident);
-    if !self.options.ignore_declaration_sort {
-      self.sort_import_decl(&import_ident_vec);
+    self.sort_import_decl(&import_ident_vec);
+  }
+
+  // Starts a new group if the declaration at `span` is separated from the
+  // previous one by another module item or a blank line
+  fn update_group(&mut self, span: Span) {
+    let start = self.context.source_map.lookup_char_pos(span.lo());
+    let end_line = self.context.source_map.lookup_char_pos(span.hi()).line;
+    if let Some(last_end_line) = self.last_import_end_line {
+      // Lines are 1-based, `get_line` indices 0-based
+      let has_blank_line = (last_end_line..start.line - 1).any(|index| {
+        matches!(start.file.get_line(index), Some(line) if line.trim().is_empty())
+      });
+      if self.separated || has_blank_line {
+        self.group += 1;
+      }
     }
+    self.last_import_end_line = Some(end_line);
+    self.separated = false;
   }
 }
 
-impl Visit for SortImportsVisitor {
+impl<'a> Visit for SortImportsVisitor<'a> {
+  fn visit_module_item(&mut self, item: &ModuleItem, parent: &dyn Node) {
+    if !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))) {
+      self.separated = true;
+    }
+    swc_ecmascript::visit::visit_module_item(self, item, parent);
+  }
+
   fn visit_import_decl(
     &mut self,
     import_stmt: &ImportDecl,
@@ -314,62 +665,87 @@ impl Visit for SortImportsVisitor {
   }
 }
 
+fn specifier_local(specifier: &ImportSpecifier) -> &Ident {
+  match specifier {
+    ImportSpecifier::Named(specifier) => &specifier.local,
+    ImportSpecifier::Default(specifier) => &specifier.local,
+    ImportSpecifier::Namespace(specifier) => &specifier.local,
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::diagnostic::{apply_edits, LintDiagnostic};
   use crate::test_util::*;
 
+  #[test]
+  fn sort_imports_valid() {
+    assert_lint_ok_n::<SortImports>(vec![
+      "import a from 'foo.js';\nimport b from 'bar.js';\nimport c from 'baz.js';",
+      "import {a, b, c, d} from 'foo.js';",
+      "import {z as a, b, c} from 'foo.js';",
+      "import {B, a, c} from 'foo.js';",
+      "import A from 'foo.js';\nimport a from 'bar.js';",
+      // `none`, `all`, `multiple` and `single` syntaxes in order
+      "import 'foo.js';\nimport * as bar from 'bar.js';\nimport {a, b} from 'baz.js';\nimport c from 'qux.js';",
+      "import 'b.js';\nimport 'a.js';",
+      "import a, {b, c} from 'foo.js';",
+      "import {a, b} from 'foo.js';\nimport {c, d} from 'bar.js';",
+      "import {\n  a,\n  b, // comment\n  c,\n} from 'foo.js';",
+      "import a from 'foo.js';\nexport { b } from 'bar.js';\nimport c from 'baz.js';",
+    ]);
+  }
+
   #[test]
   fn sort_imports_test() {
     // Sort imports alphabetically
     assert_lint_err_on_line::<SortImports>(
       "import a from 'foo.js';\nimport A from 'bar.js';",
       2,
-      0,
+      14,
     );
     assert_lint_err_on_line::<SortImports>(
       "import b from 'foo.js';\nimport a from 'bar.js';",
       2,
-      0,
+      14,
     );
     assert_lint_err_on_line::<SortImports>(
       "import {b, c} from 'foo.js';\nimport {a, d} from 'bar.js';",
       2,
-      0,
+      19,
     );
     assert_lint_err_on_line::<SortImports>(
       "import * as foo from 'foo.js';\nimport * as bar from 'bar.js';",
       2,
-      0,
+      21,
     );
 
     // Unexpected syntax order
     assert_lint_err_on_line::<SortImports>(
       "import a from 'foo.js';\nimport {b, c} from 'bar.js';",
       2,
-      0,
+      19,
     );
     assert_lint_err_on_line::<SortImports>(
       "import a from 'foo.js';\nimport * as b from 'bar.js';",
       2,
-      0,
+      19,
     );
     assert_lint_err_on_line::<SortImports>(
       "import a from 'foo.js';\nimport 'bar.js';",
       2,
-      0,
+      7,
     );
 
     // Sort members alphabetically
-    assert_lint_err_on_line::<SortImports>(
+    assert_lint_err_on_line_n::<SortImports>(
       "import {b, a, d, c} from 'foo.js';\nimport {e, f, g, h} from 'bar.js';",
-      1,
-      11,
+      vec![(1, 11), (1, 17)],
     );
-    assert_lint_err_on_line::<SortImports>(
+    assert_lint_err_on_line_n::<SortImports>(
       "import {a, B, c, D} from 'foo.js';",
-      1,
-      11,
+      vec![(1, 11), (1, 17)],
     );
     assert_lint_err_on_line::<SortImports>(
       "import {zzzzz, /* comment */ aaaaa} from 'foo.js';",
@@ -391,7 +767,7 @@ mod tests {
       1,
       15,
     );
-    assert_lint_err_on_line::<SortImports>(
+    assert_lint_err_on_line_n::<SortImports>(
       r#"import {
       boop,
       foo,
@@ -400,8 +776,672 @@ mod tests {
       bar,
       beep
     } from 'foo.js';"#,
-      5,
-      13,
+      vec![(5, 13), (6, 6)],
+    );
+  }
+
+  fn sort_imports_with(options: SortImportsOptions) -> Box<SortImports> {
+    SortImports::with_options(options).unwrap()
+  }
+
+  fn order(import_types: &[&str]) -> Vec<String> {
+    import_types.iter().map(|s| s.to_string()).collect()
+  }
+
+  #[test]
+  fn sort_imports_ignore_case() {
+    let ignore_case = || {
+      sort_imports_with(SortImportsOptions {
+        ignore_case: true,
+        ..Default::default()
+      })
+    };
+    assert_lint_ok_with(ignore_case(), "import {a, b, C, d} from 'foo.js';");
+    assert_lint_ok_with(ignore_case(), "import {a, B} from 'foo.js';");
+    assert_lint_ok_with(
+      ignore_case(),
+      "import a from 'foo.js';\nimport B from 'bar.js';\nimport c from 'baz.js';",
+    );
+    assert_lint_ok_with(
+      ignore_case(),
+      "import * as A from 'foo.js';\nimport * as b from 'bar.js';\nimport {C, d} from 'baz.js';",
+    );
+    // Letters are still sorted
+    assert_lint_err_on_line_n_with(
+      ignore_case(),
+      "import {b, A} from 'foo.js';",
+      vec![(1, 11)],
+    );
+
+    let sources = [
+      "import {a, B, c} from 'foo.js';",
+      "import a from 'foo.js';\nimport B from 'bar.js';",
+    ];
+    for source in sources.iter() {
+      assert_eq!(lint(SortImports::new(), source).len(), 1);
+      assert_lint_ok_with(
+        sort_imports_with(SortImportsOptions {
+          ignore_case: true,
+          ..Default::default()
+        }),
+        source,
+      );
+    }
+  }
+
+  #[test]
+  fn sort_imports_ignore_declaration_sort() {
+    let options = || SortImportsOptions {
+      ignore_declaration_sort: true,
+      ..Default::default()
+    };
+    assert_lint_ok_with(
+      sort_imports_with(options()),
+      "import b from 'foo.js';\nimport a from 'bar.js';",
+    );
+    assert_lint_ok_with(
+      sort_imports_with(options()),
+      "import a from 'foo.js';\nimport * as b from 'bar.js';",
+    );
+    // Members are still sorted
+    assert_lint_err_on_line_n_with(
+      sort_imports_with(options()),
+      "import {b, a} from 'foo.js';",
+      vec![(1, 11)],
+    );
+  }
+
+  #[test]
+  fn sort_imports_ignore_member_sort() {
+    let options = || SortImportsOptions {
+      ignore_member_sort: true,
+      ..Default::default()
+    };
+    assert_lint_ok_with(
+      sort_imports_with(options()),
+      "import {b, a, d, c} from 'foo.js';\nimport {e, f, g, h} from 'bar.js';",
+    );
+    // Declarations are still sorted
+    assert_lint_err_on_line_n_with(
+      sort_imports_with(options()),
+      "import {b, c} from 'foo.js';\nimport {a, d} from 'bar.js';",
+      vec![(2, 19)],
+    );
+  }
+
+  #[test]
+  fn sort_imports_member_syntax_sort_order() {
+    let options = || SortImportsOptions {
+      member_syntax_sort_order: order(&["single", "all", "multiple", "none"]),
+      ..Default::default()
+    };
+    assert_lint_ok_with(
+      sort_imports_with(options()),
+      "import a from 'foo.js';\nimport * as b from 'bar.js';",
+    );
+    assert_lint_err_on_line_n_with(
+      sort_imports_with(options()),
+      "import * as a from 'foo.js';\nimport b from 'bar.js';",
+      vec![(2, 14)],
+    );
+    let diagnostics = lint(
+      sort_imports_with(options()),
+      "import {b, c} from 'foo.js';\nimport a from 'bar.js';",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Expected 'single' syntax before 'multiple' syntax"
+    );
+  }
+
+  #[test]
+  fn sort_imports_side_effect_imports() {
+    assert_lint_ok_n::<SortImports>(vec![
+      "import 'zzz.js';\nimport aaa from 'a.js';",
+      "import 'z.js';\nimport 'a.js';\nimport a from 'a.js';\nimport b from 'b.js';",
+    ]);
+    assert_lint_ok_with(
+      sort_imports_with(SortImportsOptions {
+        member_syntax_sort_order: order(&["single", "all", "multiple", "none"]),
+        ..Default::default()
+      }),
+      "import a from 'a.js';\nimport b from 'b.js';\nimport 'zzz.js';\nimport 'aaa.js';",
+    );
+
+    // Interleaved side effect imports are still checked for their syntax
+    // order, but never compared alphabetically
+    let diagnostics = lint(
+      SortImports::new(),
+      "import a from 'a.js';\nimport 'z.js';\nimport b from 'b.js';",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(
+      diagnostics[0].message,
+      "Expected 'none' syntax before 'single' syntax"
+    );
+    assert_eq!(
+      fix(
+        "import a from 'a.js';\nimport 'z.js';\nimport 'y.js';",
+        SortImportsOptions::default()
+      ),
+      "import 'z.js';\nimport 'y.js';\nimport a from 'a.js';"
+    );
+  }
+
+  #[test]
+  fn sort_imports_default_and_named_specifiers() {
+    let import_types = |source: &str| {
+      lint(
+        sort_imports_with(SortImportsOptions {
+          member_syntax_sort_order: order(&[
+            "single", "all", "multiple", "none",
+          ]),
+          ..Default::default()
+        }),
+        source,
+      )
+      .into_iter()
+      .map(|diagnostic| diagnostic.message)
+      .collect::<Vec<_>>()
+    };
+    // Default only
+    assert_eq!(
+      import_types("import * as a from 'a.js';\nimport b from 'b.js';"),
+      vec!["Expected 'single' syntax before 'all' syntax"]
+    );
+    // Default and named
+    assert_eq!(
+      import_types("import a, {b} from 'a.js';\nimport * as c from 'c.js';"),
+      vec!["Expected 'all' syntax before 'multiple' syntax"]
+    );
+    // Default and namespace
+    assert_eq!(
+      import_types("import a, * as b from 'a.js';\nimport * as c from 'c.js';"),
+      vec!["Expected 'all' syntax before 'multiple' syntax"]
+    );
+    // Named only
+    assert_eq!(
+      import_types("import {a} from 'a.js';\nimport {b, c} from 'b.js';"),
+      Vec::<String>::new()
+    );
+    assert_eq!(
+      import_types("import {a, b} from 'a.js';\nimport {c} from 'c.js';"),
+      vec!["Expected 'single' syntax before 'multiple' syntax"]
+    );
+
+    // The declaration is compared by the name of its first specifier
+    assert_lint_ok_n::<SortImports>(vec![
+      "import React, {useEffect, useState} from 'react';\nimport {z} from 'z.js';",
+      "import {b, c} from 'bc.js';\nimport c, {a} from 'a.js';",
+      "import a, * as z from 'z.js';\nimport {b, c} from 'bc.js';",
+    ]);
+    assert_lint_err_on_line::<SortImports>(
+      "import c, {a} from 'a.js';\nimport {b, d} from 'bd.js';",
+      2,
+      19,
+    );
+  }
+
+  #[test]
+  fn sort_imports_invalid_member_syntax_sort_order() {
+    let reason = |import_types: &[&str]| {
+      SortImports::with_options(SortImportsOptions {
+        member_syntax_sort_order: order(import_types),
+        ..Default::default()
+      })
+      .err()
+      .unwrap()
+      .reason
+    };
+    assert_eq!(
+      reason(&["none", "all", "multiple", "one"]),
+      "unknown syntax `one` in memberSyntaxSortOrder, expected one of \
+       `none`, `all`, `multiple` or `single`"
+    );
+    assert_eq!(
+      reason(&["none", "all", "all", "single"]),
+      "`all` is listed twice in memberSyntaxSortOrder"
+    );
+    assert_eq!(
+      reason(&["none", "all", "single"]),
+      "memberSyntaxSortOrder must list all 4 syntaxes, found 3"
+    );
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn sort_imports_from_json() {
+    let rule = SortImports::from_json(serde_json::json!({
+      "ignoreCase": true,
+      "memberSyntaxSortOrder": ["all", "single", "multiple", "none"],
+      "sortBy": "source",
+    }))
+    .unwrap();
+    assert!(rule.options.ignore_case);
+    assert_eq!(rule.options.sort_by, SortBy::Source);
+    assert!(!rule.options.ignore_member_sort);
+    assert_eq!(
+      rule.member_syntax_sort_order,
+      vec![
+        ImportTypes::All,
+        ImportTypes::Single,
+        ImportTypes::Multiple,
+        ImportTypes::None
+      ]
+    );
+
+    let err = SortImports::from_json(serde_json::json!({
+      "memberSyntaxSortOrder": ["all", "single"],
+    }))
+    .err()
+    .unwrap();
+    assert_eq!(
+      err.to_string(),
+      "Invalid sort-imports options: memberSyntaxSortOrder must list all 4 \
+       syntaxes, found 2"
+    );
+    assert!(
+      SortImports::from_json(serde_json::json!({ "ignoreCase": "yes" }))
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn sort_imports_reports_every_unsorted_member() {
+    assert_lint_err_on_line_n::<SortImports>(
+      "import {z, y, x, w} from 'm';",
+      vec![(1, 11), (1, 14), (1, 17)],
+    );
+    // Ordered by span, before the following declarations
+    assert_lint_err_on_line_n::<SortImports>(
+      "import {d, c} from 'b';\nimport {b, a} from 'a';",
+      vec![(1, 11), (2, 11), (2, 19)],
+    );
+  }
+
+  #[test]
+  fn sort_imports_reports_declarations_at_module_specifier() {
+    assert_lint_err_on_line_n::<SortImports>(
+      r#"import { b } from "b.js";
+import {
+  a,
+} from "a.js";"#,
+      vec![(4, 7)],
+    );
+    assert_lint_err_on_line_n::<SortImports>(
+      r#"import a from "a.js";
+import {
+  b,
+  c,
+} from
+  "b.js";"#,
+      vec![(6, 2)],
+    );
+    let diagnostics = lint(
+      SortImports::new(),
+      "import a from 'a.js'; // comment\nimport * as b from 'b.js';",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Expected 'all' syntax before 'single' syntax"
+    );
+    assert_eq!(diagnostics[0].range.start.col, 19);
+    assert_eq!(diagnostics[0].range.end.col, 25);
+  }
+
+  #[test]
+  fn sort_imports_allow_separated_groups() {
+    let options = || SortImportsOptions {
+      allow_separated_groups: true,
+      ..Default::default()
+    };
+    let blank_line =
+      "import b from 'b.js';\nimport c from 'c.js';\n\nimport a from 'a.js';";
+    let statement =
+      "import b from 'b.js';\nconst x = 1;\nimport a from 'a.js';";
+    let comment = "import b from 'b.js';\n// local\nimport a from 'a.js';";
+
+    // Off by default
+    assert_lint_err_on_line::<SortImports>(blank_line, 4, 14);
+    assert_lint_err_on_line::<SortImports>(statement, 3, 14);
+
+    assert_lint_ok_with(sort_imports_with(options()), blank_line);
+    assert_lint_ok_with(sort_imports_with(options()), statement);
+    assert_lint_ok_with(
+      sort_imports_with(options()),
+      "import a from 'a.js';\n\nimport * as b from 'b.js';",
+    );
+    // A comment isn't a separator
+    assert_lint_err_on_line_n_with(
+      sort_imports_with(options()),
+      comment,
+      vec![(3, 14)],
+    );
+    // Within a group, declarations are still sorted
+    assert_lint_err_on_line_n_with(
+      sort_imports_with(options()),
+      "import x from 'x.js';\n\nimport b from 'b.js';\nimport a from 'a.js';",
+      vec![(4, 14)],
+    );
+  }
+
+  #[test]
+  fn sort_imports_sort_by_source() {
+    let options = || SortImportsOptions {
+      sort_by: SortBy::Source,
+      ..Default::default()
+    };
+    // Sorted by local name, but not by source
+    let by_local_name = "import a from './z.ts';\nimport b from './y.ts';";
+    // Sorted by source, but not by local name
+    let by_source = "import b from './y.ts';\nimport a from './z.ts';";
+
+    assert_lint_ok::<SortImports>(by_local_name);
+    assert_lint_err_on_line::<SortImports>(by_source, 2, 14);
+
+    assert_lint_ok_with(sort_imports_with(options()), by_source);
+    let diagnostics = lint(sort_imports_with(options()), by_local_name);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(diagnostics[0].range.start.col, 14);
+    assert_eq!(
+      diagnostics[0].message,
+      "Import of './y.ts' should be sorted alphabetically by module path"
+    );
+
+    // Side effect imports aren't sorted, as that could change the order
+    // their side effects run in
+    assert_lint_ok_with(
+      sort_imports_with(options()),
+      "import 'a.js';\nimport 'b.js';",
+    );
+    assert_lint_ok_with(
+      sort_imports_with(options()),
+      "import 'b.js';\nimport 'a.js';",
+    );
+
+    let diagnostics = lint(
+      sort_imports_with(options()),
+      "import a from 'a.js';\nimport * as b from 'b.js';",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Expected 'all' syntax of 'b.js' before 'single' syntax"
+    );
+
+    // Members are still sorted by their local name
+    assert_lint_err_on_line_n_with(
+      sort_imports_with(options()),
+      "import {b, a} from 'a.js';",
+      vec![(1, 11)],
+    );
+  }
+
+  #[test]
+  fn sort_imports_ignore_type_imports() {
+    let source = r#"import a from 'a.js';
+import type { z } from 'z.js';
+import b from 'b.js';
+import type { y, x } from 'y.js';
+import c from 'c.js';"#;
+    assert_lint_err_with_messages::<SortImports>(
+      source,
+      vec![
+        (3, 14, "Imports should be sorted alphabetically"),
+        (4, 17, "Member 'x' of the import declaration"),
+        (4, 26, "Expected 'multiple' syntax before 'single' syntax"),
+      ],
+    );
+
+    let options = || SortImportsOptions {
+      ignore_type_imports: true,
+      ..Default::default()
+    };
+    assert_lint_ok_with(sort_imports_with(options()), source);
+    assert_lint_err_on_line_n_with(
+      sort_imports_with(options()),
+      "import b from 'b.js';\nimport type { z } from 'z.js';\nimport a from 'a.js';",
+      vec![(3, 14)],
+    );
+
+    // Type-only imports are classified by their specifiers
+    let diagnostics = lint(
+      SortImports::new(),
+      "import type { a, b } from 'a.js';\nimport type * as c from 'c.js';",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].message,
+      "Expected 'all' syntax before 'multiple' syntax"
+    );
+  }
+
+  #[test]
+  fn sort_imports_ignore_case_unicode() {
+    let ignore_case = || {
+      sort_imports_with(SortImportsOptions {
+        ignore_case: true,
+        ..Default::default()
+      })
+    };
+    // Mixed-case ASCII
+    assert_lint_ok_with(ignore_case(), "import {a, B, c, D} from 'foo.js';");
+    assert_lint_err_on_line_n_with(
+      ignore_case(),
+      "import {B, a} from 'foo.js';",
+      vec![(1, 11)],
+    );
+    // Accented Latin identifiers are folded too
+    assert_lint_ok_with(
+      ignore_case(),
+      "import {ärger, Öl, über} from 'foo.js';",
+    );
+    assert_lint_ok_with(
+      ignore_case(),
+      "import {Ärger, öl, Über} from 'foo.js';",
+    );
+    assert_lint_err_on_line_n_with(
+      ignore_case(),
+      "import {Über, ärger} from 'foo.js';",
+      vec![(1, 14)],
+    );
+    assert_lint_ok_with(
+      ignore_case(),
+      "import Ärger from 'a.js';\nimport überSetzer from 'b.js';",
+    );
+    // Without ignoreCase, code points are compared: uppercase `Ä` comes
+    // before lowercase `ä`, but after all of ASCII
+    assert_lint_err_on_line_n::<SortImports>(
+      "import {Ärger, über, Zeit} from 'foo.js';",
+      vec![(1, 21)],
     );
+    assert_lint_ok::<SortImports>("import {Zeit, Ärger, über} from 'foo.js';");
+    // Names only differing by case keep their order either way
+    assert_lint_ok_with(ignore_case(), "import {a, A} from 'foo.js';");
+    assert_lint_ok_with(ignore_case(), "import {A, a} from 'foo.js';");
+    assert_lint_ok_with(ignore_case(), "import {Ärger, ärger} from 'foo.js';");
+    assert_lint_err_on_line_n::<SortImports>(
+      "import {a, A} from 'foo.js';",
+      vec![(1, 11)],
+    );
+  }
+
+  #[test]
+  fn sort_imports_diagnostic_ranges() {
+    let source = "import {zz, aa} from 'b.js';\nimport * as a from\n  'a.js';";
+    let ranges = lint(SortImports::new(), source)
+      .iter()
+      .map(|diagnostic| {
+        (
+          (diagnostic.range.start.line, diagnostic.range.start.col),
+          (diagnostic.range.end.line, diagnostic.range.end.col),
+          diagnostic.line_text.clone(),
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      ranges,
+      vec![
+        ((1, 12), (1, 14), "import {zz, aa} from 'b.js';".to_string()),
+        ((3, 2), (3, 8), "  'a.js';".to_string()),
+      ]
+    );
+  }
+
+  /// Lints `source` and applies the fixes until it's clean. Fixes
+  /// overlapping one that's already applied wait for the next round.
+  fn fix(source: &str, options: SortImportsOptions) -> String {
+    let mut source = source.to_string();
+    for _ in 0..10 {
+      let diagnostics = lint(sort_imports_with(options.clone()), &source);
+      if diagnostics.is_empty() {
+        return source;
+      }
+      let mut applied: Vec<&LintDiagnostic> = vec![];
+      for diagnostic in diagnostics.iter().filter(|d| d.fix.is_some()) {
+        if applied.iter().all(|other| !diagnostic.fix_overlaps(other)) {
+          applied.push(diagnostic);
+        }
+      }
+      assert!(!applied.is_empty(), "no fix for {:?}", diagnostics);
+      let mut edits = applied
+        .iter()
+        .flat_map(|diagnostic| diagnostic.fix.clone().unwrap())
+        .collect::<Vec<_>>();
+      edits.sort_by_key(|edit| edit.byte_range.start);
+      source = apply_edits(&source, &edits);
+    }
+    panic!("fixes didn't converge: {}", source);
+  }
+
+  #[test]
+  fn sort_imports_fix_members() {
+    let cases = [
+      (
+        "import {c, a, b} from 'a.js';",
+        "import {a, b, c} from 'a.js';",
+      ),
+      (
+        "import {bar, baz as qux, alpha} from 'a.js';",
+        "import {alpha, bar, baz as qux} from 'a.js';",
+      ),
+      (
+        "import {\n  b, // second\n  a, // first\n} from 'a.js';",
+        "import {\n  a, // second\n  b, // first\n} from 'a.js';",
+      ),
+      (
+        "import {b, a} from 'a.js';\nimport {d, c} from 'b.js';",
+        "import {a, b} from 'a.js';\nimport {c, d} from 'b.js';",
+      ),
+    ];
+    for (source, expected) in cases.iter() {
+      assert_eq!(&fix(source, SortImportsOptions::default()), expected);
+    }
+  }
+
+  #[test]
+  fn sort_imports_fix_declarations() {
+    let cases = [
+      (
+        "import b from 'b.js';\nimport a from 'a.js';",
+        "import a from 'a.js';\nimport b from 'b.js';",
+      ),
+      (
+        "import a from 'a.js';\nimport {b, c} from 'bc.js';\nimport * as d from 'd.js';",
+        "import * as d from 'd.js';\nimport {b, c} from 'bc.js';\nimport a from 'a.js';",
+      ),
+      (
+        "import {d, c} from 'd.js';\nimport {b, a} from 'a.js';",
+        "import {a, b} from 'a.js';\nimport {c, d} from 'd.js';",
+      ),
+      (
+        "import 'z.js';\nimport b from 'b.js';\n// comment\nimport a from 'a.js';",
+        "import 'z.js';\nimport a from 'a.js';\n// comment\nimport b from 'b.js';",
+      ),
+    ];
+    for (source, expected) in cases.iter() {
+      assert_eq!(&fix(source, SortImportsOptions::default()), expected);
+    }
+
+    assert_eq!(
+      fix(
+        "import d from 'd.js';\nimport c from 'c.js';\n\nimport b from 'b.js';\nimport a from 'a.js';",
+        SortImportsOptions {
+          allow_separated_groups: true,
+          ..Default::default()
+        }
+      ),
+      "import c from 'c.js';\nimport d from 'd.js';\n\nimport a from 'a.js';\nimport b from 'b.js';"
+    );
+  }
+
+  #[test]
+  fn sort_imports_fix_attached_once() {
+    let diagnostics = lint(SortImports::new(), "import {c, b, a} from 'a.js';");
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics[0].fix.is_some());
+    assert!(diagnostics[1].fix.is_none());
+  }
+
+  #[test]
+  fn sort_imports_hints() {
+    let hints = |source: &str| {
+      lint(SortImports::new(), source)
+        .into_iter()
+        .map(|diagnostic| diagnostic.hint.unwrap())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(
+      hints("import {zzz, aaa} from 'a.js';"),
+      vec!["Run with --fix or reorder the specifiers alphabetically; expected 'aaa' to come before 'zzz'"]
+    );
+    assert_eq!(
+      hints("import {c, b, a} from 'a.js';"),
+      vec![
+        "Run with --fix or reorder the specifiers alphabetically; expected 'b' to come before 'c'",
+        "Run with --fix or reorder the specifiers alphabetically; expected 'a' to come before 'b'",
+      ]
+    );
+    assert_eq!(
+      hints("import b from 'b.js';\nimport a from 'a.js';"),
+      vec!["Run with --fix or reorder the import declarations alphabetically; expected 'a' to come before 'b'"]
+    );
+    assert_eq!(
+      hints("import a from 'a.js';\nimport 'polyfill.js';"),
+      vec!["Run with --fix or reorder the import declarations by syntax; expected the import of 'polyfill.js' to come before the import of 'a.js'"]
+    );
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn sort_imports_hint_json() {
+    let diagnostics = lint(SortImports::new(), "import {b, a} from 'a.js';");
+    let json = serde_json::to_value(&diagnostics[0]).unwrap();
+    assert_eq!(
+      json["hint"],
+      "Run with --fix or reorder the specifiers alphabetically; expected 'a' to come before 'b'"
+    );
+  }
+
+  #[test]
+  fn sort_imports_tsx() {
+    let source = r#"import { useState, h } from "preact/hooks";
+import { Button } from "./button.tsx";
+
+export function Counter<T,>(props: { label: T }) {
+  const [count, setCount] = useState(0);
+  return <Button onClick={() => setCount(count + 1)}>{props.label}</Button>;
+}
+"#;
+    let diagnostics = lint_as(SortImports::new(), "counter.tsx", source);
+    let positions = diagnostics
+      .iter()
+      .map(|d| (d.range.start.line, d.range.start.col))
+      .collect::<Vec<_>>();
+    assert_eq!(positions, vec![(1, 19)]);
   }
 }
@@ -0,0 +1,299 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::sort_util::{compare, SortOrder};
+use std::cmp::Ordering;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  Expr, Lit, TsInterfaceBody, TsMethodSignature, TsPropertySignature,
+  TsTypeElement, TsTypeLit,
+};
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+pub struct SortInterfaceMembersOptions {
+  pub order: SortOrder,
+  pub ignore_case: bool,
+  /// Requires index signatures first, then call and construct signatures,
+  /// then properties and methods. Only the latter are sorted by name.
+  pub group_kinds: bool,
+}
+
+impl Default for SortInterfaceMembersOptions {
+  fn default() -> Self {
+    Self {
+      order: SortOrder::Alphabetical,
+      ignore_case: false,
+      group_kinds: true,
+    }
+  }
+}
+
+pub struct SortInterfaceMembers {
+  options: SortInterfaceMembersOptions,
+}
+
+impl SortInterfaceMembers {
+  pub fn with_options(options: SortInterfaceMembersOptions) -> Box<Self> {
+    Box::new(SortInterfaceMembers { options })
+  }
+}
+
+impl LintRule for SortInterfaceMembers {
+  fn new() -> Box<Self> {
+    Self::with_options(SortInterfaceMembersOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "sort-interface-members"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires the members of interfaces and object type literals to be sorted by name.
+
+### Options
+
+- `SortInterfaceMembersOptions::order`: `SortOrder::Alphabetical` (default), where `a10` comes before `a2`, or `SortOrder::Natural`, which compares runs of digits by their value.
+- `SortInterfaceMembersOptions::ignore_case`: sorts case-insensitively, `false` by default.
+- `SortInterfaceMembersOptions::group_kinds`: requires index signatures first, then call and construct signatures, then properties and methods, `true` by default. Only the latter are sorted by name.
+
+### Valid:
+```typescript
+interface A {
+  a: string;
+  b(): void;
+  c?: number;
+}
+```
+
+### Invalid:
+```typescript
+interface A {
+  b: string;
+  a: string;
+}
+```
+```typescript
+type T = { b: string; a: string };
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = SortInterfaceMembersVisitor {
+      context,
+      options: &self.options,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+enum MemberKind {
+  IndexSignature,
+  CallSignature,
+  Property,
+}
+
+impl MemberKind {
+  fn describe(self) -> &'static str {
+    match self {
+      MemberKind::IndexSignature => "index signatures",
+      MemberKind::CallSignature => "call and construct signatures",
+      MemberKind::Property => "properties",
+    }
+  }
+}
+
+struct SortInterfaceMembersVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a SortInterfaceMembersOptions,
+}
+
+impl<'a> SortInterfaceMembersVisitor<'a> {
+  fn key_name(&self, key: &Expr, computed: bool) -> String {
+    match key {
+      Expr::Ident(ident) if !computed => ident.sym.to_string(),
+      Expr::Lit(Lit::Str(str)) => str.value.to_string(),
+      Expr::Lit(Lit::Num(number)) => number.value.to_string(),
+      _ => self.context.span_text(key.span()),
+    }
+  }
+
+  /// Returns the kind of a member and the name it's sorted by, if any.
+  fn classify(&self, member: &TsTypeElement) -> (MemberKind, Option<String>) {
+    match member {
+      TsTypeElement::TsIndexSignature(_) => (MemberKind::IndexSignature, None),
+      TsTypeElement::TsCallSignatureDecl(_)
+      | TsTypeElement::TsConstructSignatureDecl(_) => {
+        (MemberKind::CallSignature, None)
+      }
+      TsTypeElement::TsPropertySignature(TsPropertySignature {
+        key,
+        computed,
+        ..
+      })
+      | TsTypeElement::TsMethodSignature(TsMethodSignature {
+        key,
+        computed,
+        ..
+      }) => (MemberKind::Property, Some(self.key_name(key, *computed))),
+    }
+  }
+
+  fn check_members(&self, members: &[TsTypeElement]) {
+    let classified = members
+      .iter()
+      .map(|member| self.classify(member))
+      .collect::<Vec<_>>();
+
+    for index in 1..classified.len() {
+      let (prev_kind, prev_name) = &classified[index - 1];
+      let (kind, name) = &classified[index];
+
+      if self.options.group_kinds && kind != prev_kind {
+        if kind < prev_kind {
+          self.context.add_diagnostic(
+            members[index].span(),
+            "sort-interface-members",
+            &format!(
+              "Expected {} before {}",
+              kind.describe(),
+              prev_kind.describe()
+            ),
+          );
+          return;
+        }
+        continue;
+      }
+
+      if let (Some(prev_name), Some(name)) = (prev_name, name) {
+        let ordering = compare(
+          name,
+          prev_name,
+          self.options.order,
+          self.options.ignore_case,
+        );
+        if ordering == Ordering::Less {
+          self.context.add_diagnostic(
+            members[index].span(),
+            "sort-interface-members",
+            &format!(
+              "Member '{}' should be sorted {}",
+              name,
+              self.options.order.describe()
+            ),
+          );
+          return;
+        }
+      }
+    }
+  }
+}
+
+impl<'a> Visit for SortInterfaceMembersVisitor<'a> {
+  fn visit_ts_interface_body(
+    &mut self,
+    body: &TsInterfaceBody,
+    parent: &dyn Node,
+  ) {
+    self.check_members(&body.body);
+    swc_ecmascript::visit::visit_ts_interface_body(self, body, parent);
+  }
+
+  fn visit_ts_type_lit(&mut self, type_lit: &TsTypeLit, parent: &dyn Node) {
+    self.check_members(&type_lit.members);
+    swc_ecmascript::visit::visit_ts_type_lit(self, type_lit, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn sort_interface_members_valid() {
+    assert_lint_ok_n::<SortInterfaceMembers>(vec![
+      "interface A { a: string; b(): void; c?: number; }",
+      "interface A extends Z, Y { a: string; b: string; }",
+      "interface A { [key: string]: unknown; (): void; new (): A; a: string; }",
+      "interface A { new (): A; (): void; }",
+      "type T = { a: string; b: { c: number; d: number; }; };",
+      "interface A { 'a-b': string; b: string; [c]: string; }",
+      "interface A {}",
+    ]);
+  }
+
+  #[test]
+  fn sort_interface_members_invalid() {
+    assert_lint_err::<SortInterfaceMembers>(
+      "interface A { b: string; a: string; }",
+      25,
+    );
+    assert_lint_err::<SortInterfaceMembers>(
+      "interface A { a(): void; C: string; }",
+      25,
+    );
+    assert_lint_err::<SortInterfaceMembers>(
+      "interface A extends B { c: string; a: string; }",
+      35,
+    );
+    assert_lint_err::<SortInterfaceMembers>(
+      "type T = { b: string; a: string; };",
+      22,
+    );
+    assert_lint_err::<SortInterfaceMembers>(
+      "type T = { a: { d: string; c: string; }; };",
+      27,
+    );
+    assert_lint_err::<SortInterfaceMembers>(
+      "interface A { a: string; [key: string]: string; }",
+      25,
+    );
+    assert_lint_err::<SortInterfaceMembers>(
+      "interface A { a: string; (): void; }",
+      25,
+    );
+    assert_lint_err_on_line::<SortInterfaceMembers>(
+      "interface A {\n  (): void;\n  [key: string]: unknown;\n}",
+      3,
+      2,
+    );
+  }
+
+  #[test]
+  fn sort_interface_members_options() {
+    let ungrouped =
+      SortInterfaceMembers::with_options(SortInterfaceMembersOptions {
+        group_kinds: false,
+        ..Default::default()
+      });
+    assert_lint_ok_with(
+      ungrouped,
+      "interface A { a: string; (): void; [k: string]: any; b: string; }",
+    );
+
+    let natural =
+      SortInterfaceMembers::with_options(SortInterfaceMembersOptions {
+        order: SortOrder::Natural,
+        ..Default::default()
+      });
+    assert_lint_ok_with(natural, "interface A { a2: string; a10: string; }");
+
+    let ignore_case =
+      SortInterfaceMembers::with_options(SortInterfaceMembersOptions {
+        ignore_case: true,
+        ..Default::default()
+      });
+    assert_lint_ok_with(ignore_case, "interface A { a: string; B: string; }");
+  }
+}
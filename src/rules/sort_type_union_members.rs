@@ -0,0 +1,224 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use super::LintRule;
+use crate::sort_util::{compare, SortOrder};
+use std::cmp::Ordering;
+use std::sync::Arc;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  TsEntityName, TsLit, TsLitType, TsType, TsTypeRef, TsUnionType,
+};
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+pub struct SortTypeUnionMembersOptions {
+  pub order: SortOrder,
+  pub ignore_case: bool,
+  /// Unions with fewer constituents than this are not checked.
+  pub min_members: usize,
+}
+
+impl Default for SortTypeUnionMembersOptions {
+  fn default() -> Self {
+    Self {
+      order: SortOrder::Alphabetical,
+      ignore_case: false,
+      min_members: 2,
+    }
+  }
+}
+
+pub struct SortTypeUnionMembers {
+  options: SortTypeUnionMembersOptions,
+}
+
+impl SortTypeUnionMembers {
+  pub fn with_options(options: SortTypeUnionMembersOptions) -> Box<Self> {
+    Box::new(SortTypeUnionMembers { options })
+  }
+}
+
+impl LintRule for SortTypeUnionMembers {
+  fn new() -> Box<Self> {
+    Self::with_options(SortTypeUnionMembersOptions::default())
+  }
+
+  fn code(&self) -> &'static str {
+    "sort-type-union-members"
+  }
+
+  fn has_options(&self) -> bool {
+    true
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires the constituents of union types to be sorted.
+
+### Options
+
+- `SortTypeUnionMembersOptions::order`: `SortOrder::Alphabetical` (default) or `SortOrder::Natural`.
+- `SortTypeUnionMembersOptions::ignore_case`: sorts case-insensitively, `false` by default.
+- `SortTypeUnionMembersOptions::min_members`: unions with fewer constituents are not checked, `2` by default.
+
+### Valid:
+```typescript
+type T = "a" | "b" | "c";
+```
+
+### Invalid:
+```typescript
+type T = "b" | "a";
+```
+```typescript
+type U = string | number;
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = SortTypeUnionMembersVisitor {
+      context,
+      options: &self.options,
+    };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct SortTypeUnionMembersVisitor<'a> {
+  context: Arc<Context>,
+  options: &'a SortTypeUnionMembersOptions,
+}
+
+fn entity_name(name: &TsEntityName) -> String {
+  match name {
+    TsEntityName::Ident(ident) => ident.sym.to_string(),
+    TsEntityName::TsQualifiedName(qualified) => {
+      format!("{}.{}", entity_name(&qualified.left), qualified.right.sym)
+    }
+  }
+}
+
+impl<'a> SortTypeUnionMembersVisitor<'a> {
+  /// Returns the string a constituent is sorted by: the value of literal
+  /// types, the name of type references and the source text otherwise.
+  fn sortable_name(&self, ts_type: &TsType) -> String {
+    match ts_type {
+      TsType::TsParenthesizedType(paren) => self.sortable_name(&paren.type_ann),
+      TsType::TsLitType(TsLitType { lit, .. }) => match lit {
+        TsLit::Str(str) => str.value.to_string(),
+        TsLit::Number(number) => number.value.to_string(),
+        TsLit::Bool(bool) => bool.value.to_string(),
+        _ => self.context.span_text(ts_type.span()),
+      },
+      TsType::TsTypeRef(TsTypeRef { type_name, .. }) => entity_name(type_name),
+      _ => self.context.span_text(ts_type.span()),
+    }
+  }
+}
+
+impl<'a> Visit for SortTypeUnionMembersVisitor<'a> {
+  fn visit_ts_union_type(&mut self, union: &TsUnionType, parent: &dyn Node) {
+    if union.types.len() >= self.options.min_members {
+      let names = union
+        .types
+        .iter()
+        .map(|ts_type| self.sortable_name(ts_type))
+        .collect::<Vec<_>>();
+      let unsorted = (1..names.len()).find(|&index| {
+        compare(
+          &names[index],
+          &names[index - 1],
+          self.options.order,
+          self.options.ignore_case,
+        ) == Ordering::Less
+      });
+      if let Some(index) = unsorted {
+        self.context.add_diagnostic(
+          union.types[index].span(),
+          "sort-type-union-members",
+          &format!(
+            "Constituent '{}' of the union type should be sorted {}",
+            names[index],
+            self.options.order.describe()
+          ),
+        );
+      }
+    }
+    swc_ecmascript::visit::visit_ts_union_type(self, union, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::*;
+
+  #[test]
+  fn sort_type_union_members_valid() {
+    assert_lint_ok_n::<SortTypeUnionMembers>(vec![
+      "type T = 'a' | 'b' | 'c';",
+      "type T = A | B.C | D;",
+      "type T = (A[]) | B;",
+      "type T = string;",
+      "type T = Array<'b'> | B;",
+      "function f(a: 'x' | 'y'): void {}",
+      "interface A extends C, B { a: 'a' | 'b'; }",
+      "type T = (A extends B ? C : D) | E;",
+    ]);
+  }
+
+  #[test]
+  fn sort_type_union_members_invalid() {
+    assert_lint_err::<SortTypeUnionMembers>("type T = 'b' | 'a';", 15);
+    assert_lint_err::<SortTypeUnionMembers>("type T = 'a' | 'c' | 'b';", 21);
+    assert_lint_err::<SortTypeUnionMembers>("type T = (B) | A;", 15);
+    assert_lint_err::<SortTypeUnionMembers>("type T = string | number;", 18);
+    assert_lint_err::<SortTypeUnionMembers>("type T = B | (A | C);", 13);
+    assert_lint_err::<SortTypeUnionMembers>(
+      "type T = (X extends B ? C : D) | (A extends B ? C : D);",
+      33,
+    );
+    assert_lint_err::<SortTypeUnionMembers>(
+      "interface A { a: 'b' | 'a'; }",
+      23,
+    );
+    assert_lint_err_on_line::<SortTypeUnionMembers>(
+      "type T =\n  | 'b'\n  | 'a';",
+      3,
+      4,
+    );
+  }
+
+  #[test]
+  fn sort_type_union_members_options() {
+    let natural =
+      SortTypeUnionMembers::with_options(SortTypeUnionMembersOptions {
+        order: SortOrder::Natural,
+        ..Default::default()
+      });
+    assert_lint_ok_with(natural, "type T = 1 | 2 | 10 | 'a2' | 'a10';");
+
+    let ignore_case =
+      SortTypeUnionMembers::with_options(SortTypeUnionMembersOptions {
+        ignore_case: true,
+        ..Default::default()
+      });
+    assert_lint_ok_with(ignore_case, "type T = a | B;");
+
+    let min_members = || {
+      SortTypeUnionMembers::with_options(SortTypeUnionMembersOptions {
+        min_members: 3,
+        ..Default::default()
+      })
+    };
+    assert_lint_ok_with(min_members(), "type T = 'b' | 'a';");
+    assert_lint_err_on_line_n_with(
+      min_members(),
+      "type T = 'c' | 'b' | 'a';",
+      vec![(1, 15)],
+    );
+  }
+}
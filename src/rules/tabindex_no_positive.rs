@@ -0,0 +1,116 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use super::Context;
+use crate::jsx_util::{attr_number_value, find_attr};
+use std::sync::Arc;
+use swc_ecmascript::ast::JSXOpeningElement;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+
+declare_rule! {
+  TabindexNoPositive, "tabindex-no-positive", tags: [];
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows positive `tabIndex` values on JSX elements.
+
+Positive values move the element ahead of the document order in the keyboard navigation, which confuses users of assistive technology. Use `0` to follow the document order, or `-1` to only focus the element programmatically.
+
+### Valid:
+```tsx
+<div tabIndex={0} />
+```
+```tsx
+<div tabIndex={-1} />
+```
+
+### Invalid:
+```tsx
+<div tabIndex={1} />
+```
+```tsx
+<div tabIndex="2" />
+```"#
+  }
+
+  fn lint_module(
+    &self,
+    context: Arc<Context>,
+    module: &swc_ecmascript::ast::Module,
+  ) {
+    let mut visitor = TabindexNoPositiveVisitor::new(context);
+    visitor.visit_module(module, module);
+  }
+}
+
+struct TabindexNoPositiveVisitor {
+  context: Arc<Context>,
+}
+
+impl TabindexNoPositiveVisitor {
+  fn new(context: Arc<Context>) -> Self {
+    Self { context }
+  }
+}
+
+impl Visit for TabindexNoPositiveVisitor {
+  noop_visit_type!();
+
+  fn visit_jsx_opening_element(
+    &mut self,
+    opening: &JSXOpeningElement,
+    parent: &dyn Node,
+  ) {
+    if let Some(attr) = find_attr(opening, "tabIndex") {
+      if let Some(value) = attr_number_value(attr) {
+        if value > 0.0 {
+          self.context.add_diagnostic_with_hint(
+            attr.span,
+            "tabindex-no-positive",
+            "Avoid positive `tabIndex` values",
+            "Use `tabIndex={0}` to follow the document order, or `tabIndex={-1}` to only focus the element programmatically",
+          );
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_jsx_opening_element(self, opening, parent);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::LintRule;
+  use crate::test_util::*;
+
+  #[test]
+  fn tabindex_no_positive_valid() {
+    for source in &[
+      "<div tabIndex={0} />",
+      "<div tabIndex={-1} />",
+      "<div tabIndex='0' />",
+      "<div tabIndex='-1' />",
+      "<div tabIndex={index} />",
+      "<div tabIndex='' />",
+      "<div />",
+    ] {
+      assert_lint_ok_tsx(TabindexNoPositive::new(), source);
+    }
+  }
+
+  #[test]
+  fn tabindex_no_positive_invalid() {
+    let rule = TabindexNoPositive::new;
+    assert_lint_err_on_line_n_tsx(rule(), "<div tabIndex={1} />", vec![(1, 5)]);
+    assert_lint_err_on_line_n_tsx(rule(), "<div tabIndex='2' />", vec![(1, 5)]);
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<div tabindex={'3'} />",
+      vec![(1, 5)],
+    );
+    assert_lint_err_on_line_n_tsx(
+      rule(),
+      "<Button tabIndex={(5)} />",
+      vec![(1, 8)],
+    );
+  }
+}
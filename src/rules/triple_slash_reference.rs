@@ -40,21 +40,35 @@ impl LintRule for TripleSlashReference {
     "triple-slash-reference"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Disallows triple slash reference directives, like `/// <reference types="foo" />`.
+
+ES module imports declare dependencies in a standard way.
+
+### Valid:
+```typescript
+import * as foo from "./foo.ts";
+```
+
+### Invalid:
+```typescript
+/// <reference types="foo" />
+import * as foo from "foo";
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
     _module: &swc_ecmascript::ast::Module,
   ) {
-    context.leading_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
-    context.trailing_comments.values().for_each(|comments| {
-      for comment in comments {
-        self.lint_comment(&context, comment);
-      }
-    });
+    for comment in context.all_comments() {
+      self.lint_comment(&context, comment);
+    }
   }
 }
 
@@ -18,6 +18,32 @@ impl LintRule for UseIsNaN {
     "use-isnan"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn docs(&self) -> &'static str {
+    r#"Requires `isNaN()` or `Number.isNaN()` to check for `NaN`.
+
+`NaN` isn't equal to anything, including itself, so comparisons with it are always `false`, and `case NaN` never matches.
+
+### Valid:
+```typescript
+if (Number.isNaN(foo)) {}
+```
+
+### Invalid:
+```typescript
+if (foo === NaN) {}
+```
+```typescript
+switch (foo) {
+  case NaN:
+    break;
+}
+```"#
+  }
+
   fn lint_module(
     &self,
     context: Arc<Context>,
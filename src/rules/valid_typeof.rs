@@ -21,6 +21,10 @@ impl LintRule for ValidTypeof {
     "valid-typeof"
   }
 
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
   fn lint_module(&self, context: Arc<Context>, module: &Module) {
     let mut visitor = ValidTypeofVisitor::new(context);
     visitor.visit_module(module, module);
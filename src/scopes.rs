@@ -1,11 +1,25 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+//! Scope analysis of a module: the bindings it declares, the scopes they
+//! belong to and the references to them. The linter analyzes each file
+//! once and shares the result with all rules through
+//! `Context::scope_manager`.
+//!
+//! Identifiers are told apart by their `Id`, which the resolver run after
+//! parsing makes unique per binding, so references declared later, like
+//! calls of hoisted functions, resolve too.
+
 use std::collections::HashMap;
 use swc_atoms::JsWord;
-use swc_common::DUMMY_SP;
+use swc_common::{BytePos, Span, DUMMY_SP};
 use swc_ecmascript::ast::{
-  ArrowExpr, BlockStmt, BlockStmtOrExpr, CatchClause, ClassDecl, ClassExpr,
-  DoWhileStmt, Expr, FnDecl, ForInStmt, ForOfStmt, ForStmt, Function, Ident,
+  ArrowExpr, AssignExpr, AssignOp, BlockStmt, BlockStmtOrExpr, CatchClause,
+  ClassDecl, ClassExpr, ClassProp, Constructor, DoWhileStmt, Expr, ExprOrSuper,
+  FnDecl, ForInStmt, ForOfStmt, ForStmt, Function, Ident,
   ImportDefaultSpecifier, ImportNamedSpecifier, ImportStarAsSpecifier, Invalid,
-  Module, Param, Pat, SwitchStmt, VarDecl, VarDeclKind, WhileStmt, WithStmt,
+  MemberExpr, Module, NamedExport, ObjectPatProp, Param, ParamOrTsParamProp,
+  Pat, PatOrExpr, Prop, SwitchStmt, TsEnumDecl, TsModuleDecl, TsModuleName,
+  TsParamPropParam, TsTypeParam, UpdateExpr, VarDecl, VarDeclKind,
+  VarDeclOrPat, WhileStmt, WithStmt,
 };
 use swc_ecmascript::utils::find_ids;
 use swc_ecmascript::utils::ident::IdentLike;
@@ -14,33 +28,140 @@ use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 use swc_ecmascript::visit::VisitWith;
 
+/// Index of a scope in `ScopeManager::scopes`.
+pub type ScopeId = usize;
+
+/// The scopes, bindings and references of a module.
 #[derive(Debug)]
-pub struct Scope {
-  vars: HashMap<Id, Var>,
+pub struct ScopeManager {
+  /// The module scope comes first and has no parent.
+  scopes: Vec<Scope>,
+  bindings: HashMap<Id, Binding>,
   symbols: HashMap<JsWord, Vec<Id>>,
+  references: Vec<Reference>,
+  /// The binding each declaring or referencing identifier refers to, by
+  /// the start and end of the identifier.
+  idents: HashMap<(BytePos, BytePos), Id>,
 }
 
-impl Scope {
-  // Get all declarations with a symbol.
-  #[allow(dead_code)]
+impl ScopeManager {
+  /// Scope of the module, which encloses all others.
+  pub const MODULE: ScopeId = 0;
+
+  pub fn scope(&self, scope: ScopeId) -> &Scope {
+    &self.scopes[scope]
+  }
+
+  pub fn scopes(&self) -> &[Scope] {
+    &self.scopes
+  }
+
+  /// Returns all bindings named `sym`, in the order of their declaration.
   pub fn ids_with_symbol(&self, sym: &JsWord) -> Option<&Vec<Id>> {
     self.symbols.get(sym)
   }
 
-  pub fn var(&self, id: &Id) -> Option<&Var> {
-    self.vars.get(id)
+  pub fn binding(&self, id: &Id) -> Option<&Binding> {
+    self.bindings.get(id)
+  }
+
+  /// Iterates over all the declared bindings, in no particular order.
+  pub fn bindings(&self) -> impl Iterator<Item = (&Id, &Binding)> {
+    self.bindings.iter()
+  }
+
+  /// Returns the binding declared or referenced by the identifier spanning
+  /// `span`, `None` for globals and identifiers which aren't references,
+  /// like property names.
+  pub fn lookup(&self, span: Span) -> Option<&Binding> {
+    let id = self.idents.get(&(span.lo, span.hi))?;
+    self.bindings.get(id)
+  }
+
+  /// All the references of the module, in the order they were found.
+  pub fn references(&self) -> &[Reference] {
+    &self.references
+  }
+
+  /// Iterates over the references to the binding `id`, in the order they
+  /// were found.
+  pub fn references_of<'a>(
+    &'a self,
+    id: &Id,
+  ) -> impl Iterator<Item = &'a Reference> {
+    let indices = match self.bindings.get(id) {
+      Some(binding) => binding.references.as_slice(),
+      None => &[],
+    };
+    indices.iter().map(move |index| &self.references[*index])
+  }
+
+  /// Returns the binding with the same name as `id` which is declared in
+  /// the closest scope enclosing the one of `id`, ie. the binding `id`
+  /// shadows.
+  pub fn shadowed_binding(&self, id: &Id) -> Option<&Binding> {
+    let binding = self.bindings.get(id)?;
+    let others = self.symbols.get(&id.0)?;
+    let mut scope = self.scopes[binding.scope].parent;
+    while let Some(current) = scope {
+      let shadowed = others
+        .iter()
+        .filter(|other| *other != id)
+        .filter_map(|other| self.bindings.get(other))
+        .find(|other| other.scope == current);
+      if shadowed.is_some() {
+        return shadowed;
+      }
+      scope = self.scopes[current].parent;
+    }
+    None
+  }
+}
+
+/// A lexical scope, eg. of a function or a block.
+#[derive(Debug)]
+pub struct Scope {
+  kind: ScopeKind,
+  span: Span,
+  parent: Option<ScopeId>,
+  bindings: Vec<Id>,
+}
+
+impl Scope {
+  pub fn kind(&self) -> ScopeKind {
+    self.kind
+  }
+
+  /// Span of the node creating the scope, eg. the whole function.
+  pub fn span(&self) -> Span {
+    self.span
+  }
+
+  /// The enclosing scope, `None` for the module scope.
+  pub fn parent(&self) -> Option<ScopeId> {
+    self.parent
+  }
+
+  /// The bindings declared in the scope, in the order of their declaration.
+  pub fn bindings(&self) -> &[Id] {
+    &self.bindings
   }
 }
 
 #[derive(Debug)]
-pub struct Var {
+pub struct Binding {
   path: Vec<ScopeKind>,
   kind: BindingKind,
+  span: Span,
+  scope: ScopeId,
+  /// Indices of the references to the binding in
+  /// `ScopeManager::references`.
+  references: Vec<usize>,
 }
 
-impl Var {
-  /// Empty path means root scope.
-  #[allow(dead_code)]
+impl Binding {
+  /// Kinds of the nodes enclosing the declaration, empty for the module
+  /// scope.
   pub fn path(&self) -> &[ScopeKind] {
     &self.path
   }
@@ -48,6 +169,17 @@ impl Var {
   pub fn kind(&self) -> BindingKind {
     self.kind
   }
+
+  /// Span of the identifier declaring the binding.
+  pub fn span(&self) -> Span {
+    self.span
+  }
+
+  /// The scope the binding belongs to. Bindings declared with `var` belong
+  /// to the enclosing function, or the module.
+  pub fn scope(&self) -> ScopeId {
+    self.scope
+  }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -60,11 +192,18 @@ pub enum BindingKind {
   Class,
   CatchClause,
   Import,
+  /// TypeScript `enum`.
+  Enum,
+  /// TypeScript `namespace` or `module` with an identifier as its name.
+  Namespace,
+  /// TypeScript type parameter, eg. `T` of `function f<T>() {}`.
+  TypeParam,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub enum ScopeKind {
-  // Module,
+  /// Only the kind of the module scope, never part of a `Binding::path`.
+  Module,
   Arrow,
   Function,
   Block,
@@ -75,51 +214,227 @@ pub enum ScopeKind {
   Catch,
 }
 
-pub fn analyze(module: &Module) -> Scope {
-  let mut scope = Scope {
-    vars: Default::default(),
+#[derive(Debug)]
+pub struct Reference {
+  id: Id,
+  span: Span,
+  kind: ReferenceKind,
+  scope: ScopeId,
+  resolved: bool,
+}
+
+impl Reference {
+  /// `Id` of the referenced identifier, whose binding is
+  /// `ScopeManager::binding(reference.id())` unless it's a global.
+  pub fn id(&self) -> &Id {
+    &self.id
+  }
+
+  /// Span of the referencing identifier.
+  pub fn span(&self) -> Span {
+    self.span
+  }
+
+  pub fn kind(&self) -> ReferenceKind {
+    self.kind
+  }
+
+  /// The scope the reference is made from.
+  pub fn scope(&self) -> ScopeId {
+    self.scope
+  }
+
+  /// Whether the reference refers to a binding of the module, rather than
+  /// a global.
+  pub fn is_resolved(&self) -> bool {
+    self.resolved
+  }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ReferenceKind {
+  Read,
+  /// Assignments, including the initializer of a declaration.
+  Write,
+  /// Compound assignments and updates, eg. `a += 1` or `a++`.
+  ReadWrite,
+}
+
+impl ReferenceKind {
+  pub fn is_read(self) -> bool {
+    self != ReferenceKind::Write
+  }
+
+  pub fn is_write(self) -> bool {
+    self != ReferenceKind::Read
+  }
+}
+
+pub fn analyze(module: &Module) -> ScopeManager {
+  let mut scope_manager = ScopeManager {
+    scopes: vec![Scope {
+      kind: ScopeKind::Module,
+      span: module.span,
+      parent: None,
+      bindings: vec![],
+    }],
+    bindings: Default::default(),
     symbols: Default::default(),
+    references: vec![],
+    idents: Default::default(),
   };
   let mut path = vec![];
 
   module.visit_with(
     &Invalid { span: DUMMY_SP },
     &mut Analyzer {
-      scope: &mut scope,
+      scope_manager: &mut scope_manager,
       path: &mut path,
+      current: ScopeManager::MODULE,
+      current_function: ScopeManager::MODULE,
     },
   );
 
-  scope
+  // References are only resolved now, as they may come before the
+  // declaration, eg. calls of hoisted functions.
+  for (index, reference) in scope_manager.references.iter_mut().enumerate() {
+    if let Some(binding) = scope_manager.bindings.get_mut(&reference.id) {
+      binding.references.push(index);
+      reference.resolved = true;
+    }
+  }
+
+  scope_manager
 }
 
 struct Analyzer<'a> {
-  scope: &'a mut Scope,
+  scope_manager: &'a mut ScopeManager,
   path: &'a mut Vec<ScopeKind>,
+  /// Lexical scope the visited declarations belong to.
+  current: ScopeId,
+  /// Scope of the enclosing function or module, which `var` declarations
+  /// are hoisted to.
+  current_function: ScopeId,
 }
 
 impl Analyzer<'_> {
-  fn declare_id(&mut self, kind: BindingKind, i: Id) {
-    self.scope.vars.insert(
-      i.clone(),
-      Var {
+  fn declare(&mut self, kind: BindingKind, i: &Ident) {
+    let scope = match kind {
+      BindingKind::Var => self.current_function,
+      _ => self.current,
+    };
+    let id = i.to_id();
+    self.scope_manager.bindings.insert(
+      id.clone(),
+      Binding {
         kind,
         path: self.path.clone(),
+        span: i.span,
+        scope,
+        references: vec![],
       },
     );
-    self.scope.symbols.entry(i.0.clone()).or_default().push(i);
+    self.scope_manager.scopes[scope].bindings.push(id.clone());
+    self
+      .scope_manager
+      .idents
+      .insert((i.span.lo, i.span.hi), id.clone());
+    self
+      .scope_manager
+      .symbols
+      .entry(id.0.clone())
+      .or_default()
+      .push(id);
   }
 
-  fn declare(&mut self, kind: BindingKind, i: &Ident) {
-    self.declare_id(kind, i.to_id());
+  fn declare_pat(&mut self, kind: BindingKind, pat: &Pat) {
+    let idents: Vec<Ident> = find_ids(pat);
+
+    for ident in &idents {
+      self.declare(kind, ident);
+    }
   }
 
-  fn declare_pat(&mut self, kind: BindingKind, pat: &Pat) {
-    let ids: Vec<Id> = find_ids(pat);
+  fn reference(&mut self, kind: ReferenceKind, i: &Ident) {
+    let id = i.to_id();
+    self
+      .scope_manager
+      .idents
+      .insert((i.span.lo, i.span.hi), id.clone());
+    self.scope_manager.references.push(Reference {
+      id,
+      span: i.span,
+      kind,
+      scope: self.current,
+      resolved: false,
+    });
+  }
+
+  /// Records the identifiers `pat` assigns to as references of `kind`, and
+  /// visits the expressions in it, like default values.
+  fn assign_pat(&mut self, kind: ReferenceKind, pat: &Pat) {
+    match pat {
+      Pat::Ident(ident) => self.reference(kind, ident),
+      Pat::Array(array_pat) => {
+        for elem in array_pat.elems.iter().flatten() {
+          self.assign_pat(kind, elem);
+        }
+      }
+      Pat::Object(object_pat) => {
+        for prop in &object_pat.props {
+          match prop {
+            ObjectPatProp::KeyValue(key_value) => {
+              key_value.key.visit_with(prop, self);
+              self.assign_pat(kind, &key_value.value);
+            }
+            ObjectPatProp::Assign(assign) => {
+              self.reference(kind, &assign.key);
+              assign.value.visit_with(prop, self);
+            }
+            ObjectPatProp::Rest(rest) => self.assign_pat(kind, &rest.arg),
+          }
+        }
+      }
+      Pat::Rest(rest_pat) => self.assign_pat(kind, &rest_pat.arg),
+      Pat::Assign(assign_pat) => {
+        self.assign_pat(kind, &assign_pat.left);
+        assign_pat.right.visit_with(pat, self);
+      }
+      Pat::Expr(expr) => self.assign_expr(kind, expr),
+      Pat::Invalid(_) => {}
+    }
+  }
 
-    for id in ids {
-      self.declare_id(kind, id);
+  fn assign_expr(&mut self, kind: ReferenceKind, expr: &Expr) {
+    match expr {
+      Expr::Ident(ident) => self.reference(kind, ident),
+      Expr::Paren(paren_expr) => self.assign_expr(kind, &paren_expr.expr),
+      // Eg. `a.b = 1` only reads `a`
+      _ => expr.visit_with(&Invalid { span: DUMMY_SP }, self),
+    }
+  }
+
+  /// Visits the declarations made by `op` in a new lexical scope. Function
+  /// scopes also receive the `var` declarations.
+  fn in_scope<F>(&mut self, kind: ScopeKind, span: Span, op: F)
+  where
+    F: FnOnce(&mut Analyzer),
+  {
+    let parent = self.current;
+    let parent_function = self.current_function;
+    self.scope_manager.scopes.push(Scope {
+      kind,
+      span,
+      parent: Some(parent),
+      bindings: vec![],
+    });
+    self.current = self.scope_manager.scopes.len() - 1;
+    if matches!(kind, ScopeKind::Function | ScopeKind::Arrow) {
+      self.current_function = self.current;
     }
+    op(self);
+    self.current = parent;
+    self.current_function = parent_function;
   }
 
   fn visit_with_path<T>(&mut self, kind: ScopeKind, node: &T)
@@ -139,11 +454,43 @@ impl Analyzer<'_> {
     op(self);
     self.path.pop();
   }
+
+  /// Records the writes of the loop variables of a `for...in` or
+  /// `for...of` loop, and visits the loop.
+  fn visit_for_each(
+    &mut self,
+    left: &VarDeclOrPat,
+    right: &Expr,
+    body: &swc_ecmascript::ast::Stmt,
+    node: &dyn Node,
+  ) {
+    match left {
+      VarDeclOrPat::VarDecl(var_decl) => {
+        var_decl.visit_with(node, self);
+        for decl in &var_decl.decls {
+          self.assign_pat(ReferenceKind::Write, &decl.name);
+        }
+      }
+      VarDeclOrPat::Pat(pat) => self.assign_pat(ReferenceKind::Write, pat),
+    }
+    right.visit_with(node, self);
+
+    self.path.push(ScopeKind::Loop);
+    body.visit_with(node, self);
+    self.path.pop();
+  }
 }
 
 impl Visit for Analyzer<'_> {
   fn visit_arrow_expr(&mut self, n: &ArrowExpr, _: &dyn Node) {
-    self.with(ScopeKind::Arrow, |a| n.visit_children_with(a))
+    self.with(ScopeKind::Arrow, |a| {
+      a.in_scope(ScopeKind::Arrow, n.span, |a| {
+        for param in &n.params {
+          a.declare_pat(BindingKind::Param, param);
+        }
+        n.visit_children_with(a)
+      })
+    })
   }
 
   /// Overriden not to add ScopeKind::Block
@@ -169,6 +516,7 @@ impl Visit for Analyzer<'_> {
           if let Pat::Ident(var_name) = &v.name {
             if var_name.sym == class_name.sym {
               self.declare(BindingKind::Class, class_name);
+              self.reference(ReferenceKind::Write, var_name);
               return;
             }
           }
@@ -183,19 +531,53 @@ impl Visit for Analyzer<'_> {
         },
         &v.name,
       );
+      if v.init.is_some() {
+        self.assign_pat(ReferenceKind::Write, &v.name);
+      }
     });
   }
 
   /// Overriden not to add ScopeKind::Block
   fn visit_function(&mut self, n: &Function, _: &dyn Node) {
     n.decorators.visit_with(n, self);
-    n.params.visit_with(n, self);
+    self.in_scope(ScopeKind::Function, n.span, |a| {
+      n.type_params.visit_with(n, a);
+      n.params.visit_with(n, a);
 
-    // Don't add ScopeKind::Block
-    match &n.body {
-      Some(s) => s.stmts.visit_with(n, self),
-      None => {}
-    }
+      // Don't add ScopeKind::Block
+      match &n.body {
+        Some(s) => s.stmts.visit_with(n, a),
+        None => {}
+      }
+    })
+  }
+
+  /// Constructors get a function scope like methods, which also holds the
+  /// parameter properties.
+  fn visit_constructor(&mut self, n: &Constructor, _: &dyn Node) {
+    n.key.visit_with(n, self);
+    self.in_scope(ScopeKind::Function, n.span, |a| {
+      for param in &n.params {
+        match param {
+          ParamOrTsParamProp::Param(param) => param.visit_with(n, a),
+          ParamOrTsParamProp::TsParamProp(prop) => {
+            prop.decorators.visit_with(n, a);
+            match &prop.param {
+              TsParamPropParam::Ident(ident) => {
+                a.declare(BindingKind::Param, ident)
+              }
+              TsParamPropParam::Assign(assign_pat) => {
+                a.declare_pat(BindingKind::Param, &assign_pat.left);
+                assign_pat.right.visit_with(n, a);
+              }
+            }
+          }
+        }
+      }
+      if let Some(body) = &n.body {
+        body.stmts.visit_with(n, a);
+      }
+    })
   }
 
   fn visit_fn_decl(&mut self, n: &FnDecl, _: &dyn Node) {
@@ -211,18 +593,24 @@ impl Visit for Analyzer<'_> {
   }
 
   fn visit_block_stmt(&mut self, n: &BlockStmt, _: &dyn Node) {
-    self.visit_with_path(ScopeKind::Block, &n.stmts)
+    self.in_scope(ScopeKind::Block, n.span, |a| {
+      a.visit_with_path(ScopeKind::Block, &n.stmts)
+    })
   }
 
   fn visit_catch_clause(&mut self, n: &CatchClause, _: &dyn Node) {
-    if let Some(pat) = &n.param {
-      self.declare_pat(BindingKind::CatchClause, pat);
-    }
-    self.visit_with_path(ScopeKind::Catch, &n.body)
+    self.in_scope(ScopeKind::Catch, n.span, |a| {
+      if let Some(pat) = &n.param {
+        a.declare_pat(BindingKind::CatchClause, pat);
+      }
+      a.visit_with_path(ScopeKind::Catch, &n.body)
+    })
   }
 
   fn visit_param(&mut self, n: &Param, _: &dyn Node) {
     self.declare_pat(BindingKind::Param, &n.pat);
+    // Default values may reference other bindings
+    n.visit_children_with(self);
   }
 
   fn visit_import_named_specifier(
@@ -255,25 +643,25 @@ impl Visit for Analyzer<'_> {
   }
 
   fn visit_for_stmt(&mut self, n: &ForStmt, _: &dyn Node) {
-    n.init.visit_with(n, self);
-    n.update.visit_with(n, self);
-    n.test.visit_with(n, self);
+    self.in_scope(ScopeKind::Loop, n.span, |a| {
+      n.init.visit_with(n, a);
+      n.update.visit_with(n, a);
+      n.test.visit_with(n, a);
 
-    self.visit_with_path(ScopeKind::Loop, &n.body);
+      a.visit_with_path(ScopeKind::Loop, &n.body);
+    })
   }
 
   fn visit_for_of_stmt(&mut self, n: &ForOfStmt, _: &dyn Node) {
-    n.left.visit_with(n, self);
-    n.right.visit_with(n, self);
-
-    self.visit_with_path(ScopeKind::Loop, &n.body);
+    self.in_scope(ScopeKind::Loop, n.span, |a| {
+      a.visit_for_each(&n.left, &n.right, &n.body, n)
+    })
   }
 
   fn visit_for_in_stmt(&mut self, n: &ForInStmt, _: &dyn Node) {
-    n.left.visit_with(n, self);
-    n.right.visit_with(n, self);
-
-    self.visit_with_path(ScopeKind::Loop, &n.body);
+    self.in_scope(ScopeKind::Loop, n.span, |a| {
+      a.visit_for_each(&n.left, &n.right, &n.body, n)
+    })
   }
 
   fn visit_do_while_stmt(&mut self, n: &DoWhileStmt, _: &dyn Node) {
@@ -291,17 +679,102 @@ impl Visit for Analyzer<'_> {
   fn visit_switch_stmt(&mut self, n: &SwitchStmt, _: &dyn Node) {
     n.discriminant.visit_with(n, self);
 
-    self.visit_with_path(ScopeKind::Switch, &n.cases);
+    self.in_scope(ScopeKind::Switch, n.span, |a| {
+      a.visit_with_path(ScopeKind::Switch, &n.cases)
+    });
+  }
+
+  fn visit_ts_enum_decl(&mut self, n: &TsEnumDecl, _: &dyn Node) {
+    self.declare(BindingKind::Enum, &n.id);
+    n.members.visit_with(n, self);
+  }
+
+  fn visit_ts_module_decl(&mut self, n: &TsModuleDecl, _: &dyn Node) {
+    if let TsModuleName::Ident(ident) = &n.id {
+      self.declare(BindingKind::Namespace, ident);
+    }
+    n.body.visit_with(n, self);
+  }
+
+  fn visit_ts_type_param(&mut self, n: &TsTypeParam, _: &dyn Node) {
+    self.declare(BindingKind::TypeParam, &n.name);
+    n.constraint.visit_with(n, self);
+    n.default.visit_with(n, self);
+  }
+
+  // References
+
+  fn visit_expr(&mut self, n: &Expr, _: &dyn Node) {
+    match n {
+      Expr::Ident(ident) => self.reference(ReferenceKind::Read, ident),
+      _ => n.visit_children_with(self),
+    }
+  }
+
+  fn visit_assign_expr(&mut self, n: &AssignExpr, _: &dyn Node) {
+    let kind = match n.op {
+      AssignOp::Assign => ReferenceKind::Write,
+      _ => ReferenceKind::ReadWrite,
+    };
+    match &n.left {
+      PatOrExpr::Pat(pat) => self.assign_pat(kind, pat),
+      PatOrExpr::Expr(expr) => self.assign_expr(kind, expr),
+    }
+    n.right.visit_with(n, self);
+  }
+
+  fn visit_update_expr(&mut self, n: &UpdateExpr, _: &dyn Node) {
+    self.assign_expr(ReferenceKind::ReadWrite, &n.arg);
+  }
+
+  fn visit_member_expr(&mut self, n: &MemberExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(obj) = &n.obj {
+      obj.visit_with(n, self);
+    }
+    // `a.b` doesn't reference `b`
+    if n.computed {
+      n.prop.visit_with(n, self);
+    }
+  }
+
+  fn visit_class_prop(&mut self, n: &ClassProp, _: &dyn Node) {
+    n.decorators.visit_with(n, self);
+    if n.computed {
+      n.key.visit_with(n, self);
+    }
+    n.value.visit_with(n, self);
+  }
+
+  fn visit_prop(&mut self, n: &Prop, _: &dyn Node) {
+    match n {
+      // `{ a }` reads `a`
+      Prop::Shorthand(ident) => self.reference(ReferenceKind::Read, ident),
+      _ => n.visit_children_with(self),
+    }
+  }
+
+  fn visit_named_export(&mut self, n: &NamedExport, _: &dyn Node) {
+    // Re-exports of other modules don't reference local bindings
+    if n.src.is_some() {
+      return;
+    }
+    for specifier in &n.specifiers {
+      if let swc_ecmascript::ast::ExportSpecifier::Named(named) = specifier {
+        self.reference(ReferenceKind::Read, &named.orig);
+      }
+    }
   }
 }
 
 #[cfg(test)]
 mod tests {
-  use super::{analyze, BindingKind, Scope, ScopeKind, Var};
+  use super::{
+    analyze, Binding, BindingKind, ReferenceKind, ScopeKind, ScopeManager,
+  };
   use crate::swc_util::{self, AstParser};
   use swc_ecmascript::utils::Id;
 
-  fn test_scope(source_code: &str) -> Scope {
+  fn test_scope(source_code: &str) -> ScopeManager {
     let ast_parser = AstParser::new();
     let syntax = swc_util::get_default_ts_config();
     let (parse_result, _comments) =
@@ -311,7 +784,7 @@ mod tests {
     analyze(&module)
   }
 
-  fn id(scope: &Scope, s: &str) -> Id {
+  fn id(scope: &ScopeManager, s: &str) -> Id {
     let ids = scope.ids_with_symbol(&s.into());
     if ids.is_none() {
       panic!("No identifier named {}", s);
@@ -324,8 +797,16 @@ mod tests {
     ids.first().unwrap().clone()
   }
 
-  fn var<'a>(scope: &'a Scope, symbol: &str) -> &'a Var {
-    scope.var(&id(scope, symbol)).unwrap()
+  fn binding<'a>(scope: &'a ScopeManager, symbol: &str) -> &'a Binding {
+    scope.binding(&id(scope, symbol)).unwrap()
+  }
+
+  /// Kinds of the references to the only binding named `symbol`.
+  fn reference_kinds(scope: &ScopeManager, symbol: &str) -> Vec<ReferenceKind> {
+    scope
+      .references_of(&id(scope, symbol))
+      .map(|reference| reference.kind())
+      .collect()
   }
 
   #[test]
@@ -352,22 +833,244 @@ try {
 } catch (e) {
   const msg = "asdf " + e.message;
 }
+const g = (h, { i }) => h + i;
 "#;
     let scope = test_scope(source_code);
-    assert_eq!(var(&scope, "a").kind(), BindingKind::Const);
-    assert_eq!(var(&scope, "a").path(), &[]);
+    assert_eq!(binding(&scope, "a").kind(), BindingKind::Const);
+    assert_eq!(binding(&scope, "a").path(), &[]);
 
-    assert_eq!(var(&scope, "b").kind(), BindingKind::Param);
+    assert_eq!(binding(&scope, "b").kind(), BindingKind::Param);
     assert_eq!(scope.ids_with_symbol(&"c".into()).unwrap().len(), 2);
     assert_eq!(
-      var(&scope, "d").path(),
+      binding(&scope, "d").path(),
       &[ScopeKind::Function, ScopeKind::Block]
     );
 
-    assert_eq!(var(&scope, "Foo").kind(), BindingKind::Class);
-    assert_eq!(var(&scope, "Foo").path(), &[]);
+    assert_eq!(binding(&scope, "Foo").kind(), BindingKind::Class);
+    assert_eq!(binding(&scope, "Foo").path(), &[]);
+
+    assert_eq!(binding(&scope, "e").kind(), BindingKind::CatchClause);
+    assert_eq!(binding(&scope, "e").path(), &[]);
+
+    assert_eq!(binding(&scope, "h").kind(), BindingKind::Param);
+    assert_eq!(binding(&scope, "h").path(), &[ScopeKind::Arrow]);
+    assert_eq!(binding(&scope, "i").kind(), BindingKind::Param);
+  }
+
+  #[test]
+  fn scope_tree() {
+    let source_code = r#"
+function foo(a) {
+  if (a) {
+    let b = 1;
+  }
+  for (let c of a) {}
+}
+"#;
+    let scope = test_scope(source_code);
+    let module = scope.scope(ScopeManager::MODULE);
+    assert_eq!(module.kind(), ScopeKind::Module);
+    assert_eq!(module.parent(), None);
+    assert_eq!(module.bindings(), &[id(&scope, "foo")]);
+
+    let function = binding(&scope, "a").scope();
+    assert_eq!(scope.scope(function).kind(), ScopeKind::Function);
+    assert_eq!(scope.scope(function).parent(), Some(ScopeManager::MODULE));
+    assert_eq!(scope.scope(function).bindings(), &[id(&scope, "a")]);
+
+    let block = scope.scope(binding(&scope, "b").scope());
+    assert_eq!(block.kind(), ScopeKind::Block);
+    assert_eq!(block.parent(), Some(function));
+    assert_eq!(block.span().lo.0, 28);
+
+    let for_of = scope.scope(binding(&scope, "c").scope());
+    assert_eq!(for_of.kind(), ScopeKind::Loop);
+    assert_eq!(for_of.parent(), Some(function));
+    assert_eq!(scope.scopes().len(), 5);
+  }
+
+  #[test]
+  fn hoisting() {
+    let source_code = r#"
+foo();
+function foo() {
+  if (true) {
+    var a = 1;
+    function bar() {}
+  }
+  return a;
+}
+"#;
+    let scope = test_scope(source_code);
+
+    // `var` belongs to the function, even when declared in a block
+    let a = binding(&scope, "a");
+    assert_eq!(scope.scope(a.scope()).kind(), ScopeKind::Function);
+    assert_eq!(a.path(), &[ScopeKind::Function, ScopeKind::Block]);
+    assert_eq!(
+      reference_kinds(&scope, "a"),
+      vec![ReferenceKind::Write, ReferenceKind::Read]
+    );
+
+    // Function declarations are block scoped in modules
+    let bar = binding(&scope, "bar");
+    assert_eq!(scope.scope(bar.scope()).kind(), ScopeKind::Block);
+
+    // References before the declaration resolve
+    let references =
+      scope.references_of(&id(&scope, "foo")).collect::<Vec<_>>();
+    assert_eq!(references.len(), 1);
+    assert_eq!(references[0].span().lo.0, 1);
+    assert!(references[0].is_resolved());
+    assert_eq!(scope.lookup(references[0].span()).unwrap().span().lo.0, 17);
+  }
+
+  #[test]
+  fn constructor_scope() {
+    let source_code = r#"
+class Foo {
+  constructor(private a: number, b = a) {
+    var c = b;
+  }
+}
+"#;
+    let scope = test_scope(source_code);
+    let a = binding(&scope, "a");
+    let constructor = scope.scope(a.scope());
+    assert_eq!(constructor.kind(), ScopeKind::Function);
+    assert_eq!(a.kind(), BindingKind::Param);
+    assert_eq!(binding(&scope, "b").scope(), a.scope());
+    // `var` is hoisted to the constructor, not to the module
+    assert_eq!(binding(&scope, "c").scope(), a.scope());
+    assert_eq!(reference_kinds(&scope, "a"), vec![ReferenceKind::Read]);
+  }
+
+  #[test]
+  fn references() {
+    let source_code = r#"
+let a = 1;
+let b, c;
+a = 2;
+a += 3;
+a++;
+[b, { c = a }] = [a, b];
+const d = { a, b: c.e, [c]: 1 };
+export { d };
+"#;
+    let scope = test_scope(source_code);
+    assert_eq!(
+      reference_kinds(&scope, "a"),
+      vec![
+        ReferenceKind::Write,
+        ReferenceKind::Write,
+        ReferenceKind::ReadWrite,
+        ReferenceKind::ReadWrite,
+        ReferenceKind::Read,
+        ReferenceKind::Read,
+        ReferenceKind::Read,
+      ]
+    );
+    assert_eq!(
+      reference_kinds(&scope, "b"),
+      vec![ReferenceKind::Write, ReferenceKind::Read]
+    );
+    assert_eq!(
+      reference_kinds(&scope, "c"),
+      vec![
+        ReferenceKind::Write,
+        ReferenceKind::Read,
+        ReferenceKind::Read
+      ]
+    );
+    assert_eq!(
+      reference_kinds(&scope, "d"),
+      vec![ReferenceKind::Write, ReferenceKind::Read]
+    );
+    assert!(ReferenceKind::ReadWrite.is_read());
+    assert!(ReferenceKind::ReadWrite.is_write());
+    assert!(!ReferenceKind::Write.is_read());
+
+    // Property names aren't references, `e` isn't declared anywhere
+    assert!(scope.ids_with_symbol(&"e".into()).is_none());
+    assert!(scope
+      .references()
+      .iter()
+      .all(|reference| reference.id().0 != *"e"));
+  }
+
+  #[test]
+  fn unresolved_references() {
+    let source_code = r#"
+const a = console.log(b);
+function f(c = b) {
+  return c;
+}
+"#;
+    let scope = test_scope(source_code);
+    let unresolved = scope
+      .references()
+      .iter()
+      .filter(|reference| !reference.is_resolved())
+      .map(|reference| reference.id().0.to_string())
+      .collect::<Vec<_>>();
+    assert_eq!(unresolved, vec!["console", "b", "b"]);
+    let console = &scope.references()[0];
+    assert_eq!(console.id().0, *"console");
+    assert!(scope.lookup(console.span()).is_none());
+
+    assert_eq!(reference_kinds(&scope, "c"), vec![ReferenceKind::Read]);
+  }
+
+  #[test]
+  fn typescript_bindings() {
+    let source_code = r#"
+enum Color { Red }
+namespace Shapes {
+  export const square = 1;
+}
+function identity<T>(value: T): T {
+  return value;
+}
+const color = Color.Red;
+"#;
+    let scope = test_scope(source_code);
+    assert_eq!(binding(&scope, "Color").kind(), BindingKind::Enum);
+    assert_eq!(binding(&scope, "Shapes").kind(), BindingKind::Namespace);
+    assert_eq!(binding(&scope, "square").kind(), BindingKind::Const);
+    let t = binding(&scope, "T");
+    assert_eq!(t.kind(), BindingKind::TypeParam);
+    assert_eq!(scope.scope(t.scope()).kind(), ScopeKind::Function);
+    assert_eq!(reference_kinds(&scope, "Color"), vec![ReferenceKind::Read]);
+  }
+
+  #[test]
+  fn shadowed_bindings() {
+    let source_code = r#"
+import { a } from "./a.ts";
+function foo(a) {
+  {
+    const a = 1;
+  }
+}
+const b = (c) => c;
+const d = (c) => c;
+"#;
+    let scope = test_scope(source_code);
+    let ids = scope.ids_with_symbol(&"a".into()).unwrap();
+    assert_eq!(ids.len(), 3);
+    let kinds = ids
+      .iter()
+      .map(|id| scope.shadowed_binding(id).map(|binding| binding.kind()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      kinds,
+      vec![None, Some(BindingKind::Import), Some(BindingKind::Param)]
+    );
 
-    assert_eq!(var(&scope, "e").kind(), BindingKind::CatchClause);
-    assert_eq!(var(&scope, "e").path(), &[]);
+    // Siblings don't shadow each other
+    let c = scope.ids_with_symbol(&"c".into()).unwrap();
+    assert_eq!(c.len(), 2);
+    assert!(c.iter().all(|id| scope.shadowed_binding(id).is_none()));
+    assert_eq!(binding(&scope, "d").span().lo.0, 100);
   }
 }
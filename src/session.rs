@@ -0,0 +1,403 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use crate::media_type::MediaType;
+use crate::swc_util::AstParser;
+use crate::swc_util::SwcDiagnosticBuffer;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use swc_ecmascript::ast::{
+  Decl, ExportDefaultExpr, ExportSpecifier, Expr, ExprOrSuper, ImportSpecifier,
+  JSXElementName, MemberExpr, Module, ModuleDecl, ModuleItem, Prop,
+};
+use swc_ecmascript::utils::find_ids;
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::noop_visit_type;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
+
+/// Summaries of all the modules of a project, which allows rules to look
+/// at the files a module imports from or re-exports.
+///
+/// Modules are added with `add_module` before linting, and the session is
+/// passed to `LinterBuilder::session` for each file linted.
+#[derive(Default)]
+pub struct LintSession {
+  modules: HashMap<String, ModuleSummary>,
+}
+
+/// Names a module exports, and the modules it re-exports with `export *`.
+#[derive(Debug, Default)]
+pub(crate) struct ModuleSummary {
+  /// Names exported by the module itself, including `default` and names
+  /// re-exported explicitly with `export { a } from "./a.ts"`.
+  pub exports: HashSet<String>,
+  /// Specifiers of `export * from "..."`, in source order.
+  pub star_exports: Vec<String>,
+  /// Imported names used as a value rather than only to access their
+  /// members, as specifier and imported name pairs. `new A()`, `extends A`
+  /// or `f(A)` are such uses of `A`, `A.create()` isn't.
+  pub value_imports: HashSet<(String, String)>,
+}
+
+impl LintSession {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Parses `source_code` and records the summary of the module under
+  /// `file_name`, which has to be the same name later passed to
+  /// `Linter::lint`.
+  pub fn add_module(
+    &mut self,
+    file_name: &str,
+    source_code: &str,
+  ) -> Result<(), SwcDiagnosticBuffer> {
+    let ast_parser = AstParser::new();
+    let (module, _) = ast_parser.parse_module(
+      file_name,
+      MediaType::from_file_name(file_name).syntax(),
+      source_code,
+    );
+    self
+      .modules
+      .insert(file_name.to_string(), ModuleSummary::from_module(&module?));
+    Ok(())
+  }
+
+  /// Finds the module `specifier` refers to when imported from `referrer`,
+  /// and returns its file name and summary. Only relative specifiers are
+  /// resolved.
+  pub(crate) fn resolve(
+    &self,
+    referrer: &str,
+    specifier: &str,
+  ) -> Option<(&str, &ModuleSummary)> {
+    self
+      .modules
+      .get_key_value(&resolve_specifier(referrer, specifier)?)
+      .map(|(file_name, summary)| (file_name.as_str(), summary))
+  }
+
+  /// Returns whether any module of the session imports `export_name` from
+  /// `file_name` and uses it as a value, see `ModuleSummary::value_imports`.
+  pub(crate) fn is_imported_as_value(
+    &self,
+    file_name: &str,
+    export_name: &str,
+  ) -> bool {
+    self.modules.iter().any(|(importer, summary)| {
+      summary.value_imports.iter().any(|(specifier, name)| {
+        name == export_name
+          && resolve_specifier(importer, specifier).as_deref()
+            == Some(file_name)
+      })
+    })
+  }
+}
+
+/// Joins a relative specifier like `../a.ts` with the directory of
+/// `referrer`.
+fn resolve_specifier(referrer: &str, specifier: &str) -> Option<String> {
+  if !specifier.starts_with("./") && !specifier.starts_with("../") {
+    return None;
+  }
+
+  let mut segments: Vec<&str> = referrer.split('/').collect();
+  segments.pop();
+  for segment in specifier.split('/') {
+    match segment {
+      "." => {}
+      ".." => match segments.last() {
+        Some(&"..") | None => segments.push(".."),
+        // `/..` is still the root.
+        Some(&"") => {}
+        Some(_) => {
+          segments.pop();
+        }
+      },
+      _ => segments.push(segment),
+    }
+  }
+  Some(segments.join("/"))
+}
+
+impl ModuleSummary {
+  pub(crate) fn from_module(module: &Module) -> Self {
+    let mut summary = Self::default();
+    let mut imports: HashMap<Id, (String, String)> = HashMap::new();
+    let mut namespaces: HashMap<Id, String> = HashMap::new();
+    for item in &module.body {
+      let module_decl = match item {
+        ModuleItem::ModuleDecl(module_decl) => module_decl,
+        ModuleItem::Stmt(_) => continue,
+      };
+      match module_decl {
+        ModuleDecl::ExportDecl(export_decl) => {
+          let names: Vec<String> = match &export_decl.decl {
+            Decl::Class(class_decl) => vec![class_decl.ident.sym.to_string()],
+            Decl::Fn(fn_decl) => vec![fn_decl.ident.sym.to_string()],
+            Decl::Var(var_decl) => var_decl
+              .decls
+              .iter()
+              .flat_map(|decl| find_ids::<_, Id>(&decl.name))
+              .map(|id| id.0.to_string())
+              .collect(),
+            Decl::TsInterface(interface) => vec![interface.id.sym.to_string()],
+            Decl::TsTypeAlias(alias) => vec![alias.id.sym.to_string()],
+            Decl::TsEnum(ts_enum) => vec![ts_enum.id.sym.to_string()],
+            Decl::TsModule(_) => vec![],
+          };
+          summary.exports.extend(names);
+        }
+        ModuleDecl::ExportNamed(named_export) => {
+          for specifier in &named_export.specifiers {
+            let name = match specifier {
+              ExportSpecifier::Named(named) => {
+                named.exported.as_ref().unwrap_or(&named.orig)
+              }
+              ExportSpecifier::Namespace(namespace) => &namespace.name,
+              ExportSpecifier::Default(_) => {
+                summary.exports.insert("default".to_string());
+                continue;
+              }
+            };
+            summary.exports.insert(name.sym.to_string());
+          }
+        }
+        ModuleDecl::ExportDefaultDecl(_) | ModuleDecl::ExportDefaultExpr(_) => {
+          summary.exports.insert("default".to_string());
+        }
+        ModuleDecl::ExportAll(export_all) => {
+          summary.star_exports.push(export_all.src.value.to_string());
+        }
+        ModuleDecl::Import(import_decl) => {
+          let src = import_decl.src.value.to_string();
+          for specifier in &import_decl.specifiers {
+            match specifier {
+              ImportSpecifier::Named(named) => {
+                let imported = named.imported.as_ref().unwrap_or(&named.local);
+                imports.insert(
+                  named.local.to_id(),
+                  (src.clone(), imported.sym.to_string()),
+                );
+              }
+              ImportSpecifier::Default(default) => {
+                imports.insert(
+                  default.local.to_id(),
+                  (src.clone(), "default".to_string()),
+                );
+              }
+              ImportSpecifier::Namespace(namespace) => {
+                namespaces.insert(namespace.local.to_id(), src.clone());
+              }
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+
+    if imports.is_empty() && namespaces.is_empty() {
+      return summary;
+    }
+    let uses = ValueUses::collect(module);
+    for id in &uses.idents {
+      if let Some(import) = imports.get(id) {
+        summary.value_imports.insert(import.clone());
+      }
+    }
+    for (id, name) in &uses.members {
+      if let Some(src) = namespaces.get(id) {
+        summary.value_imports.insert((src.clone(), name.clone()));
+      }
+    }
+    summary
+  }
+}
+
+/// Identifiers of a module used as a value rather than only to access
+/// their members.
+#[derive(Default)]
+pub(crate) struct ValueUses {
+  pub idents: HashSet<Id>,
+  /// `ns.A` used as a value, as the id of `ns` and `A`.
+  pub members: HashSet<(Id, String)>,
+}
+
+impl ValueUses {
+  pub(crate) fn collect(module: &Module) -> Self {
+    let mut uses = Self::default();
+    module.visit_with(module, &mut uses);
+    uses
+  }
+}
+
+impl Visit for ValueUses {
+  noop_visit_type!();
+
+  fn visit_expr(&mut self, expr: &Expr, _: &dyn Node) {
+    match expr {
+      Expr::Ident(ident) => {
+        self.idents.insert(ident.to_id());
+      }
+      Expr::Member(member_expr) => {
+        if let (ExprOrSuper::Expr(obj), Expr::Ident(prop), false) =
+          (&member_expr.obj, &*member_expr.prop, member_expr.computed)
+        {
+          if let Expr::Ident(obj) = &**obj {
+            self.members.insert((obj.to_id(), prop.sym.to_string()));
+          }
+        }
+        member_expr.visit_with(expr, self);
+      }
+      _ => expr.visit_children_with(self),
+    }
+  }
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(obj) = &member_expr.obj {
+      match &**obj {
+        // Only a member of the object is used.
+        Expr::Ident(_) => {}
+        Expr::Member(inner) => inner.visit_with(member_expr, self),
+        _ => obj.visit_with(member_expr, self),
+      }
+    }
+    if member_expr.computed {
+      member_expr.prop.visit_with(member_expr, self);
+    }
+  }
+
+  fn visit_prop(&mut self, prop: &Prop, _: &dyn Node) {
+    match prop {
+      Prop::Shorthand(ident) => {
+        self.idents.insert(ident.to_id());
+      }
+      _ => prop.visit_children_with(self),
+    }
+  }
+
+  fn visit_jsx_element_name(&mut self, name: &JSXElementName, _: &dyn Node) {
+    if let JSXElementName::Ident(ident) = name {
+      self.idents.insert(ident.to_id());
+    }
+  }
+
+  /// `export default A` exports `A` rather than using it.
+  fn visit_export_default_expr(
+    &mut self,
+    export_default: &ExportDefaultExpr,
+    _: &dyn Node,
+  ) {
+    if !matches!(&*export_default.expr, Expr::Ident(_)) {
+      export_default.expr.visit_with(export_default, self);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn module_summary() {
+    let mut session = LintSession::new();
+    session
+      .add_module(
+        "/project/mod.ts",
+        r#"
+export const a = 1, { b, c: [d] } = obj;
+export function f() {}
+export class C {}
+export interface I {}
+export type T = string;
+export { x, y as z } from "./x.ts";
+export * as ns from "./ns.ts";
+export * from "./all.ts";
+export default 1;
+const hidden = 2;
+        "#,
+      )
+      .unwrap();
+
+    let (file_name, summary) =
+      session.resolve("/project/main.ts", "./mod.ts").unwrap();
+    assert_eq!(file_name, "/project/mod.ts");
+    let mut exports: Vec<&str> =
+      summary.exports.iter().map(String::as_str).collect();
+    exports.sort_unstable();
+    assert_eq!(
+      exports,
+      vec!["C", "I", "T", "a", "b", "d", "default", "f", "ns", "x", "z"]
+    );
+    assert_eq!(summary.star_exports, vec!["./all.ts"]);
+  }
+
+  #[test]
+  fn value_imports() {
+    let mut session = LintSession::new();
+    session
+      .add_module(
+        "/project/main.ts",
+        r#"
+import { A, B, C as D } from "./a.ts";
+import E from "./e.ts";
+import * as ns from "./ns.ts";
+new A();
+B.create();
+f({ D });
+class F extends E {}
+ns.G.create();
+new ns.H();
+        "#,
+      )
+      .unwrap();
+
+    let (_, summary) =
+      session.resolve("/project/index.ts", "./main.ts").unwrap();
+    let mut value_imports: Vec<(&str, &str)> = summary
+      .value_imports
+      .iter()
+      .map(|(specifier, name)| (specifier.as_str(), name.as_str()))
+      .collect();
+    value_imports.sort_unstable();
+    assert_eq!(
+      value_imports,
+      vec![
+        ("./a.ts", "A"),
+        ("./a.ts", "C"),
+        ("./e.ts", "default"),
+        ("./ns.ts", "H")
+      ]
+    );
+    assert!(session.is_imported_as_value("/project/a.ts", "A"));
+    assert!(!session.is_imported_as_value("/project/a.ts", "B"));
+    assert!(!session.is_imported_as_value("/project/lib/a.ts", "A"));
+  }
+
+  #[test]
+  fn resolve_specifiers() {
+    assert_eq!(
+      resolve_specifier("/project/src/mod.ts", "./a.ts").as_deref(),
+      Some("/project/src/a.ts")
+    );
+    assert_eq!(
+      resolve_specifier("/project/src/mod.ts", "../lib/./a.ts").as_deref(),
+      Some("/project/lib/a.ts")
+    );
+    assert_eq!(
+      resolve_specifier("mod.ts", "./a.ts").as_deref(),
+      Some("a.ts")
+    );
+    assert_eq!(
+      resolve_specifier("mod.ts", "../a.ts").as_deref(),
+      Some("../a.ts")
+    );
+    assert_eq!(resolve_specifier("/project/mod.ts", "std/a.ts"), None);
+    assert_eq!(
+      resolve_specifier("/project/mod.ts", "https://deno.land/x/a.ts"),
+      None
+    );
+  }
+}
@@ -0,0 +1,116 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortOrder {
+  /// Compares strings character by character, `a10` comes before `a2`.
+  Alphabetical,
+  /// Compares runs of digits by their numeric value, `a2` comes
+  /// before `a10`.
+  Natural,
+}
+
+impl SortOrder {
+  /// Describes the order for diagnostic messages, eg. "should be sorted
+  /// alphabetically".
+  pub fn describe(self) -> &'static str {
+    match self {
+      SortOrder::Alphabetical => "alphabetically",
+      SortOrder::Natural => "in natural order",
+    }
+  }
+}
+
+pub fn compare(
+  a: &str,
+  b: &str,
+  order: SortOrder,
+  ignore_case: bool,
+) -> Ordering {
+  let (a, b) = if ignore_case {
+    (a.to_lowercase(), b.to_lowercase())
+  } else {
+    (a.to_string(), b.to_string())
+  };
+  match order {
+    SortOrder::Alphabetical => a.cmp(&b),
+    SortOrder::Natural => natural_cmp(&a, &b),
+  }
+}
+
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+  let mut a_chars = a.chars().peekable();
+  let mut b_chars = b.chars().peekable();
+  loop {
+    match (a_chars.peek(), b_chars.peek()) {
+      (None, None) => return Ordering::Equal,
+      (None, Some(_)) => return Ordering::Less,
+      (Some(_), None) => return Ordering::Greater,
+      (Some(a_ch), Some(b_ch))
+        if a_ch.is_ascii_digit() && b_ch.is_ascii_digit() =>
+      {
+        let a_num = take_digits(&mut a_chars);
+        let b_num = take_digits(&mut b_chars);
+        // Compares without parsing, so long runs of digits can't overflow;
+        // leading zeros are ignored.
+        let a_trimmed = a_num.trim_start_matches('0');
+        let b_trimmed = b_num.trim_start_matches('0');
+        let ordering = a_trimmed
+          .len()
+          .cmp(&b_trimmed.len())
+          .then_with(|| a_trimmed.cmp(b_trimmed));
+        if ordering != Ordering::Equal {
+          return ordering;
+        }
+      }
+      (Some(a_ch), Some(b_ch)) => {
+        let ordering = a_ch.cmp(b_ch);
+        if ordering != Ordering::Equal {
+          return ordering;
+        }
+        a_chars.next();
+        b_chars.next();
+      }
+    }
+  }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+  let mut digits = String::new();
+  while let Some(ch) = chars.peek() {
+    if !ch.is_ascii_digit() {
+      break;
+    }
+    digits.push(*ch);
+    chars.next();
+  }
+  digits
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn compare_alphabetical() {
+    let cmp = |a, b| compare(a, b, SortOrder::Alphabetical, false);
+    assert_eq!(cmp("a", "b"), Ordering::Less);
+    assert_eq!(cmp("a10", "a2"), Ordering::Less);
+    assert_eq!(cmp("B", "a"), Ordering::Less);
+    assert_eq!(
+      compare("B", "a", SortOrder::Alphabetical, true),
+      Ordering::Greater
+    );
+  }
+
+  #[test]
+  fn compare_natural() {
+    let cmp = |a, b| compare(a, b, SortOrder::Natural, false);
+    assert_eq!(cmp("a2", "a10"), Ordering::Less);
+    assert_eq!(cmp("a10", "a10"), Ordering::Equal);
+    assert_eq!(cmp("a02", "a2"), Ordering::Equal);
+    assert_eq!(cmp("a", "a1"), Ordering::Less);
+    assert_eq!(cmp("item9b", "item10a"), Ordering::Less);
+    assert_eq!(cmp("x99999999999999999999999", "x1"), Ordering::Greater);
+  }
+}
@@ -1,5 +1,8 @@
 // Copyright 2020 the Deno authors. All rights reserved. MIT license.
-use crate::scopes::Scope;
+use crate::diagnostic::LintDiagnostic;
+use crate::linter::{syntax_error_diagnostic, Context};
+use crate::scopes::ScopeManager;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt;
 use std::sync::Arc;
@@ -10,15 +13,23 @@ use swc_common::errors::DiagnosticBuilder;
 use swc_common::errors::Emitter;
 use swc_common::errors::Handler;
 use swc_common::errors::HandlerFlags;
+use swc_common::BytePos;
 use swc_common::FileName;
 use swc_common::Globals;
 use swc_common::SourceMap;
 use swc_common::Span;
+use swc_common::Spanned;
+use swc_common::SyntaxContext;
 use swc_common::DUMMY_SP;
 use swc_common::{Mark, GLOBALS};
 use swc_ecmascript::ast::{
-  ComputedPropName, Expr, ExprOrSpread, Ident, Lit, MemberExpr, PatOrExpr,
-  Prop, PropName, PropOrSpread, Str, Tpl,
+  ArrowExpr, BinaryOp, BlockStmt, CallExpr, ClassMember, ClassMethod,
+  ComputedPropName, Decl, ExportDecl, Expr, ExprOrSpread, ExprOrSuper, FnDecl,
+  Function, GetterProp, Ident, Lit, MemberExpr, Module, ModuleDecl, ModuleItem,
+  NewExpr, ObjectLit, OptChainExpr, ParenExpr, Pat, PatOrExpr, Prop, PropName,
+  PropOrSpread, ReturnStmt, SetterProp, Stmt, Str, Tpl, TsMethodSignature,
+  TsType, TsTypeElement, TsTypeParamInstantiation, UnaryOp, VarDecl,
+  VarDeclKind,
 };
 use swc_ecmascript::parser::lexer::Lexer;
 use swc_ecmascript::parser::EsConfig;
@@ -28,9 +39,13 @@ use swc_ecmascript::parser::StringInput;
 use swc_ecmascript::parser::Syntax;
 use swc_ecmascript::parser::TsConfig;
 use swc_ecmascript::transforms::resolver::ts_resolver;
+use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Fold;
+use swc_ecmascript::visit::Node;
+use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
 use swc_ecmascript::{
-  utils::{find_ids, ident::IdentLike},
+  utils::{find_ids, ident::IdentLike, Id},
   visit::FoldWith,
 };
 
@@ -58,48 +73,127 @@ pub fn get_default_ts_config() -> Syntax {
   Syntax::Typescript(ts_config)
 }
 
+pub fn get_default_tsx_config() -> Syntax {
+  match get_default_ts_config() {
+    Syntax::Typescript(ts_config) => Syntax::Typescript(TsConfig {
+      tsx: true,
+      ..ts_config
+    }),
+    syntax => syntax,
+  }
+}
+
+/// Escape sequence in the raw text of a string literal that is only
+/// allowed in sloppy mode.
+#[derive(Debug, PartialEq)]
+pub enum LegacyEscape {
+  /// Octal escape like `\251`, `value` is the code point it stands for.
+  Octal {
+    offset: usize,
+    len: usize,
+    value: u32,
+  },
+  /// `\8` or `\9`, which just stand for the digit itself.
+  NonOctalDecimal { offset: usize, digit: char },
+}
+
+/// Finds legacy escape sequences in the raw text of a string literal,
+/// offsets are byte offsets of the backslash and lengths include it.
+pub fn find_legacy_escapes(raw: &str) -> Vec<LegacyEscape> {
+  let bytes = raw.as_bytes();
+  let mut escapes = vec![];
+  let mut i = 0;
+  while i < bytes.len() {
+    if bytes[i] != b'\\' || i + 1 >= bytes.len() {
+      i += 1;
+      continue;
+    }
+    let first = bytes[i + 1];
+    match first {
+      b'0'..=b'7' => {
+        // `\0` is the null character unless another digit follows it.
+        let next_is_digit = matches!(bytes.get(i + 2), Some(b'0'..=b'9'));
+        if first == b'0' && !next_is_digit {
+          i += 2;
+          continue;
+        }
+        // At most three digits, and only while the value fits in a byte.
+        let max_digits = if first <= b'3' { 3 } else { 2 };
+        let mut len = 1;
+        while len < max_digits
+          && matches!(bytes.get(i + 1 + len), Some(b'0'..=b'7'))
+        {
+          len += 1;
+        }
+        let digits = &raw[i + 1..i + 1 + len];
+        escapes.push(LegacyEscape::Octal {
+          offset: i,
+          len: len + 1,
+          value: u32::from_str_radix(digits, 8).unwrap(),
+        });
+        i += len + 1;
+      }
+      b'8' | b'9' => {
+        escapes.push(LegacyEscape::NonOctalDecimal {
+          offset: i,
+          digit: first as char,
+        });
+        i += 2;
+      }
+      // Skips the escaped character, so `\\251` isn't an escape.
+      _ => i += 2,
+    }
+  }
+  escapes
+}
+
+/// Syntax errors of a module which couldn't be parsed, positioned like lint
+/// diagnostics.
 #[derive(Clone, Debug)]
 pub struct SwcDiagnosticBuffer {
-  pub diagnostics: Vec<String>,
+  pub diagnostics: Vec<LintDiagnostic>,
 }
 
 impl Error for SwcDiagnosticBuffer {}
 
 impl fmt::Display for SwcDiagnosticBuffer {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    let msg = self.diagnostics.join(",");
+    let msg = self
+      .diagnostics
+      .iter()
+      .map(|d| {
+        format!(
+          "{} at {}:{}:{}",
+          d.message, d.filename, d.range.start.line, d.range.start.col
+        )
+      })
+      .collect::<Vec<_>>()
+      .join(",");
 
     f.pad(&msg)
   }
 }
 
 impl SwcDiagnosticBuffer {
+  /// Takes the errors emitted to `error_buffer` so far.
   pub(crate) fn from_swc_error(
     error_buffer: SwcErrorBuffer,
     parser: &AstParser,
+    file_name: &str,
   ) -> Self {
-    let s = error_buffer.0.read().unwrap().clone();
+    let errors = std::mem::take(&mut *error_buffer.0.write().unwrap());
 
-    let diagnostics = s
+    let diagnostics = errors
       .iter()
       .map(|d| {
-        let mut msg = d.message();
-
-        if let Some(span) = d.span.primary_span() {
-          let location = parser.get_span_location(span);
-          let filename = match &location.file.name {
-            FileName::Custom(n) => n,
-            _ => unreachable!(),
-          };
-          msg = format!(
-            "{} at {}:{}:{}",
-            msg, filename, location.line, location.col_display
-          );
-        }
-
-        msg
+        syntax_error_diagnostic(
+          &parser.source_map,
+          file_name,
+          d.span.primary_span().unwrap_or(DUMMY_SP),
+          &d.message(),
+        )
       })
-      .collect::<Vec<String>>();
+      .collect::<Vec<_>>();
 
     Self { diagnostics }
   }
@@ -169,14 +263,30 @@ impl AstParser {
   ) -> (
     Result<swc_ecmascript::ast::Module, SwcDiagnosticBuffer>,
     SingleThreadedComments,
+  ) {
+    let (parse_result, comments, _) =
+      self.parse_module_recovering(file_name, syntax, source_code);
+    (parse_result, comments)
+  }
+
+  /// Like `parse_module`, but also returns the syntax errors the parser
+  /// recovered from, which still produce a module. If the module can't be
+  /// parsed, they are part of the returned error.
+  pub(crate) fn parse_module_recovering(
+    &self,
+    file_name: &str,
+    syntax: Syntax,
+    source_code: &str,
+  ) -> (
+    Result<swc_ecmascript::ast::Module, SwcDiagnosticBuffer>,
+    SingleThreadedComments,
+    Vec<LintDiagnostic>,
   ) {
     let swc_source_file = self.source_map.new_source_file(
       FileName::Custom(file_name.to_string()),
       source_code.to_string(),
     );
 
-    let buffered_err = self.buffered_error.clone();
-
     let comments = SingleThreadedComments::default();
     let lexer = Lexer::new(
       syntax,
@@ -187,23 +297,35 @@ impl AstParser {
 
     let mut parser = Parser::new_from(lexer);
 
-    let parse_result = parser.parse_module().map_err(move |err| {
-      let mut diagnostic_builder = err.into_diagnostic(&self.handler);
-      diagnostic_builder.emit();
-      SwcDiagnosticBuffer::from_swc_error(buffered_err, self)
-    });
-
-    let parse_result = parse_result.map(|module| {
-      GLOBALS.set(&self.globals, || {
-        module.fold_with(&mut ts_resolver(self.top_level_mark))
-      })
-    });
-
-    (parse_result, comments)
-  }
+    let parse_result = parser.parse_module();
+    for err in parser.take_errors() {
+      err.into_diagnostic(&self.handler).emit();
+    }
+    let recovered = SwcDiagnosticBuffer::from_swc_error(
+      self.buffered_error.clone(),
+      self,
+      file_name,
+    )
+    .diagnostics;
 
-  pub(crate) fn get_span_location(&self, span: Span) -> swc_common::Loc {
-    self.source_map.lookup_char_pos(span.lo())
+    match parse_result {
+      Ok(module) => {
+        let module = GLOBALS.set(&self.globals, || {
+          module.fold_with(&mut ts_resolver(self.top_level_mark))
+        });
+        (Ok(module), comments, recovered)
+      }
+      Err(err) => {
+        err.into_diagnostic(&self.handler).emit();
+        let mut buffer = SwcDiagnosticBuffer::from_swc_error(
+          self.buffered_error.clone(),
+          self,
+          file_name,
+        );
+        buffer.diagnostics.splice(0..0, recovered);
+        (Err(buffer), comments, vec![])
+      }
+    }
   }
 
   // pub(crate) fn get_span_comments(
@@ -244,10 +366,37 @@ impl DropSpan for Expr {
   }
 }
 
+impl DropSpan for TsTypeParamInstantiation {
+  fn drop_span(self) -> Self {
+    let mut dropper = SpanDropper;
+    dropper.fold_ts_type_param_instantiation(self)
+  }
+}
+
+/// Levenshtein distance between `a` and `b`, in chars.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut previous: Vec<usize> = (0..=b.len()).collect();
+  for (i, a_char) in a.chars().enumerate() {
+    let mut current = vec![i + 1];
+    for (j, b_char) in b.iter().enumerate() {
+      let substitution = previous[j] + if a_char == *b_char { 0 } else { 1 };
+      current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+    }
+    previous = current;
+  }
+  previous[b.len()]
+}
+
+/// Compares two expressions structurally, ignoring where they are located.
+pub(crate) fn node_equals(a: &Expr, b: &Expr) -> bool {
+  a.clone().drop_span() == b.clone().drop_span()
+}
+
 /// Extracts regex string from an expression, using ScopeManager.
 /// If the passed expression is not regular expression, this will return `None`.
 pub(crate) fn extract_regex(
-  scope: &Scope,
+  scope_manager: &ScopeManager,
   expr_ident: &Ident,
   expr_args: &[ExprOrSpread],
 ) -> Option<String> {
@@ -255,7 +404,7 @@ pub(crate) fn extract_regex(
     return None;
   }
 
-  if scope.var(&expr_ident.to_id()).is_some() {
+  if scope_manager.binding(&expr_ident.to_id()).is_some() {
     return None;
   }
 
@@ -269,6 +418,141 @@ pub(crate) fn extract_regex(
   }
 }
 
+/// Extracts the flags passed to the RegExp constructor, or the flags of a
+/// regex literal passed as the pattern. Returns `None` if the flags aren't
+/// known statically, and an empty string if there are none.
+///
+/// Meant to be called once `extract_regex` found the constructor call.
+pub(crate) fn extract_regex_flags(
+  expr_args: &[ExprOrSpread],
+) -> Option<String> {
+  match expr_args.get(1) {
+    Some(flags_arg) => match &*flags_arg.expr {
+      Expr::Lit(Lit::Str(literal)) => Some(literal.value.to_string()),
+      _ => None,
+    },
+    None => match expr_args.first().map(|arg| &*arg.expr) {
+      Some(Expr::Lit(Lit::Regex(regex))) => Some(regex.flags.to_string()),
+      _ => Some(String::new()),
+    },
+  }
+}
+
+/// Extracts the name under which a class member, module item or type
+/// element can be overloaded.
+pub(crate) trait ExtractMethod {
+  fn get_method(&self) -> Option<Method>;
+}
+
+impl ExtractMethod for ModuleItem {
+  fn get_method(&self) -> Option<Method> {
+    let extract_ident = |decl: &Decl| match decl {
+      Decl::Fn(FnDecl { ref ident, .. }) => Some(ident.sym.to_string()),
+      _ => None,
+    };
+
+    let method_name = match self {
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+        ref decl,
+        ..
+      })) => extract_ident(decl),
+      ModuleItem::Stmt(ref stmt) => match stmt {
+        Stmt::Decl(ref decl) => extract_ident(decl),
+        _ => None,
+      },
+      _ => None,
+    };
+
+    method_name.map(Method::Method)
+  }
+}
+
+impl ExtractMethod for ClassMember {
+  fn get_method(&self) -> Option<Method> {
+    match self {
+      ClassMember::Method(ClassMethod {
+        ref key, is_static, ..
+      }) => key.get_key().map(|k| {
+        if *is_static {
+          Method::Static(k)
+        } else {
+          Method::Method(k)
+        }
+      }),
+      ClassMember::Constructor(_) => {
+        Some(Method::Method("constructor".to_string()))
+      }
+      _ => None,
+    }
+  }
+}
+
+impl ExtractMethod for TsTypeElement {
+  fn get_method(&self) -> Option<Method> {
+    match self {
+      TsTypeElement::TsMethodSignature(TsMethodSignature {
+        ref key, ..
+      }) => match &**key {
+        Expr::Ident(Ident { ref sym, .. }) => {
+          Some(Method::Method(sym.to_string()))
+        }
+        Expr::Lit(Lit::Str(Str { ref value, .. })) => {
+          Some(Method::Method(value.to_string()))
+        }
+        _ => None,
+      },
+      TsTypeElement::TsCallSignatureDecl(_) => Some(Method::CallSignature),
+      TsTypeElement::TsConstructSignatureDecl(_) => {
+        Some(Method::ConstructSignature)
+      }
+      _ => None,
+    }
+  }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub(crate) enum Method {
+  Method(String),
+  Static(String),
+  CallSignature,
+  ConstructSignature,
+}
+
+impl Method {
+  pub(crate) fn get_name(&self) -> &str {
+    match self {
+      Method::Method(ref s) | Method::Static(ref s) => s,
+      Method::CallSignature => "call",
+      Method::ConstructSignature => "new",
+    }
+  }
+}
+
+/// Groups adjacent items which are overloads of the same method, in order.
+/// Items which can't be overloaded are left out.
+pub(crate) fn overload_groups<'a, T, U>(items: T) -> Vec<(Method, Vec<&'a U>)>
+where
+  T: IntoIterator<Item = &'a U>,
+  U: ExtractMethod + 'a,
+{
+  let mut groups: Vec<(Method, Vec<&'a U>)> = vec![];
+  let mut last_method = None;
+  for item in items {
+    let method = item.get_method();
+    match (&method, groups.last_mut()) {
+      (Some(method), Some((group_method, group)))
+        if last_method.as_ref() == Some(method) && group_method == method =>
+      {
+        group.push(item);
+      }
+      (Some(method), _) => groups.push((method.clone(), vec![item])),
+      (None, _) => {}
+    }
+    last_method = method;
+  }
+  groups
+}
+
 pub(crate) trait Key {
   fn get_key(&self) -> Option<String>;
 }
@@ -367,6 +651,138 @@ impl Key for MemberExpr {
   }
 }
 
+/// Walks a chain of method calls like `main().then(a).catch(b)` from the
+/// outermost call inwards.
+///
+/// Returns names of the called methods, outermost first (`["catch", "then"]`
+/// for the example above), and the innermost receiver (`main()`).
+pub(crate) fn member_call_chain(expr: &Expr) -> (Vec<String>, &Expr) {
+  let mut methods = vec![];
+  let mut current = expr;
+  loop {
+    match current {
+      Expr::Paren(ParenExpr { expr, .. }) => current = expr,
+      Expr::Call(CallExpr {
+        callee: ExprOrSuper::Expr(callee),
+        ..
+      }) => match &**callee {
+        Expr::Member(member_expr) => {
+          match (member_expr.get_key(), &member_expr.obj) {
+            (Some(method), ExprOrSuper::Expr(obj)) => {
+              methods.push(method);
+              current = obj;
+            }
+            _ => break,
+          }
+        }
+        _ => break,
+      },
+      _ => break,
+    }
+  }
+  (methods, current)
+}
+
+/// Splits a chain of static member accesses like `Object.prototype.foo`
+/// into the innermost object (`Object`) and the property names in source
+/// order (`["prototype", "foo"]`), looking through parentheses and
+/// optional chaining.
+pub(crate) fn member_path(expr: &Expr) -> (&Expr, Vec<String>) {
+  let mut path = vec![];
+  let mut current = expr;
+  loop {
+    match current {
+      Expr::Paren(ParenExpr { expr, .. })
+      | Expr::OptChain(OptChainExpr { expr, .. }) => current = expr,
+      // `obj[key]` with a variable key is not a static access.
+      Expr::Member(MemberExpr {
+        computed: true,
+        prop,
+        ..
+      }) if matches!(&**prop, Expr::Ident(_)) => break,
+      Expr::Member(member_expr) => {
+        match (member_expr.get_key(), &member_expr.obj) {
+          (Some(prop), ExprOrSuper::Expr(obj)) => {
+            path.push(prop);
+            current = obj;
+          }
+          _ => break,
+        }
+      }
+      _ => break,
+    }
+  }
+  path.reverse();
+  (current, path)
+}
+
+/// Returns the value of an expression which is definitely a constant
+/// string: a string literal, a template literal without substitutions, or
+/// a concatenation of those.
+pub(crate) fn constant_string(expr: &Expr) -> Option<String> {
+  match expr {
+    Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+    Expr::Tpl(tpl) if tpl.exprs.is_empty() => tpl.quasis.first().map(|quasi| {
+      quasi
+        .cooked
+        .as_ref()
+        .unwrap_or(&quasi.raw)
+        .value
+        .to_string()
+    }),
+    Expr::Paren(ParenExpr { expr, .. }) => constant_string(expr),
+    Expr::Bin(bin) if bin.op == BinaryOp::Add => {
+      let left = constant_string(&bin.left)?;
+      Some(left + &constant_string(&bin.right)?)
+    }
+    _ => None,
+  }
+}
+
+/// Returns the executor function of a `new Promise(executor)` expression,
+/// with any parentheses around it removed.
+pub(crate) fn promise_executor(new_expr: &NewExpr) -> Option<&Expr> {
+  match &*new_expr.callee {
+    Expr::Ident(ident) if ident.sym == *"Promise" => {}
+    _ => return None,
+  }
+  let mut executor = &*new_expr.args.as_ref()?.first()?.expr;
+  while let Expr::Paren(ParenExpr { expr, .. }) = executor {
+    executor = expr;
+  }
+  match executor {
+    Expr::Fn(_) | Expr::Arrow(_) => Some(executor),
+    _ => None,
+  }
+}
+
+/// Checks if a function body contains a `return` statement with a value,
+/// not counting the ones of nested functions.
+pub(crate) fn returns_value(body: &BlockStmt) -> bool {
+  let mut finder = ReturnValueFinder { found: false };
+  body.visit_with(body, &mut finder);
+  finder.found
+}
+
+struct ReturnValueFinder {
+  found: bool,
+}
+
+impl Visit for ReturnValueFinder {
+  noop_visit_type!();
+
+  fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt, _: &dyn Node) {
+    if return_stmt.arg.is_some() {
+      self.found = true;
+    }
+  }
+
+  fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
+  fn visit_arrow_expr(&mut self, _: &ArrowExpr, _: &dyn Node) {}
+  fn visit_getter_prop(&mut self, _: &GetterProp, _: &dyn Node) {}
+  fn visit_setter_prop(&mut self, _: &SetterProp, _: &dyn Node) {}
+}
+
 /// Find [Id]s in the lhs of an assigmnet expression.
 pub(crate) fn find_lhs_ids<I>(n: &PatOrExpr) -> Vec<I>
 where
@@ -380,3 +796,481 @@ where
     PatOrExpr::Pat(p) => find_ids(p),
   }
 }
+
+/// A property of an object literal whose value is replaced by a later spread
+/// of an inline object literal, like `a` in `{ a: 1, ...{ a: 2 } }`.
+pub(crate) struct OverwrittenProp {
+  pub key: String,
+  pub span: Span,
+  /// The spread which replaces the value.
+  pub overwritten_by: Span,
+  /// Whether the property comes after another spread, which it overrides.
+  pub after_spread: bool,
+}
+
+/// Finds the properties of an object literal which are overwritten by a
+/// later spread of an inline object literal with the same static key.
+///
+/// Getters and setters are never reported. Spreads of anything but object
+/// literals, including conditional ones, might define any key and their
+/// getters might read the earlier properties, so they reset the analysis.
+pub(crate) fn overwritten_props(obj_lit: &ObjectLit) -> Vec<OverwrittenProp> {
+  let mut overwritten = vec![];
+  // Last write of each key since the last reset, with the span of the
+  // property if it's reported when overwritten.
+  let mut writes: HashMap<String, Option<(Span, bool)>> = HashMap::new();
+  let mut seen_spread = false;
+
+  for prop in &obj_lit.props {
+    match prop {
+      PropOrSpread::Spread(spread) => {
+        seen_spread = true;
+        let spread_keys = match inline_object_lit(&spread.expr) {
+          Some(inner) => static_keys(inner),
+          None => {
+            writes.clear();
+            continue;
+          }
+        };
+        for key in spread_keys {
+          if let Some(Some((span, after_spread))) = writes.remove(&key) {
+            overwritten.push(OverwrittenProp {
+              key: key.clone(),
+              span,
+              overwritten_by: spread.span(),
+              after_spread,
+            });
+          }
+          writes.insert(key, None);
+        }
+      }
+      PropOrSpread::Prop(prop) => {
+        let key = match prop_key(prop) {
+          Some(key) => key,
+          None => continue,
+        };
+        let reported = match &**prop {
+          Prop::Getter(_) | Prop::Setter(_) => None,
+          _ => Some((prop.span(), seen_spread)),
+        };
+        writes.insert(key, reported);
+      }
+    }
+  }
+
+  overwritten.sort_by_key(|prop| prop.span.lo());
+  overwritten
+}
+
+fn inline_object_lit(expr: &Expr) -> Option<&ObjectLit> {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => inline_object_lit(expr),
+    Expr::Object(obj_lit) => Some(obj_lit),
+    _ => None,
+  }
+}
+
+fn prop_key(prop: &Prop) -> Option<String> {
+  match prop {
+    Prop::Shorthand(ident) => Some(ident.sym.to_string()),
+    // `__proto__: value` sets the prototype instead of a property.
+    Prop::KeyValue(key_value) => match &key_value.key {
+      PropName::Ident(ident) if ident.sym == *"__proto__" => None,
+      PropName::Str(str) if str.value == *"__proto__" => None,
+      key => key.get_key(),
+    },
+    _ => prop.get_key(),
+  }
+}
+
+/// Keys which are certainly defined by an object literal, including the
+/// ones of object literals spread into it.
+fn static_keys(obj_lit: &ObjectLit) -> Vec<String> {
+  let mut keys = vec![];
+  for prop in &obj_lit.props {
+    match prop {
+      PropOrSpread::Prop(prop) => keys.extend(prop_key(prop)),
+      PropOrSpread::Spread(spread) => {
+        if let Some(inner) = inline_object_lit(&spread.expr) {
+          keys.extend(static_keys(inner));
+        }
+      }
+    }
+  }
+  keys
+}
+
+/// Collects the functions which are known to be async from their
+/// declaration, either `async function f() {}` or `const f = async () => {}`.
+pub(crate) fn async_functions(module: &Module) -> HashSet<Id> {
+  let mut collector = AsyncFunctionCollector {
+    ids: HashSet::new(),
+  };
+  module.visit_with(module, &mut collector);
+  collector.ids
+}
+
+struct AsyncFunctionCollector {
+  ids: HashSet<Id>,
+}
+
+impl Visit for AsyncFunctionCollector {
+  noop_visit_type!();
+
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, parent: &dyn Node) {
+    if fn_decl.function.is_async {
+      self.ids.insert(fn_decl.ident.to_id());
+    }
+    swc_ecmascript::visit::visit_fn_decl(self, fn_decl, parent);
+  }
+
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, parent: &dyn Node) {
+    if var_decl.kind == VarDeclKind::Const {
+      for decl in &var_decl.decls {
+        let is_async = match &decl.init {
+          Some(init) => match unwrap_paren(init) {
+            Expr::Arrow(arrow) => arrow.is_async,
+            Expr::Fn(fn_expr) => fn_expr.function.is_async,
+            _ => false,
+          },
+          None => false,
+        };
+        if let (Pat::Ident(ident), true) = (&decl.name, is_async) {
+          self.ids.insert(ident.to_id());
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_var_decl(self, var_decl, parent);
+  }
+}
+
+pub(crate) fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => unwrap_paren(expr),
+    _ => expr,
+  }
+}
+
+/// Returns the span of a function expression up to its body, e.g.
+/// `async (x) =>` or `function (x)`.
+pub(crate) fn function_head_span(
+  context: &Context,
+  expr: &Expr,
+) -> Option<Span> {
+  let (span, body_start) = match unwrap_paren(expr) {
+    Expr::Arrow(arrow) => (arrow.span, arrow.body.span().lo),
+    Expr::Fn(fn_expr) => match &fn_expr.function.body {
+      Some(body) => (fn_expr.function.span, body.span.lo),
+      None => return None,
+    },
+    _ => return None,
+  };
+  let head = Span::new(span.lo, body_start, span.ctxt);
+  let trimmed_len = context.span_text(head).trim_end().len();
+  Some(head.with_hi(head.lo + BytePos(trimmed_len as u32)))
+}
+
+/// Checks if the expression definitely evaluates to a Number, and not a
+/// BigInt, going by its syntax: numeric literals, unary `+`, calls of the
+/// global `Number()`, `parseInt()`, `parseFloat()` and `Math` methods, and
+/// arithmetic on those.
+pub(crate) fn is_number(context: &Context, expr: &Expr) -> bool {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => is_number(context, expr),
+    Expr::Lit(Lit::Num(_)) => true,
+    // Unary `+` throws for BigInts.
+    Expr::Unary(unary) => match unary.op {
+      UnaryOp::Plus => true,
+      UnaryOp::Minus | UnaryOp::Tilde => is_number(context, &unary.arg),
+      _ => false,
+    },
+    Expr::Bin(bin) => match bin.op {
+      // `>>>` throws for BigInts.
+      BinaryOp::ZeroFillRShift => true,
+      BinaryOp::Add
+      | BinaryOp::Sub
+      | BinaryOp::Mul
+      | BinaryOp::Div
+      | BinaryOp::Mod
+      | BinaryOp::Exp
+      | BinaryOp::BitAnd
+      | BinaryOp::BitOr
+      | BinaryOp::BitXor
+      | BinaryOp::LShift
+      | BinaryOp::RShift => {
+        is_number(context, &bin.left) && is_number(context, &bin.right)
+      }
+      _ => false,
+    },
+    Expr::Call(CallExpr {
+      callee: ExprOrSuper::Expr(callee),
+      ..
+    }) => match &**callee {
+      Expr::Ident(ident) => ["Number", "parseInt", "parseFloat"]
+        .iter()
+        .any(|name| context.is_global(ident, name)),
+      _ => match member_path(callee) {
+        (Expr::Ident(ident), path) => {
+          path.len() == 1 && context.is_global(ident, "Math")
+        }
+        _ => false,
+      },
+    },
+    _ => false,
+  }
+}
+
+/// Returns how tightly an expression binds as an operand, on the scale of
+/// `BinaryOp::precedence()`, where higher binds tighter.
+pub(crate) fn expr_precedence(expr: &Expr) -> u8 {
+  match expr {
+    Expr::Seq(_)
+    | Expr::Arrow(_)
+    | Expr::Assign(_)
+    | Expr::Yield(_)
+    | Expr::Cond(_) => 0,
+    Expr::Bin(bin) => bin.op.precedence(),
+    Expr::TsAs(_) => BinaryOp::Lt.precedence(),
+    Expr::Unary(_) | Expr::Await(_) | Expr::TsTypeAssertion(_) => 12,
+    Expr::Update(update) if update.prefix => 12,
+    Expr::Update(_) => 13,
+    _ => 20,
+  }
+}
+
+/// Returns the source text of an operand of `op`, parenthesized if it
+/// would otherwise not parse as that operand.
+pub(crate) fn binary_operand_text(
+  context: &Context,
+  op: BinaryOp,
+  operand: &Expr,
+  is_left: bool,
+) -> String {
+  let text = context.span_text(operand.span());
+  let op_precedence = op.precedence();
+  let precedence = expr_precedence(operand);
+  let needs_parens = precedence < op_precedence
+    // `**` is the only right-associative binary operator.
+    || (precedence == op_precedence && (op == BinaryOp::Exp) == is_left)
+    // A unary expression can't be the base of `**`, eg. `-2 ** 3`.
+    || (op == BinaryOp::Exp
+      && is_left
+      && matches!(
+        operand,
+        Expr::Unary(_) | Expr::Await(_) | Expr::TsTypeAssertion(_)
+      ))
+    // `??` can't be mixed with `||` and `&&` without parentheses.
+    || match (op, operand) {
+      (BinaryOp::NullishCoalescing, Expr::Bin(bin)) => {
+        matches!(bin.op, BinaryOp::LogicalOr | BinaryOp::LogicalAnd)
+      }
+      (BinaryOp::LogicalOr, Expr::Bin(bin))
+      | (BinaryOp::LogicalAnd, Expr::Bin(bin)) => {
+        bin.op == BinaryOp::NullishCoalescing
+      }
+      _ => false,
+    };
+  if needs_parens {
+    format!("({})", text)
+  } else {
+    text
+  }
+}
+
+/// Prints the binary expression `left op right` for simple operands which
+/// are taken from the source, adding parentheses where needed.
+pub(crate) fn binary_expr_text(
+  context: &Context,
+  op: BinaryOp,
+  left: &Expr,
+  right: &Expr,
+) -> String {
+  format!(
+    "{} {} {}",
+    binary_operand_text(context, op, left, true),
+    op,
+    binary_operand_text(context, op, right, false)
+  )
+}
+
+/// Returns the offset of `op` in the text between two operands, which
+/// may contain comments.
+fn find_operator(between: &str, op: &str) -> usize {
+  let mut offset = 0;
+  while offset < between.len() {
+    let rest = &between[offset..];
+    if rest.starts_with("/*") {
+      offset += rest.find("*/").map_or(rest.len(), |end| end + 2);
+    } else if rest.starts_with("//") {
+      offset += rest.find('\n').unwrap_or(rest.len());
+    } else if rest.starts_with(op) {
+      return offset;
+    } else {
+      offset += rest.chars().next().map_or(1, char::len_utf8);
+    }
+  }
+  0
+}
+
+/// Returns the span of the operator `op` between the operands `left` and
+/// `right`, since the spans of binary and assignment expressions cover the
+/// whole expression.
+pub(crate) fn operator_span(
+  context: &Context,
+  left: Span,
+  right: Span,
+  op: &str,
+) -> Span {
+  let between =
+    context.span_text(Span::new(left.hi(), right.lo(), SyntaxContext::empty()));
+  let lo = left.hi() + BytePos(find_operator(&between, op) as u32);
+  Span::new(lo, lo + BytePos(op.len() as u32), SyntaxContext::empty())
+}
+
+/// Returns the span of `keyword` at the start of the node spanning `span`,
+/// eg. the `case` of a switch case.
+pub(crate) fn keyword_span(span: Span, keyword: &str) -> Span {
+  span.with_hi(span.lo + BytePos(keyword.len() as u32))
+}
+
+/// Returns the method name and receiver of `receiver.method(...)`.
+pub(crate) fn method_call(call_expr: &CallExpr) -> Option<(&str, &Expr)> {
+  let callee = match &call_expr.callee {
+    ExprOrSuper::Expr(callee) => unwrap_paren(callee),
+    ExprOrSuper::Super(_) => return None,
+  };
+  match callee {
+    Expr::Member(member) if !member.computed => {
+      match (&member.obj, &*member.prop) {
+        (ExprOrSuper::Expr(obj), Expr::Ident(prop)) => Some((&prop.sym, obj)),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+/// Array methods which return another array, so calling them on an array
+/// results in an array too.
+static ARRAY_RETURNING_METHODS: &[&str] = &[
+  "concat", "filter", "flat", "flatMap", "map", "slice", "reverse", "sort",
+];
+
+/// Methods of arrays, maps and sets which modify the receiver in place.
+pub(crate) static MUTATING_METHODS: &[&str] = &[
+  "add",
+  "clear",
+  "copyWithin",
+  "delete",
+  "fill",
+  "pop",
+  "push",
+  "reverse",
+  "set",
+  "shift",
+  "sort",
+  "splice",
+  "unshift",
+];
+
+/// The `const` bindings of a module which are initialized to arrays.
+pub(crate) struct ArrayBindings {
+  arrays: HashSet<Id>,
+}
+
+/// Collects the `const` bindings of `module` which are initialized to
+/// arrays: array literals, `new Array()`, `Array.from()`, `Array.of()` and
+/// array methods returning another array.
+pub(crate) fn array_bindings(module: &Module) -> ArrayBindings {
+  let mut bindings = ArrayBindings {
+    arrays: HashSet::new(),
+  };
+  module.visit_with(module, &mut bindings);
+  bindings
+}
+
+impl ArrayBindings {
+  /// Checks if the expression evaluates to an array, going by its syntax.
+  pub(crate) fn is_array(&self, expr: &Expr) -> bool {
+    match unwrap_paren(expr) {
+      Expr::Array(_) => true,
+      Expr::Ident(ident) => self.arrays.contains(&ident.to_id()),
+      Expr::New(NewExpr { callee, .. }) => {
+        matches!(&**callee, Expr::Ident(ident) if ident.sym == *"Array")
+      }
+      Expr::Call(call_expr) => match method_call(call_expr) {
+        Some(("from", Expr::Ident(ident)))
+        | Some(("of", Expr::Ident(ident))) => ident.sym == *"Array",
+        Some((method, receiver)) => {
+          ARRAY_RETURNING_METHODS.contains(&method) && self.is_array(receiver)
+        }
+        None => false,
+      },
+      _ => false,
+    }
+  }
+}
+
+impl Visit for ArrayBindings {
+  noop_visit_type!();
+
+  fn visit_var_decl(&mut self, var_decl: &VarDecl, parent: &dyn Node) {
+    if var_decl.kind == VarDeclKind::Const {
+      for decl in &var_decl.decls {
+        let (ident, init) = match (&decl.name, &decl.init) {
+          (Pat::Ident(ident), Some(init)) => (ident, init),
+          _ => continue,
+        };
+        if self.is_array(init) {
+          self.arrays.insert(ident.to_id());
+        }
+      }
+    }
+    swc_ecmascript::visit::visit_var_decl(self, var_decl, parent);
+  }
+}
+
+/// Counts the statements, expressions, patterns, class members, object
+/// properties and types of `module`, which is how large it is for the rules
+/// walking it.
+pub(crate) fn count_nodes(module: &Module) -> usize {
+  let mut counter = NodeCounter { count: 0 };
+  module.visit_with(module, &mut counter);
+  counter.count
+}
+
+struct NodeCounter {
+  count: usize,
+}
+
+impl Visit for NodeCounter {
+  fn visit_stmt(&mut self, stmt: &Stmt, parent: &dyn Node) {
+    self.count += 1;
+    swc_ecmascript::visit::visit_stmt(self, stmt, parent);
+  }
+
+  fn visit_expr(&mut self, expr: &Expr, parent: &dyn Node) {
+    self.count += 1;
+    swc_ecmascript::visit::visit_expr(self, expr, parent);
+  }
+
+  fn visit_pat(&mut self, pat: &Pat, parent: &dyn Node) {
+    self.count += 1;
+    swc_ecmascript::visit::visit_pat(self, pat, parent);
+  }
+
+  fn visit_class_member(&mut self, member: &ClassMember, parent: &dyn Node) {
+    self.count += 1;
+    swc_ecmascript::visit::visit_class_member(self, member, parent);
+  }
+
+  fn visit_prop(&mut self, prop: &Prop, parent: &dyn Node) {
+    self.count += 1;
+    swc_ecmascript::visit::visit_prop(self, prop, parent);
+  }
+
+  fn visit_ts_type(&mut self, ts_type: &TsType, parent: &dyn Node) {
+    self.count += 1;
+    swc_ecmascript::visit::visit_ts_type(self, ts_type, parent);
+  }
+}
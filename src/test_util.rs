@@ -3,19 +3,103 @@
 use crate::diagnostic::LintDiagnostic;
 use crate::linter::LinterBuilder;
 use crate::rules::LintRule;
+use crate::session::LintSession;
 use crate::swc_util;
+use crate::swc_util::SwcDiagnosticBuffer;
+use std::cell::Cell;
+use std::sync::Arc;
+use swc_ecmascript::parser::Syntax;
 
-fn lint(rule: Box<dyn LintRule>, source: &str) -> Vec<LintDiagnostic> {
+pub fn lint(rule: Box<dyn LintRule>, source: &str) -> Vec<LintDiagnostic> {
+  lint_with_syntax(rule, source, swc_util::get_default_ts_config())
+}
+
+fn lint_with_syntax(
+  rule: Box<dyn LintRule>,
+  source: &str,
+  syntax: Syntax,
+) -> Vec<LintDiagnostic> {
   let mut linter = LinterBuilder::default()
     .lint_unused_ignore_directives(false)
     .lint_unknown_rules(false)
-    .syntax(swc_util::get_default_ts_config())
+    .syntax(syntax)
     .rules(vec![rule])
     .build();
 
-  linter
-    .lint("deno_lint_test.tsx".to_string(), source.to_string())
-    .expect("Failed to lint")
+  rule_diagnostics(
+    linter.lint("deno_lint_test.tsx".to_string(), source.to_string()),
+  )
+}
+
+/// Lints `source` as `file_name`, for rules which look at the name.
+pub fn lint_as(
+  rule: Box<dyn LintRule>,
+  file_name: &str,
+  source: &str,
+) -> Vec<LintDiagnostic> {
+  let mut linter = LinterBuilder::default()
+    .lint_unused_ignore_directives(false)
+    .lint_unknown_rules(false)
+    .rules(vec![rule])
+    .build();
+
+  rule_diagnostics(linter.lint(file_name.to_string(), source.to_string()))
+}
+
+/// Lints `source` as `file_name`, which belongs to the project summarized
+/// by `session`.
+pub fn lint_with_session(
+  rule: Box<dyn LintRule>,
+  session: LintSession,
+  file_name: &str,
+  source: &str,
+) -> Vec<LintDiagnostic> {
+  let mut linter = LinterBuilder::default()
+    .lint_unused_ignore_directives(false)
+    .lint_unknown_rules(false)
+    .session(Arc::new(session))
+    .rules(vec![rule])
+    .build();
+
+  rule_diagnostics(linter.lint(file_name.to_string(), source.to_string()))
+}
+
+thread_local! {
+  static SYNTAX_ERRORS_ALLOWED: Cell<bool> = Cell::default();
+}
+
+/// Runs the assertions of `f` on source with syntax errors the parser
+/// recovers from, which some test cases have on purpose, eg. `with`
+/// statements in a module. The syntax errors are dropped from the linted
+/// diagnostics, while other test cases fail on any syntax error.
+pub fn with_syntax_errors(f: impl FnOnce()) {
+  SYNTAX_ERRORS_ALLOWED.with(|allowed| allowed.set(true));
+  f();
+  SYNTAX_ERRORS_ALLOWED.with(|allowed| allowed.set(false));
+}
+
+fn rule_diagnostics(
+  result: Result<Vec<LintDiagnostic>, SwcDiagnosticBuffer>,
+) -> Vec<LintDiagnostic> {
+  let diagnostics = result.expect("Failed to lint");
+  if SYNTAX_ERRORS_ALLOWED.with(|allowed| allowed.get()) {
+    return diagnostics
+      .into_iter()
+      .filter(|diagnostic| diagnostic.code != "syntax-error")
+      .collect();
+  }
+  if let Some(diagnostic) = diagnostics
+    .iter()
+    .find(|diagnostic| diagnostic.code == "syntax-error")
+  {
+    panic!(
+      "unexpected syntax error at {}:{}: {}",
+      diagnostic.range.start.line,
+      diagnostic.range.start.col,
+      diagnostic.message
+    );
+  }
+  diagnostics
 }
 
 pub fn assert_diagnostic(
@@ -44,8 +128,27 @@ pub fn assert_diagnostic(
 }
 
 pub fn assert_lint_ok<T: LintRule + 'static>(source: &str) {
-  let rule = T::new();
-  let diagnostics = lint(rule, source);
+  assert_lint_ok_with(T::new(), source);
+}
+
+/// Like `assert_lint_ok`, but runs an already constructed rule, which
+/// allows testing rules configured with non-default options.
+pub fn assert_lint_ok_with(rule: Box<dyn LintRule>, source: &str) {
+  assert_no_diagnostics(&lint(rule, source), source);
+}
+
+/// Lints the source with JSX enabled and returns all diagnostics, for
+/// tests that need to look at more than the positions.
+pub fn lint_tsx(rule: Box<dyn LintRule>, source: &str) -> Vec<LintDiagnostic> {
+  lint_with_syntax(rule, source, swc_util::get_default_tsx_config())
+}
+
+/// Like `assert_lint_ok_with`, but parses the source with JSX enabled.
+pub fn assert_lint_ok_tsx(rule: Box<dyn LintRule>, source: &str) {
+  assert_no_diagnostics(&lint_tsx(rule, source), source);
+}
+
+fn assert_no_diagnostics(diagnostics: &[LintDiagnostic], source: &str) {
   if !diagnostics.is_empty() {
     panic!(
       "Unexpected diagnostics found:\n{:#?}\n\nsource:\n{}\n",
@@ -96,10 +199,64 @@ pub fn assert_lint_err_n<T: LintRule + 'static>(
 pub fn assert_lint_err_on_line_n<T: LintRule + 'static>(
   source: &str,
   expected: Vec<(usize, usize)>,
+) {
+  assert_lint_err_on_line_n_with(T::new(), source, expected);
+}
+
+/// Like `assert_lint_err_on_line_n`, but runs an already constructed rule,
+/// which allows testing rules configured with non-default options.
+pub fn assert_lint_err_on_line_n_with(
+  rule: Box<dyn LintRule>,
+  source: &str,
+  expected: Vec<(usize, usize)>,
+) {
+  let rule_code = rule.code();
+  let diagnostics = lint(rule, source);
+  assert_diagnostics(&diagnostics, rule_code, source, expected);
+}
+
+/// Like `assert_lint_err_on_line_n_with`, but parses the source with JSX
+/// enabled.
+pub fn assert_lint_err_on_line_n_tsx(
+  rule: Box<dyn LintRule>,
+  source: &str,
+  expected: Vec<(usize, usize)>,
+) {
+  let rule_code = rule.code();
+  let diagnostics = lint_tsx(rule, source);
+  assert_diagnostics(&diagnostics, rule_code, source, expected);
+}
+
+/// Like `assert_lint_err_on_line_n`, but also checks that each message
+/// contains the expected text.
+pub fn assert_lint_err_with_messages<T: LintRule + 'static>(
+  source: &str,
+  expected: Vec<(usize, usize, &str)>,
 ) {
   let rule = T::new();
   let rule_code = rule.code();
   let diagnostics = lint(rule, source);
+  let positions = expected.iter().map(|(line, col, _)| (*line, *col));
+  assert_diagnostics(&diagnostics, rule_code, source, positions.collect());
+  for (diagnostic, (_, _, message)) in diagnostics.iter().zip(expected) {
+    assert!(
+      diagnostic.message.contains(message),
+      "expect message of diagnostic at {}:{} to contain {:?}, but got {:?}\n\nsource:\n{}\n",
+      diagnostic.range.start.line,
+      diagnostic.range.start.col,
+      message,
+      diagnostic.message,
+      source
+    );
+  }
+}
+
+fn assert_diagnostics(
+  diagnostics: &[LintDiagnostic],
+  rule_code: &str,
+  source: &str,
+  expected: Vec<(usize, usize)>,
+) {
   assert_eq!(
     diagnostics.len(),
     expected.len(),
@@ -0,0 +1,274 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+
+//! A rule defined outside of the crate, which only uses the public API.
+
+use deno_lint::linter::{Context, DuplicateRuleCode, LinterBuilder};
+use deno_lint::rules::{get_recommended_rules, LintRule};
+use deno_lint::scopes::{BindingKind, ScopeManager};
+use deno_lint::swc_ecmascript::ast::{DebuggerStmt, Module};
+use deno_lint::swc_ecmascript::visit::{Node, Visit};
+use std::sync::{Arc, Mutex};
+
+struct NoDebuggerStatement;
+
+impl LintRule for NoDebuggerStatement {
+  fn new() -> Box<Self> {
+    Box::new(NoDebuggerStatement)
+  }
+
+  fn code(&self) -> &'static str {
+    "acme-no-debugger"
+  }
+
+  fn lint_module(&self, context: Arc<Context>, module: &Module) {
+    let mut visitor = NoDebuggerStatementVisitor { context };
+    visitor.visit_module(module, module);
+  }
+}
+
+struct NoDebuggerStatementVisitor {
+  context: Arc<Context>,
+}
+
+impl Visit for NoDebuggerStatementVisitor {
+  fn visit_debugger_stmt(&mut self, stmt: &DebuggerStmt, _parent: &dyn Node) {
+    self.context.add_diagnostic_with_hint(
+      stmt.span,
+      "acme-no-debugger",
+      "`debugger` statement is not allowed",
+      "Remove the `debugger` statement",
+    );
+  }
+}
+
+/// A custom rule clashing with the built-in `no-debugger`.
+struct NoDebuggerClash;
+
+impl LintRule for NoDebuggerClash {
+  fn new() -> Box<Self> {
+    Box::new(NoDebuggerClash)
+  }
+
+  fn code(&self) -> &'static str {
+    "no-debugger"
+  }
+
+  fn lint_module(&self, _context: Arc<Context>, _module: &Module) {}
+}
+
+/// Reports `const` bindings which are never read, using the scope analysis
+/// the linter shares with all rules.
+struct NoUnreadConst;
+
+impl LintRule for NoUnreadConst {
+  fn new() -> Box<Self> {
+    Box::new(NoUnreadConst)
+  }
+
+  fn code(&self) -> &'static str {
+    "acme-no-unread-const"
+  }
+
+  fn lint_module(&self, context: Arc<Context>, _module: &Module) {
+    let scope_manager = &context.scope_manager;
+    let mut unread = scope_manager
+      .bindings()
+      .filter(|(_, binding)| binding.kind() == BindingKind::Const)
+      .filter(|(id, _)| {
+        !scope_manager
+          .references_of(id)
+          .any(|reference| reference.kind().is_read())
+      })
+      .map(|(_, binding)| binding.span())
+      .collect::<Vec<_>>();
+    unread.sort_by_key(|span| span.lo);
+    for span in unread {
+      context.add_diagnostic(span, "acme-no-unread-const", "Unread const");
+    }
+  }
+}
+
+/// Reports `TODO` markers anywhere in the source text, which rules can't
+/// find in the AST as they might be part of comments.
+struct NoTodo;
+
+impl LintRule for NoTodo {
+  fn new() -> Box<Self> {
+    Box::new(NoTodo)
+  }
+
+  fn code(&self) -> &'static str {
+    "acme-no-todo"
+  }
+
+  fn lint_module(&self, context: Arc<Context>, _module: &Module) {
+    for (start, todo) in context.source().match_indices("TODO") {
+      let span = context.byte_range_to_span(start..start + todo.len());
+      context.add_diagnostic(span, "acme-no-todo", "Unresolved `TODO`");
+    }
+  }
+}
+
+/// The scope analyses rules were given. Keeping them alive rules out a new
+/// analysis reusing the memory of a dropped one.
+type Analyses = Arc<Mutex<Vec<(&'static str, Arc<ScopeManager>)>>>;
+
+/// Records the scope analysis it is given in `analyses`.
+struct ScopeRecorder {
+  code: &'static str,
+  analyses: Analyses,
+}
+
+impl LintRule for ScopeRecorder {
+  fn new() -> Box<Self> {
+    Box::new(ScopeRecorder {
+      code: "acme-scope-recorder",
+      analyses: Analyses::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    self.code
+  }
+
+  fn lint_module(&self, context: Arc<Context>, _module: &Module) {
+    let scope_manager = context.scope_manager.clone();
+    self
+      .analyses
+      .lock()
+      .unwrap()
+      .push((self.code, scope_manager));
+  }
+}
+
+#[test]
+fn custom_rule() {
+  let source = "function f() {\n  debugger;\n}\n";
+  let mut linter = LinterBuilder::default()
+    .add_rule(NoDebuggerStatement::new())
+    .build();
+  let diagnostics = linter
+    .lint("custom.ts".to_string(), source.to_string())
+    .expect("Failed to lint");
+
+  assert_eq!(diagnostics.len(), 1);
+  let diagnostic = &diagnostics[0];
+  assert_eq!(diagnostic.code, "acme-no-debugger");
+  assert_eq!(diagnostic.message, "`debugger` statement is not allowed");
+  assert_eq!(
+    diagnostic.hint.as_deref(),
+    Some("Remove the `debugger` statement")
+  );
+  assert_eq!(
+    (diagnostic.range.start.line, diagnostic.range.start.col),
+    (2, 2)
+  );
+  assert_eq!(diagnostic.line_text, "  debugger;");
+}
+
+#[test]
+fn custom_rule_with_built_in_rules() {
+  let source = "// deno-lint-ignore acme-no-debugger\ndebugger;\ndebugger;\n";
+  let mut linter = LinterBuilder::default()
+    .rules(get_recommended_rules())
+    .add_rule(NoDebuggerStatement::new())
+    .build();
+  let diagnostics = linter
+    .lint("custom.ts".to_string(), source.to_string())
+    .expect("Failed to lint");
+
+  let codes = diagnostics
+    .iter()
+    .map(|d| (d.code.as_str(), d.range.start.line))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    codes,
+    vec![
+      ("no-debugger", 2),
+      ("no-debugger", 3),
+      ("acme-no-debugger", 3)
+    ]
+  );
+}
+
+#[test]
+fn duplicate_rule_code() {
+  let err = LinterBuilder::default()
+    .rules(get_recommended_rules())
+    .add_rule(NoDebuggerClash::new())
+    .try_build()
+    .err()
+    .unwrap();
+  assert_eq!(
+    err,
+    DuplicateRuleCode {
+      code: "no-debugger".to_string()
+    }
+  );
+  assert_eq!(
+    err.to_string(),
+    "Rule code \"no-debugger\" is used by more than one rule"
+  );
+}
+
+#[test]
+fn custom_rule_with_scope_analysis() {
+  let source =
+    "const a = 1;\nconst b = a;\nfunction f() {\n  const c = 2;\n}\n";
+  let mut linter = LinterBuilder::default()
+    .add_rule(NoUnreadConst::new())
+    .build();
+  let diagnostics = linter
+    .lint("custom.ts".to_string(), source.to_string())
+    .expect("Failed to lint");
+
+  let positions = diagnostics
+    .iter()
+    .map(|d| (d.range.start.line, d.range.start.col))
+    .collect::<Vec<_>>();
+  assert_eq!(positions, vec![(2, 6), (4, 8)]);
+}
+
+#[test]
+fn custom_rule_with_source_text() {
+  let source = "// TODO: remove\nconst a = \"ü\"; // TODO\n";
+  let mut linter = LinterBuilder::default().add_rule(NoTodo::new()).build();
+  let diagnostics = linter
+    .lint("custom.ts".to_string(), source.to_string())
+    .expect("Failed to lint");
+
+  let ranges = diagnostics
+    .iter()
+    .map(|d| (d.range.start.line, d.range.start.col, d.range.end.col))
+    .collect::<Vec<_>>();
+  assert_eq!(ranges, vec![(1, 3, 7), (2, 18, 22)]);
+}
+
+#[test]
+fn scope_analysis_is_shared_by_rules() {
+  let analyses = Analyses::default();
+  let recorder = |code| {
+    Box::new(ScopeRecorder {
+      code,
+      analyses: analyses.clone(),
+    })
+  };
+  let mut linter = LinterBuilder::default()
+    .add_rule(recorder("acme-first"))
+    .add_rule(recorder("acme-second"))
+    .add_rule(recorder("acme-third"))
+    .build();
+  linter
+    .lint("custom.ts".to_string(), "const a = 1;".to_string())
+    .expect("Failed to lint");
+
+  let analyses = analyses.lock().unwrap();
+  assert_eq!(
+    analyses.iter().map(|(code, _)| *code).collect::<Vec<_>>(),
+    vec!["acme-first", "acme-second", "acme-third"]
+  );
+  // The module is analyzed once, before the rules run
+  assert!(analyses
+    .iter()
+    .all(|(_, scope_manager)| Arc::ptr_eq(scope_manager, &analyses[0].1)));
+}
@@ -0,0 +1,35 @@
+// Copyright 2020 the Deno authors. All rights reserved. MIT license.
+#![cfg(feature = "json")]
+
+use deno_lint::linter::LinterBuilder;
+use deno_lint::rules::sort_imports::SortImports;
+use deno_lint::rules::LintRule;
+use serde_json::Value;
+
+/// The results of linting several files serialize to the JSON consumed by
+/// CI tooling, whose field names mustn't change.
+#[test]
+fn lint_files_serializes_to_json() {
+  let linter = LinterBuilder::default()
+    .rules(vec![SortImports::new()])
+    .build();
+  let results = linter.lint_files(vec![
+    (
+      "sort_imports.ts".to_string(),
+      include_str!("testdata/sort_imports.ts").to_string(),
+    ),
+    ("syntax_error.ts".to_string(), "const a = (;\n".to_string()),
+  ]);
+
+  let json: Value =
+    serde_json::from_str(&serde_json::to_string_pretty(&results).unwrap())
+      .unwrap();
+  let snapshot: Value =
+    serde_json::from_str(include_str!("testdata/sort_imports.json")).unwrap();
+  assert_eq!(
+    json,
+    snapshot,
+    "the JSON output changed:\n{}",
+    serde_json::to_string_pretty(&json).unwrap()
+  );
+}
@@ -0,0 +1,5 @@
+import { b, a } from "./b.ts";
+import * as c from "./c.ts";
+import d from "./d.ts";
+
+export { a, b, c, d };